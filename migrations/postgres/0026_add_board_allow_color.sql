@@ -0,0 +1 @@
+ALTER TABLE boards ADD COLUMN allow_color BOOLEAN NOT NULL DEFAULT TRUE;
@@ -0,0 +1,2 @@
+-- Add show_colors setting to users table
+ALTER TABLE users ADD COLUMN show_colors BOOLEAN NOT NULL DEFAULT TRUE;
@@ -0,0 +1,9 @@
+-- Full-text search index for posts.
+-- The terms column is built from space-separated search terms (words and
+-- CJK bigrams) generated by the application, using the 'simple' config.
+CREATE TABLE post_search (
+    post_id     BIGINT PRIMARY KEY REFERENCES posts(id) ON DELETE CASCADE,
+    terms       TSVECTOR NOT NULL
+);
+
+CREATE INDEX idx_post_search_terms ON post_search USING GIN (terms);
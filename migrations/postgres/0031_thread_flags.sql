@@ -0,0 +1,6 @@
+-- Moderation flags for threads
+-- Pinned threads are listed first; locked threads accept no new posts.
+ALTER TABLE threads ADD COLUMN is_pinned BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE threads ADD COLUMN is_locked BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX idx_threads_board_pinned ON threads(board_id, is_pinned, updated_at);
@@ -0,0 +1,19 @@
+-- Echomail scan pointers, one row per echo tag
+CREATE TABLE echo_areas (
+    tag                  TEXT PRIMARY KEY,
+    board_id             BIGINT NOT NULL REFERENCES boards(id) ON DELETE CASCADE,
+    last_scanned_post_id BIGINT NOT NULL DEFAULT 0,
+    updated_at           TEXT NOT NULL DEFAULT TO_CHAR(NOW(), 'YYYY-MM-DD HH24:MI:SS')
+);
+
+-- MSGIDs of tossed and scanned messages, for dupe detection and REPLY linking.
+-- post_id is NULL when the post has since been deleted.
+CREATE TABLE echomail_messages (
+    id          BIGSERIAL PRIMARY KEY,
+    msgid       TEXT NOT NULL UNIQUE,
+    board_id    BIGINT NOT NULL REFERENCES boards(id) ON DELETE CASCADE,
+    post_id     BIGINT REFERENCES posts(id) ON DELETE SET NULL,
+    created_at  TEXT NOT NULL DEFAULT TO_CHAR(NOW(), 'YYYY-MM-DD HH24:MI:SS')
+);
+
+CREATE INDEX idx_echomail_messages_post_id ON echomail_messages(post_id);
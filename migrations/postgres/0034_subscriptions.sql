@@ -0,0 +1,40 @@
+-- Board and thread subscriptions
+-- Exactly one of board_id and thread_id is set.
+-- digest = TRUE bundles notifications into a daily system mail.
+CREATE TABLE subscriptions (
+    id          BIGSERIAL PRIMARY KEY,
+    user_id     BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    board_id    BIGINT REFERENCES boards(id) ON DELETE CASCADE,
+    thread_id   BIGINT REFERENCES threads(id) ON DELETE CASCADE,
+    digest      BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at  TEXT NOT NULL DEFAULT TO_CHAR(NOW(), 'YYYY-MM-DD HH24:MI:SS'),
+    CHECK ((board_id IS NULL) <> (thread_id IS NULL))
+);
+
+CREATE UNIQUE INDEX idx_subscriptions_user_board
+    ON subscriptions(user_id, board_id) WHERE board_id IS NOT NULL;
+CREATE UNIQUE INDEX idx_subscriptions_user_thread
+    ON subscriptions(user_id, thread_id) WHERE thread_id IS NOT NULL;
+CREATE INDEX idx_subscriptions_board_id ON subscriptions(board_id);
+CREATE INDEX idx_subscriptions_thread_id ON subscriptions(thread_id);
+
+-- Queued notifications, one per user and post
+-- delivered_at is set once shown (or mailed, for digest notifications).
+CREATE TABLE notifications (
+    id            BIGSERIAL PRIMARY KEY,
+    user_id       BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    post_id       BIGINT NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    digest        BOOLEAN NOT NULL DEFAULT FALSE,
+    delivered_at  TEXT,
+    created_at    TEXT NOT NULL DEFAULT TO_CHAR(NOW(), 'YYYY-MM-DD HH24:MI:SS'),
+    UNIQUE (user_id, post_id)
+);
+
+CREATE INDEX idx_notifications_user_id ON notifications(user_id);
+
+-- Progress of the notification job (single row)
+CREATE TABLE notification_state (
+    id              INTEGER PRIMARY KEY CHECK (id = 1),
+    last_post_id    BIGINT NOT NULL DEFAULT 0,
+    last_digest_on  TEXT
+);
@@ -0,0 +1,47 @@
+-- Voting booth
+-- A poll may be attached to a thread. opens_at/closes_at are UTC and
+-- optional (NULL means open from creation / never closes).
+CREATE TABLE polls (
+    id              BIGSERIAL PRIMARY KEY,
+    question        TEXT NOT NULL,
+    description     TEXT,
+    allow_multiple  BOOLEAN NOT NULL DEFAULT FALSE,   -- several options per ballot
+    allow_write_in  BOOLEAN NOT NULL DEFAULT FALSE,   -- voters may add an answer
+    min_vote_role   TEXT NOT NULL DEFAULT 'member',
+    opens_at        TEXT,
+    closes_at       TEXT,
+    thread_id       BIGINT REFERENCES threads(id) ON DELETE SET NULL,
+    created_by      BIGINT REFERENCES users(id) ON DELETE SET NULL,
+    created_at      TEXT NOT NULL DEFAULT TO_CHAR(NOW(), 'YYYY-MM-DD HH24:MI:SS')
+);
+
+CREATE INDEX idx_polls_thread_id ON polls(thread_id);
+
+-- Poll options; write-in answers are added as options by voters
+CREATE TABLE poll_options (
+    id          BIGSERIAL PRIMARY KEY,
+    poll_id     BIGINT NOT NULL REFERENCES polls(id) ON DELETE CASCADE,
+    text        TEXT NOT NULL,
+    sort_order  INTEGER NOT NULL DEFAULT 0,
+    is_write_in BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX idx_poll_options_poll_id ON poll_options(poll_id);
+
+-- One ballot per user and poll
+CREATE TABLE poll_ballots (
+    poll_id     BIGINT NOT NULL REFERENCES polls(id) ON DELETE CASCADE,
+    user_id     BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at  TEXT NOT NULL DEFAULT TO_CHAR(NOW(), 'YYYY-MM-DD HH24:MI:SS'),
+    PRIMARY KEY (poll_id, user_id)
+);
+
+-- Options chosen on a ballot
+CREATE TABLE poll_votes (
+    poll_id     BIGINT NOT NULL REFERENCES polls(id) ON DELETE CASCADE,
+    option_id   BIGINT NOT NULL REFERENCES poll_options(id) ON DELETE CASCADE,
+    user_id     BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    PRIMARY KEY (option_id, user_id)
+);
+
+CREATE INDEX idx_poll_votes_poll_id ON poll_votes(poll_id);
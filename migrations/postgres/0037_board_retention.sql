@@ -0,0 +1,6 @@
+-- Per-board retention rules
+-- NULL means no limit. archive_pruned = TRUE writes pruned posts to an
+-- archive file before they are deleted.
+ALTER TABLE boards ADD COLUMN max_posts BIGINT;
+ALTER TABLE boards ADD COLUMN max_age_days BIGINT;
+ALTER TABLE boards ADD COLUMN archive_pruned BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,15 @@
+-- Per-user newscan configuration
+-- Boards without a row are scanned, after the configured ones.
+CREATE TABLE newscan_boards (
+    user_id     BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    board_id    BIGINT NOT NULL REFERENCES boards(id) ON DELETE CASCADE,
+    included    BOOLEAN NOT NULL DEFAULT TRUE,
+    sort_order  INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (user_id, board_id)
+);
+
+-- Whether the newscan runs automatically after login
+CREATE TABLE newscan_settings (
+    user_id        BIGINT PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    scan_on_login  BOOLEAN NOT NULL DEFAULT FALSE
+);
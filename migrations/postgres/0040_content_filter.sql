@@ -0,0 +1,26 @@
+-- Content filter rules and their hit log
+-- rule_type: word (comma/newline separated list), regex, links (maximum
+-- number of links) or new_account (minimum account age in hours).
+-- action: reject, hold or mask.
+CREATE TABLE filter_rules (
+    id          BIGSERIAL PRIMARY KEY,
+    rule_type   TEXT NOT NULL,
+    pattern     TEXT NOT NULL,
+    action      TEXT NOT NULL,
+    is_active   BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by  BIGINT REFERENCES users(id) ON DELETE SET NULL,
+    created_at  TEXT NOT NULL DEFAULT TO_CHAR(NOW(), 'YYYY-MM-DD HH24:MI:SS')
+);
+
+-- target: post, mail or chat. detail is the matched text.
+CREATE TABLE filter_hits (
+    id          BIGSERIAL PRIMARY KEY,
+    rule_id     BIGINT REFERENCES filter_rules(id) ON DELETE SET NULL,
+    user_id     BIGINT REFERENCES users(id) ON DELETE SET NULL,
+    target      TEXT NOT NULL,
+    action      TEXT NOT NULL,
+    detail      TEXT NOT NULL,
+    created_at  TEXT NOT NULL DEFAULT TO_CHAR(NOW(), 'YYYY-MM-DD HH24:MI:SS')
+);
+
+CREATE INDEX idx_filter_hits_created_at ON filter_hits(created_at);
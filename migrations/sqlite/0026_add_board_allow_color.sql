@@ -0,0 +1 @@
+ALTER TABLE boards ADD COLUMN allow_color INTEGER NOT NULL DEFAULT 1;
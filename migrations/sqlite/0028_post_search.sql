@@ -0,0 +1,10 @@
+-- Full-text search index for posts.
+-- The terms column holds space-separated search terms (words and CJK
+-- bigrams) generated by the application, so the unicode61 tokenizer only
+-- has to split on spaces. rowid is the post ID.
+CREATE VIRTUAL TABLE post_search USING fts5(terms, tokenize = 'unicode61');
+
+-- Remove index entries with their posts (including cascaded deletes)
+CREATE TRIGGER post_search_delete AFTER DELETE ON posts BEGIN
+    DELETE FROM post_search WHERE rowid = old.id;
+END;
@@ -0,0 +1,4 @@
+-- Add reply parent to posts (NULL for top-level posts)
+ALTER TABLE posts ADD COLUMN parent_id INTEGER REFERENCES posts(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_posts_parent_id ON posts(parent_id);
@@ -0,0 +1,16 @@
+-- Track post edits
+ALTER TABLE posts ADD COLUMN edited_at TEXT;
+ALTER TABLE posts ADD COLUMN edited_by INTEGER REFERENCES users(id) ON DELETE SET NULL;
+
+-- Earlier versions of edited posts
+-- Each row is a version that was replaced by an edit: who wrote it and when.
+CREATE TABLE post_revisions (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    post_id     INTEGER NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    title       TEXT,
+    body        TEXT NOT NULL,
+    editor_id   INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at  TEXT NOT NULL
+);
+
+CREATE INDEX idx_post_revisions_post_id ON post_revisions(post_id);
@@ -0,0 +1,6 @@
+-- Moderation flags for threads
+-- Pinned threads are listed first; locked threads accept no new posts.
+ALTER TABLE threads ADD COLUMN is_pinned INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE threads ADD COLUMN is_locked INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX idx_threads_board_pinned ON threads(board_id, is_pinned, updated_at);
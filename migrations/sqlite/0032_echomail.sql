@@ -0,0 +1,19 @@
+-- Echomail scan pointers, one row per echo tag
+CREATE TABLE echo_areas (
+    tag                  TEXT PRIMARY KEY,
+    board_id             INTEGER NOT NULL REFERENCES boards(id) ON DELETE CASCADE,
+    last_scanned_post_id INTEGER NOT NULL DEFAULT 0,
+    updated_at           TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+-- MSGIDs of tossed and scanned messages, for dupe detection and REPLY linking.
+-- post_id is NULL when the post has since been deleted.
+CREATE TABLE echomail_messages (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    msgid       TEXT NOT NULL UNIQUE,
+    board_id    INTEGER NOT NULL REFERENCES boards(id) ON DELETE CASCADE,
+    post_id     INTEGER REFERENCES posts(id) ON DELETE SET NULL,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_echomail_messages_post_id ON echomail_messages(post_id);
@@ -0,0 +1,18 @@
+-- Moderated boards hold posts by members for approval by a SubOp
+ALTER TABLE boards ADD COLUMN is_moderated INTEGER NOT NULL DEFAULT 0;
+
+-- Posts waiting for approval
+-- thread_id is set for replies; title is the thread title for new threads
+-- and the post title on flat boards.
+CREATE TABLE pending_posts (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    board_id    INTEGER NOT NULL REFERENCES boards(id) ON DELETE CASCADE,
+    thread_id   INTEGER REFERENCES threads(id) ON DELETE CASCADE,
+    parent_id   INTEGER REFERENCES posts(id) ON DELETE SET NULL,
+    author_id   INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    title       TEXT,
+    body        TEXT NOT NULL,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_pending_posts_board_id ON pending_posts(board_id);
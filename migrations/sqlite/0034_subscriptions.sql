@@ -0,0 +1,40 @@
+-- Board and thread subscriptions
+-- Exactly one of board_id and thread_id is set.
+-- digest = 1 bundles notifications into a daily system mail.
+CREATE TABLE subscriptions (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id     INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    board_id    INTEGER REFERENCES boards(id) ON DELETE CASCADE,
+    thread_id   INTEGER REFERENCES threads(id) ON DELETE CASCADE,
+    digest      INTEGER NOT NULL DEFAULT 0,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now')),
+    CHECK ((board_id IS NULL) <> (thread_id IS NULL))
+);
+
+CREATE UNIQUE INDEX idx_subscriptions_user_board
+    ON subscriptions(user_id, board_id) WHERE board_id IS NOT NULL;
+CREATE UNIQUE INDEX idx_subscriptions_user_thread
+    ON subscriptions(user_id, thread_id) WHERE thread_id IS NOT NULL;
+CREATE INDEX idx_subscriptions_board_id ON subscriptions(board_id);
+CREATE INDEX idx_subscriptions_thread_id ON subscriptions(thread_id);
+
+-- Queued notifications, one per user and post
+-- delivered_at is set once shown (or mailed, for digest notifications).
+CREATE TABLE notifications (
+    id            INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id       INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    post_id       INTEGER NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    digest        INTEGER NOT NULL DEFAULT 0,
+    delivered_at  TEXT,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE (user_id, post_id)
+);
+
+CREATE INDEX idx_notifications_user_id ON notifications(user_id);
+
+-- Progress of the notification job (single row)
+CREATE TABLE notification_state (
+    id              INTEGER PRIMARY KEY CHECK (id = 1),
+    last_post_id    INTEGER NOT NULL DEFAULT 0,
+    last_digest_on  TEXT
+);
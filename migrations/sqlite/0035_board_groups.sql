@@ -0,0 +1,18 @@
+-- Board groups (conferences)
+-- Boards in a group are shown under it; readers need both the group's
+-- and the board's read role.
+CREATE TABLE board_groups (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    name            TEXT NOT NULL UNIQUE,
+    description     TEXT,
+    min_read_role   TEXT NOT NULL DEFAULT 'guest',   -- minimum role to read
+    sort_order      INTEGER NOT NULL DEFAULT 0,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_board_groups_sort_order ON board_groups(sort_order);
+
+-- Boards without a group are listed at the top level
+ALTER TABLE boards ADD COLUMN group_id INTEGER REFERENCES board_groups(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_boards_group_id ON boards(group_id);
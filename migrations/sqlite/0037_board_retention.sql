@@ -0,0 +1,6 @@
+-- Per-board retention rules
+-- NULL means no limit. archive_pruned = 1 writes pruned posts to an
+-- archive file before they are deleted.
+ALTER TABLE boards ADD COLUMN max_posts INTEGER;
+ALTER TABLE boards ADD COLUMN max_age_days INTEGER;
+ALTER TABLE boards ADD COLUMN archive_pruned INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,19 @@
+-- Thread and post bookmarks
+-- Exactly one of thread_id and post_id is set. They have no foreign keys so
+-- that a bookmark outlives its target and is shown as deleted.
+-- title is the thread or post title when the bookmark was added.
+CREATE TABLE bookmarks (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id     INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    thread_id   INTEGER,
+    post_id     INTEGER,
+    title       TEXT NOT NULL,
+    note        TEXT,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now')),
+    CHECK ((thread_id IS NULL) <> (post_id IS NULL))
+);
+
+CREATE UNIQUE INDEX idx_bookmarks_user_thread
+    ON bookmarks(user_id, thread_id) WHERE thread_id IS NOT NULL;
+CREATE UNIQUE INDEX idx_bookmarks_user_post
+    ON bookmarks(user_id, post_id) WHERE post_id IS NOT NULL;
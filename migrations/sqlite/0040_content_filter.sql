@@ -0,0 +1,26 @@
+-- Content filter rules and their hit log
+-- rule_type: word (comma/newline separated list), regex, links (maximum
+-- number of links) or new_account (minimum account age in hours).
+-- action: reject, hold or mask.
+CREATE TABLE filter_rules (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    rule_type   TEXT NOT NULL,
+    pattern     TEXT NOT NULL,
+    action      TEXT NOT NULL,
+    is_active   INTEGER NOT NULL DEFAULT 1,
+    created_by  INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+-- target: post, mail or chat. detail is the matched text.
+CREATE TABLE filter_hits (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    rule_id     INTEGER REFERENCES filter_rules(id) ON DELETE SET NULL,
+    user_id     INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    target      TEXT NOT NULL,
+    action      TEXT NOT NULL,
+    detail      TEXT NOT NULL,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_filter_hits_created_at ON filter_hits(created_at);
@@ -0,0 +1,15 @@
+-- User-side twit filters (kill files)
+-- Exactly one of target_user_id (hide an author) and keyword (hide content
+-- whose title or body contains the keyword) is set.
+CREATE TABLE twit_filters (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id         INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    target_user_id  INTEGER REFERENCES users(id) ON DELETE CASCADE,
+    keyword         TEXT,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now')),
+    CHECK ((target_user_id IS NULL) <> (keyword IS NULL))
+);
+
+CREATE UNIQUE INDEX idx_twit_filters_user_target
+    ON twit_filters(user_id, target_user_id) WHERE target_user_id IS NOT NULL;
+CREATE INDEX idx_twit_filters_user ON twit_filters(user_id);
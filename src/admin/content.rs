@@ -4,8 +4,15 @@
 //! - Delete posts (SubOp and above)
 //! - Delete files (SubOp and above)
 //! - Soft delete posts (replace body with deletion message)
+//! - Move, merge and split threads (SubOp and above)
+//! - Convert posts between flat and thread boards (SubOp and above)
+//!
+//! Deleting a post also removes its revision history.
 
-use crate::board::{Post, PostRepository, PostUpdate, ThreadRepository};
+use crate::board::{
+    validate_title, Board, BoardRepository, BoardType, Post, PostRepository, PostUpdate,
+    ReorganizeRepository, RevisionRepository, SearchRepository, Thread, ThreadRepository,
+};
 use crate::db::{DbPool, User};
 use crate::file::{FileMetadata, FileRepository, FileStorage};
 
@@ -86,6 +93,10 @@ impl<'a> ContentAdminService<'a> {
             PostDeletionMode::Soft => {
                 let update = PostUpdate::new().body(DELETED_POST_MESSAGE);
                 repo.update(post_id, &update).await?;
+                // Earlier versions would still show the deleted content
+                RevisionRepository::new(self.pool)
+                    .delete_by_post(post_id)
+                    .await?;
                 Ok(true)
             }
             PostDeletionMode::Hard => {
@@ -113,6 +124,196 @@ impl<'a> ContentAdminService<'a> {
         Ok(deleted)
     }
 
+    /// Move a thread to another thread board.
+    ///
+    /// Requires SubOp or higher permission.
+    pub async fn move_thread(
+        &self,
+        thread_id: i64,
+        board_id: i64,
+        admin: &User,
+    ) -> Result<Thread, AdminError> {
+        require_admin(Some(admin))?;
+
+        let thread = self.get_thread(thread_id).await?;
+        self.get_board(board_id, BoardType::Thread).await?;
+        if thread.board_id == board_id {
+            return Err(AdminError::InvalidOperation(
+                "スレッドは既にこの掲示板にあります".to_string(),
+            ));
+        }
+
+        ReorganizeRepository::new(self.pool)
+            .move_thread(thread_id, board_id)
+            .await?;
+        self.get_thread(thread_id).await
+    }
+
+    /// Merge a thread into another, which keeps its title.
+    ///
+    /// The posts of both threads are shown together in time order.
+    /// Requires SubOp or higher permission.
+    pub async fn merge_threads(
+        &self,
+        source_id: i64,
+        target_id: i64,
+        admin: &User,
+    ) -> Result<Thread, AdminError> {
+        require_admin(Some(admin))?;
+
+        if source_id == target_id {
+            return Err(AdminError::InvalidOperation(
+                "同じスレッドには統合できません".to_string(),
+            ));
+        }
+        self.get_thread(source_id).await?;
+        self.get_thread(target_id).await?;
+
+        ReorganizeRepository::new(self.pool)
+            .merge_threads(source_id, target_id)
+            .await?;
+        // The merged posts are now searchable by the target's title
+        SearchRepository::new(self.pool)
+            .index_thread(target_id)
+            .await?;
+        self.get_thread(target_id).await
+    }
+
+    /// Split a thread into a new thread starting at a post.
+    ///
+    /// The post and all later posts move to the new thread.
+    /// Requires SubOp or higher permission.
+    pub async fn split_thread(
+        &self,
+        post_id: i64,
+        title: &str,
+        admin: &User,
+    ) -> Result<Thread, AdminError> {
+        require_admin(Some(admin))?;
+
+        validate_title(title)?;
+        let post = self.get_post(post_id, admin).await?;
+        let thread_id = post.thread_id.ok_or_else(|| {
+            AdminError::InvalidOperation("フラット掲示板の投稿は分割できません".to_string())
+        })?;
+        // Posts are listed newest first
+        let first = PostRepository::new(self.pool)
+            .list_by_thread(thread_id)
+            .await?
+            .pop();
+        if first.is_some_and(|first| first.id == post.id) {
+            return Err(AdminError::InvalidOperation(
+                "最初の投稿では分割できません".to_string(),
+            ));
+        }
+
+        let new_id = ReorganizeRepository::new(self.pool)
+            .split_thread(&post, title.trim())
+            .await?;
+        SearchRepository::new(self.pool)
+            .index_thread(new_id)
+            .await?;
+        self.get_thread(new_id).await
+    }
+
+    /// Convert a thread into flat posts of a flat board.
+    ///
+    /// The thread is removed. Returns the number of converted posts.
+    /// Requires SubOp or higher permission.
+    pub async fn convert_thread_to_flat(
+        &self,
+        thread_id: i64,
+        board_id: i64,
+        admin: &User,
+    ) -> Result<u64, AdminError> {
+        require_admin(Some(admin))?;
+
+        self.get_thread(thread_id).await?;
+        self.get_board(board_id, BoardType::Flat).await?;
+
+        let post_ids: Vec<i64> = PostRepository::new(self.pool)
+            .list_by_thread(thread_id)
+            .await?
+            .iter()
+            .map(|p| p.id)
+            .collect();
+        let converted = ReorganizeRepository::new(self.pool)
+            .thread_to_flat(thread_id, board_id)
+            .await?;
+
+        let post_repo = PostRepository::new(self.pool);
+        let search = SearchRepository::new(self.pool);
+        for id in post_ids {
+            if let Some(post) = post_repo.get_by_id(id).await? {
+                search.index_post(&post).await?;
+            }
+        }
+        Ok(converted)
+    }
+
+    /// Convert a flat post into a new thread of a thread board.
+    ///
+    /// The post's title becomes the thread title unless `title` is given.
+    /// Requires SubOp or higher permission.
+    pub async fn convert_post_to_thread(
+        &self,
+        post_id: i64,
+        board_id: i64,
+        title: Option<&str>,
+        admin: &User,
+    ) -> Result<Thread, AdminError> {
+        require_admin(Some(admin))?;
+
+        let post = self.get_post(post_id, admin).await?;
+        if post.thread_id.is_some() {
+            return Err(AdminError::InvalidOperation(
+                "フラット掲示板の投稿ではありません".to_string(),
+            ));
+        }
+        self.get_board(board_id, BoardType::Thread).await?;
+        let title = title
+            .or(post.title.as_deref())
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        validate_title(&title)?;
+
+        let thread_id = ReorganizeRepository::new(self.pool)
+            .post_to_thread(&post, board_id, &title)
+            .await?;
+        SearchRepository::new(self.pool)
+            .index_thread(thread_id)
+            .await?;
+        self.get_thread(thread_id).await
+    }
+
+    /// Get a thread by ID.
+    async fn get_thread(&self, thread_id: i64) -> Result<Thread, AdminError> {
+        ThreadRepository::new(self.pool)
+            .get_by_id(thread_id)
+            .await?
+            .ok_or_else(|| AdminError::NotFound("スレッド".to_string()))
+    }
+
+    /// Get a board that posts can be moved to, checking its type.
+    async fn get_board(&self, board_id: i64, board_type: BoardType) -> Result<Board, AdminError> {
+        let board = BoardRepository::new(self.pool)
+            .get_by_id(board_id)
+            .await?
+            .ok_or_else(|| AdminError::NotFound("掲示板".to_string()))?;
+        if board.board_type != board_type {
+            let expected = match board_type {
+                BoardType::Thread => "スレッド形式",
+                BoardType::Flat => "フラット形式",
+            };
+            return Err(AdminError::InvalidOperation(format!(
+                "{}の掲示板を選んでください",
+                expected
+            )));
+        }
+        Ok(board)
+    }
+
     /// List posts by author.
     ///
     /// Requires SubOp or higher permission.
@@ -206,7 +407,7 @@ impl<'a> ContentAdminService<'a> {
 #[cfg(all(test, feature = "sqlite"))]
 mod tests {
     use super::*;
-    use crate::board::{BoardRepository, BoardType, NewBoard, NewThread, NewThreadPost};
+    use crate::board::{NewBoard, NewFlatPost, NewThread, NewThreadPost, UnreadRepository};
     use crate::db::{Database, NewUser, Role, UserRepository};
     use crate::file::{FolderRepository, NewFile, NewFolder};
     use crate::server::CharacterEncoding;
@@ -240,6 +441,7 @@ mod tests {
             encoding: CharacterEncoding::default(),
             language: "en".to_string(),
             auto_paging: true,
+            show_colors: true,
             created_at: "2024-01-01".to_string(),
             last_login: None,
             is_active: true,
@@ -338,6 +540,41 @@ mod tests {
         assert_eq!(post.body, DELETED_POST_MESSAGE);
     }
 
+    #[tokio::test]
+    async fn test_delete_post_removes_revisions() {
+        let db = setup_db().await;
+        let pool = db.pool();
+        let user = create_test_user(pool, "author", Role::Member).await;
+        let board_id = create_test_board(pool).await;
+        let thread_id = create_test_thread(pool, board_id, user.id).await;
+        let post_id = create_test_post(pool, board_id, thread_id, user.id).await;
+
+        let revisions = RevisionRepository::new(pool);
+        let post = PostRepository::new(pool)
+            .get_by_id(post_id)
+            .await
+            .unwrap()
+            .unwrap();
+        revisions.create(&post).await.unwrap();
+        revisions.create(&post).await.unwrap();
+
+        let service = ContentAdminService::new(pool);
+        let subop = create_admin_user(100, Role::SubOp);
+
+        service
+            .delete_post(post_id, PostDeletionMode::Soft, &subop)
+            .await
+            .unwrap();
+        assert_eq!(revisions.count_by_post(post_id).await.unwrap(), 0);
+
+        revisions.create(&post).await.unwrap();
+        service
+            .delete_post(post_id, PostDeletionMode::Hard, &subop)
+            .await
+            .unwrap();
+        assert_eq!(revisions.count_by_post(post_id).await.unwrap(), 0);
+    }
+
     #[tokio::test]
     async fn test_delete_post_hard() {
         let db = setup_db().await;
@@ -603,4 +840,267 @@ mod tests {
         let files = service.list_files_in_folder(folder1.id, &subop).await.unwrap();
         assert_eq!(files.len(), 2);
     }
+
+    async fn create_named_board(pool: &SqlitePool, name: &str, board_type: BoardType) -> i64 {
+        BoardRepository::new(pool)
+            .create(&NewBoard::new(name).with_board_type(board_type))
+            .await
+            .unwrap()
+            .id
+    }
+
+    /// Create a thread post written at the given time.
+    async fn create_post_at(
+        pool: &SqlitePool,
+        board_id: i64,
+        thread_id: i64,
+        author_id: i64,
+        created_at: &str,
+    ) -> i64 {
+        let post_id = create_test_post(pool, board_id, thread_id, author_id).await;
+        sqlx::query("UPDATE posts SET created_at = ? WHERE id = ?")
+            .bind(created_at)
+            .bind(post_id)
+            .execute(pool)
+            .await
+            .unwrap();
+        ThreadRepository::new(pool)
+            .touch_and_increment(thread_id)
+            .await
+            .unwrap();
+        post_id
+    }
+
+    #[tokio::test]
+    async fn test_move_thread() {
+        let db = setup_db().await;
+        let pool = db.pool();
+        let user = create_test_user(pool, "author", Role::Member).await;
+        let from = create_named_board(pool, "from", BoardType::Thread).await;
+        let to = create_named_board(pool, "to", BoardType::Thread).await;
+        let flat = create_named_board(pool, "flat", BoardType::Flat).await;
+        let kept_thread = create_test_thread(pool, from, user.id).await;
+        let kept = create_test_post(pool, from, kept_thread, user.id).await;
+        let thread_id = create_test_thread(pool, from, user.id).await;
+        let moved = create_test_post(pool, from, thread_id, user.id).await;
+
+        // A reader who stopped at the moved post still has the rest read
+        let unread = UnreadRepository::new(pool);
+        unread.mark_as_read(user.id, from, moved).await.unwrap();
+
+        let service = ContentAdminService::new(pool);
+        let subop = create_admin_user(100, Role::SubOp);
+
+        let result = service.move_thread(thread_id, flat, &subop).await;
+        assert!(matches!(result, Err(AdminError::InvalidOperation(_))));
+        let result = service.move_thread(thread_id, from, &subop).await;
+        assert!(matches!(result, Err(AdminError::InvalidOperation(_))));
+
+        let thread = service.move_thread(thread_id, to, &subop).await.unwrap();
+        assert_eq!(thread.board_id, to);
+        let post = PostRepository::new(pool)
+            .get_by_id(moved)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(post.board_id, to);
+        assert_eq!(
+            unread.get_last_read_post_id(user.id, from).await.unwrap(),
+            kept
+        );
+        assert_eq!(unread.get_unread_count(user.id, from).await.unwrap(), 0);
+        assert_eq!(unread.get_unread_count(user.id, to).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_merge_threads_interleaves_posts() {
+        let db = setup_db().await;
+        let pool = db.pool();
+        let user = create_test_user(pool, "author", Role::Member).await;
+        let board_id = create_test_board(pool).await;
+        let target = create_test_thread(pool, board_id, user.id).await;
+        let source = create_test_thread(pool, board_id, user.id).await;
+        let t1 = create_post_at(pool, board_id, target, user.id, "2024-01-01 10:00:00").await;
+        let s1 = create_post_at(pool, board_id, source, user.id, "2024-01-01 11:00:00").await;
+        let t2 = create_post_at(pool, board_id, target, user.id, "2024-01-01 12:00:00").await;
+        let s2 = create_post_at(pool, board_id, source, user.id, "2024-01-01 13:00:00").await;
+
+        let service = ContentAdminService::new(pool);
+        let subop = create_admin_user(100, Role::SubOp);
+
+        let result = service.merge_threads(source, source, &subop).await;
+        assert!(matches!(result, Err(AdminError::InvalidOperation(_))));
+
+        let thread = service.merge_threads(source, target, &subop).await.unwrap();
+        assert_eq!(thread.post_count, 4);
+        assert_eq!(thread.updated_at, "2024-01-01 13:00:00");
+
+        let ids: Vec<i64> = PostRepository::new(pool)
+            .list_by_thread(target)
+            .await
+            .unwrap()
+            .iter()
+            .rev()
+            .map(|p| p.id)
+            .collect();
+        assert_eq!(ids, vec![t1, s1, t2, s2]);
+        assert!(ThreadRepository::new(pool)
+            .get_by_id(source)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_split_thread() {
+        let db = setup_db().await;
+        let pool = db.pool();
+        let user = create_test_user(pool, "author", Role::Member).await;
+        let other = create_test_user(pool, "other", Role::Member).await;
+        let board_id = create_test_board(pool).await;
+        let thread_id = create_test_thread(pool, board_id, user.id).await;
+        let first = create_post_at(pool, board_id, thread_id, user.id, "2024-01-01 10:00:00").await;
+        let second =
+            create_post_at(pool, board_id, thread_id, other.id, "2024-01-01 11:00:00").await;
+        let third = create_post_at(pool, board_id, thread_id, user.id, "2024-01-01 12:00:00").await;
+        sqlx::query("UPDATE posts SET parent_id = ? WHERE id = ?")
+            .bind(first)
+            .bind(third)
+            .execute(pool)
+            .await
+            .unwrap();
+
+        let service = ContentAdminService::new(pool);
+        let subop = create_admin_user(100, Role::SubOp);
+
+        let result = service.split_thread(first, "Off topic", &subop).await;
+        assert!(matches!(result, Err(AdminError::InvalidOperation(_))));
+        let result = service.split_thread(second, "  ", &subop).await;
+        assert!(result.is_err());
+
+        let new_thread = service
+            .split_thread(second, "Off topic", &subop)
+            .await
+            .unwrap();
+        assert_eq!(new_thread.title, "Off topic");
+        assert_eq!(new_thread.author_id, other.id);
+        assert_eq!(new_thread.board_id, board_id);
+        assert_eq!(new_thread.post_count, 2);
+        assert_eq!(new_thread.created_at, "2024-01-01 11:00:00");
+        assert_eq!(new_thread.updated_at, "2024-01-01 12:00:00");
+
+        let thread = ThreadRepository::new(pool)
+            .get_by_id(thread_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(thread.post_count, 1);
+        assert_eq!(thread.updated_at, "2024-01-01 10:00:00");
+
+        // The reply lost its parent, which stayed behind
+        let reply = PostRepository::new(pool)
+            .get_by_id(third)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(reply.thread_id, Some(new_thread.id));
+        assert_eq!(reply.parent_id, None);
+    }
+
+    #[tokio::test]
+    async fn test_convert_between_flat_and_thread_boards() {
+        let db = setup_db().await;
+        let pool = db.pool();
+        let user = create_test_user(pool, "author", Role::Member).await;
+        let thread_board = create_named_board(pool, "threads", BoardType::Thread).await;
+        let flat_board = create_named_board(pool, "flat", BoardType::Flat).await;
+        let thread_id = create_test_thread(pool, thread_board, user.id).await;
+        let first = create_post_at(
+            pool,
+            thread_board,
+            thread_id,
+            user.id,
+            "2024-01-01 10:00:00",
+        )
+        .await;
+        let reply = create_post_at(
+            pool,
+            thread_board,
+            thread_id,
+            user.id,
+            "2024-01-01 11:00:00",
+        )
+        .await;
+
+        let service = ContentAdminService::new(pool);
+        let subop = create_admin_user(100, Role::SubOp);
+
+        let result = service
+            .convert_thread_to_flat(thread_id, thread_board, &subop)
+            .await;
+        assert!(matches!(result, Err(AdminError::InvalidOperation(_))));
+
+        let converted = service
+            .convert_thread_to_flat(thread_id, flat_board, &subop)
+            .await
+            .unwrap();
+        assert_eq!(converted, 2);
+        let post_repo = PostRepository::new(pool);
+        let post = post_repo.get_by_id(first).await.unwrap().unwrap();
+        assert!(post.is_flat_post());
+        assert_eq!(post.board_id, flat_board);
+        assert_eq!(post.title.as_deref(), Some("Test Thread"));
+        let post = post_repo.get_by_id(reply).await.unwrap().unwrap();
+        assert_eq!(post.title.as_deref(), Some("Re: Test Thread"));
+        assert!(ThreadRepository::new(pool)
+            .get_by_id(thread_id)
+            .await
+            .unwrap()
+            .is_none());
+
+        // And back into a thread of its own
+        let thread = service
+            .convert_post_to_thread(first, thread_board, None, &subop)
+            .await
+            .unwrap();
+        assert_eq!(thread.title, "Test Thread");
+        assert_eq!(thread.post_count, 1);
+        assert_eq!(thread.created_at, "2024-01-01 10:00:00");
+        let post = post_repo.get_by_id(first).await.unwrap().unwrap();
+        assert_eq!(post.thread_id, Some(thread.id));
+        assert_eq!(post.title, None);
+
+        let result = service
+            .convert_post_to_thread(first, thread_board, None, &subop)
+            .await;
+        assert!(matches!(result, Err(AdminError::InvalidOperation(_))));
+
+        let flat = post_repo
+            .create_flat_post(&NewFlatPost::new(flat_board, user.id, "Untitled", "body"))
+            .await
+            .unwrap();
+        let thread = service
+            .convert_post_to_thread(flat.id, thread_board, Some("Renamed"), &subop)
+            .await
+            .unwrap();
+        assert_eq!(thread.title, "Renamed");
+    }
+
+    #[tokio::test]
+    async fn test_reorganize_as_member_fails() {
+        let db = setup_db().await;
+        let pool = db.pool();
+        let user = create_test_user(pool, "author", Role::Member).await;
+        let board_id = create_test_board(pool).await;
+        let thread_id = create_test_thread(pool, board_id, user.id).await;
+        let post_id = create_test_post(pool, board_id, thread_id, user.id).await;
+
+        let service = ContentAdminService::new(pool);
+        let member = create_admin_user(100, Role::Member);
+
+        let result = service.split_thread(post_id, "New", &member).await;
+        assert!(matches!(result, Err(AdminError::Permission(_))));
+        let result = service.merge_threads(thread_id, thread_id, &member).await;
+        assert!(matches!(result, Err(AdminError::Permission(_))));
+    }
 }
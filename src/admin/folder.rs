@@ -237,6 +237,7 @@ mod tests {
             encoding: CharacterEncoding::default(),
             language: "en".to_string(),
             auto_paging: true,
+            show_colors: true,
             created_at: "2024-01-01".to_string(),
             last_login: None,
             is_active: true,
@@ -4,7 +4,8 @@
 //! - Board management (create, update, delete)
 //! - Folder management (create, update, delete)
 //! - User management (list, update, change role, suspend/activate)
-//! - Post and file management (delete)
+//! - Post and file management (delete, move, merge, split, convert)
+//! - Moderation queue (approve, reject)
 //! - Session management (list, force disconnect)
 //!
 //! Access is controlled by role:
@@ -14,12 +15,14 @@
 mod board;
 mod content;
 mod folder;
+mod moderation;
 mod session;
 mod user;
 
 pub use board::{BoardAdminService, CreateBoardRequest};
 pub use content::{ContentAdminService, PostDeletionMode, DELETED_POST_MESSAGE};
 pub use folder::FolderAdminService;
+pub use moderation::{ModerationAdminService, REJECTION_MAIL_SUBJECT};
 pub use session::{format_duration, format_session_state, SessionAdminService, SessionStatistics};
 pub use user::{generate_password, UserAdminService, UserDetail, DEFAULT_PASSWORD_LENGTH};
 
@@ -95,6 +98,7 @@ pub enum AdminError {
 ///     last_login: None,
 ///     is_active: true,
 ///     auto_paging: false,
+///     show_colors: true,
 /// };
 ///
 /// assert!(require_admin(Some(&subop)).is_ok());
@@ -264,6 +268,7 @@ mod tests {
             encoding: CharacterEncoding::default(),
             language: "en".to_string(),
             auto_paging: true,
+            show_colors: true,
             created_at: "2024-01-01".to_string(),
             last_login: None,
             is_active: true,
@@ -527,6 +532,7 @@ mod tests {
             encoding: CharacterEncoding::default(),
             language: "en".to_string(),
             auto_paging: true,
+            show_colors: true,
             created_at: "2024-01-01".to_string(),
             last_login: None,
             is_active: true,
@@ -544,6 +550,7 @@ mod tests {
             encoding: CharacterEncoding::default(),
             language: "en".to_string(),
             auto_paging: true,
+            show_colors: true,
             created_at: "2024-01-01".to_string(),
             last_login: None,
             is_active: true,
@@ -599,6 +606,7 @@ mod tests {
             encoding: CharacterEncoding::default(),
             language: "en".to_string(),
             auto_paging: true,
+            show_colors: true,
             created_at: "2024-01-01".to_string(),
             last_login: None,
             is_active: true,
@@ -0,0 +1,219 @@
+//! Moderation queue for administrators.
+//!
+//! This module provides the approval workflow for moderated boards:
+//! - List posts waiting for approval (SubOp and above)
+//! - Approve a post, publishing it under its author (SubOp and above)
+//! - Reject a post with a reason mailed to the author (SubOp and above)
+
+use crate::board::{BoardRepository, BoardService, PendingPost, PendingPostRepository, Post};
+use crate::db::{Database, User};
+use crate::mail::SystemMailService;
+
+use super::{require_admin, AdminError};
+
+/// Subject of the mail sent to the author of a rejected post.
+pub const REJECTION_MAIL_SUBJECT: &str = "投稿が承認されませんでした";
+
+/// Admin service for the moderation queue.
+pub struct ModerationAdminService<'a> {
+    db: &'a Database,
+}
+
+impl<'a> ModerationAdminService<'a> {
+    /// Create a new ModerationAdminService.
+    pub fn new(db: &'a Database) -> Self {
+        Self { db }
+    }
+
+    /// List posts waiting for approval, oldest first.
+    ///
+    /// Requires SubOp or higher permission.
+    pub async fn list_pending(&self, admin: &User) -> Result<Vec<PendingPost>, AdminError> {
+        require_admin(Some(admin))?;
+
+        let posts = PendingPostRepository::new(self.db.pool()).list().await?;
+        Ok(posts)
+    }
+
+    /// Count posts waiting for approval.
+    ///
+    /// Requires SubOp or higher permission.
+    pub async fn count_pending(&self, admin: &User) -> Result<i64, AdminError> {
+        require_admin(Some(admin))?;
+
+        let count = PendingPostRepository::new(self.db.pool()).count().await?;
+        Ok(count)
+    }
+
+    /// Get a post waiting for approval.
+    ///
+    /// Requires SubOp or higher permission.
+    pub async fn get_pending(
+        &self,
+        pending_id: i64,
+        admin: &User,
+    ) -> Result<PendingPost, AdminError> {
+        require_admin(Some(admin))?;
+
+        PendingPostRepository::new(self.db.pool())
+            .get_by_id(pending_id)
+            .await?
+            .ok_or_else(|| AdminError::NotFound("承認待ちの投稿".to_string()))
+    }
+
+    /// Approve a post, publishing it.
+    ///
+    /// Requires SubOp or higher permission.
+    pub async fn approve(&self, pending_id: i64, admin: &User) -> Result<Post, AdminError> {
+        self.get_pending(pending_id, admin).await?;
+
+        let post = BoardService::new(self.db)
+            .approve_pending(pending_id, admin.role)
+            .await?;
+        Ok(post)
+    }
+
+    /// Reject a post and mail the reason to its author.
+    ///
+    /// Requires SubOp or higher permission. The mail includes the rejected
+    /// text so that the author can revise and resubmit it.
+    pub async fn reject(
+        &self,
+        pending_id: i64,
+        reason: &str,
+        admin: &User,
+    ) -> Result<PendingPost, AdminError> {
+        let pending = self.get_pending(pending_id, admin).await?;
+
+        let reason = reason.trim();
+        if reason.is_empty() {
+            return Err(AdminError::InvalidOperation(
+                "却下の理由を入力してください".to_string(),
+            ));
+        }
+
+        PendingPostRepository::new(self.db.pool())
+            .delete(pending_id)
+            .await?;
+
+        let board_name = BoardRepository::new(self.db.pool())
+            .get_by_id(pending.board_id)
+            .await?
+            .map(|b| b.name)
+            .unwrap_or_default();
+        let body = rejection_mail_body(&board_name, &pending, reason);
+        SystemMailService::new(self.db)
+            .send_notification(pending.author_id, REJECTION_MAIL_SUBJECT, &body)
+            .await?;
+
+        Ok(pending)
+    }
+}
+
+/// Build the body of the rejection mail.
+fn rejection_mail_body(board_name: &str, pending: &PendingPost, reason: &str) -> String {
+    let mut body = format!(
+        "掲示板「{}」への投稿は、モデレーターにより承認されませんでした。\n\n\
+         理由:\n{}\n\n\
+         --- 投稿内容 ---\n",
+        board_name, reason
+    );
+    if let Some(title) = &pending.title {
+        body.push_str(&format!("タイトル: {}\n\n", title));
+    }
+    body.push_str(&pending.body);
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::{NewBoard, Submission};
+    use crate::db::{NewUser, Role, UserRepository};
+    use crate::mail::MailRepository;
+
+    async fn create_user(db: &Database, username: &str, role: Role) -> User {
+        let mut new_user = NewUser::new(username, "hash", username);
+        new_user.role = role;
+        UserRepository::new(db.pool()).create(&new_user).await.unwrap()
+    }
+
+    async fn submit(db: &Database, board_id: i64, author: &User, title: &str) -> PendingPost {
+        let submission = BoardService::new(db)
+            .submit_thread(board_id, title, author.id, "本文です", author.role)
+            .await
+            .unwrap();
+        match submission {
+            Submission::Pending(pending) => pending,
+            Submission::Posted(_) => panic!("expected a queued post"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_approve() {
+        let db = Database::open_in_memory().await.unwrap();
+        let member = create_user(&db, "member", Role::Member).await;
+        let subop = create_user(&db, "subop", Role::SubOp).await;
+        let board = BoardRepository::new(db.pool())
+            .create(&NewBoard::new("moderated").with_moderated(true))
+            .await
+            .unwrap();
+        let pending = submit(&db, board.id, &member, "質問").await;
+
+        let service = ModerationAdminService::new(&db);
+        assert!(matches!(
+            service.list_pending(&member).await,
+            Err(AdminError::Permission(_))
+        ));
+        assert!(matches!(
+            service.approve(pending.id, &member).await,
+            Err(AdminError::Permission(_))
+        ));
+        assert_eq!(service.count_pending(&subop).await.unwrap(), 1);
+
+        let post = service.approve(pending.id, &subop).await.unwrap();
+        assert_eq!(post.author_id, member.id);
+        assert_eq!(post.body, "本文です");
+        assert!(service.list_pending(&subop).await.unwrap().is_empty());
+        assert!(matches!(
+            service.approve(pending.id, &subop).await,
+            Err(AdminError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_reject_mails_author() {
+        let db = Database::open_in_memory().await.unwrap();
+        let sysop = create_user(&db, "sysop", Role::SysOp).await;
+        let member = create_user(&db, "member", Role::Member).await;
+        let board = BoardRepository::new(db.pool())
+            .create(&NewBoard::new("moderated").with_moderated(true))
+            .await
+            .unwrap();
+        let pending = submit(&db, board.id, &member, "宣伝").await;
+
+        let service = ModerationAdminService::new(&db);
+        assert!(matches!(
+            service.reject(pending.id, "  ", &sysop).await,
+            Err(AdminError::InvalidOperation(_))
+        ));
+
+        let rejected = service
+            .reject(pending.id, "宣伝目的の投稿は禁止です", &sysop)
+            .await
+            .unwrap();
+        assert_eq!(rejected.id, pending.id);
+        assert_eq!(service.count_pending(&sysop).await.unwrap(), 0);
+
+        let mails = MailRepository::new(db.pool())
+            .list_inbox(member.id)
+            .await
+            .unwrap();
+        assert_eq!(mails.len(), 1);
+        assert_eq!(mails[0].subject, REJECTION_MAIL_SUBJECT);
+        assert!(mails[0].body.contains("moderated"));
+        assert!(mails[0].body.contains("宣伝目的の投稿は禁止です"));
+        assert!(mails[0].body.contains("タイトル: 宣伝"));
+        assert!(mails[0].body.contains("本文です"));
+    }
+}
@@ -121,7 +121,7 @@ impl<'a> UserAdminService<'a> {
         // Get users with pagination
         let users = sqlx::query_as::<_, User>(
             "SELECT id, username, password, nickname, email, role, profile, terminal,
-                    encoding, language, auto_paging, created_at, last_login, is_active
+                    encoding, language, auto_paging, show_colors, created_at, last_login, is_active
              FROM users
              ORDER BY created_at DESC
              LIMIT $1 OFFSET $2",
@@ -407,7 +407,7 @@ impl<'a> UserAdminService<'a> {
         // Get users with pagination
         let users = sqlx::query_as::<_, User>(
             "SELECT id, username, password, nickname, email, role, profile, terminal,
-                    encoding, language, auto_paging, created_at, last_login, is_active
+                    encoding, language, auto_paging, show_colors, created_at, last_login, is_active
              FROM users
              WHERE username LIKE $1 OR nickname LIKE $2
              ORDER BY username
@@ -452,6 +452,7 @@ mod tests {
             encoding: CharacterEncoding::default(),
             language: "en".to_string(),
             auto_paging: true,
+            show_colors: true,
             created_at: "2024-01-01".to_string(),
             last_login: None,
             is_active: true,
@@ -724,6 +725,7 @@ mod tests {
             encoding: CharacterEncoding::default(),
             language: "en".to_string(),
             auto_paging: true,
+            show_colors: true,
             created_at: "2024-01-01".to_string(),
             last_login: None,
             is_active: true,
@@ -792,6 +794,7 @@ mod tests {
             encoding: CharacterEncoding::default(),
             language: "en".to_string(),
             auto_paging: true,
+            show_colors: true,
             created_at: "2024-01-01".to_string(),
             last_login: None,
             is_active: true,
@@ -23,6 +23,8 @@ pub enum MenuAction {
     Profile,
     /// Go to member list.
     MemberList,
+    /// Go to voting booth.
+    Poll,
     /// Go to admin menu.
     Admin,
     /// Show help.
@@ -63,6 +65,7 @@ impl MenuAction {
             "N" | "9" => MenuAction::News,
             "P" | "6" if is_logged_in => MenuAction::Profile,
             "W" | "8" => MenuAction::MemberList,
+            "V" => MenuAction::Poll,
             "A" | "7" if is_admin => MenuAction::Admin,
             "H" | "?" => MenuAction::Help,
             "Q" if is_logged_in => MenuAction::Logout,
@@ -108,6 +111,7 @@ impl MenuAction {
             MenuAction::News => "N",
             MenuAction::Profile => "P",
             MenuAction::MemberList => "W",
+            MenuAction::Poll => "V",
             MenuAction::Admin => "A",
             MenuAction::Help => "H",
             MenuAction::Logout => "Q",
@@ -152,6 +156,8 @@ pub struct MenuItems {
     pub profile: bool,
     /// Whether member list is available.
     pub member_list: bool,
+    /// Whether the voting booth is available.
+    pub poll: bool,
     /// Whether admin menu is available.
     pub admin: bool,
     /// Whether help is available.
@@ -177,6 +183,7 @@ impl MenuItems {
             news: true,
             profile: true,
             member_list: true,
+            poll: true,
             admin: is_admin,
             help: true,
             logout: true,
@@ -196,6 +203,7 @@ impl MenuItems {
             news: true,
             profile: false,
             member_list: true,
+            poll: true,
             admin: false,
             help: true,
             logout: false,
@@ -273,6 +281,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_poll() {
+        assert_eq!(MenuAction::parse("V", true, false), MenuAction::Poll);
+        // The voting booth is also open to guests for viewing results
+        assert_eq!(MenuAction::parse("v", false, false), MenuAction::Poll);
+        assert_eq!(MenuAction::Poll.key(), "V");
+    }
+
     #[test]
     fn test_parse_admin() {
         assert_eq!(MenuAction::parse("A", true, true), MenuAction::Admin);
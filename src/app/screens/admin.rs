@@ -49,6 +49,7 @@ impl AdminScreen {
                 "3" => Self::edit_board(ctx, session).await?,
                 "4" => Self::delete_board(ctx, session).await?,
                 "5" => Self::manage_content(ctx, session).await?,
+                "19" => Self::moderation_queue(ctx, session).await?,
                 "6" => Self::show_user_list(ctx, session).await?,
                 "7" => Self::change_user_role(ctx, session).await?,
                 "8" => Self::suspend_user(ctx, session).await?,
@@ -63,6 +64,7 @@ impl AdminScreen {
                 "17" => Self::create_folder(ctx, session).await?,
                 "18" => Self::delete_folder(ctx, session).await?,
                 "20" => Self::show_system_status(ctx, session).await?,
+                "21" => Self::content_filter(ctx, session).await?,
                 _ => {}
             }
         }
@@ -316,6 +318,39 @@ impl AdminScreen {
                 &format!("  {}: {}", ctx.i18n.t("admin.board_paging"), paging_status),
             )
             .await?;
+            let color_status = if board.allow_color {
+                ctx.i18n.t("admin.board_color_on")
+            } else {
+                ctx.i18n.t("admin.board_color_off")
+            };
+            ctx.send_line(
+                session,
+                &format!("  {}: {}", ctx.i18n.t("admin.board_color"), color_status),
+            )
+            .await?;
+            let moderated_status = if board.is_moderated {
+                ctx.i18n.t("admin.board_moderated_on")
+            } else {
+                ctx.i18n.t("admin.board_moderated_off")
+            };
+            ctx.send_line(
+                session,
+                &format!(
+                    "  {}: {}",
+                    ctx.i18n.t("admin.board_moderated"),
+                    moderated_status
+                ),
+            )
+            .await?;
+            ctx.send_line(
+                session,
+                &format!(
+                    "  {}: {}",
+                    ctx.i18n.t("admin.board_retention"),
+                    Self::retention_to_string(&board, ctx)
+                ),
+            )
+            .await?;
 
             ctx.send_line(session, "").await?;
             ctx.send_line(
@@ -353,6 +388,21 @@ impl AdminScreen {
                 &format!("  [6] {}", ctx.i18n.t("admin.board_edit_paging")),
             )
             .await?;
+            ctx.send_line(
+                session,
+                &format!("  [7] {}", ctx.i18n.t("admin.board_edit_color")),
+            )
+            .await?;
+            ctx.send_line(
+                session,
+                &format!("  [8] {}", ctx.i18n.t("admin.board_edit_moderated")),
+            )
+            .await?;
+            ctx.send_line(
+                session,
+                &format!("  [9] {}", ctx.i18n.t("admin.board_edit_retention")),
+            )
+            .await?;
             ctx.send_line(session, "").await?;
 
             ctx.send(
@@ -480,6 +530,69 @@ impl AdminScreen {
                         .await?;
                     }
                 }
+                "7" => {
+                    let update = BoardUpdate::new().allow_color(!board.allow_color);
+                    let board_repo = BoardRepository::new(ctx.db.pool());
+                    if let Err(e) = board_repo.update(board_id, &update).await {
+                        ctx.send_line(session, &format!("Error: {}", e)).await?;
+                    } else {
+                        ctx.send_line(
+                            session,
+                            &ctx.i18n
+                                .t_with("admin.board_updated", &[("name", &board.name)]),
+                        )
+                        .await?;
+                    }
+                }
+                "8" => {
+                    let update = BoardUpdate::new().is_moderated(!board.is_moderated);
+                    let board_repo = BoardRepository::new(ctx.db.pool());
+                    if let Err(e) = board_repo.update(board_id, &update).await {
+                        ctx.send_line(session, &format!("Error: {}", e)).await?;
+                    } else {
+                        ctx.send_line(
+                            session,
+                            &ctx.i18n
+                                .t_with("admin.board_updated", &[("name", &board.name)]),
+                        )
+                        .await?;
+                    }
+                }
+                "9" => {
+                    let Some(max_posts) =
+                        Self::read_limit(ctx, session, "admin.retention_max_posts").await?
+                    else {
+                        continue;
+                    };
+                    let Some(max_age_days) =
+                        Self::read_limit(ctx, session, "admin.retention_max_age_days").await?
+                    else {
+                        continue;
+                    };
+                    ctx.send(
+                        session,
+                        &format!("{} (y/N): ", ctx.i18n.t("admin.retention_archive")),
+                    )
+                    .await?;
+                    let archive = ctx.read_line(session).await?;
+                    let archive_pruned = archive.trim().eq_ignore_ascii_case("y");
+
+                    let update = BoardUpdate::new()
+                        .max_posts(max_posts)
+                        .max_age_days(max_age_days)
+                        .archive_pruned(archive_pruned);
+                    let board_repo = BoardRepository::new(ctx.db.pool());
+                    if let Err(e) = board_repo.update(board_id, &update).await {
+                        ctx.send_line(session, &format!("Error: {}", e)).await?;
+                    } else {
+                        ctx.send_line(
+                            session,
+                            &ctx.i18n
+                                .t_with("admin.board_updated", &[("name", &board.name)]),
+                        )
+                        .await?;
+                    }
+                }
                 _ => {}
             }
         }
@@ -487,6 +600,55 @@ impl AdminScreen {
         Ok(())
     }
 
+    /// Describe a board's retention rules.
+    fn retention_to_string(board: &crate::board::Board, ctx: &ScreenContext) -> String {
+        if !board.has_retention() {
+            return ctx.i18n.t("admin.board_retention_none").to_string();
+        }
+        let mut parts = Vec::new();
+        if let Some(max_posts) = board.max_posts {
+            parts.push(ctx.i18n.t_with(
+                "admin.board_retention_posts",
+                &[("count", &max_posts.to_string())],
+            ));
+        }
+        if let Some(days) = board.max_age_days {
+            parts.push(
+                ctx.i18n
+                    .t_with("admin.board_retention_days", &[("days", &days.to_string())]),
+            );
+        }
+        if board.archive_pruned {
+            parts.push(ctx.i18n.t("admin.board_retention_archive").to_string());
+        }
+        parts.join(" / ")
+    }
+
+    /// Read a retention limit; blank means unlimited.
+    ///
+    /// Returns None (after a message) if the input is not a positive number.
+    async fn read_limit(
+        ctx: &mut ScreenContext,
+        session: &mut TelnetSession,
+        prompt_key: &str,
+    ) -> Result<Option<Option<i64>>> {
+        ctx.send(session, &format!("{}: ", ctx.i18n.t(prompt_key)))
+            .await?;
+        let input = ctx.read_line(session).await?;
+        let input = input.trim();
+        if input.is_empty() {
+            return Ok(Some(None));
+        }
+        match input.parse::<i64>() {
+            Ok(n) if n >= 1 => Ok(Some(Some(n))),
+            _ => {
+                ctx.send_line(session, ctx.i18n.t("admin.retention_invalid"))
+                    .await?;
+                Ok(None)
+            }
+        }
+    }
+
     /// Convert Role to localized string.
     fn role_to_string(role: &crate::db::Role, ctx: &ScreenContext) -> String {
         match role {
@@ -2472,7 +2634,7 @@ impl AdminScreen {
         current_user: &crate::db::User,
     ) -> Result<()> {
         use crate::admin::{AdminError, ContentAdminService, PostDeletionMode};
-        use crate::board::PostRepository;
+        use crate::board::{BoardType, PostRepository};
 
         loop {
             let post_repo = PostRepository::new(ctx.db.pool());
@@ -2522,6 +2684,11 @@ impl AdminScreen {
             ctx.send_line(session, "").await?;
             ctx.send_line(session, &format!("[D] {}", ctx.i18n.t("admin.delete_post")))
                 .await?;
+            ctx.send_line(
+                session,
+                &format!("[T] {}", ctx.i18n.t("admin.convert_to_thread")),
+            )
+            .await?;
             ctx.send_line(
                 session,
                 &format!("[Q] {}", ctx.i18n.t("admin.back_to_boards")),
@@ -2646,6 +2813,46 @@ impl AdminScreen {
                 continue;
             }
 
+            if input.eq_ignore_ascii_case("t") {
+                let Some(n) =
+                    Self::read_number(ctx, session, "admin.post_number_to_convert", posts.len())
+                        .await?
+                else {
+                    continue;
+                };
+                let target_post = &posts[n - 1];
+                let Some(target_board) =
+                    Self::select_target_board(ctx, session, BoardType::Thread, board.id).await?
+                else {
+                    continue;
+                };
+                let current_title = target_post.title.clone().unwrap_or_default();
+                let prompt = ctx
+                    .i18n
+                    .t("admin.thread_title_default")
+                    .replace("{{title}}", &current_title);
+                ctx.send(session, &format!("{}: ", prompt)).await?;
+                let title = ctx.read_line(session).await?;
+                let title = Some(title.trim()).filter(|t| !t.is_empty());
+
+                let service = ContentAdminService::new(ctx.db.pool());
+                match service
+                    .convert_post_to_thread(target_post.id, target_board.id, title, current_user)
+                    .await
+                {
+                    Ok(thread) => {
+                        let msg = ctx
+                            .i18n
+                            .t("admin.post_converted")
+                            .replace("{{title}}", &thread.title)
+                            .replace("{{board}}", &target_board.name);
+                        ctx.send_line(session, &msg).await?;
+                    }
+                    Err(e) => Self::send_content_error(ctx, session, e).await?,
+                }
+                continue;
+            }
+
             // Invalid input
             ctx.send_line(session, ctx.i18n.t("common.invalid_input"))
                 .await?;
@@ -2660,7 +2867,7 @@ impl AdminScreen {
         current_user: &crate::db::User,
     ) -> Result<()> {
         use crate::admin::{AdminError, ContentAdminService};
-        use crate::board::{PostRepository, ThreadRepository};
+        use crate::board::{BoardType, PostRepository, ThreadRepository};
 
         // Level 2: Thread list for selected board
         loop {
@@ -2722,6 +2929,18 @@ impl AdminScreen {
                 &format!("[D] {}", ctx.i18n.t("admin.delete_thread")),
             )
             .await?;
+            ctx.send_line(session, &format!("[M] {}", ctx.i18n.t("admin.move_thread")))
+                .await?;
+            ctx.send_line(
+                session,
+                &format!("[G] {}", ctx.i18n.t("admin.merge_threads")),
+            )
+            .await?;
+            ctx.send_line(
+                session,
+                &format!("[F] {}", ctx.i18n.t("admin.convert_to_flat")),
+            )
+            .await?;
             ctx.send_line(
                 session,
                 &format!("[Q] {}", ctx.i18n.t("admin.back_to_boards")),
@@ -2807,6 +3026,119 @@ impl AdminScreen {
                 continue;
             }
 
+            if input.eq_ignore_ascii_case("m") {
+                let Some(n) =
+                    Self::read_number(ctx, session, "admin.thread_number_to_move", threads.len())
+                        .await?
+                else {
+                    continue;
+                };
+                let target_thread = &threads[n - 1];
+                let Some(target_board) =
+                    Self::select_target_board(ctx, session, BoardType::Thread, board.id).await?
+                else {
+                    continue;
+                };
+
+                let service = ContentAdminService::new(ctx.db.pool());
+                match service
+                    .move_thread(target_thread.id, target_board.id, current_user)
+                    .await
+                {
+                    Ok(thread) => {
+                        let msg = ctx
+                            .i18n
+                            .t("admin.thread_moved")
+                            .replace("{{title}}", &thread.title)
+                            .replace("{{board}}", &target_board.name);
+                        ctx.send_line(session, &msg).await?;
+                    }
+                    Err(e) => Self::send_content_error(ctx, session, e).await?,
+                }
+                continue;
+            }
+
+            if input.eq_ignore_ascii_case("g") {
+                let Some(source) =
+                    Self::read_number(ctx, session, "admin.thread_number_to_merge", threads.len())
+                        .await?
+                else {
+                    continue;
+                };
+                let Some(target) =
+                    Self::read_number(ctx, session, "admin.merge_into_number", threads.len())
+                        .await?
+                else {
+                    continue;
+                };
+                let source = &threads[source - 1];
+                let target = &threads[target - 1];
+
+                let confirm_msg = ctx
+                    .i18n
+                    .t("admin.confirm_merge_threads")
+                    .replace("{{source}}", &source.title)
+                    .replace("{{target}}", &target.title);
+                ctx.send(session, &format!("{} ", confirm_msg)).await?;
+                let confirm = ctx.read_line(session).await?;
+                if !confirm.trim().eq_ignore_ascii_case("y") {
+                    continue;
+                }
+
+                let service = ContentAdminService::new(ctx.db.pool());
+                match service
+                    .merge_threads(source.id, target.id, current_user)
+                    .await
+                {
+                    Ok(thread) => {
+                        let msg = ctx
+                            .i18n
+                            .t("admin.threads_merged")
+                            .replace("{{title}}", &thread.title)
+                            .replace("{{posts}}", &thread.post_count.to_string());
+                        ctx.send_line(session, &msg).await?;
+                    }
+                    Err(e) => Self::send_content_error(ctx, session, e).await?,
+                }
+                continue;
+            }
+
+            if input.eq_ignore_ascii_case("f") {
+                let Some(n) = Self::read_number(
+                    ctx,
+                    session,
+                    "admin.thread_number_to_convert",
+                    threads.len(),
+                )
+                .await?
+                else {
+                    continue;
+                };
+                let target_thread = &threads[n - 1];
+                let Some(target_board) =
+                    Self::select_target_board(ctx, session, BoardType::Flat, board.id).await?
+                else {
+                    continue;
+                };
+
+                let service = ContentAdminService::new(ctx.db.pool());
+                match service
+                    .convert_thread_to_flat(target_thread.id, target_board.id, current_user)
+                    .await
+                {
+                    Ok(count) => {
+                        let msg = ctx
+                            .i18n
+                            .t("admin.thread_converted")
+                            .replace("{{posts}}", &count.to_string())
+                            .replace("{{board}}", &target_board.name);
+                        ctx.send_line(session, &msg).await?;
+                    }
+                    Err(e) => Self::send_content_error(ctx, session, e).await?,
+                }
+                continue;
+            }
+
             // Select thread to view posts
             let thread_num: usize = match input.parse() {
                 Ok(n) if n > 0 && n <= threads.len() => n,
@@ -2882,6 +3214,11 @@ impl AdminScreen {
             ctx.send_line(session, "").await?;
             ctx.send_line(session, &format!("[D] {}", ctx.i18n.t("admin.delete_post")))
                 .await?;
+            ctx.send_line(
+                session,
+                &format!("[S] {}", ctx.i18n.t("admin.split_thread")),
+            )
+            .await?;
             ctx.send_line(
                 session,
                 &format!("[Q] {}", ctx.i18n.t("admin.back_to_threads")),
@@ -3006,12 +3343,471 @@ impl AdminScreen {
                 continue;
             }
 
+            if input.eq_ignore_ascii_case("s") {
+                let Some(n) =
+                    Self::read_number(ctx, session, "admin.post_number_to_split", posts.len())
+                        .await?
+                else {
+                    continue;
+                };
+                let target_post = &posts[n - 1];
+                ctx.send(
+                    session,
+                    &format!("{}: ", ctx.i18n.t("admin.new_thread_title")),
+                )
+                .await?;
+                let title = ctx.read_line(session).await?;
+                if title.trim().is_empty() {
+                    continue;
+                }
+
+                let service = ContentAdminService::new(ctx.db.pool());
+                match service
+                    .split_thread(target_post.id, &title, current_user)
+                    .await
+                {
+                    Ok(thread) => {
+                        let msg = ctx
+                            .i18n
+                            .t("admin.thread_split")
+                            .replace("{{title}}", &thread.title)
+                            .replace("{{posts}}", &thread.post_count.to_string());
+                        ctx.send_line(session, &msg).await?;
+                    }
+                    Err(e) => Self::send_content_error(ctx, session, e).await?,
+                }
+                continue;
+            }
+
             // Invalid input
             ctx.send_line(session, ctx.i18n.t("common.invalid_input"))
                 .await?;
         }
     }
 
+    /// Read an item number from a list of `max` items.
+    ///
+    /// Returns None if the admin cancels or the number is out of range.
+    async fn read_number(
+        ctx: &mut ScreenContext,
+        session: &mut TelnetSession,
+        prompt_key: &str,
+        max: usize,
+    ) -> Result<Option<usize>> {
+        ctx.send(
+            session,
+            &format!(
+                "{} [Q={}]: ",
+                ctx.i18n.t(prompt_key),
+                ctx.i18n.t("common.cancel")
+            ),
+        )
+        .await?;
+
+        let input = ctx.read_line(session).await?;
+        let input = input.trim();
+        if input.eq_ignore_ascii_case("q") || input.is_empty() {
+            return Ok(None);
+        }
+
+        match input.parse() {
+            Ok(n) if n > 0 && n <= max => Ok(Some(n)),
+            _ => {
+                ctx.send_line(session, ctx.i18n.t("common.invalid_input"))
+                    .await?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Select a board of the given type, other than the current one, to
+    /// move content to.
+    async fn select_target_board(
+        ctx: &mut ScreenContext,
+        session: &mut TelnetSession,
+        board_type: crate::board::BoardType,
+        current_board_id: i64,
+    ) -> Result<Option<crate::board::Board>> {
+        use crate::board::BoardRepository;
+
+        let boards: Vec<_> = BoardRepository::new(ctx.db.pool())
+            .list_all()
+            .await?
+            .into_iter()
+            .filter(|b| b.board_type == board_type && b.id != current_board_id)
+            .collect();
+        if boards.is_empty() {
+            ctx.send_line(session, ctx.i18n.t("admin.no_target_boards"))
+                .await?;
+            return Ok(None);
+        }
+
+        ctx.send_line(session, "").await?;
+        ctx.send_line(session, ctx.i18n.t("admin.select_target_board"))
+            .await?;
+        for (i, board) in boards.iter().enumerate() {
+            ctx.send_line(session, &format!("  [{}] {}", i + 1, board.name))
+                .await?;
+        }
+
+        let n = Self::read_number(ctx, session, "menu.select_prompt", boards.len()).await?;
+        Ok(n.map(|n| boards[n - 1].clone()))
+    }
+
+    /// Show the error of a failed content operation.
+    async fn send_content_error(
+        ctx: &mut ScreenContext,
+        session: &mut TelnetSession,
+        error: crate::admin::AdminError,
+    ) -> Result<()> {
+        ctx.send_line(
+            session,
+            &format!("{}: {}", ctx.i18n.t("common.error"), error),
+        )
+        .await
+    }
+
+    /// Review posts waiting for approval on moderated boards.
+    async fn moderation_queue(ctx: &mut ScreenContext, session: &mut TelnetSession) -> Result<()> {
+        use crate::admin::ModerationAdminService;
+        use crate::board::BoardRepository;
+        use crate::db::UserRepository;
+
+        // Get current admin user
+        let current_user = match session.user_id() {
+            Some(user_id) => {
+                let user_repo = UserRepository::new(ctx.db.pool());
+                match user_repo.get_by_id(user_id).await? {
+                    Some(user) => user,
+                    None => {
+                        ctx.send_line(session, ctx.i18n.t("error.user_not_found"))
+                            .await?;
+                        return Ok(());
+                    }
+                }
+            }
+            None => {
+                ctx.send_line(session, ctx.i18n.t("error.not_logged_in"))
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        loop {
+            let pending = match ModerationAdminService::new(&ctx.db)
+                .list_pending(&current_user)
+                .await
+            {
+                Ok(pending) => pending,
+                Err(e) => {
+                    ctx.send_line(session, &format!("Error: {}", e)).await?;
+                    return Ok(());
+                }
+            };
+
+            ctx.send_line(session, "").await?;
+            ctx.send_line(
+                session,
+                &format!("=== {} ===", ctx.i18n.t("admin.moderation_queue")),
+            )
+            .await?;
+            ctx.send_line(session, "").await?;
+
+            if pending.is_empty() {
+                ctx.send_line(session, ctx.i18n.t("admin.no_pending_posts"))
+                    .await?;
+                ctx.wait_for_enter(session).await?;
+                return Ok(());
+            }
+
+            let board_repo = BoardRepository::new(ctx.db.pool());
+            let user_repo = UserRepository::new(ctx.db.pool());
+            let mut authors = Vec::with_capacity(pending.len());
+            for (i, post) in pending.iter().enumerate() {
+                let board_name = board_repo
+                    .get_by_id(post.board_id)
+                    .await?
+                    .map(|b| b.name)
+                    .unwrap_or_default();
+                let author = user_repo
+                    .get_by_id(post.author_id)
+                    .await?
+                    .map(|u| u.username)
+                    .unwrap_or_else(|| "unknown".to_string());
+                let summary = match &post.title {
+                    Some(title) => title.clone(),
+                    None => post.body.lines().next().unwrap_or_default().to_string(),
+                };
+                let kind = if post.is_reply() { "[R]" } else { "[N]" };
+                ctx.send_line(
+                    session,
+                    &format!(
+                        "  [{}] {} {} - {} ({})",
+                        i + 1,
+                        kind,
+                        board_name,
+                        summary,
+                        author
+                    ),
+                )
+                .await?;
+                authors.push(author);
+            }
+
+            ctx.send_line(session, "").await?;
+            ctx.send(
+                session,
+                &format!(
+                    "{} [Q={}]: ",
+                    ctx.i18n.t("menu.select_prompt"),
+                    ctx.i18n.t("common.back")
+                ),
+            )
+            .await?;
+
+            let input = ctx.read_line(session).await?;
+            let input = input.trim();
+
+            if input.eq_ignore_ascii_case("q") || input.is_empty() {
+                return Ok(());
+            }
+
+            let num: usize = match input.parse() {
+                Ok(n) if n > 0 && n <= pending.len() => n,
+                _ => {
+                    ctx.send_line(session, ctx.i18n.t("common.invalid_input"))
+                        .await?;
+                    continue;
+                }
+            };
+            let post = &pending[num - 1];
+
+            // Show the full post
+            ctx.send_line(session, "").await?;
+            ctx.send_line(
+                session,
+                &format!("{}: {}", ctx.i18n.t("board.author"), authors[num - 1]),
+            )
+            .await?;
+            if let Some(title) = &post.title {
+                ctx.send_line(session, &format!("{}: {}", ctx.i18n.t("board.title"), title))
+                    .await?;
+            }
+            ctx.send_line(session, "").await?;
+            for line in post.body.lines() {
+                ctx.send_line(session, line).await?;
+            }
+            ctx.send_line(session, "").await?;
+
+            ctx.send(session, ctx.i18n.t("admin.moderation_action"))
+                .await?;
+            let action = ctx.read_line(session).await?;
+
+            match action.trim().to_ascii_lowercase().as_str() {
+                "a" => {
+                    match ModerationAdminService::new(&ctx.db)
+                        .approve(post.id, &current_user)
+                        .await
+                    {
+                        Ok(_) => {
+                            ctx.send_line(session, ctx.i18n.t("admin.post_approved"))
+                                .await?
+                        }
+                        Err(e) => ctx.send_line(session, &format!("Error: {}", e)).await?,
+                    }
+                }
+                "r" => {
+                    ctx.send(
+                        session,
+                        &format!("{}: ", ctx.i18n.t("admin.rejection_reason")),
+                    )
+                    .await?;
+                    let reason = ctx.read_line(session).await?;
+                    if reason.trim().is_empty() {
+                        continue;
+                    }
+                    match ModerationAdminService::new(&ctx.db)
+                        .reject(post.id, &reason, &current_user)
+                        .await
+                    {
+                        Ok(_) => {
+                            ctx.send_line(session, ctx.i18n.t("admin.post_rejected"))
+                                .await?
+                        }
+                        Err(e) => ctx.send_line(session, &format!("Error: {}", e)).await?,
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Manage content filter rules (SysOp only).
+    async fn content_filter(ctx: &mut ScreenContext, session: &mut TelnetSession) -> Result<()> {
+        use crate::db::Role;
+        use crate::filter::{ContentFilterService, FilterAction, FilterRuleType, NewFilterRule};
+
+        let Some(user_id) = session.user_id() else {
+            return Ok(());
+        };
+        if !Self::is_sysop(ctx, session).await {
+            ctx.send_line(session, ctx.i18n.t("admin.sysop_required"))
+                .await?;
+            return Ok(());
+        }
+        let role = Role::SysOp;
+
+        loop {
+            let rules = ContentFilterService::new(ctx.db.pool())
+                .list_rules(role)
+                .await?;
+
+            ctx.send_line(session, "").await?;
+            ctx.send_line(session, &format!("=== {} ===", ctx.i18n.t("filter.title")))
+                .await?;
+            ctx.send_line(session, "").await?;
+            if rules.is_empty() {
+                ctx.send_line(session, ctx.i18n.t("filter.no_rules"))
+                    .await?;
+            }
+            for (i, rule) in rules.iter().enumerate() {
+                let pattern: String = rule
+                    .pattern
+                    .lines()
+                    .next()
+                    .unwrap_or_default()
+                    .chars()
+                    .take(40)
+                    .collect();
+                ctx.send_line(
+                    session,
+                    &format!(
+                        "  [{}] [{}] {} / {}: {}",
+                        i + 1,
+                        if rule.is_active { "*" } else { " " },
+                        ctx.i18n.t(&format!("filter.type_{}", rule.rule_type)),
+                        ctx.i18n.t(&format!("filter.action_{}", rule.action)),
+                        pattern
+                    ),
+                )
+                .await?;
+            }
+            ctx.send_line(session, "").await?;
+            ctx.send(session, ctx.i18n.t("filter.menu")).await?;
+
+            let input = ctx.read_line(session).await?;
+            let result = match input.trim().to_ascii_lowercase().as_str() {
+                "a" => {
+                    ctx.send(session, ctx.i18n.t("filter.select_type")).await?;
+                    let rule_type = match ctx.read_line(session).await?.trim() {
+                        "1" => FilterRuleType::Word,
+                        "2" => FilterRuleType::Regex,
+                        "3" => FilterRuleType::Links,
+                        "4" => FilterRuleType::NewAccount,
+                        _ => continue,
+                    };
+                    let prompt = format!("filter.pattern_{}", rule_type);
+                    ctx.send(session, &format!("{}: ", ctx.i18n.t(&prompt)))
+                        .await?;
+                    let pattern = ctx.read_line(session).await?;
+                    if pattern.trim().is_empty() {
+                        continue;
+                    }
+                    ctx.send(session, ctx.i18n.t("filter.select_action"))
+                        .await?;
+                    let action = match ctx.read_line(session).await?.trim() {
+                        "1" => FilterAction::Reject,
+                        "2" => FilterAction::Hold,
+                        "3" => FilterAction::Mask,
+                        _ => continue,
+                    };
+                    ContentFilterService::new(ctx.db.pool())
+                        .add_rule(
+                            user_id,
+                            role,
+                            NewFilterRule::new(rule_type, pattern, action),
+                        )
+                        .await
+                        .map(|_| "filter.added")
+                }
+                command @ ("t" | "d") => {
+                    ctx.send(session, &format!("{}: ", ctx.i18n.t("filter.rule_number")))
+                        .await?;
+                    let input = ctx.read_line(session).await?;
+                    let rule = match input.trim().parse::<usize>() {
+                        Ok(n) if n > 0 && n <= rules.len() => &rules[n - 1],
+                        _ => continue,
+                    };
+                    if command == "t" {
+                        ContentFilterService::new(ctx.db.pool())
+                            .set_active(rule.id, !rule.is_active, role)
+                            .await
+                            .map(|_| "filter.updated")
+                    } else {
+                        ctx.send(session, ctx.i18n.t("filter.confirm_delete"))
+                            .await?;
+                        let confirm = ctx.read_line(session).await?;
+                        if !confirm.trim().eq_ignore_ascii_case("y") {
+                            continue;
+                        }
+                        ContentFilterService::new(ctx.db.pool())
+                            .delete_rule(rule.id, role)
+                            .await
+                            .map(|_| "filter.deleted")
+                    }
+                }
+                "l" => {
+                    Self::show_filter_hits(ctx, session).await?;
+                    continue;
+                }
+                _ => return Ok(()),
+            };
+
+            match result {
+                Ok(key) => ctx.send_line(session, ctx.i18n.t(key)).await?,
+                Err(e) => ctx.send_line(session, &format!("Error: {}", e)).await?,
+            }
+        }
+    }
+
+    /// Show the most recent content filter hits.
+    async fn show_filter_hits(ctx: &mut ScreenContext, session: &mut TelnetSession) -> Result<()> {
+        use crate::datetime::format_datetime;
+        use crate::db::Role;
+        use crate::filter::ContentFilterService;
+
+        let hits = ContentFilterService::new(ctx.db.pool())
+            .list_hits(Role::SysOp)
+            .await?;
+
+        ctx.send_line(session, "").await?;
+        ctx.send_line(
+            session,
+            &format!("=== {} ===", ctx.i18n.t("filter.hit_log")),
+        )
+        .await?;
+        ctx.send_line(session, "").await?;
+        if hits.is_empty() {
+            ctx.send_line(session, ctx.i18n.t("filter.no_hits")).await?;
+        }
+        for hit in &hits {
+            let detail: String = hit.detail.chars().take(30).collect();
+            ctx.send_line(
+                session,
+                &format!(
+                    "  {} {} {} {} {}",
+                    format_datetime(&hit.created_at, &ctx.config.server.timezone, "%m/%d %H:%M"),
+                    hit.username.as_deref().unwrap_or("-"),
+                    ctx.i18n.t(&format!("filter.target_{}", hit.target)),
+                    ctx.i18n.t(&format!("filter.action_{}", hit.action)),
+                    detail
+                ),
+            )
+            .await?;
+        }
+        ctx.wait_for_enter(session).await
+    }
+
     /// Check if user is admin.
     async fn is_admin(ctx: &ScreenContext, session: &TelnetSession) -> bool {
         use crate::db::{Role, UserRepository};
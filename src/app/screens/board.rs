@@ -3,16 +3,24 @@
 use tracing::error;
 
 use super::common::{Pagination, ScreenContext};
-use crate::datetime::format_datetime;
+use crate::datetime::{days_ago_db, format_datetime, parse_local_datetime};
+use super::poll::PollScreen;
 use super::ScreenResult;
 use crate::board::{
-    BoardRepository, BoardService, BoardType, Pagination as BoardPagination, PostRepository,
-    ThreadRepository, UnreadPostWithBoard, UnreadRepository,
+    diff_lines, is_searchable, quote_body, Board, BoardGroup, BoardRepository, BoardService,
+    BoardType, NewscanService, Pagination as BoardPagination, Post, PostRepository, Submission,
+    ThreadRepository, ThreadedPost, UnreadCounts, UnreadPostWithBoard, UnreadRepository,
+    MAX_REPLY_DEPTH, QUOTE_LINES,
 };
+use crate::bookmark::{BookmarkService, BookmarkTarget};
 use crate::db::{Role, UserRepository};
-use crate::error::Result;
+use crate::error::{HobbsError, Result};
+use crate::filter::is_rejection;
+use crate::notify::{NotificationService, SubscriptionTarget};
 use crate::rate_limit::RateLimitResult;
-use crate::server::{convert_caret_escape, TelnetSession};
+use crate::screen::LightbarItem;
+use crate::server::color::strip_color_codes;
+use crate::server::{Key, TelnetSession};
 use crate::template::Value;
 
 /// Board screen handler.
@@ -20,37 +28,74 @@ pub struct BoardScreen;
 
 impl BoardScreen {
     /// Run the board list screen.
+    ///
+    /// The top level lists board groups followed by boards without a group.
+    /// Selecting a group lists its boards.
     pub async fn run_list(
         ctx: &mut ScreenContext,
         session: &mut TelnetSession,
     ) -> Result<ScreenResult> {
+        let mut current_group: Option<BoardGroup> = None;
+
         loop {
-            // Get boards
+            // Get groups and boards
             let user_role = Self::get_user_role(ctx, session).await;
             let board_service = BoardService::new(&ctx.db);
-            let boards = board_service.list_boards(user_role).await?;
+            let all_boards = board_service.list_boards(user_role).await?;
+            let group_id = current_group.as_ref().map(|g| g.id);
+            let groups: Vec<BoardGroup> = if current_group.is_none() {
+                board_service.list_groups(user_role).await?
+            } else {
+                Vec::new()
+            };
+            // Groups without readable boards are hidden
+            let mut group_sizes: std::collections::HashMap<i64, i64> =
+                std::collections::HashMap::new();
+            for id in all_boards.iter().filter_map(|b| b.group_id) {
+                *group_sizes.entry(id).or_insert(0) += 1;
+            }
+            let groups: Vec<BoardGroup> = groups
+                .into_iter()
+                .filter(|g| group_sizes.contains_key(&g.id))
+                .collect();
+            let boards: Vec<Board> = all_boards
+                .into_iter()
+                .filter(|b| b.group_id == group_id)
+                .collect();
 
             // Get unread counts for logged-in users
-            let unread_counts: std::collections::HashMap<i64, i64> =
-                if let Some(user_id) = session.user_id() {
-                    let unread_repo = UnreadRepository::new(ctx.db.pool());
-                    unread_repo
-                        .get_all_unread_counts(user_id)
-                        .await?
-                        .into_iter()
-                        .collect()
-                } else {
-                    std::collections::HashMap::new()
-                };
+            let unread_counts = if let Some(user_id) = session.user_id() {
+                let kill_file = ctx.kill_file(session).await?;
+                let unread_repo = UnreadRepository::new(ctx.db.pool()).with_kill_file(&kill_file);
+                unread_repo.get_all_unread_counts(user_id, user_role).await?
+            } else {
+                UnreadCounts::default()
+            };
 
             // Display board list using template
             let logged_in = session.user_id().is_some();
+            let lightbar = ctx.lightbar_enabled(session);
             let mut context = ctx.create_context();
             context.set("logged_in", Value::bool(logged_in));
-            context.set("has_boards", Value::bool(!boards.is_empty()));
+            context.set(
+                "has_boards",
+                Value::bool(!groups.is_empty() || !boards.is_empty()),
+            );
+            context.set("lightbar", Value::bool(lightbar));
+            if let Some(ref group) = current_group {
+                context.set("group_name", Value::string(&group.name));
+            }
 
+            let name_width = (ctx.profile.width as usize).saturating_sub(22);
             let mut board_list = Vec::new();
-            for (i, board) in boards.iter().enumerate() {
+            let mut lightbar_items = Vec::new();
+            let mut rows: Vec<(String, i64, i64)> = Vec::new();
+            for group in &groups {
+                let name = ctx.i18n.t_with("board.group_entry", &[("name", &group.name)]);
+                let count = group_sizes[&group.id];
+                rows.push((name, count, unread_counts.group(group.id)));
+            }
+            for board in &boards {
                 let count = if board.board_type == BoardType::Thread {
                     let thread_repo = ThreadRepository::new(ctx.db.pool());
                     thread_repo.count_by_board(board.id).await?
@@ -58,22 +103,37 @@ impl BoardScreen {
                     let post_repo = PostRepository::new(ctx.db.pool());
                     post_repo.count_by_flat_board(board.id).await?
                 };
+                rows.push((board.name.clone(), count, unread_counts.board(board.id)));
+            }
 
+            for (i, (name, count, unread)) in rows.iter().enumerate() {
                 let mut entry = std::collections::HashMap::new();
                 entry.insert("number".to_string(), Value::string((i + 1).to_string()));
-                entry.insert("name".to_string(), Value::string(&board.name));
+                entry.insert("name".to_string(), Value::string(name));
                 entry.insert("count".to_string(), Value::string(count.to_string()));
 
+                let unread_display = if logged_in && *unread > 0 {
+                    format!("[{}]", unread)
+                } else {
+                    String::new()
+                };
                 if logged_in {
-                    let unread = unread_counts.get(&board.id).copied().unwrap_or(0);
-                    let unread_display = if unread > 0 {
-                        format!("[{}]", unread)
-                    } else {
-                        String::new()
-                    };
-                    entry.insert("unread_display".to_string(), Value::string(unread_display));
+                    entry.insert(
+                        "unread_display".to_string(),
+                        Value::string(unread_display.clone()),
+                    );
                 }
 
+                lightbar_items.push(LightbarItem::new(
+                    (i + 1).to_string(),
+                    format!(
+                        "{:>3}  {} {:>5} {}",
+                        i + 1,
+                        ctx.pad(name, name_width),
+                        count,
+                        unread_display
+                    ),
+                ));
                 board_list.push(Value::Object(entry));
             }
             context.set("boards", Value::List(board_list));
@@ -81,35 +141,45 @@ impl BoardScreen {
             let content = ctx.render_template("board/list", &context)?;
             ctx.send(session, &content).await?;
 
-            // Prompt - show [U] option only for logged-in users
-            if session.user_id().is_some() {
-                ctx.send(
-                    session,
-                    &format!(
-                        "{} [U]={} [Q={}]: ",
-                        ctx.i18n.t("menu.select_prompt"),
-                        ctx.i18n.t("board.read_all_unread"),
-                        ctx.i18n.t("common.back")
-                    ),
+            // Prompt - show [U], [N] and [B] options only for logged-in users
+            let prompt = if session.user_id().is_some() {
+                format!(
+                    "{} [U]={} [N]={} [B]={} [S]={} [Q={}]: ",
+                    ctx.i18n.t("menu.select_prompt"),
+                    ctx.i18n.t("board.read_all_unread"),
+                    ctx.i18n.t("board.newscan"),
+                    ctx.i18n.t("board.bookmarks"),
+                    ctx.i18n.t("board.search"),
+                    ctx.i18n.t("common.back")
                 )
-                .await?;
             } else {
-                ctx.send(
-                    session,
-                    &format!(
-                        "{} [Q={}]: ",
-                        ctx.i18n.t("menu.select_prompt"),
-                        ctx.i18n.t("common.back")
-                    ),
+                format!(
+                    "{} [S]={} [Q={}]: ",
+                    ctx.i18n.t("menu.select_prompt"),
+                    ctx.i18n.t("board.search"),
+                    ctx.i18n.t("common.back")
                 )
-                .await?;
-            }
+            };
 
-            let input = ctx.read_line(session).await?;
+            let input = if lightbar {
+                let mut bar = ctx
+                    .lightbar(lightbar_items)
+                    .bind(Key::Escape, "q")
+                    .bind(Key::Left, "q");
+                ctx.read_lightbar(session, &mut bar, &prompt).await?
+            } else {
+                ctx.send(session, &prompt).await?;
+                ctx.read_line(session).await?
+            };
             let input = input.trim();
 
             match input.to_ascii_lowercase().as_str() {
-                "q" | "" => return Ok(ScreenResult::Back),
+                "q" | "" => {
+                    // Leave the group, or the board list at the top level
+                    if current_group.take().is_none() {
+                        return Ok(ScreenResult::Back);
+                    }
+                }
                 "u" => {
                     if session.user_id().is_some() {
                         Self::run_all_unread_batch_read(ctx, session).await?;
@@ -118,11 +188,31 @@ impl BoardScreen {
                             .await?;
                     }
                 }
+                "n" => {
+                    if session.user_id().is_some() {
+                        Self::run_newscan_settings(ctx, session).await?;
+                    } else {
+                        ctx.send_line(session, ctx.i18n.t("menu.login_required"))
+                            .await?;
+                    }
+                }
+                "b" => {
+                    if session.user_id().is_some() {
+                        Self::run_bookmarks(ctx, session).await?;
+                    } else {
+                        ctx.send_line(session, ctx.i18n.t("menu.login_required"))
+                            .await?;
+                    }
+                }
+                "s" => {
+                    Self::run_search(ctx, session).await?;
+                }
                 _ => {
                     if let Some(num) = ctx.parse_number(input) {
                         let idx = (num - 1) as usize;
-                        if idx < boards.len() {
-                            let board = &boards[idx];
+                        if idx < groups.len() {
+                            current_group = Some(groups[idx].clone());
+                        } else if let Some(board) = boards.get(idx - groups.len()) {
                             match board.board_type {
                                 BoardType::Thread => {
                                     Self::run_thread_list(ctx, session, board.id).await?;
@@ -138,6 +228,121 @@ impl BoardScreen {
         }
     }
 
+    /// Search posts across all readable boards.
+    async fn run_search(
+        ctx: &mut ScreenContext,
+        session: &mut TelnetSession,
+    ) -> Result<ScreenResult> {
+        ctx.send(session, ctx.i18n.t("board.search_prompt")).await?;
+        let query = ctx.read_line(session).await?;
+        let query = query.trim().to_string();
+        if query.is_empty() {
+            return Ok(ScreenResult::Back);
+        }
+        if !is_searchable(&query) {
+            ctx.send_line(session, ctx.i18n.t("board.no_results")).await?;
+            return Ok(ScreenResult::Back);
+        }
+
+        let per_page: i64 = 10;
+        let mut pagination = Pagination::new(1, per_page as usize, 0);
+
+        loop {
+            let user_role = Self::get_user_role(ctx, session).await;
+            let board_service = BoardService::new(&ctx.db);
+            let board_pagination =
+                BoardPagination::new(pagination.offset() as i64, pagination.per_page as i64);
+            let result = board_service
+                .search(&query, None, user_role, board_pagination)
+                .await?;
+
+            pagination.total = result.total as usize;
+
+            let mut context = ctx.create_context();
+            context.set("query", Value::string(query.clone()));
+            context.set("has_results", Value::bool(!result.items.is_empty()));
+            context.set("total", Value::number(result.total));
+
+            let mut result_list = Vec::new();
+            for (i, hit) in result.items.iter().enumerate() {
+                let num = pagination.offset() + i + 1;
+                let title = hit
+                    .thread_title
+                    .as_deref()
+                    .or(hit.post.title.as_deref())
+                    .unwrap_or("(no title)");
+                let excerpt = strip_color_codes(&hit.post.body)
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let formatted_time = format_datetime(
+                    &hit.post.created_at,
+                    &ctx.config.server.timezone,
+                    "%Y-%m-%d",
+                );
+
+                let mut entry = std::collections::HashMap::new();
+                entry.insert("number".to_string(), Value::string(num.to_string()));
+                entry.insert("board".to_string(), Value::string(&hit.board_name));
+                entry.insert("title".to_string(), Value::string(title));
+                entry.insert("excerpt".to_string(), Value::string(excerpt));
+                entry.insert("date".to_string(), Value::string(formatted_time));
+                result_list.push(Value::Object(entry));
+            }
+            context.set("results", Value::List(result_list));
+
+            context.set(
+                "page_info",
+                Value::string(ctx.i18n.t_with(
+                    "board.page_of",
+                    &[
+                        ("current", &pagination.page.to_string()),
+                        ("total", &pagination.total_pages().to_string()),
+                    ],
+                )),
+            );
+
+            let content = ctx.render_template("board/search", &context)?;
+            ctx.send(session, &content).await?;
+
+            ctx.send(
+                session,
+                &format!(
+                    "[N]={} [P]={} [Q]={}: ",
+                    ctx.i18n.t("common.next"),
+                    ctx.i18n.t("common.previous"),
+                    ctx.i18n.t("common.back")
+                ),
+            )
+            .await?;
+
+            let input = ctx.read_line(session).await?;
+            let input = input.trim();
+
+            match input.to_ascii_lowercase().as_str() {
+                "q" | "" => return Ok(ScreenResult::Back),
+                "n" => pagination.next(),
+                "p" => pagination.prev(),
+                _ => {
+                    if let Some(num) = ctx.parse_number(input) {
+                        let idx = num - 1 - pagination.offset() as i64;
+                        if idx >= 0 && (idx as usize) < result.items.len() {
+                            let post = &result.items[idx as usize].post;
+                            match post.thread_id {
+                                Some(thread_id) => {
+                                    Self::run_thread_view(ctx, session, thread_id).await?;
+                                }
+                                None => {
+                                    Self::run_post_view(ctx, session, post.id).await?;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     /// Run the thread list screen (for thread-type boards).
     async fn run_thread_list(
         ctx: &mut ScreenContext,
@@ -152,24 +357,33 @@ impl BoardScreen {
             let user_role = Self::get_user_role(ctx, session).await;
             let board_service = BoardService::new(&ctx.db);
             let board = board_service.get_board(board_id, user_role).await?;
+            let kill_file = ctx.kill_file(session).await?;
 
             // Get threads using service with pagination
             let board_pagination =
                 BoardPagination::new(pagination.offset() as i64, pagination.per_page as i64);
-            let result = board_service.list_threads(board_id, user_role, board_pagination).await?;
+            let result = board_service
+                .list_visible_threads(board_id, user_role, board_pagination, &kill_file)
+                .await?;
 
             pagination.total = result.total as usize;
 
             // Display thread list using template
+            let lightbar = ctx.lightbar_enabled(session);
             let mut context = ctx.create_context();
             context.set("board_name", Value::string(board.name.clone()));
             context.set("has_threads", Value::bool(!result.items.is_empty()));
+            context.set("lightbar", Value::bool(lightbar));
+
+            let title_width = (ctx.profile.width as usize).saturating_sub(18);
+            let mut lightbar_items = Vec::new();
 
             if !result.items.is_empty() {
                 // Get unread thread IDs for logged-in users
                 let unread_thread_ids = if let Some(user_id) = session.user_id() {
                     let thread_ids: Vec<i64> = result.items.iter().map(|t| t.id).collect();
-                    let unread_repo = UnreadRepository::new(ctx.db.pool());
+                    let unread_repo =
+                        UnreadRepository::new(ctx.db.pool()).with_kill_file(&kill_file);
                     unread_repo.get_unread_thread_ids(user_id, board_id, &thread_ids).await?
                 } else {
                     std::collections::HashSet::new()
@@ -185,14 +399,35 @@ impl BoardScreen {
                         " "
                     };
 
+                    let flags = format!(
+                        "{}{}",
+                        if thread.is_pinned { "P" } else { " " },
+                        if thread.is_locked { "L" } else { " " }
+                    );
+
                     let mut entry = std::collections::HashMap::new();
                     entry.insert("unread_mark".to_string(), Value::string(unread_mark));
+                    entry.insert("flags".to_string(), Value::string(&flags));
                     entry.insert("number".to_string(), Value::string(num.to_string()));
                     entry.insert("title".to_string(), Value::string(&thread.title));
                     entry.insert("post_count".to_string(), Value::string(thread.post_count.to_string()));
                     thread_list.push(Value::Object(entry));
+
+                    lightbar_items.push(LightbarItem::new(
+                        num.to_string(),
+                        format!(
+                            "{}{}{:>4}  {} {:>5}",
+                            unread_mark,
+                            flags,
+                            num,
+                            ctx.pad(&thread.title, title_width),
+                            thread.post_count
+                        ),
+                    ));
                 }
                 context.set("threads", Value::List(thread_list));
+                let has_flags = result.items.iter().any(|t| t.is_pinned || t.is_locked);
+                context.set("has_flags", Value::bool(has_flags));
             }
 
             context.set(
@@ -210,35 +445,41 @@ impl BoardScreen {
             ctx.send(session, &content).await?;
 
             // Prompt - show [U] and [A] options only for logged-in users
-            if session.user_id().is_some() {
-                ctx.send(
-                    session,
-                    &format!(
-                        "[N]={} [P]={} [U]={} [A]={} [W]={} [Q]={}: ",
-                        ctx.i18n.t("common.next"),
-                        ctx.i18n.t("common.previous"),
-                        ctx.i18n.t("board.read_unread"),
-                        ctx.i18n.t("board.mark_all_read"),
-                        ctx.i18n.t("board.new_thread"),
-                        ctx.i18n.t("common.back")
-                    ),
+            let prompt = if session.user_id().is_some() {
+                format!(
+                    "[N]={} [P]={} [U]={} [A]={} [W]={} [S]={} [X]={} [Q]={}: ",
+                    ctx.i18n.t("common.next"),
+                    ctx.i18n.t("common.previous"),
+                    ctx.i18n.t("board.read_unread"),
+                    ctx.i18n.t("board.mark_all_read"),
+                    ctx.i18n.t("board.new_thread"),
+                    ctx.i18n.t("notify.subscribe"),
+                    ctx.show_hidden_label(session),
+                    ctx.i18n.t("common.back")
                 )
-                .await?;
             } else {
-                ctx.send(
-                    session,
-                    &format!(
-                        "[N]={} [P]={} [W]={} [Q]={}: ",
-                        ctx.i18n.t("common.next"),
-                        ctx.i18n.t("common.previous"),
-                        ctx.i18n.t("board.new_thread"),
-                        ctx.i18n.t("common.back")
-                    ),
+                format!(
+                    "[N]={} [P]={} [W]={} [Q]={}: ",
+                    ctx.i18n.t("common.next"),
+                    ctx.i18n.t("common.previous"),
+                    ctx.i18n.t("board.new_thread"),
+                    ctx.i18n.t("common.back")
                 )
-                .await?;
-            }
+            };
 
-            let input = ctx.read_line(session).await?;
+            let input = if lightbar {
+                let mut bar = ctx
+                    .lightbar(lightbar_items)
+                    .bind(Key::PageDown, "n")
+                    .bind(Key::PageUp, "p")
+                    .bind(Key::Right, "n")
+                    .bind(Key::Left, "q")
+                    .bind(Key::Escape, "q");
+                ctx.read_lightbar(session, &mut bar, &prompt).await?
+            } else {
+                ctx.send(session, &prompt).await?;
+                ctx.read_line(session).await?
+            };
             let input = input.trim();
 
             match input.to_ascii_lowercase().as_str() {
@@ -269,6 +510,14 @@ impl BoardScreen {
                             .await?;
                     }
                 }
+                "s" if session.user_id().is_some() => {
+                    Self::run_subscription(ctx, session, SubscriptionTarget::Board(board_id))
+                        .await?;
+                }
+                "x" if session.user_id().is_some() => {
+                    session.set_show_hidden(!session.show_hidden());
+                    pagination.page = 1;
+                }
                 _ => {
                     if let Some(num) = ctx.parse_number(input) {
                         let offset = pagination.offset();
@@ -297,12 +546,14 @@ impl BoardScreen {
             let user_role = Self::get_user_role(ctx, session).await;
             let board_service = BoardService::new(&ctx.db);
             let board = board_service.get_board(board_id, user_role).await?;
+            let kill_file = ctx.kill_file(session).await?;
 
             // Get posts using service with pagination
             let board_pagination =
                 BoardPagination::new(pagination.offset() as i64, pagination.per_page as i64);
-            let result =
-                board_service.list_posts_in_flat_board(board_id, user_role, board_pagination).await?;
+            let result = board_service
+                .list_visible_posts_in_flat_board(board_id, user_role, board_pagination, &kill_file)
+                .await?;
 
             pagination.total = result.total as usize;
 
@@ -366,12 +617,14 @@ impl BoardScreen {
                 ctx.send(
                     session,
                     &format!(
-                        "[N]={} [P]={} [U]={} [A]={} [W]={} [Q]={}: ",
+                        "[N]={} [P]={} [U]={} [A]={} [W]={} [S]={} [X]={} [Q]={}: ",
                         ctx.i18n.t("common.next"),
                         ctx.i18n.t("common.previous"),
                         ctx.i18n.t("board.read_unread"),
                         ctx.i18n.t("board.mark_all_read"),
                         ctx.i18n.t("board.new_post"),
+                        ctx.i18n.t("notify.subscribe"),
+                        ctx.show_hidden_label(session),
                         ctx.i18n.t("common.back")
                     ),
                 )
@@ -421,6 +674,14 @@ impl BoardScreen {
                             .await?;
                     }
                 }
+                "s" if session.user_id().is_some() => {
+                    Self::run_subscription(ctx, session, SubscriptionTarget::Board(board_id))
+                        .await?;
+                }
+                "x" if session.user_id().is_some() => {
+                    session.set_show_hidden(!session.show_hidden());
+                    pagination.page = 1;
+                }
                 _ => {
                     if let Some(num) = ctx.parse_number(input) {
                         // Convert descending number to index
@@ -437,6 +698,9 @@ impl BoardScreen {
     }
 
     /// View a thread and its posts.
+    ///
+    /// Posts are listed newest first, or as a reply tree (oldest first,
+    /// replies indented under the post they answer) after `[T]`.
     async fn run_thread_view(
         ctx: &mut ScreenContext,
         session: &mut TelnetSession,
@@ -444,41 +708,68 @@ impl BoardScreen {
     ) -> Result<ScreenResult> {
         let per_page: i64 = 10;
         let mut pagination = Pagination::new(1, per_page as usize, 0);
+        let mut tree_view = false;
 
         loop {
             // Get thread info
             let user_role = Self::get_user_role(ctx, session).await;
 
             // Get thread and board info, then check disable_paging
-            let (thread, disable_paging) = {
+            let (thread, disable_paging, allow_color) = {
                 let board_service = BoardService::new(&ctx.db);
                 let thread = board_service.get_thread(thread_id, user_role).await?;
                 let board = board_service.get_board(thread.board_id, user_role).await?;
-                (thread, board.disable_paging)
+                (thread, board.disable_paging, board.allow_color)
             };
             let original_paging = ctx.auto_paging_enabled();
             if disable_paging {
                 ctx.set_auto_paging(false);
             }
 
-            // Get posts using service with pagination
-            let board_pagination =
-                BoardPagination::new(pagination.offset() as i64, pagination.per_page as i64);
+            // Get posts (with their tree depth) for the current page
             let board_service = BoardService::new(&ctx.db);
-            let result =
-                board_service.list_posts_in_thread(thread_id, user_role, board_pagination).await?;
-
-            pagination.total = result.total as usize;
+            let kill_file = ctx.kill_file(session).await?;
+            let items: Vec<ThreadedPost> = if tree_view {
+                let tree = board_service
+                    .list_visible_thread_tree(thread_id, user_role, &kill_file)
+                    .await?;
+                pagination.total = tree.len();
+                tree.into_iter()
+                    .skip(pagination.offset())
+                    .take(pagination.per_page)
+                    .collect()
+            } else {
+                let board_pagination =
+                    BoardPagination::new(pagination.offset() as i64, pagination.per_page as i64);
+                let result = board_service
+                    .list_visible_posts_in_thread(
+                        thread_id,
+                        user_role,
+                        board_pagination,
+                        &kill_file,
+                    )
+                    .await?;
+                pagination.total = result.total as usize;
+                result
+                    .items
+                    .into_iter()
+                    .map(|post| ThreadedPost { post, depth: 0 })
+                    .collect()
+            };
 
             // Display thread using template
             let mut context = ctx.create_context();
             context.set("thread_title", Value::string(thread.title.clone()));
-            context.set("has_posts", Value::bool(!result.items.is_empty()));
+            context.set("locked", Value::bool(thread.is_locked));
+            context.set("has_posts", Value::bool(!items.is_empty()));
 
-            if !result.items.is_empty() {
+            if !items.is_empty() {
                 let user_repo = UserRepository::new(ctx.db.pool());
+                let post_repo = PostRepository::new(ctx.db.pool());
+                let level = if ctx.profile.width >= 80 { "  " } else { " " };
                 let mut post_list = Vec::new();
-                for post in &result.items {
+                for (i, item) in items.iter().enumerate() {
+                    let post = &item.post;
                     let author = user_repo
                         .get_by_id(post.author_id)
                         .await?
@@ -491,19 +782,46 @@ impl BoardScreen {
                         "%Y-%m-%d %H:%M",
                     );
 
+                    // In the flat list, name the author of the post being answered
+                    let mut reply_to = String::new();
+                    if let (false, Some(parent_id)) = (tree_view, post.parent_id) {
+                        if let Some(parent) = post_repo.get_by_id(parent_id).await? {
+                            if let Some(user) = user_repo.get_by_id(parent.author_id).await? {
+                                reply_to = ctx
+                                    .i18n
+                                    .t_with("board.reply_to", &[("author", &user.nickname)]);
+                            }
+                        }
+                    }
+
+                    let indent = level.repeat(item.depth.min(MAX_REPLY_DEPTH));
+                    let body = ctx.user_content(session, &post.body, allow_color).await;
+                    let body = body
+                        .lines()
+                        .map(|line| format!("{indent}{line}"))
+                        .collect::<Vec<_>>()
+                        .join("\n");
+
                     let mut entry = std::collections::HashMap::new();
+                    entry.insert(
+                        "number".to_string(),
+                        Value::string((pagination.offset() + i + 1).to_string()),
+                    );
+                    entry.insert("indent".to_string(), Value::string(indent));
                     entry.insert("author".to_string(), Value::string(author));
                     entry.insert("created_at".to_string(), Value::string(formatted_time));
-                    entry.insert("body".to_string(), Value::string(convert_caret_escape(&post.body)));
+                    entry.insert("reply_to".to_string(), Value::string(reply_to));
+                    entry.insert("edited".to_string(), Value::bool(post.is_edited()));
+                    entry.insert("body".to_string(), Value::string(body));
                     post_list.push(Value::Object(entry));
                 }
                 context.set("posts", Value::List(post_list));
 
                 // Mark the last displayed post as read for logged-in users
                 if let Some(user_id) = session.user_id() {
-                    if let Some(last_post) = result.items.last() {
+                    if let Some(last_post) = items.last() {
                         let unread_repo = UnreadRepository::new(ctx.db.pool());
-                        unread_repo.mark_as_read(user_id, thread.board_id, last_post.id).await?;
+                        unread_repo.mark_as_read(user_id, thread.board_id, last_post.post.id).await?;
                     }
                 }
             }
@@ -526,13 +844,40 @@ impl BoardScreen {
             ctx.set_auto_paging(original_paging);
 
             // Prompt
+            let view_toggle = if tree_view { "board.list_view" } else { "board.tree_view" };
+            let member_actions = if session.user_id().is_some() {
+                format!(
+                    " [H]={} [S]={} [K]={} [X]={}",
+                    ctx.i18n.t("board.history"),
+                    ctx.i18n.t("notify.subscribe"),
+                    ctx.i18n.t("board.bookmark"),
+                    ctx.show_hidden_label(session)
+                )
+            } else {
+                String::new()
+            };
+            // SubOp+ may pin and lock the thread
+            let is_moderator = user_role >= Role::SubOp;
+            let moderation = if is_moderator {
+                format!(
+                    " [F]={} [L]={}",
+                    ctx.i18n.t(if thread.is_pinned { "board.unpin" } else { "board.pin" }),
+                    ctx.i18n.t(if thread.is_locked { "board.unlock" } else { "board.lock" })
+                )
+            } else {
+                String::new()
+            };
             ctx.send(
                 session,
                 &format!(
-                    "[N]={} [P]={} [R]={} [Q]={}: ",
+                    "[N]={} [P]={} [R]={} [T]={} [V]={}{}{} [Q]={}: ",
                     ctx.i18n.t("common.next"),
                     ctx.i18n.t("common.previous"),
                     ctx.i18n.t("board.reply"),
+                    ctx.i18n.t(view_toggle),
+                    ctx.i18n.t("poll.thread_poll"),
+                    member_actions,
+                    moderation,
                     ctx.i18n.t("common.back")
                 ),
             )
@@ -545,14 +890,80 @@ impl BoardScreen {
                 "q" | "" => return Ok(ScreenResult::Back),
                 "n" => pagination.next(),
                 "p" => pagination.prev(),
+                "t" => {
+                    tree_view = !tree_view;
+                    pagination.page = 1;
+                }
+                "r" if thread.is_locked => {
+                    ctx.send_line(session, ctx.i18n.t("board.locked")).await?;
+                }
                 "r" => {
                     if session.user_id().is_some() {
-                        Self::create_reply(ctx, session, thread_id).await?;
+                        // Pick the post being answered by its number on this page
+                        ctx.send(session, ctx.i18n.t("board.reply_to_prompt")).await?;
+                        let target = ctx.read_line(session).await?;
+                        let parent = ctx.parse_number(&target).and_then(|num| {
+                            let idx = num - 1 - pagination.offset() as i64;
+                            usize::try_from(idx).ok().and_then(|idx| items.get(idx))
+                        });
+                        let parent = parent.map(|item| &item.post);
+                        Self::create_reply(ctx, session, thread_id, parent).await?;
                     } else {
                         ctx.send_line(session, ctx.i18n.t("menu.login_required"))
                             .await?;
                     }
                 }
+                "v" => {
+                    PollScreen::run_thread_poll(ctx, session, thread_id, thread.author_id).await?;
+                }
+                "h" if session.user_id().is_some() => {
+                    ctx.send(session, ctx.i18n.t("board.history_prompt")).await?;
+                    let target = ctx.read_line(session).await?;
+                    let target = ctx.parse_number(&target).and_then(|num| {
+                        let idx = num - 1 - pagination.offset() as i64;
+                        usize::try_from(idx).ok().and_then(|idx| items.get(idx))
+                    });
+                    if let Some(item) = target {
+                        Self::run_history(ctx, session, item.post.id).await?;
+                    }
+                }
+                "s" if session.user_id().is_some() => {
+                    Self::run_subscription(ctx, session, SubscriptionTarget::Thread(thread_id))
+                        .await?;
+                }
+                "k" if session.user_id().is_some() => {
+                    ctx.send(session, ctx.i18n.t("board.bookmark_prompt"))
+                        .await?;
+                    let target = ctx.read_line(session).await?;
+                    let target = if target.trim().is_empty() {
+                        Some(BookmarkTarget::Thread(thread_id))
+                    } else {
+                        ctx.parse_number(&target).and_then(|num| {
+                            let idx = num - 1 - pagination.offset() as i64;
+                            usize::try_from(idx)
+                                .ok()
+                                .and_then(|idx| items.get(idx))
+                                .map(|item| BookmarkTarget::Post(item.post.id))
+                        })
+                    };
+                    if let Some(target) = target {
+                        Self::add_bookmark(ctx, session, target).await?;
+                    }
+                }
+                "x" if session.user_id().is_some() => {
+                    session.set_show_hidden(!session.show_hidden());
+                    pagination.page = 1;
+                }
+                "f" if is_moderator => {
+                    BoardService::new(&ctx.db)
+                        .set_thread_flags(thread_id, user_role, Some(!thread.is_pinned), None)
+                        .await?;
+                }
+                "l" if is_moderator => {
+                    BoardService::new(&ctx.db)
+                        .set_thread_flags(thread_id, user_role, None, Some(!thread.is_locked))
+                        .await?;
+                }
                 _ => {}
             }
         }
@@ -592,7 +1003,9 @@ impl BoardScreen {
         context.set("title", Value::string(post.title.as_deref().unwrap_or("(no title)").to_string()));
         context.set("author", Value::string(author));
         context.set("created_at", Value::string(formatted_time));
-        context.set("body", Value::string(convert_caret_escape(&post.body)));
+        context.set("edited", Value::bool(post.is_edited()));
+        let body = ctx.user_content(session, &post.body, board.allow_color).await;
+        context.set("body", Value::string(body));
 
         let content = ctx.render_template("board/single_post", &context)?;
         ctx.send(session, &content).await?;
@@ -603,6 +1016,37 @@ impl BoardScreen {
             unread_repo.mark_as_read(user_id, post.board_id, post_id).await?;
         }
 
+        // The author and SubOp+ may look at the edit history
+        let can_view_history = post.is_edited()
+            && (session.user_id() == Some(post.author_id) || user_role >= Role::SubOp);
+        let can_bookmark = session.user_id().is_some();
+        if can_view_history || can_bookmark {
+            let mut actions = String::new();
+            if can_view_history {
+                actions.push_str(&format!("[H]={} ", ctx.i18n.t("board.history")));
+            }
+            if can_bookmark {
+                actions.push_str(&format!("[K]={} ", ctx.i18n.t("board.bookmark")));
+            }
+            ctx.send(
+                session,
+                &format!("{}[Enter]={}: ", actions, ctx.i18n.t("common.back")),
+            )
+            .await?;
+            let input = ctx.read_line(session).await?;
+            ctx.set_auto_paging(original_paging);
+            match input.trim().to_ascii_lowercase().as_str() {
+                "h" if can_view_history => {
+                    Self::run_history(ctx, session, post_id).await?;
+                }
+                "k" if can_bookmark => {
+                    Self::add_bookmark(ctx, session, BookmarkTarget::Post(post_id)).await?;
+                }
+                _ => {}
+            }
+            return Ok(ScreenResult::Back);
+        }
+
         ctx.wait_for_enter(session).await?;
 
         // Restore auto-paging setting
@@ -611,31 +1055,154 @@ impl BoardScreen {
         Ok(ScreenResult::Back)
     }
 
-    /// Create a new thread.
-    async fn create_thread(
+    /// Show the edit history of a post and the changes made in each version.
+    async fn run_history(
         ctx: &mut ScreenContext,
         session: &mut TelnetSession,
-        board_id: i64,
-    ) -> Result<()> {
-        let user_id = match session.user_id() {
-            Some(id) => id,
-            None => return Ok(()),
+        post_id: i64,
+    ) -> Result<ScreenResult> {
+        let user_role = Self::get_user_role(ctx, session).await;
+        let board_service = BoardService::new(&ctx.db);
+        let versions = match board_service
+            .post_history(post_id, session.user_id(), user_role)
+            .await
+        {
+            Ok(versions) => versions,
+            Err(HobbsError::Permission(_)) => {
+                ctx.send_line(session, ctx.i18n.t("common.permission_denied"))
+                    .await?;
+                return Ok(ScreenResult::Back);
+            }
+            Err(e) => return Err(e),
         };
 
-        // Check rate limit
-        match ctx.rate_limiters.post.check(user_id) {
-            RateLimitResult::Denied { retry_after } => {
-                let msg = ctx.i18n.t_with(
-                    "rate_limit.post_denied",
-                    &[("seconds", &retry_after.as_secs().to_string())],
-                );
-                ctx.send_line(session, &msg).await?;
-                return Ok(());
-            }
-            RateLimitResult::Allowed => {}
+        let user_repo = UserRepository::new(ctx.db.pool());
+        let mut editors = Vec::with_capacity(versions.len());
+        for version in &versions {
+            let editor = match version.editor_id {
+                Some(id) => user_repo.get_by_id(id).await?.map(|u| u.nickname),
+                None => None,
+            };
+            editors.push(editor.unwrap_or_else(|| "Unknown".to_string()));
         }
 
-        ctx.send_line(session, "").await?;
+        loop {
+            let mut context = ctx.create_context();
+            let mut version_list = Vec::new();
+            for (version, editor) in versions.iter().zip(&editors) {
+                let formatted_time = format_datetime(
+                    &version.created_at,
+                    &ctx.config.server.timezone,
+                    "%Y-%m-%d %H:%M",
+                );
+                let excerpt = strip_color_codes(&version.body)
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                let mut entry = std::collections::HashMap::new();
+                entry.insert("number".to_string(), Value::string(version.number.to_string()));
+                entry.insert("editor".to_string(), Value::string(editor));
+                entry.insert("date".to_string(), Value::string(formatted_time));
+                entry.insert("current".to_string(), Value::bool(version.current));
+                entry.insert("excerpt".to_string(), Value::string(excerpt));
+                version_list.push(Value::Object(entry));
+            }
+            context.set("versions", Value::List(version_list));
+
+            let content = ctx.render_template("board/history", &context)?;
+            ctx.send(session, &content).await?;
+
+            ctx.send(
+                session,
+                &format!(
+                    "{} [Q]={}: ",
+                    ctx.i18n.t("menu.select_prompt"),
+                    ctx.i18n.t("common.back")
+                ),
+            )
+            .await?;
+            let input = ctx.read_line(session).await?;
+            let input = input.trim();
+            if input.is_empty() || input.eq_ignore_ascii_case("q") {
+                return Ok(ScreenResult::Back);
+            }
+
+            let Some(idx) = ctx
+                .parse_number(input)
+                .and_then(|num| usize::try_from(num - 1).ok())
+                .filter(|&idx| idx < versions.len())
+            else {
+                continue;
+            };
+            let version = &versions[idx];
+
+            let mut context = ctx.create_context();
+            context.set("number", Value::string(version.number.to_string()));
+            context.set("current", Value::bool(version.current));
+            context.set("editor", Value::string(&editors[idx]));
+            context.set(
+                "date",
+                Value::string(format_datetime(
+                    &version.created_at,
+                    &ctx.config.server.timezone,
+                    "%Y-%m-%d %H:%M",
+                )),
+            );
+            context.set("body", Value::string(strip_color_codes(&version.body)));
+
+            let diff = match idx.checked_sub(1) {
+                Some(prev) => diff_lines(
+                    &strip_color_codes(&versions[prev].body),
+                    &strip_color_codes(&version.body),
+                ),
+                None => Vec::new(),
+            };
+            context.set("has_diff", Value::bool(!diff.is_empty()));
+            let diff_list = diff
+                .iter()
+                .map(|line| {
+                    let mut entry = std::collections::HashMap::new();
+                    entry.insert(
+                        "line".to_string(),
+                        Value::string(format!("{} {}", line.prefix(), line.text())),
+                    );
+                    Value::Object(entry)
+                })
+                .collect();
+            context.set("diff", Value::List(diff_list));
+
+            let content = ctx.render_template("board/revision", &context)?;
+            ctx.send(session, &content).await?;
+            ctx.wait_for_enter(session).await?;
+        }
+    }
+
+    /// Create a new thread.
+    async fn create_thread(
+        ctx: &mut ScreenContext,
+        session: &mut TelnetSession,
+        board_id: i64,
+    ) -> Result<()> {
+        let user_id = match session.user_id() {
+            Some(id) => id,
+            None => return Ok(()),
+        };
+
+        // Check rate limit
+        match ctx.rate_limiters.post.check(user_id) {
+            RateLimitResult::Denied { retry_after } => {
+                let msg = ctx.i18n.t_with(
+                    "rate_limit.post_denied",
+                    &[("seconds", &retry_after.as_secs().to_string())],
+                );
+                ctx.send_line(session, &msg).await?;
+                return Ok(());
+            }
+            RateLimitResult::Allowed => {}
+        }
+
+        ctx.send_line(session, "").await?;
         ctx.send_line(
             session,
             &format!("=== {} ===", ctx.i18n.t("board.new_thread")),
@@ -654,8 +1221,52 @@ impl BoardScreen {
 
         // Create thread using BoardService
         let user_role = Self::get_user_role(ctx, session).await;
-        let board_service = BoardService::new(&ctx.db);
+        let moderated = BoardService::new(&ctx.db)
+            .get_board(board_id, user_role)
+            .await
+            .is_ok_and(|board| board.requires_approval(user_role));
+
+        // On a moderated board the thread is queued with its first post
+        if moderated {
+            let Some(body) = Self::read_thread_body(ctx, session).await? else {
+                return Ok(());
+            };
+            let result = BoardService::new(&ctx.db)
+                .submit_thread(board_id, title, user_id, &body, user_role)
+                .await;
+            return Self::report_submission(ctx, session, user_id, result).await;
+        }
 
+        // Only the title can be filtered yet; a held title sends the thread
+        // to the queue with its first post
+        let mut texts = [title.to_string()];
+        let verdict = BoardService::new(&ctx.db)
+            .filter_post(user_id, &mut texts)
+            .await;
+        let [title] = texts;
+        match verdict {
+            Err(e) => return Self::report_submission(ctx, session, user_id, Err(e)).await,
+            Ok(verdict) if verdict.is_hold() => {
+                let Some(body) = Self::read_thread_body(ctx, session).await? else {
+                    return Ok(());
+                };
+                let board_service = BoardService::new(&ctx.db);
+                let mut texts = [body];
+                let result = match board_service.filter_post(user_id, &mut texts).await {
+                    Ok(_) => {
+                        let [body] = texts;
+                        board_service
+                            .queue_thread(board_id, title, user_id, body, user_role)
+                            .await
+                    }
+                    Err(e) => Err(e),
+                };
+                return Self::report_submission(ctx, session, user_id, result).await;
+            }
+            Ok(_) => {}
+        }
+
+        let board_service = BoardService::new(&ctx.db);
         match board_service.create_thread(board_id, title, user_id, user_role).await {
             Ok(_) => {
                 // Record successful action for rate limiting
@@ -673,11 +1284,119 @@ impl BoardScreen {
         Ok(())
     }
 
-    /// Create a reply to a thread.
+    /// Tell the user how a submitted post was handled.
+    /// Show and change the user's subscription to a board or thread.
+    async fn run_subscription(
+        ctx: &mut ScreenContext,
+        session: &mut TelnetSession,
+        target: SubscriptionTarget,
+    ) -> Result<()> {
+        let Some(user_id) = session.user_id() else {
+            return Ok(());
+        };
+        let user_role = Self::get_user_role(ctx, session).await;
+        let service = NotificationService::new(&ctx.db);
+        let current = service.find_subscription(user_id, target).await?;
+
+        let status = match &current {
+            None => ctx.i18n.t("notify.not_subscribed"),
+            Some(s) if s.digest => ctx.i18n.t("notify.digest"),
+            Some(_) => ctx.i18n.t("notify.immediate"),
+        };
+        ctx.send_line(session, &ctx.i18n.t_with("notify.status", &[("status", status)]))
+            .await?;
+        ctx.send(
+            session,
+            &format!(
+                "[1]={} [2]={} [3]={} [Q]={}: ",
+                ctx.i18n.t("notify.immediate"),
+                ctx.i18n.t("notify.digest"),
+                ctx.i18n.t("notify.unsubscribe"),
+                ctx.i18n.t("common.back")
+            ),
+        )
+        .await?;
+
+        let input = ctx.read_line(session).await?;
+        let service = NotificationService::new(&ctx.db);
+        let message = match input.trim() {
+            choice @ ("1" | "2") => {
+                service
+                    .subscribe(user_id, user_role, target, choice == "2")
+                    .await?;
+                "notify.subscribed"
+            }
+            "3" => match current {
+                Some(subscription) => {
+                    service.unsubscribe(user_id, subscription.id).await?;
+                    "notify.unsubscribed"
+                }
+                None => return Ok(()),
+            },
+            _ => return Ok(()),
+        };
+        ctx.send_line(session, ctx.i18n.t(message)).await
+    }
+
+    /// Ask for the first post of a new thread. Returns None if cancelled.
+    async fn read_thread_body(
+        ctx: &mut ScreenContext,
+        session: &mut TelnetSession,
+    ) -> Result<Option<String>> {
+        ctx.send_line(
+            session,
+            &format!(
+                "{} ({}): ",
+                ctx.i18n.t("board.body"),
+                ctx.i18n.t("common.end_with_dot")
+            ),
+        )
+        .await?;
+        Ok(ctx
+            .read_multiline(session)
+            .await?
+            .filter(|text| !text.trim().is_empty()))
+    }
+
+    async fn report_submission(
+        ctx: &mut ScreenContext,
+        session: &mut TelnetSession,
+        user_id: i64,
+        result: Result<Submission>,
+    ) -> Result<()> {
+        match result {
+            Ok(submission) => {
+                // Record successful action for rate limiting
+                ctx.rate_limiters.post.record(user_id);
+                let message = if submission.is_pending() {
+                    "board.post_pending"
+                } else {
+                    "board.post_created"
+                };
+                ctx.send_line(session, ctx.i18n.t(message)).await?;
+            }
+            Err(e) if is_rejection(&e) => {
+                ctx.send_line(session, ctx.i18n.t("filter.rejected"))
+                    .await?;
+            }
+            Err(e) => {
+                error!("Failed to create post: {}", e);
+                ctx.send_line(session, ctx.i18n.t("common.operation_failed"))
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Create a reply to a thread, or to `parent` within it.
+    ///
+    /// Replying to a post pre-fills the body with a quote of it.
     async fn create_reply(
         ctx: &mut ScreenContext,
         session: &mut TelnetSession,
         thread_id: i64,
+        parent: Option<&Post>,
     ) -> Result<()> {
         let user_id = match session.user_id() {
             Some(id) => id,
@@ -711,34 +1430,31 @@ impl BoardScreen {
             ),
         )
         .await?;
-        let body = match ctx.read_multiline(session).await? {
+        let quote = match parent {
+            Some(post) => {
+                ctx.send_line(session, ctx.i18n.t("board.quote_hint")).await?;
+                quote_body(&strip_color_codes(&post.body), QUOTE_LINES)
+            }
+            None => String::new(),
+        };
+        let body = match ctx.read_multiline_with(session, &quote).await? {
             Some(text) => text,
             None => return Ok(()), // Cancelled
         };
 
-        if body.is_empty() {
+        if body.trim().is_empty() || body == quote {
             return Ok(());
         }
 
         // Create post using BoardService
         let user_role = Self::get_user_role(ctx, session).await;
         let board_service = BoardService::new(&ctx.db);
+        let parent_id = parent.map(|post| post.id);
 
-        match board_service.create_thread_post(thread_id, user_id, &body, user_role).await {
-            Ok(_) => {
-                // Record successful action for rate limiting
-                ctx.rate_limiters.post.record(user_id);
-                ctx.send_line(session, ctx.i18n.t("board.post_created"))
-                    .await?;
-            }
-            Err(e) => {
-                error!("Failed to create post: {}", e);
-                ctx.send_line(session, ctx.i18n.t("common.operation_failed"))
-                    .await?;
-            }
-        }
-
-        Ok(())
+        let result = board_service
+            .submit_reply(thread_id, parent_id, user_id, &body, user_role)
+            .await;
+        Self::report_submission(ctx, session, user_id, result).await
     }
 
     /// Create a post in a flat board.
@@ -801,21 +1517,10 @@ impl BoardScreen {
         let user_role = Self::get_user_role(ctx, session).await;
         let board_service = BoardService::new(&ctx.db);
 
-        match board_service.create_flat_post(board_id, user_id, &title, &body, user_role).await {
-            Ok(_) => {
-                // Record successful action for rate limiting
-                ctx.rate_limiters.post.record(user_id);
-                ctx.send_line(session, ctx.i18n.t("board.post_created"))
-                    .await?;
-            }
-            Err(e) => {
-                error!("Failed to create post: {}", e);
-                ctx.send_line(session, ctx.i18n.t("common.operation_failed"))
-                    .await?;
-            }
-        }
-
-        Ok(())
+        let result = board_service
+            .submit_flat_post(board_id, user_id, &title, &body, user_role)
+            .await;
+        Self::report_submission(ctx, session, user_id, result).await
     }
 
     /// Run unread batch read for a board.
@@ -832,8 +1537,9 @@ impl BoardScreen {
         };
 
         // Get unread posts (collect into Vec to release the borrow)
+        let kill_file = ctx.kill_file(session).await?;
         let unread_posts = {
-            let unread_repo = UnreadRepository::new(ctx.db.pool());
+            let unread_repo = UnreadRepository::new(ctx.db.pool()).with_kill_file(&kill_file);
             unread_repo.get_unread_posts(user_id, board_id).await?
         };
 
@@ -899,7 +1605,8 @@ impl BoardScreen {
             context.set("title", Value::string(title));
             context.set("author", Value::string(author));
             context.set("created_at", Value::string(formatted_time));
-            context.set("body", Value::string(convert_caret_escape(&post.body)));
+            let body = ctx.user_content(session, &post.body, board.allow_color).await;
+            context.set("body", Value::string(body));
 
             let content = ctx.render_template("board/unread_post", &context)?;
             ctx.send(session, &content).await?;
@@ -998,11 +1705,12 @@ impl BoardScreen {
         Ok(())
     }
 
-    /// Run all unread batch read across all boards.
+    /// Run all unread batch read across the user's newscan boards.
     ///
-    /// Displays unread posts from all boards one by one, marking each as read after display.
+    /// Displays unread posts from the boards included in the user's newscan,
+    /// in the user's order, one by one, marking each as read after display.
     /// Shows the board name for each post.
-    async fn run_all_unread_batch_read(
+    pub async fn run_all_unread_batch_read(
         ctx: &mut ScreenContext,
         session: &mut TelnetSession,
     ) -> Result<ScreenResult> {
@@ -1013,11 +1721,11 @@ impl BoardScreen {
 
         let user_role = Self::get_user_role(ctx, session).await;
 
-        // Get all unread posts across all boards (collect into Vec to release the borrow)
-        let unread_posts: Vec<UnreadPostWithBoard> = {
-            let unread_repo = UnreadRepository::new(ctx.db.pool());
-            unread_repo.get_all_unread_posts(user_id, user_role).await?
-        };
+        // Get unread posts of the newscan boards (collect into Vec to release the borrow)
+        let kill_file = ctx.kill_file(session).await?;
+        let unread_posts: Vec<UnreadPostWithBoard> = NewscanService::new(&ctx.db)
+            .unread_posts(user_id, user_role, &kill_file)
+            .await?;
 
         if unread_posts.is_empty() {
             ctx.send_line(session, "").await?;
@@ -1037,27 +1745,28 @@ impl BoardScreen {
         .await?;
         ctx.send_line(session, "").await?;
 
-        // Cache board_id -> disable_paging to avoid repeated DB lookups
-        let mut disable_paging_cache: std::collections::HashMap<i64, bool> =
+        // Cache board_id -> (disable_paging, allow_color) to avoid repeated DB lookups
+        let mut board_settings_cache: std::collections::HashMap<i64, (bool, bool)> =
             std::collections::HashMap::new();
         let original_paging = ctx.auto_paging_enabled();
 
         for (index, unread_post) in unread_posts.iter().enumerate() {
             let post = &unread_post.post;
 
-            // Check disable_paging for this post's board (with cache)
-            let disable_paging = if let Some(&cached) = disable_paging_cache.get(&post.board_id) {
-                cached
-            } else {
-                let board_repo = BoardRepository::new(ctx.db.pool());
-                let dp = board_repo
-                    .get_by_id(post.board_id)
-                    .await?
-                    .map(|b| b.disable_paging)
-                    .unwrap_or(false);
-                disable_paging_cache.insert(post.board_id, dp);
-                dp
-            };
+            // Check disable_paging and allow_color for this post's board (with cache)
+            let (disable_paging, allow_color) =
+                if let Some(&cached) = board_settings_cache.get(&post.board_id) {
+                    cached
+                } else {
+                    let board_repo = BoardRepository::new(ctx.db.pool());
+                    let settings = board_repo
+                        .get_by_id(post.board_id)
+                        .await?
+                        .map(|b| (b.disable_paging, b.allow_color))
+                        .unwrap_or((false, true));
+                    board_settings_cache.insert(post.board_id, settings);
+                    settings
+                };
 
             if disable_paging {
                 ctx.set_auto_paging(false);
@@ -1100,7 +1809,8 @@ impl BoardScreen {
             context.set("title", Value::string(title));
             context.set("author", Value::string(author));
             context.set("created_at", Value::string(formatted_time));
-            context.set("body", Value::string(convert_caret_escape(&post.body)));
+            let body = ctx.user_content(session, &post.body, allow_color).await;
+            context.set("body", Value::string(body));
 
             let content = ctx.render_template("board/unread_post", &context)?;
             ctx.send(session, &content).await?;
@@ -1150,6 +1860,300 @@ impl BoardScreen {
         Ok(ScreenResult::Back)
     }
 
+    /// Bookmark a thread or post, asking for a note.
+    async fn add_bookmark(
+        ctx: &mut ScreenContext,
+        session: &mut TelnetSession,
+        target: BookmarkTarget,
+    ) -> Result<()> {
+        let Some(user_id) = session.user_id() else {
+            return Ok(());
+        };
+        let user_role = Self::get_user_role(ctx, session).await;
+
+        ctx.send(session, ctx.i18n.t("board.bookmark_note_prompt"))
+            .await?;
+        let note = ctx.read_line(session).await?;
+        let service = BookmarkService::new(&ctx.db);
+        match service
+            .add(user_id, user_role, target, Some(note.trim()))
+            .await
+        {
+            Ok(_) => {
+                ctx.send_line(session, ctx.i18n.t("board.bookmark_added"))
+                    .await
+            }
+            Err(HobbsError::Validation(msg)) => ctx.send_line(session, &msg).await,
+            Err(e) => Err(e),
+        }
+    }
+
+    /// List the user's bookmarks and open, annotate or remove them.
+    async fn run_bookmarks(ctx: &mut ScreenContext, session: &mut TelnetSession) -> Result<()> {
+        let Some(user_id) = session.user_id() else {
+            return Ok(());
+        };
+
+        loop {
+            let user_role = Self::get_user_role(ctx, session).await;
+            let bookmarks = BookmarkService::new(&ctx.db)
+                .list(user_id, user_role)
+                .await?;
+
+            let mut context = ctx.create_context();
+            context.set("has_bookmarks", Value::bool(!bookmarks.is_empty()));
+            let mut bookmark_list = Vec::new();
+            for (i, bookmark) in bookmarks.iter().enumerate() {
+                let board = match &bookmark.board_name {
+                    Some(name) => name.clone(),
+                    None => ctx.i18n.t("board.bookmark_deleted").to_string(),
+                };
+                let mut entry = std::collections::HashMap::new();
+                entry.insert("number".to_string(), Value::string((i + 1).to_string()));
+                entry.insert("title".to_string(), Value::string(&bookmark.title));
+                entry.insert("board".to_string(), Value::string(board));
+                entry.insert(
+                    "note".to_string(),
+                    Value::string(bookmark.note.clone().unwrap_or_default()),
+                );
+                bookmark_list.push(Value::Object(entry));
+            }
+            context.set("bookmarks", Value::List(bookmark_list));
+
+            let content = ctx.render_template("board/bookmarks", &context)?;
+            ctx.send(session, &content).await?;
+
+            ctx.send(
+                session,
+                &format!(
+                    "[{}] [E]={} [D]={} [Q]={}: ",
+                    ctx.i18n.t("common.number"),
+                    ctx.i18n.t("board.bookmark_edit_note"),
+                    ctx.i18n.t("board.bookmark_remove"),
+                    ctx.i18n.t("common.back")
+                ),
+            )
+            .await?;
+            let input = ctx.read_line(session).await?;
+            let input = input.trim();
+
+            let command = input.to_ascii_lowercase();
+            if command == "q" || command.is_empty() {
+                return Ok(());
+            }
+            let selected = if command == "e" || command == "d" {
+                ctx.send(session, &format!("{}: ", ctx.i18n.t("common.number")))
+                    .await?;
+                let number = ctx.read_line(session).await?;
+                ctx.parse_number(number.trim())
+            } else {
+                ctx.parse_number(input)
+            };
+            let Some(bookmark) = selected
+                .and_then(|n| usize::try_from(n - 1).ok())
+                .and_then(|i| bookmarks.get(i))
+            else {
+                continue;
+            };
+
+            match command.as_str() {
+                "e" => {
+                    ctx.send(session, ctx.i18n.t("board.bookmark_note_prompt"))
+                        .await?;
+                    let note = ctx.read_line(session).await?;
+                    let result = BookmarkService::new(&ctx.db)
+                        .set_note(user_id, user_role, bookmark.id, Some(note.trim()))
+                        .await;
+                    if let Err(HobbsError::Validation(msg)) = &result {
+                        ctx.send_line(session, msg).await?;
+                    } else {
+                        result?;
+                    }
+                }
+                "d" => {
+                    BookmarkService::new(&ctx.db)
+                        .remove(user_id, bookmark.id)
+                        .await?;
+                    ctx.send_line(session, ctx.i18n.t("board.bookmark_removed"))
+                        .await?;
+                }
+                _ if bookmark.is_deleted() => {
+                    ctx.send_line(session, ctx.i18n.t("board.bookmark_gone"))
+                        .await?;
+                }
+                _ => match bookmark.target() {
+                    BookmarkTarget::Thread(thread_id) => {
+                        Self::run_thread_view(ctx, session, thread_id).await?;
+                    }
+                    BookmarkTarget::Post(post_id) => {
+                        Self::run_post_view(ctx, session, post_id).await?;
+                    }
+                },
+            }
+        }
+    }
+
+    /// Configure which boards the newscan reads and in what order.
+    async fn run_newscan_settings(
+        ctx: &mut ScreenContext,
+        session: &mut TelnetSession,
+    ) -> Result<()> {
+        let user_id = match session.user_id() {
+            Some(id) => id,
+            None => return Ok(()),
+        };
+
+        loop {
+            let user_role = Self::get_user_role(ctx, session).await;
+            let (boards, scan_on_login) = {
+                let service = NewscanService::new(&ctx.db);
+                (
+                    service.boards(user_id, user_role).await?,
+                    service.scan_on_login(user_id).await?,
+                )
+            };
+
+            let mut context = ctx.create_context();
+            let state = if scan_on_login {
+                ctx.i18n.t("common.yes")
+            } else {
+                ctx.i18n.t("common.no")
+            };
+            context.set(
+                "on_login",
+                Value::string(
+                    ctx.i18n
+                        .t_with("board.newscan_on_login", &[("state", state)]),
+                ),
+            );
+            context.set("has_boards", Value::bool(!boards.is_empty()));
+            let mut board_list = Vec::new();
+            for (i, entry) in boards.iter().enumerate() {
+                let mut item = std::collections::HashMap::new();
+                item.insert("number".to_string(), Value::string((i + 1).to_string()));
+                item.insert(
+                    "scan".to_string(),
+                    Value::string(if entry.included { "[*]" } else { "[ ]" }),
+                );
+                item.insert("name".to_string(), Value::string(&entry.board.name));
+                board_list.push(Value::Object(item));
+            }
+            context.set("boards", Value::List(board_list));
+
+            let content = ctx.render_template("board/newscan", &context)?;
+            ctx.send(session, &content).await?;
+
+            ctx.send(
+                session,
+                &format!(
+                    "[{}]={} [M]={} [L]={} [R]={} [Q]={}: ",
+                    ctx.i18n.t("common.number"),
+                    ctx.i18n.t("board.newscan_toggle"),
+                    ctx.i18n.t("board.newscan_move"),
+                    ctx.i18n.t("board.newscan_toggle_login"),
+                    ctx.i18n.t("board.newscan_reset"),
+                    ctx.i18n.t("common.back")
+                ),
+            )
+            .await?;
+            let input = ctx.read_line(session).await?;
+            let input = input.trim();
+
+            let result = match input.to_ascii_lowercase().as_str() {
+                "q" | "" => return Ok(()),
+                "l" => {
+                    NewscanService::new(&ctx.db)
+                        .set_scan_on_login(user_id, !scan_on_login)
+                        .await
+                }
+                "m" => {
+                    ctx.send(session, &format!("{}: ", ctx.i18n.t("common.number")))
+                        .await?;
+                    let from = ctx.read_line(session).await?;
+                    let entry = ctx
+                        .parse_number(from.trim())
+                        .and_then(|n| usize::try_from(n - 1).ok())
+                        .and_then(|i| boards.get(i));
+                    let Some(entry) = entry else {
+                        continue;
+                    };
+                    ctx.send(session, ctx.i18n.t("board.newscan_move_to"))
+                        .await?;
+                    let to = ctx.read_line(session).await?;
+                    let Some(position) = ctx
+                        .parse_number(to.trim())
+                        .and_then(|n| usize::try_from(n - 1).ok())
+                    else {
+                        continue;
+                    };
+                    NewscanService::new(&ctx.db)
+                        .move_board(user_id, user_role, entry.board.id, position)
+                        .await
+                }
+                "r" => {
+                    Self::reset_newscan_pointers(ctx, session, user_id, user_role).await?;
+                    continue;
+                }
+                _ => {
+                    let entry = ctx
+                        .parse_number(input)
+                        .and_then(|n| usize::try_from(n - 1).ok())
+                        .and_then(|i| boards.get(i));
+                    let Some(entry) = entry else {
+                        continue;
+                    };
+                    NewscanService::new(&ctx.db)
+                        .set_included(user_id, user_role, entry.board.id, !entry.included)
+                        .await
+                }
+            };
+
+            if let Err(e) = result {
+                error!("Failed to update newscan settings: {}", e);
+                ctx.send_line(session, ctx.i18n.t("common.operation_failed"))
+                    .await?;
+            }
+        }
+    }
+
+    /// Reset the newscan boards' read pointers to an entered date.
+    async fn reset_newscan_pointers(
+        ctx: &mut ScreenContext,
+        session: &mut TelnetSession,
+        user_id: i64,
+        user_role: Role,
+    ) -> Result<()> {
+        ctx.send(session, ctx.i18n.t("board.newscan_reset_prompt"))
+            .await?;
+        let input = ctx.read_line(session).await?;
+        let input = input.trim();
+        if input.is_empty() {
+            return Ok(());
+        }
+
+        // A bare number means that many days ago
+        let since = match input.parse::<u32>() {
+            Ok(days) => Some(days_ago_db(days.into())),
+            Err(_) => parse_local_datetime(input, &ctx.config.server.timezone),
+        };
+        let Some(since) = since else {
+            ctx.send_line(session, ctx.i18n.t("board.newscan_invalid_date"))
+                .await?;
+            return Ok(());
+        };
+
+        let count = NewscanService::new(&ctx.db)
+            .reset_pointers(user_id, user_role, &since)
+            .await?;
+        ctx.send_line(
+            session,
+            &ctx.i18n
+                .t_with("board.newscan_reset_done", &[("count", &count.to_string())]),
+        )
+        .await?;
+        Ok(())
+    }
+
     /// Get user role from session.
     async fn get_user_role(ctx: &ScreenContext, session: &TelnetSession) -> Role {
         if let Some(user_id) = session.user_id() {
@@ -11,9 +11,11 @@ use crate::chat::{
     ChatParticipant, ChatRoom, JoinResult, NewChatLog,
 };
 use crate::error::Result;
+use crate::filter::{is_rejection, ContentFilterService, FilterTarget};
 use crate::rate_limit::RateLimitResult;
 use crate::server::TelnetSession;
 use crate::template::Value;
+use crate::twit::KillFile;
 
 /// Maximum length for chat messages (in characters).
 pub const MAX_CHAT_MESSAGE_LENGTH: usize = 500;
@@ -127,8 +129,9 @@ impl ChatScreen {
             .await?;
         ctx.send_line(session, "").await?;
 
-        // Show recent logs
-        Self::show_recent_logs(ctx, session, room_id).await?;
+        // Show recent logs, leaving out what the user's twit filters hide
+        let kill_file = ctx.kill_file(session).await?;
+        Self::show_recent_logs(ctx, session, room_id, &kill_file).await?;
 
         // Main chat loop
         let result = Self::chat_loop(
@@ -139,6 +142,7 @@ impl ChatScreen {
             &session_id,
             user_id,
             &nickname,
+            &kill_file,
         )
         .await;
 
@@ -159,9 +163,11 @@ impl ChatScreen {
         ctx: &ScreenContext,
         session: &mut TelnetSession,
         room_id: &str,
+        kill_file: &KillFile,
     ) -> Result<()> {
         let repo = ChatLogRepository::new(ctx.db.pool());
-        let logs = repo.get_recent(room_id, 10).await?;
+        let mut logs = repo.get_recent(room_id, 10).await?;
+        logs.retain(|log| !kill_file.hides_chat(&log.sender_name, log.message_type, &log.content));
 
         if !logs.is_empty() {
             ctx.send_line(session, "--- Recent messages ---").await?;
@@ -175,7 +181,38 @@ impl ChatScreen {
         Ok(())
     }
 
+    /// Run the content filter over a chat message.
+    ///
+    /// Returns None, after telling the user, if the message is not sent.
+    /// Chat cannot be held for moderation, so a hold rejects the message.
+    async fn filter_message(
+        ctx: &ScreenContext,
+        session: &mut TelnetSession,
+        user_id: Option<i64>,
+        message: String,
+    ) -> Result<Option<String>> {
+        let mut texts = [message];
+        let result = ContentFilterService::new(ctx.db.pool())
+            .apply_strict(user_id, FilterTarget::Chat, &mut texts)
+            .await;
+        let key = match result {
+            Ok(()) => {
+                let [message] = texts;
+                return Ok(Some(message));
+            }
+            Err(e) if is_rejection(&e) => "filter.rejected",
+            Err(e) => {
+                tracing::error!("Failed to filter chat message: {}", e);
+                "common.operation_failed"
+            }
+        };
+        ctx.send_line(session, &format!("*** {}", ctx.i18n.t(key)))
+            .await?;
+        Ok(None)
+    }
+
     /// Main chat loop.
+    #[allow(clippy::too_many_arguments)]
     async fn chat_loop(
         ctx: &mut ScreenContext,
         session: &mut TelnetSession,
@@ -184,6 +221,7 @@ impl ChatScreen {
         session_id: &str,
         user_id: Option<i64>,
         nickname: &str,
+        kill_file: &KillFile,
     ) -> Result<ScreenResult> {
         loop {
             // Use select to handle both input and incoming messages
@@ -192,8 +230,10 @@ impl ChatScreen {
                 msg_result = receiver.recv() => {
                     match msg_result {
                         Ok(msg) => {
-                            // Don't echo our own messages
-                            if msg.sender_id.as_deref() != Some(session_id) {
+                            // Don't echo our own messages or hidden ones
+                            if msg.sender_id.as_deref() != Some(session_id)
+                                && !kill_file.hides_chat(&msg.sender_name, msg.message_type, &msg.content)
+                            {
                                 ctx.send_line(session, &msg.format()).await?;
                             }
                         }
@@ -245,6 +285,9 @@ impl ChatScreen {
                                                         continue;
                                                     }
                                                 }
+                                                let Some(action) = Self::filter_message(ctx, session, user_id, action).await? else {
+                                                    continue;
+                                                };
                                                 room.send_action(session_id, &action).await;
                                                 // Record rate limit for logged-in users
                                                 if let Some(uid) = user_id {
@@ -289,6 +332,9 @@ impl ChatScreen {
                                                 continue;
                                             }
                                         }
+                                        let Some(content) = Self::filter_message(ctx, session, user_id, content).await? else {
+                                            continue;
+                                        };
                                         // Send the message
                                         room.send_message(session_id, &content).await;
                                         // Record rate limit for logged-in users
@@ -5,24 +5,68 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::broadcast::{self, error::RecvError};
 use tokio::time::timeout;
 
 use crate::chat::ChatRoomManager;
 use crate::config::Config;
-use crate::db::Database;
+use crate::db::{Database, UserRepository};
 use crate::error::{HobbsError, Result};
 use crate::i18n::I18n;
+use crate::notify::{Notification, NotificationService};
 use crate::rate_limit::RateLimiters;
+use crate::screen::{create_screen_from_profile, Lightbar, LightbarEvent, LightbarItem};
 use crate::server::{
-    convert_caret_escape, encode_for_client, process_output_mode, CharacterEncoding, EchoMode,
-    InputResult, LineBuffer, SessionManager, TelnetSession,
+    encode_for_client, process_output_mode, render_user_colors, CharacterEncoding, EchoMode,
+    InputResult, Key, KeyDecoder, LineBuffer, OutputMode, SessionManager, TelnetSession,
+};
+use crate::template::{
+    create_system_context, pad_to_width, TemplateContext, TemplateLoader, Value,
 };
-use crate::template::{create_system_context, TemplateContext, TemplateLoader, Value};
 use crate::terminal::TerminalProfile;
+use crate::twit::{KillFile, TwitFilterService};
 
 /// Maximum number of lines in multiline input (to prevent memory exhaustion).
 pub const MAX_MULTILINE_LINES: usize = 1000;
 
+/// How long to wait after ESC before treating it as a lone Escape key.
+const ESCAPE_TIMEOUT_MS: u64 = 50;
+
+/// Rows kept free for headers and the prompt when sizing a lightbar.
+const LIGHTBAR_RESERVED_ROWS: u16 = 8;
+
+/// Receiver of live notifications for a logged-in user.
+pub(crate) type NotificationReceiver = Option<(i64, broadcast::Receiver<Notification>)>;
+
+/// Format a notification as a one-line notice.
+pub(crate) fn notification_notice(i18n: &I18n, notification: &Notification) -> String {
+    i18n.t_with(
+        "notify.new_post",
+        &[
+            ("board", &notification.board_name),
+            ("title", &notification.title),
+            ("author", &notification.author),
+        ],
+    )
+}
+
+/// Wait for the next notification addressed to the receiver's user.
+///
+/// Never completes without a receiver, so it can sit in a `select!` next
+/// to the input read.
+pub(crate) async fn next_notification(notices: &mut NotificationReceiver) -> Notification {
+    if let Some((user_id, receiver)) = notices {
+        loop {
+            match receiver.recv().await {
+                Ok(notification) if notification.user_id == *user_id => return notification,
+                Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            }
+        }
+    }
+    std::future::pending().await
+}
+
 /// Shared context for screen handlers.
 pub struct ScreenContext {
     /// Database connection.
@@ -37,6 +81,8 @@ pub struct ScreenContext {
     pub i18n: Arc<I18n>,
     /// Line buffer for input.
     pub line_buffer: LineBuffer,
+    /// Decoder for single-key input (arrows, function keys).
+    key_decoder: KeyDecoder,
     /// Chat room manager.
     pub chat_manager: Arc<ChatRoomManager>,
     /// Session manager.
@@ -79,6 +125,7 @@ impl ScreenContext {
             profile,
             i18n,
             line_buffer: LineBuffer::with_encoding(1024, encoding),
+            key_decoder: KeyDecoder::new(),
             chat_manager,
             session_manager,
             rate_limiters,
@@ -116,6 +163,7 @@ impl ScreenContext {
             profile,
             i18n,
             line_buffer: LineBuffer::with_encoding(1024, encoding),
+            key_decoder: KeyDecoder::new(),
             chat_manager,
             session_manager,
             rate_limiters,
@@ -139,6 +187,7 @@ impl ScreenContext {
     pub fn create_context(&self) -> TemplateContext {
         let mut context = create_system_context(Arc::clone(&self.i18n));
         context.set_cjk_width(self.profile.cjk_width as usize);
+        context.set_timezone(self.config.server.timezone.clone());
         context.set("bbs.name", Value::string(self.config.bbs.name.clone()));
         context.set(
             "bbs.description",
@@ -264,6 +313,53 @@ impl ScreenContext {
         Ok(())
     }
 
+    /// Subscribe to live notifications if a user is logged in.
+    pub(crate) fn notification_receiver(&self, session: &TelnetSession) -> NotificationReceiver {
+        session
+            .user_id()
+            .map(|user_id| (user_id, self.session_manager.notification_hub().subscribe()))
+    }
+
+    /// Show the logged-in user's undelivered notifications and mark them
+    /// delivered.
+    pub async fn show_pending_notifications(&self, session: &mut TelnetSession) -> Result<()> {
+        let Some(user_id) = session.user_id() else {
+            return Ok(());
+        };
+        let service = NotificationService::new(&self.db);
+        let notifications = service.list_pending(user_id).await?;
+        if notifications.is_empty() {
+            return Ok(());
+        }
+
+        self.send_line(session, self.i18n.t("notify.header")).await?;
+        for notification in &notifications {
+            let notice = notification_notice(&self.i18n, notification);
+            self.send_line(session, &notice).await?;
+            service.mark_delivered(user_id, notification.id).await?;
+        }
+        self.send_line(session, "").await
+    }
+
+    /// Interrupt input to show a live notification, then redraw the
+    /// partially typed line.
+    async fn show_live_notification(
+        &self,
+        session: &mut TelnetSession,
+        notification: &Notification,
+    ) -> Result<()> {
+        let notice = notification_notice(&self.i18n, notification);
+        self.send_raw(session, &format!("\r\n{}\r\n", notice)).await?;
+        if self.line_buffer.echo_mode() == EchoMode::Normal && !self.line_buffer.is_empty() {
+            session.stream_mut().write_all(self.line_buffer.contents()).await?;
+            session.stream_mut().flush().await?;
+        }
+        NotificationService::new(&self.db)
+            .mark_delivered(notification.user_id, notification.id)
+            .await?;
+        Ok(())
+    }
+
     /// Pause and wait for user input (for auto-paging).
     async fn pause_for_more(&self, session: &mut TelnetSession) -> Result<()> {
         // Reset counter BEFORE sending anything to avoid recursive pauses
@@ -460,9 +556,16 @@ impl ScreenContext {
             self.config.server.read_timeout_secs
         };
         let read_timeout = Duration::from_secs(timeout_secs);
+        let mut notices = self.notification_receiver(session);
 
         loop {
-            let read_result = timeout(read_timeout, session.stream_mut().read(&mut buf)).await;
+            let read_result = tokio::select! {
+                result = timeout(read_timeout, session.stream_mut().read(&mut buf)) => result,
+                notification = next_notification(&mut notices) => {
+                    self.show_live_notification(session, &notification).await?;
+                    continue;
+                }
+            };
 
             match read_result {
                 Ok(Ok(0)) => {
@@ -652,8 +755,8 @@ impl ScreenContext {
         }
     }
 
-    /// Read a single character.
-    pub async fn read_char(&self, session: &mut TelnetSession) -> Result<char> {
+    /// Read a single key press, decoding cursor and function key sequences.
+    pub async fn read_key(&mut self, session: &mut TelnetSession) -> Result<Key> {
         let mut buf = [0u8; 1];
 
         // Determine timeout based on session state
@@ -667,18 +770,31 @@ impl ScreenContext {
         let read_timeout = Duration::from_secs(timeout_secs);
 
         loop {
-            let read_result = timeout(read_timeout, session.stream_mut().read(&mut buf)).await;
+            // A pending ESC only waits briefly for the rest of its sequence
+            let wait = if self.key_decoder.is_pending() {
+                Duration::from_millis(ESCAPE_TIMEOUT_MS)
+            } else {
+                read_timeout
+            };
+            let read_result = timeout(wait, session.stream_mut().read(&mut buf)).await;
 
             match read_result {
-                Ok(Ok(0)) => return Ok('\0'),
+                Ok(Ok(0)) => {
+                    return Err(HobbsError::Io(std::io::Error::new(
+                        std::io::ErrorKind::UnexpectedEof,
+                        "Connection closed",
+                    )));
+                }
                 Ok(Ok(_)) => {
-                    let ch = buf[0] as char;
-                    if ch.is_ascii_graphic() || ch == '\r' || ch == '\n' {
-                        return Ok(ch);
+                    if let Some(key) = self.key_decoder.feed(buf[0]) {
+                        return Ok(key);
                     }
                 }
                 Ok(Err(e)) => return Err(e.into()),
                 Err(_) => {
+                    if let Some(key) = self.key_decoder.flush() {
+                        return Ok(key);
+                    }
                     return Err(HobbsError::Io(std::io::Error::new(
                         std::io::ErrorKind::TimedOut,
                         "Read timeout",
@@ -688,6 +804,127 @@ impl ScreenContext {
         }
     }
 
+    /// Read a single character.
+    ///
+    /// Escape sequences (cursor keys etc.) are consumed and skipped.
+    /// Returns `'\0'` if the connection was closed.
+    pub async fn read_char(&mut self, session: &mut TelnetSession) -> Result<char> {
+        loop {
+            match self.read_key(session).await {
+                Ok(Key::Char(c)) => return Ok(c),
+                Ok(Key::Enter) => return Ok('\r'),
+                Ok(_) => {}
+                Err(HobbsError::Io(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                    return Ok('\0');
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Whether the logged-in user wants to see colors in user content.
+    ///
+    /// Guests always see them.
+    pub async fn show_colors(&self, session: &TelnetSession) -> bool {
+        match session.user_id() {
+            Some(user_id) => UserRepository::new(self.db.pool())
+                .get_by_id(user_id)
+                .await
+                .ok()
+                .flatten()
+                .map(|u| u.show_colors)
+                .unwrap_or(true),
+            None => true,
+        }
+    }
+
+    /// Prepare a post body for display.
+    ///
+    /// Color codes are kept only when `allow_color` is set (the board
+    /// setting) and the logged-in user has not turned color display off.
+    pub async fn user_content(
+        &self,
+        session: &TelnetSession,
+        text: &str,
+        allow_color: bool,
+    ) -> String {
+        let show_colors = allow_color && self.show_colors(session).await;
+        render_user_colors(text, show_colors)
+    }
+
+    /// Load the twit filters of the logged-in user.
+    ///
+    /// Guests, and users who asked to see hidden content, get an empty kill
+    /// file.
+    pub async fn kill_file(&self, session: &TelnetSession) -> Result<KillFile> {
+        match session.user_id() {
+            Some(user_id) if !session.show_hidden() => {
+                TwitFilterService::new(self.db.pool())
+                    .kill_file(user_id)
+                    .await
+            }
+            _ => Ok(KillFile::default()),
+        }
+    }
+
+    /// Get the label of the key that shows or hides twit-filtered content.
+    pub fn show_hidden_label(&self, session: &TelnetSession) -> String {
+        if session.show_hidden() {
+            self.i18n.t("twit.hide_hidden").to_string()
+        } else {
+            self.i18n.t("twit.show_hidden").to_string()
+        }
+    }
+
+    /// Check if lightbar menus can be used for this session.
+    ///
+    /// Lightbars need cursor movement and reverse video, so Plain and
+    /// PETSCII output modes fall back to hotkey menus.
+    pub fn lightbar_enabled(&self, session: &TelnetSession) -> bool {
+        self.config.terminal.lightbar
+            && self.profile.ansi_enabled
+            && session.output_mode() == OutputMode::Ansi
+    }
+
+    /// Create a lightbar sized for the current terminal profile.
+    pub fn lightbar(&self, items: Vec<LightbarItem>) -> Lightbar {
+        let visible = self.profile.height.saturating_sub(LIGHTBAR_RESERVED_ROWS).max(3);
+        Lightbar::new(
+            items,
+            self.profile.width.saturating_sub(1) as usize,
+            visible as usize,
+            self.profile.cjk_width as usize,
+        )
+    }
+
+    /// Show a lightbar followed by a prompt and wait for a choice.
+    ///
+    /// Returns the same string the user would have typed at a hotkey prompt.
+    pub async fn read_lightbar(
+        &mut self,
+        session: &mut TelnetSession,
+        lightbar: &mut Lightbar,
+        prompt: &str,
+    ) -> Result<String> {
+        let screen = create_screen_from_profile(&self.profile);
+        self.send_raw(session, &lightbar.render(screen.as_ref()))
+            .await?;
+        self.send_raw(session, prompt).await?;
+
+        loop {
+            let key = self.read_key(session).await?;
+            match lightbar.handle_key(key, screen.as_ref()) {
+                LightbarEvent::Output(output) => self.send_raw(session, &output).await?,
+                LightbarEvent::Choose(value) => {
+                    self.send_raw(session, "\r\n").await?;
+                    self.reset_line_counter();
+                    return Ok(value);
+                }
+                LightbarEvent::Ignore => {}
+            }
+        }
+    }
+
     /// Wait for Enter key press.
     pub async fn wait_for_enter(&self, session: &mut TelnetSession) -> Result<()> {
         self.send(session, self.i18n.t("common.press_enter"))
@@ -737,7 +974,23 @@ impl ScreenContext {
     /// - `Ok(Some(text))` - User completed input
     /// - `Ok(None)` - User cancelled input
     pub async fn read_multiline(&mut self, session: &mut TelnetSession) -> Result<Option<String>> {
-        let mut lines = Vec::new();
+        self.read_multiline_with(session, "").await
+    }
+
+    /// Read multiline input that starts with `initial` already entered.
+    ///
+    /// Used to pre-fill a reply with a quote. The initial lines are echoed
+    /// as if they had been typed and count toward the line limit.
+    pub async fn read_multiline_with(
+        &mut self,
+        session: &mut TelnetSession,
+        initial: &str,
+    ) -> Result<Option<String>> {
+        let mut lines: Vec<String> = Vec::new();
+        for line in initial.lines().take(MAX_MULTILINE_LINES) {
+            self.send_line(session, &format!("> {line}")).await?;
+            lines.push(line.to_string());
+        }
 
         loop {
             self.send(session, "> ").await?;
@@ -775,6 +1028,11 @@ impl ScreenContext {
         Ok(Some(lines.join("\n")))
     }
 
+    /// Pad or truncate text to a column width for the current terminal.
+    pub fn pad(&self, text: &str, width: usize) -> String {
+        pad_to_width(text, width, self.profile.cjk_width as usize)
+    }
+
     /// Parse a number from input.
     pub fn parse_number(&self, input: &str) -> Option<i64> {
         input.trim().parse().ok()
@@ -782,14 +1040,13 @@ impl ScreenContext {
 
     /// Render a template.
     ///
-    /// Applies caret escape conversion (`^[` → ESC) so templates can use
-    /// `^[[34m` notation for ANSI escape sequences.
+    /// Caret escapes (`^[[34m`) and pipe/`@X` color codes in the template
+    /// text are converted by the loader; values in the context are left
+    /// untouched, so user data must be prepared with [`Self::user_content`].
     pub fn render_template(&self, name: &str, context: &TemplateContext) -> Result<String> {
-        let rendered = self
-            .template_loader
+        self.template_loader
             .render(name, self.profile.width, context)
-            .map_err(|e| crate::error::HobbsError::from(e))?;
-        Ok(convert_caret_escape(&rendered))
+            .map_err(|e| crate::error::HobbsError::from(e))
     }
 
     /// Set line buffer echo mode.
@@ -493,19 +493,7 @@ impl FileScreen {
 
     /// Format file size for display.
     fn format_size(size: i64) -> String {
-        const KB: i64 = 1024;
-        const MB: i64 = KB * 1024;
-        const GB: i64 = MB * 1024;
-
-        if size >= GB {
-            format!("{:.1} GB", size as f64 / GB as f64)
-        } else if size >= MB {
-            format!("{:.1} MB", size as f64 / MB as f64)
-        } else if size >= KB {
-            format!("{:.1} KB", size as f64 / KB as f64)
-        } else {
-            format!("{} B", size)
-        }
+        crate::template::format_bytes(size)
     }
 }
 
@@ -3,13 +3,16 @@
 use tracing::error;
 
 use super::common::ScreenContext;
+use super::qwk::QwkScreen;
 use super::ScreenResult;
 use crate::datetime::format_utc_datetime;
 use crate::db::UserRepository;
 use crate::error::Result;
+use crate::filter::{is_rejection, ContentFilterService, FilterTarget};
 use crate::mail::{MailRepository, NewMail};
 use crate::rate_limit::RateLimitResult;
-use crate::server::{convert_caret_escape, TelnetSession};
+use crate::screen::LightbarItem;
+use crate::server::{render_user_colors, Key, TelnetSession};
 use crate::template::Value;
 
 /// Mail screen handler.
@@ -33,13 +36,21 @@ impl MailScreen {
         loop {
             // Get mail list (no pagination in repository)
             let mail_repo = MailRepository::new(ctx.db.pool());
-            let mails = mail_repo.list_inbox(user_id).await?;
+            let mut mails = mail_repo.list_inbox(user_id).await?;
+            let kill_file = ctx.kill_file(session).await?;
+            mails.retain(|m| !kill_file.hides_mail(m));
             let total = mails.len();
 
             // Display mail list using template
+            let lightbar = ctx.lightbar_enabled(session);
             let mut context = ctx.create_context();
             context.set("has_mails", Value::bool(!mails.is_empty()));
             context.set("total", Value::number(total as i64));
+            context.set("lightbar", Value::bool(lightbar));
+
+            let from_width = if ctx.profile.width >= 80 { 16 } else { 10 };
+            let subject_width = (ctx.profile.width as usize).saturating_sub(from_width + 13);
+            let mut lightbar_items = Vec::new();
 
             if !mails.is_empty() {
                 let user_repo = UserRepository::new(ctx.db.pool());
@@ -54,6 +65,16 @@ impl MailScreen {
                     let mut entry = std::collections::HashMap::new();
                     entry.insert("number".to_string(), Value::string((i + 1).to_string()));
                     entry.insert("unread_mark".to_string(), Value::string(unread));
+                    lightbar_items.push(LightbarItem::new(
+                        (i + 1).to_string(),
+                        format!(
+                            "{:>3} {} {} {}",
+                            i + 1,
+                            unread,
+                            ctx.pad(&from, from_width),
+                            ctx.pad(&mail.subject, subject_width)
+                        ),
+                    ));
                     entry.insert("from".to_string(), Value::string(from));
                     entry.insert("subject".to_string(), Value::string(&mail.subject));
                     mail_list.push(Value::Object(entry));
@@ -65,17 +86,24 @@ impl MailScreen {
             ctx.send(session, &content).await?;
 
             // Prompt
-            ctx.send(
-                session,
-                &format!(
-                    "[W]={} [Q]={}: ",
-                    ctx.i18n.t("mail.compose"),
-                    ctx.i18n.t("common.back")
-                ),
-            )
-            .await?;
-
-            let input = ctx.read_line(session).await?;
+            let prompt = format!(
+                "[W]={} [O]={} [X]={} [Q]={}: ",
+                ctx.i18n.t("mail.compose"),
+                ctx.i18n.t("qwk.offline"),
+                ctx.show_hidden_label(session),
+                ctx.i18n.t("common.back")
+            );
+
+            let input = if lightbar {
+                let mut bar = ctx
+                    .lightbar(lightbar_items)
+                    .bind(Key::Escape, "q")
+                    .bind(Key::Left, "q");
+                ctx.read_lightbar(session, &mut bar, &prompt).await?
+            } else {
+                ctx.send(session, &prompt).await?;
+                ctx.read_line(session).await?
+            };
             let input = input.trim();
 
             match input.to_ascii_lowercase().as_str() {
@@ -83,6 +111,12 @@ impl MailScreen {
                 "w" => {
                     Self::compose(ctx, session, user_id).await?;
                 }
+                "o" => {
+                    QwkScreen::run(ctx, session).await?;
+                }
+                "x" => {
+                    session.set_show_hidden(!session.show_hidden());
+                }
                 _ => {
                     if let Some(num) = ctx.parse_number(input) {
                         let idx = num as usize - 1;
@@ -144,7 +178,8 @@ impl MailScreen {
         context.set("date", Value::string(
             format_utc_datetime(&mail.created_at, &ctx.config.server.timezone, "%Y/%m/%d %H:%M"),
         ));
-        context.set("body", Value::string(convert_caret_escape(&mail.body)));
+        let body = render_user_colors(&mail.body, ctx.show_colors(session).await);
+        context.set("body", Value::string(body));
 
         let content = ctx.render_template("mail/view", &context)?;
         ctx.send(session, &content).await?;
@@ -258,23 +293,7 @@ impl MailScreen {
         }
 
         // Send mail
-        let new_mail = NewMail::new(from_id, to_user.id, subject, &body);
-        let mail_repo = MailRepository::new(ctx.db.pool());
-
-        match mail_repo.create(&new_mail).await {
-            Ok(_) => {
-                // Record successful action for rate limiting
-                ctx.rate_limiters.mail.record(from_id);
-                ctx.send_line(session, ctx.i18n.t("mail.mail_sent")).await?;
-            }
-            Err(e) => {
-                error!("Failed to send mail: {}", e);
-                ctx.send_line(session, ctx.i18n.t("common.operation_failed"))
-                    .await?;
-            }
-        }
-
-        Ok(())
+        Self::deliver(ctx, session, from_id, to_user.id, subject, &body).await
     }
 
     /// Reply to a mail.
@@ -336,17 +355,44 @@ impl MailScreen {
         }
 
         // Send reply (reply to sender)
-        let new_mail = NewMail::new(from_id, original.sender_id, &subject, &body);
-        let mail_repo = MailRepository::new(ctx.db.pool());
+        Self::deliver(ctx, session, from_id, original.sender_id, &subject, &body).await
+    }
 
-        match mail_repo.create(&new_mail).await {
+    /// Run the content filter over a mail and store it.
+    async fn deliver(
+        ctx: &mut ScreenContext,
+        session: &mut TelnetSession,
+        from_id: i64,
+        to_id: i64,
+        subject: &str,
+        body: &str,
+    ) -> Result<()> {
+        let mut texts = [subject.to_string(), body.to_string()];
+        let filtered = ContentFilterService::new(ctx.db.pool())
+            .apply_strict(Some(from_id), FilterTarget::Mail, &mut texts)
+            .await;
+        let [subject, body] = texts;
+
+        let result = match filtered {
+            Ok(()) => {
+                let new_mail = NewMail::new(from_id, to_id, subject, body);
+                MailRepository::new(ctx.db.pool()).create(&new_mail).await
+            }
+            Err(e) => Err(e),
+        };
+
+        match result {
             Ok(_) => {
                 // Record successful action for rate limiting
                 ctx.rate_limiters.mail.record(from_id);
                 ctx.send_line(session, ctx.i18n.t("mail.mail_sent")).await?;
             }
+            Err(e) if is_rejection(&e) => {
+                ctx.send_line(session, ctx.i18n.t("filter.rejected"))
+                    .await?;
+            }
             Err(e) => {
-                error!("Failed to send reply: {}", e);
+                error!("Failed to send mail: {}", e);
                 ctx.send_line(session, ctx.i18n.t("common.operation_failed"))
                     .await?;
             }
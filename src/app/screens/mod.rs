@@ -10,7 +10,9 @@ mod file;
 mod help;
 mod mail;
 mod member;
+mod poll;
 mod profile;
+mod qwk;
 mod rss;
 mod script;
 
@@ -18,11 +20,14 @@ pub use admin::AdminScreen;
 pub use board::BoardScreen;
 pub use chat::ChatScreen;
 pub use common::ScreenContext;
+pub(crate) use common::{next_notification, notification_notice};
 pub use file::FileScreen;
 pub use help::HelpScreen;
 pub use mail::MailScreen;
 pub use member::MemberScreen;
+pub use poll::PollScreen;
 pub use profile::ProfileScreen;
+pub use qwk::QwkScreen;
 pub use rss::RssScreen;
 pub use script::ScriptScreen;
 
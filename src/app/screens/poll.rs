@@ -0,0 +1,555 @@
+//! Voting booth screen handler.
+
+use tracing::error;
+
+use super::common::ScreenContext;
+use super::ScreenResult;
+use crate::datetime::{format_datetime, parse_local_datetime};
+use crate::db::{Role, UserRepository};
+use crate::error::{HobbsError, Result};
+use crate::poll::{
+    render_bar_chart, Ballot, NewPoll, PollResults, PollService, PollStatus, MAX_POLL_OPTIONS,
+};
+use crate::server::TelnetSession;
+use crate::template::Value;
+
+/// Voting booth screen handler.
+pub struct PollScreen;
+
+impl PollScreen {
+    /// Run the poll list screen.
+    pub async fn run(ctx: &mut ScreenContext, session: &mut TelnetSession) -> Result<ScreenResult> {
+        loop {
+            let user_role = Self::get_user_role(ctx, session).await;
+            let can_create = Self::can_create(ctx, session, user_role);
+            let service = PollService::new(&ctx.db);
+            let polls = service.list_polls(user_role).await?;
+
+            let mut context = ctx.create_context();
+            context.set("has_polls", Value::bool(!polls.is_empty()));
+            let mut poll_list = Vec::new();
+            for (i, poll) in polls.iter().enumerate() {
+                let results = service
+                    .get_results(poll.id, session.user_id(), user_role)
+                    .await?;
+                let mut entry = std::collections::HashMap::new();
+                entry.insert("number".to_string(), Value::string((i + 1).to_string()));
+                entry.insert(
+                    "status".to_string(),
+                    Value::string(Self::status_name(ctx, results.status)),
+                );
+                entry.insert("question".to_string(), Value::string(&poll.question));
+                entry.insert(
+                    "voters".to_string(),
+                    Value::string(results.voters.to_string()),
+                );
+                entry.insert(
+                    "voted_marker".to_string(),
+                    Value::string(if results.has_voted() { " *" } else { "" }),
+                );
+                poll_list.push(Value::Object(entry));
+            }
+            context.set("polls", Value::List(poll_list));
+
+            let content = ctx.render_template("poll/list", &context)?;
+            ctx.send(session, &content).await?;
+
+            let prompt = if can_create {
+                format!(
+                    "[{}] [C]={} [Q]={}: ",
+                    ctx.i18n.t("common.number"),
+                    ctx.i18n.t("poll.create"),
+                    ctx.i18n.t("common.back")
+                )
+            } else {
+                format!(
+                    "[{}] [Q]={}: ",
+                    ctx.i18n.t("common.number"),
+                    ctx.i18n.t("common.back")
+                )
+            };
+            ctx.send(session, &prompt).await?;
+
+            let input = ctx.read_line(session).await?;
+            let input = input.trim();
+
+            match input.to_ascii_lowercase().as_str() {
+                "q" | "" => return Ok(ScreenResult::Back),
+                "c" if can_create => {
+                    if let Some(poll_id) = Self::create_poll(ctx, session, None).await? {
+                        Self::run_poll(ctx, session, poll_id).await?;
+                    }
+                }
+                _ => {
+                    if let Some(num) = ctx.parse_number(input) {
+                        if let Some(poll) = usize::try_from(num - 1).ok().and_then(|i| polls.get(i))
+                        {
+                            Self::run_poll(ctx, session, poll.id).await?;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Open the poll attached to a thread, offering to create one if the
+    /// thread has none and the user may attach it.
+    pub async fn run_thread_poll(
+        ctx: &mut ScreenContext,
+        session: &mut TelnetSession,
+        thread_id: i64,
+        thread_author_id: i64,
+    ) -> Result<()> {
+        let user_role = Self::get_user_role(ctx, session).await;
+        let poll = PollService::new(&ctx.db)
+            .get_thread_poll(thread_id, user_role)
+            .await?;
+        if let Some(poll) = poll {
+            return Self::run_poll(ctx, session, poll.id).await;
+        }
+
+        let may_attach = Self::can_create(ctx, session, user_role)
+            && (session.user_id() == Some(thread_author_id) || user_role >= Role::SubOp);
+        if !may_attach {
+            return ctx
+                .send_line(session, ctx.i18n.t("poll.no_thread_poll"))
+                .await;
+        }
+
+        ctx.send(session, ctx.i18n.t("poll.create_thread_poll"))
+            .await?;
+        let input = ctx.read_line(session).await?;
+        if input.trim().eq_ignore_ascii_case("y") {
+            if let Some(poll_id) = Self::create_poll(ctx, session, Some(thread_id)).await? {
+                Self::run_poll(ctx, session, poll_id).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Show a poll's results and let the user vote or manage it.
+    async fn run_poll(
+        ctx: &mut ScreenContext,
+        session: &mut TelnetSession,
+        poll_id: i64,
+    ) -> Result<()> {
+        loop {
+            let user_role = Self::get_user_role(ctx, session).await;
+            let results = match PollService::new(&ctx.db)
+                .get_results(poll_id, session.user_id(), user_role)
+                .await
+            {
+                Ok(results) => results,
+                Err(HobbsError::NotFound(_) | HobbsError::Permission(_)) => {
+                    ctx.send_line(session, ctx.i18n.t("common.not_found"))
+                        .await?;
+                    return Ok(());
+                }
+                Err(e) => return Err(e),
+            };
+            Self::show_results(ctx, session, &results).await?;
+
+            let can_vote = session.user_id().is_some()
+                && results.status == PollStatus::Open
+                && results.poll.can_vote(user_role)
+                && !results.has_voted();
+            let can_manage = session
+                .user_id()
+                .is_some_and(|user_id| PollService::can_manage(&results.poll, user_id, user_role));
+
+            let mut prompt_parts = vec![];
+            if can_vote {
+                prompt_parts.push(format!("[V]={}", ctx.i18n.t("poll.vote")));
+            }
+            if can_manage && results.status != PollStatus::Closed {
+                prompt_parts.push(format!("[C]={}", ctx.i18n.t("poll.close")));
+            }
+            if can_manage {
+                prompt_parts.push(format!("[D]={}", ctx.i18n.t("poll.delete")));
+            }
+            prompt_parts.push(format!("[Q]={}", ctx.i18n.t("common.back")));
+            ctx.send(session, &format!("{}: ", prompt_parts.join(" ")))
+                .await?;
+
+            let input = ctx.read_line(session).await?;
+            match input.trim().to_ascii_lowercase().as_str() {
+                "v" if can_vote => Self::vote(ctx, session, &results).await?,
+                "c" if can_manage && results.status != PollStatus::Closed => {
+                    ctx.send(session, ctx.i18n.t("poll.confirm_close")).await?;
+                    let confirm = ctx.read_line(session).await?;
+                    if confirm.trim().eq_ignore_ascii_case("y") {
+                        let user_id = session.user_id().unwrap_or_default();
+                        PollService::new(&ctx.db)
+                            .close_poll(poll_id, user_id, user_role)
+                            .await?;
+                        ctx.send_line(session, ctx.i18n.t("poll.closed")).await?;
+                    }
+                }
+                "d" if can_manage => {
+                    ctx.send(session, ctx.i18n.t("poll.confirm_delete")).await?;
+                    let confirm = ctx.read_line(session).await?;
+                    if confirm.trim().eq_ignore_ascii_case("y") {
+                        let user_id = session.user_id().unwrap_or_default();
+                        PollService::new(&ctx.db)
+                            .delete_poll(poll_id, user_id, user_role)
+                            .await?;
+                        ctx.send_line(session, ctx.i18n.t("poll.deleted")).await?;
+                        return Ok(());
+                    }
+                }
+                "q" | "" => return Ok(()),
+                _ => {}
+            }
+        }
+    }
+
+    /// Render a poll with its bar chart.
+    async fn show_results(
+        ctx: &mut ScreenContext,
+        session: &mut TelnetSession,
+        results: &PollResults,
+    ) -> Result<()> {
+        let poll = &results.poll;
+        let tz = &ctx.config.server.timezone;
+        let format_time = |t: &str| format_datetime(t, tz, "%Y-%m-%d %H:%M");
+
+        let mut info = Vec::new();
+        info.push(
+            ctx.i18n
+                .t(if poll.allow_multiple {
+                    "poll.multiple"
+                } else {
+                    "poll.single"
+                })
+                .to_string(),
+        );
+        if poll.allow_write_in {
+            info.push(ctx.i18n.t("poll.write_in_allowed").to_string());
+        }
+        info.push(ctx.i18n.t_with(
+            "poll.min_vote_role",
+            &[("role", Self::role_name(ctx, poll.min_vote_role))],
+        ));
+        if poll.opens_at.is_some() || poll.closes_at.is_some() {
+            let opens = poll
+                .opens_at
+                .as_deref()
+                .map(format_time)
+                .unwrap_or_else(|| format_time(&poll.created_at));
+            let closes = poll
+                .closes_at
+                .as_deref()
+                .map(format_time)
+                .unwrap_or_else(|| ctx.i18n.t("poll.no_end").to_string());
+            info.push(
+                ctx.i18n
+                    .t_with("poll.period", &[("opens", &opens), ("closes", &closes)]),
+            );
+        }
+        if let Some(creator) = &poll.creator {
+            info.push(ctx.i18n.t_with("poll.created_by", &[("name", creator)]));
+        }
+
+        let entries: Vec<(String, i64)> = results
+            .options
+            .iter()
+            .enumerate()
+            .map(|(i, r)| {
+                let mark = if results.my_votes.contains(&r.option.id) {
+                    "*"
+                } else {
+                    " "
+                };
+                (format!("{}{}. {}", mark, i + 1, r.option.text), r.votes)
+            })
+            .collect();
+        let chart = render_bar_chart(
+            &entries,
+            ctx.profile.width as usize,
+            ctx.profile.cjk_width as usize,
+        );
+
+        let mut context = ctx.create_context();
+        context.set("question", Value::string(&poll.question));
+        context.set(
+            "description",
+            Value::string(poll.description.clone().unwrap_or_default()),
+        );
+        context.set(
+            "status_text",
+            Value::string(format!("[{}]", Self::status_name(ctx, results.status))),
+        );
+        context.set(
+            "info",
+            Value::List(info.into_iter().map(Value::string).collect()),
+        );
+        context.set(
+            "chart",
+            Value::List(chart.into_iter().map(Value::string).collect()),
+        );
+        context.set(
+            "voters_text",
+            Value::string(ctx.i18n.t_with(
+                "poll.voter_count",
+                &[("count", &results.voters.to_string())],
+            )),
+        );
+        context.set("voted", Value::bool(results.has_voted()));
+
+        let content = ctx.render_template("poll/view", &context)?;
+        ctx.send(session, &content).await
+    }
+
+    /// Ask for the user's choices and cast the ballot.
+    async fn vote(
+        ctx: &mut ScreenContext,
+        session: &mut TelnetSession,
+        results: &PollResults,
+    ) -> Result<()> {
+        let Some(user_id) = session.user_id() else {
+            return Ok(());
+        };
+        let poll = &results.poll;
+
+        let mut prompt = ctx
+            .i18n
+            .t(if poll.allow_multiple {
+                "poll.enter_choices"
+            } else {
+                "poll.enter_choice"
+            })
+            .to_string();
+        if poll.allow_write_in {
+            prompt.push_str(&format!(" [W]={}", ctx.i18n.t("poll.write_in")));
+        }
+        ctx.send(session, &format!("{}: ", prompt)).await?;
+        let input = ctx.read_line(session).await?;
+
+        let mut ballot = Ballot::default();
+        for part in input.split([',', ' ']).filter(|p| !p.is_empty()) {
+            if poll.allow_write_in && part.eq_ignore_ascii_case("w") {
+                ctx.send(session, ctx.i18n.t("poll.enter_write_in")).await?;
+                let text = ctx.read_line(session).await?;
+                ballot.write_in = Some(text.trim().to_string());
+                continue;
+            }
+            let option = ctx
+                .parse_number(part)
+                .and_then(|num| usize::try_from(num - 1).ok())
+                .and_then(|idx| results.options.get(idx));
+            match option {
+                Some(option) => ballot.option_ids.push(option.option.id),
+                None => {
+                    ctx.send_line(session, ctx.i18n.t("common.invalid_input"))
+                        .await?;
+                    return Ok(());
+                }
+            }
+        }
+        if ballot.is_empty() {
+            return Ok(());
+        }
+
+        let user_role = Self::get_user_role(ctx, session).await;
+        match PollService::new(&ctx.db)
+            .vote(poll.id, user_id, user_role, ballot)
+            .await
+        {
+            Ok(_) => ctx.send_line(session, ctx.i18n.t("poll.voted")).await,
+            Err(HobbsError::Validation(_)) => {
+                ctx.send_line(session, ctx.i18n.t("poll.vote_rejected"))
+                    .await
+            }
+            Err(e) => {
+                error!("Failed to cast ballot: {}", e);
+                ctx.send_line(session, ctx.i18n.t("common.operation_failed"))
+                    .await
+            }
+        }
+    }
+
+    /// Ask for the poll's question, options and settings and create it.
+    ///
+    /// Returns the new poll's ID, or None if the user cancelled.
+    async fn create_poll(
+        ctx: &mut ScreenContext,
+        session: &mut TelnetSession,
+        thread_id: Option<i64>,
+    ) -> Result<Option<i64>> {
+        let Some(user_id) = session.user_id() else {
+            return Ok(None);
+        };
+
+        ctx.send(session, ctx.i18n.t("poll.enter_question")).await?;
+        let question = ctx.read_line(session).await?;
+        let question = question.trim().to_string();
+        if question.is_empty() {
+            return Ok(None);
+        }
+
+        ctx.send(session, ctx.i18n.t("poll.enter_description"))
+            .await?;
+        let description = ctx.read_line(session).await?;
+        let description = description.trim().to_string();
+
+        ctx.send_line(session, ctx.i18n.t("poll.enter_options"))
+            .await?;
+        let mut options = Vec::new();
+        while options.len() < MAX_POLL_OPTIONS {
+            ctx.send(
+                session,
+                &ctx.i18n.t_with(
+                    "poll.option_prompt",
+                    &[("number", &(options.len() + 1).to_string())],
+                ),
+            )
+            .await?;
+            let option = ctx.read_line(session).await?;
+            let option = option.trim().to_string();
+            if option.is_empty() {
+                break;
+            }
+            options.push(option);
+        }
+        if options.len() < 2 {
+            ctx.send_line(session, ctx.i18n.t("poll.too_few_options"))
+                .await?;
+            return Ok(None);
+        }
+
+        ctx.send(session, ctx.i18n.t("poll.allow_multiple_prompt"))
+            .await?;
+        let allow_multiple = ctx
+            .read_line(session)
+            .await?
+            .trim()
+            .eq_ignore_ascii_case("y");
+        ctx.send(session, ctx.i18n.t("poll.allow_write_in_prompt"))
+            .await?;
+        let allow_write_in = ctx
+            .read_line(session)
+            .await?
+            .trim()
+            .eq_ignore_ascii_case("y");
+
+        ctx.send(session, ctx.i18n.t("poll.min_vote_role_prompt"))
+            .await?;
+        let min_vote_role = match ctx.read_line(session).await?.trim() {
+            "1" => Role::Guest,
+            "3" => Role::SubOp,
+            "4" => Role::SysOp,
+            _ => Role::Member,
+        };
+
+        let Some(opens_at) = Self::read_datetime(ctx, session, "poll.opens_prompt").await? else {
+            return Ok(None);
+        };
+        let Some(closes_at) = Self::read_datetime(ctx, session, "poll.closes_prompt").await? else {
+            return Ok(None);
+        };
+
+        let mut new_poll = NewPoll::new(question, options)
+            .with_multiple(allow_multiple)
+            .with_write_in(allow_write_in)
+            .with_min_vote_role(min_vote_role)
+            .with_period(opens_at, closes_at);
+        if !description.is_empty() {
+            new_poll = new_poll.with_description(description);
+        }
+        if let Some(thread_id) = thread_id {
+            new_poll = new_poll.with_thread(thread_id);
+        }
+
+        let user_role = Self::get_user_role(ctx, session).await;
+        let create_role = ctx.config.polls.create_role();
+        match PollService::new(&ctx.db)
+            .create_poll(user_id, user_role, create_role, new_poll)
+            .await
+        {
+            Ok(poll) => {
+                ctx.send_line(session, ctx.i18n.t("poll.created")).await?;
+                Ok(Some(poll.id))
+            }
+            Err(HobbsError::Validation(_)) => {
+                ctx.send_line(session, ctx.i18n.t("common.invalid_input"))
+                    .await?;
+                Ok(None)
+            }
+            Err(HobbsError::Permission(_)) => {
+                ctx.send_line(session, ctx.i18n.t("common.permission_denied"))
+                    .await?;
+                Ok(None)
+            }
+            Err(e) => {
+                error!("Failed to create poll: {}", e);
+                ctx.send_line(session, ctx.i18n.t("common.operation_failed"))
+                    .await?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Read an optional date and time in the server's timezone.
+    ///
+    /// Returns `Some(None)` for an empty answer and `None` if the input
+    /// could not be parsed.
+    async fn read_datetime(
+        ctx: &mut ScreenContext,
+        session: &mut TelnetSession,
+        prompt_key: &str,
+    ) -> Result<Option<Option<String>>> {
+        ctx.send(session, ctx.i18n.t(prompt_key)).await?;
+        let input = ctx.read_line(session).await?;
+        let input = input.trim();
+        if input.is_empty() {
+            return Ok(Some(None));
+        }
+        match parse_local_datetime(input, &ctx.config.server.timezone) {
+            Some(datetime) => Ok(Some(Some(datetime))),
+            None => {
+                ctx.send_line(session, ctx.i18n.t("poll.invalid_date"))
+                    .await?;
+                Ok(None)
+            }
+        }
+    }
+
+    fn can_create(ctx: &ScreenContext, session: &TelnetSession, user_role: Role) -> bool {
+        session.user_id().is_some() && user_role.can_access(ctx.config.polls.create_role())
+    }
+
+    fn status_name(ctx: &ScreenContext, status: PollStatus) -> String {
+        ctx.i18n
+            .t(&format!("poll.status_{}", status.as_str()))
+            .to_string()
+    }
+
+    fn role_name(ctx: &ScreenContext, role: Role) -> &str {
+        match role {
+            Role::Guest => ctx.i18n.t("role.guest"),
+            Role::Member => ctx.i18n.t("role.member"),
+            Role::SubOp => ctx.i18n.t("role.subop"),
+            Role::SysOp => ctx.i18n.t("role.sysop"),
+        }
+    }
+
+    async fn get_user_role(ctx: &ScreenContext, session: &TelnetSession) -> Role {
+        if let Some(user_id) = session.user_id() {
+            let user_repo = UserRepository::new(ctx.db.pool());
+            if let Ok(Some(user)) = user_repo.get_by_id(user_id).await {
+                return user.role;
+            }
+        }
+        Role::Guest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poll_screen_exists() {
+        let _ = PollScreen;
+    }
+}
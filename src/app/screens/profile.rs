@@ -11,6 +11,7 @@ use crate::error::Result;
 use crate::server::{EchoMode, TelnetSession};
 use crate::template::Value;
 use crate::terminal::TerminalProfile;
+use crate::twit::{TwitFilterService, TwitTarget};
 
 /// Profile screen handler.
 pub struct ProfileScreen;
@@ -75,10 +76,11 @@ impl ProfileScreen {
             ctx.send(
                 session,
                 &format!(
-                    "[E]={} [P]={} [S]={} [Q]={}: ",
+                    "[E]={} [P]={} [S]={} [T]={} [Q]={}: ",
                     ctx.i18n.t("profile.edit"),
                     ctx.i18n.t("profile.change_password"),
                     ctx.i18n.t("menu.settings"),
+                    ctx.i18n.t("twit.title"),
                     ctx.i18n.t("common.back")
                 ),
             )
@@ -100,11 +102,113 @@ impl ProfileScreen {
                         return Ok(result);
                     }
                 }
+                "t" => {
+                    Self::twit_filters(ctx, session, user_id).await?;
+                }
                 _ => {}
             }
         }
     }
 
+    /// Manage the user's twit filters (hidden users and keywords).
+    async fn twit_filters(
+        ctx: &mut ScreenContext,
+        session: &mut TelnetSession,
+        user_id: i64,
+    ) -> Result<()> {
+        loop {
+            let filters = TwitFilterService::new(ctx.db.pool()).list(user_id).await?;
+
+            ctx.send_line(session, "").await?;
+            ctx.send_line(session, &format!("=== {} ===", ctx.i18n.t("twit.title")))
+                .await?;
+            ctx.send_line(session, "").await?;
+            if filters.is_empty() {
+                ctx.send_line(session, ctx.i18n.t("twit.no_filters"))
+                    .await?;
+            }
+            for (i, filter) in filters.iter().enumerate() {
+                let kind = match filter.target() {
+                    TwitTarget::User(_) => "twit.kind_user",
+                    TwitTarget::Keyword(_) => "twit.kind_keyword",
+                };
+                ctx.send_line(
+                    session,
+                    &format!("  [{}] {}: {}", i + 1, ctx.i18n.t(kind), filter.value()),
+                )
+                .await?;
+            }
+            ctx.send_line(session, "").await?;
+            let status = if session.show_hidden() {
+                "twit.status_shown"
+            } else {
+                "twit.status_hidden"
+            };
+            ctx.send_line(session, ctx.i18n.t(status)).await?;
+            ctx.send(
+                session,
+                &format!(
+                    "{}[X]={} [Q]={}: ",
+                    ctx.i18n.t("twit.menu"),
+                    ctx.show_hidden_label(session),
+                    ctx.i18n.t("common.back")
+                ),
+            )
+            .await?;
+
+            let input = ctx.read_line(session).await?;
+            let result = match input.trim().to_ascii_lowercase().as_str() {
+                "u" => {
+                    ctx.send(session, &format!("{}: ", ctx.i18n.t("profile.username")))
+                        .await?;
+                    let username = ctx.read_line(session).await?;
+                    if username.trim().is_empty() {
+                        continue;
+                    }
+                    TwitFilterService::new(ctx.db.pool())
+                        .add_user(user_id, &username)
+                        .await
+                        .map(|_| "twit.added")
+                }
+                "k" => {
+                    ctx.send(session, &format!("{}: ", ctx.i18n.t("twit.keyword")))
+                        .await?;
+                    let keyword = ctx.read_line(session).await?;
+                    if keyword.trim().is_empty() {
+                        continue;
+                    }
+                    TwitFilterService::new(ctx.db.pool())
+                        .add_keyword(user_id, &keyword)
+                        .await
+                        .map(|_| "twit.added")
+                }
+                "d" => {
+                    ctx.send(session, &format!("{}: ", ctx.i18n.t("twit.filter_number")))
+                        .await?;
+                    let input = ctx.read_line(session).await?;
+                    let filter = match input.trim().parse::<usize>() {
+                        Ok(n) if n > 0 && n <= filters.len() => &filters[n - 1],
+                        _ => continue,
+                    };
+                    TwitFilterService::new(ctx.db.pool())
+                        .remove(user_id, filter.id)
+                        .await
+                        .map(|_| "twit.deleted")
+                }
+                "x" => {
+                    session.set_show_hidden(!session.show_hidden());
+                    continue;
+                }
+                _ => return Ok(()),
+            };
+
+            match result {
+                Ok(key) => ctx.send_line(session, ctx.i18n.t(key)).await?,
+                Err(e) => ctx.send_line(session, &format!("Error: {}", e)).await?,
+            }
+        }
+    }
+
     /// Edit profile.
     async fn edit_profile(
         ctx: &mut ScreenContext,
@@ -286,7 +390,13 @@ impl ProfileScreen {
         user_id: i64,
     ) -> Result<Option<ScreenResult>> {
         // Get current settings
-        let (current_language, current_encoding, current_terminal, current_auto_paging) = {
+        let (
+            current_language,
+            current_encoding,
+            current_terminal,
+            current_auto_paging,
+            current_show_colors,
+        ) = {
             let user_repo = UserRepository::new(ctx.db.pool());
             let user = match user_repo.get_by_id(user_id).await? {
                 Some(u) => u,
@@ -297,6 +407,7 @@ impl ProfileScreen {
                 user.encoding,
                 user.terminal.clone(),
                 user.auto_paging,
+                user.show_colors,
             )
         };
 
@@ -350,6 +461,19 @@ impl ProfileScreen {
             ),
         )
         .await?;
+        ctx.send_line(
+            session,
+            &format!(
+                "{}: {}",
+                ctx.i18n.t("settings.show_colors"),
+                if current_show_colors {
+                    ctx.i18n.t("settings.enabled")
+                } else {
+                    ctx.i18n.t("settings.disabled")
+                }
+            ),
+        )
+        .await?;
         ctx.send_line(session, "").await?;
 
         // Language selection
@@ -488,13 +612,46 @@ impl ProfileScreen {
             _ => current_auto_paging,
         };
 
+        // Color display selection
+        ctx.send_line(session, "").await?;
+        ctx.send_line(session, &format!("{}:", ctx.i18n.t("settings.show_colors")))
+            .await?;
+        ctx.send_line(
+            session,
+            &format!("  [1] {}", ctx.i18n.t("settings.show_colors_on")),
+        )
+        .await?;
+        ctx.send_line(
+            session,
+            &format!("  [2] {}", ctx.i18n.t("settings.show_colors_off")),
+        )
+        .await?;
+        ctx.send(
+            session,
+            &format!(
+                "{} [{}]: ",
+                ctx.i18n.t("common.number"),
+                if current_show_colors { "1" } else { "2" }
+            ),
+        )
+        .await?;
+
+        let colors_input = ctx.read_line(session).await?;
+        let new_show_colors = match colors_input.trim() {
+            "1" => true,
+            "2" => false,
+            _ => current_show_colors,
+        };
+
         // Check if anything changed
         let terminal_changed = new_terminal.is_some() && actual_new_terminal != current_terminal;
         let auto_paging_changed = new_auto_paging != current_auto_paging;
+        let show_colors_changed = new_show_colors != current_show_colors;
         if new_language == current_language
             && new_encoding == current_encoding
             && !terminal_changed
             && !auto_paging_changed
+            && !show_colors_changed
         {
             ctx.send_line(session, "").await?;
             return Ok(None);
@@ -514,6 +671,10 @@ impl ProfileScreen {
             update = update.auto_paging(new_auto_paging);
         }
 
+        if show_colors_changed {
+            update = update.show_colors(new_show_colors);
+        }
+
         match user_repo.update(user_id, &update).await {
             Ok(_) => {
                 ctx.send_line(session, "").await?;
@@ -0,0 +1,209 @@
+//! QWK offline mail screen handler.
+
+use super::common::ScreenContext;
+use super::ScreenResult;
+use crate::db::{User, UserRepository};
+use crate::error::{HobbsError, Result};
+use crate::qwk::{PacketOptions, QwkService};
+use crate::server::TelnetSession;
+use crate::xmodem::{xmodem_receive, xmodem_send, TransferError};
+
+/// QWK offline mail screen handler.
+pub struct QwkScreen;
+
+impl QwkScreen {
+    /// Run the QWK offline mail screen.
+    pub async fn run(ctx: &mut ScreenContext, session: &mut TelnetSession) -> Result<ScreenResult> {
+        let user_id = match session.user_id() {
+            Some(id) => id,
+            None => {
+                ctx.send_line(session, ctx.i18n.t("menu.login_required"))
+                    .await?;
+                return Ok(ScreenResult::Back);
+            }
+        };
+
+        loop {
+            let user = match UserRepository::new(ctx.db.pool()).get_by_id(user_id).await? {
+                Some(user) => user,
+                None => {
+                    ctx.send_line(session, ctx.i18n.t("error.user_not_found"))
+                        .await?;
+                    return Ok(ScreenResult::Back);
+                }
+            };
+
+            ctx.send_line(session, "").await?;
+            ctx.send_line(session, &format!("=== {} ===", ctx.i18n.t("qwk.title")))
+                .await?;
+            let prompt = format!(
+                "[D]={} [U]={} [Q]={}: ",
+                ctx.i18n.t("qwk.download"),
+                ctx.i18n.t("qwk.upload"),
+                ctx.i18n.t("common.back")
+            );
+            ctx.send(session, &prompt).await?;
+            let input = ctx.read_line(session).await?;
+
+            match input.trim().to_ascii_lowercase().as_str() {
+                "q" | "" => return Ok(ScreenResult::Back),
+                "d" => Self::download(ctx, session, &user).await?,
+                "u" => Self::upload(ctx, session, &user).await?,
+                _ => {}
+            }
+        }
+    }
+
+    /// Send a QWK packet and advance read pointers once it has been received.
+    async fn download(
+        ctx: &mut ScreenContext,
+        session: &mut TelnetSession,
+        user: &User,
+    ) -> Result<()> {
+        let options = PacketOptions::from_config(&ctx.config);
+        let service = QwkService::new(&ctx.db);
+        let packet = service.build_packet(user, &options).await?;
+
+        ctx.send_line(session, "").await?;
+        if packet.message_count == 0 {
+            ctx.send_line(session, ctx.i18n.t("qwk.no_messages"))
+                .await?;
+            ctx.wait_for_enter(session).await?;
+            return Ok(());
+        }
+
+        ctx.send_line(
+            session,
+            &ctx.i18n.t_with(
+                "qwk.packet_ready",
+                &[
+                    ("filename", &packet.filename),
+                    ("count", &packet.message_count.to_string()),
+                    ("size", &packet.data.len().to_string()),
+                ],
+            ),
+        )
+        .await?;
+        ctx.send_line(session, ctx.i18n.t("file.xmodem_start_download"))
+            .await?;
+        ctx.send_line(session, "").await?;
+
+        // Small delay to let user start their XMODEM receiver
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+
+        match xmodem_send(session.stream_mut(), &packet.data).await {
+            Ok(_) => {
+                service.commit(user.id, &packet).await?;
+                ctx.send_line(session, "").await?;
+                ctx.send_line(session, ctx.i18n.t("qwk.download_complete"))
+                    .await?;
+            }
+            Err(e) => {
+                ctx.send_line(session, "").await?;
+                let error_msg = match e {
+                    TransferError::Cancelled => ctx.i18n.t("file.xmodem_cancelled").to_string(),
+                    TransferError::Timeout => ctx.i18n.t("file.xmodem_timeout").to_string(),
+                    _ => format!("{}: {}", ctx.i18n.t("file.xmodem_failed"), e),
+                };
+                ctx.send_line(session, &error_msg).await?;
+                ctx.send_line(session, ctx.i18n.t("qwk.pointers_unchanged"))
+                    .await?;
+            }
+        }
+
+        ctx.wait_for_enter(session).await?;
+        Ok(())
+    }
+
+    /// Receive a REP packet and turn it into posts and mail.
+    async fn upload(
+        ctx: &mut ScreenContext,
+        session: &mut TelnetSession,
+        user: &User,
+    ) -> Result<()> {
+        let options = PacketOptions::from_config(&ctx.config);
+        let filename = format!("{}.REP", options.bbs_id);
+
+        ctx.send_line(session, "").await?;
+        ctx.send_line(
+            session,
+            &ctx.i18n
+                .t_with("qwk.upload_prompt", &[("filename", &filename)]),
+        )
+        .await?;
+        ctx.send_line(session, ctx.i18n.t("file.xmodem_start_upload"))
+            .await?;
+        ctx.send_line(session, &format!("({})", ctx.i18n.t("file.xmodem_waiting")))
+            .await?;
+
+        // Small delay to let user start their XMODEM sender
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+
+        let max_size = (ctx.config.files.max_upload_size_mb as usize) * 1024 * 1024;
+        let data = match xmodem_receive(session.stream_mut(), max_size).await {
+            Ok(data) => data,
+            Err(e) => {
+                ctx.send_line(session, "").await?;
+                let error_msg = match e {
+                    TransferError::Cancelled => ctx.i18n.t("file.xmodem_cancelled").to_string(),
+                    TransferError::Timeout => ctx.i18n.t("file.xmodem_timeout").to_string(),
+                    TransferError::FileTooLarge(_) => {
+                        ctx.i18n.t("file.xmodem_file_too_large").to_string()
+                    }
+                    _ => format!("{}: {}", ctx.i18n.t("file.xmodem_failed"), e),
+                };
+                ctx.send_line(session, &error_msg).await?;
+                ctx.wait_for_enter(session).await?;
+                return Ok(());
+            }
+        };
+
+        ctx.send_line(session, "").await?;
+        match QwkService::new(&ctx.db)
+            .import_reply(user, &options, &data)
+            .await
+        {
+            Ok(result) => {
+                ctx.send_line(
+                    session,
+                    &ctx.i18n.t_with(
+                        "qwk.import_result",
+                        &[
+                            ("posts", &result.posts.to_string()),
+                            ("mails", &result.mails.to_string()),
+                        ],
+                    ),
+                )
+                .await?;
+                if result.pending > 0 {
+                    ctx.send_line(
+                        session,
+                        &ctx.i18n.t_with(
+                            "qwk.import_pending",
+                            &[("count", &result.pending.to_string())],
+                        ),
+                    )
+                    .await?;
+                }
+                for failure in &result.failed {
+                    ctx.send_line(
+                        session,
+                        &format!("{}: {}", ctx.i18n.t("qwk.import_failed"), failure),
+                    )
+                    .await?;
+                }
+            }
+            Err(HobbsError::Qwk(e)) => {
+                ctx.send_line(
+                    session,
+                    &format!("{}: {}", ctx.i18n.t("qwk.invalid_packet"), e),
+                )
+                .await?;
+            }
+            Err(e) => return Err(e),
+        }
+
+        ctx.wait_for_enter(session).await?;
+        Ok(())
+    }
+}
@@ -10,7 +10,9 @@ use tokio::time::timeout;
 use tracing::{error, info, warn};
 
 use super::menu::{MenuAction, MenuItems};
+use super::screens::{next_notification, notification_notice};
 use crate::auth::{verify_password, LimitResult, LoginLimiter, RegistrationRequest};
+use crate::board::NewscanService;
 use crate::chat::ChatRoomManager;
 use crate::config::Config;
 use crate::datetime::format_datetime_default;
@@ -18,12 +20,12 @@ use crate::db::{Database, Role, UserRepository};
 use crate::error::{HobbsError, Result};
 use crate::i18n::{I18n, I18nManager};
 use crate::mail::MailRepository;
+use crate::notify::{Notification, NotificationService};
 use crate::rate_limit::RateLimiters;
-use crate::screen::{create_screen_from_profile, Screen};
+use crate::screen::{create_screen_from_profile, LightbarItem, Screen};
 use crate::server::{
-    convert_caret_escape, encode_for_client, initial_negotiation, process_output_mode,
-    CharacterEncoding, EchoMode, InputResult, LineBuffer, SessionManager, SessionState,
-    TelnetParser, TelnetSession,
+    encode_for_client, initial_negotiation, process_output_mode, CharacterEncoding, EchoMode,
+    InputResult, LineBuffer, SessionManager, SessionState, TelnetParser, TelnetSession,
 };
 use crate::template::{create_system_context, TemplateContext, TemplateLoader, Value};
 use crate::terminal::TerminalProfile;
@@ -195,6 +197,7 @@ impl SessionHandler {
                 }
                 SessionState::Login => {
                     if self.handle_login(session).await? {
+                        self.run_login_newscan(session).await?;
                         session.set_state(SessionState::MainMenu);
                     } else {
                         session.set_state(SessionState::Welcome);
@@ -429,7 +432,6 @@ Select language / Gengo sentaku:
         let content = self
             .template_loader
             .render("welcome", self.profile.width, &context)?;
-        let content = convert_caret_escape(&content);
         self.send(session, &content).await
     }
 
@@ -598,6 +600,25 @@ Select language / Gengo sentaku:
         }
     }
 
+    /// Run the newscan right after login if the user has enabled it.
+    async fn run_login_newscan(&mut self, session: &mut TelnetSession) -> Result<()> {
+        let Some(user_id) = session.user_id() else {
+            return Ok(());
+        };
+        match NewscanService::new(&self.db).scan_on_login(user_id).await {
+            Ok(true) => {}
+            Ok(false) => return Ok(()),
+            Err(e) => {
+                warn!("Failed to load newscan settings: {}", e);
+                return Ok(());
+            }
+        }
+
+        let mut screen_ctx = self.create_screen_context();
+        super::screens::BoardScreen::run_all_unread_batch_read(&mut screen_ctx, session).await?;
+        Ok(())
+    }
+
     /// Handle registration.
     async fn handle_registration(&mut self, session: &mut TelnetSession) -> Result<bool> {
         self.send_line(session, self.i18n.t("register.title"))
@@ -713,15 +734,31 @@ Select language / Gengo sentaku:
     /// Handle main menu.
     async fn handle_main_menu(&mut self, session: &mut TelnetSession) -> Result<MenuResult> {
         // Show main menu
-        self.show_main_menu(session).await?;
+        let mut screen_ctx = self.create_screen_context();
+        let lightbar = screen_ctx.lightbar_enabled(session);
+        self.show_main_menu(session, lightbar).await?;
+        screen_ctx.show_pending_notifications(session).await?;
+
+        let is_logged_in = session.is_logged_in();
+        let is_admin = self.is_admin(session).await;
 
         // Get user input
-        self.send(session, "> ").await?;
-        let input = self.read_line(session).await?;
+        let input = if lightbar {
+            let menu_items = if is_logged_in {
+                MenuItems::for_member(is_admin)
+            } else {
+                MenuItems::for_guest()
+            };
+            let mut bar = screen_ctx
+                .lightbar(self.main_menu_lightbar_items(&menu_items, is_logged_in));
+            let prompt = self.i18n.t("menu.select_prompt").to_string();
+            screen_ctx.read_lightbar(session, &mut bar, &prompt).await?
+        } else {
+            self.send(session, "> ").await?;
+            self.read_line(session).await?
+        };
 
         // Parse action
-        let is_logged_in = session.is_logged_in();
-        let is_admin = self.is_admin(session).await;
         let action = MenuAction::parse(&input, is_logged_in, is_admin);
 
         // Handle action
@@ -791,6 +828,18 @@ Select language / Gengo sentaku:
                     _ => {}
                 }
             }
+            MenuAction::Poll => {
+                let mut screen_ctx = self.create_screen_context();
+                match super::screens::PollScreen::run(&mut screen_ctx, session).await? {
+                    super::screens::ScreenResult::Logout => {
+                        return Ok(MenuResult::Logout);
+                    }
+                    super::screens::ScreenResult::Quit => {
+                        return Ok(MenuResult::Quit);
+                    }
+                    _ => {}
+                }
+            }
             MenuAction::Admin => {
                 if is_admin {
                     session.set_state(SessionState::Admin);
@@ -829,12 +878,72 @@ Select language / Gengo sentaku:
         Ok(MenuResult::Continue)
     }
 
+    /// Build lightbar entries for the main menu.
+    ///
+    /// Each entry's value is the hotkey, so the choice goes through
+    /// `MenuAction::parse` just like typed input.
+    fn main_menu_lightbar_items(
+        &self,
+        menu_items: &MenuItems,
+        is_logged_in: bool,
+    ) -> Vec<LightbarItem> {
+        let entries = [
+            (menu_items.board, MenuAction::Board, "menu.board", "menu.board_desc"),
+            (menu_items.chat, MenuAction::Chat, "menu.chat", "menu.chat_desc"),
+            (menu_items.mail, MenuAction::Mail, "menu.mail", "menu.mail_desc"),
+            (menu_items.file, MenuAction::File, "menu.file", "menu.file_desc"),
+            (true, MenuAction::Script, "menu.door", "menu.door_desc"),
+            (menu_items.news, MenuAction::News, "menu.news", "menu.news_desc"),
+            (menu_items.profile, MenuAction::Profile, "menu.profile", "menu.profile_desc"),
+            (
+                menu_items.member_list,
+                MenuAction::MemberList,
+                "menu.user_list",
+                "menu.user_list_desc",
+            ),
+            (menu_items.poll, MenuAction::Poll, "menu.poll", "menu.poll_desc"),
+            (menu_items.admin, MenuAction::Admin, "menu.admin", "menu.admin_desc"),
+            (menu_items.help, MenuAction::Help, "common.help", ""),
+            (menu_items.login, MenuAction::Login, "auth.login", ""),
+            (menu_items.register, MenuAction::Register, "auth.register", ""),
+            (menu_items.logout, MenuAction::Logout, "auth.logout", ""),
+            (menu_items.quit && !is_logged_in, MenuAction::Quit, "common.quit", ""),
+        ];
+
+        let wide = self.profile.width >= 80;
+        let cjk_width = self.profile.cjk_width as usize;
+        entries
+            .into_iter()
+            .filter(|(available, ..)| *available)
+            .map(|(_, action, name_key, desc_key)| {
+                let key = action.key();
+                let name = self.i18n.t(name_key);
+                let label = if wide && !desc_key.is_empty() {
+                    format!(
+                        "[{}] {} - {}",
+                        key,
+                        crate::template::pad_to_width(name, 14, cjk_width),
+                        self.i18n.t(desc_key)
+                    )
+                } else {
+                    format!("[{}] {}", key, name)
+                };
+                let hotkey = key.chars().next().unwrap_or_default();
+                LightbarItem::new(key, label).with_hotkey(hotkey)
+            })
+            .collect()
+    }
+
     /// Show the main menu.
-    async fn show_main_menu(&self, session: &mut TelnetSession) -> Result<()> {
+    ///
+    /// When `lightbar` is set, the template omits its hotkey list because
+    /// the lightbar draws the entries itself.
+    async fn show_main_menu(&self, session: &mut TelnetSession, lightbar: bool) -> Result<()> {
         let is_logged_in = session.is_logged_in();
         let is_admin = self.is_admin(session).await;
 
         let mut context = self.create_context();
+        context.set("lightbar", Value::bool(lightbar));
 
         // Set user info
         if let Some(user_id) = session.user_id() {
@@ -853,6 +962,7 @@ Select language / Gengo sentaku:
                     Role::SysOp => self.i18n.t("role.sysop"),
                 };
                 context.set("user.role_name", Value::string(role_name.to_string()));
+                context.set("user.role", Value::string(user.role.as_str()));
 
                 // Set unread mail count
                 let mail_repo = MailRepository::new(self.db.pool());
@@ -874,6 +984,7 @@ Select language / Gengo sentaku:
                     "user.role_name",
                     Value::string(self.i18n.t("role.guest").to_string()),
                 );
+                context.set("user.role", Value::string(Role::Guest.as_str()));
                 context.set("user.unread_mail", Value::number(0));
             }
         } else {
@@ -892,6 +1003,7 @@ Select language / Gengo sentaku:
                 "user.role_name",
                 Value::string(self.i18n.t("role.guest").to_string()),
             );
+            context.set("user.role", Value::string(Role::Guest.as_str()));
             context.set("user.unread_mail", Value::number(0));
         }
 
@@ -913,6 +1025,7 @@ Select language / Gengo sentaku:
         context.set("menu.news", Value::bool(menu_items.news));
         context.set("menu.profile", Value::bool(menu_items.profile));
         context.set("menu.member_list", Value::bool(menu_items.member_list));
+        context.set("menu.poll", Value::bool(menu_items.poll));
         context.set("menu.admin", Value::bool(menu_items.admin));
         context.set("menu.help", Value::bool(menu_items.help));
         context.set("menu.logout", Value::bool(menu_items.logout));
@@ -922,7 +1035,6 @@ Select language / Gengo sentaku:
         let content = self
             .template_loader
             .render("main_menu", self.profile.width, &context)?;
-        let content = convert_caret_escape(&content);
         self.send(session, &content).await
     }
 
@@ -932,7 +1044,6 @@ Select language / Gengo sentaku:
         let content = self
             .template_loader
             .render("help", self.profile.width, &context)?;
-        let content = convert_caret_escape(&content);
         self.send(session, &content).await?;
 
         // Wait for key press
@@ -958,6 +1069,7 @@ Select language / Gengo sentaku:
     /// Create a template context.
     fn create_context(&self) -> TemplateContext {
         let mut context = create_system_context(Arc::clone(&self.i18n));
+        context.set_timezone(self.config.server.timezone.clone());
         context.set("bbs.name", Value::string(self.config.bbs.name.clone()));
         context.set(
             "bbs.description",
@@ -1038,10 +1150,19 @@ Select language / Gengo sentaku:
             self.config.server.read_timeout_secs
         };
         let read_timeout = Duration::from_secs(timeout_secs);
+        let mut notices = session.user_id().map(|user_id| {
+            (user_id, self.session_manager.notification_hub().subscribe())
+        });
 
         loop {
             // Apply timeout to each read operation
-            let read_result = timeout(read_timeout, session.stream_mut().read(&mut buf)).await;
+            let read_result = tokio::select! {
+                result = timeout(read_timeout, session.stream_mut().read(&mut buf)) => result,
+                notification = next_notification(&mut notices) => {
+                    self.show_live_notification(session, &notification).await?;
+                    continue;
+                }
+            };
 
             match read_result {
                 Ok(Ok(0)) => {
@@ -1072,6 +1193,25 @@ Select language / Gengo sentaku:
         }
     }
 
+    /// Interrupt input to show a live notification, then redraw the
+    /// partially typed line.
+    async fn show_live_notification(
+        &self,
+        session: &mut TelnetSession,
+        notification: &Notification,
+    ) -> Result<()> {
+        let notice = notification_notice(&self.i18n, notification);
+        self.send(session, &format!("\r\n{}\r\n", notice)).await?;
+        if self.line_buffer.echo_mode() == EchoMode::Normal && !self.line_buffer.is_empty() {
+            session.stream_mut().write_all(self.line_buffer.contents()).await?;
+            session.stream_mut().flush().await?;
+        }
+        NotificationService::new(&self.db)
+            .mark_delivered(notification.user_id, notification.id)
+            .await?;
+        Ok(())
+    }
+
     /// Process input bytes and return the line if complete.
     ///
     /// Saves any bytes after the line terminator to pending_bytes for the next read.
@@ -0,0 +1,231 @@
+//! Versioned JSON board archive format.
+//!
+//! Authors are stored by username so that archives can be imported into
+//! another installation. Timestamps are RFC 3339 in UTC.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{HobbsError, Result};
+
+/// Value of the `format` field identifying a board archive.
+pub const ARCHIVE_FORMAT: &str = "hobbs-board-archive";
+
+/// Current archive format version.
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// A board archive holding one or more boards.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoardArchive {
+    /// Always [`ARCHIVE_FORMAT`].
+    pub format: String,
+    /// Format version, [`ARCHIVE_VERSION`] when written by this version.
+    pub version: u32,
+    /// Export timestamp.
+    pub exported_at: String,
+    /// Archived boards.
+    pub boards: Vec<ArchivedBoard>,
+}
+
+/// A board with its threads and posts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedBoard {
+    /// Board name.
+    pub name: String,
+    /// Board description.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Board type ("thread" or "flat").
+    pub board_type: String,
+    /// Minimum role to read ("guest", "member", "subop" or "sysop").
+    pub min_read_role: String,
+    /// Minimum role to write.
+    pub min_write_role: String,
+    /// Display order.
+    #[serde(default)]
+    pub sort_order: i32,
+    /// Whether the board is active.
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+    /// Whether paging is disabled.
+    #[serde(default)]
+    pub disable_paging: bool,
+    /// Whether ANSI color codes in posts are shown.
+    #[serde(default)]
+    pub allow_color: bool,
+    /// Whether member posts need approval.
+    #[serde(default)]
+    pub is_moderated: bool,
+    /// Board creation timestamp.
+    pub created_at: String,
+    /// Threads (thread-based boards).
+    #[serde(default)]
+    pub threads: Vec<ArchivedThread>,
+    /// Posts (flat boards).
+    #[serde(default)]
+    pub posts: Vec<ArchivedPost>,
+}
+
+/// A thread with its posts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedThread {
+    /// Thread title.
+    pub title: String,
+    /// Author's username.
+    pub author: String,
+    /// Author's nickname.
+    #[serde(default)]
+    pub author_name: Option<String>,
+    /// Whether the thread is pinned.
+    #[serde(default)]
+    pub is_pinned: bool,
+    /// Whether the thread is locked.
+    #[serde(default)]
+    pub is_locked: bool,
+    /// Thread creation timestamp.
+    pub created_at: String,
+    /// Last update timestamp.
+    pub updated_at: String,
+    /// Posts, oldest first.
+    pub posts: Vec<ArchivedPost>,
+}
+
+/// A post.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedPost {
+    /// Post ID in the exporting installation, used to link replies.
+    pub id: i64,
+    /// ID of the post this one replies to (same numbering as `id`).
+    #[serde(default)]
+    pub parent_id: Option<i64>,
+    /// Author's username.
+    pub author: String,
+    /// Author's nickname.
+    #[serde(default)]
+    pub author_name: Option<String>,
+    /// Post title (flat boards).
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Post body.
+    pub body: String,
+    /// Post creation timestamp.
+    pub created_at: String,
+    /// Timestamp of the last edit.
+    #[serde(default)]
+    pub edited_at: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl BoardArchive {
+    /// Create an empty archive stamped with the current time.
+    pub fn new() -> Self {
+        Self {
+            format: ARCHIVE_FORMAT.to_string(),
+            version: ARCHIVE_VERSION,
+            exported_at: Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+            boards: Vec::new(),
+        }
+    }
+
+    /// Parse an archive, checking the format and version.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let archive: BoardArchive = serde_json::from_str(json)
+            .map_err(|e| HobbsError::Archive(format!("invalid archive: {}", e)))?;
+        archive.check()?;
+        Ok(archive)
+    }
+
+    /// Serialize the archive as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| HobbsError::Archive(e.to_string()))
+    }
+
+    /// Check the format and version fields.
+    pub fn check(&self) -> Result<()> {
+        if self.format != ARCHIVE_FORMAT {
+            return Err(HobbsError::Archive(format!(
+                "not a board archive: {}",
+                self.format
+            )));
+        }
+        if self.version == 0 || self.version > ARCHIVE_VERSION {
+            return Err(HobbsError::Archive(format!(
+                "unsupported archive version: {}",
+                self.version
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Default for BoardArchive {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Convert an RFC 3339 archive timestamp to the database format
+/// (UTC, `YYYY-MM-DD HH:MM:SS`).
+pub fn parse_timestamp(timestamp: &str) -> Result<String> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .map(|dt| dt.with_timezone(&Utc).format("%Y-%m-%d %H:%M:%S").to_string())
+        .map_err(|_| HobbsError::Archive(format!("invalid timestamp: {}", timestamp)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut archive = BoardArchive::new();
+        archive.boards.push(ArchivedBoard {
+            name: "general".to_string(),
+            description: None,
+            board_type: "flat".to_string(),
+            min_read_role: "guest".to_string(),
+            min_write_role: "member".to_string(),
+            sort_order: 0,
+            is_active: true,
+            disable_paging: false,
+            allow_color: false,
+            is_moderated: false,
+            created_at: "2024-01-15T10:30:00Z".to_string(),
+            threads: Vec::new(),
+            posts: Vec::new(),
+        });
+
+        let parsed = BoardArchive::from_json(&archive.to_json().unwrap()).unwrap();
+        assert_eq!(parsed.version, ARCHIVE_VERSION);
+        assert_eq!(parsed.boards[0].name, "general");
+    }
+
+    #[test]
+    fn test_rejects_other_formats() {
+        let json = r#"{"format":"other","version":1,"exported_at":"","boards":[]}"#;
+        assert!(matches!(
+            BoardArchive::from_json(json),
+            Err(HobbsError::Archive(_))
+        ));
+
+        let json = r#"{"format":"hobbs-board-archive","version":99,"exported_at":"","boards":[]}"#;
+        assert!(BoardArchive::from_json(json).is_err());
+        assert!(BoardArchive::from_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(
+            parse_timestamp("2024-01-15T10:30:00Z").unwrap(),
+            "2024-01-15 10:30:00"
+        );
+        assert_eq!(
+            parse_timestamp("2024-01-15T19:30:00+09:00").unwrap(),
+            "2024-01-15 10:30:00"
+        );
+        assert!(parse_timestamp("2024-01-15 10:30:00").is_err());
+    }
+}
@@ -0,0 +1,139 @@
+//! mbox export of board archives.
+//!
+//! Writes mboxrd: one UTF-8 message per post, with body lines that start
+//! with `From ` (after any number of `>`) quoted by an extra `>`.
+//! Message-IDs match the NNTP server's, so replies thread in mail clients.
+
+use chrono::{DateTime, Utc};
+
+use super::format::{ArchivedBoard, ArchivedPost, BoardArchive};
+use crate::nntp::{encode_header, format_mailbox, message_id};
+
+/// Write every post in an archive as an mbox mailbox.
+pub fn write_mbox(archive: &BoardArchive, domain: &str) -> String {
+    let mut out = String::new();
+    for board in &archive.boards {
+        for thread in &board.threads {
+            for (i, post) in thread.posts.iter().enumerate() {
+                let subject = if i == 0 {
+                    thread.title.clone()
+                } else {
+                    format!("Re: {}", thread.title)
+                };
+                write_message(&mut out, board, post, &subject, domain);
+            }
+        }
+        for post in &board.posts {
+            let subject = post.title.clone().unwrap_or_default();
+            write_message(&mut out, board, post, &subject, domain);
+        }
+    }
+    out
+}
+
+fn write_message(
+    out: &mut String,
+    board: &ArchivedBoard,
+    post: &ArchivedPost,
+    subject: &str,
+    domain: &str,
+) {
+    let date = DateTime::parse_from_rfc3339(&post.created_at)
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_default();
+    let display_name = post.author_name.as_deref().unwrap_or(&post.author);
+
+    out.push_str(&format!(
+        "From {}@{} {}\n",
+        post.author,
+        domain,
+        date.format("%a %b %e %H:%M:%S %Y")
+    ));
+    out.push_str(&format!(
+        "From: {}\n",
+        format_mailbox(display_name, &post.author, domain)
+    ));
+    out.push_str(&format!("Date: {}\n", date.to_rfc2822()));
+    out.push_str(&format!("Subject: {}\n", encode_header(subject)));
+    out.push_str(&format!("Message-ID: {}\n", message_id(post.id, domain)));
+    if let Some(parent_id) = post.parent_id {
+        let parent = message_id(parent_id, domain);
+        out.push_str(&format!("In-Reply-To: {}\n", parent));
+        out.push_str(&format!("References: {}\n", parent));
+    }
+    out.push_str(&format!("X-Board: {}\n", encode_header(&board.name)));
+    out.push_str("MIME-Version: 1.0\n");
+    out.push_str("Content-Type: text/plain; charset=UTF-8\n");
+    out.push_str("Content-Transfer-Encoding: 8bit\n");
+    out.push('\n');
+
+    for line in post.body.lines() {
+        if line.trim_start_matches('>').starts_with("From ") {
+            out.push('>');
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    out.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::archive::ArchivedThread;
+
+    fn post(id: i64, parent_id: Option<i64>, body: &str) -> ArchivedPost {
+        ArchivedPost {
+            id,
+            parent_id,
+            author: "alice".to_string(),
+            author_name: Some("アリス".to_string()),
+            title: None,
+            body: body.to_string(),
+            created_at: "2024-01-15T10:30:00Z".to_string(),
+            edited_at: None,
+        }
+    }
+
+    #[test]
+    fn test_write_mbox() {
+        let mut archive = BoardArchive::new();
+        archive.boards.push(ArchivedBoard {
+            name: "general".to_string(),
+            description: None,
+            board_type: "thread".to_string(),
+            min_read_role: "guest".to_string(),
+            min_write_role: "member".to_string(),
+            sort_order: 0,
+            is_active: true,
+            disable_paging: false,
+            allow_color: false,
+            is_moderated: false,
+            created_at: "2024-01-15T10:30:00Z".to_string(),
+            threads: vec![ArchivedThread {
+                title: "Hello".to_string(),
+                author: "alice".to_string(),
+                author_name: None,
+                is_pinned: false,
+                is_locked: false,
+                created_at: "2024-01-15T10:30:00Z".to_string(),
+                updated_at: "2024-01-15T10:30:00Z".to_string(),
+                posts: vec![
+                    post(1, None, "first"),
+                    post(2, Some(1), "From here\n>From there"),
+                ],
+            }],
+            posts: Vec::new(),
+        });
+
+        let mbox = write_mbox(&archive, "bbs.example");
+        assert!(mbox.starts_with("From alice@bbs.example Mon Jan 15 10:30:00 2024\n"));
+        assert_eq!(mbox.matches("\nFrom alice@bbs.example ").count(), 1);
+        assert!(mbox.contains("Subject: Hello\n"));
+        assert!(mbox.contains("Subject: Re: Hello\n"));
+        assert!(mbox.contains("Message-ID: <post.2@bbs.example>\n"));
+        assert!(mbox.contains("In-Reply-To: <post.1@bbs.example>\n"));
+        assert!(mbox.contains("\n>From here\n>>From there\n"));
+        assert!(mbox.contains("Date: Mon, 15 Jan 2024 10:30:00 +0000\n"));
+    }
+}
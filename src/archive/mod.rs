@@ -0,0 +1,21 @@
+//! Board archive module for HOBBS.
+//!
+//! This module moves boards between installations and archives old ones:
+//! - Export of boards with threads, posts, authors and timestamps to a
+//!   versioned JSON format
+//! - Export to mbox for mail clients
+//! - Import of JSON archives, mapping authors by username
+//!
+//! Missing authors are created as inactive placeholder accounts.
+
+pub mod format;
+pub mod mbox;
+pub mod repository;
+pub mod service;
+
+pub use format::{
+    ArchivedBoard, ArchivedPost, ArchivedThread, BoardArchive, ARCHIVE_FORMAT, ARCHIVE_VERSION,
+};
+pub use mbox::write_mbox;
+pub use repository::ArchiveRepository;
+pub use service::{ArchiveService, ImportResult};
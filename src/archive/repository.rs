@@ -0,0 +1,91 @@
+//! Archive repository for HOBBS.
+//!
+//! Inserts imported threads and posts with their original timestamps,
+//! which the board repositories always set to the current time.
+
+use super::format::{parse_timestamp, ArchivedPost, ArchivedThread};
+use crate::db::DbPool;
+use crate::{HobbsError, Result};
+
+/// Repository for board archive imports.
+pub struct ArchiveRepository<'a> {
+    pool: &'a DbPool,
+}
+
+impl<'a> ArchiveRepository<'a> {
+    /// Create a new ArchiveRepository with the given database pool.
+    pub fn new(pool: &'a DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Set a board's creation timestamp (RFC 3339).
+    pub async fn set_board_created_at(&self, board_id: i64, created_at: &str) -> Result<()> {
+        sqlx::query("UPDATE boards SET created_at = $1 WHERE id = $2")
+            .bind(parse_timestamp(created_at)?)
+            .bind(board_id)
+            .execute(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Insert a thread and return its ID.
+    ///
+    /// The post count is taken from the archived posts.
+    pub async fn insert_thread(
+        &self,
+        board_id: i64,
+        author_id: i64,
+        thread: &ArchivedThread,
+    ) -> Result<i64> {
+        let id: i64 = sqlx::query_scalar(
+            "INSERT INTO threads (board_id, title, author_id, post_count, created_at,
+                                  updated_at, is_pinned, is_locked)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8) RETURNING id",
+        )
+        .bind(board_id)
+        .bind(&thread.title)
+        .bind(author_id)
+        .bind(thread.posts.len() as i32)
+        .bind(parse_timestamp(&thread.created_at)?)
+        .bind(parse_timestamp(&thread.updated_at)?)
+        .bind(thread.is_pinned)
+        .bind(thread.is_locked)
+        .fetch_one(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(id)
+    }
+
+    /// Insert a post and return its ID.
+    pub async fn insert_post(
+        &self,
+        board_id: i64,
+        thread_id: Option<i64>,
+        parent_id: Option<i64>,
+        author_id: i64,
+        post: &ArchivedPost,
+    ) -> Result<i64> {
+        let edited_at = post.edited_at.as_deref().map(parse_timestamp).transpose()?;
+        let id: i64 = sqlx::query_scalar(
+            "INSERT INTO posts (board_id, thread_id, parent_id, author_id, title, body,
+                                created_at, edited_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8) RETURNING id",
+        )
+        .bind(board_id)
+        .bind(thread_id)
+        .bind(parent_id)
+        .bind(author_id)
+        .bind(&post.title)
+        .bind(&post.body)
+        .bind(parse_timestamp(&post.created_at)?)
+        .bind(edited_at)
+        .fetch_one(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(id)
+    }
+}
@@ -0,0 +1,507 @@
+//! Board export and import.
+
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+
+use tracing::warn;
+
+use super::format::{ArchivedBoard, ArchivedPost, ArchivedThread, BoardArchive};
+use super::repository::ArchiveRepository;
+use crate::admin::{generate_password, DEFAULT_PASSWORD_LENGTH};
+use crate::auth::hash_password;
+use crate::board::{
+    Board, BoardRepository, BoardType, BoardUpdate, NewBoard, Post, PostRepository,
+    SearchRepository, Thread, ThreadRepository,
+};
+use crate::datetime::to_rfc3339;
+use crate::db::{Database, NewUser, Role, User, UserRepository, UserUpdate};
+use crate::{HobbsError, Result};
+
+/// Result of importing a board archive.
+#[derive(Debug, Clone, Default)]
+pub struct ImportResult {
+    /// Number of boards created.
+    pub boards: usize,
+    /// Number of threads created.
+    pub threads: usize,
+    /// Number of posts created.
+    pub posts: usize,
+    /// Usernames of the placeholder accounts created for missing authors.
+    pub placeholders: Vec<String>,
+    /// Boards that could not be imported, with the reason.
+    pub failed: Vec<String>,
+}
+
+/// Service for exporting and importing boards.
+pub struct ArchiveService<'a> {
+    db: &'a Database,
+}
+
+impl<'a> ArchiveService<'a> {
+    /// Create a new ArchiveService.
+    pub fn new(db: &'a Database) -> Self {
+        Self { db }
+    }
+
+    /// Export all boards, including inactive ones.
+    pub async fn export_all(&self) -> Result<BoardArchive> {
+        let boards = BoardRepository::new(self.db.pool()).list_all().await?;
+        self.export(&boards).await
+    }
+
+    /// Export the given boards.
+    pub async fn export_boards(&self, board_ids: &[i64]) -> Result<BoardArchive> {
+        let repo = BoardRepository::new(self.db.pool());
+        let mut boards = Vec::with_capacity(board_ids.len());
+        for &id in board_ids {
+            let board = repo
+                .get_by_id(id)
+                .await?
+                .ok_or_else(|| HobbsError::NotFound("board".to_string()))?;
+            boards.push(board);
+        }
+        self.export(&boards).await
+    }
+
+    async fn export(&self, boards: &[Board]) -> Result<BoardArchive> {
+        let thread_repo = ThreadRepository::new(self.db.pool());
+        let post_repo = PostRepository::new(self.db.pool());
+        let mut authors: HashMap<i64, User> = HashMap::new();
+        let mut archive = BoardArchive::new();
+
+        for board in boards {
+            let mut threads = thread_repo.list_by_board(board.id).await?;
+            threads.sort_by_key(|t| t.id);
+            let mut archived_threads = Vec::with_capacity(threads.len());
+            for thread in threads {
+                let posts = post_repo.list_by_thread(thread.id).await?;
+                archived_threads.push(self.archive_thread(thread, posts, &mut authors).await?);
+            }
+
+            let posts = post_repo.list_by_flat_board(board.id).await?;
+            let posts = self.archive_posts(posts, &mut authors).await?;
+            archive
+                .boards
+                .push(archived_board(board, archived_threads, posts));
+        }
+
+        Ok(archive)
+    }
+
+    /// Export some posts of a board, e.g. before they are pruned.
+    ///
+    /// Thread posts are grouped under their threads; threads keep their
+    /// title and flags but only hold the given posts.
+    pub async fn export_posts(&self, board: &Board, posts: Vec<Post>) -> Result<BoardArchive> {
+        let thread_repo = ThreadRepository::new(self.db.pool());
+        let mut authors: HashMap<i64, User> = HashMap::new();
+
+        let mut by_thread: BTreeMap<i64, Vec<Post>> = BTreeMap::new();
+        let mut flat_posts = Vec::new();
+        for post in posts {
+            match post.thread_id {
+                Some(thread_id) => by_thread.entry(thread_id).or_default().push(post),
+                None => flat_posts.push(post),
+            }
+        }
+
+        let mut archived_threads = Vec::with_capacity(by_thread.len());
+        for (thread_id, posts) in by_thread {
+            let thread = thread_repo
+                .get_by_id(thread_id)
+                .await?
+                .ok_or_else(|| HobbsError::NotFound("thread".to_string()))?;
+            archived_threads.push(self.archive_thread(thread, posts, &mut authors).await?);
+        }
+
+        let flat_posts = self.archive_posts(flat_posts, &mut authors).await?;
+        let mut archive = BoardArchive::new();
+        archive
+            .boards
+            .push(archived_board(board, archived_threads, flat_posts));
+        Ok(archive)
+    }
+
+    async fn archive_thread(
+        &self,
+        thread: Thread,
+        posts: Vec<Post>,
+        authors: &mut HashMap<i64, User>,
+    ) -> Result<ArchivedThread> {
+        let author = self.author(thread.author_id, authors).await?;
+        let (username, nickname) = (author.username.clone(), author.nickname.clone());
+        Ok(ArchivedThread {
+            title: thread.title,
+            author: username,
+            author_name: Some(nickname),
+            is_pinned: thread.is_pinned,
+            is_locked: thread.is_locked,
+            created_at: to_rfc3339(&thread.created_at),
+            updated_at: to_rfc3339(&thread.updated_at),
+            posts: self.archive_posts(posts, authors).await?,
+        })
+    }
+
+    /// Convert posts to their archived form, oldest first.
+    async fn archive_posts(
+        &self,
+        mut posts: Vec<Post>,
+        authors: &mut HashMap<i64, User>,
+    ) -> Result<Vec<ArchivedPost>> {
+        posts.sort_by_key(|p| p.id);
+        let mut archived = Vec::with_capacity(posts.len());
+        for post in posts {
+            let author = self.author(post.author_id, authors).await?;
+            archived.push(ArchivedPost {
+                id: post.id,
+                parent_id: post.parent_id,
+                author: author.username.clone(),
+                author_name: Some(author.nickname.clone()),
+                title: post.title,
+                body: post.body,
+                created_at: to_rfc3339(&post.created_at),
+                edited_at: post.edited_at.as_deref().map(to_rfc3339),
+            });
+        }
+        Ok(archived)
+    }
+
+    async fn author<'m>(
+        &self,
+        user_id: i64,
+        authors: &'m mut HashMap<i64, User>,
+    ) -> Result<&'m User> {
+        if let Entry::Vacant(entry) = authors.entry(user_id) {
+            let user = UserRepository::new(self.db.pool())
+                .get_by_id(user_id)
+                .await?
+                .ok_or_else(|| HobbsError::NotFound("user".to_string()))?;
+            entry.insert(user);
+        }
+        Ok(&authors[&user_id])
+    }
+
+    /// Import the boards in an archive as new boards.
+    ///
+    /// Authors are matched by username. Missing users are created as
+    /// inactive placeholder accounts with a random password, which a SysOp
+    /// can later activate. Boards whose name is already taken are not
+    /// imported; a board that fails partway is removed again.
+    pub async fn import(&self, archive: &BoardArchive) -> Result<ImportResult> {
+        archive.check()?;
+
+        let mut result = ImportResult::default();
+        let mut users: HashMap<String, i64> = HashMap::new();
+        let board_repo = BoardRepository::new(self.db.pool());
+
+        for board in &archive.boards {
+            if board_repo.name_exists(&board.name).await? {
+                result
+                    .failed
+                    .push(format!("{}: board already exists", board.name));
+                continue;
+            }
+            let new_board = match new_board(board) {
+                Ok(new_board) => new_board,
+                Err(e) => {
+                    result.failed.push(format!("{}: {}", board.name, e));
+                    continue;
+                }
+            };
+
+            let created = board_repo.create(&new_board).await?;
+            let (threads, posts) = match self
+                .import_contents(created.id, board, &mut users, &mut result)
+                .await
+            {
+                Ok(counts) => counts,
+                Err(e) => {
+                    warn!("Failed to import board {}: {}", board.name, e);
+                    board_repo.delete(created.id).await?;
+                    result.failed.push(format!("{}: {}", board.name, e));
+                    continue;
+                }
+            };
+
+            result.boards += 1;
+            result.threads += threads;
+            result.posts += posts;
+        }
+
+        SearchRepository::new(self.db.pool()).sync().await?;
+        Ok(result)
+    }
+
+    /// Import the threads and posts of one board.
+    ///
+    /// Returns the number of threads and posts created.
+    async fn import_contents(
+        &self,
+        board_id: i64,
+        board: &ArchivedBoard,
+        users: &mut HashMap<String, i64>,
+        result: &mut ImportResult,
+    ) -> Result<(usize, usize)> {
+        let repo = ArchiveRepository::new(self.db.pool());
+        repo.set_board_created_at(board_id, &board.created_at)
+            .await?;
+        if !board.is_active {
+            BoardRepository::new(self.db.pool())
+                .update(board_id, &BoardUpdate::new().is_active(false))
+                .await?;
+        }
+
+        let mut post_count = 0;
+        for thread in &board.threads {
+            let author_id = self
+                .user_id(&thread.author, thread.author_name.as_deref(), users, result)
+                .await?;
+            let thread_id = repo.insert_thread(board_id, author_id, thread).await?;
+            post_count += self
+                .import_posts(board_id, Some(thread_id), &thread.posts, users, result)
+                .await?;
+        }
+        post_count += self
+            .import_posts(board_id, None, &board.posts, users, result)
+            .await?;
+
+        Ok((board.threads.len(), post_count))
+    }
+
+    async fn import_posts(
+        &self,
+        board_id: i64,
+        thread_id: Option<i64>,
+        posts: &[ArchivedPost],
+        users: &mut HashMap<String, i64>,
+        result: &mut ImportResult,
+    ) -> Result<usize> {
+        let repo = ArchiveRepository::new(self.db.pool());
+        // Archived post ID -> new post ID, for linking replies
+        let mut ids: HashMap<i64, i64> = HashMap::new();
+
+        for post in posts {
+            let author_id = self
+                .user_id(&post.author, post.author_name.as_deref(), users, result)
+                .await?;
+            let parent_id = post.parent_id.and_then(|id| ids.get(&id).copied());
+            let id = repo
+                .insert_post(board_id, thread_id, parent_id, author_id, post)
+                .await?;
+            ids.insert(post.id, id);
+        }
+
+        Ok(posts.len())
+    }
+
+    /// Find a user by username, creating a placeholder if there is none.
+    async fn user_id(
+        &self,
+        username: &str,
+        nickname: Option<&str>,
+        users: &mut HashMap<String, i64>,
+        result: &mut ImportResult,
+    ) -> Result<i64> {
+        if let Some(&id) = users.get(username) {
+            return Ok(id);
+        }
+        if username.is_empty() {
+            return Err(HobbsError::Archive("post without author".to_string()));
+        }
+
+        let repo = UserRepository::new(self.db.pool());
+        let id = match repo.get_by_username(username).await? {
+            Some(user) => user.id,
+            None => {
+                let password = hash_password(&generate_password(DEFAULT_PASSWORD_LENGTH))
+                    .map_err(|e| HobbsError::Auth(e.to_string()))?;
+                let nickname = nickname.filter(|n| !n.is_empty()).unwrap_or(username);
+                let user = repo
+                    .create(&NewUser::new(username, password, nickname).with_role(Role::Member))
+                    .await?;
+                repo.update(user.id, &UserUpdate::new().is_active(false))
+                    .await?;
+                result.placeholders.push(username.to_string());
+                user.id
+            }
+        };
+
+        users.insert(username.to_string(), id);
+        Ok(id)
+    }
+}
+
+/// Build the board to create for an archived board.
+fn new_board(board: &ArchivedBoard) -> Result<NewBoard> {
+    let board_type = BoardType::from_str(&board.board_type).map_err(HobbsError::Archive)?;
+    let min_read_role = Role::from_str(&board.min_read_role).map_err(HobbsError::Archive)?;
+    let min_write_role = Role::from_str(&board.min_write_role).map_err(HobbsError::Archive)?;
+
+    let mut new_board = NewBoard::new(&board.name)
+        .with_board_type(board_type)
+        .with_min_read_role(min_read_role)
+        .with_min_write_role(min_write_role)
+        .with_sort_order(board.sort_order)
+        .with_disable_paging(board.disable_paging)
+        .with_allow_color(board.allow_color)
+        .with_moderated(board.is_moderated);
+    if let Some(description) = &board.description {
+        new_board = new_board.with_description(description);
+    }
+    Ok(new_board)
+}
+
+/// Archived form of a board holding the given threads and posts.
+fn archived_board(
+    board: &Board,
+    threads: Vec<ArchivedThread>,
+    posts: Vec<ArchivedPost>,
+) -> ArchivedBoard {
+    ArchivedBoard {
+        name: board.name.clone(),
+        description: board.description.clone(),
+        board_type: board.board_type.as_str().to_string(),
+        min_read_role: board.min_read_role.as_str().to_string(),
+        min_write_role: board.min_write_role.as_str().to_string(),
+        sort_order: board.sort_order,
+        is_active: board.is_active,
+        disable_paging: board.disable_paging,
+        allow_color: board.allow_color,
+        is_moderated: board.is_moderated,
+        created_at: to_rfc3339(&board.created_at),
+        threads,
+        posts,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::BoardService;
+
+    async fn create_user(db: &Database, name: &str) -> User {
+        UserRepository::new(db.pool())
+            .create(&NewUser::new(name, "hash", name).with_role(Role::Member))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_export_and_import() {
+        let source = Database::open_in_memory().await.unwrap();
+        let alice = create_user(&source, "alice").await;
+        let bob = create_user(&source, "bob").await;
+        let board = BoardRepository::new(source.pool())
+            .create(&NewBoard::new("general").with_description("chat"))
+            .await
+            .unwrap();
+        let service = BoardService::new(&source);
+        let thread = service
+            .create_thread(board.id, "Hello", alice.id, Role::Member)
+            .await
+            .unwrap();
+        let first = service
+            .create_thread_post(thread.id, alice.id, "first post", Role::Member)
+            .await
+            .unwrap();
+        service
+            .create_reply(thread.id, Some(first.id), bob.id, "reply", Role::Member)
+            .await
+            .unwrap();
+
+        let archive = ArchiveService::new(&source).export_all().await.unwrap();
+        assert_eq!(archive.boards.len(), 1);
+        let archived_thread = &archive.boards[0].threads[0];
+        assert_eq!(archived_thread.author, "alice");
+        assert_eq!(archived_thread.posts.len(), 2);
+        assert_eq!(archived_thread.posts[1].parent_id, Some(first.id));
+
+        // bob exists in the target installation, alice does not
+        let target = Database::open_in_memory().await.unwrap();
+        let target_bob = create_user(&target, "bob").await;
+        let json = archive.to_json().unwrap();
+        let result = ArchiveService::new(&target)
+            .import(&BoardArchive::from_json(&json).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(result.boards, 1);
+        assert_eq!(result.threads, 1);
+        assert_eq!(result.posts, 2);
+        assert_eq!(result.placeholders, vec!["alice".to_string()]);
+        assert!(result.failed.is_empty());
+
+        let placeholder = UserRepository::new(target.pool())
+            .get_by_username("alice")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!placeholder.is_active);
+
+        let imported = BoardRepository::new(target.pool())
+            .get_by_name("general")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(imported.description.as_deref(), Some("chat"));
+        let threads = ThreadRepository::new(target.pool())
+            .list_by_board(imported.id)
+            .await
+            .unwrap();
+        assert_eq!(threads[0].post_count, 2);
+        assert_eq!(threads[0].created_at, thread.created_at);
+        let mut posts = PostRepository::new(target.pool())
+            .list_by_thread(threads[0].id)
+            .await
+            .unwrap();
+        posts.sort_by_key(|p| p.id);
+        assert_eq!(posts[0].author_id, placeholder.id);
+        assert_eq!(posts[1].author_id, target_bob.id);
+        assert_eq!(posts[1].parent_id, Some(posts[0].id));
+        assert_eq!(posts[0].created_at, first.created_at);
+
+        // Importing again skips the existing board
+        let result = ArchiveService::new(&target).import(&archive).await.unwrap();
+        assert_eq!(result.boards, 0);
+        assert_eq!(result.failed.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_import_removes_failed_board() {
+        let db = Database::open_in_memory().await.unwrap();
+        let mut archive = BoardArchive::new();
+        archive.boards.push(ArchivedBoard {
+            name: "broken".to_string(),
+            description: None,
+            board_type: "flat".to_string(),
+            min_read_role: "guest".to_string(),
+            min_write_role: "member".to_string(),
+            sort_order: 0,
+            is_active: true,
+            disable_paging: false,
+            allow_color: false,
+            is_moderated: false,
+            created_at: "2024-01-15T10:30:00Z".to_string(),
+            threads: Vec::new(),
+            posts: vec![ArchivedPost {
+                id: 1,
+                parent_id: None,
+                author: "alice".to_string(),
+                author_name: None,
+                title: Some("Hi".to_string()),
+                body: "hello".to_string(),
+                created_at: "yesterday".to_string(),
+                edited_at: None,
+            }],
+        });
+
+        let result = ArchiveService::new(&db).import(&archive).await.unwrap();
+        assert_eq!(result.boards, 0);
+        assert_eq!(result.failed.len(), 1);
+        let board = BoardRepository::new(db.pool())
+            .get_by_name("broken")
+            .await
+            .unwrap();
+        assert!(board.is_none());
+    }
+}
@@ -0,0 +1,375 @@
+//! Board groups (conferences) for HOBBS.
+//!
+//! Boards can be gathered into groups, which are listed first and opened to
+//! show their boards. A group has its own read role; a board in a group is
+//! readable only by users who may read both the group and the board.
+
+use crate::db::{DbPool, Role};
+use crate::{HobbsError, Result};
+
+/// A board group.
+#[derive(Debug, Clone)]
+pub struct BoardGroup {
+    /// Unique group ID.
+    pub id: i64,
+    /// Group name (unique).
+    pub name: String,
+    /// Group description.
+    pub description: Option<String>,
+    /// Minimum role required to see the group and its boards.
+    pub min_read_role: Role,
+    /// Sort order for display.
+    pub sort_order: i32,
+    /// Group creation timestamp.
+    pub created_at: String,
+}
+
+impl BoardGroup {
+    /// Check if a user with the given role can read this group.
+    pub fn can_read(&self, role: Role) -> bool {
+        role.can_access(self.min_read_role)
+    }
+}
+
+/// Data for creating a new board group.
+#[derive(Debug, Clone)]
+pub struct NewBoardGroup {
+    /// Group name.
+    pub name: String,
+    /// Group description.
+    pub description: Option<String>,
+    /// Minimum role required to read (defaults to Guest).
+    pub min_read_role: Role,
+    /// Sort order for display (defaults to 0).
+    pub sort_order: i32,
+}
+
+impl NewBoardGroup {
+    /// Create a new group with minimal required fields.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            min_read_role: Role::Guest,
+            sort_order: 0,
+        }
+    }
+
+    /// Set the description.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the minimum read role.
+    pub fn with_min_read_role(mut self, role: Role) -> Self {
+        self.min_read_role = role;
+        self
+    }
+
+    /// Set the sort order.
+    pub fn with_sort_order(mut self, sort_order: i32) -> Self {
+        self.sort_order = sort_order;
+        self
+    }
+}
+
+/// Data for updating an existing board group.
+#[derive(Debug, Clone, Default)]
+pub struct BoardGroupUpdate {
+    /// New name.
+    pub name: Option<String>,
+    /// New description.
+    pub description: Option<Option<String>>,
+    /// New minimum read role.
+    pub min_read_role: Option<Role>,
+    /// New sort order.
+    pub sort_order: Option<i32>,
+}
+
+impl BoardGroupUpdate {
+    /// Create an empty update.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set new name.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set new description.
+    pub fn description(mut self, description: Option<String>) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    /// Set new minimum read role.
+    pub fn min_read_role(mut self, role: Role) -> Self {
+        self.min_read_role = Some(role);
+        self
+    }
+
+    /// Set new sort order.
+    pub fn sort_order(mut self, sort_order: i32) -> Self {
+        self.sort_order = Some(sort_order);
+        self
+    }
+
+    /// Check if any fields are set.
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.description.is_none()
+            && self.min_read_role.is_none()
+            && self.sort_order.is_none()
+    }
+}
+
+/// Repository for board group CRUD operations.
+pub struct BoardGroupRepository<'a> {
+    pool: &'a DbPool,
+}
+
+impl<'a> BoardGroupRepository<'a> {
+    /// Create a new BoardGroupRepository with the given pool reference.
+    pub fn new(pool: &'a DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Create a new group.
+    pub async fn create(&self, new_group: &NewBoardGroup) -> Result<BoardGroup> {
+        let row: BoardGroupRow = sqlx::query_as(
+            "INSERT INTO board_groups (name, description, min_read_role, sort_order)
+             VALUES ($1, $2, $3, $4)
+             RETURNING id, name, description, min_read_role, sort_order, created_at",
+        )
+        .bind(&new_group.name)
+        .bind(&new_group.description)
+        .bind(new_group.min_read_role.as_str())
+        .bind(new_group.sort_order)
+        .fetch_one(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(row.into_group())
+    }
+
+    /// Get a group by ID.
+    pub async fn get_by_id(&self, id: i64) -> Result<Option<BoardGroup>> {
+        let row: Option<BoardGroupRow> = sqlx::query_as(
+            "SELECT id, name, description, min_read_role, sort_order, created_at
+             FROM board_groups WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(row.map(|r| r.into_group()))
+    }
+
+    /// Get a group by name.
+    pub async fn get_by_name(&self, name: &str) -> Result<Option<BoardGroup>> {
+        let row: Option<BoardGroupRow> = sqlx::query_as(
+            "SELECT id, name, description, min_read_role, sort_order, created_at
+             FROM board_groups WHERE name = $1",
+        )
+        .bind(name)
+        .fetch_optional(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(row.map(|r| r.into_group()))
+    }
+
+    /// Update a group by ID.
+    ///
+    /// Returns the updated group, or None if not found.
+    pub async fn update(&self, id: i64, update: &BoardGroupUpdate) -> Result<Option<BoardGroup>> {
+        let Some(group) = self.get_by_id(id).await? else {
+            return Ok(None);
+        };
+        if update.is_empty() {
+            return Ok(Some(group));
+        }
+
+        let name = update.name.clone().unwrap_or(group.name);
+        let description = update.description.clone().unwrap_or(group.description);
+        let min_read_role = update.min_read_role.unwrap_or(group.min_read_role);
+        let sort_order = update.sort_order.unwrap_or(group.sort_order);
+
+        sqlx::query(
+            "UPDATE board_groups SET name = $1, description = $2, min_read_role = $3,
+                    sort_order = $4
+             WHERE id = $5",
+        )
+        .bind(name)
+        .bind(description)
+        .bind(min_read_role.as_str())
+        .bind(sort_order)
+        .bind(id)
+        .execute(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        self.get_by_id(id).await
+    }
+
+    /// Delete a group by ID.
+    ///
+    /// Its boards are kept and move to the top level (ON DELETE SET NULL).
+    /// Returns true if a group was deleted, false if not found.
+    pub async fn delete(&self, id: i64) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM board_groups WHERE id = $1")
+            .bind(id)
+            .execute(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// List all groups, ordered by sort_order then name.
+    pub async fn list_all(&self) -> Result<Vec<BoardGroup>> {
+        let rows: Vec<BoardGroupRow> = sqlx::query_as(
+            "SELECT id, name, description, min_read_role, sort_order, created_at
+             FROM board_groups ORDER BY sort_order ASC, name ASC, id ASC",
+        )
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(|r| r.into_group()).collect())
+    }
+
+    /// List groups readable by a user with the given role.
+    pub async fn list_accessible(&self, user_role: Role) -> Result<Vec<BoardGroup>> {
+        let groups = self.list_all().await?;
+        Ok(groups.into_iter().filter(|g| g.can_read(user_role)).collect())
+    }
+
+    /// Check if a group name is already taken.
+    pub async fn name_exists(&self, name: &str) -> Result<bool> {
+        let exists: (bool,) =
+            sqlx::query_as("SELECT EXISTS(SELECT 1 FROM board_groups WHERE name = $1)")
+                .bind(name)
+                .fetch_one(self.pool)
+                .await
+                .map_err(|e| HobbsError::Database(e.to_string()))?;
+        Ok(exists.0)
+    }
+}
+
+/// Internal struct for mapping database rows to BoardGroup.
+#[derive(sqlx::FromRow)]
+struct BoardGroupRow {
+    id: i64,
+    name: String,
+    description: Option<String>,
+    min_read_role: String,
+    sort_order: i32,
+    created_at: String,
+}
+
+impl BoardGroupRow {
+    fn into_group(self) -> BoardGroup {
+        BoardGroup {
+            id: self.id,
+            name: self.name,
+            description: self.description,
+            min_read_role: self.min_read_role.parse().unwrap_or(Role::Guest),
+            sort_order: self.sort_order,
+            created_at: self.created_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::{BoardRepository, BoardUpdate, NewBoard};
+    use crate::Database;
+
+    #[tokio::test]
+    async fn test_board_group_crud() {
+        let db = Database::open_in_memory().await.unwrap();
+        let repo = BoardGroupRepository::new(db.pool());
+
+        let hobby = repo
+            .create(&NewBoardGroup::new("Hobby").with_sort_order(2))
+            .await
+            .unwrap();
+        let staff = repo
+            .create(
+                &NewBoardGroup::new("Staff")
+                    .with_description("For staff")
+                    .with_min_read_role(Role::SubOp)
+                    .with_sort_order(1),
+            )
+            .await
+            .unwrap();
+        assert_eq!(staff.description.as_deref(), Some("For staff"));
+        assert!(repo.name_exists("Hobby").await.unwrap());
+
+        let names: Vec<String> = repo
+            .list_all()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|g| g.name)
+            .collect();
+        assert_eq!(names, vec!["Staff", "Hobby"]);
+        assert_eq!(repo.list_accessible(Role::Member).await.unwrap().len(), 1);
+
+        let updated = repo
+            .update(hobby.id, &BoardGroupUpdate::new().name("Hobbies"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.name, "Hobbies");
+        assert_eq!(updated.sort_order, 2);
+        assert!(repo
+            .update(999, &BoardGroupUpdate::new().sort_order(1))
+            .await
+            .unwrap()
+            .is_none());
+
+        assert!(repo.delete(hobby.id).await.unwrap());
+        assert!(!repo.delete(hobby.id).await.unwrap());
+        assert!(repo.get_by_id(hobby.id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_board_inherits_group_read_role() {
+        let db = Database::open_in_memory().await.unwrap();
+        let groups = BoardGroupRepository::new(db.pool());
+        let boards = BoardRepository::new(db.pool());
+
+        let staff = groups
+            .create(&NewBoardGroup::new("Staff").with_min_read_role(Role::SubOp))
+            .await
+            .unwrap();
+        let board = boards
+            .create(&NewBoard::new("ops").with_group(staff.id))
+            .await
+            .unwrap();
+        assert_eq!(board.group_id, Some(staff.id));
+        assert_eq!(board.group_read_role, Some(Role::SubOp));
+        assert!(!board.can_read(Role::Member));
+        assert!(boards.list_accessible(Role::Member).await.unwrap().is_empty());
+
+        // Deleting the group moves its boards to the top level
+        groups.delete(staff.id).await.unwrap();
+        let board = boards.get_by_id(board.id).await.unwrap().unwrap();
+        assert_eq!(board.group_id, None);
+        assert!(board.can_read(Role::Member));
+
+        let board = boards
+            .update(board.id, &BoardUpdate::new().group_id(None))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(board.group_id, None);
+    }
+}
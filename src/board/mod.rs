@@ -5,23 +5,47 @@
 //! - Thread management for thread-based boards
 //! - Post management for both thread and flat boards
 //! - Board types (thread-based and flat)
+//! - Board groups (conferences) with their own read roles
 //! - Role-based access control for read/write permissions
 //! - Unread management for tracking user's read positions
+//! - Full-text search over posts and thread titles
+//! - Reply trees and quoting within threads
+//! - Revision history of edited posts
+//! - Moderation queue for moderated boards
+//! - Per-user newscan configuration
+//! - Moving, merging, splitting and converting threads
 
+mod group;
+mod moderation;
+mod newscan;
 mod post;
 mod post_repository;
+mod reorganize;
+mod reply;
 mod repository;
+mod revision;
+mod search;
 mod service;
 mod thread;
 mod thread_repository;
 mod types;
 mod unread;
 
+pub use group::{BoardGroup, BoardGroupRepository, BoardGroupUpdate, NewBoardGroup};
+pub use moderation::{NewPendingPost, PendingPost, PendingPostRepository, Submission};
+pub use newscan::{NewscanBoard, NewscanEntry, NewscanRepository, NewscanService};
 pub use post::{NewFlatPost, NewThreadPost, Post, PostUpdate};
 pub use post_repository::PostRepository;
+pub use reorganize::ReorganizeRepository;
+pub use reply::{quote_body, reply_tree, ThreadedPost, MAX_REPLY_DEPTH, QUOTE_LINES};
 pub use repository::BoardRepository;
-pub use service::{BoardService, PaginatedResult, Pagination};
+pub use revision::{
+    diff_lines, post_versions, DiffLine, PostRevision, PostVersion, RevisionRepository,
+};
+pub use search::{is_searchable, search_terms, SearchHit, SearchRepository};
+pub(crate) use service::validate_title;
+pub use service::{BoardService, PaginatedResult, Pagination, MAX_BODY_LENGTH, MAX_TITLE_LENGTH};
 pub use thread::{NewThread, Thread, ThreadUpdate};
 pub use thread_repository::ThreadRepository;
 pub use types::{Board, BoardType, BoardUpdate, NewBoard};
-pub use unread::{ReadPosition, UnreadPostWithBoard, UnreadRepository};
+pub use unread::{ReadPosition, UnreadCounts, UnreadPostWithBoard, UnreadRepository};
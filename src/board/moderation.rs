@@ -0,0 +1,266 @@
+//! Moderation queue for HOBBS.
+//!
+//! On moderated boards, posts by members are stored in `pending_posts`
+//! instead of `posts`. They stay invisible until a SubOp approves them,
+//! which publishes them as ordinary posts, or rejects them.
+
+use super::post::Post;
+use crate::db::DbPool;
+use crate::{HobbsError, Result};
+
+/// A post waiting for moderator approval.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct PendingPost {
+    /// Unique pending post ID.
+    pub id: i64,
+    /// ID of the board the post was submitted to.
+    pub board_id: i64,
+    /// Thread the post replies in (None for new threads and flat posts).
+    pub thread_id: Option<i64>,
+    /// ID of the post being replied to.
+    pub parent_id: Option<i64>,
+    /// ID of the user who submitted the post.
+    pub author_id: i64,
+    /// Thread title for a new thread, or post title on a flat board.
+    pub title: Option<String>,
+    /// Post body/content.
+    pub body: String,
+    /// Submission timestamp.
+    pub created_at: String,
+}
+
+impl PendingPost {
+    /// Check if this submission is a reply in an existing thread.
+    pub fn is_reply(&self) -> bool {
+        self.thread_id.is_some()
+    }
+}
+
+/// Data for queueing a post.
+#[derive(Debug, Clone)]
+pub struct NewPendingPost {
+    /// ID of the board.
+    pub board_id: i64,
+    /// Thread the post replies in.
+    pub thread_id: Option<i64>,
+    /// ID of the post being replied to.
+    pub parent_id: Option<i64>,
+    /// ID of the user submitting the post.
+    pub author_id: i64,
+    /// Thread or post title.
+    pub title: Option<String>,
+    /// Post body/content.
+    pub body: String,
+}
+
+impl NewPendingPost {
+    /// Queue a new thread with its first post.
+    pub fn thread(
+        board_id: i64,
+        author_id: i64,
+        title: impl Into<String>,
+        body: impl Into<String>,
+    ) -> Self {
+        Self {
+            board_id,
+            thread_id: None,
+            parent_id: None,
+            author_id,
+            title: Some(title.into()),
+            body: body.into(),
+        }
+    }
+
+    /// Queue a post in an existing thread.
+    pub fn reply(
+        board_id: i64,
+        thread_id: i64,
+        parent_id: Option<i64>,
+        author_id: i64,
+        body: impl Into<String>,
+    ) -> Self {
+        Self {
+            board_id,
+            thread_id: Some(thread_id),
+            parent_id,
+            author_id,
+            title: None,
+            body: body.into(),
+        }
+    }
+
+    /// Queue a post in a flat board.
+    pub fn flat(
+        board_id: i64,
+        author_id: i64,
+        title: impl Into<String>,
+        body: impl Into<String>,
+    ) -> Self {
+        Self::thread(board_id, author_id, title, body)
+    }
+}
+
+/// Outcome of submitting a post to a board.
+#[derive(Debug, Clone)]
+pub enum Submission {
+    /// The post was published.
+    Posted(Post),
+    /// The post is waiting for moderator approval.
+    Pending(PendingPost),
+}
+
+impl Submission {
+    /// Check if the post is waiting for approval.
+    pub fn is_pending(&self) -> bool {
+        matches!(self, Submission::Pending(_))
+    }
+}
+
+/// Repository for the moderation queue.
+pub struct PendingPostRepository<'a> {
+    pool: &'a DbPool,
+}
+
+impl<'a> PendingPostRepository<'a> {
+    /// Create a new PendingPostRepository with the given pool reference.
+    pub fn new(pool: &'a DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Add a post to the queue.
+    pub async fn create(&self, new_post: &NewPendingPost) -> Result<PendingPost> {
+        sqlx::query_as::<_, PendingPost>(
+            "INSERT INTO pending_posts (board_id, thread_id, parent_id, author_id, title, body)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             RETURNING id, board_id, thread_id, parent_id, author_id, title, body, created_at",
+        )
+        .bind(new_post.board_id)
+        .bind(new_post.thread_id)
+        .bind(new_post.parent_id)
+        .bind(new_post.author_id)
+        .bind(&new_post.title)
+        .bind(&new_post.body)
+        .fetch_one(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// Get a queued post by ID.
+    pub async fn get_by_id(&self, id: i64) -> Result<Option<PendingPost>> {
+        sqlx::query_as::<_, PendingPost>(
+            "SELECT id, board_id, thread_id, parent_id, author_id, title, body, created_at
+             FROM pending_posts WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// List all queued posts, oldest first.
+    pub async fn list(&self) -> Result<Vec<PendingPost>> {
+        sqlx::query_as::<_, PendingPost>(
+            "SELECT id, board_id, thread_id, parent_id, author_id, title, body, created_at
+             FROM pending_posts ORDER BY id ASC",
+        )
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// List queued posts submitted by a user, oldest first.
+    pub async fn list_by_author(&self, author_id: i64) -> Result<Vec<PendingPost>> {
+        sqlx::query_as::<_, PendingPost>(
+            "SELECT id, board_id, thread_id, parent_id, author_id, title, body, created_at
+             FROM pending_posts WHERE author_id = $1 ORDER BY id ASC",
+        )
+        .bind(author_id)
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// Count queued posts.
+    pub async fn count(&self) -> Result<i64> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM pending_posts")
+            .fetch_one(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// Remove a post from the queue.
+    ///
+    /// Returns true if a post was removed, false if not found.
+    pub async fn delete(&self, id: i64) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM pending_posts WHERE id = $1")
+            .bind(id)
+            .execute(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::{BoardRepository, NewBoard, NewThread, ThreadRepository};
+    use crate::db::{NewUser, UserRepository};
+    use crate::Database;
+
+    #[tokio::test]
+    async fn test_pending_post_crud() {
+        let db = Database::open_in_memory().await.unwrap();
+        let user = UserRepository::new(db.pool())
+            .create(&NewUser::new("alice", "hash", "Alice"))
+            .await
+            .unwrap();
+        let board = BoardRepository::new(db.pool())
+            .create(&NewBoard::new("general"))
+            .await
+            .unwrap();
+        let thread = ThreadRepository::new(db.pool())
+            .create(&NewThread::new(board.id, "Topic", user.id))
+            .await
+            .unwrap();
+        let repo = PendingPostRepository::new(db.pool());
+
+        let new_thread = repo
+            .create(&NewPendingPost::thread(board.id, user.id, "New topic", "first"))
+            .await
+            .unwrap();
+        assert!(!new_thread.is_reply());
+        assert_eq!(new_thread.title.as_deref(), Some("New topic"));
+
+        let reply = repo
+            .create(&NewPendingPost::reply(board.id, thread.id, None, user.id, "reply"))
+            .await
+            .unwrap();
+        assert!(reply.is_reply());
+        assert_eq!(reply.title, None);
+
+        assert_eq!(repo.count().await.unwrap(), 2);
+        let listed: Vec<i64> = repo.list().await.unwrap().iter().map(|p| p.id).collect();
+        assert_eq!(listed, vec![new_thread.id, reply.id]);
+        assert_eq!(repo.list_by_author(user.id).await.unwrap().len(), 2);
+        assert_eq!(
+            repo.get_by_id(reply.id).await.unwrap().unwrap().body,
+            "reply"
+        );
+
+        assert!(repo.delete(reply.id).await.unwrap());
+        assert!(!repo.delete(reply.id).await.unwrap());
+        assert!(repo.get_by_id(reply.id).await.unwrap().is_none());
+
+        // Deleting the thread drops queued replies to it
+        repo.create(&NewPendingPost::reply(board.id, thread.id, None, user.id, "again"))
+            .await
+            .unwrap();
+        ThreadRepository::new(db.pool())
+            .delete(thread.id)
+            .await
+            .unwrap();
+        assert_eq!(repo.count().await.unwrap(), 1);
+    }
+}
@@ -0,0 +1,603 @@
+//! Newscan configuration for HOBBS.
+//!
+//! The newscan reads unread posts across boards in one pass. Each user can
+//! leave noisy boards out of it, put the boards in their own order, have it
+//! run after login, and reset their read pointers to a date.
+//!
+//! Boards the user has not configured (e.g. boards created later) are
+//! scanned after the configured ones, in the default board order.
+
+use std::collections::HashMap;
+
+use super::repository::BoardRepository;
+use super::types::Board;
+use super::unread::{UnreadPostWithBoard, UnreadRepository};
+use crate::db::{Database, DbPool, Role};
+use crate::twit::KillFile;
+use crate::{HobbsError, Result};
+
+/// A board in a user's newscan, in scan order.
+#[derive(Debug, Clone)]
+pub struct NewscanBoard {
+    /// The board.
+    pub board: Board,
+    /// Whether the board is scanned.
+    pub included: bool,
+}
+
+/// Stored newscan setting for one board.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewscanEntry {
+    /// Board ID.
+    pub board_id: i64,
+    /// Whether the board is scanned.
+    pub included: bool,
+}
+
+/// Repository for newscan configuration.
+pub struct NewscanRepository<'a> {
+    pool: &'a DbPool,
+}
+
+impl<'a> NewscanRepository<'a> {
+    /// Create a new NewscanRepository with the given database pool.
+    pub fn new(pool: &'a DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// List a user's configured boards in scan order.
+    pub async fn list_entries(&self, user_id: i64) -> Result<Vec<NewscanEntry>> {
+        let rows: Vec<(i64, bool)> = sqlx::query_as(
+            "SELECT board_id, included FROM newscan_boards
+             WHERE user_id = $1 ORDER BY sort_order ASC, board_id ASC",
+        )
+        .bind(user_id)
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(board_id, included)| NewscanEntry { board_id, included })
+            .collect())
+    }
+
+    /// Replace a user's configured boards; the slice order is the scan order.
+    pub async fn save_entries(&self, user_id: i64, entries: &[NewscanEntry]) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        sqlx::query("DELETE FROM newscan_boards WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        for (i, entry) in entries.iter().enumerate() {
+            sqlx::query(
+                "INSERT INTO newscan_boards (user_id, board_id, included, sort_order)
+                 VALUES ($1, $2, $3, $4)",
+            )
+            .bind(user_id)
+            .bind(entry.board_id)
+            .bind(entry.included)
+            .bind(i as i32)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Check whether the newscan runs after login (false if never set).
+    pub async fn get_scan_on_login(&self, user_id: i64) -> Result<bool> {
+        let value: Option<bool> =
+            sqlx::query_scalar("SELECT scan_on_login FROM newscan_settings WHERE user_id = $1")
+                .bind(user_id)
+                .fetch_optional(self.pool)
+                .await
+                .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(value.unwrap_or(false))
+    }
+
+    /// Set whether the newscan runs after login.
+    pub async fn set_scan_on_login(&self, user_id: i64, scan_on_login: bool) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO newscan_settings (user_id, scan_on_login) VALUES ($1, $2)
+             ON CONFLICT(user_id) DO UPDATE SET scan_on_login = excluded.scan_on_login",
+        )
+        .bind(user_id)
+        .bind(scan_on_login)
+        .execute(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Get the read position that leaves the posts created at or after a
+    /// timestamp unread.
+    ///
+    /// This is the newest post below the first post created at or after
+    /// `since`, or the newest post of the board if there is none. Post IDs
+    /// do not follow creation time for imported posts, so the position is
+    /// taken from the first new post rather than from the last old one.
+    pub async fn read_position_before(&self, board_id: i64, since: &str) -> Result<Option<i64>> {
+        sqlx::query_scalar(
+            "SELECT MAX(id) FROM posts
+             WHERE board_id = $1
+               AND id < COALESCE(
+                   (SELECT MIN(id) FROM posts WHERE board_id = $1 AND created_at >= $2),
+                   id + 1)",
+        )
+        .bind(board_id)
+        .bind(since)
+        .fetch_one(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+}
+
+/// Service for the configurable newscan.
+pub struct NewscanService<'a> {
+    db: &'a Database,
+}
+
+impl<'a> NewscanService<'a> {
+    /// Create a new NewscanService.
+    pub fn new(db: &'a Database) -> Self {
+        Self { db }
+    }
+
+    /// List the boards readable by a user in scan order.
+    pub async fn boards(&self, user_id: i64, user_role: Role) -> Result<Vec<NewscanBoard>> {
+        let readable = BoardRepository::new(self.db.pool())
+            .list_accessible(user_role)
+            .await?;
+        let entries = NewscanRepository::new(self.db.pool())
+            .list_entries(user_id)
+            .await?;
+        Ok(order_boards(readable, &entries))
+    }
+
+    /// Save the scan order and selection.
+    ///
+    /// `boards` lists board IDs in scan order with whether each is scanned.
+    /// Boards the user cannot read are rejected.
+    pub async fn save(&self, user_id: i64, user_role: Role, boards: &[NewscanEntry]) -> Result<()> {
+        let readable = BoardRepository::new(self.db.pool())
+            .list_accessible(user_role)
+            .await?;
+        let mut seen = std::collections::HashSet::new();
+        for entry in boards {
+            if !readable.iter().any(|b| b.id == entry.board_id) {
+                return Err(HobbsError::NotFound("board".to_string()));
+            }
+            if !seen.insert(entry.board_id) {
+                return Err(HobbsError::Validation(
+                    "同じ掲示板が複数回指定されています".to_string(),
+                ));
+            }
+        }
+
+        NewscanRepository::new(self.db.pool())
+            .save_entries(user_id, boards)
+            .await
+    }
+
+    /// Include or exclude one board, keeping the current order.
+    pub async fn set_included(
+        &self,
+        user_id: i64,
+        user_role: Role,
+        board_id: i64,
+        included: bool,
+    ) -> Result<()> {
+        let mut entries = entries_of(&self.boards(user_id, user_role).await?);
+        let entry = entries
+            .iter_mut()
+            .find(|e| e.board_id == board_id)
+            .ok_or_else(|| HobbsError::NotFound("board".to_string()))?;
+        entry.included = included;
+        self.save(user_id, user_role, &entries).await
+    }
+
+    /// Move a board to a new position (0-based) in the scan order.
+    pub async fn move_board(
+        &self,
+        user_id: i64,
+        user_role: Role,
+        board_id: i64,
+        position: usize,
+    ) -> Result<()> {
+        let mut entries = entries_of(&self.boards(user_id, user_role).await?);
+        let from = entries
+            .iter()
+            .position(|e| e.board_id == board_id)
+            .ok_or_else(|| HobbsError::NotFound("board".to_string()))?;
+        let entry = entries.remove(from);
+        entries.insert(position.min(entries.len()), entry);
+        self.save(user_id, user_role, &entries).await
+    }
+
+    /// Get the unread posts of the scanned boards, in scan order.
+    ///
+    /// Posts hidden by the kill file are left out.
+    pub async fn unread_posts(
+        &self,
+        user_id: i64,
+        user_role: Role,
+        kill_file: &KillFile,
+    ) -> Result<Vec<UnreadPostWithBoard>> {
+        let unread_repo = UnreadRepository::new(self.db.pool()).with_kill_file(kill_file);
+        let mut unread = Vec::new();
+        for entry in self.boards(user_id, user_role).await? {
+            if !entry.included {
+                continue;
+            }
+            for post in unread_repo
+                .get_unread_posts(user_id, entry.board.id)
+                .await?
+            {
+                unread.push(UnreadPostWithBoard {
+                    post,
+                    board_name: entry.board.name.clone(),
+                });
+            }
+        }
+        Ok(unread)
+    }
+
+    /// Reset the read pointers of the scanned boards to a date.
+    ///
+    /// Posts created at or after `since` (a database datetime in UTC)
+    /// become unread and older ones read, except that an older post
+    /// imported after a newer one stays unread. Returns the number of
+    /// boards reset.
+    pub async fn reset_pointers(
+        &self,
+        user_id: i64,
+        user_role: Role,
+        since: &str,
+    ) -> Result<usize> {
+        let newscan_repo = NewscanRepository::new(self.db.pool());
+        let unread_repo = UnreadRepository::new(self.db.pool());
+        let mut count = 0;
+        for entry in self.boards(user_id, user_role).await? {
+            if !entry.included {
+                continue;
+            }
+            match newscan_repo
+                .read_position_before(entry.board.id, since)
+                .await?
+            {
+                Some(post_id) => {
+                    unread_repo
+                        .set_read_position(user_id, entry.board.id, post_id)
+                        .await?
+                }
+                None => {
+                    unread_repo
+                        .delete_read_position(user_id, entry.board.id)
+                        .await?;
+                }
+            }
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Check whether the newscan runs after login.
+    pub async fn scan_on_login(&self, user_id: i64) -> Result<bool> {
+        NewscanRepository::new(self.db.pool())
+            .get_scan_on_login(user_id)
+            .await
+    }
+
+    /// Set whether the newscan runs after login.
+    pub async fn set_scan_on_login(&self, user_id: i64, scan_on_login: bool) -> Result<()> {
+        NewscanRepository::new(self.db.pool())
+            .set_scan_on_login(user_id, scan_on_login)
+            .await
+    }
+}
+
+/// Order readable boards by a user's entries.
+///
+/// Configured boards come first in their stored order, then the others in
+/// the given order, scanned. Entries for boards that are not readable are
+/// ignored.
+fn order_boards(readable: Vec<Board>, entries: &[NewscanEntry]) -> Vec<NewscanBoard> {
+    let mut by_id: HashMap<i64, Board> = HashMap::new();
+    let mut default_order = Vec::with_capacity(readable.len());
+    for board in readable {
+        default_order.push(board.id);
+        by_id.insert(board.id, board);
+    }
+
+    let mut ordered = Vec::with_capacity(default_order.len());
+    for entry in entries {
+        if let Some(board) = by_id.remove(&entry.board_id) {
+            ordered.push(NewscanBoard {
+                board,
+                included: entry.included,
+            });
+        }
+    }
+    for id in default_order {
+        if let Some(board) = by_id.remove(&id) {
+            ordered.push(NewscanBoard {
+                board,
+                included: true,
+            });
+        }
+    }
+    ordered
+}
+
+fn entries_of(boards: &[NewscanBoard]) -> Vec<NewscanEntry> {
+    boards
+        .iter()
+        .map(|b| NewscanEntry {
+            board_id: b.board.id,
+            included: b.included,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::{BoardService, BoardType, NewBoard};
+    use crate::db::{NewUser, User, UserRepository};
+
+    async fn setup() -> (Database, User, Vec<Board>) {
+        let db = Database::open_in_memory().await.unwrap();
+        let user = UserRepository::new(db.pool())
+            .create(&NewUser::new("alice", "hash", "alice"))
+            .await
+            .unwrap();
+        let repo = BoardRepository::new(db.pool());
+        let mut boards = Vec::new();
+        for (i, name) in ["general", "noisy", "tech"].iter().enumerate() {
+            boards.push(
+                repo.create(
+                    &NewBoard::new(*name)
+                        .with_board_type(BoardType::Flat)
+                        .with_sort_order(i as i32),
+                )
+                .await
+                .unwrap(),
+            );
+        }
+        (db, user, boards)
+    }
+
+    fn names(boards: &[NewscanBoard]) -> Vec<(&str, bool)> {
+        boards
+            .iter()
+            .map(|b| (b.board.name.as_str(), b.included))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_default_scans_all_boards() {
+        let (db, user, _) = setup().await;
+        let boards = NewscanService::new(&db)
+            .boards(user.id, Role::Member)
+            .await
+            .unwrap();
+        assert_eq!(
+            names(&boards),
+            vec![("general", true), ("noisy", true), ("tech", true)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_exclude_and_reorder() {
+        let (db, user, boards) = setup().await;
+        let service = NewscanService::new(&db);
+        service
+            .set_included(user.id, Role::Member, boards[1].id, false)
+            .await
+            .unwrap();
+        service
+            .move_board(user.id, Role::Member, boards[2].id, 0)
+            .await
+            .unwrap();
+
+        // Boards created later are scanned last
+        BoardRepository::new(db.pool())
+            .create(&NewBoard::new("new").with_board_type(BoardType::Flat))
+            .await
+            .unwrap();
+        let scan = service.boards(user.id, Role::Member).await.unwrap();
+        assert_eq!(
+            names(&scan),
+            vec![
+                ("tech", true),
+                ("general", true),
+                ("noisy", false),
+                ("new", true)
+            ]
+        );
+
+        let posts = BoardService::new(&db);
+        for board in &boards {
+            posts
+                .create_flat_post(board.id, user.id, &board.name, "body", Role::Member)
+                .await
+                .unwrap();
+        }
+        let unread = service
+            .unread_posts(user.id, Role::Member, &KillFile::default())
+            .await
+            .unwrap();
+        let order: Vec<&str> = unread.iter().map(|u| u.board_name.as_str()).collect();
+        assert_eq!(order, vec!["tech", "general"]);
+    }
+
+    #[tokio::test]
+    async fn test_save_rejects_unreadable_board() {
+        let (db, user, _) = setup().await;
+        let staff = BoardRepository::new(db.pool())
+            .create(&NewBoard::new("staff").with_min_read_role(Role::SubOp))
+            .await
+            .unwrap();
+        let result = NewscanService::new(&db)
+            .save(
+                user.id,
+                Role::Member,
+                &[NewscanEntry {
+                    board_id: staff.id,
+                    included: true,
+                }],
+            )
+            .await;
+        assert!(matches!(result, Err(HobbsError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_reset_pointers() {
+        let (db, user, boards) = setup().await;
+        let posts = BoardService::new(&db);
+        let mut created = Vec::new();
+        for title in ["old", "mid", "new"] {
+            created.push(
+                posts
+                    .create_flat_post(boards[0].id, user.id, title, "body", Role::Member)
+                    .await
+                    .unwrap(),
+            );
+        }
+        for (post, created_at) in created.iter().zip([
+            "2024-01-01 00:00:00",
+            "2024-02-01 00:00:00",
+            "2024-03-01 00:00:00",
+        ]) {
+            sqlx::query("UPDATE posts SET created_at = $1 WHERE id = $2")
+                .bind(created_at)
+                .bind(post.id)
+                .execute(db.pool())
+                .await
+                .unwrap();
+        }
+        let unread = UnreadRepository::new(db.pool());
+        unread
+            .mark_as_read(user.id, boards[0].id, created[2].id)
+            .await
+            .unwrap();
+
+        let service = NewscanService::new(&db);
+        let reset = service
+            .reset_pointers(user.id, Role::Member, "2024-01-15 00:00:00")
+            .await
+            .unwrap();
+        assert_eq!(reset, 3);
+        assert_eq!(
+            unread
+                .get_last_read_post_id(user.id, boards[0].id)
+                .await
+                .unwrap(),
+            created[0].id
+        );
+        assert_eq!(
+            unread
+                .get_unread_count(user.id, boards[0].id)
+                .await
+                .unwrap(),
+            2
+        );
+
+        // Before the first post, everything is unread
+        service
+            .reset_pointers(user.id, Role::Member, "2023-12-31 00:00:00")
+            .await
+            .unwrap();
+        assert!(unread
+            .get_read_position(user.id, boards[0].id)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reset_pointers_with_imported_post() {
+        let (db, user, boards) = setup().await;
+        let posts = BoardService::new(&db);
+        let mut created = Vec::new();
+        // The last post is imported: it keeps its old date but gets the
+        // highest ID.
+        for (title, created_at) in [
+            ("old", "2024-01-01 00:00:00"),
+            ("new", "2024-03-01 00:00:00"),
+            ("imported", "2024-01-02 00:00:00"),
+        ] {
+            let post = posts
+                .create_flat_post(boards[0].id, user.id, title, "body", Role::Member)
+                .await
+                .unwrap();
+            sqlx::query("UPDATE posts SET created_at = $1 WHERE id = $2")
+                .bind(created_at)
+                .bind(post.id)
+                .execute(db.pool())
+                .await
+                .unwrap();
+            created.push(post);
+        }
+
+        let service = NewscanService::new(&db);
+        service
+            .reset_pointers(user.id, Role::Member, "2024-02-01 00:00:00")
+            .await
+            .unwrap();
+        let unread = UnreadRepository::new(db.pool());
+        assert_eq!(
+            unread
+                .get_last_read_post_id(user.id, boards[0].id)
+                .await
+                .unwrap(),
+            created[0].id
+        );
+        assert_eq!(
+            unread
+                .get_unread_count(user.id, boards[0].id)
+                .await
+                .unwrap(),
+            2
+        );
+
+        // With nothing new since the date, the whole board is read.
+        service
+            .reset_pointers(user.id, Role::Member, "2024-04-01 00:00:00")
+            .await
+            .unwrap();
+        assert_eq!(
+            unread
+                .get_last_read_post_id(user.id, boards[0].id)
+                .await
+                .unwrap(),
+            created[2].id
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scan_on_login() {
+        let (db, user, _) = setup().await;
+        let service = NewscanService::new(&db);
+        assert!(!service.scan_on_login(user.id).await.unwrap());
+        service.set_scan_on_login(user.id, true).await.unwrap();
+        assert!(service.scan_on_login(user.id).await.unwrap());
+        service.set_scan_on_login(user.id, false).await.unwrap();
+        assert!(!service.scan_on_login(user.id).await.unwrap());
+    }
+}
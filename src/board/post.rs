@@ -11,6 +11,8 @@ pub struct Post {
     pub board_id: i64,
     /// ID of the thread this post belongs to (None for flat boards).
     pub thread_id: Option<i64>,
+    /// ID of the post this one replies to (None for top-level posts).
+    pub parent_id: Option<i64>,
     /// ID of the user who created the post.
     pub author_id: i64,
     /// Post title (used for flat boards, None for thread posts).
@@ -19,6 +21,10 @@ pub struct Post {
     pub body: String,
     /// Post creation timestamp.
     pub created_at: String,
+    /// Timestamp of the last edit (None if never edited).
+    pub edited_at: Option<String>,
+    /// ID of the user who made the last edit.
+    pub edited_by: Option<i64>,
 }
 
 impl Post {
@@ -31,6 +37,11 @@ impl Post {
     pub fn is_flat_post(&self) -> bool {
         self.thread_id.is_none()
     }
+
+    /// Check if this post has been edited.
+    pub fn is_edited(&self) -> bool {
+        self.edited_at.is_some()
+    }
 }
 
 /// Data for creating a new post in a thread.
@@ -40,6 +51,8 @@ pub struct NewThreadPost {
     pub board_id: i64,
     /// ID of the thread to post in.
     pub thread_id: i64,
+    /// ID of the post being replied to.
+    pub parent_id: Option<i64>,
     /// ID of the user creating the post.
     pub author_id: i64,
     /// Post body/content.
@@ -52,10 +65,17 @@ impl NewThreadPost {
         Self {
             board_id,
             thread_id,
+            parent_id: None,
             author_id,
             body: body.into(),
         }
     }
+
+    /// Make this post a reply to another post in the thread.
+    pub fn reply_to(mut self, parent_id: i64) -> Self {
+        self.parent_id = Some(parent_id);
+        self
+    }
 }
 
 /// Data for creating a new post in a flat board.
@@ -95,6 +115,8 @@ pub struct PostUpdate {
     pub title: Option<Option<String>>,
     /// New body.
     pub body: Option<String>,
+    /// User making the edit; when set, the post is marked as edited.
+    pub editor: Option<i64>,
 }
 
 impl PostUpdate {
@@ -115,6 +137,12 @@ impl PostUpdate {
         self
     }
 
+    /// Mark the post as edited by the given user.
+    pub fn edited_by(mut self, user_id: i64) -> Self {
+        self.editor = Some(user_id);
+        self
+    }
+
     /// Check if any fields are set.
     pub fn is_empty(&self) -> bool {
         self.title.is_none() && self.body.is_none()
@@ -131,10 +159,13 @@ mod tests {
             id: 1,
             board_id: 1,
             thread_id: Some(1),
+            parent_id: None,
             author_id: 1,
             title: None,
             body: "Test".to_string(),
             created_at: "2024-01-01".to_string(),
+            edited_at: None,
+            edited_by: None,
         };
         assert!(post.is_thread_post());
         assert!(!post.is_flat_post());
@@ -146,10 +177,13 @@ mod tests {
             id: 1,
             board_id: 1,
             thread_id: None,
+            parent_id: None,
             author_id: 1,
             title: Some("Title".to_string()),
             body: "Test".to_string(),
             created_at: "2024-01-01".to_string(),
+            edited_at: None,
+            edited_by: None,
         };
         assert!(!post.is_thread_post());
         assert!(post.is_flat_post());
@@ -162,6 +196,10 @@ mod tests {
         assert_eq!(post.thread_id, 2);
         assert_eq!(post.author_id, 3);
         assert_eq!(post.body, "Hello World");
+        assert_eq!(post.parent_id, None);
+
+        let reply = NewThreadPost::new(1, 2, 3, "Re").reply_to(9);
+        assert_eq!(reply.parent_id, Some(9));
     }
 
     #[test]
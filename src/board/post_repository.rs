@@ -3,9 +3,16 @@
 //! This module provides CRUD operations for posts in the database.
 
 use super::post::{NewFlatPost, NewThreadPost, Post, PostUpdate};
+use super::search::SearchRepository;
 use crate::db::DbPool;
 use crate::{HobbsError, Result};
 
+// SQL datetime function for current timestamp
+#[cfg(feature = "sqlite")]
+const SQL_NOW: &str = "datetime('now')";
+#[cfg(feature = "postgres")]
+const SQL_NOW: &str = "TO_CHAR(NOW(), 'YYYY-MM-DD HH24:MI:SS')";
+
 /// Repository for post CRUD operations.
 pub struct PostRepository<'a> {
     pool: &'a DbPool,
@@ -23,19 +30,24 @@ impl<'a> PostRepository<'a> {
     #[cfg(feature = "sqlite")]
     pub async fn create_thread_post(&self, new_post: &NewThreadPost) -> Result<Post> {
         let id: i64 = sqlx::query_scalar(
-            "INSERT INTO posts (board_id, thread_id, author_id, body) VALUES (?, ?, ?, ?) RETURNING id",
+            "INSERT INTO posts (board_id, thread_id, parent_id, author_id, body)
+             VALUES (?, ?, ?, ?, ?) RETURNING id",
         )
         .bind(new_post.board_id)
         .bind(new_post.thread_id)
+        .bind(new_post.parent_id)
         .bind(new_post.author_id)
         .bind(&new_post.body)
         .fetch_one(self.pool)
         .await
         .map_err(|e| HobbsError::Database(e.to_string()))?;
 
-        self.get_by_id(id)
+        let post = self
+            .get_by_id(id)
             .await?
-            .ok_or_else(|| HobbsError::NotFound("post".to_string()))
+            .ok_or_else(|| HobbsError::NotFound("post".to_string()))?;
+        SearchRepository::new(self.pool).index_post(&post).await?;
+        Ok(post)
     }
 
     /// Create a new post in a thread.
@@ -44,19 +56,24 @@ impl<'a> PostRepository<'a> {
     #[cfg(feature = "postgres")]
     pub async fn create_thread_post(&self, new_post: &NewThreadPost) -> Result<Post> {
         let id: i64 = sqlx::query_scalar(
-            "INSERT INTO posts (board_id, thread_id, author_id, body) VALUES ($1, $2, $3, $4) RETURNING id",
+            "INSERT INTO posts (board_id, thread_id, parent_id, author_id, body)
+             VALUES ($1, $2, $3, $4, $5) RETURNING id",
         )
         .bind(new_post.board_id)
         .bind(new_post.thread_id)
+        .bind(new_post.parent_id)
         .bind(new_post.author_id)
         .bind(&new_post.body)
         .fetch_one(self.pool)
         .await
         .map_err(|e| HobbsError::Database(e.to_string()))?;
 
-        self.get_by_id(id)
+        let post = self
+            .get_by_id(id)
             .await?
-            .ok_or_else(|| HobbsError::NotFound("post".to_string()))
+            .ok_or_else(|| HobbsError::NotFound("post".to_string()))?;
+        SearchRepository::new(self.pool).index_post(&post).await?;
+        Ok(post)
     }
 
     /// Create a new post in a flat board.
@@ -74,9 +91,12 @@ impl<'a> PostRepository<'a> {
                 .await
                 .map_err(|e| HobbsError::Database(e.to_string()))?;
 
-        self.get_by_id(id)
+        let post = self
+            .get_by_id(id)
             .await?
-            .ok_or_else(|| HobbsError::NotFound("post".to_string()))
+            .ok_or_else(|| HobbsError::NotFound("post".to_string()))?;
+        SearchRepository::new(self.pool).index_post(&post).await?;
+        Ok(post)
     }
 
     /// Create a new post in a flat board.
@@ -94,15 +114,19 @@ impl<'a> PostRepository<'a> {
                 .await
                 .map_err(|e| HobbsError::Database(e.to_string()))?;
 
-        self.get_by_id(id)
+        let post = self
+            .get_by_id(id)
             .await?
-            .ok_or_else(|| HobbsError::NotFound("post".to_string()))
+            .ok_or_else(|| HobbsError::NotFound("post".to_string()))?;
+        SearchRepository::new(self.pool).index_post(&post).await?;
+        Ok(post)
     }
 
     /// Get a post by ID.
     pub async fn get_by_id(&self, id: i64) -> Result<Option<Post>> {
         let post = sqlx::query_as::<_, Post>(
-            "SELECT id, board_id, thread_id, author_id, title, body, created_at
+            "SELECT id, board_id, thread_id, parent_id, author_id, title, body,
+                    created_at, edited_at, edited_by
              FROM posts WHERE id = $1",
         )
         .bind(id)
@@ -134,6 +158,10 @@ impl<'a> PostRepository<'a> {
             set_clauses.push(format!("body = ${}", param_num));
             param_num += 1;
         }
+        if update.editor.is_some() {
+            set_clauses.push(format!("edited_by = ${}, edited_at = {}", param_num, SQL_NOW));
+            param_num += 1;
+        }
 
         let sql = format!("UPDATE posts SET {} WHERE id = ${}", set_clauses.join(", "), param_num);
 
@@ -147,6 +175,9 @@ impl<'a> PostRepository<'a> {
         if let Some(ref body) = update.body {
             query = query.bind(body);
         }
+        if let Some(editor) = update.editor {
+            query = query.bind(editor);
+        }
         query = query.bind(id);
 
         let result = query
@@ -158,7 +189,11 @@ impl<'a> PostRepository<'a> {
             return Ok(None);
         }
 
-        self.get_by_id(id).await
+        let post = self.get_by_id(id).await?;
+        if let Some(ref post) = post {
+            SearchRepository::new(self.pool).index_post(post).await?;
+        }
+        Ok(post)
     }
 
     /// Delete a post by ID.
@@ -177,7 +212,8 @@ impl<'a> PostRepository<'a> {
     /// List posts in a thread, ordered by created_at descending.
     pub async fn list_by_thread(&self, thread_id: i64) -> Result<Vec<Post>> {
         let posts = sqlx::query_as::<_, Post>(
-            "SELECT id, board_id, thread_id, author_id, title, body, created_at
+            "SELECT id, board_id, thread_id, parent_id, author_id, title, body,
+                    created_at, edited_at, edited_by
              FROM posts WHERE thread_id = $1 ORDER BY created_at DESC, id DESC",
         )
         .bind(thread_id)
@@ -196,7 +232,8 @@ impl<'a> PostRepository<'a> {
         limit: i64,
     ) -> Result<Vec<Post>> {
         let posts = sqlx::query_as::<_, Post>(
-            "SELECT id, board_id, thread_id, author_id, title, body, created_at
+            "SELECT id, board_id, thread_id, parent_id, author_id, title, body,
+                    created_at, edited_at, edited_by
              FROM posts WHERE thread_id = $1 ORDER BY created_at DESC, id DESC LIMIT $2 OFFSET $3",
         )
         .bind(thread_id)
@@ -212,7 +249,8 @@ impl<'a> PostRepository<'a> {
     /// List posts in a flat board (posts without thread_id), ordered by created_at descending.
     pub async fn list_by_flat_board(&self, board_id: i64) -> Result<Vec<Post>> {
         let posts = sqlx::query_as::<_, Post>(
-            "SELECT id, board_id, thread_id, author_id, title, body, created_at
+            "SELECT id, board_id, thread_id, parent_id, author_id, title, body,
+                    created_at, edited_at, edited_by
              FROM posts WHERE board_id = $1 AND thread_id IS NULL ORDER BY created_at DESC, id DESC",
         )
         .bind(board_id)
@@ -231,7 +269,8 @@ impl<'a> PostRepository<'a> {
         limit: i64,
     ) -> Result<Vec<Post>> {
         let posts = sqlx::query_as::<_, Post>(
-            "SELECT id, board_id, thread_id, author_id, title, body, created_at
+            "SELECT id, board_id, thread_id, parent_id, author_id, title, body,
+                    created_at, edited_at, edited_by
              FROM posts WHERE board_id = $1 AND thread_id IS NULL
              ORDER BY created_at DESC, id DESC LIMIT $2 OFFSET $3",
         )
@@ -248,7 +287,8 @@ impl<'a> PostRepository<'a> {
     /// List posts by author.
     pub async fn list_by_author(&self, author_id: i64) -> Result<Vec<Post>> {
         let posts = sqlx::query_as::<_, Post>(
-            "SELECT id, board_id, thread_id, author_id, title, body, created_at
+            "SELECT id, board_id, thread_id, parent_id, author_id, title, body,
+                    created_at, edited_at, edited_by
              FROM posts WHERE author_id = $1 ORDER BY created_at DESC",
         )
         .bind(author_id)
@@ -297,7 +337,8 @@ impl<'a> PostRepository<'a> {
     /// Get the latest post in a thread.
     pub async fn get_latest_in_thread(&self, thread_id: i64) -> Result<Option<Post>> {
         let post = sqlx::query_as::<_, Post>(
-            "SELECT id, board_id, thread_id, author_id, title, body, created_at
+            "SELECT id, board_id, thread_id, parent_id, author_id, title, body,
+                    created_at, edited_at, edited_by
              FROM posts WHERE thread_id = $1 ORDER BY created_at DESC, id DESC LIMIT 1",
         )
         .bind(thread_id)
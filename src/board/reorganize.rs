@@ -0,0 +1,345 @@
+//! Reorganizing threads for HOBBS.
+//!
+//! Moving threads between boards, merging and splitting threads, and
+//! converting posts between flat and thread boards. Posts keep their IDs
+//! and timestamps, so threads stay in time order and users' read positions
+//! keep working.
+//!
+//! Read positions are per board. When posts leave a board, positions on
+//! them move back to the newest post left before them, or are removed when
+//! there is none, so no remaining post becomes unread. In the new board the
+//! posts are read or unread according to the reader's position there.
+
+use super::post::Post;
+use crate::db::{DbPool, DbTransaction};
+use crate::{HobbsError, Result};
+
+/// Repository for moving posts between threads and boards.
+pub struct ReorganizeRepository<'a> {
+    pool: &'a DbPool,
+}
+
+impl<'a> ReorganizeRepository<'a> {
+    /// Create a new ReorganizeRepository with the given database pool.
+    pub fn new(pool: &'a DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Move a thread and its posts to another board.
+    pub async fn move_thread(&self, thread_id: i64, board_id: i64) -> Result<()> {
+        let mut tx = begin(self.pool).await?;
+
+        let from_board = thread_board(&mut tx, thread_id).await?;
+        let ids = thread_post_ids(&mut tx, thread_id).await?;
+        release_read_positions(&mut tx, from_board, &ids).await?;
+
+        sqlx::query("UPDATE threads SET board_id = $1 WHERE id = $2")
+            .bind(board_id)
+            .bind(thread_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+        for table in ["posts", "pending_posts"] {
+            sqlx::query(&format!(
+                "UPDATE {table} SET board_id = $1 WHERE thread_id = $2"
+            ))
+            .bind(board_id)
+            .bind(thread_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+        }
+
+        commit(tx).await
+    }
+
+    /// Merge one thread into another and delete it.
+    ///
+    /// The posts are shown in time order, so they interleave with the
+    /// posts already in the target thread. Polls, pending posts,
+    /// subscriptions and bookmarks of the merged thread move to the target.
+    pub async fn merge_threads(&self, source_id: i64, target_id: i64) -> Result<()> {
+        let mut tx = begin(self.pool).await?;
+
+        let from_board = thread_board(&mut tx, source_id).await?;
+        let to_board = thread_board(&mut tx, target_id).await?;
+        if from_board != to_board {
+            let ids = thread_post_ids(&mut tx, source_id).await?;
+            release_read_positions(&mut tx, from_board, &ids).await?;
+        }
+
+        for table in ["posts", "pending_posts"] {
+            sqlx::query(&format!(
+                "UPDATE {table} SET board_id = $1, thread_id = $2 WHERE thread_id = $3"
+            ))
+            .bind(to_board)
+            .bind(target_id)
+            .bind(source_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+        }
+
+        // Users following both threads keep a single subscription or
+        // bookmark; the rest of the source's go with the thread
+        let statements = [
+            "UPDATE polls SET thread_id = $2 WHERE thread_id = $1",
+            "UPDATE subscriptions SET thread_id = $2
+             WHERE thread_id = $1 AND user_id NOT IN
+                   (SELECT s.user_id FROM subscriptions s WHERE s.thread_id = $2)",
+            "UPDATE bookmarks SET thread_id = $2
+             WHERE thread_id = $1 AND user_id NOT IN
+                   (SELECT b.user_id FROM bookmarks b WHERE b.thread_id = $2)",
+            "DELETE FROM bookmarks WHERE thread_id = $1",
+            "DELETE FROM threads WHERE id = $1",
+        ];
+        for statement in statements {
+            sqlx::query(statement)
+                .bind(source_id)
+                .bind(target_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| HobbsError::Database(e.to_string()))?;
+        }
+
+        recount_thread(&mut tx, target_id).await?;
+        commit(tx).await
+    }
+
+    /// Split a thread at a post into a new thread in the same board.
+    ///
+    /// The post and every post after it in time order move to the new
+    /// thread, which is started by the post's author. Replies that end up in
+    /// a different thread from the post they reply to become top-level.
+    ///
+    /// Returns the ID of the new thread.
+    pub async fn split_thread(&self, post: &Post, title: &str) -> Result<i64> {
+        let thread_id = post
+            .thread_id
+            .ok_or_else(|| HobbsError::Validation("スレッドの投稿ではありません".to_string()))?;
+        let mut tx = begin(self.pool).await?;
+
+        let new_id: i64 = sqlx::query_scalar(
+            "INSERT INTO threads (board_id, title, author_id, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $4) RETURNING id",
+        )
+        .bind(post.board_id)
+        .bind(title)
+        .bind(post.author_id)
+        .bind(&post.created_at)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        sqlx::query(
+            "UPDATE posts SET thread_id = $1
+             WHERE thread_id = $2 AND (created_at > $3 OR (created_at = $3 AND id >= $4))",
+        )
+        .bind(new_id)
+        .bind(thread_id)
+        .bind(&post.created_at)
+        .bind(post.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        sqlx::query(
+            "UPDATE posts SET parent_id = NULL
+             WHERE thread_id IN ($1, $2) AND parent_id IS NOT NULL
+               AND parent_id NOT IN (SELECT p.id FROM posts p WHERE p.thread_id = posts.thread_id)",
+        )
+        .bind(thread_id)
+        .bind(new_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        recount_thread(&mut tx, thread_id).await?;
+        recount_thread(&mut tx, new_id).await?;
+        commit(tx).await?;
+        Ok(new_id)
+    }
+
+    /// Turn the posts of a thread into flat posts of a flat board and
+    /// delete the thread.
+    ///
+    /// Posts without a title get the thread title, replies prefixed with
+    /// "Re: ". Pending posts of the thread move along.
+    pub async fn thread_to_flat(&self, thread_id: i64, board_id: i64) -> Result<u64> {
+        let mut tx = begin(self.pool).await?;
+
+        let from_board = thread_board(&mut tx, thread_id).await?;
+        let title: String = sqlx::query_scalar("SELECT title FROM threads WHERE id = $1")
+            .bind(thread_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+        let ids = thread_post_ids(&mut tx, thread_id).await?;
+        release_read_positions(&mut tx, from_board, &ids).await?;
+
+        let reply_title = format!("Re: {title}");
+        for (i, id) in ids.iter().enumerate() {
+            sqlx::query(
+                "UPDATE posts SET board_id = $1, thread_id = NULL, parent_id = NULL,
+                                  title = COALESCE(title, $2)
+                 WHERE id = $3",
+            )
+            .bind(board_id)
+            .bind(if i == 0 { &title } else { &reply_title })
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+        }
+
+        sqlx::query(
+            "UPDATE pending_posts SET board_id = $1, thread_id = NULL, parent_id = NULL,
+                                      title = COALESCE(title, $2)
+             WHERE thread_id = $3",
+        )
+        .bind(board_id)
+        .bind(&reply_title)
+        .bind(thread_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        sqlx::query("DELETE FROM threads WHERE id = $1")
+            .bind(thread_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        commit(tx).await?;
+        Ok(ids.len() as u64)
+    }
+
+    /// Turn a flat post into a new thread of a thread board.
+    ///
+    /// The thread is started by the post's author at the time of the post.
+    ///
+    /// Returns the ID of the new thread.
+    pub async fn post_to_thread(&self, post: &Post, board_id: i64, title: &str) -> Result<i64> {
+        let mut tx = begin(self.pool).await?;
+
+        release_read_positions(&mut tx, post.board_id, &[post.id]).await?;
+
+        let thread_id: i64 = sqlx::query_scalar(
+            "INSERT INTO threads (board_id, title, author_id, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $4) RETURNING id",
+        )
+        .bind(board_id)
+        .bind(title)
+        .bind(post.author_id)
+        .bind(&post.created_at)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        sqlx::query(
+            "UPDATE posts SET board_id = $1, thread_id = $2, parent_id = NULL, title = NULL
+             WHERE id = $3",
+        )
+        .bind(board_id)
+        .bind(thread_id)
+        .bind(post.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        recount_thread(&mut tx, thread_id).await?;
+        commit(tx).await?;
+        Ok(thread_id)
+    }
+}
+
+async fn begin(pool: &DbPool) -> Result<DbTransaction<'_>> {
+    pool.begin()
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))
+}
+
+async fn commit(tx: DbTransaction<'_>) -> Result<()> {
+    tx.commit()
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))
+}
+
+/// Get the board of a thread.
+async fn thread_board(tx: &mut DbTransaction<'_>, thread_id: i64) -> Result<i64> {
+    sqlx::query_scalar("SELECT board_id FROM threads WHERE id = $1")
+        .bind(thread_id)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?
+        .ok_or_else(|| HobbsError::NotFound("スレッド".to_string()))
+}
+
+/// Get the IDs of a thread's posts in time order.
+async fn thread_post_ids(tx: &mut DbTransaction<'_>, thread_id: i64) -> Result<Vec<i64>> {
+    sqlx::query_scalar("SELECT id FROM posts WHERE thread_id = $1 ORDER BY created_at, id")
+        .bind(thread_id)
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))
+}
+
+/// Move read positions in a board off posts that are leaving it.
+///
+/// Positions go back to the newest post that stays before them, or are
+/// removed when there is none.
+async fn release_read_positions(
+    tx: &mut DbTransaction<'_>,
+    board_id: i64,
+    post_ids: &[i64],
+) -> Result<()> {
+    if post_ids.is_empty() {
+        return Ok(());
+    }
+    // IDs are integers, so they can be inlined
+    let ids = post_ids
+        .iter()
+        .map(i64::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    let kept_before = format!(
+        "FROM posts k WHERE k.board_id = $1
+           AND k.id <= read_positions.last_read_post_id AND k.id NOT IN ({ids})"
+    );
+
+    let statements = [
+        format!(
+            "DELETE FROM read_positions
+             WHERE board_id = $1 AND last_read_post_id IN ({ids})
+               AND NOT EXISTS (SELECT 1 {kept_before})"
+        ),
+        format!(
+            "UPDATE read_positions SET last_read_post_id = (SELECT MAX(k.id) {kept_before})
+             WHERE board_id = $1 AND last_read_post_id IN ({ids})"
+        ),
+    ];
+    for statement in statements {
+        sqlx::query(&statement)
+            .bind(board_id)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+    }
+    Ok(())
+}
+
+/// Recompute a thread's post count and last update from its posts.
+async fn recount_thread(tx: &mut DbTransaction<'_>, thread_id: i64) -> Result<()> {
+    sqlx::query(
+        "UPDATE threads
+         SET post_count = (SELECT COUNT(*) FROM posts WHERE thread_id = $1),
+             updated_at = COALESCE((SELECT MAX(created_at) FROM posts WHERE thread_id = $1),
+                                   updated_at)
+         WHERE id = $1",
+    )
+    .bind(thread_id)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| HobbsError::Database(e.to_string()))?;
+    Ok(())
+}
@@ -0,0 +1,156 @@
+//! Reply trees and quoting for HOBBS.
+//!
+//! Posts in a thread may name the post they answer (`parent_id`). This
+//! module arranges a thread's posts into conversation order and builds the
+//! `> ` quote that pre-fills a reply.
+
+use std::collections::HashMap;
+
+use super::post::Post;
+
+/// Maximum indentation depth used when displaying a reply tree.
+///
+/// Deeper replies are shown at this depth so that long conversations still
+/// fit on a 40-column terminal.
+pub const MAX_REPLY_DEPTH: usize = 8;
+
+/// Default number of lines copied into a reply quote.
+pub const QUOTE_LINES: usize = 5;
+
+/// A post placed in a reply tree.
+#[derive(Debug, Clone)]
+pub struct ThreadedPost {
+    /// The post.
+    pub post: Post,
+    /// Nesting level (0 for top-level posts).
+    pub depth: usize,
+}
+
+/// Arrange posts into reply-tree order.
+///
+/// Each post is followed by its replies (oldest first, depth-first).
+/// Posts without a parent, or whose parent is not in `posts`, are roots.
+pub fn reply_tree(mut posts: Vec<Post>) -> Vec<ThreadedPost> {
+    posts.sort_by_key(|p| p.id);
+
+    let index: HashMap<i64, usize> = posts.iter().enumerate().map(|(i, p)| (p.id, i)).collect();
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); posts.len()];
+    let mut roots = Vec::new();
+    for (i, post) in posts.iter().enumerate() {
+        match post.parent_id.and_then(|id| index.get(&id)) {
+            // A parent is always older; anything else would form a cycle
+            Some(&parent) if parent < i => children[parent].push(i),
+            _ => roots.push(i),
+        }
+    }
+
+    let mut order = Vec::with_capacity(posts.len());
+    let mut stack: Vec<(usize, usize)> = roots.into_iter().rev().map(|i| (i, 0)).collect();
+    while let Some((i, depth)) = stack.pop() {
+        order.push((i, depth));
+        stack.extend(children[i].iter().rev().map(|&c| (c, depth + 1)));
+    }
+
+    let mut slots: Vec<Option<Post>> = posts.into_iter().map(Some).collect();
+    order
+        .into_iter()
+        .filter_map(|(i, depth)| slots[i].take().map(|post| ThreadedPost { post, depth }))
+        .collect()
+}
+
+/// Build a `> `-quoted excerpt of a post body for a reply.
+///
+/// Leading and trailing blank lines are dropped, and at most `max_lines`
+/// lines are kept (followed by `> ...` when the body is longer). Lines that
+/// are already quotes get another `>`.
+pub fn quote_body(body: &str, max_lines: usize) -> String {
+    let lines: Vec<&str> = body.lines().map(str::trim_end).collect();
+    let start = lines
+        .iter()
+        .position(|l| !l.is_empty())
+        .unwrap_or(lines.len());
+    let end = lines
+        .iter()
+        .rposition(|l| !l.is_empty())
+        .map_or(start, |i| i + 1);
+    let lines = &lines[start..end];
+
+    let mut quoted: Vec<String> = lines
+        .iter()
+        .take(max_lines)
+        .map(|line| {
+            if line.is_empty() {
+                ">".to_string()
+            } else if line.starts_with('>') {
+                format!(">{line}")
+            } else {
+                format!("> {line}")
+            }
+        })
+        .collect();
+    if lines.len() > max_lines {
+        quoted.push("> ...".to_string());
+    }
+
+    quoted.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn post(id: i64, parent_id: Option<i64>) -> Post {
+        Post {
+            id,
+            board_id: 1,
+            thread_id: Some(1),
+            parent_id,
+            author_id: 1,
+            title: None,
+            body: format!("post {id}"),
+            created_at: "2024-01-01 00:00:00".to_string(),
+            edited_at: None,
+            edited_by: None,
+        }
+    }
+
+    #[test]
+    fn test_reply_tree_order_and_depth() {
+        let posts = vec![
+            post(5, Some(2)),
+            post(1, None),
+            post(4, None),
+            post(3, Some(1)),
+            post(2, Some(1)),
+            post(6, Some(5)),
+        ];
+
+        let tree: Vec<(i64, usize)> = reply_tree(posts)
+            .iter()
+            .map(|t| (t.post.id, t.depth))
+            .collect();
+        assert_eq!(tree, vec![(1, 0), (2, 1), (5, 2), (6, 3), (3, 1), (4, 0)]);
+    }
+
+    #[test]
+    fn test_reply_tree_missing_parent_is_root() {
+        let tree = reply_tree(vec![post(3, Some(99)), post(4, Some(3))]);
+        let tree: Vec<(i64, usize)> = tree.iter().map(|t| (t.post.id, t.depth)).collect();
+        assert_eq!(tree, vec![(3, 0), (4, 1)]);
+    }
+
+    #[test]
+    fn test_reply_tree_ignores_newer_parent() {
+        let tree = reply_tree(vec![post(1, Some(2)), post(2, Some(1))]);
+        let tree: Vec<(i64, usize)> = tree.iter().map(|t| (t.post.id, t.depth)).collect();
+        assert_eq!(tree, vec![(1, 0), (2, 1)]);
+    }
+
+    #[test]
+    fn test_quote_body() {
+        assert_eq!(quote_body("\nHello\n\nWorld\n\n", 5), "> Hello\n>\n> World");
+        assert_eq!(quote_body("> earlier\nreply", 5), ">> earlier\n> reply");
+        assert_eq!(quote_body("a\nb\nc", 2), "> a\n> b\n> ...");
+        assert_eq!(quote_body("  \n", 5), "");
+    }
+}
@@ -8,6 +8,14 @@ use super::types::{Board, BoardType, BoardUpdate, NewBoard};
 use crate::db::{DbPool, Role, SQL_TRUE};
 use crate::{HobbsError, Result};
 
+/// Board columns joined with the read role of the board's group.
+const BOARD_SELECT: &str =
+    "SELECT b.id, b.name, b.description, b.board_type, b.min_read_role, b.min_write_role,
+            b.sort_order, b.is_active, b.disable_paging, b.allow_color, b.is_moderated,
+            b.group_id, g.min_read_role AS group_read_role, b.max_posts, b.max_age_days,
+            b.archive_pruned, b.created_at
+     FROM boards b LEFT JOIN board_groups g ON g.id = b.group_id";
+
 /// Repository for board CRUD operations.
 pub struct BoardRepository<'a> {
     pool: &'a DbPool,
@@ -25,8 +33,8 @@ impl<'a> BoardRepository<'a> {
     #[cfg(feature = "sqlite")]
     pub async fn create(&self, new_board: &NewBoard) -> Result<Board> {
         let id: i64 = sqlx::query_scalar(
-            "INSERT INTO boards (name, description, board_type, min_read_role, min_write_role, sort_order, disable_paging)
-             VALUES (?, ?, ?, ?, ?, ?, ?) RETURNING id",
+            "INSERT INTO boards (name, description, board_type, min_read_role, min_write_role, sort_order, disable_paging, allow_color, is_moderated, group_id, max_posts, max_age_days, archive_pruned)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) RETURNING id",
         )
         .bind(&new_board.name)
         .bind(&new_board.description)
@@ -35,6 +43,12 @@ impl<'a> BoardRepository<'a> {
         .bind(new_board.min_write_role.as_str())
         .bind(new_board.sort_order)
         .bind(new_board.disable_paging)
+        .bind(new_board.allow_color)
+        .bind(new_board.is_moderated)
+        .bind(new_board.group_id)
+        .bind(new_board.max_posts)
+        .bind(new_board.max_age_days)
+        .bind(new_board.archive_pruned)
         .fetch_one(self.pool)
         .await
         .map_err(|e| HobbsError::Database(e.to_string()))?;
@@ -50,8 +64,8 @@ impl<'a> BoardRepository<'a> {
     #[cfg(feature = "postgres")]
     pub async fn create(&self, new_board: &NewBoard) -> Result<Board> {
         let id: i64 = sqlx::query_scalar(
-            "INSERT INTO boards (name, description, board_type, min_read_role, min_write_role, sort_order, disable_paging)
-             VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING id",
+            "INSERT INTO boards (name, description, board_type, min_read_role, min_write_role, sort_order, disable_paging, allow_color, is_moderated, group_id, max_posts, max_age_days, archive_pruned)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13) RETURNING id",
         )
         .bind(&new_board.name)
         .bind(&new_board.description)
@@ -60,6 +74,12 @@ impl<'a> BoardRepository<'a> {
         .bind(new_board.min_write_role.as_str())
         .bind(new_board.sort_order)
         .bind(new_board.disable_paging)
+        .bind(new_board.allow_color)
+        .bind(new_board.is_moderated)
+        .bind(new_board.group_id)
+        .bind(new_board.max_posts)
+        .bind(new_board.max_age_days)
+        .bind(new_board.archive_pruned)
         .fetch_one(self.pool)
         .await
         .map_err(|e| HobbsError::Database(e.to_string()))?;
@@ -71,30 +91,24 @@ impl<'a> BoardRepository<'a> {
 
     /// Get a board by ID.
     pub async fn get_by_id(&self, id: i64) -> Result<Option<Board>> {
-        let result: Option<BoardRow> = sqlx::query_as(
-            "SELECT id, name, description, board_type, min_read_role, min_write_role,
-                    sort_order, is_active, disable_paging, created_at
-             FROM boards WHERE id = $1",
-        )
-        .bind(id)
-        .fetch_optional(self.pool)
-        .await
-        .map_err(|e| HobbsError::Database(e.to_string()))?;
+        let query = format!("{} WHERE b.id = $1", BOARD_SELECT);
+        let result: Option<BoardRow> = sqlx::query_as(&query)
+            .bind(id)
+            .fetch_optional(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
 
         Ok(result.map(|row| row.into_board()))
     }
 
     /// Get a board by name.
     pub async fn get_by_name(&self, name: &str) -> Result<Option<Board>> {
-        let result: Option<BoardRow> = sqlx::query_as(
-            "SELECT id, name, description, board_type, min_read_role, min_write_role,
-                    sort_order, is_active, disable_paging, created_at
-             FROM boards WHERE name = $1",
-        )
-        .bind(name)
-        .fetch_optional(self.pool)
-        .await
-        .map_err(|e| HobbsError::Database(e.to_string()))?;
+        let query = format!("{} WHERE b.name = $1", BOARD_SELECT);
+        let result: Option<BoardRow> = sqlx::query_as(&query)
+            .bind(name)
+            .fetch_optional(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
 
         Ok(result.map(|row| row.into_board()))
     }
@@ -144,6 +158,30 @@ impl<'a> BoardRepository<'a> {
             separated.push("disable_paging = ");
             separated.push_bind_unseparated(disable_paging);
         }
+        if let Some(allow_color) = update.allow_color {
+            separated.push("allow_color = ");
+            separated.push_bind_unseparated(allow_color);
+        }
+        if let Some(is_moderated) = update.is_moderated {
+            separated.push("is_moderated = ");
+            separated.push_bind_unseparated(is_moderated);
+        }
+        if let Some(group_id) = update.group_id {
+            separated.push("group_id = ");
+            separated.push_bind_unseparated(group_id);
+        }
+        if let Some(max_posts) = update.max_posts {
+            separated.push("max_posts = ");
+            separated.push_bind_unseparated(max_posts);
+        }
+        if let Some(max_age_days) = update.max_age_days {
+            separated.push("max_age_days = ");
+            separated.push_bind_unseparated(max_age_days);
+        }
+        if let Some(archive_pruned) = update.archive_pruned {
+            separated.push("archive_pruned = ");
+            separated.push_bind_unseparated(archive_pruned);
+        }
 
         query.push(" WHERE id = ");
         query.push_bind(id);
@@ -206,6 +244,30 @@ impl<'a> BoardRepository<'a> {
             separated.push("disable_paging = ");
             separated.push_bind_unseparated(disable_paging);
         }
+        if let Some(allow_color) = update.allow_color {
+            separated.push("allow_color = ");
+            separated.push_bind_unseparated(allow_color);
+        }
+        if let Some(is_moderated) = update.is_moderated {
+            separated.push("is_moderated = ");
+            separated.push_bind_unseparated(is_moderated);
+        }
+        if let Some(group_id) = update.group_id {
+            separated.push("group_id = ");
+            separated.push_bind_unseparated(group_id);
+        }
+        if let Some(max_posts) = update.max_posts {
+            separated.push("max_posts = ");
+            separated.push_bind_unseparated(max_posts);
+        }
+        if let Some(max_age_days) = update.max_age_days {
+            separated.push("max_age_days = ");
+            separated.push_bind_unseparated(max_age_days);
+        }
+        if let Some(archive_pruned) = update.archive_pruned {
+            separated.push("archive_pruned = ");
+            separated.push_bind_unseparated(archive_pruned);
+        }
 
         query.push(" WHERE id = ");
         query.push_bind(id);
@@ -238,10 +300,8 @@ impl<'a> BoardRepository<'a> {
     /// List all active boards, ordered by sort_order then created_at.
     pub async fn list_active(&self) -> Result<Vec<Board>> {
         let query = format!(
-            "SELECT id, name, description, board_type, min_read_role, min_write_role,
-                    sort_order, is_active, disable_paging, created_at
-             FROM boards WHERE is_active = {} ORDER BY sort_order ASC, created_at ASC, id ASC",
-            SQL_TRUE
+            "{} WHERE b.is_active = {} ORDER BY b.sort_order ASC, b.created_at ASC, b.id ASC",
+            BOARD_SELECT, SQL_TRUE
         );
         let rows: Vec<BoardRow> = sqlx::query_as(&query)
             .fetch_all(self.pool)
@@ -253,14 +313,14 @@ impl<'a> BoardRepository<'a> {
 
     /// List all boards (including inactive), ordered by sort_order then created_at.
     pub async fn list_all(&self) -> Result<Vec<Board>> {
-        let rows: Vec<BoardRow> = sqlx::query_as(
-            "SELECT id, name, description, board_type, min_read_role, min_write_role,
-                    sort_order, is_active, disable_paging, created_at
-             FROM boards ORDER BY sort_order ASC, created_at ASC, id ASC",
-        )
-        .fetch_all(self.pool)
-        .await
-        .map_err(|e| HobbsError::Database(e.to_string()))?;
+        let query = format!(
+            "{} ORDER BY b.sort_order ASC, b.created_at ASC, b.id ASC",
+            BOARD_SELECT
+        );
+        let rows: Vec<BoardRow> = sqlx::query_as(&query)
+            .fetch_all(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
 
         Ok(rows.into_iter().map(|row| row.into_board()).collect())
     }
@@ -331,6 +391,13 @@ struct BoardRow {
     sort_order: i32,
     is_active: bool,
     disable_paging: bool,
+    allow_color: bool,
+    is_moderated: bool,
+    group_id: Option<i64>,
+    group_read_role: Option<String>,
+    max_posts: Option<i64>,
+    max_age_days: Option<i64>,
+    archive_pruned: bool,
     created_at: String,
 }
 
@@ -346,6 +413,13 @@ impl BoardRow {
             sort_order: self.sort_order,
             is_active: self.is_active,
             disable_paging: self.disable_paging,
+            allow_color: self.allow_color,
+            is_moderated: self.is_moderated,
+            group_id: self.group_id,
+            group_read_role: self.group_read_role.and_then(|r| r.parse().ok()),
+            max_posts: self.max_posts,
+            max_age_days: self.max_age_days,
+            archive_pruned: self.archive_pruned,
             created_at: self.created_at,
         }
     }
@@ -515,6 +589,59 @@ mod tests {
         assert!(!updated.is_active);
     }
 
+    #[tokio::test]
+    async fn test_update_allow_color() {
+        let db = setup_db().await;
+        let repo = BoardRepository::new(db.pool());
+
+        let new_board = NewBoard::new("general");
+        let board = repo.create(&new_board).await.unwrap();
+        assert!(board.allow_color);
+
+        let update = BoardUpdate::new().allow_color(false);
+        let updated = repo.update(board.id, &update).await.unwrap().unwrap();
+
+        assert!(!updated.allow_color);
+    }
+
+    #[tokio::test]
+    async fn test_update_is_moderated() {
+        let db = setup_db().await;
+        let repo = BoardRepository::new(db.pool());
+
+        let board = repo.create(&NewBoard::new("general")).await.unwrap();
+        assert!(!board.is_moderated);
+
+        let update = BoardUpdate::new().is_moderated(true);
+        let updated = repo.update(board.id, &update).await.unwrap().unwrap();
+
+        assert!(updated.is_moderated);
+    }
+
+    #[tokio::test]
+    async fn test_update_retention() {
+        let db = setup_db().await;
+        let repo = BoardRepository::new(db.pool());
+
+        let board = repo
+            .create(&NewBoard::new("general").with_retention(Some(100), None, true))
+            .await
+            .unwrap();
+        assert_eq!(board.max_posts, Some(100));
+        assert_eq!(board.max_age_days, None);
+        assert!(board.archive_pruned);
+
+        let update = BoardUpdate::new()
+            .max_posts(None)
+            .max_age_days(Some(30))
+            .archive_pruned(false);
+        let updated = repo.update(board.id, &update).await.unwrap().unwrap();
+
+        assert_eq!(updated.max_posts, None);
+        assert_eq!(updated.max_age_days, Some(30));
+        assert!(!updated.archive_pruned);
+    }
+
     #[tokio::test]
     async fn test_update_clear_description() {
         let db = setup_db().await;
@@ -0,0 +1,315 @@
+//! Post revision history for HOBBS.
+//!
+//! When a post is edited, the version it replaces is kept in
+//! `post_revisions` with the user who wrote it and when, so that the
+//! author and moderators can review what changed.
+
+use super::post::Post;
+use crate::db::DbPool;
+use crate::{HobbsError, Result};
+
+/// Upper bound on the diff table size (lines × lines).
+///
+/// Larger bodies are shown as a full replacement instead of a line diff.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// A stored earlier version of a post.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct PostRevision {
+    /// Unique revision ID.
+    pub id: i64,
+    /// ID of the post.
+    pub post_id: i64,
+    /// Title of this version.
+    pub title: Option<String>,
+    /// Body of this version.
+    pub body: String,
+    /// User who wrote this version (None if the user was deleted).
+    pub editor_id: Option<i64>,
+    /// When this version was written.
+    pub created_at: String,
+}
+
+/// One version of a post, as shown in the revision viewer.
+#[derive(Debug, Clone)]
+pub struct PostVersion {
+    /// 1-based version number (the original post is 1).
+    pub number: usize,
+    /// Title of this version.
+    pub title: Option<String>,
+    /// Body of this version.
+    pub body: String,
+    /// User who wrote this version.
+    pub editor_id: Option<i64>,
+    /// When this version was written.
+    pub created_at: String,
+    /// Whether this is the current content of the post.
+    pub current: bool,
+}
+
+/// Build the version list of a post from its stored revisions.
+///
+/// `revisions` must be oldest first; the post's current content is
+/// appended as the last version.
+pub fn post_versions(post: &Post, revisions: Vec<PostRevision>) -> Vec<PostVersion> {
+    let mut versions: Vec<PostVersion> = revisions
+        .into_iter()
+        .enumerate()
+        .map(|(i, rev)| PostVersion {
+            number: i + 1,
+            title: rev.title,
+            body: rev.body,
+            editor_id: rev.editor_id,
+            created_at: rev.created_at,
+            current: false,
+        })
+        .collect();
+    versions.push(PostVersion {
+        number: versions.len() + 1,
+        title: post.title.clone(),
+        body: post.body.clone(),
+        editor_id: Some(post.edited_by.unwrap_or(post.author_id)),
+        created_at: post
+            .edited_at
+            .clone()
+            .unwrap_or_else(|| post.created_at.clone()),
+        current: true,
+    });
+    versions
+}
+
+/// A line in a diff between two versions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    /// Line present in both versions.
+    Same(String),
+    /// Line only in the older version.
+    Removed(String),
+    /// Line only in the newer version.
+    Added(String),
+}
+
+impl DiffLine {
+    /// Unified-diff style prefix (`' '`, `'-'` or `'+'`).
+    pub fn prefix(&self) -> char {
+        match self {
+            DiffLine::Same(_) => ' ',
+            DiffLine::Removed(_) => '-',
+            DiffLine::Added(_) => '+',
+        }
+    }
+
+    /// Text of the line.
+    pub fn text(&self) -> &str {
+        match self {
+            DiffLine::Same(text) | DiffLine::Removed(text) | DiffLine::Added(text) => text,
+        }
+    }
+}
+
+/// Compute a line diff from `old` to `new`.
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    // Common prefix and suffix need no table
+    let prefix = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (mid_a, mid_b) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+
+    let mut diff: Vec<DiffLine> = a[..prefix]
+        .iter()
+        .map(|l| DiffLine::Same(l.to_string()))
+        .collect();
+
+    if mid_a.len() * mid_b.len() > MAX_DIFF_CELLS {
+        diff.extend(mid_a.iter().map(|l| DiffLine::Removed(l.to_string())));
+        diff.extend(mid_b.iter().map(|l| DiffLine::Added(l.to_string())));
+    } else {
+        diff.extend(lcs_diff(mid_a, mid_b));
+    }
+
+    diff.extend(
+        a[a.len() - suffix..]
+            .iter()
+            .map(|l| DiffLine::Same(l.to_string())),
+    );
+    diff
+}
+
+/// Line diff based on the longest common subsequence.
+fn lcs_diff(a: &[&str], b: &[&str]) -> Vec<DiffLine> {
+    let (n, m) = (a.len(), b.len());
+    // lcs[i][j] = LCS length of a[i..] and b[j..]
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::with_capacity(n + m);
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if a[i] == b[j] {
+            diff.push(DiffLine::Same(a[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            diff.push(DiffLine::Removed(a[i].to_string()));
+            i += 1;
+        } else {
+            diff.push(DiffLine::Added(b[j].to_string()));
+            j += 1;
+        }
+    }
+    diff.extend(a[i..].iter().map(|l| DiffLine::Removed(l.to_string())));
+    diff.extend(b[j..].iter().map(|l| DiffLine::Added(l.to_string())));
+    diff
+}
+
+/// Repository for post revisions.
+pub struct RevisionRepository<'a> {
+    pool: &'a DbPool,
+}
+
+impl<'a> RevisionRepository<'a> {
+    /// Create a new RevisionRepository with the given pool reference.
+    pub fn new(pool: &'a DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Store the current content of a post as a revision.
+    ///
+    /// Call this before the post is overwritten by an edit.
+    pub async fn create(&self, post: &Post) -> Result<PostRevision> {
+        let editor_id = post.edited_by.unwrap_or(post.author_id);
+        let created_at = post.edited_at.as_deref().unwrap_or(&post.created_at);
+
+        sqlx::query_as::<_, PostRevision>(
+            "INSERT INTO post_revisions (post_id, title, body, editor_id, created_at)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING id, post_id, title, body, editor_id, created_at",
+        )
+        .bind(post.id)
+        .bind(&post.title)
+        .bind(&post.body)
+        .bind(editor_id)
+        .bind(created_at)
+        .fetch_one(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// List the revisions of a post, oldest first.
+    pub async fn list_by_post(&self, post_id: i64) -> Result<Vec<PostRevision>> {
+        sqlx::query_as::<_, PostRevision>(
+            "SELECT id, post_id, title, body, editor_id, created_at
+             FROM post_revisions WHERE post_id = $1 ORDER BY id ASC",
+        )
+        .bind(post_id)
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// Count the revisions of a post.
+    pub async fn count_by_post(&self, post_id: i64) -> Result<i64> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM post_revisions WHERE post_id = $1")
+            .bind(post_id)
+            .fetch_one(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// Delete all revisions of a post.
+    ///
+    /// Returns the number of revisions deleted.
+    pub async fn delete_by_post(&self, post_id: i64) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM post_revisions WHERE post_id = $1")
+            .bind(post_id)
+            .execute(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diff_text(old: &str, new: &str) -> Vec<String> {
+        diff_lines(old, new)
+            .iter()
+            .map(|l| format!("{}{}", l.prefix(), l.text()))
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        assert_eq!(
+            diff_text("a\nb\nc\nd", "a\nx\nc\nd\ne"),
+            vec![" a", "-b", "+x", " c", " d", "+e"]
+        );
+        assert_eq!(diff_text("same", "same"), vec![" same"]);
+        assert_eq!(diff_text("", "new"), vec!["+new"]);
+        assert_eq!(diff_text("old\n", ""), vec!["-old"]);
+    }
+
+    #[test]
+    fn test_diff_lines_moved_block() {
+        assert_eq!(
+            diff_text("1\n2\n3\n4", "3\n4\n1\n2"),
+            vec!["-1", "-2", " 3", " 4", "+1", "+2"]
+        );
+    }
+
+    #[test]
+    fn test_post_versions() {
+        let post = Post {
+            id: 1,
+            board_id: 1,
+            thread_id: None,
+            parent_id: None,
+            author_id: 2,
+            title: Some("Title".to_string()),
+            body: "v3".to_string(),
+            created_at: "2024-01-01 00:00:00".to_string(),
+            edited_at: Some("2024-01-03 00:00:00".to_string()),
+            edited_by: Some(5),
+        };
+        let revision = |id: i64, body: &str, editor_id: i64, created_at: &str| PostRevision {
+            id,
+            post_id: 1,
+            title: Some("Title".to_string()),
+            body: body.to_string(),
+            editor_id: Some(editor_id),
+            created_at: created_at.to_string(),
+        };
+        let revisions = vec![
+            revision(10, "v1", 2, "2024-01-01 00:00:00"),
+            revision(11, "v2", 2, "2024-01-02 00:00:00"),
+        ];
+
+        let versions = post_versions(&post, revisions);
+        assert_eq!(versions.len(), 3);
+        assert_eq!(versions[0].number, 1);
+        assert_eq!(versions[0].body, "v1");
+        assert!(!versions[1].current);
+        assert_eq!(versions[2].body, "v3");
+        assert_eq!(versions[2].editor_id, Some(5));
+        assert_eq!(versions[2].created_at, "2024-01-03 00:00:00");
+        assert!(versions[2].current);
+    }
+}
@@ -0,0 +1,594 @@
+//! Full-text search for HOBBS.
+//!
+//! Posts are indexed by their body, their own title (flat boards) and the
+//! title of their thread. The index stores search terms generated here
+//! rather than relying on a database tokenizer, so that Japanese text is
+//! searchable on both backends:
+//!
+//! - Runs of CJK characters (kanji, kana, hangul) become overlapping
+//!   bigrams, plus the last character on its own so that one-character
+//!   queries can match.
+//! - Other runs of letters and digits become lowercase words.
+//!
+//! SQLite keeps the terms in an FTS5 table; PostgreSQL keeps them as a
+//! `tsvector` with a GIN index. Index entries are removed together with
+//! their posts by the database.
+
+use super::Post;
+use crate::db::DbPool;
+use crate::{HobbsError, Result};
+
+/// A post found by search.
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    /// The post.
+    pub post: Post,
+    /// Name of the board the post belongs to.
+    pub board_name: String,
+    /// Title of the thread the post belongs to (None for flat boards).
+    pub thread_title: Option<String>,
+}
+
+/// A unit of text for indexing.
+#[derive(Debug, PartialEq, Eq)]
+enum Run {
+    /// A word of non-CJK letters and digits.
+    Word(String),
+    /// A run of CJK characters.
+    Cjk(Vec<char>),
+}
+
+/// Whether a character belongs to a script written without spaces.
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3005}'..='\u{3007}'      // 々〆〇
+        | '\u{3040}'..='\u{30FF}'    // Hiragana, Katakana
+        | '\u{3400}'..='\u{4DBF}'    // CJK Extension A
+        | '\u{4E00}'..='\u{9FFF}'    // CJK Unified Ideographs
+        | '\u{AC00}'..='\u{D7AF}'    // Hangul syllables
+        | '\u{F900}'..='\u{FAFF}'    // CJK Compatibility Ideographs
+        | '\u{FF66}'..='\u{FF9F}'    // Halfwidth Katakana
+        | '\u{20000}'..='\u{2FFFF}' // CJK Extension B and later
+    )
+}
+
+/// Fold full-width ASCII to ASCII and lowercase.
+fn normalize(c: char) -> impl Iterator<Item = char> {
+    let c = match c {
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+        _ => c,
+    };
+    c.to_lowercase()
+}
+
+/// Split text into words and CJK runs.
+fn runs(text: &str) -> Vec<Run> {
+    let mut runs = Vec::new();
+    let mut word = String::new();
+    let mut cjk = Vec::new();
+
+    for c in text.chars().flat_map(normalize) {
+        if is_cjk(c) {
+            if !word.is_empty() {
+                runs.push(Run::Word(std::mem::take(&mut word)));
+            }
+            cjk.push(c);
+        } else {
+            if !cjk.is_empty() {
+                runs.push(Run::Cjk(std::mem::take(&mut cjk)));
+            }
+            if c.is_alphanumeric() {
+                word.push(c);
+            } else if !word.is_empty() {
+                runs.push(Run::Word(std::mem::take(&mut word)));
+            }
+        }
+    }
+    if !word.is_empty() {
+        runs.push(Run::Word(word));
+    }
+    if !cjk.is_empty() {
+        runs.push(Run::Cjk(cjk));
+    }
+    runs
+}
+
+/// Overlapping two-character terms of a CJK run.
+fn bigrams(chars: &[char]) -> Vec<String> {
+    chars.windows(2).map(|pair| pair.iter().collect()).collect()
+}
+
+/// Generate the search terms stored in the index for a text.
+///
+/// # Example
+///
+/// ```
+/// use hobbs::board::search_terms;
+///
+/// assert_eq!(search_terms("東京都 Hello!"), "東京 京都 都 hello");
+/// ```
+pub fn search_terms(text: &str) -> String {
+    let mut terms = Vec::new();
+    for run in runs(text) {
+        match run {
+            Run::Word(word) => terms.push(word),
+            Run::Cjk(chars) => {
+                terms.extend(bigrams(&chars));
+                if let Some(last) = chars.last() {
+                    terms.push(last.to_string());
+                }
+            }
+        }
+    }
+    terms.join(" ")
+}
+
+/// Build an FTS5 MATCH expression for a user query.
+///
+/// Words and single CJK characters match as prefixes; longer CJK runs
+/// match as phrases of their bigrams. All parts must match. Returns None
+/// if the query contains nothing searchable.
+#[cfg(feature = "sqlite")]
+fn match_expression(query: &str) -> Option<String> {
+    let parts: Vec<String> = runs(query)
+        .into_iter()
+        .map(|run| match run {
+            Run::Word(word) => format!("\"{word}\"*"),
+            Run::Cjk(chars) if chars.len() == 1 => format!("\"{}\"*", chars[0]),
+            Run::Cjk(chars) => format!("\"{}\"", bigrams(&chars).join(" ")),
+        })
+        .collect();
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(" AND "))
+    }
+}
+
+/// Build a `to_tsquery` expression for a user query.
+///
+/// Same semantics as the SQLite version: prefixes for words and single
+/// CJK characters, `<->` phrases for longer CJK runs.
+#[cfg(feature = "postgres")]
+fn match_expression(query: &str) -> Option<String> {
+    let parts: Vec<String> = runs(query)
+        .into_iter()
+        .map(|run| match run {
+            Run::Word(word) => format!("'{word}':*"),
+            Run::Cjk(chars) if chars.len() == 1 => format!("'{}':*", chars[0]),
+            Run::Cjk(chars) => {
+                let phrase: Vec<String> =
+                    bigrams(&chars).iter().map(|b| format!("'{b}'")).collect();
+                format!("({})", phrase.join(" <-> "))
+            }
+        })
+        .collect();
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(" & "))
+    }
+}
+
+#[cfg(feature = "sqlite")]
+const SQL_MATCH: &str = "s.post_search MATCH $1";
+#[cfg(feature = "postgres")]
+const SQL_MATCH: &str = "s.terms @@ to_tsquery('simple', $1)";
+
+#[cfg(feature = "sqlite")]
+const SQL_SEARCH_JOIN: &str = "post_search s JOIN posts p ON p.id = s.rowid";
+#[cfg(feature = "postgres")]
+const SQL_SEARCH_JOIN: &str = "post_search s JOIN posts p ON p.id = s.post_id";
+
+/// Row type for search hits.
+#[derive(sqlx::FromRow)]
+struct SearchRow {
+    id: i64,
+    board_id: i64,
+    thread_id: Option<i64>,
+    parent_id: Option<i64>,
+    author_id: i64,
+    title: Option<String>,
+    body: String,
+    created_at: String,
+    edited_at: Option<String>,
+    edited_by: Option<i64>,
+    board_name: String,
+    thread_title: Option<String>,
+}
+
+impl From<SearchRow> for SearchHit {
+    fn from(row: SearchRow) -> Self {
+        SearchHit {
+            post: Post {
+                id: row.id,
+                board_id: row.board_id,
+                thread_id: row.thread_id,
+                parent_id: row.parent_id,
+                author_id: row.author_id,
+                title: row.title,
+                body: row.body,
+                created_at: row.created_at,
+                edited_at: row.edited_at,
+                edited_by: row.edited_by,
+            },
+            board_name: row.board_name,
+            thread_title: row.thread_title,
+        }
+    }
+}
+
+/// Repository for the post search index.
+pub struct SearchRepository<'a> {
+    pool: &'a DbPool,
+}
+
+impl<'a> SearchRepository<'a> {
+    /// Create a new SearchRepository with the given pool reference.
+    pub fn new(pool: &'a DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Add or replace the index entry for a post.
+    pub async fn index_post(&self, post: &Post) -> Result<()> {
+        let thread_title: Option<String> = match post.thread_id {
+            Some(thread_id) => sqlx::query_scalar("SELECT title FROM threads WHERE id = $1")
+                .bind(thread_id)
+                .fetch_optional(self.pool)
+                .await
+                .map_err(|e| HobbsError::Database(e.to_string()))?,
+            None => None,
+        };
+
+        let text = [
+            thread_title.as_deref(),
+            post.title.as_deref(),
+            Some(post.body.as_str()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("\n");
+
+        self.store(post.id, &search_terms(&text)).await
+    }
+
+    #[cfg(feature = "sqlite")]
+    async fn store(&self, post_id: i64, terms: &str) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+        sqlx::query("DELETE FROM post_search WHERE rowid = $1")
+            .bind(post_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+        sqlx::query("INSERT INTO post_search (rowid, terms) VALUES ($1, $2)")
+            .bind(post_id)
+            .bind(terms)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+        tx.commit()
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    #[cfg(feature = "postgres")]
+    async fn store(&self, post_id: i64, terms: &str) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO post_search (post_id, terms) VALUES ($1, to_tsvector('simple', $2))
+             ON CONFLICT (post_id) DO UPDATE SET terms = excluded.terms",
+        )
+        .bind(post_id)
+        .bind(terms)
+        .execute(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Re-index every post in a thread (after its title changed).
+    pub async fn index_thread(&self, thread_id: i64) -> Result<()> {
+        let posts = sqlx::query_as::<_, Post>(
+            "SELECT id, board_id, thread_id, parent_id, author_id, title, body,
+                    created_at, edited_at, edited_by
+             FROM posts WHERE thread_id = $1",
+        )
+        .bind(thread_id)
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        for post in &posts {
+            self.index_post(post).await?;
+        }
+        Ok(())
+    }
+
+    /// Index all posts that are missing from the index.
+    ///
+    /// Run at startup so that posts written before the index existed, or by
+    /// tools that bypass the repositories, become searchable.
+    /// Returns the number of posts indexed.
+    pub async fn sync(&self) -> Result<usize> {
+        #[cfg(feature = "sqlite")]
+        let sql = "SELECT id, board_id, thread_id, parent_id, author_id, title, body,
+                    created_at, edited_at, edited_by
+             FROM posts WHERE id NOT IN (SELECT rowid FROM post_search)";
+        #[cfg(feature = "postgres")]
+        let sql = "SELECT id, board_id, thread_id, parent_id, author_id, title, body,
+                    created_at, edited_at, edited_by
+             FROM posts WHERE id NOT IN (SELECT post_id FROM post_search)";
+
+        let posts = sqlx::query_as::<_, Post>(sql)
+            .fetch_all(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        for post in &posts {
+            self.index_post(post).await?;
+        }
+        Ok(posts.len())
+    }
+
+    /// Search posts in the given boards, newest first.
+    ///
+    /// Returns an empty list if the query has no searchable terms or no
+    /// boards are given.
+    pub async fn search(
+        &self,
+        query: &str,
+        board_ids: &[i64],
+        offset: i64,
+        limit: i64,
+    ) -> Result<Vec<SearchHit>> {
+        let Some(expression) = match_expression(query) else {
+            return Ok(Vec::new());
+        };
+        if board_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let sql = format!(
+            "SELECT p.id, p.board_id, p.thread_id, p.parent_id, p.author_id, p.title, p.body,
+                    p.created_at, p.edited_at, p.edited_by,
+                    b.name AS board_name, t.title AS thread_title
+             FROM {SQL_SEARCH_JOIN}
+             JOIN boards b ON b.id = p.board_id
+             LEFT JOIN threads t ON t.id = p.thread_id
+             WHERE {SQL_MATCH} AND p.board_id IN ({})
+             ORDER BY p.id DESC
+             LIMIT $2 OFFSET $3",
+            id_list(board_ids)
+        );
+        let rows = sqlx::query_as::<_, SearchRow>(&sql)
+            .bind(&expression)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Count the posts matching a query in the given boards.
+    pub async fn count(&self, query: &str, board_ids: &[i64]) -> Result<i64> {
+        let Some(expression) = match_expression(query) else {
+            return Ok(0);
+        };
+        if board_ids.is_empty() {
+            return Ok(0);
+        }
+
+        let sql = format!(
+            "SELECT COUNT(*) FROM {SQL_SEARCH_JOIN}
+             WHERE {SQL_MATCH} AND p.board_id IN ({})",
+            id_list(board_ids)
+        );
+        let count: i64 = sqlx::query_scalar(&sql)
+            .bind(&expression)
+            .fetch_one(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(count)
+    }
+}
+
+/// Whether a query contains anything to search for.
+pub fn is_searchable(query: &str) -> bool {
+    !runs(query).is_empty()
+}
+
+/// Comma-separated list of IDs for an `IN` clause.
+fn id_list(ids: &[i64]) -> String {
+    ids.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::{
+        BoardRepository, NewBoard, NewFlatPost, NewThread, NewThreadPost, PostRepository,
+        PostUpdate, ThreadRepository, ThreadUpdate,
+    };
+    use crate::db::{Database, NewUser, UserRepository};
+
+    #[test]
+    fn test_search_terms_japanese() {
+        assert_eq!(search_terms("東京"), "東京 京");
+        assert_eq!(search_terms("猫"), "猫");
+        assert_eq!(
+            search_terms("今日はいい天気。"),
+            "今日 日は はい いい い天 天気 気"
+        );
+    }
+
+    #[test]
+    fn test_search_terms_mixed() {
+        assert_eq!(
+            search_terms("HOBBSで Rust-2021 を使う"),
+            "hobbs で rust 2021 を使 使う う"
+        );
+        // Full-width ASCII is folded
+        assert_eq!(search_terms("ＨＯＢＢＳ"), "hobbs");
+        assert_eq!(search_terms("...!!"), "");
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_match_expression() {
+        assert_eq!(
+            match_expression("東京都 hobbs 猫").unwrap(),
+            "\"東京 京都\" AND \"hobbs\"* AND \"猫\"*"
+        );
+        assert_eq!(match_expression("\"* ()"), None);
+        assert!(!is_searchable(" - "));
+        assert!(is_searchable("a"));
+    }
+
+    async fn setup() -> (Database, i64, i64, i64) {
+        let db = Database::open_in_memory().await.unwrap();
+        let user = UserRepository::new(db.pool())
+            .create(&NewUser::new("taro", "password", "Taro"))
+            .await
+            .unwrap();
+        let board_repo = BoardRepository::new(db.pool());
+        let thread_board = board_repo.create(&NewBoard::new("General")).await.unwrap();
+        let flat_board = board_repo
+            .create(&NewBoard::new("Notices").with_board_type(crate::board::BoardType::Flat))
+            .await
+            .unwrap();
+        (db, user.id, thread_board.id, flat_board.id)
+    }
+
+    #[tokio::test]
+    async fn test_search_posts_and_titles() {
+        let (db, user_id, thread_board, flat_board) = setup().await;
+        let thread = ThreadRepository::new(db.pool())
+            .create(&NewThread::new(thread_board, "東京の天気", user_id))
+            .await
+            .unwrap();
+        let post_repo = PostRepository::new(db.pool());
+        let reply = post_repo
+            .create_thread_post(&NewThreadPost::new(
+                thread_board,
+                thread.id,
+                user_id,
+                "今日は雨が降っています",
+            ))
+            .await
+            .unwrap();
+        let notice = post_repo
+            .create_flat_post(&NewFlatPost::new(
+                flat_board,
+                user_id,
+                "Maintenance",
+                "The server restarts tonight.",
+            ))
+            .await
+            .unwrap();
+
+        let search = SearchRepository::new(db.pool());
+        let boards = [thread_board, flat_board];
+
+        // Body, thread title, flat post title
+        let hits = search.search("雨", &boards, 0, 10).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].post.id, reply.id);
+        assert_eq!(hits[0].board_name, "General");
+        assert_eq!(hits[0].thread_title.as_deref(), Some("東京の天気"));
+
+        let hits = search.search("天気", &boards, 0, 10).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].post.id, reply.id);
+
+        let hits = search.search("mainten", &boards, 0, 10).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].post.id, notice.id);
+        assert_eq!(hits[0].thread_title, None);
+
+        // All parts must match, in order within a CJK run
+        assert_eq!(search.count("雨 server", &boards).await.unwrap(), 0);
+        assert_eq!(search.count("降る", &boards).await.unwrap(), 0);
+        assert_eq!(search.count("降って", &boards).await.unwrap(), 1);
+
+        // Only the given boards are searched
+        assert!(search
+            .search("tonight", &[thread_board], 0, 10)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(search
+            .search("tonight", &[], 0, 10)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_index_follows_updates_and_deletes() {
+        let (db, user_id, thread_board, _) = setup().await;
+        let thread_repo = ThreadRepository::new(db.pool());
+        let thread = thread_repo
+            .create(&NewThread::new(thread_board, "Old title", user_id))
+            .await
+            .unwrap();
+        let post_repo = PostRepository::new(db.pool());
+        let post = post_repo
+            .create_thread_post(&NewThreadPost::new(
+                thread_board,
+                thread.id,
+                user_id,
+                "first body",
+            ))
+            .await
+            .unwrap();
+        let search = SearchRepository::new(db.pool());
+        let boards = [thread_board];
+
+        post_repo
+            .update(post.id, &PostUpdate::new().body("second body"))
+            .await
+            .unwrap();
+        assert_eq!(search.count("first", &boards).await.unwrap(), 0);
+        assert_eq!(search.count("second", &boards).await.unwrap(), 1);
+
+        thread_repo
+            .update(thread.id, &ThreadUpdate::new().title("新しい題名"))
+            .await
+            .unwrap();
+        assert_eq!(search.count("old", &boards).await.unwrap(), 0);
+        assert_eq!(search.count("題名", &boards).await.unwrap(), 1);
+
+        thread_repo.delete(thread.id).await.unwrap();
+        assert_eq!(search.count("second", &boards).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_sync_indexes_missing_posts() {
+        let (db, user_id, _, flat_board) = setup().await;
+        sqlx::query("INSERT INTO posts (board_id, author_id, title, body) VALUES ($1, $2, $3, $4)")
+            .bind(flat_board)
+            .bind(user_id)
+            .bind("Imported")
+            .bind("from an old database")
+            .execute(db.pool())
+            .await
+            .unwrap();
+
+        let search = SearchRepository::new(db.pool());
+        assert_eq!(search.count("imported", &[flat_board]).await.unwrap(), 0);
+        assert_eq!(search.sync().await.unwrap(), 1);
+        assert_eq!(search.count("imported", &[flat_board]).await.unwrap(), 1);
+        assert_eq!(search.sync().await.unwrap(), 0);
+    }
+}
@@ -4,10 +4,17 @@
 //! with built-in permission checking and pagination support.
 
 use crate::db::{Database, Role};
+use crate::filter::{ContentFilterService, FilterTarget, FilterVerdict};
+use crate::twit::KillFile;
 use crate::{HobbsError, Result};
 
+use super::group::{BoardGroup, BoardGroupRepository};
+use super::moderation::{NewPendingPost, PendingPostRepository, Submission};
 use super::post_repository::PostRepository;
 use super::repository::BoardRepository;
+use super::reply::{reply_tree, ThreadedPost};
+use super::revision::{post_versions, PostVersion, RevisionRepository};
+use super::search::{is_searchable, SearchHit, SearchRepository};
 use super::thread_repository::ThreadRepository;
 use super::types::{Board, BoardType};
 use super::{Post, Thread};
@@ -25,7 +32,7 @@ pub const MAX_TITLE_LENGTH: usize = 50;
 pub const MAX_BODY_LENGTH: usize = 10_000;
 
 /// Validate a title string.
-fn validate_title(title: &str) -> Result<()> {
+pub(crate) fn validate_title(title: &str) -> Result<()> {
     let char_count = title.chars().count();
     if char_count > MAX_TITLE_LENGTH {
         return Err(HobbsError::Validation(format!(
@@ -91,6 +98,22 @@ pub struct PaginatedResult<T> {
 }
 
 impl<T> PaginatedResult<T> {
+    /// Take one page out of a complete list of items.
+    pub fn from_all(items: Vec<T>, pagination: Pagination) -> Self {
+        let total = items.len() as i64;
+        let items = items
+            .into_iter()
+            .skip(pagination.offset.max(0) as usize)
+            .take(pagination.limit.max(0) as usize)
+            .collect();
+        Self {
+            items,
+            total,
+            offset: pagination.offset,
+            limit: pagination.limit,
+        }
+    }
+
     /// Check if there are more items after this page.
     pub fn has_more(&self) -> bool {
         self.offset + (self.items.len() as i64) < self.total
@@ -125,6 +148,12 @@ impl<'a> BoardService<'a> {
         repo.list_accessible(user_role).await
     }
 
+    /// List all board groups readable by a user with the given role.
+    pub async fn list_groups(&self, user_role: Role) -> Result<Vec<BoardGroup>> {
+        let repo = BoardGroupRepository::new(self.db.pool());
+        repo.list_accessible(user_role).await
+    }
+
     /// Get a board by ID with permission check.
     ///
     /// Returns an error if the board doesn't exist or the user doesn't have
@@ -181,6 +210,23 @@ impl<'a> BoardService<'a> {
         })
     }
 
+    /// List the threads in a board that a user's twit filters do not hide.
+    pub async fn list_visible_threads(
+        &self,
+        board_id: i64,
+        user_role: Role,
+        pagination: Pagination,
+        kill_file: &KillFile,
+    ) -> Result<PaginatedResult<Thread>> {
+        if kill_file.is_empty() {
+            return self.list_threads(board_id, user_role, pagination).await;
+        }
+
+        let mut threads = self.list_all_threads(board_id, user_role).await?;
+        threads.retain(|t| !kill_file.hides_thread(t));
+        Ok(PaginatedResult::from_all(threads, pagination))
+    }
+
     /// List all threads in a board without pagination.
     pub async fn list_all_threads(&self, board_id: i64, user_role: Role) -> Result<Vec<Thread>> {
         // First check board access
@@ -244,6 +290,25 @@ impl<'a> BoardService<'a> {
         })
     }
 
+    /// List the posts in a thread that a user's twit filters do not hide.
+    pub async fn list_visible_posts_in_thread(
+        &self,
+        thread_id: i64,
+        user_role: Role,
+        pagination: Pagination,
+        kill_file: &KillFile,
+    ) -> Result<PaginatedResult<Post>> {
+        if kill_file.is_empty() {
+            return self
+                .list_posts_in_thread(thread_id, user_role, pagination)
+                .await;
+        }
+
+        let mut posts = self.list_all_posts_in_thread(thread_id, user_role).await?;
+        posts.retain(|p| !kill_file.hides_post(p));
+        Ok(PaginatedResult::from_all(posts, pagination))
+    }
+
     /// List all posts in a thread without pagination.
     pub async fn list_all_posts_in_thread(
         &self,
@@ -267,6 +332,31 @@ impl<'a> BoardService<'a> {
         repo.list_by_thread(thread_id).await
     }
 
+    /// List all posts in a thread as a reply tree.
+    pub async fn list_thread_tree(
+        &self,
+        thread_id: i64,
+        user_role: Role,
+    ) -> Result<Vec<ThreadedPost>> {
+        let posts = self.list_all_posts_in_thread(thread_id, user_role).await?;
+        Ok(reply_tree(posts))
+    }
+
+    /// List the posts in a thread as a reply tree, leaving out posts hidden
+    /// by a user's twit filters.
+    ///
+    /// Replies to a hidden post keep their place in the tree.
+    pub async fn list_visible_thread_tree(
+        &self,
+        thread_id: i64,
+        user_role: Role,
+        kill_file: &KillFile,
+    ) -> Result<Vec<ThreadedPost>> {
+        let mut tree = self.list_thread_tree(thread_id, user_role).await?;
+        tree.retain(|t| !kill_file.hides_post(&t.post));
+        Ok(tree)
+    }
+
     /// List posts in a flat board with permission check and pagination.
     pub async fn list_posts_in_flat_board(
         &self,
@@ -297,6 +387,28 @@ impl<'a> BoardService<'a> {
         })
     }
 
+    /// List the posts in a flat board that a user's twit filters do not
+    /// hide.
+    pub async fn list_visible_posts_in_flat_board(
+        &self,
+        board_id: i64,
+        user_role: Role,
+        pagination: Pagination,
+        kill_file: &KillFile,
+    ) -> Result<PaginatedResult<Post>> {
+        if kill_file.is_empty() {
+            return self
+                .list_posts_in_flat_board(board_id, user_role, pagination)
+                .await;
+        }
+
+        let mut posts = self
+            .list_all_posts_in_flat_board(board_id, user_role)
+            .await?;
+        posts.retain(|p| !kill_file.hides_post(p));
+        Ok(PaginatedResult::from_all(posts, pagination))
+    }
+
     /// List all posts in a flat board without pagination.
     pub async fn list_all_posts_in_flat_board(
         &self,
@@ -362,11 +474,26 @@ impl<'a> BoardService<'a> {
     /// Create a new post in a thread.
     ///
     /// This automatically updates the thread's `updated_at` and `post_count`.
+    pub async fn create_thread_post(
+        &self,
+        thread_id: i64,
+        author_id: i64,
+        body: impl Into<String>,
+        user_role: Role,
+    ) -> Result<Post> {
+        self.create_reply(thread_id, None, author_id, body, user_role).await
+    }
+
+    /// Create a new post in a thread, optionally as a reply to an earlier post.
+    ///
+    /// `parent_id` must refer to a post in the same thread.
+    /// This automatically updates the thread's `updated_at` and `post_count`.
     /// The post creation and thread update are performed atomically within a transaction.
     #[cfg(feature = "sqlite")]
-    pub async fn create_thread_post(
+    pub async fn create_reply(
         &self,
         thread_id: i64,
+        parent_id: Option<i64>,
         author_id: i64,
         body: impl Into<String>,
         user_role: Role,
@@ -387,15 +514,34 @@ impl<'a> BoardService<'a> {
             ));
         }
 
+        // Locked threads accept no new posts
+        if thread.is_locked {
+            return Err(HobbsError::Permission(
+                "このスレッドはロックされているため投稿できません".to_string(),
+            ));
+        }
+
+        // The parent must be in the same thread
+        if let Some(parent_id) = parent_id {
+            let parent = PostRepository::new(self.db.pool()).get_by_id(parent_id).await?;
+            if parent.and_then(|p| p.thread_id) != Some(thread_id) {
+                return Err(HobbsError::Validation(
+                    "返信先の投稿がこのスレッドにありません".to_string(),
+                ));
+            }
+        }
+
         // Start transaction
         let mut tx = self.db.begin().await?;
 
         // Create the post within transaction
         let post_id: i64 = sqlx::query_scalar(
-            "INSERT INTO posts (board_id, thread_id, author_id, body) VALUES (?, ?, ?, ?) RETURNING id",
+            "INSERT INTO posts (board_id, thread_id, parent_id, author_id, body)
+             VALUES (?, ?, ?, ?, ?) RETURNING id",
         )
         .bind(thread.board_id)
         .bind(thread_id)
+        .bind(parent_id)
         .bind(author_id)
         .bind(&body)
         .fetch_one(&mut *tx)
@@ -418,20 +564,24 @@ impl<'a> BoardService<'a> {
 
         // Fetch the created post
         let post_repo = PostRepository::new(self.db.pool());
-        post_repo
+        let post = post_repo
             .get_by_id(post_id)
             .await?
-            .ok_or_else(|| HobbsError::NotFound("post".to_string()))
+            .ok_or_else(|| HobbsError::NotFound("post".to_string()))?;
+        SearchRepository::new(self.db.pool()).index_post(&post).await?;
+        Ok(post)
     }
 
-    /// Create a new post in a thread.
+    /// Create a new post in a thread, optionally as a reply to an earlier post.
     ///
+    /// `parent_id` must refer to a post in the same thread.
     /// This automatically updates the thread's `updated_at` and `post_count`.
     /// The post creation and thread update are performed atomically within a transaction.
     #[cfg(feature = "postgres")]
-    pub async fn create_thread_post(
+    pub async fn create_reply(
         &self,
         thread_id: i64,
+        parent_id: Option<i64>,
         author_id: i64,
         body: impl Into<String>,
         user_role: Role,
@@ -452,15 +602,34 @@ impl<'a> BoardService<'a> {
             ));
         }
 
+        // Locked threads accept no new posts
+        if thread.is_locked {
+            return Err(HobbsError::Permission(
+                "このスレッドはロックされているため投稿できません".to_string(),
+            ));
+        }
+
+        // The parent must be in the same thread
+        if let Some(parent_id) = parent_id {
+            let parent = PostRepository::new(self.db.pool()).get_by_id(parent_id).await?;
+            if parent.and_then(|p| p.thread_id) != Some(thread_id) {
+                return Err(HobbsError::Validation(
+                    "返信先の投稿がこのスレッドにありません".to_string(),
+                ));
+            }
+        }
+
         // Start transaction
         let mut tx = self.db.begin().await?;
 
         // Create the post within transaction
         let post_id: i64 = sqlx::query_scalar(
-            "INSERT INTO posts (board_id, thread_id, author_id, body) VALUES ($1, $2, $3, $4) RETURNING id",
+            "INSERT INTO posts (board_id, thread_id, parent_id, author_id, body)
+             VALUES ($1, $2, $3, $4, $5) RETURNING id",
         )
         .bind(thread.board_id)
         .bind(thread_id)
+        .bind(parent_id)
         .bind(author_id)
         .bind(&body)
         .fetch_one(&mut *tx)
@@ -483,10 +652,12 @@ impl<'a> BoardService<'a> {
 
         // Fetch the created post
         let post_repo = PostRepository::new(self.db.pool());
-        post_repo
+        let post = post_repo
             .get_by_id(post_id)
             .await?
-            .ok_or_else(|| HobbsError::NotFound("post".to_string()))
+            .ok_or_else(|| HobbsError::NotFound("post".to_string()))?;
+        SearchRepository::new(self.db.pool()).index_post(&post).await?;
+        Ok(post)
     }
 
     /// Create a new post in a flat board.
@@ -525,6 +696,223 @@ impl<'a> BoardService<'a> {
         post_repo.create_flat_post(&new_post).await
     }
 
+    // ========== Submit Operations ==========
+    //
+    // These are the entry points for posts written by users. On a moderated
+    // board, posts by members are queued for approval instead of published.
+
+    /// Submit a new thread with its first post.
+    pub async fn submit_thread(
+        &self,
+        board_id: i64,
+        title: impl Into<String>,
+        author_id: i64,
+        body: impl Into<String>,
+        user_role: Role,
+    ) -> Result<Submission> {
+        let title = title.into();
+        let body = body.into();
+        validate_title(&title)?;
+        validate_body(&body)?;
+
+        let board = self.get_board(board_id, user_role).await?;
+        let mut texts = [title, body];
+        let held = self.filter_post(author_id, &mut texts).await?.is_hold();
+        let [title, body] = texts;
+        if !held && !board.requires_approval(user_role) {
+            let thread = self.create_thread(board_id, title, author_id, user_role).await?;
+            let post = self
+                .create_thread_post(thread.id, author_id, body, user_role)
+                .await?;
+            return Ok(Submission::Posted(post));
+        }
+
+        self.queue_thread(board_id, title, author_id, body, user_role)
+            .await
+    }
+
+    /// Queue a new thread with its first post for approval, even on a board
+    /// that is not moderated.
+    ///
+    /// Used for posts held by the content filter; the content is not
+    /// filtered again.
+    pub async fn queue_thread(
+        &self,
+        board_id: i64,
+        title: impl Into<String>,
+        author_id: i64,
+        body: impl Into<String>,
+        user_role: Role,
+    ) -> Result<Submission> {
+        let title = title.into();
+        let body = body.into();
+        validate_title(&title)?;
+        validate_body(&body)?;
+
+        let board = self.get_board(board_id, user_role).await?;
+        if board.board_type != BoardType::Thread {
+            return Err(HobbsError::Validation(
+                "この掲示板はスレッド形式ではありません".to_string(),
+            ));
+        }
+        if !board.can_write(user_role) {
+            return Err(HobbsError::Permission(
+                "この掲示板に書き込む権限がありません".to_string(),
+            ));
+        }
+
+        let new_post = NewPendingPost::thread(board_id, author_id, title, body);
+        let pending = PendingPostRepository::new(self.db.pool()).create(&new_post).await?;
+        Ok(Submission::Pending(pending))
+    }
+
+    /// Submit a post in a thread, optionally as a reply to an earlier post.
+    pub async fn submit_reply(
+        &self,
+        thread_id: i64,
+        parent_id: Option<i64>,
+        author_id: i64,
+        body: impl Into<String>,
+        user_role: Role,
+    ) -> Result<Submission> {
+        let thread = self.get_thread(thread_id, user_role).await?;
+        let board = self.get_board(thread.board_id, user_role).await?;
+        let mut texts = [body.into()];
+        let held = self.filter_post(author_id, &mut texts).await?.is_hold();
+        let [body] = texts;
+        if !held && !board.requires_approval(user_role) {
+            return self
+                .create_reply(thread_id, parent_id, author_id, body, user_role)
+                .await
+                .map(Submission::Posted);
+        }
+
+        validate_body(&body)?;
+        if !board.can_write(user_role) {
+            return Err(HobbsError::Permission(
+                "この掲示板に書き込む権限がありません".to_string(),
+            ));
+        }
+        if thread.is_locked {
+            return Err(HobbsError::Permission(
+                "このスレッドはロックされているため投稿できません".to_string(),
+            ));
+        }
+        if let Some(parent_id) = parent_id {
+            let parent = PostRepository::new(self.db.pool()).get_by_id(parent_id).await?;
+            if parent.and_then(|p| p.thread_id) != Some(thread_id) {
+                return Err(HobbsError::Validation(
+                    "返信先の投稿がこのスレッドにありません".to_string(),
+                ));
+            }
+        }
+
+        let new_post = NewPendingPost::reply(board.id, thread_id, parent_id, author_id, body);
+        let pending = PendingPostRepository::new(self.db.pool()).create(&new_post).await?;
+        Ok(Submission::Pending(pending))
+    }
+
+    /// Submit a post in a flat board.
+    pub async fn submit_flat_post(
+        &self,
+        board_id: i64,
+        author_id: i64,
+        title: impl Into<String>,
+        body: impl Into<String>,
+        user_role: Role,
+    ) -> Result<Submission> {
+        let board = self.get_board(board_id, user_role).await?;
+        let mut texts = [title.into(), body.into()];
+        let held = self.filter_post(author_id, &mut texts).await?.is_hold();
+        let [title, body] = texts;
+        if !held && !board.requires_approval(user_role) {
+            return self
+                .create_flat_post(board_id, author_id, title, body, user_role)
+                .await
+                .map(Submission::Posted);
+        }
+
+        validate_title(&title)?;
+        validate_body(&body)?;
+        if board.board_type != BoardType::Flat {
+            return Err(HobbsError::Validation(
+                "この掲示板はフラット形式ではありません".to_string(),
+            ));
+        }
+        if !board.can_write(user_role) {
+            return Err(HobbsError::Permission(
+                "この掲示板に書き込む権限がありません".to_string(),
+            ));
+        }
+
+        let new_post = NewPendingPost::flat(board_id, author_id, title, body);
+        let pending = PendingPostRepository::new(self.db.pool()).create(&new_post).await?;
+        Ok(Submission::Pending(pending))
+    }
+
+    /// Run the content filter over a post written by a user.
+    ///
+    /// Masked text is replaced in `texts`. Returns an error if the post is
+    /// rejected.
+    pub async fn filter_post(&self, author_id: i64, texts: &mut [String]) -> Result<FilterVerdict> {
+        ContentFilterService::new(self.db.pool())
+            .apply(Some(author_id), FilterTarget::Post, texts)
+            .await
+    }
+
+    /// Publish a queued post and remove it from the queue.
+    ///
+    /// Requires SubOp or higher. The post keeps its original author.
+    pub async fn approve_pending(&self, pending_id: i64, user_role: Role) -> Result<Post> {
+        if !user_role.can_access(Role::SubOp) {
+            return Err(HobbsError::Permission(
+                "投稿を承認する権限がありません".to_string(),
+            ));
+        }
+
+        let queue = PendingPostRepository::new(self.db.pool());
+        let pending = queue
+            .get_by_id(pending_id)
+            .await?
+            .ok_or_else(|| HobbsError::NotFound("承認待ちの投稿".to_string()))?;
+        let board = self.get_board(pending.board_id, user_role).await?;
+        let title = pending.title.clone().unwrap_or_default();
+
+        let post = match (pending.thread_id, board.board_type) {
+            (Some(thread_id), _) => {
+                self.create_reply(
+                    thread_id,
+                    pending.parent_id,
+                    pending.author_id,
+                    pending.body.clone(),
+                    user_role,
+                )
+                .await?
+            }
+            (None, BoardType::Thread) => {
+                let thread = self
+                    .create_thread(board.id, title, pending.author_id, user_role)
+                    .await?;
+                let body = pending.body.clone();
+                self.create_thread_post(thread.id, pending.author_id, body, user_role)
+                    .await?
+            }
+            (None, BoardType::Flat) => {
+                self.create_flat_post(
+                    board.id,
+                    pending.author_id,
+                    title,
+                    pending.body.clone(),
+                    user_role,
+                )
+                .await?
+            }
+        };
+
+        queue.delete(pending_id).await?;
+        Ok(post)
+    }
+
     // ========== Delete Operations ==========
 
     /// Delete a post by ID.
@@ -687,11 +1075,37 @@ impl<'a> BoardService<'a> {
             ));
         }
 
+        // Nothing to record if the content is unchanged
+        let title_changed = title.is_some() && title != post.title;
+        if !title_changed && body == post.body {
+            return Ok(post);
+        }
+
+        // Edits cannot be held for approval, so a hold rejects them
+        let (title, body) = match title {
+            Some(title) => {
+                let mut texts = [title, body];
+                self.filter_edit(user_id, &mut texts).await?;
+                let [title, body] = texts;
+                (Some(title), body)
+            }
+            None => {
+                let mut texts = [body];
+                self.filter_edit(user_id, &mut texts).await?;
+                let [body] = texts;
+                (None, body)
+            }
+        };
+
+        // Keep the version being replaced
+        RevisionRepository::new(self.db.pool()).create(&post).await?;
+
         let mut update = super::post::PostUpdate::new();
         if title.is_some() {
             update.title = Some(title);
         }
         update.body = Some(body);
+        update.editor = Some(user_id.unwrap_or(post.author_id));
 
         post_repo
             .update(post_id, &update)
@@ -699,6 +1113,40 @@ impl<'a> BoardService<'a> {
             .ok_or_else(|| HobbsError::NotFound("post".to_string()))
     }
 
+    /// Run the content filter over an edit, rejecting content that would
+    /// be held.
+    async fn filter_edit(&self, user_id: Option<i64>, texts: &mut [String]) -> Result<()> {
+        ContentFilterService::new(self.db.pool())
+            .apply_strict(user_id, FilterTarget::Post, texts)
+            .await
+    }
+
+    /// Get every version of a post, oldest first (the last is the current one).
+    ///
+    /// Permission rules:
+    /// - The post author can view the history of their own post
+    /// - SubOp or higher can view the history of any post
+    pub async fn post_history(
+        &self,
+        post_id: i64,
+        user_id: Option<i64>,
+        user_role: Role,
+    ) -> Result<Vec<PostVersion>> {
+        let post = self.get_post(post_id, user_role).await?;
+
+        let is_owner = user_id.is_some() && user_id == Some(post.author_id);
+        if !is_owner && user_role < Role::SubOp {
+            return Err(HobbsError::Permission(
+                "この投稿の編集履歴を見る権限がありません".to_string(),
+            ));
+        }
+
+        let revisions = RevisionRepository::new(self.db.pool())
+            .list_by_post(post_id)
+            .await?;
+        Ok(post_versions(&post, revisions))
+    }
+
     /// Update a thread by ID.
     ///
     /// Permission rules:
@@ -733,6 +1181,10 @@ impl<'a> BoardService<'a> {
             ));
         }
 
+        let mut texts = [title];
+        self.filter_edit(user_id, &mut texts).await?;
+        let [title] = texts;
+
         let update = super::thread::ThreadUpdate::new().title(title);
 
         thread_repo
@@ -741,6 +1193,45 @@ impl<'a> BoardService<'a> {
             .ok_or_else(|| HobbsError::NotFound("thread".to_string()))
     }
 
+    /// Pin/unpin or lock/unlock a thread.
+    ///
+    /// Only SubOp or higher can change these flags. `None` leaves a flag as is.
+    pub async fn set_thread_flags(
+        &self,
+        thread_id: i64,
+        user_role: Role,
+        is_pinned: Option<bool>,
+        is_locked: Option<bool>,
+    ) -> Result<Thread> {
+        let thread_repo = ThreadRepository::new(self.db.pool());
+        let thread = thread_repo
+            .get_by_id(thread_id)
+            .await?
+            .ok_or_else(|| HobbsError::NotFound("thread".to_string()))?;
+
+        // Check board access
+        self.get_board(thread.board_id, user_role).await?;
+
+        if user_role < Role::SubOp {
+            return Err(HobbsError::Permission(
+                "スレッドの固定・ロックを変更する権限がありません".to_string(),
+            ));
+        }
+
+        let mut update = super::thread::ThreadUpdate::new();
+        if let Some(is_pinned) = is_pinned {
+            update = update.pinned(is_pinned);
+        }
+        if let Some(is_locked) = is_locked {
+            update = update.locked(is_locked);
+        }
+
+        thread_repo
+            .update(thread_id, &update)
+            .await?
+            .ok_or_else(|| HobbsError::NotFound("thread".to_string()))
+    }
+
     /// Delete a thread by ID.
     ///
     /// Permission rules:
@@ -776,6 +1267,48 @@ impl<'a> BoardService<'a> {
         thread_repo.delete(thread_id).await
     }
 
+    // ========== Search ==========
+
+    /// Search posts across the boards the user can read, newest first.
+    ///
+    /// With `board_id`, only that board is searched.
+    pub async fn search(
+        &self,
+        query: &str,
+        board_id: Option<i64>,
+        user_role: Role,
+        pagination: Pagination,
+    ) -> Result<PaginatedResult<SearchHit>> {
+        if !is_searchable(query) {
+            return Err(HobbsError::Validation(
+                "検索語を入力してください".to_string(),
+            ));
+        }
+
+        let board_ids: Vec<i64> = match board_id {
+            Some(id) => vec![self.get_board(id, user_role).await?.id],
+            None => self
+                .list_boards(user_role)
+                .await?
+                .into_iter()
+                .map(|board| board.id)
+                .collect(),
+        };
+
+        let search_repo = SearchRepository::new(self.db.pool());
+        let items = search_repo
+            .search(query, &board_ids, pagination.offset, pagination.limit)
+            .await?;
+        let total = search_repo.count(query, &board_ids).await?;
+
+        Ok(PaginatedResult {
+            items,
+            total,
+            offset: pagination.offset,
+            limit: pagination.limit,
+        })
+    }
+
     /// Get a post by ID with permission check.
     pub async fn get_post(&self, post_id: i64, user_role: Role) -> Result<Post> {
         let post_repo = PostRepository::new(self.db.pool());
@@ -796,6 +1329,7 @@ mod tests {
     use super::*;
     use crate::board::{NewBoard, NewFlatPost, NewThread, NewThreadPost};
     use crate::db::{NewUser, UserRepository};
+    use crate::twit::TwitFilterService;
 
     async fn setup_db() -> Database {
         Database::open_in_memory().await.unwrap()
@@ -1092,32 +1626,70 @@ mod tests {
         let post_repo = PostRepository::new(db.pool());
         for i in 1..=5 {
             post_repo
-                .create_thread_post(&NewThreadPost::new(
+                .create_thread_post(&NewThreadPost::new(
+                    board.id,
+                    thread.id,
+                    author_id,
+                    format!("Post {i}"),
+                ))
+                .await
+                .unwrap();
+        }
+
+        let service = BoardService::new(&db);
+        let posts = service
+            .list_all_posts_in_thread(thread.id, Role::Guest)
+            .await
+            .unwrap();
+
+        assert_eq!(posts.len(), 5);
+    }
+
+    // list_posts_in_flat_board tests
+    #[tokio::test]
+    async fn test_list_posts_in_flat_board() {
+        let db = setup_db().await;
+        let author_id = create_test_user(&db).await;
+        let board_repo = BoardRepository::new(db.pool());
+        let board = board_repo
+            .create(&NewBoard::new("flat").with_board_type(BoardType::Flat))
+            .await
+            .unwrap();
+
+        let post_repo = PostRepository::new(db.pool());
+        for i in 1..=5 {
+            post_repo
+                .create_flat_post(&NewFlatPost::new(
                     board.id,
-                    thread.id,
                     author_id,
-                    format!("Post {i}"),
+                    format!("Title {i}"),
+                    format!("Body {i}"),
                 ))
                 .await
                 .unwrap();
         }
 
         let service = BoardService::new(&db);
-        let posts = service
-            .list_all_posts_in_thread(thread.id, Role::Guest)
+        let result = service
+            .list_posts_in_flat_board(board.id, Role::Guest, Pagination::new(0, 3))
             .await
             .unwrap();
 
-        assert_eq!(posts.len(), 5);
+        assert_eq!(result.items.len(), 3);
+        assert_eq!(result.total, 5);
+        assert!(result.has_more());
     }
 
-    // list_posts_in_flat_board tests
     #[tokio::test]
-    async fn test_list_posts_in_flat_board() {
+    async fn test_list_visible_posts_in_flat_board() {
         let db = setup_db().await;
         let author_id = create_test_user(&db).await;
-        let board_repo = BoardRepository::new(db.pool());
-        let board = board_repo
+        let reader_id = UserRepository::new(db.pool())
+            .create(&NewUser::new("reader", "hash", "Reader"))
+            .await
+            .unwrap()
+            .id;
+        let board = BoardRepository::new(db.pool())
             .create(&NewBoard::new("flat").with_board_type(BoardType::Flat))
             .await
             .unwrap();
@@ -1135,15 +1707,37 @@ mod tests {
                 .unwrap();
         }
 
+        let twit = TwitFilterService::new(db.pool());
+        twit.add_keyword(reader_id, "body 2").await.unwrap();
+        let kill_file = twit.kill_file(reader_id).await.unwrap();
+
         let service = BoardService::new(&db);
         let result = service
-            .list_posts_in_flat_board(board.id, Role::Guest, Pagination::new(0, 3))
+            .list_visible_posts_in_flat_board(
+                board.id,
+                Role::Member,
+                Pagination::new(0, 3),
+                &kill_file,
+            )
             .await
             .unwrap();
-
+        assert_eq!(result.total, 4);
         assert_eq!(result.items.len(), 3);
-        assert_eq!(result.total, 5);
-        assert!(result.has_more());
+        assert!(result.items.iter().all(|p| p.body != "Body 2"));
+
+        twit.add_user(reader_id, "testuser").await.unwrap();
+        let kill_file = twit.kill_file(reader_id).await.unwrap();
+        let result = service
+            .list_visible_posts_in_flat_board(
+                board.id,
+                Role::Member,
+                Pagination::new(0, 3),
+                &kill_file,
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.total, 0);
+        assert!(!result.has_more());
     }
 
     #[tokio::test]
@@ -1428,6 +2022,56 @@ mod tests {
         assert_eq!(updated_thread.post_count, 1);
     }
 
+    #[tokio::test]
+    async fn test_create_reply_builds_tree() {
+        let db = setup_db().await;
+        let author_id = create_test_user(&db).await;
+        let board_repo = BoardRepository::new(db.pool());
+        let board = board_repo
+            .create(&NewBoard::new("test").with_board_type(BoardType::Thread))
+            .await
+            .unwrap();
+        let thread_repo = ThreadRepository::new(db.pool());
+        let thread = thread_repo
+            .create(&NewThread::new(board.id, "Test Thread", author_id))
+            .await
+            .unwrap();
+        let other = thread_repo
+            .create(&NewThread::new(board.id, "Other Thread", author_id))
+            .await
+            .unwrap();
+
+        let service = BoardService::new(&db);
+        let first = service
+            .create_thread_post(thread.id, author_id, "First", Role::Member)
+            .await
+            .unwrap();
+        let second = service
+            .create_thread_post(thread.id, author_id, "Second", Role::Member)
+            .await
+            .unwrap();
+        let reply = service
+            .create_reply(thread.id, Some(first.id), author_id, "Re: First", Role::Member)
+            .await
+            .unwrap();
+        assert_eq!(reply.parent_id, Some(first.id));
+
+        let tree: Vec<(i64, usize)> = service
+            .list_thread_tree(thread.id, Role::Member)
+            .await
+            .unwrap()
+            .iter()
+            .map(|t| (t.post.id, t.depth))
+            .collect();
+        assert_eq!(tree, vec![(first.id, 0), (reply.id, 1), (second.id, 0)]);
+
+        // The parent must be in the same thread
+        let result = service
+            .create_reply(other.id, Some(first.id), author_id, "Wrong", Role::Member)
+            .await;
+        assert!(matches!(result, Err(HobbsError::Validation(_))));
+    }
+
     #[tokio::test]
     async fn test_create_thread_post_permission_denied() {
         let db = setup_db().await;
@@ -1855,6 +2499,64 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_set_thread_flags() {
+        let db = setup_db().await;
+        let author_id = create_test_user(&db).await;
+        let board_repo = BoardRepository::new(db.pool());
+        let board = board_repo
+            .create(&NewBoard::new("test").with_board_type(BoardType::Thread))
+            .await
+            .unwrap();
+
+        let service = BoardService::new(&db);
+        let notice = service
+            .create_thread(board.id, "Notice", author_id, Role::Member)
+            .await
+            .unwrap();
+        service
+            .create_thread(board.id, "Chatter", author_id, Role::Member)
+            .await
+            .unwrap();
+
+        // Members cannot pin or lock, even their own thread
+        let result = service
+            .set_thread_flags(notice.id, Role::Member, Some(true), None)
+            .await;
+        assert!(matches!(result, Err(HobbsError::Permission(_))));
+
+        let thread = service
+            .set_thread_flags(notice.id, Role::SubOp, Some(true), Some(true))
+            .await
+            .unwrap();
+        assert!(thread.is_pinned);
+        assert!(thread.is_locked);
+
+        let threads = service
+            .list_threads(board.id, Role::Member, Pagination::new(0, 10))
+            .await
+            .unwrap();
+        assert_eq!(threads.items[0].title, "Notice");
+
+        // Locked threads reject new posts
+        let result = service
+            .create_thread_post(notice.id, author_id, "Reply", Role::SubOp)
+            .await;
+        assert!(matches!(result, Err(HobbsError::Permission(_))));
+
+        // Unlocking keeps the pin
+        let thread = service
+            .set_thread_flags(notice.id, Role::SubOp, None, Some(false))
+            .await
+            .unwrap();
+        assert!(thread.is_pinned);
+        assert!(!thread.is_locked);
+        service
+            .create_thread_post(notice.id, author_id, "Reply", Role::Member)
+            .await
+            .unwrap();
+    }
+
     #[tokio::test]
     async fn test_update_thread_not_found() {
         let db = setup_db().await;
@@ -1915,6 +2617,54 @@ mod tests {
 
     // ========== get_post tests ==========
 
+    #[tokio::test]
+    async fn test_update_post_keeps_history() {
+        let db = setup_db().await;
+        let author_id = create_test_user(&db).await;
+        let board_repo = BoardRepository::new(db.pool());
+        let board = board_repo
+            .create(&NewBoard::new("test").with_board_type(BoardType::Flat))
+            .await
+            .unwrap();
+
+        let service = BoardService::new(&db);
+        let post = service
+            .create_flat_post(board.id, author_id, "Title", "one\ntwo", Role::Member)
+            .await
+            .unwrap();
+        assert!(!post.is_edited());
+
+        // Saving unchanged content records nothing
+        let same = service
+            .update_post(post.id, Some(author_id), Role::Member, None, "one\ntwo".to_string())
+            .await
+            .unwrap();
+        assert!(!same.is_edited());
+
+        let edited = service
+            .update_post(post.id, Some(author_id), Role::Member, None, "one\nthree".to_string())
+            .await
+            .unwrap();
+        assert!(edited.is_edited());
+        assert_eq!(edited.edited_by, Some(author_id));
+
+        let history = service
+            .post_history(post.id, Some(author_id), Role::Member)
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].body, "one\ntwo");
+        assert_eq!(history[0].created_at, post.created_at);
+        assert!(history[1].current);
+        assert_eq!(history[1].body, "one\nthree");
+
+        // Only the author and SubOp+ may see the history
+        let result = service.post_history(post.id, Some(999), Role::Member).await;
+        assert!(matches!(result, Err(HobbsError::Permission(_))));
+        let history = service.post_history(post.id, None, Role::SubOp).await.unwrap();
+        assert_eq!(history.len(), 2);
+    }
+
     #[tokio::test]
     async fn test_get_post_success() {
         let db = setup_db().await;
@@ -2092,4 +2842,284 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    // search tests
+    #[tokio::test]
+    async fn test_search_respects_read_role() {
+        let db = setup_db().await;
+        let user_id = create_test_user(&db).await;
+        let board_repo = BoardRepository::new(db.pool());
+        let public = board_repo
+            .create(
+                &NewBoard::new("public")
+                    .with_board_type(BoardType::Flat)
+                    .with_min_read_role(Role::Guest),
+            )
+            .await
+            .unwrap();
+        let staff = board_repo
+            .create(
+                &NewBoard::new("staff")
+                    .with_board_type(BoardType::Flat)
+                    .with_min_read_role(Role::SubOp),
+            )
+            .await
+            .unwrap();
+        let post_repo = PostRepository::new(db.pool());
+        for board_id in [public.id, staff.id] {
+            post_repo
+                .create_flat_post(&NewFlatPost::new(
+                    board_id,
+                    user_id,
+                    "会議",
+                    "次の会議は月曜日",
+                ))
+                .await
+                .unwrap();
+        }
+
+        let service = BoardService::new(&db);
+        let result = service
+            .search("会議", None, Role::Guest, Pagination::first(10))
+            .await
+            .unwrap();
+        assert_eq!(result.total, 1);
+        assert_eq!(result.items[0].board_name, "public");
+
+        let result = service
+            .search("月曜", None, Role::SysOp, Pagination::first(10))
+            .await
+            .unwrap();
+        assert_eq!(result.total, 2);
+
+        let result = service
+            .search("会議", Some(staff.id), Role::Member, Pagination::first(10))
+            .await;
+        assert!(matches!(result, Err(HobbsError::Permission(_))));
+
+        let result = service
+            .search("  ", None, Role::Guest, Pagination::first(10))
+            .await;
+        assert!(matches!(result, Err(HobbsError::Validation(_))));
+    }
+
+    // moderation tests
+    #[tokio::test]
+    async fn test_submit_on_unmoderated_board_posts_directly() {
+        let db = setup_db().await;
+        let user_id = create_test_user(&db).await;
+        let board = BoardRepository::new(db.pool())
+            .create(&NewBoard::new("open"))
+            .await
+            .unwrap();
+
+        let service = BoardService::new(&db);
+        let submission = service
+            .submit_thread(board.id, "Topic", user_id, "first", Role::Member)
+            .await
+            .unwrap();
+        let Submission::Posted(post) = submission else {
+            panic!("expected a published post");
+        };
+        let thread = service
+            .get_thread(post.thread_id.unwrap(), Role::Member)
+            .await
+            .unwrap();
+        assert_eq!(thread.title, "Topic");
+        assert_eq!(thread.post_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_submit_on_moderated_board_is_queued() {
+        let db = setup_db().await;
+        let user_id = create_test_user(&db).await;
+        let boards = BoardRepository::new(db.pool());
+        let board = boards
+            .create(&NewBoard::new("moderated").with_moderated(true))
+            .await
+            .unwrap();
+        let flat = boards
+            .create(
+                &NewBoard::new("flat")
+                    .with_board_type(BoardType::Flat)
+                    .with_moderated(true),
+            )
+            .await
+            .unwrap();
+        let service = BoardService::new(&db);
+        let queue = PendingPostRepository::new(db.pool());
+
+        let submission = service
+            .submit_thread(board.id, "Topic", user_id, "first", Role::Member)
+            .await
+            .unwrap();
+        assert!(submission.is_pending());
+        assert!(service.list_all_threads(board.id, Role::Member).await.unwrap().is_empty());
+
+        let submission = service
+            .submit_flat_post(flat.id, user_id, "Note", "hello", Role::Member)
+            .await
+            .unwrap();
+        assert!(submission.is_pending());
+        assert_eq!(
+            PostRepository::new(db.pool()).count_by_board(flat.id).await.unwrap(),
+            0
+        );
+        assert_eq!(queue.count().await.unwrap(), 2);
+
+        // Moderators post directly
+        let submission = service
+            .submit_thread(board.id, "Rules", user_id, "be nice", Role::SubOp)
+            .await
+            .unwrap();
+        let Submission::Posted(rules) = submission else {
+            panic!("expected a published post");
+        };
+
+        // Replies are queued too, and validated before queueing
+        let rules_thread = rules.thread_id.unwrap();
+        let submission = service
+            .submit_reply(rules_thread, Some(rules.id), user_id, "ok", Role::Member)
+            .await
+            .unwrap();
+        assert!(submission.is_pending());
+        let result = service
+            .submit_reply(rules_thread, None, user_id, "  ", Role::Member)
+            .await;
+        assert!(matches!(result, Err(HobbsError::Validation(_))));
+        let result = service
+            .submit_thread(flat.id, "Topic", user_id, "body", Role::Member)
+            .await;
+        assert!(matches!(result, Err(HobbsError::Validation(_))));
+        assert_eq!(queue.count().await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_submit_applies_content_filter() {
+        use crate::filter::{FilterAction, FilterRuleType, NewFilterRule};
+
+        let db = setup_db().await;
+        let user_id = create_test_user(&db).await;
+        let board = BoardRepository::new(db.pool())
+            .create(&NewBoard::new("open"))
+            .await
+            .unwrap();
+        let filter = ContentFilterService::new(db.pool());
+        for (rule_type, pattern, action) in [
+            (FilterRuleType::Word, "darn", FilterAction::Mask),
+            (FilterRuleType::Word, "casino", FilterAction::Hold),
+            (FilterRuleType::Word, "slur", FilterAction::Reject),
+        ] {
+            filter
+                .add_rule(
+                    user_id,
+                    Role::SysOp,
+                    NewFilterRule::new(rule_type, pattern, action),
+                )
+                .await
+                .unwrap();
+        }
+        let service = BoardService::new(&db);
+
+        let Submission::Posted(post) = service
+            .submit_thread(board.id, "Darn it", user_id, "darn", Role::Member)
+            .await
+            .unwrap()
+        else {
+            panic!("expected a published post");
+        };
+        assert_eq!(post.body, "****");
+        let thread = service
+            .get_thread(post.thread_id.unwrap(), Role::Member)
+            .await
+            .unwrap();
+        assert_eq!(thread.title, "**** it");
+
+        // Held posts are queued even on a board that is not moderated
+        let submission = service
+            .submit_reply(thread.id, None, user_id, "visit my casino", Role::Member)
+            .await
+            .unwrap();
+        assert!(submission.is_pending());
+
+        let result = service
+            .submit_reply(thread.id, None, user_id, "slur", Role::Member)
+            .await;
+        assert!(matches!(result, Err(HobbsError::Validation(_))));
+
+        // Edits cannot be held, so a hold rejects them
+        let result = service
+            .update_post(
+                post.id,
+                Some(user_id),
+                Role::Member,
+                None,
+                "casino".to_string(),
+            )
+            .await;
+        assert!(matches!(result, Err(HobbsError::Validation(_))));
+        let thread = service.get_thread(thread.id, Role::Member).await.unwrap();
+        assert_eq!(thread.post_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_approve_pending() {
+        let db = setup_db().await;
+        let user_id = create_test_user(&db).await;
+        let boards = BoardRepository::new(db.pool());
+        let board = boards
+            .create(&NewBoard::new("moderated").with_moderated(true))
+            .await
+            .unwrap();
+        let flat = boards
+            .create(
+                &NewBoard::new("flat")
+                    .with_board_type(BoardType::Flat)
+                    .with_moderated(true),
+            )
+            .await
+            .unwrap();
+        let service = BoardService::new(&db);
+
+        let Submission::Pending(thread) = service
+            .submit_thread(board.id, "Topic", user_id, "first", Role::Member)
+            .await
+            .unwrap()
+        else {
+            panic!("expected a queued post");
+        };
+        let Submission::Pending(note) = service
+            .submit_flat_post(flat.id, user_id, "Note", "hello", Role::Member)
+            .await
+            .unwrap()
+        else {
+            panic!("expected a queued post");
+        };
+
+        let result = service.approve_pending(thread.id, Role::Member).await;
+        assert!(matches!(result, Err(HobbsError::Permission(_))));
+
+        let first = service.approve_pending(thread.id, Role::SubOp).await.unwrap();
+        assert_eq!(first.author_id, user_id);
+        let threads = service.list_all_threads(board.id, Role::Member).await.unwrap();
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].title, "Topic");
+
+        let Submission::Pending(reply) = service
+            .submit_reply(threads[0].id, Some(first.id), user_id, "reply", Role::Member)
+            .await
+            .unwrap()
+        else {
+            panic!("expected a queued post");
+        };
+        let reply = service.approve_pending(reply.id, Role::SysOp).await.unwrap();
+        assert_eq!(reply.parent_id, Some(first.id));
+
+        let post = service.approve_pending(note.id, Role::SubOp).await.unwrap();
+        assert_eq!(post.title.as_deref(), Some("Note"));
+
+        assert_eq!(PendingPostRepository::new(db.pool()).count().await.unwrap(), 0);
+        let result = service.approve_pending(note.id, Role::SubOp).await;
+        assert!(matches!(result, Err(HobbsError::NotFound(_))));
+    }
 }
@@ -19,6 +19,10 @@ pub struct Thread {
     pub created_at: String,
     /// Last update timestamp (when a new post was added).
     pub updated_at: String,
+    /// Whether the thread is pinned to the top of the list.
+    pub is_pinned: bool,
+    /// Whether the thread is closed to new posts.
+    pub is_locked: bool,
 }
 
 /// Data for creating a new thread.
@@ -52,6 +56,10 @@ pub struct ThreadUpdate {
     pub post_count_delta: Option<i32>,
     /// Update the updated_at timestamp to now.
     pub touch: bool,
+    /// New pinned state.
+    pub is_pinned: Option<bool>,
+    /// New locked state.
+    pub is_locked: Option<bool>,
 }
 
 impl ThreadUpdate {
@@ -84,9 +92,25 @@ impl ThreadUpdate {
         self
     }
 
+    /// Set pinned state.
+    pub fn pinned(mut self, is_pinned: bool) -> Self {
+        self.is_pinned = Some(is_pinned);
+        self
+    }
+
+    /// Set locked state.
+    pub fn locked(mut self, is_locked: bool) -> Self {
+        self.is_locked = Some(is_locked);
+        self
+    }
+
     /// Check if any fields are set.
     pub fn is_empty(&self) -> bool {
-        self.title.is_none() && self.post_count_delta.is_none() && !self.touch
+        self.title.is_none()
+            && self.post_count_delta.is_none()
+            && !self.touch
+            && self.is_pinned.is_none()
+            && self.is_locked.is_none()
     }
 }
 
@@ -136,6 +160,14 @@ mod tests {
         assert!(!update.is_empty());
     }
 
+    #[test]
+    fn test_thread_update_flags() {
+        let update = ThreadUpdate::new().pinned(true).locked(false);
+        assert_eq!(update.is_pinned, Some(true));
+        assert_eq!(update.is_locked, Some(false));
+        assert!(!update.is_empty());
+    }
+
     #[test]
     fn test_thread_update_combined() {
         let update = ThreadUpdate::new()
@@ -4,6 +4,7 @@
 
 use sqlx::{FromRow, QueryBuilder};
 
+use super::search::SearchRepository;
 use super::thread::{NewThread, ThreadUpdate};
 
 // SQL datetime function for current timestamp
@@ -33,6 +34,10 @@ pub struct ThreadRow {
     pub created_at: String,
     /// Last update timestamp (when a new post was added).
     pub updated_at: String,
+    /// Whether the thread is pinned to the top of the list.
+    pub is_pinned: bool,
+    /// Whether the thread is closed to new posts.
+    pub is_locked: bool,
 }
 
 impl From<ThreadRow> for super::thread::Thread {
@@ -45,6 +50,8 @@ impl From<ThreadRow> for super::thread::Thread {
             post_count: row.post_count,
             created_at: row.created_at,
             updated_at: row.updated_at,
+            is_pinned: row.is_pinned,
+            is_locked: row.is_locked,
         }
     }
 }
@@ -101,7 +108,8 @@ impl<'a> ThreadRepository<'a> {
     /// Get a thread by ID.
     pub async fn get_by_id(&self, id: i64) -> Result<Option<super::thread::Thread>> {
         let result = sqlx::query_as::<_, ThreadRow>(
-            "SELECT id, board_id, title, author_id, post_count, created_at, updated_at
+            "SELECT id, board_id, title, author_id, post_count, created_at, updated_at,
+             is_pinned, is_locked
              FROM threads WHERE id = $1",
         )
         .bind(id)
@@ -140,6 +148,14 @@ impl<'a> ThreadRepository<'a> {
         if update.touch {
             separated.push(format!("updated_at = {SQL_NOW}"));
         }
+        if let Some(is_pinned) = update.is_pinned {
+            separated.push("is_pinned = ");
+            separated.push_bind_unseparated(is_pinned);
+        }
+        if let Some(is_locked) = update.is_locked {
+            separated.push("is_locked = ");
+            separated.push_bind_unseparated(is_locked);
+        }
 
         query.push(" WHERE id = ");
         query.push_bind(id);
@@ -154,6 +170,10 @@ impl<'a> ThreadRepository<'a> {
             return Ok(None);
         }
 
+        if update.title.is_some() {
+            SearchRepository::new(self.pool).index_thread(id).await?;
+        }
+
         self.get_by_id(id).await
     }
 
@@ -185,6 +205,14 @@ impl<'a> ThreadRepository<'a> {
         if update.touch {
             separated.push(format!("updated_at = {SQL_NOW}"));
         }
+        if let Some(is_pinned) = update.is_pinned {
+            separated.push("is_pinned = ");
+            separated.push_bind_unseparated(is_pinned);
+        }
+        if let Some(is_locked) = update.is_locked {
+            separated.push("is_locked = ");
+            separated.push_bind_unseparated(is_locked);
+        }
 
         query.push(" WHERE id = ");
         query.push_bind(id);
@@ -199,6 +227,10 @@ impl<'a> ThreadRepository<'a> {
             return Ok(None);
         }
 
+        if update.title.is_some() {
+            SearchRepository::new(self.pool).index_thread(id).await?;
+        }
+
         self.get_by_id(id).await
     }
 
@@ -215,11 +247,13 @@ impl<'a> ThreadRepository<'a> {
         Ok(result.rows_affected() > 0)
     }
 
-    /// List threads in a board, ordered by updated_at descending.
+    /// List threads in a board, pinned threads first, then by updated_at descending.
     pub async fn list_by_board(&self, board_id: i64) -> Result<Vec<super::thread::Thread>> {
         let rows = sqlx::query_as::<_, ThreadRow>(
-            "SELECT id, board_id, title, author_id, post_count, created_at, updated_at
-             FROM threads WHERE board_id = $1 ORDER BY updated_at DESC, id DESC",
+            "SELECT id, board_id, title, author_id, post_count, created_at, updated_at,
+             is_pinned, is_locked
+             FROM threads WHERE board_id = $1
+             ORDER BY is_pinned DESC, updated_at DESC, id DESC",
         )
         .bind(board_id)
         .fetch_all(self.pool)
@@ -237,8 +271,10 @@ impl<'a> ThreadRepository<'a> {
         limit: i64,
     ) -> Result<Vec<super::thread::Thread>> {
         let rows = sqlx::query_as::<_, ThreadRow>(
-            "SELECT id, board_id, title, author_id, post_count, created_at, updated_at
-             FROM threads WHERE board_id = $1 ORDER BY updated_at DESC, id DESC LIMIT $2 OFFSET $3",
+            "SELECT id, board_id, title, author_id, post_count, created_at, updated_at,
+             is_pinned, is_locked
+             FROM threads WHERE board_id = $1
+             ORDER BY is_pinned DESC, updated_at DESC, id DESC LIMIT $2 OFFSET $3",
         )
         .bind(board_id)
         .bind(limit)
@@ -253,7 +289,8 @@ impl<'a> ThreadRepository<'a> {
     /// List threads by author.
     pub async fn list_by_author(&self, author_id: i64) -> Result<Vec<super::thread::Thread>> {
         let rows = sqlx::query_as::<_, ThreadRow>(
-            "SELECT id, board_id, title, author_id, post_count, created_at, updated_at
+            "SELECT id, board_id, title, author_id, post_count, created_at, updated_at,
+             is_pinned, is_locked
              FROM threads WHERE author_id = $1 ORDER BY updated_at DESC",
         )
         .bind(author_id)
@@ -508,6 +545,40 @@ mod tests {
         assert_eq!(page2[1].title, "Thread 2");
     }
 
+    #[tokio::test]
+    async fn test_pinned_threads_listed_first() {
+        let db = setup_db().await;
+        let board_id = create_test_board(&db).await;
+        let author_id = create_test_user(&db).await;
+        let repo = ThreadRepository::new(db.pool());
+
+        let notice = repo
+            .create(&NewThread::new(board_id, "Notice", author_id))
+            .await
+            .unwrap();
+        for i in 1..=3 {
+            repo.create(&NewThread::new(board_id, format!("Thread {i}"), author_id))
+                .await
+                .unwrap();
+        }
+
+        let pinned = repo
+            .update(notice.id, &ThreadUpdate::new().pinned(true).locked(true))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(pinned.is_pinned);
+        assert!(pinned.is_locked);
+
+        let threads = repo.list_by_board(board_id).await.unwrap();
+        assert_eq!(threads[0].title, "Notice");
+        assert_eq!(threads[1].title, "Thread 3");
+
+        let page1 = repo.list_by_board_paginated(board_id, 0, 2).await.unwrap();
+        assert_eq!(page1[0].title, "Notice");
+        assert_eq!(page1[1].title, "Thread 3");
+    }
+
     #[tokio::test]
     async fn test_list_by_author() {
         let db = setup_db().await;
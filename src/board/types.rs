@@ -74,20 +74,49 @@ pub struct Board {
     pub is_active: bool,
     /// Whether auto-paging is disabled for this board.
     pub disable_paging: bool,
+    /// Whether color codes in posts are displayed (stripped when false).
+    pub allow_color: bool,
+    /// Whether posts by members wait for approval by a SubOp.
+    pub is_moderated: bool,
+    /// Board group (conference) this board belongs to.
+    pub group_id: Option<i64>,
+    /// Minimum role required to read the board's group, if it has one.
+    pub group_read_role: Option<Role>,
+    /// Maximum number of posts kept; older posts are pruned.
+    pub max_posts: Option<i64>,
+    /// Maximum age of posts in days; older posts are pruned.
+    pub max_age_days: Option<i64>,
+    /// Whether pruned posts are written to an archive file before deletion.
+    pub archive_pruned: bool,
     /// Board creation timestamp.
     pub created_at: String,
 }
 
 impl Board {
     /// Check if a user with the given role can read this board.
+    ///
+    /// Boards in a group also require the group's read role.
     pub fn can_read(&self, role: Role) -> bool {
         role.can_access(self.min_read_role)
+            && self.group_read_role.is_none_or(|r| role.can_access(r))
     }
 
     /// Check if a user with the given role can write to this board.
     pub fn can_write(&self, role: Role) -> bool {
         role.can_access(self.min_write_role)
     }
+
+    /// Check if posts by a user with the given role need moderator approval.
+    ///
+    /// SubOps and SysOps post directly even on moderated boards.
+    pub fn requires_approval(&self, role: Role) -> bool {
+        self.is_moderated && !role.can_access(Role::SubOp)
+    }
+
+    /// Check if the board has any retention rule.
+    pub fn has_retention(&self) -> bool {
+        self.max_posts.is_some() || self.max_age_days.is_some()
+    }
 }
 
 /// Data for creating a new board.
@@ -107,6 +136,18 @@ pub struct NewBoard {
     pub sort_order: i32,
     /// Whether auto-paging is disabled for this board.
     pub disable_paging: bool,
+    /// Whether color codes in posts are displayed (defaults to true).
+    pub allow_color: bool,
+    /// Whether posts by members wait for approval (defaults to false).
+    pub is_moderated: bool,
+    /// Board group (defaults to none).
+    pub group_id: Option<i64>,
+    /// Maximum number of posts kept (defaults to unlimited).
+    pub max_posts: Option<i64>,
+    /// Maximum age of posts in days (defaults to unlimited).
+    pub max_age_days: Option<i64>,
+    /// Whether pruned posts are archived (defaults to false).
+    pub archive_pruned: bool,
 }
 
 impl NewBoard {
@@ -120,6 +161,12 @@ impl NewBoard {
             min_write_role: Role::Member,
             sort_order: 0,
             disable_paging: false,
+            allow_color: true,
+            is_moderated: false,
+            group_id: None,
+            max_posts: None,
+            max_age_days: None,
+            archive_pruned: false,
         }
     }
 
@@ -158,6 +205,37 @@ impl NewBoard {
         self.disable_paging = disable_paging;
         self
     }
+
+    /// Set whether color codes in posts are displayed.
+    pub fn with_allow_color(mut self, allow_color: bool) -> Self {
+        self.allow_color = allow_color;
+        self
+    }
+
+    /// Set whether posts by members wait for approval.
+    pub fn with_moderated(mut self, is_moderated: bool) -> Self {
+        self.is_moderated = is_moderated;
+        self
+    }
+
+    /// Set the board group.
+    pub fn with_group(mut self, group_id: i64) -> Self {
+        self.group_id = Some(group_id);
+        self
+    }
+
+    /// Set the retention rules.
+    pub fn with_retention(
+        mut self,
+        max_posts: Option<i64>,
+        max_age_days: Option<i64>,
+        archive_pruned: bool,
+    ) -> Self {
+        self.max_posts = max_posts;
+        self.max_age_days = max_age_days;
+        self.archive_pruned = archive_pruned;
+        self
+    }
 }
 
 /// Data for updating an existing board.
@@ -179,6 +257,18 @@ pub struct BoardUpdate {
     pub is_active: Option<bool>,
     /// New disable_paging status.
     pub disable_paging: Option<bool>,
+    /// New allow_color status.
+    pub allow_color: Option<bool>,
+    /// New is_moderated status.
+    pub is_moderated: Option<bool>,
+    /// New board group (Some(None) removes the board from its group).
+    pub group_id: Option<Option<i64>>,
+    /// New maximum number of posts (Some(None) removes the limit).
+    pub max_posts: Option<Option<i64>>,
+    /// New maximum age in days (Some(None) removes the limit).
+    pub max_age_days: Option<Option<i64>>,
+    /// New archive_pruned status.
+    pub archive_pruned: Option<bool>,
 }
 
 impl BoardUpdate {
@@ -235,6 +325,42 @@ impl BoardUpdate {
         self
     }
 
+    /// Set allow_color status.
+    pub fn allow_color(mut self, allow_color: bool) -> Self {
+        self.allow_color = Some(allow_color);
+        self
+    }
+
+    /// Set is_moderated status.
+    pub fn is_moderated(mut self, is_moderated: bool) -> Self {
+        self.is_moderated = Some(is_moderated);
+        self
+    }
+
+    /// Set new board group.
+    pub fn group_id(mut self, group_id: Option<i64>) -> Self {
+        self.group_id = Some(group_id);
+        self
+    }
+
+    /// Set new maximum number of posts.
+    pub fn max_posts(mut self, max_posts: Option<i64>) -> Self {
+        self.max_posts = Some(max_posts);
+        self
+    }
+
+    /// Set new maximum age in days.
+    pub fn max_age_days(mut self, max_age_days: Option<i64>) -> Self {
+        self.max_age_days = Some(max_age_days);
+        self
+    }
+
+    /// Set archive_pruned status.
+    pub fn archive_pruned(mut self, archive_pruned: bool) -> Self {
+        self.archive_pruned = Some(archive_pruned);
+        self
+    }
+
     /// Check if any fields are set.
     pub fn is_empty(&self) -> bool {
         self.name.is_none()
@@ -245,6 +371,12 @@ impl BoardUpdate {
             && self.sort_order.is_none()
             && self.is_active.is_none()
             && self.disable_paging.is_none()
+            && self.allow_color.is_none()
+            && self.is_moderated.is_none()
+            && self.group_id.is_none()
+            && self.max_posts.is_none()
+            && self.max_age_days.is_none()
+            && self.archive_pruned.is_none()
     }
 }
 
@@ -295,6 +427,13 @@ mod tests {
             sort_order: 0,
             is_active: true,
             disable_paging: false,
+            allow_color: true,
+            is_moderated: false,
+            group_id: None,
+            group_read_role: None,
+            max_posts: None,
+            max_age_days: None,
+            archive_pruned: false,
             created_at: "2024-01-01".to_string(),
         };
 
@@ -304,6 +443,32 @@ mod tests {
         assert!(board.can_read(Role::SysOp));
     }
 
+    #[test]
+    fn test_board_can_read_requires_group_role() {
+        let board = Board {
+            id: 1,
+            name: "test".to_string(),
+            description: None,
+            board_type: BoardType::Thread,
+            min_read_role: Role::Guest,
+            min_write_role: Role::Member,
+            sort_order: 0,
+            is_active: true,
+            disable_paging: false,
+            allow_color: true,
+            is_moderated: false,
+            group_id: Some(1),
+            group_read_role: Some(Role::SubOp),
+            max_posts: None,
+            max_age_days: None,
+            archive_pruned: false,
+            created_at: "2024-01-01".to_string(),
+        };
+
+        assert!(!board.can_read(Role::Member));
+        assert!(board.can_read(Role::SubOp));
+    }
+
     #[test]
     fn test_board_can_write() {
         let board = Board {
@@ -316,6 +481,13 @@ mod tests {
             sort_order: 0,
             is_active: true,
             disable_paging: false,
+            allow_color: true,
+            is_moderated: false,
+            group_id: None,
+            group_read_role: None,
+            max_posts: None,
+            max_age_days: None,
+            archive_pruned: false,
             created_at: "2024-01-01".to_string(),
         };
 
@@ -325,6 +497,35 @@ mod tests {
         assert!(board.can_write(Role::SysOp));
     }
 
+    #[test]
+    fn test_board_requires_approval() {
+        let mut board = Board {
+            id: 1,
+            name: "test".to_string(),
+            description: None,
+            board_type: BoardType::Thread,
+            min_read_role: Role::Guest,
+            min_write_role: Role::Member,
+            sort_order: 0,
+            is_active: true,
+            disable_paging: false,
+            allow_color: true,
+            is_moderated: false,
+            group_id: None,
+            group_read_role: None,
+            max_posts: None,
+            max_age_days: None,
+            archive_pruned: false,
+            created_at: "2024-01-01".to_string(),
+        };
+        assert!(!board.requires_approval(Role::Member));
+
+        board.is_moderated = true;
+        assert!(board.requires_approval(Role::Member));
+        assert!(!board.requires_approval(Role::SubOp));
+        assert!(!board.requires_approval(Role::SysOp));
+    }
+
     #[test]
     fn test_new_board_builder() {
         let board = NewBoard::new("Test Board")
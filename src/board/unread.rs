@@ -3,7 +3,10 @@
 //! This module provides functionality to track and manage unread posts
 //! for each user per board.
 
+use std::collections::{HashMap, HashSet};
+
 use crate::db::{DbPool, Role, SQL_TRUE};
+use crate::twit::KillFile;
 use crate::HobbsError;
 use crate::Result;
 
@@ -12,7 +15,7 @@ const SQL_NOW: &str = "datetime('now')";
 #[cfg(feature = "postgres")]
 const SQL_NOW: &str = "TO_CHAR(NOW(), 'YYYY-MM-DD HH24:MI:SS')";
 
-use super::Post;
+use super::{BoardRepository, Post};
 
 /// Unread post with board information for cross-board reading.
 #[derive(Debug, Clone)]
@@ -23,6 +26,35 @@ pub struct UnreadPostWithBoard {
     pub board_name: String,
 }
 
+/// Board ID, group ID and unread count.
+type UnreadCountRow = (i64, Option<i64>, i64);
+
+/// Unread post counts per board, rolled up per board group.
+#[derive(Debug, Clone, Default)]
+pub struct UnreadCounts {
+    /// Unread posts per board ID.
+    pub boards: HashMap<i64, i64>,
+    /// Unread posts per group ID, summed over the group's readable boards.
+    pub groups: HashMap<i64, i64>,
+}
+
+impl UnreadCounts {
+    /// Get the unread count of a board (0 if unknown).
+    pub fn board(&self, board_id: i64) -> i64 {
+        self.boards.get(&board_id).copied().unwrap_or(0)
+    }
+
+    /// Get the unread count of a board group (0 if unknown).
+    pub fn group(&self, group_id: i64) -> i64 {
+        self.groups.get(&group_id).copied().unwrap_or(0)
+    }
+
+    /// Get the total unread count over all boards.
+    pub fn total(&self) -> i64 {
+        self.boards.values().sum()
+    }
+}
+
 /// Read position tracking for a user on a board.
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct ReadPosition {
@@ -41,12 +73,31 @@ pub struct ReadPosition {
 /// Repository for unread management operations.
 pub struct UnreadRepository<'a> {
     pool: &'a DbPool,
+    kill_file: Option<&'a KillFile>,
 }
 
 impl<'a> UnreadRepository<'a> {
     /// Create a new UnreadRepository with the given pool reference.
     pub fn new(pool: &'a DbPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            kill_file: None,
+        }
+    }
+
+    /// Leave posts hidden by a user's twit filters out of unread posts and
+    /// counts.
+    pub fn with_kill_file(mut self, kill_file: &'a KillFile) -> Self {
+        self.kill_file = Some(kill_file);
+        self
+    }
+
+    /// Build the condition leaving out hidden posts, see
+    /// [`KillFile::visible_posts_sql`].
+    fn visible_posts_sql(&self, posts: &str, first_param: usize) -> (String, Vec<String>) {
+        self.kill_file
+            .map(|k| k.visible_posts_sql(posts, first_param))
+            .unwrap_or_default()
     }
 
     /// Get the read position for a user on a board.
@@ -94,54 +145,93 @@ impl<'a> UnreadRepository<'a> {
         Ok(())
     }
 
+    /// Set the read position for a user on a board.
+    ///
+    /// Unlike `mark_as_read`, this also moves the position backwards.
+    pub async fn set_read_position(&self, user_id: i64, board_id: i64, post_id: i64) -> Result<()> {
+        let query = format!(
+            "INSERT INTO read_positions (user_id, board_id, last_read_post_id, last_read_at)
+             VALUES ($1, $2, $3, {})
+             ON CONFLICT(user_id, board_id) DO UPDATE SET
+                 last_read_post_id = excluded.last_read_post_id,
+                 last_read_at = {}",
+            SQL_NOW, SQL_NOW
+        );
+        sqlx::query(&query)
+            .bind(user_id)
+            .bind(board_id)
+            .bind(post_id)
+            .execute(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
     /// Get unread count for a user on a board.
     ///
     /// Returns the number of posts with ID greater than the last read post ID.
     /// If the user has no read position for this board, returns the total post count.
     pub async fn get_unread_count(&self, user_id: i64, board_id: i64) -> Result<i64> {
-        let read_position = self.get_read_position(user_id, board_id).await?;
+        let last_read_id = self.get_last_read_post_id(user_id, board_id).await?;
 
-        let count: i64 = match read_position {
-            Some(pos) => {
-                sqlx::query_scalar("SELECT COUNT(*) FROM posts WHERE board_id = $1 AND id > $2")
-                    .bind(board_id)
-                    .bind(pos.last_read_post_id)
-                    .fetch_one(self.pool)
-                    .await
-                    .map_err(|e| HobbsError::Database(e.to_string()))?
-            }
-            None => sqlx::query_scalar("SELECT COUNT(*) FROM posts WHERE board_id = $1")
-                .bind(board_id)
-                .fetch_one(self.pool)
-                .await
-                .map_err(|e| HobbsError::Database(e.to_string()))?,
-        };
+        let (hidden, binds) = self.visible_posts_sql("posts", 3);
+        let query = format!("SELECT COUNT(*) FROM posts WHERE board_id = $1 AND id > $2{hidden}");
+        let mut query_builder = sqlx::query_scalar(&query).bind(board_id).bind(last_read_id);
+        for bind in &binds {
+            query_builder = query_builder.bind(bind);
+        }
+        let count: i64 = query_builder
+            .fetch_one(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
 
         Ok(count)
     }
 
-    /// Get unread counts for all boards for a user.
+    /// Get unread counts for all boards readable by a user.
     ///
-    /// Returns a list of (board_id, unread_count) tuples.
-    pub async fn get_all_unread_counts(&self, user_id: i64) -> Result<Vec<(i64, i64)>> {
+    /// Counts of boards in a group are also summed into the group's count.
+    pub async fn get_all_unread_counts(
+        &self,
+        user_id: i64,
+        user_role: Role,
+    ) -> Result<UnreadCounts> {
+        let (hidden, binds) = self.visible_posts_sql("p", 2);
         let query = format!(
-            "SELECT b.id,
+            "SELECT b.id, b.group_id,
                     (SELECT COUNT(*) FROM posts p WHERE p.board_id = b.id
                      AND p.id > COALESCE(
                          (SELECT last_read_post_id FROM read_positions
                           WHERE user_id = $1 AND board_id = b.id),
                          0
-                     )) as unread_count
+                     ){}) as unread_count
              FROM boards b
              WHERE b.is_active = {}
              ORDER BY b.sort_order, b.id",
-            SQL_TRUE
+            hidden, SQL_TRUE
         );
-        let counts: Vec<(i64, i64)> = sqlx::query_as(&query)
-        .bind(user_id)
-        .fetch_all(self.pool)
-        .await
-        .map_err(|e| HobbsError::Database(e.to_string()))?;
+        let mut query_builder = sqlx::query_as(&query).bind(user_id);
+        for bind in &binds {
+            query_builder = query_builder.bind(bind);
+        }
+        let rows: Vec<UnreadCountRow> = query_builder
+            .fetch_all(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        let readable = self.readable_board_ids(user_role).await?;
+        let mut counts = UnreadCounts::default();
+        for (board_id, group_id, unread) in rows {
+            if !readable.contains(&board_id) {
+                continue;
+            }
+
+            counts.boards.insert(board_id, unread);
+            if let Some(group_id) = group_id {
+                *counts.groups.entry(group_id).or_insert(0) += unread;
+            }
+        }
 
         Ok(counts)
     }
@@ -151,29 +241,25 @@ impl<'a> UnreadRepository<'a> {
     /// Returns posts with ID greater than the last read post ID.
     /// If the user has no read position, returns all posts.
     pub async fn get_unread_posts(&self, user_id: i64, board_id: i64) -> Result<Vec<Post>> {
-        let read_position = self.get_read_position(user_id, board_id).await?;
+        let last_read_id = self.get_last_read_post_id(user_id, board_id).await?;
 
-        let posts = match read_position {
-            Some(pos) => sqlx::query_as::<_, PostRow>(
-                "SELECT id, board_id, thread_id, author_id, title, body, created_at
-                     FROM posts WHERE board_id = $1 AND id > $2
-                     ORDER BY id ASC",
-            )
-            .bind(board_id)
-            .bind(pos.last_read_post_id)
-            .fetch_all(self.pool)
-            .await
-            .map_err(|e| HobbsError::Database(e.to_string()))?,
-            None => sqlx::query_as::<_, PostRow>(
-                "SELECT id, board_id, thread_id, author_id, title, body, created_at
-                     FROM posts WHERE board_id = $1
-                     ORDER BY id ASC",
-            )
+        let (hidden, binds) = self.visible_posts_sql("posts", 3);
+        let query = format!(
+            "SELECT id, board_id, thread_id, parent_id, author_id, title, body,
+                    created_at, edited_at, edited_by
+             FROM posts WHERE board_id = $1 AND id > $2{hidden}
+             ORDER BY id ASC"
+        );
+        let mut query_builder = sqlx::query_as::<_, PostRow>(&query)
             .bind(board_id)
+            .bind(last_read_id);
+        for bind in &binds {
+            query_builder = query_builder.bind(bind);
+        }
+        let posts = query_builder
             .fetch_all(self.pool)
             .await
-            .map_err(|e| HobbsError::Database(e.to_string()))?,
-        };
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
 
         Ok(posts.into_iter().map(|r| r.into()).collect())
     }
@@ -186,33 +272,27 @@ impl<'a> UnreadRepository<'a> {
         offset: i64,
         limit: i64,
     ) -> Result<Vec<Post>> {
-        let read_position = self.get_read_position(user_id, board_id).await?;
+        let last_read_id = self.get_last_read_post_id(user_id, board_id).await?;
 
-        let posts = match read_position {
-            Some(pos) => sqlx::query_as::<_, PostRow>(
-                "SELECT id, board_id, thread_id, author_id, title, body, created_at
-                     FROM posts WHERE board_id = $1 AND id > $2
-                     ORDER BY id ASC LIMIT $3 OFFSET $4",
-            )
-            .bind(board_id)
-            .bind(pos.last_read_post_id)
-            .bind(limit)
-            .bind(offset)
-            .fetch_all(self.pool)
-            .await
-            .map_err(|e| HobbsError::Database(e.to_string()))?,
-            None => sqlx::query_as::<_, PostRow>(
-                "SELECT id, board_id, thread_id, author_id, title, body, created_at
-                     FROM posts WHERE board_id = $1
-                     ORDER BY id ASC LIMIT $2 OFFSET $3",
-            )
+        let (hidden, binds) = self.visible_posts_sql("posts", 5);
+        let query = format!(
+            "SELECT id, board_id, thread_id, parent_id, author_id, title, body,
+                    created_at, edited_at, edited_by
+             FROM posts WHERE board_id = $1 AND id > $2{hidden}
+             ORDER BY id ASC LIMIT $3 OFFSET $4"
+        );
+        let mut query_builder = sqlx::query_as::<_, PostRow>(&query)
             .bind(board_id)
+            .bind(last_read_id)
             .bind(limit)
-            .bind(offset)
+            .bind(offset);
+        for bind in &binds {
+            query_builder = query_builder.bind(bind);
+        }
+        let posts = query_builder
             .fetch_all(self.pool)
             .await
-            .map_err(|e| HobbsError::Database(e.to_string()))?,
-        };
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
 
         Ok(posts.into_iter().map(|r| r.into()).collect())
     }
@@ -284,11 +364,13 @@ impl<'a> UnreadRepository<'a> {
         let placeholders: Vec<String> = (1..=thread_ids.len())
             .map(|i| format!("${}", i))
             .collect();
+        let (hidden, binds) = self.visible_posts_sql("posts", thread_ids.len() + 2);
         let query = format!(
             "SELECT DISTINCT thread_id FROM posts
-             WHERE thread_id IN ({}) AND id > ${}",
+             WHERE thread_id IN ({}) AND id > ${}{}",
             placeholders.join(","),
-            thread_ids.len() + 1
+            thread_ids.len() + 1,
+            hidden
         );
 
         let mut query_builder = sqlx::query_scalar::<_, i64>(&query);
@@ -296,6 +378,9 @@ impl<'a> UnreadRepository<'a> {
             query_builder = query_builder.bind(*thread_id);
         }
         query_builder = query_builder.bind(last_read_id);
+        for bind in &binds {
+            query_builder = query_builder.bind(bind);
+        }
 
         let unread_ids: Vec<i64> = query_builder
             .fetch_all(self.pool)
@@ -325,7 +410,7 @@ impl<'a> UnreadRepository<'a> {
     ) -> Result<Vec<UnreadPostWithBoard>> {
         // Get all active boards with their read positions
         let query = format!(
-            "SELECT b.id, b.name, b.min_read_role,
+            "SELECT b.id, b.name,
                     COALESCE(
                         (SELECT last_read_post_id FROM read_positions
                          WHERE user_id = $1 AND board_id = b.id),
@@ -336,32 +421,38 @@ impl<'a> UnreadRepository<'a> {
              ORDER BY b.sort_order, b.id",
             SQL_TRUE
         );
-        let boards: Vec<(i64, String, String, i64)> = sqlx::query_as(&query)
+        let boards: Vec<(i64, String, i64)> = sqlx::query_as(&query)
         .bind(user_id)
         .fetch_all(self.pool)
         .await
         .map_err(|e| HobbsError::Database(e.to_string()))?;
 
+        let (hidden, binds) = self.visible_posts_sql("posts", 3);
+        let posts_query = format!(
+            "SELECT id, board_id, thread_id, parent_id, author_id, title, body,
+                    created_at, edited_at, edited_by
+             FROM posts WHERE board_id = $1 AND id > $2{hidden}
+             ORDER BY id ASC"
+        );
+        let readable = self.readable_board_ids(user_role).await?;
         let mut all_unread = Vec::new();
 
         // Get unread posts for each accessible board
-        for (board_id, board_name, min_read_role_str, last_read_post_id) in boards {
-            // Check if user can access this board
-            let min_read_role: Role = min_read_role_str.parse().unwrap_or(Role::Guest);
-            if !user_role.can_access(min_read_role) {
+        for (board_id, board_name, last_read_post_id) in boards {
+            if !readable.contains(&board_id) {
                 continue;
             }
 
-            let posts: Vec<PostRow> = sqlx::query_as(
-                "SELECT id, board_id, thread_id, author_id, title, body, created_at
-                 FROM posts WHERE board_id = $1 AND id > $2
-                 ORDER BY id ASC",
-            )
-            .bind(board_id)
-            .bind(last_read_post_id)
-            .fetch_all(self.pool)
-            .await
-            .map_err(|e| HobbsError::Database(e.to_string()))?;
+            let mut query_builder = sqlx::query_as(&posts_query)
+                .bind(board_id)
+                .bind(last_read_post_id);
+            for bind in &binds {
+                query_builder = query_builder.bind(bind);
+            }
+            let posts: Vec<PostRow> = query_builder
+                .fetch_all(self.pool)
+                .await
+                .map_err(|e| HobbsError::Database(e.to_string()))?;
 
             for post_row in posts {
                 all_unread.push(UnreadPostWithBoard {
@@ -378,7 +469,7 @@ impl<'a> UnreadRepository<'a> {
     pub async fn get_total_unread_count(&self, user_id: i64, user_role: Role) -> Result<i64> {
         // Get all active boards with their read positions
         let query = format!(
-            "SELECT b.id, b.min_read_role,
+            "SELECT b.id,
                     COALESCE(
                         (SELECT last_read_post_id FROM read_positions
                          WHERE user_id = $1 AND board_id = b.id),
@@ -388,34 +479,48 @@ impl<'a> UnreadRepository<'a> {
              WHERE b.is_active = {}",
             SQL_TRUE
         );
-        let boards: Vec<(i64, String, i64)> = sqlx::query_as(&query)
+        let boards: Vec<(i64, i64)> = sqlx::query_as(&query)
         .bind(user_id)
         .fetch_all(self.pool)
         .await
         .map_err(|e| HobbsError::Database(e.to_string()))?;
 
+        let (hidden, binds) = self.visible_posts_sql("posts", 3);
+        let count_query =
+            format!("SELECT COUNT(*) FROM posts WHERE board_id = $1 AND id > $2{hidden}");
         let mut total_count: i64 = 0;
 
-        for (board_id, min_read_role_str, last_read_post_id) in boards {
-            // Check if user can access this board
-            let min_read_role: Role = min_read_role_str.parse().unwrap_or(Role::Guest);
-            if !user_role.can_access(min_read_role) {
+        let readable = self.readable_board_ids(user_role).await?;
+        for (board_id, last_read_post_id) in boards {
+            if !readable.contains(&board_id) {
                 continue;
             }
 
-            let count: i64 =
-                sqlx::query_scalar("SELECT COUNT(*) FROM posts WHERE board_id = $1 AND id > $2")
-                    .bind(board_id)
-                    .bind(last_read_post_id)
-                    .fetch_one(self.pool)
-                    .await
-                    .map_err(|e| HobbsError::Database(e.to_string()))?;
+            let mut query_builder = sqlx::query_scalar(&count_query)
+                .bind(board_id)
+                .bind(last_read_post_id);
+            for bind in &binds {
+                query_builder = query_builder.bind(bind);
+            }
+            let count: i64 = query_builder
+                .fetch_one(self.pool)
+                .await
+                .map_err(|e| HobbsError::Database(e.to_string()))?;
 
             total_count += count;
         }
 
         Ok(total_count)
     }
+
+    /// IDs of the boards a user can read, decided the same way as the
+    /// board listing.
+    async fn readable_board_ids(&self, user_role: Role) -> Result<HashSet<i64>> {
+        let boards = BoardRepository::new(self.pool)
+            .list_accessible(user_role)
+            .await?;
+        Ok(boards.into_iter().map(|board| board.id).collect())
+    }
 }
 
 /// Internal struct for mapping post rows from sqlx.
@@ -424,10 +529,13 @@ struct PostRow {
     id: i64,
     board_id: i64,
     thread_id: Option<i64>,
+    parent_id: Option<i64>,
     author_id: i64,
     title: Option<String>,
     body: String,
     created_at: String,
+    edited_at: Option<String>,
+    edited_by: Option<i64>,
 }
 
 impl From<PostRow> for Post {
@@ -436,10 +544,13 @@ impl From<PostRow> for Post {
             id: row.id,
             board_id: row.board_id,
             thread_id: row.thread_id,
+            parent_id: row.parent_id,
             author_id: row.author_id,
             title: row.title,
             body: row.body,
             created_at: row.created_at,
+            edited_at: row.edited_at,
+            edited_by: row.edited_by,
         }
     }
 }
@@ -713,14 +824,53 @@ mod tests {
         create_test_post(pool, board2_id, user_id).await;
 
         let repo = UnreadRepository::new(pool);
-        let counts = repo.get_all_unread_counts(user_id).await.unwrap();
-
-        assert_eq!(counts.len(), 2);
-        // Find the counts for each board
-        let board1_count = counts.iter().find(|(id, _)| *id == board1_id).unwrap().1;
-        let board2_count = counts.iter().find(|(id, _)| *id == board2_id).unwrap().1;
-        assert_eq!(board1_count, 2);
-        assert_eq!(board2_count, 3);
+        let counts = repo.get_all_unread_counts(user_id, Role::Member).await.unwrap();
+
+        assert_eq!(counts.boards.len(), 2);
+        assert_eq!(counts.board(board1_id), 2);
+        assert_eq!(counts.board(board2_id), 3);
+        assert_eq!(counts.total(), 5);
+        assert!(counts.groups.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_all_unread_counts_rolls_up_groups() {
+        use crate::board::{BoardGroupRepository, BoardRepository, BoardUpdate, NewBoardGroup};
+
+        let db = setup_db().await;
+        let pool = db.pool();
+        let user_id = create_test_user(pool).await;
+        let groups = BoardGroupRepository::new(pool);
+        let hobby = groups.create(&NewBoardGroup::new("Hobby")).await.unwrap();
+        let staff = groups
+            .create(&NewBoardGroup::new("Staff").with_min_read_role(Role::SubOp))
+            .await
+            .unwrap();
+
+        let board1_id = create_test_board_with_name(pool, "board1").await;
+        let board2_id = create_test_board_with_name(pool, "board2").await;
+        let board3_id = create_test_board_with_name(pool, "board3").await;
+        let boards = BoardRepository::new(pool);
+        let members = [(board1_id, hobby.id), (board2_id, hobby.id), (board3_id, staff.id)];
+        for (board_id, group_id) in members {
+            boards
+                .update(board_id, &BoardUpdate::new().group_id(Some(group_id)))
+                .await
+                .unwrap();
+            create_test_post(pool, board_id, user_id).await;
+        }
+        create_test_post(pool, board2_id, user_id).await;
+
+        let repo = UnreadRepository::new(pool);
+        let counts = repo.get_all_unread_counts(user_id, Role::Member).await.unwrap();
+        assert_eq!(counts.group(hobby.id), 3);
+        // The staff group is not readable by members
+        assert_eq!(counts.group(staff.id), 0);
+        assert_eq!(counts.board(board3_id), 0);
+
+        let counts = repo.get_all_unread_counts(user_id, Role::SubOp).await.unwrap();
+        assert_eq!(counts.group(staff.id), 1);
+        assert_eq!(counts.total(), 4);
     }
 
     #[tokio::test]
@@ -1230,4 +1380,120 @@ mod tests {
         // 1 unread in board1 (post2) + 1 unread in board2 (post3)
         assert_eq!(count, 2);
     }
+
+    #[tokio::test]
+    async fn test_kill_file_hides_unread_posts() {
+        use crate::db::{NewUser, UserRepository};
+        use crate::twit::TwitFilterService;
+
+        let db = setup_db().await;
+        let pool = db.pool();
+        let user_id = create_test_user(pool).await;
+        let troll_id = UserRepository::new(pool)
+            .create(&NewUser::new("troll", "hash", "Troll"))
+            .await
+            .unwrap()
+            .id;
+        let board_id = create_test_board(pool).await;
+
+        create_test_post(pool, board_id, user_id).await;
+        create_test_post(pool, board_id, troll_id).await;
+        let thread_id = create_test_thread(pool, board_id, user_id).await;
+        create_test_thread_post(pool, board_id, thread_id, user_id).await;
+
+        let twit = TwitFilterService::new(pool);
+        twit.add_user(user_id, "troll").await.unwrap();
+        twit.add_keyword(user_id, "test thread").await.unwrap();
+        let kill_file = twit.kill_file(user_id).await.unwrap();
+
+        let repo = UnreadRepository::new(pool);
+        assert_eq!(repo.get_unread_count(user_id, board_id).await.unwrap(), 3);
+
+        // The troll's post and the post in the hidden thread are left out.
+        let repo = UnreadRepository::new(pool).with_kill_file(&kill_file);
+        assert_eq!(repo.get_unread_count(user_id, board_id).await.unwrap(), 1);
+        let posts = repo.get_unread_posts(user_id, board_id).await.unwrap();
+        assert_eq!(posts.len(), 1);
+        let counts = repo
+            .get_all_unread_counts(user_id, Role::Member)
+            .await
+            .unwrap();
+        assert_eq!(counts.board(board_id), 1);
+        assert_eq!(
+            repo.get_total_unread_count(user_id, Role::Member)
+                .await
+                .unwrap(),
+            1
+        );
+        assert!(repo
+            .get_unread_thread_ids(user_id, board_id, &[thread_id])
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_kill_file_keyword_case_matches_lists() {
+        use crate::twit::TwitFilterService;
+
+        let db = setup_db().await;
+        let pool = db.pool();
+        let user_id = create_test_user(pool).await;
+        let board_id = create_test_board(pool).await;
+        for body in [
+            "Ｎｅｗｓ flash",
+            "ｎｅｗｓ flash",
+            "news FLASH",
+            "ПРИВЕТ",
+            "привет",
+        ] {
+            sqlx::query("INSERT INTO posts (board_id, author_id, title, body) VALUES (?, ?, ?, ?)")
+                .bind(board_id)
+                .bind(user_id)
+                .bind("Title")
+                .bind(body)
+                .execute(pool)
+                .await
+                .unwrap();
+        }
+
+        let twit = TwitFilterService::new(pool);
+        twit.add_keyword(user_id, "Ｎｅｗｓ").await.unwrap();
+        twit.add_keyword(user_id, "Привет").await.unwrap();
+        let kill_file = twit.kill_file(user_id).await.unwrap();
+
+        // What the list shows is what the unread count counts.
+        let all = UnreadRepository::new(pool)
+            .get_unread_posts(user_id, board_id)
+            .await
+            .unwrap();
+        let listed: Vec<_> = all
+            .iter()
+            .filter(|p| !kill_file.hides_post(p))
+            .map(|p| p.body.as_str())
+            .collect();
+        assert_eq!(
+            listed,
+            vec!["ｎｅｗｓ flash", "news FLASH", "ПРИВЕТ", "привет"]
+        );
+
+        let repo = UnreadRepository::new(pool).with_kill_file(&kill_file);
+        assert_eq!(
+            repo.get_unread_count(user_id, board_id).await.unwrap(),
+            listed.len() as i64
+        );
+        let counts = repo
+            .get_all_unread_counts(user_id, Role::Member)
+            .await
+            .unwrap();
+        assert_eq!(counts.board(board_id), listed.len() as i64);
+        let unread: Vec<_> = repo
+            .get_unread_posts(user_id, board_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|p| p.body)
+            .collect();
+        assert_eq!(unread, listed);
+    }
 }
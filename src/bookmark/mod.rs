@@ -0,0 +1,14 @@
+//! Thread and post bookmarks for HOBBS.
+//!
+//! Users bookmark threads and individual posts they want to keep at hand,
+//! each with an optional note. A bookmark outlives its target: once the
+//! thread or post is deleted (or its board can no longer be read) the
+//! bookmark is shown as deleted until the user removes it.
+
+pub mod repository;
+pub mod service;
+pub mod types;
+
+pub use repository::BookmarkRepository;
+pub use service::{BookmarkService, MAX_NOTE_LENGTH};
+pub use types::{Bookmark, BookmarkTarget};
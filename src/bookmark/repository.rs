@@ -0,0 +1,135 @@
+//! Bookmark repository for HOBBS.
+
+use super::types::{Bookmark, BookmarkTarget};
+use crate::db::DbPool;
+use crate::{HobbsError, Result};
+
+const BOOKMARK_SELECT: &str = "SELECT bm.id, bm.user_id, bm.thread_id, bm.post_id,
+            COALESCE(t.title, p.title, pt.title, bm.title) AS title, bm.note,
+            b.id AS board_id, b.name AS board_name, p.thread_id AS post_thread_id,
+            bm.created_at
+     FROM bookmarks bm
+     LEFT JOIN threads t ON t.id = bm.thread_id
+     LEFT JOIN posts p ON p.id = bm.post_id
+     LEFT JOIN threads pt ON pt.id = p.thread_id
+     LEFT JOIN boards b ON b.id = COALESCE(t.board_id, p.board_id)";
+
+/// Repository for bookmarks.
+pub struct BookmarkRepository<'a> {
+    pool: &'a DbPool,
+}
+
+impl<'a> BookmarkRepository<'a> {
+    /// Create a new BookmarkRepository with the given database pool.
+    pub fn new(pool: &'a DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Bookmark a target, or change the note of an existing bookmark.
+    ///
+    /// `title` is kept to show the bookmark after the target is deleted.
+    pub async fn create(
+        &self,
+        user_id: i64,
+        target: BookmarkTarget,
+        title: &str,
+        note: Option<&str>,
+    ) -> Result<Bookmark> {
+        if let Some(existing) = self.get_by_target(user_id, target).await? {
+            self.set_note(existing.id, note).await?;
+            return self.require(existing.id).await;
+        }
+
+        let id: i64 = sqlx::query_scalar(
+            "INSERT INTO bookmarks (user_id, thread_id, post_id, title, note)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING id",
+        )
+        .bind(user_id)
+        .bind(target.thread_id())
+        .bind(target.post_id())
+        .bind(title)
+        .bind(note)
+        .fetch_one(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        self.require(id).await
+    }
+
+    async fn require(&self, id: i64) -> Result<Bookmark> {
+        self.get_by_id(id)
+            .await?
+            .ok_or_else(|| HobbsError::NotFound("bookmark".to_string()))
+    }
+
+    /// Get a bookmark by ID.
+    pub async fn get_by_id(&self, id: i64) -> Result<Option<Bookmark>> {
+        let query = format!("{BOOKMARK_SELECT} WHERE bm.id = $1");
+        sqlx::query_as::<_, Bookmark>(&query)
+            .bind(id)
+            .fetch_optional(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// Get a user's bookmark to a target.
+    pub async fn get_by_target(
+        &self,
+        user_id: i64,
+        target: BookmarkTarget,
+    ) -> Result<Option<Bookmark>> {
+        let query = match target {
+            BookmarkTarget::Thread(_) => {
+                format!("{BOOKMARK_SELECT} WHERE bm.user_id = $1 AND bm.thread_id = $2")
+            }
+            BookmarkTarget::Post(_) => {
+                format!("{BOOKMARK_SELECT} WHERE bm.user_id = $1 AND bm.post_id = $2")
+            }
+        };
+        let target_id = target.thread_id().or(target.post_id());
+        sqlx::query_as::<_, Bookmark>(&query)
+            .bind(user_id)
+            .bind(target_id)
+            .fetch_optional(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// List a user's bookmarks, oldest first.
+    pub async fn list_by_user(&self, user_id: i64) -> Result<Vec<Bookmark>> {
+        let query = format!("{BOOKMARK_SELECT} WHERE bm.user_id = $1 ORDER BY bm.id");
+        sqlx::query_as::<_, Bookmark>(&query)
+            .bind(user_id)
+            .fetch_all(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// Change the note of a bookmark.
+    ///
+    /// Returns false if the bookmark does not exist.
+    pub async fn set_note(&self, id: i64, note: Option<&str>) -> Result<bool> {
+        let result = sqlx::query("UPDATE bookmarks SET note = $1 WHERE id = $2")
+            .bind(note)
+            .bind(id)
+            .execute(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Delete a bookmark.
+    ///
+    /// Returns false if the bookmark does not exist.
+    pub async fn delete(&self, id: i64) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM bookmarks WHERE id = $1")
+            .bind(id)
+            .execute(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
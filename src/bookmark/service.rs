@@ -0,0 +1,356 @@
+//! Bookmark service for HOBBS.
+
+use std::collections::HashMap;
+
+use super::repository::BookmarkRepository;
+use super::types::{Bookmark, BookmarkTarget};
+use crate::board::BoardService;
+use crate::db::{Database, Role};
+use crate::{HobbsError, Result};
+
+/// Maximum length of a bookmark note in characters.
+pub const MAX_NOTE_LENGTH: usize = 200;
+
+/// Service for bookmarks.
+pub struct BookmarkService<'a> {
+    db: &'a Database,
+}
+
+impl<'a> BookmarkService<'a> {
+    /// Create a new BookmarkService with the given database reference.
+    pub fn new(db: &'a Database) -> Self {
+        Self { db }
+    }
+
+    /// Bookmark a thread or post the user can read.
+    ///
+    /// Bookmarking the same target again only changes the note. An empty
+    /// note is stored as no note.
+    pub async fn add(
+        &self,
+        user_id: i64,
+        user_role: Role,
+        target: BookmarkTarget,
+        note: Option<&str>,
+    ) -> Result<Bookmark> {
+        let note = validate_note(note)?;
+
+        let boards = BoardService::new(self.db);
+        let title = match target {
+            BookmarkTarget::Thread(thread_id) => {
+                boards.get_thread(thread_id, user_role).await?.title
+            }
+            BookmarkTarget::Post(post_id) => {
+                let post = boards.get_post(post_id, user_role).await?;
+                match (post.title, post.thread_id) {
+                    (Some(title), _) => title,
+                    (None, Some(thread_id)) => boards.get_thread(thread_id, user_role).await?.title,
+                    (None, None) => String::new(),
+                }
+            }
+        };
+
+        let bookmark = BookmarkRepository::new(self.db.pool())
+            .create(user_id, target, &title, note)
+            .await?;
+        self.resolve(vec![bookmark], user_role)
+            .await
+            .map(|mut b| b.remove(0))
+    }
+
+    /// Get one of the user's bookmarks.
+    pub async fn get(&self, user_id: i64, user_role: Role, id: i64) -> Result<Bookmark> {
+        let bookmark = self.get_own(user_id, id).await?;
+        self.resolve(vec![bookmark], user_role)
+            .await
+            .map(|mut b| b.remove(0))
+    }
+
+    /// Find the user's bookmark to a target.
+    pub async fn find(&self, user_id: i64, target: BookmarkTarget) -> Result<Option<Bookmark>> {
+        BookmarkRepository::new(self.db.pool())
+            .get_by_target(user_id, target)
+            .await
+    }
+
+    /// List the user's bookmarks, oldest first.
+    ///
+    /// Bookmarks whose target is deleted or no longer readable are listed
+    /// as deleted.
+    pub async fn list(&self, user_id: i64, user_role: Role) -> Result<Vec<Bookmark>> {
+        let bookmarks = BookmarkRepository::new(self.db.pool())
+            .list_by_user(user_id)
+            .await?;
+        self.resolve(bookmarks, user_role).await
+    }
+
+    /// Change the note of one of the user's bookmarks.
+    pub async fn set_note(
+        &self,
+        user_id: i64,
+        user_role: Role,
+        id: i64,
+        note: Option<&str>,
+    ) -> Result<Bookmark> {
+        let note = validate_note(note)?;
+        self.get_own(user_id, id).await?;
+        BookmarkRepository::new(self.db.pool())
+            .set_note(id, note)
+            .await?;
+        self.get(user_id, user_role, id).await
+    }
+
+    /// Delete one of the user's bookmarks.
+    pub async fn remove(&self, user_id: i64, id: i64) -> Result<()> {
+        self.get_own(user_id, id).await?;
+        BookmarkRepository::new(self.db.pool()).delete(id).await?;
+        Ok(())
+    }
+
+    async fn get_own(&self, user_id: i64, id: i64) -> Result<Bookmark> {
+        BookmarkRepository::new(self.db.pool())
+            .get_by_id(id)
+            .await?
+            .filter(|b| b.user_id == user_id)
+            .ok_or_else(|| HobbsError::NotFound("bookmark".to_string()))
+    }
+
+    /// Mark bookmarks on boards the user cannot read as deleted.
+    async fn resolve(
+        &self,
+        mut bookmarks: Vec<Bookmark>,
+        user_role: Role,
+    ) -> Result<Vec<Bookmark>> {
+        let boards = BoardService::new(self.db);
+        let mut readable: HashMap<i64, bool> = HashMap::new();
+        for bookmark in &mut bookmarks {
+            let Some(board_id) = bookmark.board_id else {
+                continue;
+            };
+            let can_read = match readable.get(&board_id) {
+                Some(&can_read) => can_read,
+                None => {
+                    let can_read = match boards.get_board(board_id, user_role).await {
+                        Ok(_) => true,
+                        Err(HobbsError::NotFound(_) | HobbsError::Permission(_)) => false,
+                        Err(e) => return Err(e),
+                    };
+                    readable.insert(board_id, can_read);
+                    can_read
+                }
+            };
+            if !can_read {
+                bookmark.board_id = None;
+                bookmark.board_name = None;
+                bookmark.post_thread_id = None;
+            }
+        }
+        Ok(bookmarks)
+    }
+}
+
+/// Trim a note, turning an empty one into None.
+fn validate_note(note: Option<&str>) -> Result<Option<&str>> {
+    let note = note.map(str::trim).filter(|n| !n.is_empty());
+    if let Some(note) = note {
+        if note.chars().count() > MAX_NOTE_LENGTH {
+            return Err(HobbsError::Validation(format!(
+                "メモは{MAX_NOTE_LENGTH}文字以内にしてください"
+            )));
+        }
+    }
+    Ok(note)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::{
+        Board, BoardRepository, BoardType, NewBoard, NewThread, NewThreadPost, PostRepository,
+        ThreadRepository,
+    };
+    use crate::db::{NewUser, User, UserRepository};
+
+    async fn setup() -> (Database, User, Board) {
+        let db = Database::open_in_memory().await.unwrap();
+        let user = UserRepository::new(db.pool())
+            .create(&NewUser::new("alice", "hash", "alice"))
+            .await
+            .unwrap();
+        let board = BoardRepository::new(db.pool())
+            .create(&NewBoard::new("general"))
+            .await
+            .unwrap();
+        (db, user, board)
+    }
+
+    async fn create_thread(db: &Database, board: &Board, user: &User, title: &str) -> (i64, i64) {
+        let thread = ThreadRepository::new(db.pool())
+            .create(&NewThread::new(board.id, title, user.id))
+            .await
+            .unwrap();
+        let post = PostRepository::new(db.pool())
+            .create_thread_post(&NewThreadPost::new(board.id, thread.id, user.id, "body"))
+            .await
+            .unwrap();
+        (thread.id, post.id)
+    }
+
+    #[tokio::test]
+    async fn test_add_list_and_remove() {
+        let (db, user, board) = setup().await;
+        let (thread_id, post_id) = create_thread(&db, &board, &user, "FAQ").await;
+        let service = BookmarkService::new(&db);
+
+        let thread_mark = service
+            .add(
+                user.id,
+                user.role,
+                BookmarkTarget::Thread(thread_id),
+                Some(" read first "),
+            )
+            .await
+            .unwrap();
+        assert_eq!(thread_mark.target(), BookmarkTarget::Thread(thread_id));
+        assert_eq!(thread_mark.title, "FAQ");
+        assert_eq!(thread_mark.note.as_deref(), Some("read first"));
+        assert_eq!(thread_mark.board_name.as_deref(), Some("general"));
+
+        // A post in a thread is titled after the thread
+        let post_mark = service
+            .add(user.id, user.role, BookmarkTarget::Post(post_id), None)
+            .await
+            .unwrap();
+        assert_eq!(post_mark.title, "FAQ");
+        assert_eq!(post_mark.post_thread_id, Some(thread_id));
+
+        // Bookmarking again only changes the note
+        let again = service
+            .add(
+                user.id,
+                user.role,
+                BookmarkTarget::Thread(thread_id),
+                Some(""),
+            )
+            .await
+            .unwrap();
+        assert_eq!(again.id, thread_mark.id);
+        assert!(again.note.is_none());
+        assert_eq!(service.list(user.id, user.role).await.unwrap().len(), 2);
+
+        service.remove(user.id, thread_mark.id).await.unwrap();
+        let left = service.list(user.id, user.role).await.unwrap();
+        assert_eq!(left.len(), 1);
+        assert_eq!(left[0].id, post_mark.id);
+    }
+
+    #[tokio::test]
+    async fn test_deleted_target_leaves_tombstone() {
+        let (db, user, board) = setup().await;
+        let (thread_id, post_id) = create_thread(&db, &board, &user, "Manual").await;
+        let service = BookmarkService::new(&db);
+        service
+            .add(user.id, user.role, BookmarkTarget::Thread(thread_id), None)
+            .await
+            .unwrap();
+        service
+            .add(user.id, user.role, BookmarkTarget::Post(post_id), None)
+            .await
+            .unwrap();
+
+        ThreadRepository::new(db.pool())
+            .delete(thread_id)
+            .await
+            .unwrap();
+
+        let bookmarks = service.list(user.id, user.role).await.unwrap();
+        assert_eq!(bookmarks.len(), 2);
+        for bookmark in &bookmarks {
+            assert!(bookmark.is_deleted());
+            assert_eq!(bookmark.title, "Manual");
+            assert!(bookmark.board_name.is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unreadable_board_is_shown_as_deleted() {
+        let (db, user, board) = setup().await;
+        let (thread_id, _) = create_thread(&db, &board, &user, "Notes").await;
+        let service = BookmarkService::new(&db);
+        let bookmark = service
+            .add(user.id, user.role, BookmarkTarget::Thread(thread_id), None)
+            .await
+            .unwrap();
+        assert!(!bookmark.is_deleted());
+
+        BoardRepository::new(db.pool())
+            .update(
+                board.id,
+                &crate::board::BoardUpdate::new().min_read_role(Role::SubOp),
+            )
+            .await
+            .unwrap();
+        let bookmark = service.get(user.id, user.role, bookmark.id).await.unwrap();
+        assert!(bookmark.is_deleted());
+    }
+
+    #[tokio::test]
+    async fn test_add_requires_read_permission() {
+        let (db, user, _) = setup().await;
+        let staff = BoardRepository::new(db.pool())
+            .create(
+                &NewBoard::new("staff")
+                    .with_board_type(BoardType::Flat)
+                    .with_min_read_role(Role::SubOp),
+            )
+            .await
+            .unwrap();
+        let post = BoardService::new(&db)
+            .create_flat_post(staff.id, user.id, "secret", "body", Role::SysOp)
+            .await
+            .unwrap();
+
+        let result = BookmarkService::new(&db)
+            .add(user.id, user.role, BookmarkTarget::Post(post.id), None)
+            .await;
+        assert!(matches!(result, Err(HobbsError::Permission(_))));
+    }
+
+    #[tokio::test]
+    async fn test_note_validation_and_ownership() {
+        let (db, user, board) = setup().await;
+        let (thread_id, _) = create_thread(&db, &board, &user, "FAQ").await;
+        let other = UserRepository::new(db.pool())
+            .create(&NewUser::new("bob", "hash", "bob"))
+            .await
+            .unwrap();
+        let service = BookmarkService::new(&db);
+
+        let long = "x".repeat(MAX_NOTE_LENGTH + 1);
+        let result = service
+            .add(
+                user.id,
+                user.role,
+                BookmarkTarget::Thread(thread_id),
+                Some(&long),
+            )
+            .await;
+        assert!(matches!(result, Err(HobbsError::Validation(_))));
+
+        let bookmark = service
+            .add(user.id, user.role, BookmarkTarget::Thread(thread_id), None)
+            .await
+            .unwrap();
+        let updated = service
+            .set_note(user.id, user.role, bookmark.id, Some("chapter 3"))
+            .await
+            .unwrap();
+        assert_eq!(updated.note.as_deref(), Some("chapter 3"));
+
+        assert!(matches!(
+            service.remove(other.id, bookmark.id).await,
+            Err(HobbsError::NotFound(_))
+        ));
+        assert!(service.list(other.id, other.role).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,70 @@
+//! Bookmark types.
+
+/// What a bookmark points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookmarkTarget {
+    /// A whole thread.
+    Thread(i64),
+    /// A single post.
+    Post(i64),
+}
+
+impl BookmarkTarget {
+    /// Get the thread ID, if this targets a thread.
+    pub fn thread_id(&self) -> Option<i64> {
+        match self {
+            BookmarkTarget::Thread(id) => Some(*id),
+            BookmarkTarget::Post(_) => None,
+        }
+    }
+
+    /// Get the post ID, if this targets a post.
+    pub fn post_id(&self) -> Option<i64> {
+        match self {
+            BookmarkTarget::Thread(_) => None,
+            BookmarkTarget::Post(id) => Some(*id),
+        }
+    }
+}
+
+/// A user's bookmark to a thread or post.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Bookmark {
+    /// Unique bookmark ID.
+    pub id: i64,
+    /// ID of the user owning the bookmark.
+    pub user_id: i64,
+    /// Bookmarked thread (None for post bookmarks).
+    pub thread_id: Option<i64>,
+    /// Bookmarked post (None for thread bookmarks).
+    pub post_id: Option<i64>,
+    /// Current title of the target, or its title when bookmarked once the
+    /// target is gone.
+    pub title: String,
+    /// The user's note.
+    pub note: Option<String>,
+    /// Board the target is in (None once the target is deleted or can no
+    /// longer be read).
+    pub board_id: Option<i64>,
+    /// Name of that board.
+    pub board_name: Option<String>,
+    /// Thread a bookmarked post belongs to (None on flat boards).
+    pub post_thread_id: Option<i64>,
+    /// Bookmark timestamp.
+    pub created_at: String,
+}
+
+impl Bookmark {
+    /// Get the bookmarked target.
+    pub fn target(&self) -> BookmarkTarget {
+        match (self.thread_id, self.post_id) {
+            (Some(thread_id), _) => BookmarkTarget::Thread(thread_id),
+            (None, post_id) => BookmarkTarget::Post(post_id.unwrap_or_default()),
+        }
+    }
+
+    /// Check whether the target is gone, leaving only a tombstone.
+    pub fn is_deleted(&self) -> bool {
+        self.board_id.is_none()
+    }
+}
@@ -1,8 +1,10 @@
 //! Configuration module for HOBBS.
 
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::path::Path;
 
+use crate::db::Role;
 use crate::{HobbsError, Result};
 
 /// Server configuration.
@@ -279,16 +281,24 @@ pub struct TemplatesConfig {
     /// Path to the templates directory.
     #[serde(default = "default_templates_path")]
     pub path: String,
+    /// Reload template files when they change on disk.
+    #[serde(default = "default_templates_hot_reload")]
+    pub hot_reload: bool,
 }
 
 fn default_templates_path() -> String {
     "templates".to_string()
 }
 
+fn default_templates_hot_reload() -> bool {
+    true
+}
+
 impl Default for TemplatesConfig {
     fn default() -> Self {
         Self {
             path: default_templates_path(),
+            hot_reload: default_templates_hot_reload(),
         }
     }
 }
@@ -333,6 +343,10 @@ pub struct TerminalConfig {
     /// Lines before auto-pause (0 = auto-calculate from terminal height - 4).
     #[serde(default)]
     pub paging_lines: usize,
+    /// Use lightbar (arrow-key) menus on ANSI terminals.
+    /// Plain and PETSCII profiles always use hotkey menus.
+    #[serde(default = "default_lightbar")]
+    pub lightbar: bool,
     /// Custom terminal profile definitions.
     #[serde(default)]
     pub profiles: Vec<ProfileConfig>,
@@ -388,6 +402,10 @@ fn default_auto_paging() -> bool {
     true
 }
 
+fn default_lightbar() -> bool {
+    true
+}
+
 fn default_profile_width() -> u16 {
     80
 }
@@ -422,6 +440,7 @@ impl Default for TerminalConfig {
             default_profile: default_terminal_profile(),
             auto_paging: default_auto_paging(),
             paging_lines: 0,
+            lightbar: default_lightbar(),
             profiles: Vec::new(),
         }
     }
@@ -563,6 +582,265 @@ impl Default for RssConfig {
     }
 }
 
+/// QWK offline mail configuration.
+#[derive(Debug, Clone, Deserialize)]
+pub struct QwkConfig {
+    /// Packet ID used for the .QWK/.REP file names (1-8 alphanumeric characters).
+    #[serde(default = "default_qwk_bbs_id")]
+    pub bbs_id: String,
+    /// Maximum number of messages in one packet.
+    #[serde(default = "default_qwk_max_messages")]
+    pub max_messages: usize,
+}
+
+fn default_qwk_bbs_id() -> String {
+    "HOBBS".to_string()
+}
+
+fn default_qwk_max_messages() -> usize {
+    500
+}
+
+impl Default for QwkConfig {
+    fn default() -> Self {
+        Self {
+            bbs_id: default_qwk_bbs_id(),
+            max_messages: default_qwk_max_messages(),
+        }
+    }
+}
+
+/// FTN echomail configuration.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FtnConfig {
+    /// Whether echomail processing is enabled.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Our FTN address (e.g. "21:1/100").
+    #[serde(default)]
+    pub address: String,
+    /// Uplink address that outbound packets are addressed to.
+    #[serde(default)]
+    pub uplink: String,
+    /// Packet password (up to 8 characters, empty for none).
+    #[serde(default)]
+    pub password: String,
+    /// Directory the mailer drops inbound .PKT files into.
+    #[serde(default = "default_ftn_inbound")]
+    pub inbound: String,
+    /// Directory outbound .PKT files are written to for the mailer.
+    #[serde(default = "default_ftn_outbound")]
+    pub outbound: String,
+    /// Default character set of echomail ("shiftjis", "utf8" or "cp437").
+    #[serde(default = "default_ftn_charset")]
+    pub charset: String,
+    /// Origin line text (defaults to the BBS name).
+    #[serde(default)]
+    pub origin: String,
+    /// Interval between toss/scan runs in seconds.
+    #[serde(default = "default_ftn_interval")]
+    pub interval_secs: u64,
+    /// Echo tag to board ID mapping.
+    #[serde(default)]
+    pub areas: BTreeMap<String, i64>,
+}
+
+fn default_ftn_inbound() -> String {
+    "data/ftn/inbound".to_string()
+}
+
+fn default_ftn_outbound() -> String {
+    "data/ftn/outbound".to_string()
+}
+
+fn default_ftn_charset() -> String {
+    "shiftjis".to_string()
+}
+
+fn default_ftn_interval() -> u64 {
+    300 // 5 minutes
+}
+
+impl Default for FtnConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            address: String::new(),
+            uplink: String::new(),
+            password: String::new(),
+            inbound: default_ftn_inbound(),
+            outbound: default_ftn_outbound(),
+            charset: default_ftn_charset(),
+            origin: String::new(),
+            interval_secs: default_ftn_interval(),
+            areas: BTreeMap::new(),
+        }
+    }
+}
+
+/// NNTP server configuration.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NntpConfig {
+    /// Whether the NNTP server is enabled.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Host address to bind.
+    #[serde(default = "default_nntp_host")]
+    pub host: String,
+    /// Port number to listen on.
+    #[serde(default = "default_nntp_port")]
+    pub port: u16,
+    /// Domain used in Message-ID, Path and Xref headers.
+    #[serde(default = "default_nntp_domain")]
+    pub domain: String,
+    /// Prefix of newsgroup names (e.g. "hobbs" gives "hobbs.general").
+    #[serde(default = "default_nntp_group_prefix")]
+    pub group_prefix: String,
+    /// Maximum concurrent NNTP connections.
+    #[serde(default = "default_nntp_max_connections")]
+    pub max_connections: usize,
+    /// Idle timeout in seconds.
+    #[serde(default = "default_nntp_idle_timeout")]
+    pub idle_timeout_secs: u64,
+}
+
+fn default_nntp_host() -> String {
+    "0.0.0.0".to_string()
+}
+
+fn default_nntp_port() -> u16 {
+    1119
+}
+
+fn default_nntp_domain() -> String {
+    "hobbs.invalid".to_string()
+}
+
+fn default_nntp_group_prefix() -> String {
+    "hobbs".to_string()
+}
+
+fn default_nntp_max_connections() -> usize {
+    20
+}
+
+fn default_nntp_idle_timeout() -> u64 {
+    600 // 10 minutes
+}
+
+impl Default for NntpConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: default_nntp_host(),
+            port: default_nntp_port(),
+            domain: default_nntp_domain(),
+            group_prefix: default_nntp_group_prefix(),
+            max_connections: default_nntp_max_connections(),
+            idle_timeout_secs: default_nntp_idle_timeout(),
+        }
+    }
+}
+
+/// Subscription notification configuration.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NotificationsConfig {
+    /// Whether the notification job runs.
+    #[serde(default = "default_notifications_enabled")]
+    pub enabled: bool,
+    /// Interval between checks for new posts in seconds.
+    #[serde(default = "default_notifications_interval")]
+    pub interval_secs: u64,
+    /// Local hour (0-23, in server.timezone) the daily digest mail is sent.
+    #[serde(default = "default_notifications_digest_hour")]
+    pub digest_hour: u32,
+}
+
+fn default_notifications_enabled() -> bool {
+    true
+}
+
+fn default_notifications_interval() -> u64 {
+    30
+}
+
+fn default_notifications_digest_hour() -> u32 {
+    7
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_notifications_enabled(),
+            interval_secs: default_notifications_interval(),
+            digest_hour: default_notifications_digest_hour(),
+        }
+    }
+}
+
+/// Board retention configuration.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RetentionConfig {
+    /// Whether the pruning job runs.
+    #[serde(default = "default_retention_enabled")]
+    pub enabled: bool,
+    /// Interval between pruning runs in seconds.
+    #[serde(default = "default_retention_interval")]
+    pub interval_secs: u64,
+    /// Directory for archives of pruned posts.
+    #[serde(default = "default_retention_archive_dir")]
+    pub archive_dir: String,
+}
+
+fn default_retention_enabled() -> bool {
+    true
+}
+
+fn default_retention_interval() -> u64 {
+    3600
+}
+
+fn default_retention_archive_dir() -> String {
+    "data/archives".to_string()
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_retention_enabled(),
+            interval_secs: default_retention_interval(),
+            archive_dir: default_retention_archive_dir(),
+        }
+    }
+}
+
+/// Voting booth configuration.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PollsConfig {
+    /// Minimum role allowed to create polls ("member" or "subop").
+    #[serde(default = "default_polls_create_role")]
+    pub create_role: String,
+}
+
+fn default_polls_create_role() -> String {
+    "member".to_string()
+}
+
+impl PollsConfig {
+    /// Get the minimum role allowed to create polls.
+    pub fn create_role(&self) -> Role {
+        self.create_role.parse().unwrap_or(Role::Member)
+    }
+}
+
+impl Default for PollsConfig {
+    fn default() -> Self {
+        Self {
+            create_role: default_polls_create_role(),
+        }
+    }
+}
+
 /// Web UI configuration.
 #[derive(Debug, Clone, Deserialize)]
 pub struct WebConfig {
@@ -752,6 +1030,24 @@ pub struct Config {
     /// RSS configuration.
     #[serde(default)]
     pub rss: RssConfig,
+    /// QWK offline mail configuration.
+    #[serde(default)]
+    pub qwk: QwkConfig,
+    /// FTN echomail configuration.
+    #[serde(default)]
+    pub ftn: FtnConfig,
+    /// NNTP server configuration.
+    #[serde(default)]
+    pub nntp: NntpConfig,
+    /// Subscription notification configuration.
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+    /// Board retention configuration.
+    #[serde(default)]
+    pub retention: RetentionConfig,
+    /// Voting booth configuration.
+    #[serde(default)]
+    pub polls: PollsConfig,
     /// Web UI configuration.
     #[serde(default)]
     pub web: WebConfig,
@@ -828,6 +1124,53 @@ impl Config {
             }
         }
 
+        let bbs_id = &self.qwk.bbs_id;
+        let valid_id = bbs_id.chars().all(|c| c.is_ascii_alphanumeric());
+        if bbs_id.is_empty() || bbs_id.len() > 8 || !valid_id {
+            return Err(HobbsError::Validation(
+                "qwk.bbs_id must be 1-8 alphanumeric characters.".to_string(),
+            ));
+        }
+
+        if self.ftn.enabled {
+            self.validate_ftn()?;
+        }
+
+        if self.nntp.enabled {
+            let prefix = &self.nntp.group_prefix;
+            let valid = prefix
+                .split('.')
+                .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_alphanumeric()));
+            if !valid {
+                return Err(HobbsError::Validation(
+                    "nntp.group_prefix must be dot-separated alphanumeric words.".to_string(),
+                ));
+            }
+            if self.nntp.max_connections < 1 {
+                return Err(HobbsError::Validation(
+                    "nntp.max_connections must be at least 1.".to_string(),
+                ));
+            }
+        }
+
+        if self.notifications.digest_hour > 23 {
+            return Err(HobbsError::Validation(
+                "notifications.digest_hour must be between 0 and 23.".to_string(),
+            ));
+        }
+
+        if self.retention.enabled && self.retention.interval_secs < 60 {
+            return Err(HobbsError::Validation(
+                "retention.interval_secs must be at least 60.".to_string(),
+            ));
+        }
+
+        if !matches!(self.polls.create_role.parse(), Ok(Role::Member | Role::SubOp)) {
+            return Err(HobbsError::Validation(
+                "polls.create_role must be \"member\" or \"subop\".".to_string(),
+            ));
+        }
+
         if self.ssh.enabled {
             if self.ssh.password.is_empty() {
                 return Err(HobbsError::Validation(
@@ -849,6 +1192,35 @@ impl Config {
         }
         Ok(())
     }
+
+    /// Validate the FTN echomail configuration.
+    fn validate_ftn(&self) -> Result<()> {
+        let ftn = &self.ftn;
+        for (name, address) in [("address", &ftn.address), ("uplink", &ftn.uplink)] {
+            if address.parse::<crate::ftn::FtnAddress>().is_err() {
+                return Err(HobbsError::Validation(format!(
+                    "ftn.{} must be an FTN address like 21:1/100 (got \"{}\").",
+                    name, address
+                )));
+            }
+        }
+        if ftn.password.len() > 8 {
+            return Err(HobbsError::Validation(
+                "ftn.password must be at most 8 characters.".to_string(),
+            ));
+        }
+        if crate::ftn::parse_charset(&ftn.charset).is_none() {
+            return Err(HobbsError::Validation(
+                "ftn.charset must be one of shiftjis, utf8 or cp437.".to_string(),
+            ));
+        }
+        if ftn.areas.keys().any(|tag| tag.is_empty() || tag.contains(char::is_whitespace)) {
+            return Err(HobbsError::Validation(
+                "ftn.areas tags must not be empty or contain spaces.".to_string(),
+            ));
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -886,6 +1258,7 @@ mod tests {
         assert_eq!(config.locale.language, "ja");
 
         assert_eq!(config.templates.path, "templates");
+        assert!(config.templates.hot_reload);
 
         assert_eq!(config.logging.level, "info");
         assert_eq!(config.logging.file, "logs/hobbs.log");
@@ -893,6 +1266,7 @@ mod tests {
         assert_eq!(config.terminal.default_profile, "standard");
         assert!(config.terminal.auto_paging);
         assert_eq!(config.terminal.paging_lines, 0);
+        assert!(config.terminal.lightbar);
 
         assert!(config.rss.enabled);
         assert_eq!(config.rss.update_interval_secs, 300);
@@ -906,6 +1280,28 @@ mod tests {
         assert_eq!(config.rss.max_redirects, 5);
         assert_eq!(config.rss.max_consecutive_errors, 5);
 
+        assert_eq!(config.qwk.bbs_id, "HOBBS");
+        assert_eq!(config.qwk.max_messages, 500);
+
+        assert!(!config.ftn.enabled);
+        assert_eq!(config.ftn.inbound, "data/ftn/inbound");
+        assert_eq!(config.ftn.outbound, "data/ftn/outbound");
+        assert_eq!(config.ftn.charset, "shiftjis");
+        assert_eq!(config.ftn.interval_secs, 300);
+        assert!(config.ftn.areas.is_empty());
+
+        assert!(!config.nntp.enabled);
+        assert_eq!(config.nntp.port, 1119);
+        assert_eq!(config.nntp.domain, "hobbs.invalid");
+        assert_eq!(config.nntp.group_prefix, "hobbs");
+        assert_eq!(config.nntp.max_connections, 20);
+        assert!(config.notifications.enabled);
+        assert_eq!(config.notifications.interval_secs, 30);
+        assert_eq!(config.notifications.digest_hour, 7);
+        assert!(config.retention.enabled);
+        assert_eq!(config.retention.interval_secs, 3600);
+        assert_eq!(config.retention.archive_dir, "data/archives");
+
         assert!(!config.web.enabled);
         assert_eq!(config.web.host, "0.0.0.0");
         assert_eq!(config.web.port, 8080);
@@ -1274,6 +1670,126 @@ max_channels_per_connection = 3
         }
     }
 
+    #[test]
+    fn test_validate_qwk_bbs_id() {
+        let mut config = Config::default();
+        assert!(config.validate().is_ok());
+
+        for bbs_id in ["", "TOOLONGID", "BAD ID"] {
+            config.qwk.bbs_id = bbs_id.to_string();
+            match config.validate() {
+                Err(HobbsError::Validation(msg)) => assert!(msg.contains("bbs_id")),
+                other => panic!("expected validation error, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_ftn_config() {
+        let toml = r#"
+[ftn]
+enabled = true
+address = "21:1/100"
+uplink = "21:1/1"
+password = "secret"
+charset = "utf8"
+
+[ftn.areas]
+"HOBBS.GENERAL" = 1
+"HOBBS.CHAT" = 2
+"#;
+
+        let config = Config::parse(toml).unwrap();
+
+        assert!(config.ftn.enabled);
+        assert_eq!(config.ftn.address, "21:1/100");
+        assert_eq!(config.ftn.password, "secret");
+        assert_eq!(config.ftn.areas.get("HOBBS.GENERAL"), Some(&1));
+        assert_eq!(config.ftn.areas.get("HOBBS.CHAT"), Some(&2));
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_ftn() {
+        let mut config = Config::default();
+        // Disabled echomail is not validated
+        config.ftn.address = "bogus".to_string();
+        assert!(config.validate().is_ok());
+
+        config.ftn.enabled = true;
+        config.ftn.uplink = "21:1/1".to_string();
+        assert!(config.validate().is_err());
+
+        config.ftn.address = "21:1/100".to_string();
+        assert!(config.validate().is_ok());
+
+        config.ftn.password = "TOOLONGPW".to_string();
+        assert!(config.validate().is_err());
+        config.ftn.password = String::new();
+
+        config.ftn.charset = "petscii".to_string();
+        assert!(config.validate().is_err());
+        config.ftn.charset = "cp437".to_string();
+
+        config.ftn.areas.insert("BAD TAG".to_string(), 1);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_nntp() {
+        let mut config = Config::default();
+        config.nntp.enabled = true;
+        assert!(config.validate().is_ok());
+
+        for prefix in ["", "bad prefix", "trailing.", "a..b"] {
+            config.nntp.group_prefix = prefix.to_string();
+            assert!(config.validate().is_err(), "{:?} should be rejected", prefix);
+        }
+        config.nntp.group_prefix = "fj.hobbs".to_string();
+        assert!(config.validate().is_ok());
+
+        config.nntp.max_connections = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_notifications() {
+        let mut config = Config::default();
+        config.notifications.digest_hour = 23;
+        assert!(config.validate().is_ok());
+
+        config.notifications.digest_hour = 24;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_retention() {
+        let mut config = Config::default();
+        config.retention.interval_secs = 60;
+        assert!(config.validate().is_ok());
+
+        config.retention.interval_secs = 10;
+        assert!(config.validate().is_err());
+
+        config.retention.enabled = false;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_polls() {
+        let mut config = Config::default();
+        assert_eq!(config.polls.create_role(), Role::Member);
+
+        config.polls.create_role = "subop".to_string();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.polls.create_role(), Role::SubOp);
+
+        for role in ["guest", "sysop", "admin"] {
+            config.polls.create_role = role.to_string();
+            assert!(config.validate().is_err(), "{:?} should be rejected", role);
+        }
+    }
+
     #[test]
     fn test_apply_env_overrides_ssh_password() {
         let original = std::env::var("HOBBS_SSH_PASSWORD").ok();
@@ -3,6 +3,9 @@
 use chrono::{DateTime, NaiveDateTime, Utc};
 use chrono_tz::Tz;
 
+/// Datetime format used in the database.
+const DB_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
 /// Format a datetime string (stored as UTC) to the specified timezone.
 ///
 /// # Arguments
@@ -80,6 +83,48 @@ pub fn to_rfc3339(datetime_str: &str) -> String {
     format!("{}Z", datetime_str.replace(' ', "T"))
 }
 
+/// Parse a user-entered datetime into a database datetime string (UTC).
+///
+/// Accepts RFC3339, or "YYYY-MM-DD HH:MM" / "YYYY-MM-DD" in the given
+/// timezone (a bare date means midnight).
+///
+/// # Returns
+///
+/// Datetime string in SQLite format (YYYY-MM-DD HH:MM:SS), or None if the
+/// input or timezone cannot be parsed.
+pub fn parse_local_datetime(input: &str, timezone: &str) -> Option<String> {
+    let input = input.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(input) {
+        return Some(dt.with_timezone(&Utc).format(DB_FORMAT).to_string());
+    }
+
+    let tz: Tz = timezone.parse().ok()?;
+    let naive = NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M")
+        .or_else(|_| NaiveDateTime::parse_from_str(&format!("{input} 00:00"), "%Y-%m-%d %H:%M"))
+        .ok()?;
+    let local = naive.and_local_timezone(tz).earliest()?;
+    Some(local.with_timezone(&Utc).format(DB_FORMAT).to_string())
+}
+
+/// Current time as a database datetime string (UTC).
+pub fn now_db() -> String {
+    Utc::now().format(DB_FORMAT).to_string()
+}
+
+/// Time a number of days ago as a database datetime string (UTC).
+pub fn days_ago_db(days: i64) -> String {
+    (Utc::now() - chrono::Duration::days(days))
+        .format(DB_FORMAT)
+        .to_string()
+}
+
+/// Time a number of hours ago as a database datetime string (UTC).
+pub fn hours_ago_db(hours: i64) -> String {
+    (Utc::now() - chrono::Duration::hours(hours))
+        .format(DB_FORMAT)
+        .to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,6 +145,36 @@ mod tests {
         assert_eq!(result, "2024/01/15 19:30"); // UTC+9
     }
 
+    #[test]
+    fn test_days_ago_db() {
+        assert_eq!(days_ago_db(0).len(), now_db().len());
+        assert!(days_ago_db(7) < now_db());
+    }
+
+    #[test]
+    fn test_hours_ago_db() {
+        assert!(hours_ago_db(1) < now_db());
+        assert!(hours_ago_db(25) < days_ago_db(1));
+    }
+
+    #[test]
+    fn test_parse_local_datetime() {
+        assert_eq!(
+            parse_local_datetime("2024-01-15 19:30", "Asia/Tokyo").as_deref(),
+            Some("2024-01-15 10:30:00")
+        );
+        assert_eq!(
+            parse_local_datetime("2024-01-15", "UTC").as_deref(),
+            Some("2024-01-15 00:00:00")
+        );
+        assert_eq!(
+            parse_local_datetime("2024-01-15T10:30:00+09:00", "UTC").as_deref(),
+            Some("2024-01-15 01:30:00")
+        );
+        assert!(parse_local_datetime("tomorrow", "UTC").is_none());
+        assert!(parse_local_datetime("2024-01-15", "Nowhere/City").is_none());
+    }
+
     #[test]
     fn test_format_datetime_utc() {
         let dt = "2024-01-15 10:30:00";
@@ -56,7 +56,7 @@ impl<'a> UserRepository<'a> {
     pub async fn get_by_id(&self, id: i64) -> Result<Option<User>> {
         let result = sqlx::query_as::<_, User>(
             "SELECT id, username, password, nickname, email, role, profile, terminal,
-                    encoding, language, auto_paging, created_at, last_login, is_active
+                    encoding, language, auto_paging, show_colors, created_at, last_login, is_active
              FROM users WHERE id = $1",
         )
         .bind(id)
@@ -71,11 +71,11 @@ impl<'a> UserRepository<'a> {
     pub async fn get_by_username(&self, username: &str) -> Result<Option<User>> {
         #[cfg(feature = "sqlite")]
         let query = "SELECT id, username, password, nickname, email, role, profile, terminal,
-                    encoding, language, auto_paging, created_at, last_login, is_active
+                    encoding, language, auto_paging, show_colors, created_at, last_login, is_active
              FROM users WHERE username = $1 COLLATE NOCASE";
         #[cfg(feature = "postgres")]
         let query = "SELECT id, username, password, nickname, email, role, profile, terminal,
-                    encoding, language, auto_paging, created_at, last_login, is_active
+                    encoding, language, auto_paging, show_colors, created_at, last_login, is_active
              FROM users WHERE LOWER(username) = LOWER($1)";
 
         let result = sqlx::query_as::<_, User>(query)
@@ -142,6 +142,10 @@ impl<'a> UserRepository<'a> {
             separated.push("auto_paging = ");
             separated.push_bind_unseparated(auto_paging);
         }
+        if let Some(show_colors) = update.show_colors {
+            separated.push("show_colors = ");
+            separated.push_bind_unseparated(show_colors);
+        }
 
         query.push(" WHERE id = ");
         query.push_bind(id);
@@ -186,7 +190,7 @@ impl<'a> UserRepository<'a> {
     pub async fn list_active(&self) -> Result<Vec<User>> {
         let query = format!(
             "SELECT id, username, password, nickname, email, role, profile, terminal,
-                    encoding, language, auto_paging, created_at, last_login, is_active
+                    encoding, language, auto_paging, show_colors, created_at, last_login, is_active
              FROM users WHERE is_active = {} ORDER BY username",
             SQL_TRUE
         );
@@ -202,7 +206,7 @@ impl<'a> UserRepository<'a> {
     pub async fn list_all(&self) -> Result<Vec<User>> {
         let users = sqlx::query_as::<_, User>(
             "SELECT id, username, password, nickname, email, role, profile, terminal,
-                    encoding, language, auto_paging, created_at, last_login, is_active
+                    encoding, language, auto_paging, show_colors, created_at, last_login, is_active
              FROM users ORDER BY username",
         )
         .fetch_all(self.pool)
@@ -216,7 +220,7 @@ impl<'a> UserRepository<'a> {
     pub async fn list_by_role(&self, role: Role) -> Result<Vec<User>> {
         let query = format!(
             "SELECT id, username, password, nickname, email, role, profile, terminal,
-                    encoding, language, auto_paging, created_at, last_login, is_active
+                    encoding, language, auto_paging, show_colors, created_at, last_login, is_active
              FROM users WHERE role = $1 AND is_active = {} ORDER BY username",
             SQL_TRUE
         );
@@ -113,6 +113,8 @@ pub struct User {
     pub language: String,
     /// Auto-paging enabled (for terminals without scroll capability).
     pub auto_paging: bool,
+    /// Whether color codes in other users' posts and mail are displayed.
+    pub show_colors: bool,
     /// Account creation timestamp.
     pub created_at: String,
     /// Last login timestamp (optional).
@@ -241,6 +243,8 @@ pub struct UserUpdate {
     pub is_active: Option<bool>,
     /// New auto-paging preference.
     pub auto_paging: Option<bool>,
+    /// New color display preference.
+    pub show_colors: Option<bool>,
 }
 
 impl UserUpdate {
@@ -309,6 +313,12 @@ impl UserUpdate {
         self
     }
 
+    /// Set color display preference.
+    pub fn show_colors(mut self, show_colors: bool) -> Self {
+        self.show_colors = Some(show_colors);
+        self
+    }
+
     /// Check if any fields are set.
     pub fn is_empty(&self) -> bool {
         self.password.is_none()
@@ -321,6 +331,7 @@ impl UserUpdate {
             && self.language.is_none()
             && self.is_active.is_none()
             && self.auto_paging.is_none()
+            && self.show_colors.is_none()
     }
 }
 
@@ -412,6 +423,7 @@ mod tests {
             encoding: CharacterEncoding::default(),
             language: "en".to_string(),
             auto_paging: true,
+            show_colors: true,
             created_at: "2024-01-01".to_string(),
             last_login: None,
             is_active: true,
@@ -437,6 +449,7 @@ mod tests {
             encoding: CharacterEncoding::default(),
             language: "en".to_string(),
             auto_paging: true,
+            show_colors: true,
             created_at: "2024-01-01".to_string(),
             last_login: None,
             is_active: true,
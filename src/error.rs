@@ -48,6 +48,18 @@ pub enum HobbsError {
     #[error("RSS error: {0}")]
     Rss(String),
 
+    /// QWK packet error.
+    #[error("QWK error: {0}")]
+    Qwk(String),
+
+    /// FTN packet error.
+    #[error("FTN error: {0}")]
+    Ftn(String),
+
+    /// Board archive error.
+    #[error("archive error: {0}")]
+    Archive(String),
+
     /// Configuration error.
     #[error("configuration error: {0}")]
     Config(String),
@@ -0,0 +1,20 @@
+//! Content filter for HOBBS.
+//!
+//! The SysOp maintains rules that are checked against board posts, mail and
+//! chat messages from every front end (Telnet, web, NNTP and QWK):
+//! - Word lists and regular expressions
+//! - A limit on the number of links
+//! - Restrictions on accounts younger than a number of hours
+//!
+//! A matching rule rejects the content, holds it for moderation or masks
+//! the matched text. Every hit is logged for the SysOp to review.
+
+pub mod repository;
+pub mod service;
+pub mod types;
+
+pub use repository::FilterRepository;
+pub use service::{is_rejection, ContentFilterService, HIT_LOG_LIMIT, MAX_PATTERN_LENGTH};
+pub use types::{
+    FilterAction, FilterHit, FilterRule, FilterRuleType, FilterTarget, FilterVerdict, NewFilterRule,
+};
@@ -0,0 +1,165 @@
+//! Content filter repository for HOBBS.
+
+use super::types::{FilterAction, FilterHit, FilterRule, FilterRuleType, NewFilterRule};
+use crate::db::{DbPool, SQL_TRUE};
+use crate::{HobbsError, Result};
+
+const RULE_SELECT: &str =
+    "SELECT id, rule_type, pattern, action, is_active, created_by, created_at FROM filter_rules";
+
+/// Repository for content filter rules and their hit log.
+pub struct FilterRepository<'a> {
+    pool: &'a DbPool,
+}
+
+impl<'a> FilterRepository<'a> {
+    /// Create a new FilterRepository with the given database pool.
+    pub fn new(pool: &'a DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Add a rule.
+    pub async fn create_rule(&self, rule: &NewFilterRule, created_by: i64) -> Result<FilterRule> {
+        let id: i64 = sqlx::query_scalar(
+            "INSERT INTO filter_rules (rule_type, pattern, action, created_by)
+             VALUES ($1, $2, $3, $4)
+             RETURNING id",
+        )
+        .bind(rule.rule_type.as_str())
+        .bind(&rule.pattern)
+        .bind(rule.action.as_str())
+        .bind(created_by)
+        .fetch_one(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        self.get_rule(id)
+            .await?
+            .ok_or_else(|| HobbsError::NotFound("filter rule".to_string()))
+    }
+
+    /// Get a rule by ID.
+    pub async fn get_rule(&self, id: i64) -> Result<Option<FilterRule>> {
+        let query = format!("{RULE_SELECT} WHERE id = $1");
+        let row: Option<FilterRuleRow> = sqlx::query_as(&query)
+            .bind(id)
+            .fetch_optional(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(row.map(FilterRuleRow::into_rule))
+    }
+
+    /// List all rules in the order they were added.
+    pub async fn list_rules(&self) -> Result<Vec<FilterRule>> {
+        let query = format!("{RULE_SELECT} ORDER BY id");
+        self.fetch_rules(&query).await
+    }
+
+    /// List the rules that are applied.
+    pub async fn list_active_rules(&self) -> Result<Vec<FilterRule>> {
+        let query = format!("{RULE_SELECT} WHERE is_active = {SQL_TRUE} ORDER BY id");
+        self.fetch_rules(&query).await
+    }
+
+    async fn fetch_rules(&self, query: &str) -> Result<Vec<FilterRule>> {
+        let rows: Vec<FilterRuleRow> = sqlx::query_as(query)
+            .fetch_all(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(FilterRuleRow::into_rule).collect())
+    }
+
+    /// Turn a rule on or off.
+    ///
+    /// Returns false if the rule does not exist.
+    pub async fn set_active(&self, id: i64, is_active: bool) -> Result<bool> {
+        let result = sqlx::query("UPDATE filter_rules SET is_active = $1 WHERE id = $2")
+            .bind(is_active)
+            .bind(id)
+            .execute(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Delete a rule. Its logged hits are kept.
+    ///
+    /// Returns false if the rule does not exist.
+    pub async fn delete_rule(&self, id: i64) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM filter_rules WHERE id = $1")
+            .bind(id)
+            .execute(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Log a rule hit.
+    pub async fn log_hit(
+        &self,
+        rule: &FilterRule,
+        user_id: Option<i64>,
+        target: &str,
+        detail: &str,
+    ) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO filter_hits (rule_id, user_id, target, action, detail)
+             VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(rule.id)
+        .bind(user_id)
+        .bind(target)
+        .bind(rule.action.as_str())
+        .bind(detail)
+        .execute(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// List the most recent hits, newest first.
+    pub async fn list_hits(&self, limit: i64) -> Result<Vec<FilterHit>> {
+        sqlx::query_as::<_, FilterHit>(
+            "SELECT h.id, h.rule_id, h.user_id, u.username, h.target, h.action, h.detail,
+                    h.created_at
+             FROM filter_hits h
+             LEFT JOIN users u ON u.id = h.user_id
+             ORDER BY h.id DESC
+             LIMIT $1",
+        )
+        .bind(limit)
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct FilterRuleRow {
+    id: i64,
+    rule_type: String,
+    pattern: String,
+    action: String,
+    is_active: bool,
+    created_by: Option<i64>,
+    created_at: String,
+}
+
+impl FilterRuleRow {
+    fn into_rule(self) -> FilterRule {
+        FilterRule {
+            id: self.id,
+            rule_type: self.rule_type.parse().unwrap_or(FilterRuleType::Word),
+            pattern: self.pattern,
+            action: self.action.parse().unwrap_or(FilterAction::Reject),
+            is_active: self.is_active,
+            created_by: self.created_by,
+            created_at: self.created_at,
+        }
+    }
+}
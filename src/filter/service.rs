@@ -0,0 +1,526 @@
+//! Content filter service for HOBBS.
+//!
+//! Active rules are checked against every post, mail and chat message
+//! before it is stored or sent. Each rule that matches is logged; the most
+//! severe action among them decides what happens to the content:
+//! - Reject: the content is refused
+//! - Hold: posts go to the moderation queue; mail and chat are refused
+//! - Mask: the matched text is replaced with asterisks
+//!
+//! Content by SubOps and SysOps is never filtered.
+
+use regex::{Regex, RegexBuilder};
+use tracing::{info, warn};
+
+use super::repository::FilterRepository;
+use super::types::{
+    FilterAction, FilterHit, FilterRule, FilterRuleType, FilterTarget, FilterVerdict, NewFilterRule,
+};
+use crate::datetime::hours_ago_db;
+use crate::db::{DbPool, Role, User, UserRepository};
+use crate::{HobbsError, Result};
+
+/// Maximum length of a rule pattern in characters.
+pub const MAX_PATTERN_LENGTH: usize = 2000;
+
+/// Number of hits shown in the hit log.
+pub const HIT_LOG_LIMIT: i64 = 100;
+
+/// Maximum length of the matched text kept in the hit log.
+const MAX_DETAIL_LENGTH: usize = 100;
+
+/// Compiled size limit for regular expression rules.
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+/// Error message for refused content. The rule is not named so that the
+/// filter cannot be probed.
+const REJECTED_MESSAGE: &str = "この内容はコンテンツフィルタにより拒否されました";
+
+/// Matches http(s) and www links.
+const LINK_PATTERN: &str = r"(?i)(?:https?://|www\.)\S+";
+
+/// Service for the content filter.
+pub struct ContentFilterService<'a> {
+    pool: &'a DbPool,
+}
+
+impl<'a> ContentFilterService<'a> {
+    /// Create a new ContentFilterService.
+    pub fn new(pool: &'a DbPool) -> Self {
+        Self { pool }
+    }
+
+    // ========== Rule Management (SysOp) ==========
+
+    /// List all rules.
+    pub async fn list_rules(&self, user_role: Role) -> Result<Vec<FilterRule>> {
+        require_sysop(user_role)?;
+        FilterRepository::new(self.pool).list_rules().await
+    }
+
+    /// Add a rule after checking its pattern.
+    pub async fn add_rule(
+        &self,
+        user_id: i64,
+        user_role: Role,
+        mut rule: NewFilterRule,
+    ) -> Result<FilterRule> {
+        require_sysop(user_role)?;
+
+        rule.pattern = rule.pattern.trim().to_string();
+        if rule.pattern.chars().count() > MAX_PATTERN_LENGTH {
+            return Err(HobbsError::Validation(format!(
+                "パターンは{MAX_PATTERN_LENGTH}文字以内にしてください"
+            )));
+        }
+        if rule.rule_type == FilterRuleType::NewAccount && rule.action == FilterAction::Mask {
+            return Err(HobbsError::Validation(
+                "新規アカウントのルールでは伏せ字は使えません".to_string(),
+            ));
+        }
+        Matcher::compile(rule.rule_type, &rule.pattern)?;
+
+        FilterRepository::new(self.pool)
+            .create_rule(&rule, user_id)
+            .await
+    }
+
+    /// Turn a rule on or off.
+    pub async fn set_active(
+        &self,
+        rule_id: i64,
+        is_active: bool,
+        user_role: Role,
+    ) -> Result<FilterRule> {
+        require_sysop(user_role)?;
+
+        let repo = FilterRepository::new(self.pool);
+        if !repo.set_active(rule_id, is_active).await? {
+            return Err(HobbsError::NotFound("フィルタルール".to_string()));
+        }
+        repo.get_rule(rule_id)
+            .await?
+            .ok_or_else(|| HobbsError::NotFound("フィルタルール".to_string()))
+    }
+
+    /// Delete a rule.
+    pub async fn delete_rule(&self, rule_id: i64, user_role: Role) -> Result<()> {
+        require_sysop(user_role)?;
+
+        if !FilterRepository::new(self.pool)
+            .delete_rule(rule_id)
+            .await?
+        {
+            return Err(HobbsError::NotFound("フィルタルール".to_string()));
+        }
+        Ok(())
+    }
+
+    /// List the most recent rule hits, newest first.
+    pub async fn list_hits(&self, user_role: Role) -> Result<Vec<FilterHit>> {
+        require_sysop(user_role)?;
+        FilterRepository::new(self.pool)
+            .list_hits(HIT_LOG_LIMIT)
+            .await
+    }
+
+    // ========== Filtering ==========
+
+    /// Filter content written by a user (None for guests).
+    ///
+    /// `texts` are the parts of the content, such as a title and a body;
+    /// masked text is replaced in place. Returns an error if the content is
+    /// rejected.
+    pub async fn apply(
+        &self,
+        user_id: Option<i64>,
+        target: FilterTarget,
+        texts: &mut [String],
+    ) -> Result<FilterVerdict> {
+        let repo = FilterRepository::new(self.pool);
+        let mut rules = repo.list_active_rules().await?;
+        if rules.is_empty() {
+            return Ok(FilterVerdict::Allow);
+        }
+
+        let user = match user_id {
+            Some(id) => UserRepository::new(self.pool).get_by_id(id).await?,
+            None => None,
+        };
+        if user
+            .as_ref()
+            .is_some_and(|u| u.role.can_access(Role::SubOp))
+        {
+            return Ok(FilterVerdict::Allow);
+        }
+
+        // Mask last, so that masking cannot hide text from other rules
+        rules.sort_by_key(|rule| std::cmp::Reverse(rule.action));
+
+        let mut worst = None;
+        for rule in &rules {
+            let matcher = match Matcher::compile(rule.rule_type, &rule.pattern) {
+                Ok(matcher) => matcher,
+                Err(e) => {
+                    warn!("Skipping invalid filter rule {}: {}", rule.id, e);
+                    continue;
+                }
+            };
+            let Some(detail) = matcher.find(user.as_ref(), texts) else {
+                continue;
+            };
+
+            info!(
+                "Content filter rule {} ({}) hit on {} by user {:?}: {}",
+                rule.id, rule.action, target, user_id, detail
+            );
+            repo.log_hit(rule, user_id, target.as_str(), &detail)
+                .await?;
+
+            if rule.action == FilterAction::Mask {
+                matcher.mask(texts);
+            }
+            worst = worst.max(Some(rule.action));
+        }
+
+        match worst {
+            Some(FilterAction::Reject) => Err(HobbsError::Validation(REJECTED_MESSAGE.to_string())),
+            Some(FilterAction::Hold) => Ok(FilterVerdict::Hold),
+            _ => Ok(FilterVerdict::Allow),
+        }
+    }
+
+    /// Filter content that cannot be held for moderation, such as mail and
+    /// chat messages. Content that would be held is rejected instead.
+    pub async fn apply_strict(
+        &self,
+        user_id: Option<i64>,
+        target: FilterTarget,
+        texts: &mut [String],
+    ) -> Result<()> {
+        if self.apply(user_id, target, texts).await?.is_hold() {
+            return Err(HobbsError::Validation(REJECTED_MESSAGE.to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// Check whether an error is the content filter rejecting content.
+pub fn is_rejection(error: &HobbsError) -> bool {
+    matches!(error, HobbsError::Validation(message) if message == REJECTED_MESSAGE)
+}
+
+fn require_sysop(user_role: Role) -> Result<()> {
+    if user_role.can_access(Role::SysOp) {
+        Ok(())
+    } else {
+        Err(HobbsError::Permission(
+            "コンテンツフィルタを管理する権限がありません".to_string(),
+        ))
+    }
+}
+
+/// A rule ready to be checked against content.
+enum Matcher {
+    /// Words or a regular expression.
+    Text(Regex),
+    /// More links than allowed.
+    Links(Regex, usize),
+    /// Accounts younger than this many hours.
+    NewAccount(i64),
+}
+
+impl Matcher {
+    fn compile(rule_type: FilterRuleType, pattern: &str) -> Result<Self> {
+        match rule_type {
+            FilterRuleType::Word => {
+                let mut words: Vec<&str> = pattern
+                    .split([',', '\n'])
+                    .map(str::trim)
+                    .filter(|w| !w.is_empty())
+                    .collect();
+                if words.is_empty() {
+                    return Err(HobbsError::Validation(
+                        "禁止語を入力してください".to_string(),
+                    ));
+                }
+                // Longer words first, so that the whole word is masked
+                words.sort_by_key(|w| std::cmp::Reverse(w.chars().count()));
+                let alternation: Vec<String> = words.iter().map(|w| regex::escape(w)).collect();
+                Ok(Matcher::Text(build_regex(&format!(
+                    "(?i){}",
+                    alternation.join("|")
+                ))?))
+            }
+            FilterRuleType::Regex => {
+                if pattern.is_empty() {
+                    return Err(HobbsError::Validation(
+                        "正規表現を入力してください".to_string(),
+                    ));
+                }
+                Ok(Matcher::Text(build_regex(pattern)?))
+            }
+            FilterRuleType::Links => {
+                let limit = pattern.parse::<usize>().map_err(|_| {
+                    HobbsError::Validation("リンク数には0以上の数を入力してください".to_string())
+                })?;
+                Ok(Matcher::Links(build_regex(LINK_PATTERN)?, limit))
+            }
+            FilterRuleType::NewAccount => match pattern.parse::<i64>() {
+                Ok(hours) if hours > 0 => Ok(Matcher::NewAccount(hours)),
+                _ => Err(HobbsError::Validation(
+                    "登録からの時間には1以上の数を入力してください".to_string(),
+                )),
+            },
+        }
+    }
+
+    /// Check the content, returning what matched.
+    fn find(&self, user: Option<&User>, texts: &[String]) -> Option<String> {
+        match self {
+            Matcher::Text(regex) => texts
+                .iter()
+                .find_map(|text| regex.find(text))
+                .map(|m| m.as_str().chars().take(MAX_DETAIL_LENGTH).collect()),
+            Matcher::Links(regex, limit) => {
+                let count: usize = texts.iter().map(|text| regex.find_iter(text).count()).sum();
+                (count > *limit).then(|| format!("リンク{count}件"))
+            }
+            Matcher::NewAccount(hours) => {
+                // Guests have no account age and count as new
+                let is_new = user.is_none_or(|u| u.created_at > hours_ago_db(*hours));
+                is_new.then(|| format!("登録から{hours}時間以内"))
+            }
+        }
+    }
+
+    /// Replace what matched with asterisks.
+    fn mask(&self, texts: &mut [String]) {
+        let regex = match self {
+            Matcher::Text(regex) | Matcher::Links(regex, _) => regex,
+            Matcher::NewAccount(_) => return,
+        };
+        for text in texts.iter_mut() {
+            let masked = regex.replace_all(text, |caps: &regex::Captures| {
+                "*".repeat(caps[0].chars().count())
+            });
+            *text = masked.into_owned();
+        }
+    }
+}
+
+fn build_regex(pattern: &str) -> Result<Regex> {
+    RegexBuilder::new(pattern)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .build()
+        .map_err(|e| HobbsError::Validation(format!("正規表現が正しくありません: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{Database, NewUser};
+
+    async fn setup() -> (Database, i64, i64) {
+        let db = Database::open_in_memory().await.unwrap();
+        let repo = UserRepository::new(db.pool());
+        let sysop = repo
+            .create(&NewUser::new("sysop", "hash", "SysOp").with_role(Role::SysOp))
+            .await
+            .unwrap();
+        let member = repo
+            .create(&NewUser::new("member", "hash", "Member"))
+            .await
+            .unwrap();
+        (db, sysop.id, member.id)
+    }
+
+    async fn add(
+        db: &Database,
+        sysop: i64,
+        rule_type: FilterRuleType,
+        pattern: &str,
+        action: FilterAction,
+    ) -> FilterRule {
+        ContentFilterService::new(db.pool())
+            .add_rule(
+                sysop,
+                Role::SysOp,
+                NewFilterRule::new(rule_type, pattern, action),
+            )
+            .await
+            .unwrap()
+    }
+
+    fn texts(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_add_rule_validation() {
+        let (db, sysop, member) = setup().await;
+        let service = ContentFilterService::new(db.pool());
+
+        let rule = NewFilterRule::new(FilterRuleType::Word, "spam", FilterAction::Reject);
+        let result = service.add_rule(member, Role::Member, rule).await;
+        assert!(matches!(result, Err(HobbsError::Permission(_))));
+
+        for (rule_type, pattern, action) in [
+            (FilterRuleType::Regex, "(unclosed", FilterAction::Reject),
+            (FilterRuleType::Word, " , ", FilterAction::Reject),
+            (FilterRuleType::Links, "many", FilterAction::Hold),
+            (FilterRuleType::NewAccount, "0", FilterAction::Hold),
+            (FilterRuleType::NewAccount, "24", FilterAction::Mask),
+        ] {
+            let rule = NewFilterRule::new(rule_type, pattern, action);
+            let result = service.add_rule(sysop, Role::SysOp, rule).await;
+            assert!(
+                matches!(result, Err(HobbsError::Validation(_))),
+                "{pattern}"
+            );
+        }
+        assert!(service.list_rules(Role::SysOp).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_word_rule_masks() {
+        let (db, sysop, member) = setup().await;
+        add(
+            &db,
+            sysop,
+            FilterRuleType::Word,
+            "darn, heck",
+            FilterAction::Mask,
+        )
+        .await;
+        let service = ContentFilterService::new(db.pool());
+
+        let mut content = texts(&["Oh Darn", "what the heck"]);
+        let verdict = service
+            .apply(Some(member), FilterTarget::Post, &mut content)
+            .await
+            .unwrap();
+        assert_eq!(verdict, FilterVerdict::Allow);
+        assert_eq!(content, texts(&["Oh ****", "what the ****"]));
+
+        let hits = service.list_hits(Role::SysOp).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].detail, "Darn");
+        assert_eq!(hits[0].target, "post");
+        assert_eq!(hits[0].username.as_deref(), Some("member"));
+    }
+
+    #[tokio::test]
+    async fn test_most_severe_action_wins() {
+        let (db, sysop, member) = setup().await;
+        add(&db, sysop, FilterRuleType::Word, "buy", FilterAction::Mask).await;
+        add(&db, sysop, FilterRuleType::Links, "1", FilterAction::Hold).await;
+        let reject = add(
+            &db,
+            sysop,
+            FilterRuleType::Regex,
+            r"(?i)cheap\s+pills",
+            FilterAction::Reject,
+        )
+        .await;
+        let service = ContentFilterService::new(db.pool());
+
+        let mut content = texts(&["buy http://a.example www.b.example"]);
+        let verdict = service
+            .apply(Some(member), FilterTarget::Post, &mut content)
+            .await
+            .unwrap();
+        assert!(verdict.is_hold());
+        assert_eq!(content[0], "*** http://a.example www.b.example");
+
+        let mut content = texts(&["buy CHEAP  pills"]);
+        let result = service
+            .apply(Some(member), FilterTarget::Post, &mut content)
+            .await;
+        assert!(is_rejection(&result.unwrap_err()));
+
+        // Held content cannot be sent as mail or chat
+        let mut content = texts(&["see http://a.example and http://b.example"]);
+        let result = service
+            .apply_strict(Some(member), FilterTarget::Mail, &mut content)
+            .await;
+        assert!(matches!(result, Err(HobbsError::Validation(_))));
+
+        // Disabled rules are not applied
+        service
+            .set_active(reject.id, false, Role::SysOp)
+            .await
+            .unwrap();
+        let mut content = texts(&["cheap pills"]);
+        service
+            .apply_strict(Some(member), FilterTarget::Chat, &mut content)
+            .await
+            .unwrap();
+
+        assert_eq!(service.list_hits(Role::SysOp).await.unwrap().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_new_account_rule() {
+        let (db, sysop, member) = setup().await;
+        add(
+            &db,
+            sysop,
+            FilterRuleType::NewAccount,
+            "24",
+            FilterAction::Hold,
+        )
+        .await;
+        let service = ContentFilterService::new(db.pool());
+
+        let mut content = texts(&["hello"]);
+        let verdict = service
+            .apply(Some(member), FilterTarget::Post, &mut content)
+            .await
+            .unwrap();
+        assert!(verdict.is_hold());
+
+        // Guests count as new accounts
+        let verdict = service
+            .apply(None, FilterTarget::Chat, &mut content)
+            .await
+            .unwrap();
+        assert!(verdict.is_hold());
+
+        sqlx::query("UPDATE users SET created_at = '2000-01-01 00:00:00' WHERE id = $1")
+            .bind(member)
+            .execute(db.pool())
+            .await
+            .unwrap();
+        let verdict = service
+            .apply(Some(member), FilterTarget::Post, &mut content)
+            .await
+            .unwrap();
+        assert_eq!(verdict, FilterVerdict::Allow);
+    }
+
+    #[tokio::test]
+    async fn test_staff_are_exempt() {
+        let (db, sysop, _) = setup().await;
+        let rule = add(
+            &db,
+            sysop,
+            FilterRuleType::Word,
+            "spam",
+            FilterAction::Reject,
+        )
+        .await;
+        let service = ContentFilterService::new(db.pool());
+
+        let mut content = texts(&["spam"]);
+        service
+            .apply(Some(sysop), FilterTarget::Post, &mut content)
+            .await
+            .unwrap();
+        assert!(service.list_hits(Role::SysOp).await.unwrap().is_empty());
+
+        service.delete_rule(rule.id, Role::SysOp).await.unwrap();
+        let result = service.delete_rule(rule.id, Role::SysOp).await;
+        assert!(matches!(result, Err(HobbsError::NotFound(_))));
+    }
+}
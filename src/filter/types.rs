@@ -0,0 +1,229 @@
+//! Content filter types.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// What a filter rule looks at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterRuleType {
+    /// A list of words, separated by commas or newlines (case-insensitive).
+    Word,
+    /// A regular expression.
+    Regex,
+    /// The maximum number of links allowed.
+    Links,
+    /// The minimum account age in hours.
+    NewAccount,
+}
+
+impl FilterRuleType {
+    /// Convert the rule type to its database string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FilterRuleType::Word => "word",
+            FilterRuleType::Regex => "regex",
+            FilterRuleType::Links => "links",
+            FilterRuleType::NewAccount => "new_account",
+        }
+    }
+}
+
+impl fmt::Display for FilterRuleType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for FilterRuleType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "word" => Ok(FilterRuleType::Word),
+            "regex" => Ok(FilterRuleType::Regex),
+            "links" => Ok(FilterRuleType::Links),
+            "new_account" => Ok(FilterRuleType::NewAccount),
+            _ => Err(format!("unknown filter rule type: {s}")),
+        }
+    }
+}
+
+/// What happens to content that matches a rule.
+///
+/// Variants are ordered by severity; when several rules match, the most
+/// severe action wins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FilterAction {
+    /// Replace the matched text with asterisks.
+    Mask,
+    /// Hold the content for moderator approval.
+    Hold,
+    /// Refuse the content.
+    Reject,
+}
+
+impl FilterAction {
+    /// Convert the action to its database string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FilterAction::Mask => "mask",
+            FilterAction::Hold => "hold",
+            FilterAction::Reject => "reject",
+        }
+    }
+}
+
+impl fmt::Display for FilterAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for FilterAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "mask" => Ok(FilterAction::Mask),
+            "hold" => Ok(FilterAction::Hold),
+            "reject" => Ok(FilterAction::Reject),
+            _ => Err(format!("unknown filter action: {s}")),
+        }
+    }
+}
+
+/// Kind of content being filtered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterTarget {
+    /// A board post (or thread title).
+    Post,
+    /// A mail.
+    Mail,
+    /// A chat message.
+    Chat,
+}
+
+impl FilterTarget {
+    /// Convert the target to its database string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FilterTarget::Post => "post",
+            FilterTarget::Mail => "mail",
+            FilterTarget::Chat => "chat",
+        }
+    }
+}
+
+impl fmt::Display for FilterTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A content filter rule.
+#[derive(Debug, Clone)]
+pub struct FilterRule {
+    /// Unique rule ID.
+    pub id: i64,
+    /// What the rule looks at.
+    pub rule_type: FilterRuleType,
+    /// Word list, regular expression, link limit or account age in hours,
+    /// depending on the rule type.
+    pub pattern: String,
+    /// What happens to matching content.
+    pub action: FilterAction,
+    /// Whether the rule is applied.
+    pub is_active: bool,
+    /// ID of the SysOp who added the rule.
+    pub created_by: Option<i64>,
+    /// Creation timestamp.
+    pub created_at: String,
+}
+
+/// Data for adding a filter rule.
+#[derive(Debug, Clone)]
+pub struct NewFilterRule {
+    /// What the rule looks at.
+    pub rule_type: FilterRuleType,
+    /// Word list, regular expression, link limit or account age in hours.
+    pub pattern: String,
+    /// What happens to matching content.
+    pub action: FilterAction,
+}
+
+impl NewFilterRule {
+    /// Create a new rule.
+    pub fn new(
+        rule_type: FilterRuleType,
+        pattern: impl Into<String>,
+        action: FilterAction,
+    ) -> Self {
+        Self {
+            rule_type,
+            pattern: pattern.into(),
+            action,
+        }
+    }
+}
+
+/// A logged rule hit.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct FilterHit {
+    /// Unique hit ID.
+    pub id: i64,
+    /// Rule that matched (None once the rule is deleted).
+    pub rule_id: Option<i64>,
+    /// User whose content matched (None for guests).
+    pub user_id: Option<i64>,
+    /// Username of that user.
+    pub username: Option<String>,
+    /// Kind of content: post, mail or chat.
+    pub target: String,
+    /// Action of the rule: reject, hold or mask.
+    pub action: String,
+    /// The matched text, or a description for link and account rules.
+    pub detail: String,
+    /// Hit timestamp.
+    pub created_at: String,
+}
+
+/// Outcome of filtering content that was not rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterVerdict {
+    /// The content may be published (possibly masked).
+    Allow,
+    /// The content must be held for moderator approval.
+    Hold,
+}
+
+impl FilterVerdict {
+    /// Check whether the content must be held.
+    pub fn is_hold(&self) -> bool {
+        matches!(self, FilterVerdict::Hold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_type_roundtrip() {
+        for rule_type in [
+            FilterRuleType::Word,
+            FilterRuleType::Regex,
+            FilterRuleType::Links,
+            FilterRuleType::NewAccount,
+        ] {
+            assert_eq!(rule_type.as_str().parse::<FilterRuleType>(), Ok(rule_type));
+        }
+        assert!("other".parse::<FilterRuleType>().is_err());
+    }
+
+    #[test]
+    fn test_action_severity() {
+        assert!(FilterAction::Reject > FilterAction::Hold);
+        assert!(FilterAction::Hold > FilterAction::Mask);
+        assert_eq!("HOLD".parse::<FilterAction>(), Ok(FilterAction::Hold));
+    }
+}
@@ -0,0 +1,99 @@
+//! FTN node addresses.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::HobbsError;
+
+/// A FidoNet-style node address (`zone:net/node[.point]`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FtnAddress {
+    /// Zone number.
+    pub zone: u16,
+    /// Net number.
+    pub net: u16,
+    /// Node number.
+    pub node: u16,
+    /// Point number (0 for a node).
+    pub point: u16,
+}
+
+impl FtnAddress {
+    /// Create a node address without a point.
+    pub fn new(zone: u16, net: u16, node: u16) -> Self {
+        Self {
+            zone,
+            net,
+            node,
+            point: 0,
+        }
+    }
+
+    /// The 2D `net/node` pair used in SEEN-BY and PATH lines.
+    pub fn net_node(&self) -> (u16, u16) {
+        (self.net, self.node)
+    }
+}
+
+impl fmt::Display for FtnAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}/{}", self.zone, self.net, self.node)?;
+        if self.point != 0 {
+            write!(f, ".{}", self.point)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for FtnAddress {
+    type Err = HobbsError;
+
+    /// Parse `zone:net/node[.point]`, ignoring an `@domain` suffix.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || HobbsError::Validation(format!("invalid FTN address: {}", s));
+        let text = s.trim();
+        let text = text.split_once('@').map_or(text, |(addr, _)| addr);
+
+        let (zone, rest) = text.split_once(':').ok_or_else(invalid)?;
+        let (net, rest) = rest.split_once('/').ok_or_else(invalid)?;
+        let (node, point) = match rest.split_once('.') {
+            Some((node, point)) => (node, Some(point)),
+            None => (rest, None),
+        };
+
+        let number = |part: &str| part.parse::<u16>().map_err(|_| invalid());
+        Ok(Self {
+            zone: number(zone)?,
+            net: number(net)?,
+            node: number(node)?,
+            point: point.map(number).transpose()?.unwrap_or(0),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display() {
+        let addr: FtnAddress = "21:1/100".parse().unwrap();
+        assert_eq!(addr, FtnAddress::new(21, 1, 100));
+        assert_eq!(addr.to_string(), "21:1/100");
+
+        let point: FtnAddress = "2:5020/1042.7@fidonet".parse().unwrap();
+        assert_eq!(point.point, 7);
+        assert_eq!(point.net_node(), (5020, 1042));
+        assert_eq!(point.to_string(), "2:5020/1042.7");
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!("".parse::<FtnAddress>().is_err());
+        assert!("1/100".parse::<FtnAddress>().is_err());
+        assert!("1:1".parse::<FtnAddress>().is_err());
+        assert!("1:x/100".parse::<FtnAddress>().is_err());
+        assert!("1:1/100.".parse::<FtnAddress>().is_err());
+        assert!("1:1/70000".parse::<FtnAddress>().is_err());
+    }
+}
@@ -0,0 +1,375 @@
+//! Echomail message text.
+//!
+//! An echomail message body starts with an `AREA:` line, carries control
+//! lines ("kludges") prefixed with `^A`, and ends with a tear line, an
+//! origin line, `SEEN-BY:` lines and `^APATH:` lines.
+
+use super::packet::PackedMessage;
+use super::FtnAddress;
+use crate::server::encoding::{decode_from_client, encode_for_client, CharacterEncoding};
+
+/// Kludge line prefix.
+const KLUDGE: char = '\u{1}';
+
+/// Maximum length of SEEN-BY and PATH lines.
+const MAX_LINE: usize = 79;
+
+/// Product identifier used in the PID kludge and tear line.
+const PRODUCT: &str = "HOBBS";
+
+/// Parse a character set name from the configuration.
+///
+/// PETSCII is not used on FTN networks and is rejected.
+pub fn parse_charset(name: &str) -> Option<CharacterEncoding> {
+    match name.parse::<CharacterEncoding>() {
+        Ok(CharacterEncoding::Petscii) | Err(_) => None,
+        Ok(encoding) => Some(encoding),
+    }
+}
+
+/// CHRS kludge value for a character set.
+fn chrs_name(encoding: CharacterEncoding) -> &'static str {
+    match encoding {
+        CharacterEncoding::Utf8 => "UTF-8 4",
+        CharacterEncoding::ShiftJIS => "SHIFT_JIS 2",
+        CharacterEncoding::Cp437 | CharacterEncoding::Petscii => "CP437 2",
+    }
+}
+
+/// Character set named by a CHRS kludge value, if known.
+fn chrs_encoding(value: &str) -> Option<CharacterEncoding> {
+    let name = value.split_whitespace().next()?.to_ascii_uppercase();
+    match name.as_str() {
+        "UTF-8" | "UTF8" => Some(CharacterEncoding::Utf8),
+        "SHIFT_JIS" | "SHIFT-JIS" | "SJIS" => Some(CharacterEncoding::ShiftJIS),
+        "CP437" | "IBMPC" | "IBM437" => Some(CharacterEncoding::Cp437),
+        _ => None,
+    }
+}
+
+/// Parse a SEEN-BY or PATH list, where a bare number reuses the previous net.
+fn parse_net_nodes(text: &str, list: &mut Vec<(u16, u16)>) {
+    let mut net = list.last().map(|&(net, _)| net);
+    for item in text.split_whitespace() {
+        let (item_net, node) = match item.split_once('/') {
+            Some((n, node)) => (n.parse().ok(), node),
+            None => (net, item),
+        };
+        // Points and zones are not part of 2D addresses
+        let node = node.split('.').next().unwrap_or_default();
+        if let (Some(item_net), Ok(node)) = (item_net, node.parse()) {
+            net = Some(item_net);
+            list.push((item_net, node));
+        }
+    }
+}
+
+/// Format a SEEN-BY or PATH list, wrapping lines and compressing nets.
+fn format_net_nodes(prefix: &str, list: &[(u16, u16)]) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::from(prefix);
+    let mut net = None;
+    for &(item_net, node) in list {
+        let item = if net == Some(item_net) {
+            node.to_string()
+        } else {
+            format!("{}/{}", item_net, node)
+        };
+        if line.len() + 1 + item.len() > MAX_LINE && line.len() > prefix.len() {
+            // A new line always starts with a full net/node
+            lines.push(std::mem::replace(&mut line, String::from(prefix)));
+            line.push_str(&format!(" {}/{}", item_net, node));
+        } else {
+            line.push(' ');
+            line.push_str(&item);
+        }
+        net = Some(item_net);
+    }
+    if line.len() > prefix.len() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// An echomail message with its text decoded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EchoMessage {
+    /// Echo tag (upper case), or None for netmail.
+    pub area: Option<String>,
+    /// Sender name.
+    pub from: String,
+    /// Recipient name.
+    pub to: String,
+    /// Subject.
+    pub subject: String,
+    /// Date and time text from the packed message.
+    pub datetime: String,
+    /// MSGID kludge value.
+    pub msgid: Option<String>,
+    /// REPLY kludge value.
+    pub reply: Option<String>,
+    /// Address from the origin line.
+    pub origin: Option<FtnAddress>,
+    /// Message body without kludges, tear line, origin, SEEN-BY or PATH.
+    pub body: String,
+    /// SEEN-BY entries.
+    pub seen_by: Vec<(u16, u16)>,
+    /// PATH entries.
+    pub path: Vec<(u16, u16)>,
+}
+
+impl EchoMessage {
+    /// Decode a packed message.
+    ///
+    /// The CHRS kludge selects the character set; `default` is used when it
+    /// is missing or unknown.
+    pub fn parse(packed: &PackedMessage, default: CharacterEncoding) -> Self {
+        // Kludges are ASCII, so the raw lines can be scanned before decoding
+        let encoding = packed
+            .text
+            .split(|&b| b == b'\r')
+            .find_map(|line| line.strip_prefix(b"\x01CHRS:"))
+            .and_then(|value| chrs_encoding(&String::from_utf8_lossy(value)))
+            .unwrap_or(default);
+        let decode = |bytes: &[u8]| decode_from_client(bytes, encoding);
+
+        let mut message = EchoMessage {
+            from: decode(&packed.from),
+            to: decode(&packed.to),
+            subject: decode(&packed.subject),
+            datetime: packed.datetime.clone(),
+            ..Default::default()
+        };
+
+        let text = decode(&packed.text).replace('\n', "");
+        let mut lines: Vec<&str> = text.split('\r').collect();
+        if let Some(area) = lines.first().and_then(|l| l.strip_prefix("AREA:")) {
+            message.area = Some(area.trim().to_ascii_uppercase());
+            lines.remove(0);
+        }
+
+        let mut body = Vec::new();
+        for line in lines {
+            if let Some(kludge) = line.strip_prefix(KLUDGE) {
+                let (name, value) = kludge.split_once(':').unwrap_or((kludge, ""));
+                let value = value.trim();
+                match name {
+                    "MSGID" => message.msgid = Some(value.to_string()),
+                    "REPLY" => message.reply = Some(value.to_string()),
+                    "PATH" => parse_net_nodes(value, &mut message.path),
+                    _ => {}
+                }
+            } else if let Some(value) = line.strip_prefix("SEEN-BY:") {
+                parse_net_nodes(value, &mut message.seen_by);
+            } else {
+                body.push(line);
+            }
+        }
+
+        // The origin line ends the body, preceded by an optional tear line
+        if let Some(index) = body.iter().rposition(|l| l.starts_with(" * Origin:")) {
+            message.origin = body[index]
+                .rsplit_once('(')
+                .and_then(|(_, addr)| addr.trim_end().trim_end_matches(')').parse().ok());
+            body.truncate(index);
+            if body.last().is_some_and(|l| *l == "---" || l.starts_with("--- ")) {
+                body.pop();
+            }
+        }
+        while body.last().is_some_and(|l| l.trim().is_empty()) {
+            body.pop();
+        }
+        message.body = body.join("\n");
+
+        // Without an origin line the MSGID names the originating node
+        if message.origin.is_none() {
+            message.origin = message
+                .msgid
+                .as_deref()
+                .and_then(|id| id.split_whitespace().next())
+                .and_then(|addr| addr.parse().ok());
+        }
+        message
+    }
+
+    /// Encode the message for a packet sent from `orig` to `dest`.
+    ///
+    /// `origin_text` is the origin line text; SEEN-BY lists both nodes and
+    /// PATH lists `orig`, after any entries the message already carries.
+    pub fn to_packed(
+        &self,
+        orig: &FtnAddress,
+        dest: &FtnAddress,
+        origin_text: &str,
+        encoding: CharacterEncoding,
+    ) -> PackedMessage {
+        let mut text = String::new();
+        if let Some(area) = &self.area {
+            text.push_str(&format!("AREA:{}\r", area));
+        }
+        if let Some(msgid) = &self.msgid {
+            text.push_str(&format!("{}MSGID: {}\r", KLUDGE, msgid));
+        }
+        if let Some(reply) = &self.reply {
+            text.push_str(&format!("{}REPLY: {}\r", KLUDGE, reply));
+        }
+        text.push_str(&format!("{}CHRS: {}\r", KLUDGE, chrs_name(encoding)));
+        text.push_str(&format!(
+            "{}PID: {} {}\r",
+            KLUDGE,
+            PRODUCT,
+            env!("CARGO_PKG_VERSION")
+        ));
+        for line in self.body.lines() {
+            text.push_str(line);
+            text.push('\r');
+        }
+        text.push_str(&format!("\r--- {}\r", PRODUCT));
+
+        let addr = format!(" ({})", orig);
+        let max_origin = MAX_LINE - " * Origin: ".len() - addr.len();
+        let origin: String = origin_text.chars().take(max_origin).collect();
+        text.push_str(&format!(" * Origin: {}{}\r", origin, addr));
+
+        let mut seen_by = self.seen_by.clone();
+        for node in [orig.net_node(), dest.net_node()] {
+            if !seen_by.contains(&node) {
+                seen_by.push(node);
+            }
+        }
+        seen_by.sort_unstable();
+        for line in format_net_nodes("SEEN-BY:", &seen_by) {
+            text.push_str(&line);
+            text.push('\r');
+        }
+        let mut path = self.path.clone();
+        if path.last() != Some(&orig.net_node()) {
+            path.push(orig.net_node());
+        }
+        for line in format_net_nodes(&format!("{}PATH:", KLUDGE), &path) {
+            text.push_str(&line);
+            text.push('\r');
+        }
+
+        PackedMessage {
+            orig_node: orig.node,
+            dest_node: dest.node,
+            orig_net: orig.net,
+            dest_net: dest.net,
+            datetime: self.datetime.clone(),
+            to: encode_for_client(&self.to, encoding),
+            from: encode_for_client(&self.from, encoding),
+            subject: encode_for_client(&self.subject, encoding),
+            text: encode_for_client(&text, encoding),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(s: &str) -> FtnAddress {
+        s.parse().unwrap()
+    }
+
+    fn sample() -> EchoMessage {
+        EchoMessage {
+            area: Some("HOBBS.TEST".to_string()),
+            from: "太郎".to_string(),
+            to: "All".to_string(),
+            subject: "こんにちは".to_string(),
+            datetime: "05 Mar 24  12:34:56".to_string(),
+            msgid: Some("21:1/100 0000002a".to_string()),
+            reply: Some("21:1/200 00000010".to_string()),
+            body: "一行目\n\n三行目".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_charset() {
+        assert_eq!(parse_charset("sjis"), Some(CharacterEncoding::ShiftJIS));
+        assert_eq!(parse_charset("utf-8"), Some(CharacterEncoding::Utf8));
+        assert_eq!(parse_charset("cp437"), Some(CharacterEncoding::Cp437));
+        assert_eq!(parse_charset("petscii"), None);
+        assert_eq!(parse_charset("latin1"), None);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let orig = addr("21:1/100");
+        let dest = addr("21:1/1");
+        let packed = sample().to_packed(&orig, &dest, "Test BBS", CharacterEncoding::ShiftJIS);
+        let text = String::from_utf8_lossy(&packed.text).into_owned();
+        assert!(text.starts_with("AREA:HOBBS.TEST\r\x01MSGID: 21:1/100 0000002a\r"));
+        assert!(text.contains("\x01CHRS: SHIFT_JIS 2\r"));
+        assert!(text.contains(" * Origin: Test BBS (21:1/100)\r"));
+        assert!(text.contains("SEEN-BY: 1/1 100\r"));
+        assert!(text.ends_with("\x01PATH: 1/100\r"));
+
+        // The CHRS kludge overrides the default character set
+        let parsed = EchoMessage::parse(&packed, CharacterEncoding::Cp437);
+        assert_eq!(parsed.area.as_deref(), Some("HOBBS.TEST"));
+        assert_eq!(parsed.from, "太郎");
+        assert_eq!(parsed.subject, "こんにちは");
+        assert_eq!(parsed.msgid, sample().msgid);
+        assert_eq!(parsed.reply, sample().reply);
+        assert_eq!(parsed.body, "一行目\n\n三行目");
+        assert_eq!(parsed.origin, Some(orig));
+        assert_eq!(parsed.seen_by, vec![(1, 1), (1, 100)]);
+        assert_eq!(parsed.path, vec![(1, 100)]);
+    }
+
+    #[test]
+    fn test_parse_without_chrs_uses_default() {
+        let mut packed = PackedMessage {
+            from: b"Joe".to_vec(),
+            text: b"AREA:test\rCaf\x82\r--- \r * Origin: Far away\r".to_vec(),
+            ..Default::default()
+        };
+        packed.text.extend_from_slice(b"\x01MSGID: 2:5020/1 1234abcd\r");
+        let parsed = EchoMessage::parse(&packed, CharacterEncoding::Cp437);
+        assert_eq!(parsed.area.as_deref(), Some("TEST"));
+        assert_eq!(parsed.body, "Café");
+        // No address in the origin line, so the MSGID address is used
+        assert_eq!(parsed.origin, Some(addr("2:5020/1")));
+    }
+
+    #[test]
+    fn test_tear_line_only_stripped_before_origin() {
+        let packed = PackedMessage {
+            text: b"AREA:TEST\rabove\r---\rbelow\r".to_vec(),
+            ..Default::default()
+        };
+        let parsed = EchoMessage::parse(&packed, CharacterEncoding::Utf8);
+        assert_eq!(parsed.body, "above\n---\nbelow");
+        assert_eq!(parsed.origin, None);
+    }
+
+    #[test]
+    fn test_net_node_lists() {
+        let mut list = Vec::new();
+        parse_net_nodes("1/1 2 3 5020/100.1 200", &mut list);
+        parse_net_nodes("4", &mut list);
+        assert_eq!(
+            list,
+            vec![(1, 1), (1, 2), (1, 3), (5020, 100), (5020, 200), (5020, 4)]
+        );
+
+        let lines = format_net_nodes("SEEN-BY:", &[(1, 1), (1, 2), (5020, 100)]);
+        assert_eq!(lines, vec!["SEEN-BY: 1/1 2 5020/100"]);
+
+        let many: Vec<(u16, u16)> = (1..=40).map(|n| (1, n)).collect();
+        let lines = format_net_nodes("SEEN-BY:", &many);
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|l| l.len() <= MAX_LINE));
+        assert!(lines[1].starts_with("SEEN-BY: 1/"));
+        let mut parsed = Vec::new();
+        for line in &lines {
+            parse_net_nodes(line.strip_prefix("SEEN-BY:").unwrap(), &mut parsed);
+        }
+        assert_eq!(parsed, many);
+    }
+}
@@ -0,0 +1,22 @@
+//! FTN echomail module for HOBBS.
+//!
+//! Links boards to FidoNet-style echo areas. New local posts are scanned
+//! into Type-2+ packets in an outbound directory and inbound packets are
+//! tossed into posts. Transferring packets is left to an external mailer.
+
+pub mod address;
+pub mod message;
+pub mod packet;
+pub mod repository;
+pub mod service;
+pub mod updater;
+
+pub use address::FtnAddress;
+pub use message::{parse_charset, EchoMessage};
+pub use packet::{
+    format_ftn_datetime, read_packet, write_packet, PackedMessage, Packet, PacketHeader,
+    FTN_DATE_FORMAT, PACKET_HEADER_SIZE,
+};
+pub use repository::EchomailRepository;
+pub use service::{EchomailOptions, EchomailService, ScanResult, TossResult};
+pub use updater::{start_echomail_with_config, EchomailUpdater};
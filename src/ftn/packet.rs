@@ -0,0 +1,402 @@
+//! Type-2+ (FSC-0039) packet format.
+//!
+//! A packet is a 58-byte header followed by packed messages and a
+//! terminating zero word. All words are little-endian. This module only
+//! deals with bytes; message text is interpreted by [`super::EchoMessage`].
+
+use chrono::{Datelike, NaiveDateTime, Timelike};
+
+use super::FtnAddress;
+use crate::{HobbsError, Result};
+
+/// Size of the packet header in bytes.
+pub const PACKET_HEADER_SIZE: usize = 58;
+
+/// Packet type stored in the header.
+const PACKET_TYPE: u16 = 2;
+
+/// Type marker at the start of every packed message.
+const MESSAGE_TYPE: u16 = 2;
+
+/// Capability word announcing Type-2+ support.
+const CAPABILITY_WORD: u16 = 0x0001;
+
+/// Product code for unregistered software.
+const PRODUCT_CODE: u8 = 0xFE;
+
+/// Date format of packed message headers.
+pub const FTN_DATE_FORMAT: &str = "%d %b %y  %H:%M:%S";
+
+/// Maximum lengths of the NUL-terminated header fields (excluding NUL).
+const MAX_DATETIME: usize = 19;
+const MAX_TO_FROM: usize = 35;
+const MAX_SUBJECT: usize = 71;
+
+/// Packet header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PacketHeader {
+    /// Originating address.
+    pub orig: FtnAddress,
+    /// Destination address.
+    pub dest: FtnAddress,
+    /// Creation time.
+    pub created: NaiveDateTime,
+    /// Packet password (up to 8 bytes).
+    pub password: String,
+}
+
+/// A packed message with its header fields still encoded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackedMessage {
+    /// Originating node.
+    pub orig_node: u16,
+    /// Destination node.
+    pub dest_node: u16,
+    /// Originating net.
+    pub orig_net: u16,
+    /// Destination net.
+    pub dest_net: u16,
+    /// Message attribute flags.
+    pub attribute: u16,
+    /// Message cost.
+    pub cost: u16,
+    /// Date and time text (`DD Mon YY  HH:MM:SS`).
+    pub datetime: String,
+    /// Recipient name.
+    pub to: Vec<u8>,
+    /// Sender name.
+    pub from: Vec<u8>,
+    /// Subject.
+    pub subject: Vec<u8>,
+    /// Message text, lines separated by CR.
+    pub text: Vec<u8>,
+}
+
+/// A parsed packet.
+#[derive(Debug, Clone)]
+pub struct Packet {
+    /// Packet header.
+    pub header: PacketHeader,
+    /// Messages in the packet.
+    pub messages: Vec<PackedMessage>,
+}
+
+/// Format a timestamp the way packed messages store it.
+pub fn format_ftn_datetime(datetime: &NaiveDateTime) -> String {
+    datetime.format(FTN_DATE_FORMAT).to_string()
+}
+
+/// Serialize a packet.
+pub fn write_packet(header: &PacketHeader, messages: &[PackedMessage]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(PACKET_HEADER_SIZE);
+    let word = |data: &mut Vec<u8>, value: u16| data.extend_from_slice(&value.to_le_bytes());
+    let created = &header.created;
+
+    word(&mut data, header.orig.node);
+    word(&mut data, header.dest.node);
+    word(&mut data, created.year() as u16);
+    word(&mut data, created.month0() as u16);
+    word(&mut data, created.day() as u16);
+    word(&mut data, created.hour() as u16);
+    word(&mut data, created.minute() as u16);
+    word(&mut data, created.second() as u16);
+    word(&mut data, 0); // baud
+    word(&mut data, PACKET_TYPE);
+    // A point originates from net 0xFFFF with its real net in auxNet
+    let orig_net = if header.orig.point != 0 {
+        0xFFFF
+    } else {
+        header.orig.net
+    };
+    word(&mut data, orig_net);
+    word(&mut data, header.dest.net);
+    data.push(PRODUCT_CODE);
+    data.push(0); // revision major
+    let mut password = [0u8; 8];
+    for (slot, byte) in password.iter_mut().zip(header.password.bytes()) {
+        *slot = byte;
+    }
+    data.extend_from_slice(&password);
+    word(&mut data, header.orig.zone);
+    word(&mut data, header.dest.zone);
+    let aux_net = if header.orig.point != 0 {
+        header.orig.net
+    } else {
+        0
+    };
+    word(&mut data, aux_net);
+    word(&mut data, CAPABILITY_WORD.swap_bytes());
+    data.push(0); // product code high
+    data.push(0); // revision minor
+    word(&mut data, CAPABILITY_WORD);
+    word(&mut data, header.orig.zone);
+    word(&mut data, header.dest.zone);
+    word(&mut data, header.orig.point);
+    word(&mut data, header.dest.point);
+    data.extend_from_slice(&[0; 4]); // product data
+    debug_assert_eq!(data.len(), PACKET_HEADER_SIZE);
+
+    for message in messages {
+        word(&mut data, MESSAGE_TYPE);
+        word(&mut data, message.orig_node);
+        word(&mut data, message.dest_node);
+        word(&mut data, message.orig_net);
+        word(&mut data, message.dest_net);
+        word(&mut data, message.attribute);
+        word(&mut data, message.cost);
+        let mut datetime = message.datetime.as_bytes().to_vec();
+        datetime.truncate(MAX_DATETIME);
+        datetime.resize(MAX_DATETIME + 1, 0);
+        data.extend_from_slice(&datetime);
+        push_cstring(&mut data, &message.to, MAX_TO_FROM);
+        push_cstring(&mut data, &message.from, MAX_TO_FROM);
+        push_cstring(&mut data, &message.subject, MAX_SUBJECT);
+        data.extend(message.text.iter().copied().filter(|&b| b != 0));
+        data.push(0);
+    }
+    word(&mut data, 0);
+    data
+}
+
+/// Parse a packet.
+pub fn read_packet(data: &[u8]) -> Result<Packet> {
+    if data.len() < PACKET_HEADER_SIZE {
+        return Err(HobbsError::Ftn("packet header is truncated".to_string()));
+    }
+    let word = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]);
+
+    if word(18) != PACKET_TYPE {
+        return Err(HobbsError::Ftn(format!(
+            "unsupported packet type {}",
+            word(18)
+        )));
+    }
+
+    let created = chrono::NaiveDate::from_ymd_opt(
+        i32::from(word(4)),
+        u32::from(word(6)) + 1,
+        u32::from(word(8)),
+    )
+    .and_then(|date| {
+        date.and_hms_opt(
+            u32::from(word(10)),
+            u32::from(word(12)),
+            u32::from(word(14)),
+        )
+    })
+    .unwrap_or_default();
+
+    let password = String::from_utf8_lossy(&data[26..34])
+        .trim_end_matches('\0')
+        .to_string();
+
+    let mut orig = FtnAddress::new(word(34), word(20), word(0));
+    let mut dest = FtnAddress::new(word(36), word(22), word(2));
+    // Type-2+ fields are only valid when the capability word is confirmed
+    let capability = word(44);
+    if capability == word(40).swap_bytes() && capability & CAPABILITY_WORD != 0 {
+        orig.zone = word(46);
+        dest.zone = word(48);
+        orig.point = word(50);
+        dest.point = word(52);
+        if orig.net == 0xFFFF && orig.point != 0 {
+            orig.net = word(38);
+        }
+    }
+
+    let header = PacketHeader {
+        orig,
+        dest,
+        created,
+        password,
+    };
+
+    let mut messages = Vec::new();
+    let mut reader = Reader {
+        data,
+        pos: PACKET_HEADER_SIZE,
+    };
+    // Some tossers omit the terminating zero word
+    while let Some(kind) = reader.word() {
+        match kind {
+            0 => break,
+            MESSAGE_TYPE => messages.push(reader.message()?),
+            other => {
+                return Err(HobbsError::Ftn(format!(
+                    "unknown message type {} at offset {}",
+                    other,
+                    reader.pos - 2
+                )))
+            }
+        }
+    }
+
+    Ok(Packet { header, messages })
+}
+
+fn push_cstring(data: &mut Vec<u8>, value: &[u8], max: usize) {
+    data.extend(value.iter().copied().filter(|&b| b != 0).take(max));
+    data.push(0);
+}
+
+/// Cursor over packed message data.
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn word(&mut self) -> Option<u16> {
+        let bytes = self.data.get(self.pos..self.pos + 2)?;
+        self.pos += 2;
+        Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn require_word(&mut self) -> Result<u16> {
+        self.word()
+            .ok_or_else(|| HobbsError::Ftn("packed message header is truncated".to_string()))
+    }
+
+    /// Read a NUL-terminated string of at most `max` bytes.
+    fn cstring(&mut self, max: usize) -> Result<Vec<u8>> {
+        let rest = &self.data[self.pos..];
+        let len = rest
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or_else(|| HobbsError::Ftn("unterminated field in packed message".to_string()))?;
+        if len > max {
+            return Err(HobbsError::Ftn(format!(
+                "field too long in packed message at offset {}",
+                self.pos
+            )));
+        }
+        self.pos += len + 1;
+        Ok(rest[..len].to_vec())
+    }
+
+    fn message(&mut self) -> Result<PackedMessage> {
+        let orig_node = self.require_word()?;
+        let dest_node = self.require_word()?;
+        let orig_net = self.require_word()?;
+        let dest_net = self.require_word()?;
+        let attribute = self.require_word()?;
+        let cost = self.require_word()?;
+        let datetime = String::from_utf8_lossy(&self.cstring(MAX_DATETIME)?).into_owned();
+        let to = self.cstring(MAX_TO_FROM)?;
+        let from = self.cstring(MAX_TO_FROM)?;
+        let subject = self.cstring(MAX_SUBJECT)?;
+        let text = self.cstring(usize::MAX)?;
+
+        Ok(PackedMessage {
+            orig_node,
+            dest_node,
+            orig_net,
+            dest_net,
+            attribute,
+            cost,
+            datetime,
+            to,
+            from,
+            subject,
+            text,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header() -> PacketHeader {
+        PacketHeader {
+            orig: "21:1/100".parse().unwrap(),
+            dest: "21:1/1".parse().unwrap(),
+            created: NaiveDateTime::parse_from_str("2024-03-05 12:34:56", "%Y-%m-%d %H:%M:%S")
+                .unwrap(),
+            password: "SECRET".to_string(),
+        }
+    }
+
+    fn message() -> PackedMessage {
+        PackedMessage {
+            orig_node: 100,
+            dest_node: 1,
+            orig_net: 1,
+            dest_net: 1,
+            datetime: "05 Mar 24  12:34:56".to_string(),
+            to: b"All".to_vec(),
+            from: b"sysop".to_vec(),
+            subject: b"Hello".to_vec(),
+            text: b"AREA:TEST\rHello there\r".to_vec(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_header_layout() {
+        let data = write_packet(&header(), &[]);
+        assert_eq!(data.len(), PACKET_HEADER_SIZE + 2);
+        assert_eq!(&data[0..2], &100u16.to_le_bytes());
+        assert_eq!(&data[4..6], &2024u16.to_le_bytes());
+        assert_eq!(&data[6..8], &2u16.to_le_bytes()); // March, zero-based
+        assert_eq!(&data[18..20], &2u16.to_le_bytes());
+        assert_eq!(&data[26..34], b"SECRET\0\0");
+        assert_eq!(&data[40..42], &[0x00, 0x01]); // byte-swapped copy
+        assert_eq!(&data[44..46], &[0x01, 0x00]);
+        assert_eq!(&data[58..60], &[0, 0]);
+    }
+
+    #[test]
+    fn test_format_ftn_datetime() {
+        assert_eq!(format_ftn_datetime(&header().created), "05 Mar 24  12:34:56");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let data = write_packet(&header(), &[message(), message()]);
+        let packet = read_packet(&data).unwrap();
+        assert_eq!(packet.header, header());
+        assert_eq!(packet.messages, vec![message(), message()]);
+    }
+
+    #[test]
+    fn test_point_address_round_trip() {
+        let mut header = header();
+        header.orig = "21:1/100.5".parse().unwrap();
+        let data = write_packet(&header, &[]);
+        assert_eq!(&data[20..22], &0xFFFFu16.to_le_bytes());
+        assert_eq!(read_packet(&data).unwrap().header.orig, header.orig);
+    }
+
+    #[test]
+    fn test_long_fields_are_truncated() {
+        let mut long = message();
+        long.subject = vec![b'x'; 100];
+        let packet = read_packet(&write_packet(&header(), &[long])).unwrap();
+        assert_eq!(packet.messages[0].subject.len(), MAX_SUBJECT);
+    }
+
+    #[test]
+    fn test_missing_terminator_is_accepted() {
+        let mut data = write_packet(&header(), &[message()]);
+        data.truncate(data.len() - 2);
+        assert_eq!(read_packet(&data).unwrap().messages.len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_packets() {
+        assert!(read_packet(&[0; 10]).is_err());
+
+        let mut data = write_packet(&header(), &[message()]);
+        data[18] = 1;
+        assert!(read_packet(&data).is_err());
+
+        let mut data = write_packet(&header(), &[message()]);
+        data[PACKET_HEADER_SIZE] = 9;
+        assert!(read_packet(&data).is_err());
+
+        let mut data = write_packet(&header(), &[message()]);
+        data.truncate(PACKET_HEADER_SIZE + 30);
+        assert!(read_packet(&data).is_err());
+    }
+}
@@ -0,0 +1,159 @@
+//! Echomail repository for HOBBS.
+//!
+//! Stores per-area scan pointers and the MSGIDs of messages that have
+//! passed through the tosser or scanner.
+
+use crate::board::Post;
+use crate::db::DbPool;
+use crate::{HobbsError, Result};
+
+// SQL datetime function for current timestamp
+#[cfg(feature = "sqlite")]
+const SQL_NOW: &str = "datetime('now')";
+#[cfg(feature = "postgres")]
+const SQL_NOW: &str = "TO_CHAR(NOW(), 'YYYY-MM-DD HH24:MI:SS')";
+
+/// Repository for echomail bookkeeping.
+pub struct EchomailRepository<'a> {
+    pool: &'a DbPool,
+}
+
+impl<'a> EchomailRepository<'a> {
+    /// Create a new EchomailRepository with the given database pool.
+    pub fn new(pool: &'a DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Get the ID of the last post scanned out for an area.
+    ///
+    /// Returns None if the area has never been scanned.
+    pub async fn get_scan_pointer(&self, tag: &str) -> Result<Option<i64>> {
+        let pointer: Option<i64> =
+            sqlx::query_scalar("SELECT last_scanned_post_id FROM echo_areas WHERE tag = $1")
+                .bind(tag)
+                .fetch_optional(self.pool)
+                .await
+                .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(pointer)
+    }
+
+    /// Set the scan pointer for an area.
+    pub async fn set_scan_pointer(&self, tag: &str, board_id: i64, post_id: i64) -> Result<()> {
+        let query = format!(
+            "INSERT INTO echo_areas (tag, board_id, last_scanned_post_id, updated_at)
+             VALUES ($1, $2, $3, {})
+             ON CONFLICT(tag) DO UPDATE SET
+                 board_id = excluded.board_id,
+                 last_scanned_post_id = excluded.last_scanned_post_id,
+                 updated_at = {}",
+            SQL_NOW, SQL_NOW
+        );
+        sqlx::query(&query)
+            .bind(tag)
+            .bind(board_id)
+            .bind(post_id)
+            .execute(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Get the ID of the newest post in a board (0 if the board is empty).
+    pub async fn latest_post_id(&self, board_id: i64) -> Result<i64> {
+        let id: Option<i64> = sqlx::query_scalar("SELECT MAX(id) FROM posts WHERE board_id = $1")
+            .bind(board_id)
+            .fetch_one(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(id.unwrap_or(0))
+    }
+
+    /// List local posts in a board newer than `after_id`, oldest first.
+    ///
+    /// Posts that arrived by echomail are excluded so they are not echoed back.
+    pub async fn list_posts_to_scan(&self, board_id: i64, after_id: i64) -> Result<Vec<Post>> {
+        let posts = sqlx::query_as::<_, Post>(
+            "SELECT id, board_id, thread_id, parent_id, author_id, title, body,
+                    created_at, edited_at, edited_by
+             FROM posts p
+             WHERE board_id = $1 AND id > $2
+               AND NOT EXISTS (SELECT 1 FROM echomail_messages m WHERE m.post_id = p.id)
+             ORDER BY id ASC",
+        )
+        .bind(board_id)
+        .bind(after_id)
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(posts)
+    }
+
+    /// Get the ID of the first post in a thread.
+    pub async fn first_post_id(&self, thread_id: i64) -> Result<Option<i64>> {
+        let id: Option<i64> = sqlx::query_scalar("SELECT MIN(id) FROM posts WHERE thread_id = $1")
+            .bind(thread_id)
+            .fetch_one(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(id)
+    }
+
+    /// Check whether a MSGID has been seen before.
+    pub async fn is_known(&self, msgid: &str) -> Result<bool> {
+        let count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM echomail_messages WHERE msgid = $1")
+                .bind(msgid)
+                .fetch_one(self.pool)
+                .await
+                .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(count > 0)
+    }
+
+    /// Record a MSGID and the post it belongs to.
+    ///
+    /// Recording an already known MSGID is a no-op.
+    pub async fn record(&self, msgid: &str, board_id: i64, post_id: Option<i64>) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO echomail_messages (msgid, board_id, post_id) VALUES ($1, $2, $3)
+             ON CONFLICT (msgid) DO NOTHING",
+        )
+        .bind(msgid)
+        .bind(board_id)
+        .bind(post_id)
+        .execute(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Find the local post for a MSGID.
+    pub async fn find_post(&self, msgid: &str) -> Result<Option<i64>> {
+        let post_id: Option<Option<i64>> =
+            sqlx::query_scalar("SELECT post_id FROM echomail_messages WHERE msgid = $1")
+                .bind(msgid)
+                .fetch_optional(self.pool)
+                .await
+                .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(post_id.flatten())
+    }
+
+    /// Find the MSGID of a local post.
+    pub async fn find_msgid(&self, post_id: i64) -> Result<Option<String>> {
+        let msgid: Option<String> =
+            sqlx::query_scalar("SELECT msgid FROM echomail_messages WHERE post_id = $1")
+                .bind(post_id)
+                .fetch_optional(self.pool)
+                .await
+                .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(msgid)
+    }
+}
@@ -0,0 +1,731 @@
+//! Echomail service for HOBBS.
+//!
+//! The scanner exports new local posts in linked boards as a packet in the
+//! outbound directory; the tosser imports packets from the inbound
+//! directory as posts. Moving packets to and from the uplink is left to an
+//! external mailer.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use chrono::{NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use sha2::{Digest, Sha256};
+use tracing::warn;
+
+use super::message::{parse_charset, EchoMessage};
+use super::packet::{read_packet, write_packet, Packet, PacketHeader, FTN_DATE_FORMAT};
+use super::repository::EchomailRepository;
+use super::FtnAddress;
+use crate::board::{
+    BoardRepository, BoardService, BoardType, Post, PostRepository, ThreadRepository,
+    MAX_BODY_LENGTH, MAX_TITLE_LENGTH,
+};
+use crate::config::Config;
+use crate::datetime::format_datetime;
+use crate::db::{Database, Role, User, UserRepository};
+use crate::server::encoding::CharacterEncoding;
+use crate::{HobbsError, Result};
+
+/// Subject used when an incoming message has none.
+const NO_SUBJECT: &str = "(no subject)";
+
+/// Settings for scanning and tossing.
+#[derive(Debug, Clone)]
+pub struct EchomailOptions {
+    /// Our address.
+    pub address: FtnAddress,
+    /// Uplink address outbound packets are addressed to.
+    pub uplink: FtnAddress,
+    /// Packet password (empty for none).
+    pub password: String,
+    /// Inbound packet directory.
+    pub inbound: PathBuf,
+    /// Outbound packet directory.
+    pub outbound: PathBuf,
+    /// Character set for outgoing messages and incoming ones without CHRS.
+    pub charset: CharacterEncoding,
+    /// Origin line text.
+    pub origin: String,
+    /// Timezone for message dates.
+    pub timezone: String,
+    /// Upper-case echo tag to board ID mapping.
+    pub areas: BTreeMap<String, i64>,
+}
+
+impl EchomailOptions {
+    /// Build echomail options from the application configuration.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let ftn = &config.ftn;
+        let origin = if ftn.origin.is_empty() {
+            config.bbs.name.clone()
+        } else {
+            ftn.origin.clone()
+        };
+        Ok(Self {
+            address: ftn.address.parse()?,
+            uplink: ftn.uplink.parse()?,
+            password: ftn.password.clone(),
+            inbound: PathBuf::from(&ftn.inbound),
+            outbound: PathBuf::from(&ftn.outbound),
+            charset: parse_charset(&ftn.charset).ok_or_else(|| {
+                HobbsError::Validation(format!("unsupported FTN charset: {}", ftn.charset))
+            })?,
+            origin,
+            timezone: config.server.timezone.clone(),
+            areas: ftn
+                .areas
+                .iter()
+                .map(|(tag, &board_id)| (tag.to_ascii_uppercase(), board_id))
+                .collect(),
+        })
+    }
+}
+
+/// Result of a scan run.
+#[derive(Debug, Clone, Default)]
+pub struct ScanResult {
+    /// Number of messages exported.
+    pub exported: usize,
+    /// Path of the packet written, if any.
+    pub packet: Option<PathBuf>,
+}
+
+/// Result of a toss run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TossResult {
+    /// Number of packets processed.
+    pub packets: usize,
+    /// Number of messages imported as posts.
+    pub imported: usize,
+    /// Number of duplicate messages dropped.
+    pub dupes: usize,
+    /// Number of messages for areas that are not linked.
+    pub skipped: usize,
+    /// Number of messages that could not be posted.
+    pub failed: usize,
+    /// Number of unreadable packets renamed to `.bad`.
+    pub bad_packets: usize,
+}
+
+/// What happened to one tossed message.
+enum Tossed {
+    Imported,
+    Dupe,
+    Skipped,
+}
+
+/// Service for echomail scanning and tossing.
+pub struct EchomailService<'a> {
+    db: &'a Database,
+    options: &'a EchomailOptions,
+}
+
+impl<'a> EchomailService<'a> {
+    /// Create a new EchomailService.
+    pub fn new(db: &'a Database, options: &'a EchomailOptions) -> Self {
+        Self { db, options }
+    }
+
+    /// Export new local posts in linked boards into one outbound packet.
+    ///
+    /// An area that has never been scanned starts at the current end of its
+    /// board, so linking a board does not flood the network with old posts.
+    pub async fn scan(&self) -> Result<ScanResult> {
+        let repo = EchomailRepository::new(self.db.pool());
+        let board_repo = BoardRepository::new(self.db.pool());
+        let mut names = HashMap::new();
+        let mut messages = Vec::new();
+        let mut records = Vec::new();
+        let mut pointers = Vec::new();
+
+        for (tag, &board_id) in &self.options.areas {
+            if board_repo.get_by_id(board_id).await?.is_none() {
+                warn!("Echo area {} is linked to missing board {}", tag, board_id);
+                continue;
+            }
+            let Some(pointer) = repo.get_scan_pointer(tag).await? else {
+                let latest = repo.latest_post_id(board_id).await?;
+                repo.set_scan_pointer(tag, board_id, latest).await?;
+                continue;
+            };
+
+            let posts = repo.list_posts_to_scan(board_id, pointer).await?;
+            let Some(last) = posts.last() else { continue };
+            pointers.push((tag, board_id, last.id));
+
+            for post in &posts {
+                let message = self.export_message(tag, post, &mut names).await?;
+                let msgid = message.msgid.clone().unwrap_or_default();
+                messages.push(message.to_packed(
+                    &self.options.address,
+                    &self.options.uplink,
+                    &self.options.origin,
+                    self.options.charset,
+                ));
+                records.push((msgid, board_id, post.id));
+            }
+        }
+
+        let mut result = ScanResult::default();
+        if !messages.is_empty() {
+            let header = PacketHeader {
+                orig: self.options.address,
+                dest: self.options.uplink,
+                created: self.local_now(),
+                password: self.options.password.clone(),
+            };
+            let data = write_packet(&header, &messages);
+            result.packet = Some(write_outbound(&self.options.outbound, &data)?);
+            result.exported = messages.len();
+        }
+
+        // Only record messages once the packet is safely on disk
+        for (msgid, board_id, post_id) in &records {
+            repo.record(msgid, *board_id, Some(*post_id)).await?;
+        }
+        for (tag, board_id, post_id) in pointers {
+            repo.set_scan_pointer(tag, board_id, post_id).await?;
+        }
+        Ok(result)
+    }
+
+    /// Import all packets in the inbound directory.
+    ///
+    /// Processed packets are deleted; packets that cannot be read or fail
+    /// the password check are renamed to `.bad` and left for the SysOp.
+    pub async fn toss(&self) -> Result<TossResult> {
+        let mut result = TossResult::default();
+        let mut paths = match std::fs::read_dir(&self.options.inbound) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| is_packet(path))
+                .collect::<Vec<_>>(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(result),
+            Err(e) => return Err(e.into()),
+        };
+        paths.sort();
+
+        let mut sysop = None;
+        for path in paths {
+            let data = std::fs::read(&path)?;
+            let packet = match read_packet(&data).and_then(|p| self.check_password(p)) {
+                Ok(packet) => packet,
+                Err(e) => {
+                    warn!("Bad packet {}: {}", path.display(), e);
+                    std::fs::rename(&path, path.with_extension("bad"))?;
+                    result.bad_packets += 1;
+                    continue;
+                }
+            };
+
+            if sysop.is_none() && !packet.messages.is_empty() {
+                sysop = Some(self.sysop().await?);
+            }
+            for packed in &packet.messages {
+                let message = EchoMessage::parse(packed, self.options.charset);
+                let Some(sysop) = &sysop else { break };
+                match self.toss_message(&message, sysop).await {
+                    Ok(Tossed::Imported) => result.imported += 1,
+                    Ok(Tossed::Dupe) => result.dupes += 1,
+                    Ok(Tossed::Skipped) => result.skipped += 1,
+                    Err(e) => {
+                        warn!(
+                            "Failed to toss message {:?} in {:?}: {}",
+                            message.msgid, message.area, e
+                        );
+                        result.failed += 1;
+                    }
+                }
+            }
+
+            std::fs::remove_file(&path)?;
+            result.packets += 1;
+        }
+        Ok(result)
+    }
+
+    fn check_password(&self, packet: Packet) -> Result<Packet> {
+        let expected = &self.options.password;
+        if !expected.is_empty() && !packet.header.password.eq_ignore_ascii_case(expected) {
+            return Err(HobbsError::Ftn(format!(
+                "wrong packet password from {}",
+                packet.header.orig
+            )));
+        }
+        Ok(packet)
+    }
+
+    /// Tossed messages are posted by the SysOp with the sender in the body.
+    async fn sysop(&self) -> Result<User> {
+        UserRepository::new(self.db.pool())
+            .list_by_role(Role::SysOp)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| HobbsError::NotFound("SysOp user".to_string()))
+    }
+
+    async fn toss_message(&self, message: &EchoMessage, sysop: &User) -> Result<Tossed> {
+        let Some(&board_id) = message.area.as_ref().and_then(|a| self.options.areas.get(a))
+        else {
+            return Ok(Tossed::Skipped);
+        };
+
+        let repo = EchomailRepository::new(self.db.pool());
+        let key = message.msgid.clone().unwrap_or_else(|| dupe_key(message));
+        if message.path.contains(&self.options.address.net_node()) || repo.is_known(&key).await?
+        {
+            return Ok(Tossed::Dupe);
+        }
+
+        let post = self.import(board_id, message, sysop).await?;
+        repo.record(&key, board_id, Some(post.id)).await?;
+        Ok(Tossed::Imported)
+    }
+
+    async fn import(&self, board_id: i64, message: &EchoMessage, sysop: &User) -> Result<Post> {
+        let service = BoardService::new(self.db);
+        let sender = match message.origin {
+            Some(addr) => format!("{} ({})", message.from, addr),
+            None => message.from.clone(),
+        };
+        let body = truncate(&format!("From: {}\n\n{}", sender, message.body), MAX_BODY_LENGTH);
+        let subject = match message.subject.trim() {
+            "" => NO_SUBJECT.to_string(),
+            subject => truncate(subject, MAX_TITLE_LENGTH),
+        };
+
+        let board = service.get_board(board_id, sysop.role).await?;
+        if board.board_type == BoardType::Flat {
+            return service
+                .create_flat_post(board_id, sysop.id, subject, body, sysop.role)
+                .await;
+        }
+
+        // Follow the REPLY kludge to the parent post when we have it
+        if let Some(reply) = &message.reply {
+            if let Some(post_id) = EchomailRepository::new(self.db.pool()).find_post(reply).await? {
+                let parent = PostRepository::new(self.db.pool())
+                    .get_by_id(post_id)
+                    .await?
+                    .filter(|p| p.board_id == board_id);
+                if let Some(thread_id) = parent.as_ref().and_then(|p| p.thread_id) {
+                    return service
+                        .create_reply(thread_id, Some(post_id), sysop.id, body, sysop.role)
+                        .await;
+                }
+            }
+        }
+
+        // Otherwise a "Re:" subject joins the thread with the same title
+        let base = strip_reply_prefix(&subject);
+        if base.len() != subject.len() {
+            let threads = ThreadRepository::new(self.db.pool())
+                .list_by_board(board_id)
+                .await?;
+            if let Some(thread) = threads.iter().find(|t| t.title == base) {
+                return service
+                    .create_thread_post(thread.id, sysop.id, body, sysop.role)
+                    .await;
+            }
+        }
+
+        let thread = service
+            .create_thread(board_id, subject, sysop.id, sysop.role)
+            .await?;
+        service
+            .create_thread_post(thread.id, sysop.id, body, sysop.role)
+            .await
+    }
+
+    async fn export_message(
+        &self,
+        tag: &str,
+        post: &Post,
+        names: &mut HashMap<i64, String>,
+    ) -> Result<EchoMessage> {
+        let repo = EchomailRepository::new(self.db.pool());
+        let subject = match post.thread_id {
+            Some(thread_id) => {
+                let title = ThreadRepository::new(self.db.pool())
+                    .get_by_id(thread_id)
+                    .await?
+                    .map(|t| t.title)
+                    .unwrap_or_default();
+                let first = repo.first_post_id(thread_id).await?;
+                if post.parent_id.is_some() || first != Some(post.id) {
+                    format!("Re: {}", title)
+                } else {
+                    title
+                }
+            }
+            None => post.title.clone().unwrap_or_default(),
+        };
+
+        let (to, reply) = match post.parent_id {
+            Some(parent_id) => {
+                let parent = PostRepository::new(self.db.pool()).get_by_id(parent_id).await?;
+                let to = match parent {
+                    Some(parent) => self.username(parent.author_id, names).await?,
+                    None => "All".to_string(),
+                };
+                // Local parents scanned in this same run are not recorded yet
+                let reply = match repo.find_msgid(parent_id).await? {
+                    Some(msgid) => msgid,
+                    None => self.local_msgid(parent_id),
+                };
+                (to, Some(reply))
+            }
+            None => ("All".to_string(), None),
+        };
+
+        Ok(EchoMessage {
+            area: Some(tag.to_string()),
+            from: self.username(post.author_id, names).await?,
+            to,
+            subject,
+            datetime: format_datetime(&post.created_at, &self.options.timezone, FTN_DATE_FORMAT),
+            msgid: Some(self.local_msgid(post.id)),
+            reply,
+            body: post.body.clone(),
+            ..Default::default()
+        })
+    }
+
+    /// MSGID of a local post: our address and the post ID as the serial.
+    fn local_msgid(&self, post_id: i64) -> String {
+        format!("{} {:08x}", self.options.address, post_id)
+    }
+
+    async fn username(&self, user_id: i64, names: &mut HashMap<i64, String>) -> Result<String> {
+        if let Some(name) = names.get(&user_id) {
+            return Ok(name.clone());
+        }
+        let name = UserRepository::new(self.db.pool())
+            .get_by_id(user_id)
+            .await?
+            .map(|u| u.username)
+            .unwrap_or_else(|| "unknown".to_string());
+        names.insert(user_id, name.clone());
+        Ok(name)
+    }
+
+    fn local_now(&self) -> NaiveDateTime {
+        let tz: Tz = self.options.timezone.parse().unwrap_or(Tz::UTC);
+        Utc::now().with_timezone(&tz).naive_local()
+    }
+}
+
+/// Check whether a path looks like a packet (`*.pkt`, any case).
+fn is_packet(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("pkt"))
+}
+
+/// Write a packet to the outbound directory under a fresh name.
+///
+/// The packet is written to a temporary file first so the mailer never
+/// picks up a partial packet.
+fn write_outbound(dir: &Path, data: &[u8]) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let mut stamp = Utc::now().timestamp() as u32;
+    let path = loop {
+        let path = dir.join(format!("{:08x}.pkt", stamp));
+        if !path.exists() {
+            break path;
+        }
+        stamp = stamp.wrapping_add(1);
+    };
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, data)?;
+    std::fs::rename(&tmp, &path)?;
+    Ok(path)
+}
+
+/// Dupe key for messages without a MSGID.
+fn dupe_key(message: &EchoMessage) -> String {
+    let mut hasher = Sha256::new();
+    for part in [
+        message.area.as_deref().unwrap_or_default(),
+        &message.from,
+        &message.subject,
+        &message.datetime,
+        &message.body,
+    ] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    format!("NOMSGID:{:x}", hasher.finalize())
+}
+
+/// Strip any number of leading "Re:" prefixes.
+fn strip_reply_prefix(subject: &str) -> &str {
+    let mut text = subject.trim_start();
+    while text.len() >= 3 && text[..3].eq_ignore_ascii_case("re:") {
+        text = text[3..].trim_start();
+    }
+    text
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    text.chars().take(max_chars).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::NewBoard;
+    use crate::db::NewUser;
+    use crate::ftn::packet::PackedMessage;
+
+    async fn setup_db() -> Database {
+        Database::open_in_memory().await.unwrap()
+    }
+
+    async fn create_user(db: &Database, name: &str, role: Role) -> User {
+        UserRepository::new(db.pool())
+            .create(&NewUser::new(name, "hash", name).with_role(role))
+            .await
+            .unwrap()
+    }
+
+    fn options(dir: &Path, board_id: i64) -> EchomailOptions {
+        EchomailOptions {
+            address: "21:1/100".parse().unwrap(),
+            uplink: "21:1/1".parse().unwrap(),
+            password: "PW".to_string(),
+            inbound: dir.join("in"),
+            outbound: dir.join("out"),
+            charset: CharacterEncoding::ShiftJIS,
+            origin: "HOBBS Test".to_string(),
+            timezone: "Asia/Tokyo".to_string(),
+            areas: BTreeMap::from([("HOBBS.TEST".to_string(), board_id)]),
+        }
+    }
+
+    fn incoming(
+        msgid: Option<&str>,
+        reply: Option<&str>,
+        subject: &str,
+        body: &str,
+    ) -> EchoMessage {
+        EchoMessage {
+            area: Some("HOBBS.TEST".to_string()),
+            from: "Remote User".to_string(),
+            to: "All".to_string(),
+            subject: subject.to_string(),
+            datetime: "05 Mar 24  12:34:56".to_string(),
+            msgid: msgid.map(str::to_string),
+            reply: reply.map(str::to_string),
+            body: body.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Write an inbound packet as the uplink would send it.
+    fn drop_inbound(options: &EchomailOptions, name: &str, messages: &[EchoMessage]) {
+        let header = PacketHeader {
+            orig: options.uplink,
+            dest: options.address,
+            created: NaiveDateTime::default(),
+            password: options.password.clone(),
+        };
+        let packed: Vec<PackedMessage> = messages
+            .iter()
+            .map(|m| {
+                m.to_packed(&options.uplink, &options.address, "Remote", CharacterEncoding::Utf8)
+            })
+            .collect();
+        std::fs::create_dir_all(&options.inbound).unwrap();
+        std::fs::write(options.inbound.join(name), write_packet(&header, &packed)).unwrap();
+    }
+
+    #[test]
+    fn test_strip_reply_prefix() {
+        assert_eq!(strip_reply_prefix("Re: hello"), "hello");
+        assert_eq!(strip_reply_prefix("RE:Re: hello"), "hello");
+        assert_eq!(strip_reply_prefix("Regarding"), "Regarding");
+        assert_eq!(strip_reply_prefix("日本語"), "日本語");
+    }
+
+    #[tokio::test]
+    async fn test_scan_exports_new_posts() {
+        let db = setup_db().await;
+        let dir = tempfile::tempdir().unwrap();
+        let user = create_user(&db, "alice", Role::Member).await;
+        let board = BoardRepository::new(db.pool())
+            .create(&NewBoard::new("echo"))
+            .await
+            .unwrap();
+        let options = options(dir.path(), board.id);
+        let service = EchomailService::new(&db, &options);
+        let boards = BoardService::new(&db);
+
+        let thread = boards
+            .create_thread(board.id, "Old", user.id, user.role)
+            .await
+            .unwrap();
+        boards
+            .create_thread_post(thread.id, user.id, "before linking", user.role)
+            .await
+            .unwrap();
+
+        // The first scan only sets the pointer
+        let result = service.scan().await.unwrap();
+        assert_eq!(result.exported, 0);
+        assert!(result.packet.is_none());
+
+        let thread = boards
+            .create_thread(board.id, "挨拶", user.id, user.role)
+            .await
+            .unwrap();
+        let first = boards
+            .create_thread_post(thread.id, user.id, "こんにちは", user.role)
+            .await
+            .unwrap();
+        boards
+            .create_reply(thread.id, Some(first.id), user.id, "返信です", user.role)
+            .await
+            .unwrap();
+
+        let result = service.scan().await.unwrap();
+        assert_eq!(result.exported, 2);
+        let data = std::fs::read(result.packet.unwrap()).unwrap();
+        let packet = read_packet(&data).unwrap();
+        assert_eq!(packet.header.orig, options.address);
+        assert_eq!(packet.header.dest, options.uplink);
+        assert_eq!(packet.header.password, "PW");
+
+        let messages: Vec<EchoMessage> = packet
+            .messages
+            .iter()
+            .map(|m| EchoMessage::parse(m, CharacterEncoding::Utf8))
+            .collect();
+        assert_eq!(messages[0].area.as_deref(), Some("HOBBS.TEST"));
+        assert_eq!(messages[0].subject, "挨拶");
+        assert_eq!(messages[0].from, "alice");
+        assert_eq!(messages[0].body, "こんにちは");
+        assert_eq!(messages[1].subject, "Re: 挨拶");
+        assert_eq!(messages[1].to, "alice");
+        assert_eq!(messages[1].reply, messages[0].msgid);
+        assert_eq!(messages[1].origin, Some(options.address));
+
+        // Nothing new to send
+        let result = service.scan().await.unwrap();
+        assert_eq!(result.exported, 0);
+    }
+
+    #[tokio::test]
+    async fn test_toss_imports_and_detects_dupes() {
+        let db = setup_db().await;
+        let dir = tempfile::tempdir().unwrap();
+        let sysop = create_user(&db, "sysop", Role::SysOp).await;
+        let board = BoardRepository::new(db.pool())
+            .create(&NewBoard::new("echo"))
+            .await
+            .unwrap();
+        let options = options(dir.path(), board.id);
+        let service = EchomailService::new(&db, &options);
+        // Link the area before anything arrives
+        service.scan().await.unwrap();
+
+        let mut unlinked = incoming(Some("21:1/200 00000003"), None, "Other", "x");
+        unlinked.area = Some("OTHER.ECHO".to_string());
+        let mut looped = incoming(Some("21:1/200 00000004"), None, "Loop", "x");
+        looped.path = vec![(1, 100)];
+        drop_inbound(
+            &options,
+            "0001.PKT",
+            &[
+                incoming(Some("21:1/200 00000001"), None, "Hello", "first"),
+                incoming(
+                    Some("21:1/200 00000002"),
+                    Some("21:1/200 00000001"),
+                    "Re: Hello",
+                    "reply",
+                ),
+                incoming(None, None, "Re: Hello", "no msgid"),
+                incoming(Some("21:1/200 00000001"), None, "Hello", "dupe"),
+                unlinked,
+                looped,
+            ],
+        );
+
+        let result = service.toss().await.unwrap();
+        assert_eq!(
+            result,
+            TossResult {
+                packets: 1,
+                imported: 3,
+                dupes: 2,
+                skipped: 1,
+                ..Default::default()
+            }
+        );
+        assert!(!options.inbound.join("0001.PKT").exists());
+
+        let threads = ThreadRepository::new(db.pool())
+            .list_by_board(board.id)
+            .await
+            .unwrap();
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].title, "Hello");
+        assert_eq!(threads[0].author_id, sysop.id);
+
+        let posts = BoardService::new(&db)
+            .list_all_posts_in_thread(threads[0].id, Role::SysOp)
+            .await
+            .unwrap();
+        assert_eq!(posts.len(), 3);
+        let first = posts.iter().find(|p| p.body.ends_with("first")).unwrap();
+        assert!(first.body.starts_with("From: Remote User (21:1/1)\n\n"));
+        let reply = posts.iter().find(|p| p.body.ends_with("\nreply")).unwrap();
+        assert_eq!(reply.parent_id, Some(first.id));
+
+        // The same messages again are all dupes, including the one without MSGID
+        drop_inbound(
+            &options,
+            "0002.pkt",
+            &[incoming(None, None, "Re: Hello", "no msgid")],
+        );
+        let result = service.toss().await.unwrap();
+        assert_eq!(result.dupes, 1);
+
+        // Tossed posts are not echoed back by the scanner
+        let result = service.scan().await.unwrap();
+        assert_eq!(result.exported, 0);
+    }
+
+    #[tokio::test]
+    async fn test_toss_rejects_bad_packets() {
+        let db = setup_db().await;
+        let dir = tempfile::tempdir().unwrap();
+        create_user(&db, "sysop", Role::SysOp).await;
+        let board = BoardRepository::new(db.pool())
+            .create(&NewBoard::new("echo"))
+            .await
+            .unwrap();
+        let mut options = options(dir.path(), board.id);
+        drop_inbound(&options, "0001.pkt", &[incoming(None, None, "Hi", "x")]);
+        std::fs::write(options.inbound.join("0002.pkt"), b"garbage").unwrap();
+
+        options.password = "OTHER".to_string();
+        let result = EchomailService::new(&db, &options).toss().await.unwrap();
+        assert_eq!(result.bad_packets, 2);
+        assert_eq!(result.imported, 0);
+        assert!(options.inbound.join("0001.bad").exists());
+        assert!(options.inbound.join("0002.bad").exists());
+    }
+
+    #[tokio::test]
+    async fn test_toss_without_inbound_directory() {
+        let db = setup_db().await;
+        let dir = tempfile::tempdir().unwrap();
+        let options = options(dir.path(), 1);
+        let result = EchomailService::new(&db, &options).toss().await.unwrap();
+        assert_eq!(result, TossResult::default());
+    }
+}
@@ -0,0 +1,114 @@
+//! Echomail background task for HOBBS.
+//!
+//! Periodically tosses inbound packets and scans new posts out.
+
+use std::sync::Arc;
+
+use tokio::time::{interval, Duration};
+use tracing::{debug, error, info};
+
+use super::service::{EchomailOptions, EchomailService};
+use crate::config::Config;
+use crate::db::Database;
+
+/// Echomail background updater.
+pub struct EchomailUpdater {
+    db: Arc<Database>,
+    options: EchomailOptions,
+    interval: Duration,
+}
+
+impl EchomailUpdater {
+    /// Create a new EchomailUpdater.
+    pub fn new(db: Arc<Database>, options: EchomailOptions, interval_secs: u64) -> Self {
+        Self {
+            db,
+            options,
+            interval: Duration::from_secs(interval_secs),
+        }
+    }
+
+    /// Run the updater loop.
+    ///
+    /// Each run tosses first so replies to incoming messages can be linked
+    /// before the scan.
+    pub async fn run(&self) {
+        info!(
+            "Echomail updater started (interval: {} seconds)",
+            self.interval.as_secs()
+        );
+
+        let mut timer = interval(self.interval);
+        loop {
+            timer.tick().await;
+            self.run_once().await;
+        }
+    }
+
+    /// Toss inbound packets, then scan new posts out.
+    pub async fn run_once(&self) {
+        let service = EchomailService::new(&self.db, &self.options);
+
+        match service.toss().await {
+            Ok(result) if result.packets > 0 || result.bad_packets > 0 => info!(
+                "Echomail toss: {} packets, {} imported, {} dupes, {} skipped, {} failed, {} bad",
+                result.packets,
+                result.imported,
+                result.dupes,
+                result.skipped,
+                result.failed,
+                result.bad_packets
+            ),
+            Ok(_) => debug!("No inbound echomail"),
+            Err(e) => error!("Echomail toss failed: {}", e),
+        }
+
+        match service.scan().await {
+            Ok(result) => {
+                if let Some(path) = result.packet {
+                    info!(
+                        "Echomail scan: {} messages to {}",
+                        result.exported,
+                        path.display()
+                    );
+                }
+            }
+            Err(e) => error!("Echomail scan failed: {}", e),
+        }
+    }
+}
+
+/// Start the echomail updater with configuration.
+///
+/// This function checks if echomail is enabled in the config before starting.
+/// Returns true if the updater was started, false if it is disabled.
+pub fn start_echomail_with_config(db: Arc<Database>, config: &Config) -> bool {
+    if !config.ftn.enabled {
+        info!("FTN echomail is disabled in configuration");
+        return false;
+    }
+
+    let options = match EchomailOptions::from_config(config) {
+        Ok(options) => options,
+        Err(e) => {
+            error!("Invalid FTN configuration: {}", e);
+            return false;
+        }
+    };
+    let updater = EchomailUpdater::new(db, options, config.ftn.interval_secs);
+    tokio::task::spawn_local(async move {
+        updater.run().await;
+    });
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_start_disabled() {
+        let db = Arc::new(Database::open_in_memory().await.unwrap());
+        assert!(!start_echomail_with_config(db, &Config::default()));
+    }
+}
@@ -4,33 +4,43 @@
 
 pub mod admin;
 pub mod app;
+pub mod archive;
 pub mod auth;
 pub mod board;
+pub mod bookmark;
 pub mod chat;
 pub mod config;
 pub mod datetime;
 pub mod db;
 pub mod error;
 pub mod file;
+pub mod filter;
+pub mod ftn;
 pub mod i18n;
 pub mod logging;
 pub mod mail;
+pub mod nntp;
+pub mod notify;
+pub mod poll;
+pub mod qwk;
 pub mod rate_limit;
+pub mod retention;
 pub mod rss;
 pub mod screen;
 pub mod script;
 pub mod server;
 pub mod template;
 pub mod terminal;
+pub mod twit;
 pub mod web;
 pub mod xmodem;
 
 pub use admin::{
     can_change_role, can_edit_user, format_duration, format_session_state, generate_password,
     is_admin, is_sysop, require_admin, AdminError, AdminService, BoardAdminService,
-    ContentAdminService, CreateBoardRequest, FolderAdminService, PostDeletionMode,
-    SessionAdminService, SessionStatistics, UserAdminService, UserDetail, DEFAULT_PASSWORD_LENGTH,
-    DELETED_POST_MESSAGE,
+    ContentAdminService, CreateBoardRequest, FolderAdminService, ModerationAdminService,
+    PostDeletionMode, SessionAdminService, SessionStatistics, UserAdminService, UserDetail,
+    DEFAULT_PASSWORD_LENGTH, DELETED_POST_MESSAGE,
 };
 pub use auth::{
     can_modify_resource, change_password, check_permission, get_profile, get_profile_by_username,
@@ -64,6 +74,7 @@ pub use mail::{
     MAX_BODY_LENGTH as MAX_MAIL_BODY_LENGTH, MAX_SUBJECT_LENGTH as MAX_MAIL_SUBJECT_LENGTH,
     WELCOME_MAIL_BODY, WELCOME_MAIL_SUBJECT,
 };
+pub use qwk::{QwkPacket, QwkService};
 pub use rss::{
     fetch_feed, start_rss_updater, start_rss_updater_with_config, start_rss_updater_with_interval,
     validate_url, AddFeedRequest, NewRssFeed, NewRssItem, ParsedFeed, ParsedItem, RssFeed,
@@ -4,6 +4,7 @@
 //! including recipient validation, automatic read marking, and access control.
 
 use crate::db::{Database, UserRepository, SQL_TRUE};
+use crate::filter::{ContentFilterService, FilterTarget};
 use crate::{HobbsError, Result};
 
 use super::repository::MailRepository;
@@ -61,6 +62,7 @@ impl<'a> MailService<'a> {
     /// - Body is empty or too long
     /// - Recipient user doesn't exist
     /// - Sender and recipient are the same user
+    /// - The content filter rejects the mail
     pub async fn send_mail(&self, request: &SendMailRequest) -> Result<Mail> {
         // Validate subject
         let subject = request.subject.trim();
@@ -105,6 +107,13 @@ impl<'a> MailService<'a> {
             ));
         }
 
+        // Mail cannot be held for moderation, so a hold rejects it
+        let mut texts = [subject.to_string(), body.to_string()];
+        ContentFilterService::new(self.db.pool())
+            .apply_strict(Some(request.sender_id), FilterTarget::Mail, &mut texts)
+            .await?;
+        let [subject, body] = texts;
+
         // Create the mail
         let new_mail = NewMail::new(request.sender_id, recipient.id, subject, body);
         let mail_repo = MailRepository::new(self.db.pool());
@@ -446,6 +455,31 @@ mod tests {
         assert!(matches!(result, Err(HobbsError::Validation(_))));
     }
 
+    #[tokio::test]
+    async fn test_send_mail_content_filter() {
+        use crate::db::Role;
+        use crate::filter::{is_rejection, FilterAction, FilterRuleType, NewFilterRule};
+
+        let db = setup_db().await;
+        let (sender_id, _) = create_test_users(&db).await;
+        let filter = ContentFilterService::new(db.pool());
+        for (pattern, action) in [("darn", FilterAction::Mask), ("casino", FilterAction::Hold)] {
+            let rule = NewFilterRule::new(FilterRuleType::Word, pattern, action);
+            filter.add_rule(sender_id, Role::SysOp, rule).await.unwrap();
+        }
+        let service = MailService::new(&db);
+
+        let request = SendMailRequest::new(sender_id, "bob", "Darn", "darn it");
+        let mail = service.send_mail(&request).await.unwrap();
+        assert_eq!(mail.subject, "****");
+        assert_eq!(mail.body, "**** it");
+
+        // Mail cannot be held, so a hold rejects it
+        let request = SendMailRequest::new(sender_id, "bob", "Offer", "casino");
+        let result = service.send_mail(&request).await;
+        assert!(is_rejection(&result.unwrap_err()));
+    }
+
     #[tokio::test]
     async fn test_list_inbox() {
         let db = setup_db().await;
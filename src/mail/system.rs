@@ -186,6 +186,31 @@ impl<'a> SystemMailService<'a> {
 
         Ok(count)
     }
+
+    /// Send a report to all active SysOps.
+    ///
+    /// The first SysOp is the sender, so it receives the report as a mail
+    /// to itself.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of mails sent.
+    pub async fn notify_sysops(&self, subject: &str, body: &str) -> Result<usize> {
+        let user_repo = UserRepository::new(self.db.pool());
+        let sysops = user_repo.list_by_role(Role::SysOp).await?;
+        let sender = match sysops.first() {
+            Some(u) => u.id,
+            None => return Ok(0),
+        };
+
+        let mail_repo = MailRepository::new(self.db.pool());
+        for sysop in &sysops {
+            let new_mail = NewMail::new(sender, sysop.id, subject, body);
+            mail_repo.create(&new_mail).await?;
+        }
+
+        Ok(sysops.len())
+    }
 }
 
 #[cfg(test)]
@@ -421,4 +446,28 @@ mod tests {
         // SysOp is skipped, so no mails sent
         assert_eq!(count, 0);
     }
+
+    #[tokio::test]
+    async fn test_notify_sysops() {
+        let db = setup_db().await;
+        let sysop = create_sysop(&db).await;
+        let member = create_member(&db, "alice", "Alice").await;
+
+        let service = SystemMailService::new(&db);
+        let count = service.notify_sysops("Report", "Pruned").await.unwrap();
+
+        assert_eq!(count, 1);
+        let mail_repo = MailRepository::new(db.pool());
+        assert_eq!(mail_repo.list_inbox(sysop.id).await.unwrap().len(), 1);
+        assert!(mail_repo.list_inbox(member.id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_notify_sysops_no_sysop() {
+        let db = setup_db().await;
+        create_member(&db, "alice", "Alice").await;
+
+        let service = SystemMailService::new(&db);
+        assert_eq!(service.notify_sysops("Report", "Pruned").await.unwrap(), 0);
+    }
 }
@@ -1,8 +1,15 @@
 use std::sync::Arc;
 
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
+use hobbs::archive::{write_mbox, ArchiveService, BoardArchive};
+use hobbs::board::SearchRepository;
+use hobbs::ftn::start_echomail_with_config;
+use hobbs::notify::start_notifications_with_config;
+use hobbs::qwk::PacketOptions as QwkPacketOptions;
+use hobbs::retention::start_retention_with_config;
 use hobbs::server::SessionManager;
+use hobbs::template::{validate_templates, TemplateIssue};
 use hobbs::web::WebServer;
 use hobbs::{
     chat::ChatRoomManager, start_rss_updater_with_config, Application, Config, Database,
@@ -22,6 +29,15 @@ fn main() {
         }
     };
 
+    // Admin subcommands run once and exit
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("check-templates") => std::process::exit(check_templates(&config)),
+        Some("export-boards") => std::process::exit(export_boards(&config, &args[1..])),
+        Some("import-boards") => std::process::exit(import_boards(&config, &args[1..])),
+        _ => {}
+    }
+
     // Validate configuration
     if let Err(e) = config.validate() {
         eprintln!("Configuration error: {e}");
@@ -81,13 +97,36 @@ async fn run_server(config: Config) -> Result<(), Box<dyn std::error::Error>> {
                 config.database.pool_size, config.database.min_connections
             );
 
+            // Index posts that are not yet searchable (e.g. written before
+            // the search index existed)
+            match SearchRepository::new(db.pool()).sync().await {
+                Ok(0) => {}
+                Ok(count) => info!("Search index: indexed {} post(s)", count),
+                Err(e) => warn!("Failed to update search index: {}", e),
+            }
+
             // Load I18n
             let i18n_manager = Arc::new(I18nManager::load_all("locales")?);
             info!("I18n loaded");
 
             // Load templates
-            let template_loader = Arc::new(TemplateLoader::new(&config.templates.path));
+            let template_loader = Arc::new(
+                TemplateLoader::new(&config.templates.path)
+                    .with_hot_reload(config.templates.hot_reload),
+            );
             info!("Templates loaded from: {}", config.templates.path);
+            match find_template_issues(&config) {
+                Ok(issues) => {
+                    for issue in &issues {
+                        if issue.is_error() {
+                            error!("Template error: {issue}");
+                        } else {
+                            warn!("Template warning: {issue}");
+                        }
+                    }
+                }
+                Err(e) => warn!("Template validation failed: {e}"),
+            }
 
             // Create session manager
             let session_manager = Arc::new(SessionManager::new(config.server.idle_timeout_secs));
@@ -139,7 +178,11 @@ async fn run_server(config: Config) -> Result<(), Box<dyn std::error::Error>> {
                     &config.bbs,
                     config.server.enabled,
                 )
-                .with_chat_manager(web_chat_manager);
+                .with_chat_manager(web_chat_manager)
+                .with_qwk(QwkPacketOptions::from_config(&config))
+                .with_notification_hub(Arc::clone(app.session_manager().notification_hub()))
+                .with_message_domain(&config.nntp.domain)
+                .with_poll_create_role(config.polls.create_role());
                 let web_addr = web_server.addr();
 
                 tokio::spawn(async move {
@@ -161,6 +204,17 @@ async fn run_server(config: Config) -> Result<(), Box<dyn std::error::Error>> {
                 });
             }
 
+            // Start NNTP server if enabled (runs in separate task)
+            if config.nntp.enabled {
+                let nntp_db = Arc::clone(&app.db());
+                let nntp_config = Arc::new(config.clone());
+                tokio::spawn(async move {
+                    if let Err(e) = hobbs::nntp::run(nntp_db, nntp_config).await {
+                        error!("NNTP server error: {}", e);
+                    }
+                });
+            }
+
             // Clone db and config for RSS updater
             let rss_db = Arc::clone(&app.db());
             let rss_config = config.rss.clone();
@@ -170,6 +224,25 @@ async fn run_server(config: Config) -> Result<(), Box<dyn std::error::Error>> {
                 info!("RSS updater started");
             }
 
+            // Start FTN echomail tosser/scanner (if enabled)
+            if start_echomail_with_config(Arc::clone(&app.db()), &config) {
+                info!("Echomail updater started");
+            }
+
+            // Start subscription notifications (if enabled)
+            if start_notifications_with_config(
+                Arc::clone(&app.db()),
+                Arc::clone(app.session_manager().notification_hub()),
+                &config,
+            ) {
+                info!("Notification updater started");
+            }
+
+            // Start board retention pruning (if enabled)
+            if start_retention_with_config(Arc::clone(&app.db()), &config) {
+                info!("Retention updater started");
+            }
+
             // Telnet sessions use spawn_local because ScreenContext contains Cell (non-Send)
             loop {
                 match server.accept().await {
@@ -196,3 +269,180 @@ async fn run_server(config: Config) -> Result<(), Box<dyn std::error::Error>> {
         })
         .await
 }
+
+/// Validate all templates against every locale.
+fn find_template_issues(config: &Config) -> Result<Vec<TemplateIssue>, Box<dyn std::error::Error>> {
+    let i18n_manager = I18nManager::load_all("locales")?;
+    let mut locales: Vec<_> = i18n_manager
+        .available_locales()
+        .into_iter()
+        .filter_map(|locale| i18n_manager.get(locale))
+        .collect();
+    locales.sort_by_key(|i18n| i18n.locale());
+    let loader = TemplateLoader::new(&config.templates.path);
+    Ok(validate_templates(&loader, &locales)?)
+}
+
+/// Run the `check-templates` subcommand and return the exit code.
+///
+/// Exits with 1 when any template has a syntax error or an unknown
+/// translation key, so it can be used in CI.
+fn check_templates(config: &Config) -> i32 {
+    match find_template_issues(config) {
+        Ok(issues) if issues.is_empty() => {
+            println!("All templates in {} are valid.", config.templates.path);
+            0
+        }
+        Ok(issues) => {
+            for issue in &issues {
+                eprintln!("{issue}");
+            }
+            eprintln!("{} problem(s) found.", issues.len());
+            1
+        }
+        Err(e) => {
+            eprintln!("Template validation failed: {e}");
+            1
+        }
+    }
+}
+
+/// Open the database for a one-shot admin subcommand.
+fn open_database(config: &Config) -> Result<(tokio::runtime::Runtime, Database), String> {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| format!("Failed to create tokio runtime: {e}"))?;
+    let db = rt
+        .block_on(Database::open_with_config(&config.database))
+        .map_err(|e| format!("Failed to open database: {e}"))?;
+    Ok((rt, db))
+}
+
+/// Run the `export-boards` subcommand and return the exit code.
+///
+/// Usage: `hobbs export-boards [--mbox] [-o FILE] [BOARD_ID...]`
+///
+/// Exports all boards when no IDs are given and writes to stdout unless
+/// `-o` is given.
+fn export_boards(config: &Config, args: &[String]) -> i32 {
+    let mut mbox = false;
+    let mut output: Option<String> = None;
+    let mut board_ids = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--mbox" => mbox = true,
+            "-o" | "--output" => match iter.next() {
+                Some(path) => output = Some(path.clone()),
+                None => {
+                    eprintln!("{arg} requires a file name");
+                    return 2;
+                }
+            },
+            id => match id.parse::<i64>() {
+                Ok(id) => board_ids.push(id),
+                Err(_) => {
+                    eprintln!("Usage: hobbs export-boards [--mbox] [-o FILE] [BOARD_ID...]");
+                    return 2;
+                }
+            },
+        }
+    }
+
+    let (rt, db) = match open_database(config) {
+        Ok(opened) => opened,
+        Err(e) => {
+            eprintln!("{e}");
+            return 1;
+        }
+    };
+    let service = ArchiveService::new(&db);
+    let archive = if board_ids.is_empty() {
+        rt.block_on(service.export_all())
+    } else {
+        rt.block_on(service.export_boards(&board_ids))
+    };
+    let content = archive.and_then(|archive| {
+        if mbox {
+            Ok(write_mbox(&archive, &config.nntp.domain))
+        } else {
+            archive.to_json()
+        }
+    });
+    let content = match content {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("Export failed: {e}");
+            return 1;
+        }
+    };
+
+    match output {
+        Some(path) => {
+            if let Err(e) = std::fs::write(&path, content) {
+                eprintln!("Failed to write {path}: {e}");
+                return 1;
+            }
+            println!("Exported to {path}.");
+        }
+        None => print!("{content}"),
+    }
+    0
+}
+
+/// Run the `import-boards` subcommand and return the exit code.
+///
+/// Usage: `hobbs import-boards FILE`
+///
+/// Exits with 1 when any board could not be imported.
+fn import_boards(config: &Config, args: &[String]) -> i32 {
+    let [path] = args else {
+        eprintln!("Usage: hobbs import-boards FILE");
+        return 2;
+    };
+    let archive = match std::fs::read_to_string(path)
+        .map_err(HobbsError::from)
+        .and_then(|json| BoardArchive::from_json(&json))
+    {
+        Ok(archive) => archive,
+        Err(e) => {
+            eprintln!("Failed to read {path}: {e}");
+            return 1;
+        }
+    };
+
+    let (rt, db) = match open_database(config) {
+        Ok(opened) => opened,
+        Err(e) => {
+            eprintln!("{e}");
+            return 1;
+        }
+    };
+    let result = match rt.block_on(ArchiveService::new(&db).import(&archive)) {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("Import failed: {e}");
+            return 1;
+        }
+    };
+
+    println!(
+        "Imported {} board(s), {} thread(s), {} post(s).",
+        result.boards, result.threads, result.posts
+    );
+    if !result.placeholders.is_empty() {
+        println!(
+            "Created inactive placeholder users: {}",
+            result.placeholders.join(", ")
+        );
+    }
+    for failure in &result.failed {
+        eprintln!("Skipped {failure}");
+    }
+    if result.failed.is_empty() {
+        0
+    } else {
+        1
+    }
+}
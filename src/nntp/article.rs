@@ -0,0 +1,650 @@
+//! Article formatting and parsing for the NNTP server.
+//!
+//! Boards become newsgroups and posts become articles numbered by post ID.
+//! Outgoing articles are UTF-8 with 8bit transfer encoding and RFC 2047
+//! encoded headers; posted articles may use any charset known to
+//! `encoding_rs` and quoted-printable or base64 transfer encoding.
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::NaiveDateTime;
+use encoding_rs::Encoding;
+
+use crate::board::Board;
+use crate::{HobbsError, Result};
+
+/// Overview fields advertised by LIST OVERVIEW.FMT, in OVER order.
+pub const OVERVIEW_FORMAT: &[&str] = &[
+    "Subject:",
+    "From:",
+    "Date:",
+    "Message-ID:",
+    "References:",
+    ":bytes",
+    ":lines",
+];
+
+/// Maximum bytes of UTF-8 text in one encoded word (keeps words under 76 chars).
+const ENCODED_WORD_BYTES: usize = 45;
+
+/// Newsgroup name for a board.
+///
+/// Board names that are not valid newsgroup components (e.g. Japanese
+/// names) fall back to `board<ID>`.
+pub fn group_name(prefix: &str, board: &Board) -> String {
+    let name = board.name.to_ascii_lowercase();
+    let valid = name.split('.').all(|part| {
+        !part.is_empty()
+            && part
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'+'))
+    });
+    if valid {
+        format!("{}.{}", prefix, name)
+    } else {
+        format!("{}.board{}", prefix, board.id)
+    }
+}
+
+/// Message-ID of a post.
+pub fn message_id(post_id: i64, domain: &str) -> String {
+    format!("<post.{}@{}>", post_id, domain)
+}
+
+/// Post ID from one of our Message-IDs.
+pub fn parse_message_id(id: &str, domain: &str) -> Option<i64> {
+    let inner = id.trim().strip_prefix('<')?.strip_suffix('>')?;
+    let (local, host) = inner.split_once('@')?;
+    if !host.eq_ignore_ascii_case(domain) {
+        return None;
+    }
+    local.strip_prefix("post.")?.parse().ok()
+}
+
+/// Format a stored UTC timestamp as an RFC 5322 date.
+pub fn format_date(created_at: &str) -> String {
+    NaiveDateTime::parse_from_str(created_at, "%Y-%m-%d %H:%M:%S")
+        .map(|dt| dt.and_utc().to_rfc2822())
+        .unwrap_or_else(|_| created_at.to_string())
+}
+
+/// Encode header text with RFC 2047 encoded words if it is not plain ASCII.
+pub fn encode_header(text: &str) -> String {
+    if text.bytes().all(|b| (0x20..0x7F).contains(&b)) {
+        return text.to_string();
+    }
+    let mut words = Vec::new();
+    let mut chunk = String::new();
+    for c in text.chars() {
+        if chunk.len() + c.len_utf8() > ENCODED_WORD_BYTES {
+            words.push(encoded_word(&chunk));
+            chunk.clear();
+        }
+        chunk.push(c);
+    }
+    if !chunk.is_empty() {
+        words.push(encoded_word(&chunk));
+    }
+    words.join(" ")
+}
+
+fn encoded_word(text: &str) -> String {
+    format!("=?UTF-8?B?{}?=", BASE64.encode(text.as_bytes()))
+}
+
+/// Format a From header mailbox.
+pub fn format_mailbox(display_name: &str, username: &str, domain: &str) -> String {
+    let address = format!("{}@{}", username, domain);
+    if display_name.is_ascii() {
+        let escaped = display_name.replace('\\', "\\\\").replace('"', "\\\"");
+        format!("\"{}\" <{}>", escaped, address)
+    } else {
+        format!("{} <{}>", encode_header(display_name), address)
+    }
+}
+
+/// Decode RFC 2047 encoded words in a header value.
+pub fn decode_header(value: &str) -> String {
+    let mut out = String::new();
+    let mut rest = value;
+    let mut after_word = false;
+    while let Some(start) = rest.find("=?") {
+        let (before, candidate) = rest.split_at(start);
+        match decode_encoded_word(candidate) {
+            Some((text, len)) => {
+                // Whitespace between adjacent encoded words is dropped
+                if !(after_word && before.trim().is_empty()) {
+                    out.push_str(before);
+                }
+                out.push_str(&text);
+                rest = &candidate[len..];
+                after_word = true;
+            }
+            None => {
+                out.push_str(before);
+                out.push_str("=?");
+                rest = &candidate[2..];
+                after_word = false;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Decode one encoded word at the start of `text`, returning it and its length.
+fn decode_encoded_word(text: &str) -> Option<(String, usize)> {
+    let body = text.strip_prefix("=?")?;
+    let (charset, body) = body.split_once('?')?;
+    let (encoding, body) = body.split_once('?')?;
+    let end = body.find("?=")?;
+    let payload = &body[..end];
+    if payload.contains(char::is_whitespace) {
+        return None;
+    }
+    // RFC 2231 language suffix: charset*lang
+    let charset = charset.split('*').next().unwrap_or(charset);
+    let bytes = match encoding {
+        "B" | "b" => BASE64.decode(payload).ok()?,
+        "Q" | "q" => decode_quoted_printable(&payload.replace('_', " ")),
+        _ => return None,
+    };
+    let len = text.len() - body.len() + end + 2;
+    Some((decode_charset(&bytes, charset)?, len))
+}
+
+/// Decode bytes in a MIME charset.
+fn decode_charset(bytes: &[u8], charset: &str) -> Option<String> {
+    let encoding = Encoding::for_label(charset.trim().as_bytes())?;
+    let (text, _, _) = encoding.decode(bytes);
+    Some(text.into_owned())
+}
+
+/// Decode quoted-printable text, including soft line breaks.
+fn decode_quoted_printable(text: &str) -> Vec<u8> {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'=' {
+            let rest = &bytes[i + 1..];
+            if rest.starts_with(b"\r\n") {
+                i += 3;
+                continue;
+            }
+            if rest.starts_with(b"\n") {
+                i += 2;
+                continue;
+            }
+            let hex = rest
+                .get(..2)
+                .and_then(|h| std::str::from_utf8(h).ok())
+                .and_then(|h| u8::from_str_radix(h, 16).ok());
+            if let Some(byte) = hex {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    out
+}
+
+/// Undo RFC 3676 format=flowed soft line breaks.
+fn unflow(text: &str, delsp: bool) -> String {
+    let quote = |depth: usize, line: String| {
+        if depth == 0 {
+            line
+        } else {
+            format!("{} {}", ">".repeat(depth), line)
+        }
+    };
+    let mut lines = Vec::new();
+    let mut pending: Option<(usize, String)> = None;
+    for raw in text.split('\n') {
+        let depth = raw.bytes().take_while(|&b| b == b'>').count();
+        let content = &raw[depth..];
+        let content = content.strip_prefix(' ').unwrap_or(content);
+        let flowed = content.ends_with(' ') && content != "-- ";
+        let content = if flowed && delsp {
+            &content[..content.len() - 1]
+        } else {
+            content
+        };
+        let line = match pending.take() {
+            Some((d, mut prev)) if d == depth => {
+                prev.push_str(content);
+                prev
+            }
+            Some((d, prev)) => {
+                lines.push(quote(d, prev));
+                content.to_string()
+            }
+            None => content.to_string(),
+        };
+        if flowed {
+            pending = Some((depth, line));
+        } else {
+            lines.push(quote(depth, line));
+        }
+    }
+    if let Some((depth, line)) = pending {
+        lines.push(quote(depth, line));
+    }
+    lines.join("\n")
+}
+
+/// An article assembled from a post.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Article {
+    /// Article number (post ID).
+    pub number: i64,
+    /// Message-ID.
+    pub message_id: String,
+    /// Newsgroup name.
+    pub group: String,
+    /// Subject (decoded).
+    pub subject: String,
+    /// From header value (already encoded).
+    pub from: String,
+    /// Date header value.
+    pub date: String,
+    /// Message-IDs of ancestors, oldest first.
+    pub references: Vec<String>,
+    /// Body text.
+    pub body: String,
+}
+
+impl Article {
+    /// Header lines, without the terminating blank line.
+    pub fn header_lines(&self, domain: &str) -> Vec<String> {
+        let mut headers = vec![
+            format!("Path: {}!not-for-mail", domain),
+            format!("From: {}", self.from),
+            format!("Newsgroups: {}", self.group),
+            format!("Subject: {}", encode_header(&self.subject)),
+            format!("Date: {}", self.date),
+            format!("Message-ID: {}", self.message_id),
+        ];
+        if !self.references.is_empty() {
+            headers.push(format!("References: {}", self.references.join(" ")));
+        }
+        headers.extend([
+            "MIME-Version: 1.0".to_string(),
+            "Content-Type: text/plain; charset=UTF-8".to_string(),
+            "Content-Transfer-Encoding: 8bit".to_string(),
+            format!("Xref: {} {}:{}", domain, self.group, self.number),
+            format!("Lines: {}", self.body_lines().len()),
+        ]);
+        headers
+    }
+
+    /// Body lines.
+    pub fn body_lines(&self) -> Vec<&str> {
+        self.body.lines().collect()
+    }
+
+    /// Size of the article in bytes as sent (CRLF line endings).
+    pub fn size(&self, domain: &str) -> usize {
+        let headers: usize = self.header_lines(domain).iter().map(|l| l.len() + 2).sum();
+        let body: usize = self.body_lines().iter().map(|l| l.len() + 2).sum();
+        headers + 2 + body
+    }
+
+    /// OVER line for the article.
+    pub fn overview(&self, domain: &str) -> String {
+        let clean = |s: &str| s.replace(['\t', '\r', '\n'], " ");
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.number,
+            clean(&encode_header(&self.subject)),
+            clean(&self.from),
+            self.date,
+            self.message_id,
+            self.references.join(" "),
+            self.size(domain),
+            self.body_lines().len()
+        )
+    }
+}
+
+/// An article received with POST.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PostedArticle {
+    /// Newsgroups to post to.
+    pub newsgroups: Vec<String>,
+    /// Subject (decoded).
+    pub subject: String,
+    /// References, oldest first.
+    pub references: Vec<String>,
+    /// Body text with `\n` line endings.
+    pub body: String,
+}
+
+impl PostedArticle {
+    /// Parse an article sent with POST (lines already un-dot-stuffed).
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let invalid = |msg: &str| HobbsError::Validation(msg.to_string());
+        let text_end = |data: &[u8]| -> usize {
+            // Header block ends at the first empty line
+            let mut pos = 0;
+            for line in data.split(|&b| b == b'\n') {
+                if line == b"" || line == b"\r" {
+                    return pos;
+                }
+                pos += line.len() + 1;
+            }
+            data.len()
+        };
+        let header_len = text_end(data);
+        let header_text = String::from_utf8_lossy(&data[..header_len]);
+        let body = data.get(header_len..).unwrap_or_default();
+        let body = body
+            .strip_prefix(b"\r\n")
+            .or_else(|| body.strip_prefix(b"\n"))
+            .unwrap_or(body);
+
+        // Unfold continuation lines
+        let mut headers: Vec<(String, String)> = Vec::new();
+        for line in header_text.lines() {
+            let line = line.trim_end_matches('\r');
+            if line.starts_with([' ', '\t']) {
+                if let Some((_, value)) = headers.last_mut() {
+                    value.push(' ');
+                    value.push_str(line.trim());
+                }
+            } else if let Some((name, value)) = line.split_once(':') {
+                headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+            }
+        }
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, v)| v.as_str())
+        };
+
+        let newsgroups: Vec<String> = header("newsgroups")
+            .unwrap_or_default()
+            .split(',')
+            .map(|g| g.trim().to_ascii_lowercase())
+            .filter(|g| !g.is_empty())
+            .collect();
+        if newsgroups.is_empty() {
+            return Err(invalid("Newsgroups header is missing"));
+        }
+        let references = header("references")
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(str::to_string)
+            .collect();
+
+        // Content-Type parameters
+        let content_type = header("content-type").unwrap_or("text/plain");
+        let mut parts = content_type.split(';');
+        let media_type = parts.next().unwrap_or_default().trim().to_ascii_lowercase();
+        let params: Vec<(String, String)> = parts
+            .filter_map(|p| p.split_once('='))
+            .map(|(k, v)| {
+                (
+                    k.trim().to_ascii_lowercase(),
+                    v.trim().trim_matches('"').to_string(),
+                )
+            })
+            .collect();
+        let param = |name: &str| {
+            params
+                .iter()
+                .find(|(k, _)| k == name)
+                .map(|(_, v)| v.as_str())
+        };
+        if media_type != "text/plain" {
+            return Err(invalid("only text/plain articles are supported"));
+        }
+
+        let transfer = header("content-transfer-encoding")
+            .unwrap_or("8bit")
+            .to_ascii_lowercase();
+        let bytes = match transfer.as_str() {
+            "7bit" | "8bit" | "binary" => body.to_vec(),
+            "quoted-printable" => decode_quoted_printable(&String::from_utf8_lossy(body)),
+            "base64" => {
+                let compact: Vec<u8> = body
+                    .iter()
+                    .copied()
+                    .filter(|b| !b.is_ascii_whitespace())
+                    .collect();
+                BASE64
+                    .decode(compact)
+                    .map_err(|_| invalid("invalid base64 body"))?
+            }
+            _ => return Err(invalid("unsupported Content-Transfer-Encoding")),
+        };
+
+        let charset = param("charset").unwrap_or("UTF-8");
+        let mut body =
+            decode_charset(&bytes, charset).ok_or_else(|| invalid("unsupported charset"))?;
+        body = body.replace("\r\n", "\n");
+        if param("format").is_some_and(|f| f.eq_ignore_ascii_case("flowed")) {
+            let delsp = param("delsp").is_some_and(|d| d.eq_ignore_ascii_case("yes"));
+            body = unflow(&body, delsp);
+        }
+        let body = body.trim_end().to_string();
+
+        Ok(Self {
+            newsgroups,
+            subject: decode_header(header("subject").unwrap_or_default())
+                .trim()
+                .to_string(),
+            references,
+            body,
+        })
+    }
+}
+
+/// Match a group name against an RFC 3977 wildmat (`*`, `?`, `,` and `!`).
+pub fn wildmat(pattern: &str, name: &str) -> bool {
+    let mut matched = false;
+    for item in pattern.split(',') {
+        let (negate, item) = match item.strip_prefix('!') {
+            Some(item) => (true, item),
+            None => (false, item),
+        };
+        if glob(item.as_bytes(), name.as_bytes()) {
+            matched = !negate;
+        }
+    }
+    matched
+}
+
+fn glob(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some((b'*', rest)) => (0..=text.len()).any(|i| glob(rest, &text[i..])),
+        Some((b'?', rest)) => !text.is_empty() && glob(rest, &text[1..]),
+        Some((c, rest)) => text.first() == Some(c) && glob(rest, &text[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::BoardType;
+    use crate::db::Role;
+
+    fn board(id: i64, name: &str) -> Board {
+        Board {
+            id,
+            name: name.to_string(),
+            description: None,
+            board_type: BoardType::Thread,
+            min_read_role: Role::Guest,
+            min_write_role: Role::Member,
+            sort_order: 0,
+            is_active: true,
+            disable_paging: false,
+            allow_color: true,
+            is_moderated: false,
+            group_id: None,
+            group_read_role: None,
+            max_posts: None,
+            max_age_days: None,
+            archive_pruned: false,
+            created_at: String::new(),
+        }
+    }
+
+    fn sample() -> Article {
+        Article {
+            number: 42,
+            message_id: message_id(42, "bbs.example"),
+            group: "hobbs.general".to_string(),
+            subject: "Re: こんにちは".to_string(),
+            from: format_mailbox("太郎", "taro", "bbs.example"),
+            date: format_date("2024-03-05 12:34:56"),
+            references: vec![message_id(40, "bbs.example")],
+            body: "一行目\n二行目".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_group_name() {
+        assert_eq!(group_name("hobbs", &board(1, "General")), "hobbs.general");
+        assert_eq!(group_name("hobbs", &board(2, "comp.lang")), "hobbs.comp.lang");
+        assert_eq!(group_name("hobbs", &board(3, "雑談")), "hobbs.board3");
+        assert_eq!(group_name("hobbs", &board(4, "a b")), "hobbs.board4");
+    }
+
+    #[test]
+    fn test_message_id_round_trip() {
+        let id = message_id(123, "bbs.example");
+        assert_eq!(id, "<post.123@bbs.example>");
+        assert_eq!(parse_message_id(&id, "BBS.example"), Some(123));
+        assert_eq!(parse_message_id(&id, "other.example"), None);
+        assert_eq!(parse_message_id("<foo@bbs.example>", "bbs.example"), None);
+        assert_eq!(parse_message_id("post.1@bbs.example", "bbs.example"), None);
+    }
+
+    #[test]
+    fn test_format_date() {
+        assert_eq!(format_date("2024-03-05 12:34:56"), "Tue, 5 Mar 2024 12:34:56 +0000");
+    }
+
+    #[test]
+    fn test_header_encoding_round_trip() {
+        assert_eq!(encode_header("Hello"), "Hello");
+        let long = "日本語の件名がとても長い場合は複数のエンコードワードに分割されます";
+        let encoded = encode_header(long);
+        assert!(encoded.starts_with("=?UTF-8?B?"));
+        assert!(encoded.split(' ').count() > 1);
+        assert!(encoded.split(' ').all(|w| w.len() <= 75));
+        assert_eq!(decode_header(&encoded), long);
+
+        assert_eq!(decode_header("=?ISO-2022-JP?B?GyRCJEYkOSRIGyhC?="), "てすと");
+        assert_eq!(decode_header("Re: =?utf-8?q?caf=C3=A9_time?= ok"), "Re: café time ok");
+        assert_eq!(decode_header("not =?encoded"), "not =?encoded");
+    }
+
+    #[test]
+    fn test_format_mailbox() {
+        assert_eq!(
+            format_mailbox("Joe \"J\"", "joe", "bbs.example"),
+            "\"Joe \\\"J\\\"\" <joe@bbs.example>"
+        );
+        assert!(format_mailbox("太郎", "taro", "x").starts_with("=?UTF-8?B?"));
+    }
+
+    #[test]
+    fn test_article_headers_and_overview() {
+        let article = sample();
+        let headers = article.header_lines("bbs.example");
+        assert!(headers.contains(&"Message-ID: <post.42@bbs.example>".to_string()));
+        assert!(headers.contains(&"References: <post.40@bbs.example>".to_string()));
+        assert!(headers.contains(&"Content-Type: text/plain; charset=UTF-8".to_string()));
+        assert!(headers.contains(&"Xref: bbs.example hobbs.general:42".to_string()));
+
+        let overview = article.overview("bbs.example");
+        let fields: Vec<&str> = overview.split('\t').collect();
+        assert_eq!(fields.len(), 8);
+        assert_eq!(fields[0], "42");
+        assert_eq!(decode_header(fields[1]), "Re: こんにちは");
+        assert_eq!(fields[4], "<post.42@bbs.example>");
+        assert_eq!(fields[6], article.size("bbs.example").to_string());
+        assert_eq!(fields[7], "2");
+    }
+
+    #[test]
+    fn test_parse_posted_article() {
+        let data = b"From: taro <taro@example.com>\r\n\
+Newsgroups: Hobbs.General, hobbs.other\r\n\
+Subject: =?UTF-8?B?44GT44KT44Gr44Gh44Gv?=\r\n\
+References: <post.1@bbs.example>\r\n <post.2@bbs.example>\r\n\
+\r\n\
+Hello\r\nWorld\r\n\r\n";
+        let article = PostedArticle::parse(data).unwrap();
+        assert_eq!(article.newsgroups, vec!["hobbs.general", "hobbs.other"]);
+        assert_eq!(article.subject, "こんにちは");
+        assert_eq!(
+            article.references,
+            vec!["<post.1@bbs.example>", "<post.2@bbs.example>"]
+        );
+        assert_eq!(article.body, "Hello\nWorld");
+    }
+
+    #[test]
+    fn test_parse_posted_article_encodings() {
+        let data = b"Newsgroups: g\n\
+Subject: qp\n\
+Content-Type: text/plain; charset=\"ISO-8859-1\"\n\
+Content-Transfer-Encoding: quoted-printable\n\
+\n\
+caf=E9 au l=\n\
+ait\n";
+        assert_eq!(PostedArticle::parse(data).unwrap().body, "café au lait");
+
+        let data = b"Newsgroups: g\nSubject: b64\nContent-Type: text/plain; charset=UTF-8\n\
+Content-Transfer-Encoding: base64\n\n44GC44GE\n44GG\n";
+        assert_eq!(PostedArticle::parse(data).unwrap().body, "あいう");
+
+        let data = b"Newsgroups: g\nSubject: jis\nContent-Type: text/plain; charset=ISO-2022-JP\n\n\
+\x1b$B$F$9$H\x1b(B\n";
+        assert_eq!(PostedArticle::parse(data).unwrap().body, "てすと");
+    }
+
+    #[test]
+    fn test_parse_posted_article_flowed() {
+        let data = b"Newsgroups: g\nSubject: f\n\
+Content-Type: text/plain; charset=UTF-8; format=flowed; delsp=no\n\n\
+This is a \nlong line.\n> quoted \n> text\n\n-- \nsig\n";
+        assert_eq!(
+            PostedArticle::parse(data).unwrap().body,
+            "This is a long line.\n> quoted text\n\n-- \nsig"
+        );
+
+        let data = b"Newsgroups: g\nSubject: f\n\
+Content-Type: text/plain; charset=UTF-8; format=flowed; delsp=yes\n\n\
+\xe6\x97\xa5\xe6\x9c\xac \n\xe8\xaa\x9e\n";
+        assert_eq!(PostedArticle::parse(data).unwrap().body, "日本語");
+    }
+
+    #[test]
+    fn test_parse_posted_article_errors() {
+        assert!(PostedArticle::parse(b"Subject: x\n\nbody\n").is_err());
+        let multipart = b"Newsgroups: g\nContent-Type: multipart/mixed; boundary=x\n\nbody\n";
+        assert!(PostedArticle::parse(multipart).is_err());
+        let bad_charset = b"Newsgroups: g\nContent-Type: text/plain; charset=klingon\n\nx\n";
+        assert!(PostedArticle::parse(bad_charset).is_err());
+    }
+
+    #[test]
+    fn test_wildmat() {
+        assert!(wildmat("*", "hobbs.general"));
+        assert!(wildmat("hobbs.*", "hobbs.general"));
+        assert!(!wildmat("fj.*", "hobbs.general"));
+        assert!(wildmat("hobbs.gen?ral", "hobbs.general"));
+        assert!(!wildmat("hobbs.*,!hobbs.general", "hobbs.general"));
+        assert!(wildmat("hobbs.*,!hobbs.general", "hobbs.other"));
+    }
+}
@@ -0,0 +1,18 @@
+//! NNTP server module for HOBBS.
+//!
+//! Exposes boards as newsgroups so that ordinary newsreaders can read and
+//! post. Post IDs are used as article numbers, reply relationships become
+//! References headers and AUTHINFO logs in with BBS accounts.
+
+pub mod article;
+pub mod repository;
+pub mod server;
+pub mod session;
+
+pub use article::{
+    decode_header, encode_header, format_date, format_mailbox, group_name, message_id,
+    parse_message_id, wildmat, Article, PostedArticle, OVERVIEW_FORMAT,
+};
+pub use repository::{ArticleRepository, GroupStats};
+pub use server::run;
+pub use session::{NntpOptions, NntpSession};
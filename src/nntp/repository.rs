@@ -0,0 +1,113 @@
+//! Article queries for the NNTP server.
+
+use crate::board::Post;
+use crate::db::DbPool;
+use crate::{HobbsError, Result};
+
+/// Article count and number range of a group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GroupStats {
+    /// Number of articles.
+    pub count: i64,
+    /// Lowest article number (1 if the group is empty).
+    pub low: i64,
+    /// Highest article number (0 if the group is empty).
+    pub high: i64,
+}
+
+/// Repository for article lookups by board and post ID.
+pub struct ArticleRepository<'a> {
+    pool: &'a DbPool,
+}
+
+impl<'a> ArticleRepository<'a> {
+    /// Create a new ArticleRepository with the given database pool.
+    pub fn new(pool: &'a DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Get the article count and number range of a board.
+    pub async fn stats(&self, board_id: i64) -> Result<GroupStats> {
+        let (count, low, high): (i64, Option<i64>, Option<i64>) =
+            sqlx::query_as("SELECT COUNT(*), MIN(id), MAX(id) FROM posts WHERE board_id = $1")
+                .bind(board_id)
+                .fetch_one(self.pool)
+                .await
+                .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(GroupStats {
+            count,
+            low: low.unwrap_or(1),
+            high: high.unwrap_or(0),
+        })
+    }
+
+    /// List post IDs in a board within `low..=high`.
+    pub async fn list_numbers(&self, board_id: i64, low: i64, high: i64) -> Result<Vec<i64>> {
+        let ids = sqlx::query_scalar(
+            "SELECT id FROM posts WHERE board_id = $1 AND id >= $2 AND id <= $3 ORDER BY id",
+        )
+        .bind(board_id)
+        .bind(low)
+        .bind(high)
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(ids)
+    }
+
+    /// List posts in a board within `low..=high`, oldest first.
+    pub async fn list_posts(&self, board_id: i64, low: i64, high: i64) -> Result<Vec<Post>> {
+        let posts = sqlx::query_as::<_, Post>(
+            "SELECT id, board_id, thread_id, parent_id, author_id, title, body,
+                    created_at, edited_at, edited_by
+             FROM posts WHERE board_id = $1 AND id >= $2 AND id <= $3 ORDER BY id",
+        )
+        .bind(board_id)
+        .bind(low)
+        .bind(high)
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(posts)
+    }
+
+    /// Get the next post ID in a board after `id`.
+    pub async fn next_number(&self, board_id: i64, id: i64) -> Result<Option<i64>> {
+        let next: Option<i64> =
+            sqlx::query_scalar("SELECT MIN(id) FROM posts WHERE board_id = $1 AND id > $2")
+                .bind(board_id)
+                .bind(id)
+                .fetch_one(self.pool)
+                .await
+                .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(next)
+    }
+
+    /// Get the previous post ID in a board before `id`.
+    pub async fn previous_number(&self, board_id: i64, id: i64) -> Result<Option<i64>> {
+        let previous: Option<i64> =
+            sqlx::query_scalar("SELECT MAX(id) FROM posts WHERE board_id = $1 AND id < $2")
+                .bind(board_id)
+                .bind(id)
+                .fetch_one(self.pool)
+                .await
+                .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(previous)
+    }
+
+    /// Get the ID of the first post in a thread.
+    pub async fn first_post_id(&self, thread_id: i64) -> Result<Option<i64>> {
+        let id: Option<i64> = sqlx::query_scalar("SELECT MIN(id) FROM posts WHERE thread_id = $1")
+            .bind(thread_id)
+            .fetch_one(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(id)
+    }
+}
@@ -0,0 +1,53 @@
+//! NNTP listener.
+
+use std::sync::{Arc, Mutex};
+
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+use tokio::sync::Semaphore;
+use tracing::{info, warn};
+
+use super::session::{NntpOptions, NntpSession};
+use crate::auth::LoginLimiter;
+use crate::db::Database;
+use crate::{Config, HobbsError, Result};
+
+/// Run the NNTP server.
+pub async fn run(db: Arc<Database>, config: Arc<Config>) -> Result<()> {
+    let options = Arc::new(NntpOptions::from_config(&config));
+    let limiter = Arc::new(Mutex::new(LoginLimiter::new()));
+    let semaphore = Arc::new(Semaphore::new(config.nntp.max_connections));
+    let addr = format!("{}:{}", config.nntp.host, config.nntp.port);
+
+    let listener = TcpListener::bind(&addr).await.map_err(|e| {
+        HobbsError::Config(format!("Failed to bind NNTP server on {}: {}", addr, e))
+    })?;
+    info!("NNTP server listening on {}", addr);
+
+    loop {
+        let (mut stream, peer_addr) = listener
+            .accept()
+            .await
+            .map_err(|e| HobbsError::Config(format!("NNTP accept error: {}", e)))?;
+
+        let permit = match semaphore.clone().try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(_) => {
+                warn!(
+                    "NNTP connection limit reached, rejecting connection from {}",
+                    peer_addr
+                );
+                let _ = stream.write_all(b"400 Too many connections\r\n").await;
+                continue;
+            }
+        };
+
+        let session = NntpSession::new(Arc::clone(&db), Arc::clone(&options), Arc::clone(&limiter));
+        tokio::spawn(async move {
+            let _permit = permit;
+            if let Err(e) = session.run(stream).await {
+                info!("NNTP session ended for {}: {}", peer_addr, e);
+            }
+        });
+    }
+}
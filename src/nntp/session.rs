@@ -0,0 +1,1163 @@
+//! NNTP session handling.
+//!
+//! Implements the reader subset of RFC 3977 plus AUTHINFO USER/PASS
+//! (RFC 4643) over any byte stream.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tracing::{error, info, warn};
+
+use super::article::{
+    format_date, format_mailbox, group_name, message_id, parse_message_id, wildmat, Article,
+    PostedArticle, OVERVIEW_FORMAT,
+};
+use super::repository::ArticleRepository;
+use crate::auth::{verify_password, LimitResult, LoginLimiter};
+use crate::board::{
+    Board, BoardService, BoardType, Post, PostRepository, Submission, ThreadRepository,
+};
+use crate::board::{MAX_BODY_LENGTH, MAX_REPLY_DEPTH};
+use crate::config::Config;
+use crate::db::{Database, Role, User, UserRepository};
+use crate::{HobbsError, Result};
+
+/// Maximum length of a command line (RFC 3977 allows 512 octets).
+const MAX_COMMAND_LENGTH: usize = 512;
+
+/// Maximum size of a posted article in bytes.
+const MAX_ARTICLE_SIZE: usize = 256 * 1024;
+
+/// Settings shared by all NNTP sessions.
+#[derive(Debug, Clone)]
+pub struct NntpOptions {
+    /// Domain used in Message-ID, Path and Xref headers.
+    pub domain: String,
+    /// Newsgroup name prefix.
+    pub group_prefix: String,
+    /// Idle timeout.
+    pub idle_timeout: Duration,
+}
+
+impl NntpOptions {
+    /// Build NNTP options from the application configuration.
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            domain: config.nntp.domain.clone(),
+            group_prefix: config.nntp.group_prefix.to_ascii_lowercase(),
+            idle_timeout: Duration::from_secs(config.nntp.idle_timeout_secs),
+        }
+    }
+}
+
+impl Default for NntpOptions {
+    fn default() -> Self {
+        Self::from_config(&Config::default())
+    }
+}
+
+/// A response: status line and an optional multi-line block.
+struct Response {
+    status: String,
+    block: Option<Vec<String>>,
+}
+
+impl Response {
+    fn status(status: impl Into<String>) -> Self {
+        Self {
+            status: status.into(),
+            block: None,
+        }
+    }
+
+    fn block(status: impl Into<String>, lines: Vec<String>) -> Self {
+        Self {
+            status: status.into(),
+            block: Some(lines),
+        }
+    }
+}
+
+/// Which parts of an article a retrieval command sends.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Part {
+    Article,
+    Head,
+    Body,
+    Stat,
+}
+
+/// The selected newsgroup.
+struct CurrentGroup {
+    name: String,
+    board: Board,
+}
+
+/// One NNTP client session.
+pub struct NntpSession {
+    db: Arc<Database>,
+    options: Arc<NntpOptions>,
+    limiter: Arc<Mutex<LoginLimiter>>,
+    user: Option<User>,
+    pending_user: Option<String>,
+    group: Option<CurrentGroup>,
+    article: Option<i64>,
+}
+
+impl NntpSession {
+    /// Create a new session.
+    pub fn new(
+        db: Arc<Database>,
+        options: Arc<NntpOptions>,
+        limiter: Arc<Mutex<LoginLimiter>>,
+    ) -> Self {
+        Self {
+            db,
+            options,
+            limiter,
+            user: None,
+            pending_user: None,
+            group: None,
+            article: None,
+        }
+    }
+
+    /// Serve the session until the client quits or disconnects.
+    pub async fn run<S>(mut self, stream: S) -> Result<()>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let (reader, mut writer) = tokio::io::split(stream);
+        let mut reader = BufReader::new(reader);
+        let greeting = Response::status("200 HOBBS NNTP server ready (posting ok)");
+        write_response(&mut writer, &greeting).await?;
+
+        loop {
+            let line = match tokio::time::timeout(
+                self.options.idle_timeout,
+                read_line(&mut reader, MAX_COMMAND_LENGTH),
+            )
+            .await
+            {
+                Ok(Ok(Some(line))) => line,
+                Ok(Ok(None)) => return Ok(()),
+                Ok(Err(e)) => return Err(e),
+                Err(_) => {
+                    let timeout = Response::status("400 Idle timeout, closing connection");
+                    write_response(&mut writer, &timeout).await?;
+                    return Ok(());
+                }
+            };
+
+            let (keyword, args) = match line.trim().split_once(char::is_whitespace) {
+                Some((keyword, args)) => (keyword.to_ascii_uppercase(), args.trim()),
+                None => (line.trim().to_ascii_uppercase(), ""),
+            };
+
+            let response = match keyword.as_str() {
+                "QUIT" => {
+                    write_response(&mut writer, &Response::status("205 Bye")).await?;
+                    return Ok(());
+                }
+                "POST" if self.user.is_none() => {
+                    Ok(Response::status("480 Authentication required"))
+                }
+                "POST" => {
+                    let start = Response::status("340 Send article to be posted");
+                    write_response(&mut writer, &start).await?;
+                    match read_article(&mut reader).await? {
+                        Some(data) => self.post(&data).await,
+                        None => {
+                            // The rest of the article is still in flight.
+                            let too_large = Response::status("441 Article too large");
+                            write_response(&mut writer, &too_large).await?;
+                            return Ok(());
+                        }
+                    }
+                }
+                _ => self.dispatch(&keyword, args).await,
+            };
+
+            let response = response.unwrap_or_else(|e| {
+                error!("NNTP command {} failed: {}", keyword, e);
+                Response::status("403 Internal fault")
+            });
+            write_response(&mut writer, &response).await?;
+        }
+    }
+
+    async fn dispatch(&mut self, keyword: &str, args: &str) -> Result<Response> {
+        match keyword {
+            "CAPABILITIES" => Ok(self.capabilities()),
+            "MODE" if args.eq_ignore_ascii_case("READER") => {
+                Ok(Response::status("200 Posting allowed"))
+            }
+            "DATE" => Ok(Response::status(format!(
+                "111 {}",
+                chrono::Utc::now().format("%Y%m%d%H%M%S")
+            ))),
+            "HELP" => Ok(Response::block(
+                "100 Help text follows",
+                vec![
+                    "ARTICLE BODY HEAD STAT [number|<message-id>]".to_string(),
+                    "AUTHINFO USER name / AUTHINFO PASS password".to_string(),
+                    "CAPABILITIES DATE HELP MODE READER QUIT".to_string(),
+                    "GROUP LISTGROUP NEXT LAST".to_string(),
+                    "LIST [ACTIVE|NEWSGROUPS|OVERVIEW.FMT] [wildmat]".to_string(),
+                    "OVER XOVER [range|<message-id>]".to_string(),
+                    "POST".to_string(),
+                ],
+            )),
+            "AUTHINFO" => self.authinfo(args).await,
+            "LIST" => self.list(args).await,
+            "GROUP" => self.select_group(args, false).await,
+            "LISTGROUP" => self.select_group(args, true).await,
+            "ARTICLE" => self.retrieve(args, Part::Article).await,
+            "HEAD" => self.retrieve(args, Part::Head).await,
+            "BODY" => self.retrieve(args, Part::Body).await,
+            "STAT" => self.retrieve(args, Part::Stat).await,
+            "NEXT" => self.step(true).await,
+            "LAST" => self.step(false).await,
+            "OVER" | "XOVER" => self.over(args).await,
+            _ => Ok(Response::status("500 Unknown command")),
+        }
+    }
+
+    fn role(&self) -> Role {
+        self.user.as_ref().map_or(Role::Guest, |u| u.role)
+    }
+
+    fn capabilities(&self) -> Response {
+        let mut lines = vec![
+            "VERSION 2".to_string(),
+            "READER".to_string(),
+            "POST".to_string(),
+            "LIST ACTIVE NEWSGROUPS OVERVIEW.FMT".to_string(),
+            "OVER MSGID".to_string(),
+            "IMPLEMENTATION HOBBS".to_string(),
+        ];
+        if self.user.is_none() {
+            lines.push("AUTHINFO USER".to_string());
+        }
+        Response::block("101 Capability list:", lines)
+    }
+
+    async fn authinfo(&mut self, args: &str) -> Result<Response> {
+        if self.user.is_some() {
+            return Ok(Response::status("502 Already authenticated"));
+        }
+        let (kind, value) = args.split_once(' ').unwrap_or((args, ""));
+        match kind.to_ascii_uppercase().as_str() {
+            "USER" if !value.is_empty() => {
+                self.pending_user = Some(value.trim().to_string());
+                Ok(Response::status("381 Password required"))
+            }
+            "PASS" => {
+                let Some(username) = self.pending_user.take() else {
+                    return Ok(Response::status(
+                        "482 Authentication commands issued out of sequence",
+                    ));
+                };
+                self.authenticate(&username, value).await
+            }
+            _ => Ok(Response::status("501 Syntax error")),
+        }
+    }
+
+    async fn authenticate(&mut self, username: &str, password: &str) -> Result<Response> {
+        let locked = matches!(
+            self.limiter.lock().unwrap().check(username),
+            LimitResult::Locked(_)
+        );
+        if locked {
+            warn!(username = %username, "NNTP login rejected: too many attempts");
+            return Ok(Response::status("481 Too many failed attempts, try again later"));
+        }
+
+        let user = UserRepository::new(self.db.pool())
+            .get_by_username(username)
+            .await?
+            .filter(|u| u.is_active && verify_password(password, &u.password).is_ok());
+        let Some(user) = user else {
+            self.limiter.lock().unwrap().record_failure(username);
+            warn!(username = %username, "NNTP login failed");
+            return Ok(Response::status("481 Authentication failed"));
+        };
+
+        self.limiter.lock().unwrap().clear(username);
+        info!(username = %user.username, "NNTP login");
+        self.user = Some(user);
+        Ok(Response::status("281 Authentication accepted"))
+    }
+
+    /// Readable groups for the current user.
+    async fn groups(&self) -> Result<Vec<CurrentGroup>> {
+        let boards = BoardService::new(&self.db).list_boards(self.role()).await?;
+        Ok(boards
+            .into_iter()
+            .map(|board| CurrentGroup {
+                name: group_name(&self.options.group_prefix, &board),
+                board,
+            })
+            .collect())
+    }
+
+    async fn list(&self, args: &str) -> Result<Response> {
+        let mut parts = args.split_whitespace();
+        let keyword = parts.next().unwrap_or("ACTIVE").to_ascii_uppercase();
+        let pattern = parts.next().unwrap_or("*");
+
+        if keyword == "OVERVIEW.FMT" {
+            let lines = OVERVIEW_FORMAT.iter().map(|f| f.to_string()).collect();
+            return Ok(Response::block("215 Order of fields in overview database", lines));
+        }
+        if keyword != "ACTIVE" && keyword != "NEWSGROUPS" {
+            return Ok(Response::status("501 Unsupported LIST keyword"));
+        }
+
+        let repo = ArticleRepository::new(self.db.pool());
+        let role = self.role();
+        let mut lines = Vec::new();
+        for group in self.groups().await? {
+            if !wildmat(pattern, &group.name) {
+                continue;
+            }
+            if keyword == "ACTIVE" {
+                let stats = repo.stats(group.board.id).await?;
+                let posting = if group.board.can_write(role) { 'y' } else { 'n' };
+                lines.push(format!(
+                    "{} {} {} {}",
+                    group.name, stats.high, stats.low, posting
+                ));
+            } else {
+                let description = group.board.description.as_deref().unwrap_or(&group.board.name);
+                lines.push(format!("{}\t{}", group.name, description.replace('\n', " ")));
+            }
+        }
+        Ok(Response::block("215 List of newsgroups follows", lines))
+    }
+
+    async fn select_group(&mut self, args: &str, list: bool) -> Result<Response> {
+        let mut parts = args.split_whitespace();
+        let name = match parts.next() {
+            Some(name) => name.to_ascii_lowercase(),
+            None if list => match &self.group {
+                Some(group) => group.name.clone(),
+                None => return Ok(Response::status("412 No newsgroup selected")),
+            },
+            None => return Ok(Response::status("501 Syntax error")),
+        };
+        let range = match parts.next().map(parse_range) {
+            Some(Some(range)) => Some(range),
+            Some(None) => return Ok(Response::status("501 Invalid range")),
+            None => None,
+        };
+
+        let Some(group) = self.groups().await?.into_iter().find(|g| g.name == name) else {
+            return Ok(Response::status("411 No such newsgroup"));
+        };
+        let repo = ArticleRepository::new(self.db.pool());
+        let stats = repo.stats(group.board.id).await?;
+        let status = format!("211 {} {} {} {}", stats.count, stats.low, stats.high, group.name);
+        self.article = (stats.count > 0).then_some(stats.low);
+        let board_id = group.board.id;
+        self.group = Some(group);
+
+        if !list {
+            return Ok(Response::status(status));
+        }
+        let (low, high) = range.unwrap_or((stats.low, stats.high));
+        let numbers = repo.list_numbers(board_id, low, high).await?;
+        Ok(Response::block(
+            status,
+            numbers.iter().map(|n| n.to_string()).collect(),
+        ))
+    }
+
+    /// Resolve an article argument to a readable post and its reported number.
+    async fn select_article(
+        &mut self,
+        arg: &str,
+    ) -> Result<std::result::Result<(Post, i64), Response>> {
+        let posts = PostRepository::new(self.db.pool());
+        if arg.starts_with('<') {
+            let post = match parse_message_id(arg, &self.options.domain) {
+                Some(id) => posts.get_by_id(id).await?,
+                None => None,
+            };
+            let Some(post) = post else {
+                return Ok(Err(Response::status("430 No article with that message-id")));
+            };
+            if !self.can_read(post.board_id).await? {
+                return Ok(Err(Response::status("430 No article with that message-id")));
+            }
+            let in_group = self.group.as_ref().is_some_and(|g| g.board.id == post.board_id);
+            let number = if in_group { post.id } else { 0 };
+            return Ok(Ok((post, number)));
+        }
+
+        let Some(board_id) = self.group.as_ref().map(|g| g.board.id) else {
+            return Ok(Err(Response::status("412 No newsgroup selected")));
+        };
+        let number = if arg.is_empty() {
+            match self.article {
+                Some(number) => number,
+                None => return Ok(Err(Response::status("420 Current article number is invalid"))),
+            }
+        } else {
+            match arg.parse::<i64>() {
+                Ok(number) => number,
+                Err(_) => return Ok(Err(Response::status("501 Syntax error"))),
+            }
+        };
+        match posts.get_by_id(number).await?.filter(|p| p.board_id == board_id) {
+            Some(post) => {
+                self.article = Some(number);
+                Ok(Ok((post, number)))
+            }
+            None => Ok(Err(Response::status("423 No article with that number"))),
+        }
+    }
+
+    async fn can_read(&self, board_id: i64) -> Result<bool> {
+        match BoardService::new(&self.db).get_board(board_id, self.role()).await {
+            Ok(_) => Ok(true),
+            Err(HobbsError::NotFound(_)) | Err(HobbsError::Permission(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn retrieve(&mut self, arg: &str, part: Part) -> Result<Response> {
+        let (post, number) = match self.select_article(arg).await? {
+            Ok(selected) => selected,
+            Err(response) => return Ok(response),
+        };
+        let article = self.build_article(&post).await?;
+        let domain = &self.options.domain;
+        let id = &article.message_id;
+
+        Ok(match part {
+            Part::Stat => Response::status(format!("223 {} {}", number, id)),
+            Part::Head => Response::block(
+                format!("221 {} {}", number, id),
+                article.header_lines(domain),
+            ),
+            Part::Body => Response::block(
+                format!("222 {} {}", number, id),
+                article.body_lines().iter().map(|l| l.to_string()).collect(),
+            ),
+            Part::Article => {
+                let mut lines = article.header_lines(domain);
+                lines.push(String::new());
+                lines.extend(article.body_lines().iter().map(|l| l.to_string()));
+                Response::block(format!("220 {} {}", number, id), lines)
+            }
+        })
+    }
+
+    async fn step(&mut self, forward: bool) -> Result<Response> {
+        let Some(board_id) = self.group.as_ref().map(|g| g.board.id) else {
+            return Ok(Response::status("412 No newsgroup selected"));
+        };
+        let Some(current) = self.article else {
+            return Ok(Response::status("420 Current article number is invalid"));
+        };
+        let repo = ArticleRepository::new(self.db.pool());
+        let target = if forward {
+            repo.next_number(board_id, current).await?
+        } else {
+            repo.previous_number(board_id, current).await?
+        };
+        match target {
+            Some(number) => {
+                self.article = Some(number);
+                Ok(Response::status(format!(
+                    "223 {} {}",
+                    number,
+                    message_id(number, &self.options.domain)
+                )))
+            }
+            None if forward => Ok(Response::status("421 No next article in this group")),
+            None => Ok(Response::status("422 No previous article in this group")),
+        }
+    }
+
+    async fn over(&mut self, arg: &str) -> Result<Response> {
+        let domain = self.options.domain.clone();
+        if arg.starts_with('<') || arg.is_empty() {
+            let (post, number) = match self.select_article(arg).await? {
+                Ok(selected) => selected,
+                Err(response) => return Ok(response),
+            };
+            let mut article = self.build_article(&post).await?;
+            article.number = number;
+            return Ok(Response::block(
+                "224 Overview information follows",
+                vec![article.overview(&domain)],
+            ));
+        }
+
+        let Some(board_id) = self.group.as_ref().map(|g| g.board.id) else {
+            return Ok(Response::status("412 No newsgroup selected"));
+        };
+        let Some((low, high)) = parse_range(arg) else {
+            return Ok(Response::status("501 Invalid range"));
+        };
+        let posts = ArticleRepository::new(self.db.pool())
+            .list_posts(board_id, low, high)
+            .await?;
+        if posts.is_empty() {
+            return Ok(Response::status("423 No articles in that range"));
+        }
+        let mut lines = Vec::with_capacity(posts.len());
+        for post in &posts {
+            lines.push(self.build_article(post).await?.overview(&domain));
+        }
+        Ok(Response::block("224 Overview information follows", lines))
+    }
+
+    /// Build the article for a post.
+    async fn build_article(&self, post: &Post) -> Result<Article> {
+        let pool = self.db.pool();
+        let domain = &self.options.domain;
+        let board = crate::board::BoardRepository::new(pool)
+            .get_by_id(post.board_id)
+            .await?
+            .ok_or_else(|| HobbsError::NotFound("board".to_string()))?;
+
+        let from = match UserRepository::new(pool).get_by_id(post.author_id).await? {
+            Some(user) => format_mailbox(&user.nickname, &user.username, domain),
+            None => format_mailbox("unknown", "unknown", domain),
+        };
+
+        // Ancestors by reply relationship, then the thread's first post
+        let posts = PostRepository::new(pool);
+        let mut ancestors = Vec::new();
+        let mut parent_id = post.parent_id;
+        while let Some(id) = parent_id {
+            if ancestors.len() >= MAX_REPLY_DEPTH || ancestors.contains(&id) {
+                break;
+            }
+            ancestors.push(id);
+            parent_id = posts.get_by_id(id).await?.and_then(|p| p.parent_id);
+        }
+        ancestors.reverse();
+
+        let subject = match post.thread_id {
+            Some(thread_id) => {
+                let title = ThreadRepository::new(pool)
+                    .get_by_id(thread_id)
+                    .await?
+                    .map(|t| t.title)
+                    .unwrap_or_default();
+                let first = ArticleRepository::new(pool).first_post_id(thread_id).await?;
+                if let Some(first) = first.filter(|&f| f != post.id) {
+                    if ancestors.first() != Some(&first) {
+                        ancestors.insert(0, first);
+                    }
+                }
+                if ancestors.is_empty() {
+                    title
+                } else {
+                    format!("Re: {}", title)
+                }
+            }
+            None => post.title.clone().unwrap_or_default(),
+        };
+
+        Ok(Article {
+            number: post.id,
+            message_id: message_id(post.id, domain),
+            group: group_name(&self.options.group_prefix, &board),
+            subject,
+            from,
+            date: format_date(&post.created_at),
+            references: ancestors.iter().map(|&id| message_id(id, domain)).collect(),
+            body: post.body.clone(),
+        })
+    }
+
+    async fn post(&mut self, data: &[u8]) -> Result<Response> {
+        let Some(user) = self.user.clone() else {
+            return Ok(Response::status("480 Authentication required"));
+        };
+        let article = match PostedArticle::parse(data) {
+            Ok(article) => article,
+            Err(e) => return Ok(Response::status(format!("441 {}", reason(&e)))),
+        };
+        if article.body.trim().is_empty() {
+            return Ok(Response::status("441 Article body is empty"));
+        }
+        if article.body.chars().count() > MAX_BODY_LENGTH {
+            return Ok(Response::status(format!(
+                "441 Article body is longer than {} characters",
+                MAX_BODY_LENGTH
+            )));
+        }
+
+        let groups = self.groups().await?;
+        let Some(group) = article
+            .newsgroups
+            .iter()
+            .find_map(|name| groups.iter().find(|g| &g.name == name))
+        else {
+            return Ok(Response::status("441 No such newsgroup"));
+        };
+        let board = &group.board;
+        if !board.can_write(user.role) {
+            return Ok(Response::status("441 Posting not permitted in this group"));
+        }
+
+        let service = BoardService::new(&self.db);
+        let result = match board.board_type {
+            BoardType::Thread => {
+                // Reply to the nearest referenced article that is ours
+                let mut parent = None;
+                for reference in article.references.iter().rev() {
+                    let Some(id) = parse_message_id(reference, &self.options.domain) else {
+                        continue;
+                    };
+                    parent = PostRepository::new(self.db.pool())
+                        .get_by_id(id)
+                        .await?
+                        .filter(|p| p.board_id == board.id && p.thread_id.is_some());
+                    if parent.is_some() {
+                        break;
+                    }
+                }
+                match parent {
+                    Some(parent) => {
+                        let thread_id = parent.thread_id.unwrap_or_default();
+                        service
+                            .submit_reply(
+                                thread_id,
+                                Some(parent.id),
+                                user.id,
+                                article.body,
+                                user.role,
+                            )
+                            .await
+                    }
+                    None => {
+                        service
+                            .submit_thread(
+                                board.id,
+                                article.subject,
+                                user.id,
+                                article.body,
+                                user.role,
+                            )
+                            .await
+                    }
+                }
+            }
+            BoardType::Flat => {
+                service
+                    .submit_flat_post(board.id, user.id, article.subject, article.body, user.role)
+                    .await
+            }
+        };
+
+        match result {
+            Ok(Submission::Posted(post)) => {
+                info!(username = %user.username, post_id = post.id, "NNTP post");
+                Ok(Response::status(format!(
+                    "240 Article received {}",
+                    message_id(post.id, &self.options.domain)
+                )))
+            }
+            Ok(Submission::Pending(pending)) => {
+                info!(username = %user.username, pending_id = pending.id, "NNTP post queued");
+                Ok(Response::status(
+                    "240 Article received, awaiting moderator approval",
+                ))
+            }
+            Err(
+                e @ (HobbsError::Validation(_)
+                | HobbsError::Permission(_)
+                | HobbsError::NotFound(_)),
+            ) => Ok(Response::status(format!("441 {}", reason(&e)))),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Error text without the error kind prefix.
+fn reason(error: &HobbsError) -> String {
+    match error {
+        HobbsError::Validation(msg) | HobbsError::Permission(msg) | HobbsError::NotFound(msg) => {
+            msg.clone()
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Parse an article range: `n`, `n-` or `n-m`.
+fn parse_range(text: &str) -> Option<(i64, i64)> {
+    match text.split_once('-') {
+        Some((low, "")) => Some((low.parse().ok()?, i64::MAX)),
+        Some((low, high)) => Some((low.parse().ok()?, high.parse().ok()?)),
+        None => {
+            let number = text.parse().ok()?;
+            Some((number, number))
+        }
+    }
+}
+
+/// Read up to and including the next `\n` into `buf`, keeping at most
+/// `max` bytes.
+///
+/// The rest of an overlong line is consumed without being buffered.
+/// Returns the number of bytes consumed and whether the line was cut.
+async fn read_bounded<R>(reader: &mut R, buf: &mut Vec<u8>, max: usize) -> Result<(usize, bool)>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut consumed = 0;
+    let mut overlong = false;
+    loop {
+        let available = reader.fill_buf().await?;
+        if available.is_empty() {
+            break;
+        }
+        let (chunk, done) = match available.iter().position(|&b| b == b'\n') {
+            Some(i) => (&available[..=i], true),
+            None => (available, false),
+        };
+        let keep = chunk.len().min(max.saturating_sub(buf.len()));
+        buf.extend_from_slice(&chunk[..keep]);
+        overlong |= keep < chunk.len();
+        let len = chunk.len();
+        reader.consume(len);
+        consumed += len;
+        if done {
+            break;
+        }
+    }
+    Ok((consumed, overlong))
+}
+
+/// Read one CRLF- or LF-terminated line, or None at end of stream.
+///
+/// Overlong lines are truncated to `max` bytes.
+async fn read_line<R>(reader: &mut R, max: usize) -> Result<Option<String>>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut buf = Vec::new();
+    let (n, _) = read_bounded(reader, &mut buf, max).await?;
+    if n == 0 {
+        return Ok(None);
+    }
+    while buf.last().is_some_and(|&b| b == b'\n' || b == b'\r') {
+        buf.pop();
+    }
+    Ok(Some(String::from_utf8_lossy(&buf).into_owned()))
+}
+
+/// Read a dot-terminated article, undoing dot-stuffing.
+///
+/// Returns None as soon as the article exceeds [`MAX_ARTICLE_SIZE`]. The
+/// rest of it is left unread, so the caller must close the connection.
+async fn read_article<R>(reader: &mut R) -> Result<Option<Vec<u8>>>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut data = Vec::new();
+    loop {
+        // Room for the rest of the article plus a leading dot and CRLF.
+        let max = MAX_ARTICLE_SIZE - data.len() + 3;
+        let mut line = Vec::new();
+        let (n, overlong) = read_bounded(reader, &mut line, max).await?;
+        if n == 0 {
+            return Err(HobbsError::Io(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "connection closed during POST",
+            )));
+        }
+        if overlong {
+            return Ok(None);
+        }
+        while line.last().is_some_and(|&b| b == b'\n' || b == b'\r') {
+            line.pop();
+        }
+        if line == b"." {
+            break;
+        }
+        let content = line.strip_prefix(b".").unwrap_or(&line);
+        if data.len() + content.len() + 1 > MAX_ARTICLE_SIZE {
+            return Ok(None);
+        }
+        data.extend_from_slice(content);
+        data.push(b'\n');
+    }
+    Ok(Some(data))
+}
+
+/// Write a response, dot-stuffing and terminating any block.
+async fn write_response<W>(writer: &mut W, response: &Response) -> Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let mut out = String::new();
+    out.push_str(&response.status);
+    out.push_str("\r\n");
+    if let Some(lines) = &response.block {
+        for line in lines {
+            if line.starts_with('.') {
+                out.push('.');
+            }
+            out.push_str(line);
+            out.push_str("\r\n");
+        }
+        out.push_str(".\r\n");
+    }
+    writer.write_all(out.as_bytes()).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::hash_password;
+    use crate::board::{BoardRepository, NewBoard};
+    use crate::db::NewUser;
+    use tokio::io::{DuplexStream, ReadHalf, WriteHalf};
+
+    struct Client {
+        reader: BufReader<ReadHalf<DuplexStream>>,
+        writer: WriteHalf<DuplexStream>,
+    }
+
+    impl Client {
+        async fn line(&mut self) -> String {
+            read_line(&mut self.reader, usize::MAX).await.unwrap().unwrap()
+        }
+
+        async fn cmd(&mut self, command: &str) -> String {
+            self.send(command).await;
+            self.line().await
+        }
+
+        async fn send(&mut self, text: &str) {
+            self.writer
+                .write_all(format!("{}\r\n", text).as_bytes())
+                .await
+                .unwrap();
+        }
+
+        async fn block(&mut self) -> Vec<String> {
+            let mut lines = Vec::new();
+            loop {
+                let line = self.line().await;
+                if line == "." {
+                    return lines;
+                }
+                lines.push(line.strip_prefix('.').map(str::to_string).unwrap_or(line));
+            }
+        }
+    }
+
+    struct Fixture {
+        db: Arc<Database>,
+        general: Board,
+        members: Board,
+        flat: Board,
+    }
+
+    async fn setup() -> Fixture {
+        let db = Arc::new(Database::open_in_memory().await.unwrap());
+        let users = UserRepository::new(db.pool());
+        let hash = hash_password("password123").unwrap();
+        users
+            .create(&NewUser::new("alice", &hash, "アリス"))
+            .await
+            .unwrap();
+        users
+            .create(&NewUser::new("bob", &hash, "Bob"))
+            .await
+            .unwrap();
+
+        let boards = BoardRepository::new(db.pool());
+        let general = boards
+            .create(&NewBoard::new("general").with_min_read_role(Role::Guest))
+            .await
+            .unwrap();
+        let members = boards
+            .create(&NewBoard::new("会員専用").with_min_read_role(Role::Member))
+            .await
+            .unwrap();
+        let flat = boards
+            .create(
+                &NewBoard::new("notes")
+                    .with_board_type(BoardType::Flat)
+                    .with_min_read_role(Role::Guest),
+            )
+            .await
+            .unwrap();
+        Fixture {
+            db,
+            general,
+            members,
+            flat,
+        }
+    }
+
+    async fn connect(db: &Arc<Database>) -> Client {
+        let (client, server) = tokio::io::duplex(64 * 1024);
+        let options = Arc::new(NntpOptions {
+            domain: "bbs.example".to_string(),
+            ..Default::default()
+        });
+        let session = NntpSession::new(
+            Arc::clone(db),
+            options,
+            Arc::new(Mutex::new(LoginLimiter::new())),
+        );
+        tokio::spawn(async move { session.run(server).await });
+        let (reader, writer) = tokio::io::split(client);
+        let mut client = Client {
+            reader: BufReader::new(reader),
+            writer,
+        };
+        assert!(client.line().await.starts_with("200 "));
+        client
+    }
+
+    async fn login(client: &mut Client, username: &str) {
+        assert!(client
+            .cmd(&format!("AUTHINFO USER {}", username))
+            .await
+            .starts_with("381"));
+        assert!(client
+            .cmd("AUTHINFO PASS password123")
+            .await
+            .starts_with("281"));
+    }
+
+    async fn post(client: &mut Client, lines: &[&str]) -> String {
+        assert!(client.cmd("POST").await.starts_with("340"));
+        for line in lines {
+            client.send(line).await;
+        }
+        client.cmd(".").await
+    }
+
+    #[tokio::test]
+    async fn test_list_respects_read_role() {
+        let fx = setup().await;
+        let mut client = connect(&fx.db).await;
+
+        assert!(client.cmd("LIST").await.starts_with("215"));
+        let groups = client.block().await;
+        assert_eq!(groups, vec!["hobbs.general 0 1 n", "hobbs.notes 0 1 n"]);
+
+        let members_group = format!("hobbs.board{}", fx.members.id);
+        assert!(client
+            .cmd(&format!("GROUP {}", members_group))
+            .await
+            .starts_with("411"));
+
+        login(&mut client, "alice").await;
+        assert!(client.cmd("LIST ACTIVE hobbs.board*").await.starts_with("215"));
+        assert_eq!(
+            client.block().await,
+            vec![format!("{} 0 1 y", members_group)]
+        );
+        assert!(client.cmd("LIST NEWSGROUPS").await.starts_with("215"));
+        assert_eq!(client.block().await.len(), 3);
+        assert!(client.cmd("LIST OVERVIEW.FMT").await.starts_with("215"));
+        assert_eq!(client.block().await[0], "Subject:");
+        assert_eq!(client.cmd("QUIT").await, "205 Bye");
+    }
+
+    #[tokio::test]
+    async fn test_authinfo() {
+        let fx = setup().await;
+        let mut client = connect(&fx.db).await;
+
+        assert!(client.cmd("AUTHINFO PASS x").await.starts_with("482"));
+        assert!(client.cmd("AUTHINFO USER alice").await.starts_with("381"));
+        assert!(client.cmd("AUTHINFO PASS wrong").await.starts_with("481"));
+        assert!(client.cmd("POST").await.starts_with("480"));
+
+        login(&mut client, "alice").await;
+        assert!(client.cmd("AUTHINFO USER alice").await.starts_with("502"));
+        assert!(client.cmd("CAPABILITIES").await.starts_with("101"));
+        assert!(!client.block().await.contains(&"AUTHINFO USER".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_post_and_read_thread() {
+        let fx = setup().await;
+        let mut client = connect(&fx.db).await;
+        login(&mut client, "alice").await;
+
+        let response = post(
+            &mut client,
+            &[
+                "Newsgroups: hobbs.general",
+                "Subject: =?UTF-8?B?44GT44KT44Gr44Gh44Gv?=",
+                "Content-Type: text/plain; charset=UTF-8",
+                "",
+                "最初の投稿",
+                "..dot line",
+            ],
+        )
+        .await;
+        assert!(response.starts_with("240"), "{}", response);
+        let first_id = response.rsplit(' ').next().unwrap().to_string();
+
+        let response = post(
+            &mut client,
+            &[
+                "Newsgroups: hobbs.general",
+                "Subject: Re: こんにちは",
+                &format!("References: {}", first_id),
+                "",
+                "返信です",
+            ],
+        )
+        .await;
+        assert!(response.starts_with("240"), "{}", response);
+        let reply_id = response.rsplit(' ').next().unwrap().to_string();
+
+        // Read it back as a guest
+        let mut guest = connect(&fx.db).await;
+        let status = guest.cmd("GROUP hobbs.general").await;
+        let first = parse_message_id(&first_id, "bbs.example").unwrap();
+        let reply = parse_message_id(&reply_id, "bbs.example").unwrap();
+        assert_eq!(
+            status,
+            format!("211 2 {} {} hobbs.general", first, reply)
+        );
+
+        assert!(guest.cmd("ARTICLE").await.starts_with(&format!("220 {} ", first)));
+        let lines = guest.block().await;
+        assert!(lines.contains(&"Subject: =?UTF-8?B?44GT44KT44Gr44Gh44Gv?=".to_string()));
+        assert!(lines.contains(&"Content-Type: text/plain; charset=UTF-8".to_string()));
+        assert!(lines.iter().any(|l| l.starts_with("From: =?UTF-8?B?")));
+        assert_eq!(&lines[lines.len() - 2..], ["最初の投稿", ".dot line"]);
+
+        assert_eq!(
+            guest.cmd("NEXT").await,
+            format!("223 {} {}", reply, reply_id)
+        );
+        assert!(guest.cmd("HEAD").await.starts_with("221"));
+        let headers = guest.block().await;
+        assert!(headers.contains(&format!("References: {}", first_id)));
+        assert!(guest.cmd("NEXT").await.starts_with("421"));
+        assert!(guest.cmd("LAST").await.starts_with("223"));
+
+        assert!(guest.cmd(&format!("XOVER {}-", first)).await.starts_with("224"));
+        let overview = guest.block().await;
+        assert_eq!(overview.len(), 2);
+        let fields: Vec<&str> = overview[1].split('\t').collect();
+        assert_eq!(fields[4], reply_id);
+        assert_eq!(fields[5], first_id);
+
+        assert!(guest.cmd(&format!("BODY {}", reply_id)).await.starts_with("222"));
+        assert_eq!(guest.block().await, vec!["返信です"]);
+        assert!(guest.cmd("STAT 999999").await.starts_with("423"));
+        assert!(guest.cmd("STAT <nope@bbs.example>").await.starts_with("430"));
+
+        let posts = BoardService::new(&fx.db)
+            .list_all_threads(fx.general.id, Role::Member)
+            .await
+            .unwrap();
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].title, "こんにちは");
+        let stored = PostRepository::new(fx.db.pool())
+            .get_by_id(reply)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.parent_id, Some(first));
+    }
+
+    #[tokio::test]
+    async fn test_post_enforces_write_role_and_groups() {
+        let fx = setup().await;
+        BoardRepository::new(fx.db.pool())
+            .update(
+                fx.flat.id,
+                &crate::board::BoardUpdate::new().min_write_role(Role::SubOp),
+            )
+            .await
+            .unwrap();
+        let mut client = connect(&fx.db).await;
+        login(&mut client, "bob").await;
+
+        let response = post(
+            &mut client,
+            &["Newsgroups: hobbs.notes", "Subject: hi", "", "body"],
+        )
+        .await;
+        assert!(response.starts_with("441"), "{}", response);
+
+        let response = post(
+            &mut client,
+            &["Newsgroups: hobbs.missing", "Subject: hi", "", "body"],
+        )
+        .await;
+        assert!(response.starts_with("441"), "{}", response);
+
+        let response = post(&mut client, &["Newsgroups: hobbs.general", "Subject: hi", ""]).await;
+        assert!(response.starts_with("441"), "{}", response);
+    }
+
+    #[tokio::test]
+    async fn test_commands_without_group() {
+        let fx = setup().await;
+        let mut client = connect(&fx.db).await;
+        assert!(client.cmd("ARTICLE").await.starts_with("412"));
+        assert!(client.cmd("NEXT").await.starts_with("412"));
+        assert!(client.cmd("OVER 1-2").await.starts_with("412"));
+        assert!(client.cmd("FOO").await.starts_with("500"));
+        assert!(client.cmd("MODE READER").await.starts_with("200"));
+        assert!(client.cmd("DATE").await.starts_with("111"));
+        assert!(client.cmd("GROUP hobbs.general").await.starts_with("211 0 1 0"));
+        assert!(client.cmd("ARTICLE").await.starts_with("420"));
+        assert!(client.cmd("LISTGROUP").await.starts_with("211"));
+        assert!(client.block().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_read_line_drops_rest_of_overlong_line() {
+        let input = format!("{}\r\nQUIT\r\n", "A".repeat(10_000));
+        let mut reader = BufReader::with_capacity(64, input.as_bytes());
+        let line = read_line(&mut reader, 16).await.unwrap().unwrap();
+        assert_eq!(line, "A".repeat(16));
+        let line = read_line(&mut reader, 16).await.unwrap().unwrap();
+        assert_eq!(line, "QUIT");
+        assert!(read_line(&mut reader, 16).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_read_article_stops_at_size_limit() {
+        let input = "Subject: hi\r\n\r\n..x\r\n.\r\n";
+        let mut reader = BufReader::new(input.as_bytes());
+        let data = read_article(&mut reader).await.unwrap().unwrap();
+        assert_eq!(data, b"Subject: hi\n\n.x\n");
+
+        let input = format!("{}\r\n.\r\n", "A".repeat(MAX_ARTICLE_SIZE + 1));
+        let mut reader = BufReader::new(input.as_bytes());
+        assert!(read_article(&mut reader).await.unwrap().is_none());
+
+        let line = format!("{}\r\n", "A".repeat(1000));
+        let input = line.repeat(MAX_ARTICLE_SIZE / 1000 + 1);
+        let mut reader = BufReader::new(input.as_bytes());
+        assert!(read_article(&mut reader).await.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("5"), Some((5, 5)));
+        assert_eq!(parse_range("5-"), Some((5, i64::MAX)));
+        assert_eq!(parse_range("5-9"), Some((5, 9)));
+        assert_eq!(parse_range("x"), None);
+        assert_eq!(parse_range("-5"), None);
+    }
+}
@@ -0,0 +1,79 @@
+//! Live notification delivery.
+//!
+//! The notification job publishes queued notifications here. Online Telnet
+//! sessions and web sockets subscribe and pick out their own user's.
+
+use tokio::sync::broadcast;
+
+use super::types::Notification;
+
+/// Number of notifications buffered for slow receivers.
+const HUB_CAPACITY: usize = 256;
+
+/// Broadcast channel for new notifications.
+#[derive(Debug, Clone)]
+pub struct NotificationHub {
+    sender: broadcast::Sender<Notification>,
+}
+
+impl NotificationHub {
+    /// Create a new NotificationHub.
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(HUB_CAPACITY);
+        Self { sender }
+    }
+
+    /// Publish a notification to all receivers.
+    ///
+    /// Notifications published while nobody listens are dropped; they stay
+    /// queued in the database for the next login.
+    pub fn publish(&self, notification: Notification) {
+        let _ = self.sender.send(notification);
+    }
+
+    /// Subscribe to notifications published from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<Notification> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for NotificationHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification(user_id: i64) -> Notification {
+        Notification {
+            id: 1,
+            user_id,
+            post_id: 1,
+            board_id: 1,
+            board_name: "general".to_string(),
+            thread_id: None,
+            title: "Hello".to_string(),
+            author: "alice".to_string(),
+            digest: false,
+            created_at: "2024-01-01 00:00:00".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_publish_and_receive() {
+        let hub = NotificationHub::new();
+        let mut rx = hub.subscribe();
+
+        hub.publish(notification(42));
+        assert_eq!(rx.recv().await.unwrap().user_id, 42);
+    }
+
+    #[test]
+    fn test_publish_without_receivers() {
+        let hub = NotificationHub::new();
+        hub.publish(notification(1));
+    }
+}
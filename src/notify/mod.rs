@@ -0,0 +1,19 @@
+//! Board and thread subscriptions for HOBBS.
+//!
+//! Users subscribe to boards or threads. A background job queues a
+//! notification for each new post in a subscribed target. Immediate
+//! notifications are shown at the next login, pushed live to online Telnet
+//! sessions and web sockets, while digest notifications are bundled into a
+//! daily system mail.
+
+pub mod hub;
+pub mod repository;
+pub mod service;
+pub mod types;
+pub mod updater;
+
+pub use hub::NotificationHub;
+pub use repository::{NotificationRepository, Subscriber, SubscriptionRepository};
+pub use service::{NotificationService, DIGEST_MAIL_SUBJECT};
+pub use types::{Notification, Subscription, SubscriptionTarget};
+pub use updater::{start_notifications_with_config, NotificationUpdater};
@@ -0,0 +1,372 @@
+//! Subscription and notification repositories for HOBBS.
+
+use super::types::{Notification, Subscription, SubscriptionTarget};
+use crate::board::Post;
+use crate::db::DbPool;
+use crate::{HobbsError, Result};
+
+// SQL datetime function for current timestamp
+#[cfg(feature = "sqlite")]
+const SQL_NOW: &str = "datetime('now')";
+#[cfg(feature = "postgres")]
+const SQL_NOW: &str = "TO_CHAR(NOW(), 'YYYY-MM-DD HH24:MI:SS')";
+
+const SUBSCRIPTION_SELECT: &str =
+    "SELECT s.id, s.user_id, s.board_id, s.thread_id, s.digest,
+            COALESCE(b.name, t.title, '') AS name, s.created_at
+     FROM subscriptions s
+     LEFT JOIN boards b ON b.id = s.board_id
+     LEFT JOIN threads t ON t.id = s.thread_id";
+
+const NOTIFICATION_SELECT: &str =
+    "SELECT n.id, n.user_id, n.post_id, p.board_id, b.name AS board_name, p.thread_id,
+            COALESCE(t.title, p.title, '') AS title, u.nickname AS author,
+            n.digest, n.created_at
+     FROM notifications n
+     JOIN posts p ON p.id = n.post_id
+     JOIN boards b ON b.id = p.board_id
+     JOIN users u ON u.id = p.author_id
+     LEFT JOIN threads t ON t.id = p.thread_id";
+
+/// A user subscribed to a post's board or thread.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Subscriber {
+    /// ID of the subscribing user.
+    pub user_id: i64,
+    /// Role of the user, as stored.
+    pub role: String,
+    /// Whether the subscription is in digest mode.
+    pub digest: bool,
+}
+
+/// Repository for subscriptions.
+pub struct SubscriptionRepository<'a> {
+    pool: &'a DbPool,
+}
+
+impl<'a> SubscriptionRepository<'a> {
+    /// Create a new SubscriptionRepository with the given database pool.
+    pub fn new(pool: &'a DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Subscribe a user to a target, or change the mode of an existing
+    /// subscription.
+    pub async fn subscribe(
+        &self,
+        user_id: i64,
+        target: SubscriptionTarget,
+        digest: bool,
+    ) -> Result<Subscription> {
+        if let Some(existing) = self.get_by_target(user_id, target).await? {
+            self.set_digest(existing.id, digest).await?;
+            return self.require(existing.id).await;
+        }
+
+        let id: i64 = sqlx::query_scalar(
+            "INSERT INTO subscriptions (user_id, board_id, thread_id, digest)
+             VALUES ($1, $2, $3, $4)
+             RETURNING id",
+        )
+        .bind(user_id)
+        .bind(target.board_id())
+        .bind(target.thread_id())
+        .bind(digest)
+        .fetch_one(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        self.require(id).await
+    }
+
+    async fn require(&self, id: i64) -> Result<Subscription> {
+        self.get_by_id(id)
+            .await?
+            .ok_or_else(|| HobbsError::NotFound("subscription".to_string()))
+    }
+
+    /// Get a subscription by ID.
+    pub async fn get_by_id(&self, id: i64) -> Result<Option<Subscription>> {
+        let query = format!("{SUBSCRIPTION_SELECT} WHERE s.id = $1");
+        sqlx::query_as::<_, Subscription>(&query)
+            .bind(id)
+            .fetch_optional(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// Get a user's subscription to a target.
+    pub async fn get_by_target(
+        &self,
+        user_id: i64,
+        target: SubscriptionTarget,
+    ) -> Result<Option<Subscription>> {
+        let query = match target {
+            SubscriptionTarget::Board(_) => {
+                format!("{SUBSCRIPTION_SELECT} WHERE s.user_id = $1 AND s.board_id = $2")
+            }
+            SubscriptionTarget::Thread(_) => {
+                format!("{SUBSCRIPTION_SELECT} WHERE s.user_id = $1 AND s.thread_id = $2")
+            }
+        };
+        let target_id = target.board_id().or(target.thread_id());
+        sqlx::query_as::<_, Subscription>(&query)
+            .bind(user_id)
+            .bind(target_id)
+            .fetch_optional(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// List a user's subscriptions, oldest first.
+    pub async fn list_by_user(&self, user_id: i64) -> Result<Vec<Subscription>> {
+        let query = format!("{SUBSCRIPTION_SELECT} WHERE s.user_id = $1 ORDER BY s.id");
+        sqlx::query_as::<_, Subscription>(&query)
+            .bind(user_id)
+            .fetch_all(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// Switch a subscription between immediate and digest delivery.
+    ///
+    /// Returns false if the subscription does not exist.
+    pub async fn set_digest(&self, id: i64, digest: bool) -> Result<bool> {
+        let result = sqlx::query("UPDATE subscriptions SET digest = $1 WHERE id = $2")
+            .bind(digest)
+            .bind(id)
+            .execute(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Delete a subscription.
+    ///
+    /// Returns false if the subscription does not exist.
+    pub async fn delete(&self, id: i64) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM subscriptions WHERE id = $1")
+            .bind(id)
+            .execute(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// List subscribers of a post's board or thread.
+    ///
+    /// A user subscribed to both appears twice, once per subscription.
+    pub async fn list_subscribers(
+        &self,
+        board_id: i64,
+        thread_id: Option<i64>,
+    ) -> Result<Vec<Subscriber>> {
+        sqlx::query_as::<_, Subscriber>(
+            "SELECT s.user_id, u.role, s.digest
+             FROM subscriptions s
+             JOIN users u ON u.id = s.user_id
+             WHERE u.is_active = $3 AND (s.board_id = $1 OR s.thread_id = $2)
+             ORDER BY s.user_id",
+        )
+        .bind(board_id)
+        .bind(thread_id)
+        .bind(true)
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+}
+
+/// Repository for queued notifications and the notification job state.
+pub struct NotificationRepository<'a> {
+    pool: &'a DbPool,
+}
+
+impl<'a> NotificationRepository<'a> {
+    /// Create a new NotificationRepository with the given database pool.
+    pub fn new(pool: &'a DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Queue a notification.
+    ///
+    /// Returns None if the user was already notified about the post.
+    pub async fn create(
+        &self,
+        user_id: i64,
+        post_id: i64,
+        digest: bool,
+    ) -> Result<Option<Notification>> {
+        let id: Option<i64> = sqlx::query_scalar(
+            "INSERT INTO notifications (user_id, post_id, digest) VALUES ($1, $2, $3)
+             ON CONFLICT (user_id, post_id) DO NOTHING
+             RETURNING id",
+        )
+        .bind(user_id)
+        .bind(post_id)
+        .bind(digest)
+        .fetch_optional(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        match id {
+            Some(id) => self.get_by_id(id).await,
+            None => Ok(None),
+        }
+    }
+
+    /// Get a notification by ID.
+    pub async fn get_by_id(&self, id: i64) -> Result<Option<Notification>> {
+        let query = format!("{NOTIFICATION_SELECT} WHERE n.id = $1");
+        sqlx::query_as::<_, Notification>(&query)
+            .bind(id)
+            .fetch_optional(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// List a user's undelivered immediate notifications, oldest first.
+    pub async fn list_pending(&self, user_id: i64) -> Result<Vec<Notification>> {
+        let query = format!(
+            "{NOTIFICATION_SELECT}
+             WHERE n.user_id = $1 AND n.digest = $2 AND n.delivered_at IS NULL
+             ORDER BY n.id"
+        );
+        sqlx::query_as::<_, Notification>(&query)
+            .bind(user_id)
+            .bind(false)
+            .fetch_all(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// List undelivered digest notifications of all users, grouped by user.
+    pub async fn list_digest_pending(&self) -> Result<Vec<Notification>> {
+        let query = format!(
+            "{NOTIFICATION_SELECT}
+             WHERE n.digest = $1 AND n.delivered_at IS NULL
+             ORDER BY n.user_id, n.id"
+        );
+        sqlx::query_as::<_, Notification>(&query)
+            .bind(true)
+            .fetch_all(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// Mark one of a user's notifications as delivered.
+    pub async fn mark_delivered(&self, user_id: i64, id: i64) -> Result<bool> {
+        let query = format!(
+            "UPDATE notifications SET delivered_at = {SQL_NOW}
+             WHERE id = $1 AND user_id = $2 AND delivered_at IS NULL"
+        );
+        let result = sqlx::query(&query)
+            .bind(id)
+            .bind(user_id)
+            .execute(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Mark a user's notifications of one kind up to `max_id` as delivered.
+    ///
+    /// Returns the number of notifications marked.
+    pub async fn mark_delivered_up_to(
+        &self,
+        user_id: i64,
+        max_id: i64,
+        digest: bool,
+    ) -> Result<u64> {
+        let query = format!(
+            "UPDATE notifications SET delivered_at = {SQL_NOW}
+             WHERE user_id = $1 AND id <= $2 AND digest = $3 AND delivered_at IS NULL"
+        );
+        let result = sqlx::query(&query)
+            .bind(user_id)
+            .bind(max_id)
+            .bind(digest)
+            .execute(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Get the ID of the last post checked for subscribers.
+    ///
+    /// Returns None before the first run.
+    pub async fn get_last_post_id(&self) -> Result<Option<i64>> {
+        sqlx::query_scalar("SELECT last_post_id FROM notification_state WHERE id = 1")
+            .fetch_optional(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// Set the ID of the last post checked for subscribers.
+    pub async fn set_last_post_id(&self, post_id: i64) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO notification_state (id, last_post_id) VALUES (1, $1)
+             ON CONFLICT(id) DO UPDATE SET last_post_id = excluded.last_post_id",
+        )
+        .bind(post_id)
+        .execute(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Get the local date (YYYY-MM-DD) the digest was last sent on.
+    pub async fn get_last_digest_on(&self) -> Result<Option<String>> {
+        let date: Option<Option<String>> =
+            sqlx::query_scalar("SELECT last_digest_on FROM notification_state WHERE id = 1")
+                .fetch_optional(self.pool)
+                .await
+                .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(date.flatten())
+    }
+
+    /// Record the local date the digest was sent on.
+    pub async fn set_last_digest_on(&self, date: &str) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO notification_state (id, last_digest_on) VALUES (1, $1)
+             ON CONFLICT(id) DO UPDATE SET last_digest_on = excluded.last_digest_on",
+        )
+        .bind(date)
+        .execute(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Get the ID of the newest post (0 if there are none).
+    pub async fn latest_post_id(&self) -> Result<i64> {
+        let id: Option<i64> = sqlx::query_scalar("SELECT MAX(id) FROM posts")
+            .fetch_one(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(id.unwrap_or(0))
+    }
+
+    /// List posts newer than `after_id`, oldest first.
+    pub async fn list_posts_after(&self, after_id: i64) -> Result<Vec<Post>> {
+        sqlx::query_as::<_, Post>(
+            "SELECT id, board_id, thread_id, parent_id, author_id, title, body,
+                    created_at, edited_at, edited_by
+             FROM posts
+             WHERE id > $1
+             ORDER BY id ASC",
+        )
+        .bind(after_id)
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+}
@@ -0,0 +1,440 @@
+//! Notification service for HOBBS.
+//!
+//! Subscriptions are checked against new posts by a background job rather
+//! than at posting time, so posts from every front end (Telnet, Web API,
+//! NNTP, QWK, echomail and approved moderation) are covered alike.
+
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+
+use super::repository::{NotificationRepository, SubscriptionRepository};
+use super::types::{Notification, Subscription, SubscriptionTarget};
+use crate::board::{Board, BoardRepository, BoardService};
+use crate::datetime::format_datetime;
+use crate::db::{Database, Role};
+use crate::mail::SystemMailService;
+use crate::{HobbsError, Result};
+
+/// Subject of the daily digest mail.
+pub const DIGEST_MAIL_SUBJECT: &str = "購読中の新着投稿";
+
+/// Service for subscriptions and notifications.
+pub struct NotificationService<'a> {
+    db: &'a Database,
+}
+
+impl<'a> NotificationService<'a> {
+    /// Create a new NotificationService with the given database reference.
+    pub fn new(db: &'a Database) -> Self {
+        Self { db }
+    }
+
+    /// Subscribe to a board or thread the user can read.
+    ///
+    /// Subscribing again only changes the delivery mode.
+    pub async fn subscribe(
+        &self,
+        user_id: i64,
+        user_role: Role,
+        target: SubscriptionTarget,
+        digest: bool,
+    ) -> Result<Subscription> {
+        let boards = BoardService::new(self.db);
+        match target {
+            SubscriptionTarget::Board(board_id) => {
+                boards.get_board(board_id, user_role).await?;
+            }
+            SubscriptionTarget::Thread(thread_id) => {
+                let thread = boards.get_thread(thread_id, user_role).await?;
+                boards.get_board(thread.board_id, user_role).await?;
+            }
+        }
+
+        SubscriptionRepository::new(self.db.pool())
+            .subscribe(user_id, target, digest)
+            .await
+    }
+
+    /// Get one of the user's subscriptions.
+    pub async fn get_subscription(&self, user_id: i64, id: i64) -> Result<Subscription> {
+        SubscriptionRepository::new(self.db.pool())
+            .get_by_id(id)
+            .await?
+            .filter(|s| s.user_id == user_id)
+            .ok_or_else(|| HobbsError::NotFound("subscription".to_string()))
+    }
+
+    /// Find the user's subscription to a target.
+    pub async fn find_subscription(
+        &self,
+        user_id: i64,
+        target: SubscriptionTarget,
+    ) -> Result<Option<Subscription>> {
+        SubscriptionRepository::new(self.db.pool())
+            .get_by_target(user_id, target)
+            .await
+    }
+
+    /// List the user's subscriptions.
+    pub async fn list_subscriptions(&self, user_id: i64) -> Result<Vec<Subscription>> {
+        SubscriptionRepository::new(self.db.pool())
+            .list_by_user(user_id)
+            .await
+    }
+
+    /// Switch one of the user's subscriptions between immediate and digest
+    /// delivery.
+    pub async fn set_digest(&self, user_id: i64, id: i64, digest: bool) -> Result<Subscription> {
+        self.get_subscription(user_id, id).await?;
+        SubscriptionRepository::new(self.db.pool())
+            .set_digest(id, digest)
+            .await?;
+        self.get_subscription(user_id, id).await
+    }
+
+    /// Delete one of the user's subscriptions.
+    pub async fn unsubscribe(&self, user_id: i64, id: i64) -> Result<()> {
+        self.get_subscription(user_id, id).await?;
+        SubscriptionRepository::new(self.db.pool()).delete(id).await?;
+        Ok(())
+    }
+
+    /// List the user's undelivered notifications, oldest first.
+    ///
+    /// Digest notifications are left for the daily mail.
+    pub async fn list_pending(&self, user_id: i64) -> Result<Vec<Notification>> {
+        NotificationRepository::new(self.db.pool())
+            .list_pending(user_id)
+            .await
+    }
+
+    /// Mark one of the user's notifications as delivered.
+    pub async fn mark_delivered(&self, user_id: i64, id: i64) -> Result<bool> {
+        NotificationRepository::new(self.db.pool())
+            .mark_delivered(user_id, id)
+            .await
+    }
+
+    /// Mark all of the user's undelivered notifications as delivered.
+    pub async fn mark_all_delivered(&self, user_id: i64) -> Result<u64> {
+        NotificationRepository::new(self.db.pool())
+            .mark_delivered_up_to(user_id, i64::MAX, false)
+            .await
+    }
+
+    /// Queue notifications for posts made since the last run.
+    ///
+    /// The first run only records the newest post, so existing posts do not
+    /// flood subscribers. A user subscribed to both a board and one of its
+    /// threads is notified once, immediately unless both are digests.
+    /// Authors are not notified about their own posts, and subscribers who
+    /// can no longer read the board are skipped.
+    ///
+    /// Returns the queued immediate notifications for live delivery.
+    pub async fn queue_new_posts(&self) -> Result<Vec<Notification>> {
+        let repo = NotificationRepository::new(self.db.pool());
+        let Some(last_post_id) = repo.get_last_post_id().await? else {
+            repo.set_last_post_id(repo.latest_post_id().await?).await?;
+            return Ok(Vec::new());
+        };
+
+        let subscriptions = SubscriptionRepository::new(self.db.pool());
+        let board_repo = BoardRepository::new(self.db.pool());
+        let mut boards: HashMap<i64, Option<Board>> = HashMap::new();
+        let mut queued = Vec::new();
+
+        for post in repo.list_posts_after(last_post_id).await? {
+            if let Entry::Vacant(entry) = boards.entry(post.board_id) {
+                entry.insert(board_repo.get_by_id(post.board_id).await?);
+            }
+            let Some(board) = boards[&post.board_id].as_ref() else {
+                continue;
+            };
+
+            // user_id -> digest; an immediate subscription wins
+            let mut recipients: BTreeMap<i64, bool> = BTreeMap::new();
+            for subscriber in subscriptions
+                .list_subscribers(post.board_id, post.thread_id)
+                .await?
+            {
+                let role = Role::from_str(&subscriber.role).unwrap_or(Role::Guest);
+                if subscriber.user_id == post.author_id || !board.can_read(role) {
+                    continue;
+                }
+                recipients
+                    .entry(subscriber.user_id)
+                    .and_modify(|digest| *digest &= subscriber.digest)
+                    .or_insert(subscriber.digest);
+            }
+
+            for (user_id, digest) in recipients {
+                if let Some(notification) = repo.create(user_id, post.id, digest).await? {
+                    if !digest {
+                        queued.push(notification);
+                    }
+                }
+            }
+            repo.set_last_post_id(post.id).await?;
+        }
+
+        Ok(queued)
+    }
+
+    /// Mail each user their undelivered digest notifications.
+    ///
+    /// Returns the number of mails sent.
+    pub async fn send_digests(&self, timezone: &str) -> Result<usize> {
+        let repo = NotificationRepository::new(self.db.pool());
+        let mut by_user: BTreeMap<i64, Vec<Notification>> = BTreeMap::new();
+        for notification in repo.list_digest_pending().await? {
+            by_user
+                .entry(notification.user_id)
+                .or_default()
+                .push(notification);
+        }
+
+        let mail = SystemMailService::new(self.db);
+        let mut sent = 0;
+        for (user_id, notifications) in by_user {
+            let body = digest_mail_body(&notifications, timezone);
+            if mail
+                .send_notification(user_id, DIGEST_MAIL_SUBJECT, &body)
+                .await?
+            {
+                sent += 1;
+            }
+            let max_id = notifications.iter().map(|n| n.id).max().unwrap_or(0);
+            repo.mark_delivered_up_to(user_id, max_id, true).await?;
+        }
+
+        Ok(sent)
+    }
+}
+
+/// Build the body of the digest mail.
+fn digest_mail_body(notifications: &[Notification], timezone: &str) -> String {
+    let mut body = format!(
+        "購読中の掲示板・スレッドに{}件の新しい投稿がありました。\n\n",
+        notifications.len()
+    );
+    for n in notifications {
+        let time = format_datetime(&n.created_at, timezone, "%m/%d %H:%M");
+        body.push_str(&format!(
+            "{} [{}] {} - {}\n",
+            time, n.board_name, n.title, n.author
+        ));
+    }
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::{BoardType, NewBoard};
+    use crate::db::{NewUser, User, UserRepository};
+    use crate::mail::MailRepository;
+
+    async fn setup_db() -> Database {
+        Database::open_in_memory().await.unwrap()
+    }
+
+    async fn create_user(db: &Database, name: &str, role: Role) -> User {
+        UserRepository::new(db.pool())
+            .create(&NewUser::new(name, "hash", name).with_role(role))
+            .await
+            .unwrap()
+    }
+
+    async fn create_board(db: &Database, name: &str) -> Board {
+        BoardRepository::new(db.pool())
+            .create(&NewBoard::new(name))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_and_unsubscribe() {
+        let db = setup_db().await;
+        let user = create_user(&db, "alice", Role::Member).await;
+        let board = create_board(&db, "general").await;
+        let service = NotificationService::new(&db);
+
+        let sub = service
+            .subscribe(user.id, user.role, SubscriptionTarget::Board(board.id), false)
+            .await
+            .unwrap();
+        assert_eq!(sub.target(), SubscriptionTarget::Board(board.id));
+        assert_eq!(sub.name, "general");
+        assert!(!sub.digest);
+
+        // Subscribing again switches the mode instead of duplicating
+        let again = service
+            .subscribe(user.id, user.role, SubscriptionTarget::Board(board.id), true)
+            .await
+            .unwrap();
+        assert_eq!(again.id, sub.id);
+        assert!(again.digest);
+        assert_eq!(service.list_subscriptions(user.id).await.unwrap().len(), 1);
+
+        service.unsubscribe(user.id, sub.id).await.unwrap();
+        assert!(service.list_subscriptions(user.id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_requires_read_permission() {
+        let db = setup_db().await;
+        let user = create_user(&db, "alice", Role::Member).await;
+        let board = BoardRepository::new(db.pool())
+            .create(&NewBoard::new("staff").with_min_read_role(Role::SubOp))
+            .await
+            .unwrap();
+        let service = NotificationService::new(&db);
+
+        let result = service
+            .subscribe(user.id, user.role, SubscriptionTarget::Board(board.id), false)
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_other_users_subscription_is_not_found() {
+        let db = setup_db().await;
+        let alice = create_user(&db, "alice", Role::Member).await;
+        let bob = create_user(&db, "bob", Role::Member).await;
+        let board = create_board(&db, "general").await;
+        let service = NotificationService::new(&db);
+
+        let sub = service
+            .subscribe(alice.id, alice.role, SubscriptionTarget::Board(board.id), false)
+            .await
+            .unwrap();
+        assert!(service.unsubscribe(bob.id, sub.id).await.is_err());
+        assert!(service.set_digest(bob.id, sub.id, true).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_queue_new_posts() {
+        let db = setup_db().await;
+        let alice = create_user(&db, "alice", Role::Member).await;
+        let bob = create_user(&db, "bob", Role::Member).await;
+        let carol = create_user(&db, "carol", Role::Member).await;
+        let board = create_board(&db, "general").await;
+        let boards = BoardService::new(&db);
+        let service = NotificationService::new(&db);
+
+        let thread = boards
+            .create_thread(board.id, "Hello", alice.id, alice.role)
+            .await
+            .unwrap();
+        boards
+            .create_thread_post(thread.id, alice.id, "first", alice.role)
+            .await
+            .unwrap();
+
+        // The first run only sets the pointer
+        assert!(service.queue_new_posts().await.unwrap().is_empty());
+
+        service
+            .subscribe(bob.id, bob.role, SubscriptionTarget::Board(board.id), false)
+            .await
+            .unwrap();
+        service
+            .subscribe(bob.id, bob.role, SubscriptionTarget::Thread(thread.id), true)
+            .await
+            .unwrap();
+        service
+            .subscribe(carol.id, carol.role, SubscriptionTarget::Thread(thread.id), true)
+            .await
+            .unwrap();
+        service
+            .subscribe(alice.id, alice.role, SubscriptionTarget::Thread(thread.id), false)
+            .await
+            .unwrap();
+
+        let post = boards
+            .create_reply(thread.id, None, alice.id, "second", alice.role)
+            .await
+            .unwrap();
+
+        // Bob is notified once and immediately; Carol waits for the digest;
+        // Alice wrote the post
+        let queued = service.queue_new_posts().await.unwrap();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].user_id, bob.id);
+        assert_eq!(queued[0].post_id, post.id);
+        assert_eq!(queued[0].title, "Hello");
+        assert_eq!(queued[0].author, "alice");
+        assert_eq!(queued[0].board_name, "general");
+
+        // Nothing new on the next run
+        assert!(service.queue_new_posts().await.unwrap().is_empty());
+
+        assert_eq!(service.list_pending(bob.id).await.unwrap().len(), 1);
+        assert!(service.list_pending(carol.id).await.unwrap().is_empty());
+        assert!(service.list_pending(alice.id).await.unwrap().is_empty());
+
+        assert_eq!(service.mark_all_delivered(bob.id).await.unwrap(), 1);
+        assert!(service.list_pending(bob.id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_send_digests() {
+        let db = setup_db().await;
+        create_user(&db, "sysop", Role::SysOp).await;
+        let alice = create_user(&db, "alice", Role::Member).await;
+        let bob = create_user(&db, "bob", Role::Member).await;
+        let board = BoardRepository::new(db.pool())
+            .create(&NewBoard::new("news").with_board_type(BoardType::Flat))
+            .await
+            .unwrap();
+        let boards = BoardService::new(&db);
+        let service = NotificationService::new(&db);
+
+        service.queue_new_posts().await.unwrap();
+        service
+            .subscribe(bob.id, bob.role, SubscriptionTarget::Board(board.id), true)
+            .await
+            .unwrap();
+
+        boards
+            .create_flat_post(board.id, alice.id, "Topic 1", "body", alice.role)
+            .await
+            .unwrap();
+        boards
+            .create_flat_post(board.id, alice.id, "Topic 2", "body", alice.role)
+            .await
+            .unwrap();
+        service.queue_new_posts().await.unwrap();
+
+        assert_eq!(service.send_digests("Asia/Tokyo").await.unwrap(), 1);
+        let inbox = MailRepository::new(db.pool())
+            .list_inbox(bob.id)
+            .await
+            .unwrap();
+        assert_eq!(inbox.len(), 1);
+        assert_eq!(inbox[0].subject, DIGEST_MAIL_SUBJECT);
+
+        // Delivered notifications are not mailed again
+        assert_eq!(service.send_digests("Asia/Tokyo").await.unwrap(), 0);
+    }
+
+    #[test]
+    fn test_digest_mail_body() {
+        let notifications = vec![Notification {
+            id: 1,
+            user_id: 2,
+            post_id: 3,
+            board_id: 4,
+            board_name: "news".to_string(),
+            thread_id: None,
+            title: "Topic".to_string(),
+            author: "alice".to_string(),
+            digest: true,
+            created_at: "2024-03-05 03:04:00".to_string(),
+        }];
+        let body = digest_mail_body(&notifications, "Asia/Tokyo");
+        assert!(body.contains("1件"));
+        assert!(body.contains("03/05 12:04 [news] Topic - alice"));
+    }
+}
@@ -0,0 +1,84 @@
+//! Subscription and notification types.
+
+/// What a subscription follows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubscriptionTarget {
+    /// Every new post in a board.
+    Board(i64),
+    /// New replies in a thread.
+    Thread(i64),
+}
+
+impl SubscriptionTarget {
+    /// Get the board ID, if this targets a board.
+    pub fn board_id(&self) -> Option<i64> {
+        match self {
+            SubscriptionTarget::Board(id) => Some(*id),
+            SubscriptionTarget::Thread(_) => None,
+        }
+    }
+
+    /// Get the thread ID, if this targets a thread.
+    pub fn thread_id(&self) -> Option<i64> {
+        match self {
+            SubscriptionTarget::Board(_) => None,
+            SubscriptionTarget::Thread(id) => Some(*id),
+        }
+    }
+}
+
+/// A user's subscription to a board or thread.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Subscription {
+    /// Unique subscription ID.
+    pub id: i64,
+    /// ID of the subscribing user.
+    pub user_id: i64,
+    /// Subscribed board (None for thread subscriptions).
+    pub board_id: Option<i64>,
+    /// Subscribed thread (None for board subscriptions).
+    pub thread_id: Option<i64>,
+    /// Bundle notifications into the daily digest mail instead of
+    /// delivering them one by one.
+    pub digest: bool,
+    /// Board name or thread title.
+    pub name: String,
+    /// Subscription timestamp.
+    pub created_at: String,
+}
+
+impl Subscription {
+    /// Get the subscribed target.
+    pub fn target(&self) -> SubscriptionTarget {
+        match (self.board_id, self.thread_id) {
+            (_, Some(thread_id)) => SubscriptionTarget::Thread(thread_id),
+            (board_id, None) => SubscriptionTarget::Board(board_id.unwrap_or_default()),
+        }
+    }
+}
+
+/// A queued notification about a new post, with the details needed to
+/// show it.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Notification {
+    /// Unique notification ID.
+    pub id: i64,
+    /// ID of the user to notify.
+    pub user_id: i64,
+    /// ID of the new post.
+    pub post_id: i64,
+    /// ID of the board the post is in.
+    pub board_id: i64,
+    /// Name of the board.
+    pub board_name: String,
+    /// ID of the thread the post is in (None for flat boards).
+    pub thread_id: Option<i64>,
+    /// Thread title, or the post title on flat boards.
+    pub title: String,
+    /// Nickname of the post author.
+    pub author: String,
+    /// Whether this notification waits for the digest mail.
+    pub digest: bool,
+    /// Timestamp the notification was queued.
+    pub created_at: String,
+}
@@ -0,0 +1,190 @@
+//! Notification background task for HOBBS.
+//!
+//! Periodically queues notifications for new posts, publishes the immediate
+//! ones to the hub and sends the daily digest mail.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use tokio::time::{interval, Duration};
+use tracing::{debug, error, info};
+
+use super::hub::NotificationHub;
+use super::repository::NotificationRepository;
+use super::service::NotificationService;
+use crate::config::Config;
+use crate::datetime::format_utc_datetime;
+use crate::db::Database;
+
+/// Notification background updater.
+pub struct NotificationUpdater {
+    db: Arc<Database>,
+    hub: Arc<NotificationHub>,
+    interval: Duration,
+    digest_hour: u32,
+    timezone: String,
+}
+
+impl NotificationUpdater {
+    /// Create a new NotificationUpdater.
+    pub fn new(
+        db: Arc<Database>,
+        hub: Arc<NotificationHub>,
+        interval_secs: u64,
+        digest_hour: u32,
+        timezone: impl Into<String>,
+    ) -> Self {
+        Self {
+            db,
+            hub,
+            interval: Duration::from_secs(interval_secs),
+            digest_hour,
+            timezone: timezone.into(),
+        }
+    }
+
+    /// Run the updater loop.
+    pub async fn run(&self) {
+        info!(
+            "Notification updater started (interval: {} seconds)",
+            self.interval.as_secs()
+        );
+
+        let mut timer = interval(self.interval);
+        loop {
+            timer.tick().await;
+            self.run_once().await;
+        }
+    }
+
+    /// Queue and publish notifications, then send the digest if it is due.
+    pub async fn run_once(&self) {
+        let service = NotificationService::new(&self.db);
+
+        match service.queue_new_posts().await {
+            Ok(queued) => {
+                if !queued.is_empty() {
+                    debug!("Queued {} notifications", queued.len());
+                }
+                for notification in queued {
+                    self.hub.publish(notification);
+                }
+            }
+            Err(e) => error!("Failed to queue notifications: {}", e),
+        }
+
+        if let Err(e) = self.send_digest_if_due(&service).await {
+            error!("Failed to send digest mail: {}", e);
+        }
+    }
+
+    async fn send_digest_if_due(&self, service: &NotificationService<'_>) -> crate::Result<()> {
+        let now = Utc::now();
+        let today = format_utc_datetime(&now, &self.timezone, "%Y-%m-%d");
+        let hour: u32 = format_utc_datetime(&now, &self.timezone, "%H")
+            .parse()
+            .unwrap_or(0);
+
+        let repo = NotificationRepository::new(self.db.pool());
+        let last = repo.get_last_digest_on().await?;
+        if !digest_due(&today, hour, last.as_deref(), self.digest_hour) {
+            return Ok(());
+        }
+
+        let sent = service.send_digests(&self.timezone).await?;
+        repo.set_last_digest_on(&today).await?;
+        if sent > 0 {
+            info!("Sent {} digest mails", sent);
+        }
+        Ok(())
+    }
+}
+
+/// Check whether the daily digest should be sent now.
+fn digest_due(today: &str, hour: u32, last_sent_on: Option<&str>, digest_hour: u32) -> bool {
+    hour >= digest_hour && last_sent_on != Some(today)
+}
+
+/// Start the notification updater with configuration.
+///
+/// This function checks if notifications are enabled in the config before
+/// starting. Returns true if the updater was started, false if it is
+/// disabled.
+pub fn start_notifications_with_config(
+    db: Arc<Database>,
+    hub: Arc<NotificationHub>,
+    config: &Config,
+) -> bool {
+    if !config.notifications.enabled {
+        info!("Notifications are disabled in configuration");
+        return false;
+    }
+
+    let updater = NotificationUpdater::new(
+        db,
+        hub,
+        config.notifications.interval_secs,
+        config.notifications.digest_hour,
+        config.server.timezone.clone(),
+    );
+    tokio::task::spawn_local(async move {
+        updater.run().await;
+    });
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_start_disabled() {
+        let db = Arc::new(Database::open_in_memory().await.unwrap());
+        let mut config = Config::default();
+        config.notifications.enabled = false;
+        let hub = Arc::new(NotificationHub::new());
+        assert!(!start_notifications_with_config(db, hub, &config));
+    }
+
+    #[test]
+    fn test_digest_due() {
+        assert!(!digest_due("2024-03-05", 6, None, 7));
+        assert!(digest_due("2024-03-05", 7, None, 7));
+        assert!(digest_due("2024-03-05", 23, Some("2024-03-04"), 7));
+        assert!(!digest_due("2024-03-05", 8, Some("2024-03-05"), 7));
+    }
+
+    #[tokio::test]
+    async fn test_run_once_publishes_notifications() {
+        use crate::board::{BoardRepository, BoardService, BoardType, NewBoard};
+        use crate::db::{NewUser, Role, UserRepository};
+        use crate::notify::SubscriptionTarget;
+
+        let db = Arc::new(Database::open_in_memory().await.unwrap());
+        let users = UserRepository::new(db.pool());
+        let alice = users.create(&NewUser::new("alice", "hash", "alice")).await.unwrap();
+        let bob = users.create(&NewUser::new("bob", "hash", "bob")).await.unwrap();
+        let board = BoardRepository::new(db.pool())
+            .create(&NewBoard::new("general").with_board_type(BoardType::Flat))
+            .await
+            .unwrap();
+        let hub = Arc::new(NotificationHub::new());
+        let mut rx = hub.subscribe();
+        let updater = NotificationUpdater::new(db.clone(), hub, 30, 7, "Asia/Tokyo");
+
+        updater.run_once().await;
+        NotificationService::new(&db)
+            .subscribe(bob.id, Role::Member, SubscriptionTarget::Board(board.id), false)
+            .await
+            .unwrap();
+        BoardService::new(&db)
+            .create_flat_post(board.id, alice.id, "Hi", "hello", Role::Member)
+            .await
+            .unwrap();
+        updater.run_once().await;
+
+        let notification = rx.try_recv().unwrap();
+        assert_eq!(notification.user_id, bob.id);
+        assert_eq!(notification.title, "Hi");
+    }
+}
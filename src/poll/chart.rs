@@ -0,0 +1,97 @@
+//! ASCII bar chart for poll results.
+
+use crate::template::{display_width, pad_to_width};
+
+/// Character used to draw bars.
+const BAR_CHAR: char = '#';
+
+/// Render vote counts as a horizontal bar chart.
+///
+/// Each entry becomes one line of the form `label  ######     12 ( 40%)`.
+/// Lines are kept one column narrower than `width` so terminals that wrap
+/// at the last column do not add blank lines. Labels take at most a third
+/// of the width and are truncated with `~` when longer.
+pub fn render_bar_chart(entries: &[(String, i64)], width: usize, cjk_width: usize) -> Vec<String> {
+    let total: i64 = entries.iter().map(|(_, votes)| *votes).sum();
+    let max_votes = entries.iter().map(|(_, votes)| *votes).max().unwrap_or(0);
+
+    let usable = width.saturating_sub(1);
+    let label_width = entries
+        .iter()
+        .map(|(label, _)| display_width(label, cjk_width))
+        .max()
+        .unwrap_or(0)
+        .min(usable / 3)
+        .max(1);
+    let count_width = max_votes.to_string().len();
+    // " " + count + " (" + "100" + "%)"
+    let suffix_width = 1 + count_width + 2 + 3 + 2;
+    let bar_width = usable.saturating_sub(label_width + 1 + suffix_width).max(1);
+
+    entries
+        .iter()
+        .map(|(label, votes)| {
+            let percent = if total > 0 { votes * 100 / total } else { 0 };
+            let filled = if max_votes > 0 {
+                (*votes as usize * bar_width).div_ceil(max_votes as usize)
+            } else {
+                0
+            };
+            let bar: String = std::iter::repeat_n(BAR_CHAR, filled).collect();
+            format!(
+                "{} {}{} {:>cw$} ({:>3}%)",
+                pad_to_width(label, label_width, cjk_width),
+                bar,
+                " ".repeat(bar_width - filled),
+                votes,
+                percent,
+                cw = count_width
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(items: &[(&str, i64)]) -> Vec<(String, i64)> {
+        items.iter().map(|(l, v)| (l.to_string(), *v)).collect()
+    }
+
+    #[test]
+    fn test_render_bar_chart_fits_width() {
+        let data = entries(&[("Pizza", 6), ("Sushi", 3), ("A very long option label", 0)]);
+        for width in [40, 80] {
+            let lines = render_bar_chart(&data, width, 2);
+            assert_eq!(lines.len(), 3);
+            for line in &lines {
+                assert_eq!(display_width(line, 2), width - 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_render_bar_chart_scales_to_max() {
+        let lines = render_bar_chart(&entries(&[("a", 4), ("b", 2), ("c", 0)]), 40, 2);
+        let bars: Vec<usize> = lines.iter().map(|l| l.matches('#').count()).collect();
+        assert!(bars[0] > 0);
+        assert_eq!(bars[1], bars[0].div_ceil(2));
+        assert_eq!(bars[2], 0);
+        assert!(lines[0].ends_with("4 ( 66%)"));
+        assert!(lines[2].ends_with("0 (  0%)"));
+    }
+
+    #[test]
+    fn test_render_bar_chart_cjk_labels() {
+        let lines = render_bar_chart(&entries(&[("寿司", 1), ("ピザ", 1)]), 40, 2);
+        assert_eq!(display_width(&lines[0], 2), 39);
+        assert!(lines[0].starts_with("寿司 #"));
+    }
+
+    #[test]
+    fn test_render_bar_chart_no_votes() {
+        let lines = render_bar_chart(&entries(&[("a", 0), ("b", 0)]), 80, 2);
+        assert!(lines.iter().all(|l| !l.contains('#')));
+    }
+}
@@ -0,0 +1,16 @@
+//! Voting booth for HOBBS.
+//!
+//! Polls offer single or multiple choice answers, optionally with write-ins
+//! that become new options. Each user casts one ballot per poll during the
+//! poll's voting period. A poll can stand alone or be attached to a thread,
+//! in which case it follows the thread's board permissions.
+
+pub mod chart;
+pub mod repository;
+pub mod service;
+pub mod types;
+
+pub use chart::render_bar_chart;
+pub use repository::PollRepository;
+pub use service::{PollService, MAX_OPTION_LENGTH, MAX_POLL_OPTIONS, MAX_QUESTION_LENGTH};
+pub use types::{Ballot, NewPoll, OptionResult, Poll, PollOption, PollResults, PollStatus};
@@ -0,0 +1,373 @@
+//! Poll repository for HOBBS.
+
+use std::collections::HashMap;
+
+use super::types::{NewPoll, Poll, PollOption};
+use crate::db::{DbPool, Role};
+use crate::{HobbsError, Result};
+
+const POLL_SELECT: &str =
+    "SELECT p.id, p.question, p.description, p.allow_multiple, p.allow_write_in,
+            p.min_vote_role, p.opens_at, p.closes_at, p.thread_id, p.created_by,
+            u.nickname AS creator, p.created_at
+     FROM polls p
+     LEFT JOIN users u ON u.id = p.created_by";
+
+/// Repository for polls, their options and votes.
+pub struct PollRepository<'a> {
+    pool: &'a DbPool,
+}
+
+impl<'a> PollRepository<'a> {
+    /// Create a new PollRepository with the given database pool.
+    pub fn new(pool: &'a DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Create a poll with its options.
+    pub async fn create(&self, new_poll: &NewPoll, created_by: i64) -> Result<Poll> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        let id: i64 = sqlx::query_scalar(
+            "INSERT INTO polls (question, description, allow_multiple, allow_write_in,
+                                min_vote_role, opens_at, closes_at, thread_id, created_by)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+             RETURNING id",
+        )
+        .bind(&new_poll.question)
+        .bind(&new_poll.description)
+        .bind(new_poll.allow_multiple)
+        .bind(new_poll.allow_write_in)
+        .bind(new_poll.min_vote_role.as_str())
+        .bind(&new_poll.opens_at)
+        .bind(&new_poll.closes_at)
+        .bind(new_poll.thread_id)
+        .bind(created_by)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        for (i, text) in new_poll.options.iter().enumerate() {
+            sqlx::query("INSERT INTO poll_options (poll_id, text, sort_order) VALUES ($1, $2, $3)")
+                .bind(id)
+                .bind(text)
+                .bind(i as i32)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| HobbsError::Database(e.to_string()))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        self.get_by_id(id)
+            .await?
+            .ok_or_else(|| HobbsError::NotFound("poll".to_string()))
+    }
+
+    /// Get a poll by ID.
+    pub async fn get_by_id(&self, id: i64) -> Result<Option<Poll>> {
+        let query = format!("{POLL_SELECT} WHERE p.id = $1");
+        let row: Option<PollRow> = sqlx::query_as(&query)
+            .bind(id)
+            .fetch_optional(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(row.map(PollRow::into_poll))
+    }
+
+    /// Get the poll attached to a thread.
+    pub async fn get_by_thread(&self, thread_id: i64) -> Result<Option<Poll>> {
+        let query = format!("{POLL_SELECT} WHERE p.thread_id = $1 ORDER BY p.id DESC LIMIT 1");
+        let row: Option<PollRow> = sqlx::query_as(&query)
+            .bind(thread_id)
+            .fetch_optional(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(row.map(PollRow::into_poll))
+    }
+
+    /// List all polls, newest first.
+    pub async fn list(&self) -> Result<Vec<Poll>> {
+        let query = format!("{POLL_SELECT} ORDER BY p.id DESC");
+        let rows: Vec<PollRow> = sqlx::query_as(&query)
+            .fetch_all(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(PollRow::into_poll).collect())
+    }
+
+    /// Set the end of voting.
+    pub async fn set_closes_at(&self, id: i64, closes_at: &str) -> Result<bool> {
+        let result = sqlx::query("UPDATE polls SET closes_at = $1 WHERE id = $2")
+            .bind(closes_at)
+            .bind(id)
+            .execute(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Delete a poll with its options and votes.
+    pub async fn delete(&self, id: i64) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM polls WHERE id = $1")
+            .bind(id)
+            .execute(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// List the options of a poll in display order.
+    pub async fn list_options(&self, poll_id: i64) -> Result<Vec<PollOption>> {
+        sqlx::query_as(
+            "SELECT id, poll_id, text, sort_order, is_write_in
+             FROM poll_options WHERE poll_id = $1
+             ORDER BY sort_order, id",
+        )
+        .bind(poll_id)
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// Record a ballot: the chosen options plus an optional write-in answer.
+    ///
+    /// A write-in matching an existing option's text counts for that option;
+    /// otherwise it is added as a new option. Returns false without voting
+    /// if the user already cast a ballot in this poll.
+    pub async fn cast_ballot(
+        &self,
+        poll_id: i64,
+        user_id: i64,
+        option_ids: &[i64],
+        write_in: Option<&str>,
+    ) -> Result<bool> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        let exists: (bool,) = sqlx::query_as(
+            "SELECT EXISTS(SELECT 1 FROM poll_ballots WHERE poll_id = $1 AND user_id = $2)",
+        )
+        .bind(poll_id)
+        .bind(user_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+        if exists.0 {
+            return Ok(false);
+        }
+
+        sqlx::query("INSERT INTO poll_ballots (poll_id, user_id) VALUES ($1, $2)")
+            .bind(poll_id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        let mut chosen = option_ids.to_vec();
+        if let Some(text) = write_in {
+            let existing: Option<i64> = sqlx::query_scalar(
+                "SELECT id FROM poll_options WHERE poll_id = $1 AND LOWER(text) = LOWER($2)",
+            )
+            .bind(poll_id)
+            .bind(text)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+            let option_id = match existing {
+                Some(id) => id,
+                None => sqlx::query_scalar(
+                    "INSERT INTO poll_options (poll_id, text, sort_order, is_write_in)
+                     VALUES ($1, $2,
+                             (SELECT COALESCE(MAX(sort_order), 0) + 1
+                              FROM poll_options WHERE poll_id = $1),
+                             $3)
+                     RETURNING id",
+                )
+                .bind(poll_id)
+                .bind(text)
+                .bind(true)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(|e| HobbsError::Database(e.to_string()))?,
+            };
+            chosen.push(option_id);
+        }
+        chosen.sort_unstable();
+        chosen.dedup();
+
+        for option_id in chosen {
+            sqlx::query("INSERT INTO poll_votes (poll_id, option_id, user_id) VALUES ($1, $2, $3)")
+                .bind(poll_id)
+                .bind(option_id)
+                .bind(user_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| HobbsError::Database(e.to_string()))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+        Ok(true)
+    }
+
+    /// Count votes per option of a poll.
+    pub async fn vote_counts(&self, poll_id: i64) -> Result<HashMap<i64, i64>> {
+        let rows: Vec<(i64, i64)> = sqlx::query_as(
+            "SELECT option_id, COUNT(*) FROM poll_votes WHERE poll_id = $1 GROUP BY option_id",
+        )
+        .bind(poll_id)
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().collect())
+    }
+
+    /// Count the ballots cast in a poll.
+    pub async fn count_ballots(&self, poll_id: i64) -> Result<i64> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM poll_ballots WHERE poll_id = $1")
+            .bind(poll_id)
+            .fetch_one(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// List the options a user voted for in a poll.
+    pub async fn user_votes(&self, poll_id: i64, user_id: i64) -> Result<Vec<i64>> {
+        sqlx::query_scalar(
+            "SELECT option_id FROM poll_votes WHERE poll_id = $1 AND user_id = $2
+             ORDER BY option_id",
+        )
+        .bind(poll_id)
+        .bind(user_id)
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+}
+
+/// Internal struct for mapping database rows to Poll.
+#[derive(sqlx::FromRow)]
+struct PollRow {
+    id: i64,
+    question: String,
+    description: Option<String>,
+    allow_multiple: bool,
+    allow_write_in: bool,
+    min_vote_role: String,
+    opens_at: Option<String>,
+    closes_at: Option<String>,
+    thread_id: Option<i64>,
+    created_by: Option<i64>,
+    creator: Option<String>,
+    created_at: String,
+}
+
+impl PollRow {
+    fn into_poll(self) -> Poll {
+        Poll {
+            id: self.id,
+            question: self.question,
+            description: self.description,
+            allow_multiple: self.allow_multiple,
+            allow_write_in: self.allow_write_in,
+            min_vote_role: self.min_vote_role.parse().unwrap_or(Role::Member),
+            opens_at: self.opens_at,
+            closes_at: self.closes_at,
+            thread_id: self.thread_id,
+            created_by: self.created_by,
+            creator: self.creator,
+            created_at: self.created_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{NewUser, UserRepository};
+    use crate::Database;
+
+    async fn setup() -> (Database, i64) {
+        let db = Database::open_in_memory().await.unwrap();
+        let user = UserRepository::new(db.pool())
+            .create(&NewUser::new("voter", "hash", "Voter"))
+            .await
+            .unwrap();
+        (db, user.id)
+    }
+
+    #[tokio::test]
+    async fn test_create_and_get_poll() {
+        let (db, user_id) = setup().await;
+        let repo = PollRepository::new(db.pool());
+
+        let poll = repo
+            .create(
+                &NewPoll::new("Best editor?", ["vi", "emacs"]).with_multiple(true),
+                user_id,
+            )
+            .await
+            .unwrap();
+        assert_eq!(poll.question, "Best editor?");
+        assert!(poll.allow_multiple);
+        assert_eq!(poll.creator.as_deref(), Some("Voter"));
+
+        let options = repo.list_options(poll.id).await.unwrap();
+        let texts: Vec<&str> = options.iter().map(|o| o.text.as_str()).collect();
+        assert_eq!(texts, vec!["vi", "emacs"]);
+        assert_eq!(repo.list().await.unwrap().len(), 1);
+
+        assert!(repo.delete(poll.id).await.unwrap());
+        assert!(repo.get_by_id(poll.id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cast_ballot_once() {
+        let (db, user_id) = setup().await;
+        let repo = PollRepository::new(db.pool());
+        let poll = repo
+            .create(&NewPoll::new("Q", ["a", "b"]).with_write_in(true), user_id)
+            .await
+            .unwrap();
+        let options = repo.list_options(poll.id).await.unwrap();
+
+        assert!(repo
+            .cast_ballot(poll.id, user_id, &[options[0].id], Some("c"))
+            .await
+            .unwrap());
+        assert!(!repo
+            .cast_ballot(poll.id, user_id, &[options[1].id], None)
+            .await
+            .unwrap());
+
+        let options = repo.list_options(poll.id).await.unwrap();
+        assert_eq!(options.len(), 3);
+        assert!(options[2].is_write_in);
+        let counts = repo.vote_counts(poll.id).await.unwrap();
+        assert_eq!(counts.get(&options[0].id), Some(&1));
+        assert_eq!(counts.get(&options[1].id), None);
+        assert_eq!(counts.get(&options[2].id), Some(&1));
+        assert_eq!(repo.count_ballots(poll.id).await.unwrap(), 1);
+        assert_eq!(
+            repo.user_votes(poll.id, user_id).await.unwrap(),
+            vec![options[0].id, options[2].id]
+        );
+    }
+}
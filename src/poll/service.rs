@@ -0,0 +1,501 @@
+//! Poll service for HOBBS.
+//!
+//! Polls attached to a thread are visible only to users who can read the
+//! thread's board. Everyone who can see a poll may see its results; voting
+//! needs the poll's minimum role and an open voting period.
+
+use super::repository::PollRepository;
+use super::types::{Ballot, NewPoll, OptionResult, Poll, PollResults, PollStatus};
+use crate::board::BoardService;
+use crate::datetime::now_db;
+use crate::db::{Database, Role};
+use crate::{HobbsError, Result};
+
+/// Maximum number of options when creating a poll.
+pub const MAX_POLL_OPTIONS: usize = 20;
+
+/// Maximum length of a poll question in characters.
+pub const MAX_QUESTION_LENGTH: usize = 200;
+
+/// Maximum length of an option (or write-in answer) in characters.
+pub const MAX_OPTION_LENGTH: usize = 100;
+
+/// Service for the voting booth.
+pub struct PollService<'a> {
+    db: &'a Database,
+}
+
+impl<'a> PollService<'a> {
+    /// Create a new PollService with the given database reference.
+    pub fn new(db: &'a Database) -> Self {
+        Self { db }
+    }
+
+    /// Create a poll.
+    ///
+    /// `create_role` is the configured minimum role for creating polls.
+    /// Only the thread's author or a SubOp may attach a poll to a thread,
+    /// and a thread has at most one poll.
+    pub async fn create_poll(
+        &self,
+        user_id: i64,
+        user_role: Role,
+        create_role: Role,
+        mut new_poll: NewPoll,
+    ) -> Result<Poll> {
+        if !user_role.can_access(create_role) {
+            return Err(HobbsError::Permission(
+                "投票を作成する権限がありません".to_string(),
+            ));
+        }
+
+        new_poll.question = new_poll.question.trim().to_string();
+        if new_poll.question.is_empty() {
+            return Err(HobbsError::Validation("質問を入力してください".to_string()));
+        }
+        if new_poll.question.chars().count() > MAX_QUESTION_LENGTH {
+            return Err(HobbsError::Validation(format!(
+                "質問が長すぎます（{}文字以内）",
+                MAX_QUESTION_LENGTH
+            )));
+        }
+
+        new_poll.options = new_poll
+            .options
+            .iter()
+            .map(|o| o.trim().to_string())
+            .filter(|o| !o.is_empty())
+            .collect();
+        if new_poll.options.len() < 2 || new_poll.options.len() > MAX_POLL_OPTIONS {
+            return Err(HobbsError::Validation(format!(
+                "選択肢は2〜{}個にしてください",
+                MAX_POLL_OPTIONS
+            )));
+        }
+        if new_poll
+            .options
+            .iter()
+            .any(|o| o.chars().count() > MAX_OPTION_LENGTH)
+        {
+            return Err(HobbsError::Validation(format!(
+                "選択肢が長すぎます（{}文字以内）",
+                MAX_OPTION_LENGTH
+            )));
+        }
+        let mut seen: Vec<String> = Vec::new();
+        for option in &new_poll.options {
+            let key = option.to_lowercase();
+            if seen.contains(&key) {
+                return Err(HobbsError::Validation("選択肢が重複しています".to_string()));
+            }
+            seen.push(key);
+        }
+
+        if let (Some(opens_at), Some(closes_at)) = (&new_poll.opens_at, &new_poll.closes_at) {
+            if closes_at <= opens_at {
+                return Err(HobbsError::Validation(
+                    "終了日時は開始日時より後にしてください".to_string(),
+                ));
+            }
+        }
+
+        if let Some(thread_id) = new_poll.thread_id {
+            let thread = BoardService::new(self.db)
+                .get_thread(thread_id, user_role)
+                .await?;
+            if thread.author_id != user_id && user_role < Role::SubOp {
+                return Err(HobbsError::Permission(
+                    "このスレッドに投票を付ける権限がありません".to_string(),
+                ));
+            }
+            let repo = PollRepository::new(self.db.pool());
+            if repo.get_by_thread(thread_id).await?.is_some() {
+                return Err(HobbsError::Validation(
+                    "このスレッドには既に投票があります".to_string(),
+                ));
+            }
+        }
+
+        PollRepository::new(self.db.pool())
+            .create(&new_poll, user_id)
+            .await
+    }
+
+    /// Get a poll visible to a user with the given role.
+    pub async fn get_poll(&self, poll_id: i64, user_role: Role) -> Result<Poll> {
+        let poll = PollRepository::new(self.db.pool())
+            .get_by_id(poll_id)
+            .await?
+            .ok_or_else(|| HobbsError::NotFound("poll".to_string()))?;
+        self.check_visible(&poll, user_role).await?;
+        Ok(poll)
+    }
+
+    /// Get the poll attached to a thread, if any.
+    pub async fn get_thread_poll(&self, thread_id: i64, user_role: Role) -> Result<Option<Poll>> {
+        BoardService::new(self.db)
+            .get_thread(thread_id, user_role)
+            .await?;
+        PollRepository::new(self.db.pool())
+            .get_by_thread(thread_id)
+            .await
+    }
+
+    /// List polls visible to a user with the given role, newest first.
+    pub async fn list_polls(&self, user_role: Role) -> Result<Vec<Poll>> {
+        let polls = PollRepository::new(self.db.pool()).list().await?;
+        let mut visible = Vec::new();
+        for poll in polls {
+            if self.check_visible(&poll, user_role).await.is_ok() {
+                visible.push(poll);
+            }
+        }
+        Ok(visible)
+    }
+
+    /// Get a poll with its vote counts.
+    ///
+    /// `user_id` is the viewing user, whose own choices are included.
+    pub async fn get_results(
+        &self,
+        poll_id: i64,
+        user_id: Option<i64>,
+        user_role: Role,
+    ) -> Result<PollResults> {
+        let poll = self.get_poll(poll_id, user_role).await?;
+        let repo = PollRepository::new(self.db.pool());
+        let counts = repo.vote_counts(poll_id).await?;
+        let options = repo
+            .list_options(poll_id)
+            .await?
+            .into_iter()
+            .map(|option| OptionResult {
+                votes: counts.get(&option.id).copied().unwrap_or(0),
+                option,
+            })
+            .collect();
+        let voters = repo.count_ballots(poll_id).await?;
+        let my_votes = match user_id {
+            Some(user_id) => repo.user_votes(poll_id, user_id).await?,
+            None => Vec::new(),
+        };
+
+        Ok(PollResults {
+            status: poll.status(&now_db()),
+            poll,
+            options,
+            voters,
+            my_votes,
+        })
+    }
+
+    /// Cast a user's ballot and return the updated results.
+    pub async fn vote(
+        &self,
+        poll_id: i64,
+        user_id: i64,
+        user_role: Role,
+        mut ballot: Ballot,
+    ) -> Result<PollResults> {
+        let poll = self.get_poll(poll_id, user_role).await?;
+        if !poll.can_vote(user_role) {
+            return Err(HobbsError::Permission(
+                "この投票に参加する権限がありません".to_string(),
+            ));
+        }
+        match poll.status(&now_db()) {
+            PollStatus::Upcoming => {
+                return Err(HobbsError::Validation(
+                    "投票はまだ始まっていません".to_string(),
+                ))
+            }
+            PollStatus::Closed => {
+                return Err(HobbsError::Validation("投票は締め切られました".to_string()))
+            }
+            PollStatus::Open => {}
+        }
+
+        ballot.write_in = ballot
+            .write_in
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty());
+        ballot.option_ids.sort_unstable();
+        ballot.option_ids.dedup();
+        if ballot.is_empty() {
+            return Err(HobbsError::Validation("選択肢を選んでください".to_string()));
+        }
+        if !poll.allow_multiple && ballot.len() > 1 {
+            return Err(HobbsError::Validation(
+                "この投票では1つだけ選べます".to_string(),
+            ));
+        }
+        if let Some(ref text) = ballot.write_in {
+            if !poll.allow_write_in {
+                return Err(HobbsError::Validation(
+                    "この投票では自由回答はできません".to_string(),
+                ));
+            }
+            if text.chars().count() > MAX_OPTION_LENGTH {
+                return Err(HobbsError::Validation(format!(
+                    "回答が長すぎます（{}文字以内）",
+                    MAX_OPTION_LENGTH
+                )));
+            }
+        }
+
+        let repo = PollRepository::new(self.db.pool());
+        let options = repo.list_options(poll_id).await?;
+        if ballot
+            .option_ids
+            .iter()
+            .any(|id| !options.iter().any(|o| o.id == *id))
+        {
+            return Err(HobbsError::Validation(
+                "選択肢が正しくありません".to_string(),
+            ));
+        }
+
+        let cast = repo
+            .cast_ballot(
+                poll_id,
+                user_id,
+                &ballot.option_ids,
+                ballot.write_in.as_deref(),
+            )
+            .await?;
+        if !cast {
+            return Err(HobbsError::Validation("既に投票済みです".to_string()));
+        }
+
+        self.get_results(poll_id, Some(user_id), user_role).await
+    }
+
+    /// End voting now. Only the poll's creator or a SubOp may close it.
+    pub async fn close_poll(&self, poll_id: i64, user_id: i64, user_role: Role) -> Result<Poll> {
+        let poll = self.get_poll(poll_id, user_role).await?;
+        Self::check_owner(&poll, user_id, user_role)?;
+        if poll.status(&now_db()) == PollStatus::Closed {
+            return Ok(poll);
+        }
+
+        let repo = PollRepository::new(self.db.pool());
+        repo.set_closes_at(poll_id, &now_db()).await?;
+        repo.get_by_id(poll_id)
+            .await?
+            .ok_or_else(|| HobbsError::NotFound("poll".to_string()))
+    }
+
+    /// Delete a poll. Only the poll's creator or a SubOp may delete it.
+    pub async fn delete_poll(&self, poll_id: i64, user_id: i64, user_role: Role) -> Result<()> {
+        let poll = self.get_poll(poll_id, user_role).await?;
+        Self::check_owner(&poll, user_id, user_role)?;
+        PollRepository::new(self.db.pool()).delete(poll_id).await?;
+        Ok(())
+    }
+
+    /// Check if a user may manage (close or delete) a poll.
+    pub fn can_manage(poll: &Poll, user_id: i64, user_role: Role) -> bool {
+        poll.created_by == Some(user_id) || user_role >= Role::SubOp
+    }
+
+    fn check_owner(poll: &Poll, user_id: i64, user_role: Role) -> Result<()> {
+        if Self::can_manage(poll, user_id, user_role) {
+            Ok(())
+        } else {
+            Err(HobbsError::Permission(
+                "この投票を変更する権限がありません".to_string(),
+            ))
+        }
+    }
+
+    /// Polls attached to a thread are only visible to readers of its board.
+    async fn check_visible(&self, poll: &Poll, user_role: Role) -> Result<()> {
+        if let Some(thread_id) = poll.thread_id {
+            BoardService::new(self.db)
+                .get_thread(thread_id, user_role)
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::{BoardRepository, NewBoard, NewThread, ThreadRepository};
+    use crate::db::{NewUser, UserRepository};
+
+    async fn setup() -> (Database, i64, i64) {
+        let db = Database::open_in_memory().await.unwrap();
+        let users = UserRepository::new(db.pool());
+        let alice = users
+            .create(&NewUser::new("alice", "hash", "Alice"))
+            .await
+            .unwrap();
+        let bob = users
+            .create(&NewUser::new("bob", "hash", "Bob"))
+            .await
+            .unwrap();
+        (db, alice.id, bob.id)
+    }
+
+    #[tokio::test]
+    async fn test_create_poll_validation() {
+        let (db, alice, _) = setup().await;
+        let service = PollService::new(&db);
+
+        let result = service
+            .create_poll(
+                alice,
+                Role::Member,
+                Role::SubOp,
+                NewPoll::new("Q", ["a", "b"]),
+            )
+            .await;
+        assert!(matches!(result, Err(HobbsError::Permission(_))));
+
+        for new_poll in [
+            NewPoll::new("  ", ["a", "b"]),
+            NewPoll::new("Q", ["a", " "]),
+            NewPoll::new("Q", ["a", "A"]),
+            NewPoll::new("Q", ["a", "b"]).with_period(
+                Some("2024-02-01 00:00:00".to_string()),
+                Some("2024-01-01 00:00:00".to_string()),
+            ),
+        ] {
+            let result = service
+                .create_poll(alice, Role::Member, Role::Member, new_poll)
+                .await;
+            assert!(matches!(result, Err(HobbsError::Validation(_))));
+        }
+
+        let poll = service
+            .create_poll(
+                alice,
+                Role::Member,
+                Role::Member,
+                NewPoll::new(" Q ", ["a", "b"]),
+            )
+            .await
+            .unwrap();
+        assert_eq!(poll.question, "Q");
+    }
+
+    #[tokio::test]
+    async fn test_vote_rules() {
+        let (db, alice, bob) = setup().await;
+        let service = PollService::new(&db);
+        let poll = service
+            .create_poll(
+                alice,
+                Role::Member,
+                Role::Member,
+                NewPoll::new("Q", ["a", "b"]).with_write_in(true),
+            )
+            .await
+            .unwrap();
+        let results = service
+            .get_results(poll.id, None, Role::Guest)
+            .await
+            .unwrap();
+        let a = results.options[0].option.id;
+        let b = results.options[1].option.id;
+
+        // Guests cannot vote; single choice allows one option
+        let result = service
+            .vote(poll.id, alice, Role::Guest, Ballot::new(vec![a]))
+            .await;
+        assert!(matches!(result, Err(HobbsError::Permission(_))));
+        let result = service
+            .vote(poll.id, alice, Role::Member, Ballot::new(vec![a, b]))
+            .await;
+        assert!(matches!(result, Err(HobbsError::Validation(_))));
+        let result = service
+            .vote(poll.id, alice, Role::Member, Ballot::new(vec![999]))
+            .await;
+        assert!(matches!(result, Err(HobbsError::Validation(_))));
+
+        let results = service
+            .vote(poll.id, alice, Role::Member, Ballot::new(vec![a]))
+            .await
+            .unwrap();
+        assert_eq!(results.voters, 1);
+        assert_eq!(results.my_votes, vec![a]);
+
+        // One ballot per user
+        let result = service
+            .vote(poll.id, alice, Role::Member, Ballot::new(vec![b]))
+            .await;
+        assert!(matches!(result, Err(HobbsError::Validation(_))));
+
+        // A write-in naming an existing option counts for it
+        let results = service
+            .vote(
+                poll.id,
+                bob,
+                Role::Member,
+                Ballot::default().with_write_in("A"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(results.options.len(), 2);
+        assert_eq!(results.options[0].votes, 2);
+
+        // Closed polls take no votes; only the creator or a SubOp may close
+        let result = service.close_poll(poll.id, bob, Role::Member).await;
+        assert!(matches!(result, Err(HobbsError::Permission(_))));
+        let closed = service
+            .close_poll(poll.id, alice, Role::Member)
+            .await
+            .unwrap();
+        assert_eq!(closed.status(&now_db()), PollStatus::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_thread_poll() {
+        let (db, alice, bob) = setup().await;
+        let board = BoardRepository::new(db.pool())
+            .create(&NewBoard::new("general").with_min_read_role(Role::Member))
+            .await
+            .unwrap();
+        let thread = ThreadRepository::new(db.pool())
+            .create(&NewThread::new(board.id, "Lunch", alice))
+            .await
+            .unwrap();
+        let service = PollService::new(&db);
+
+        let new_poll = NewPoll::new("Where?", ["here", "there"]).with_thread(thread.id);
+        let result = service
+            .create_poll(bob, Role::Member, Role::Member, new_poll.clone())
+            .await;
+        assert!(matches!(result, Err(HobbsError::Permission(_))));
+
+        let poll = service
+            .create_poll(alice, Role::Member, Role::Member, new_poll.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            service
+                .get_thread_poll(thread.id, Role::Member)
+                .await
+                .unwrap()
+                .map(|p| p.id),
+            Some(poll.id)
+        );
+        let result = service
+            .create_poll(alice, Role::Member, Role::Member, new_poll)
+            .await;
+        assert!(matches!(result, Err(HobbsError::Validation(_))));
+
+        // Hidden from those who cannot read the board
+        assert!(service.get_poll(poll.id, Role::Guest).await.is_err());
+        assert!(service.list_polls(Role::Guest).await.unwrap().is_empty());
+        assert_eq!(service.list_polls(Role::Member).await.unwrap().len(), 1);
+
+        service
+            .delete_poll(poll.id, alice, Role::Member)
+            .await
+            .unwrap();
+        assert!(service.list_polls(Role::Member).await.unwrap().is_empty());
+    }
+}
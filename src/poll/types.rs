@@ -0,0 +1,286 @@
+//! Poll types.
+
+use crate::db::Role;
+
+/// Where a poll is in its voting period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PollStatus {
+    /// Voting has not started yet.
+    Upcoming,
+    /// Voting is open.
+    Open,
+    /// Voting has ended.
+    Closed,
+}
+
+impl PollStatus {
+    /// Get the status as a string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PollStatus::Upcoming => "upcoming",
+            PollStatus::Open => "open",
+            PollStatus::Closed => "closed",
+        }
+    }
+}
+
+/// A poll in the voting booth.
+#[derive(Debug, Clone)]
+pub struct Poll {
+    /// Unique poll ID.
+    pub id: i64,
+    /// The question asked.
+    pub question: String,
+    /// Optional longer explanation.
+    pub description: Option<String>,
+    /// Whether a ballot may choose several options.
+    pub allow_multiple: bool,
+    /// Whether voters may add their own answer.
+    pub allow_write_in: bool,
+    /// Minimum role required to vote.
+    pub min_vote_role: Role,
+    /// Start of voting (UTC, None = from creation).
+    pub opens_at: Option<String>,
+    /// End of voting (UTC, None = no end).
+    pub closes_at: Option<String>,
+    /// Thread the poll is attached to.
+    pub thread_id: Option<i64>,
+    /// ID of the creating user.
+    pub created_by: Option<i64>,
+    /// Nickname of the creating user.
+    pub creator: Option<String>,
+    /// Poll creation timestamp.
+    pub created_at: String,
+}
+
+impl Poll {
+    /// Get the status of the poll at the given time (database format, UTC).
+    pub fn status(&self, now: &str) -> PollStatus {
+        if self.opens_at.as_deref().is_some_and(|t| now < t) {
+            PollStatus::Upcoming
+        } else if self.closes_at.as_deref().is_some_and(|t| now >= t) {
+            PollStatus::Closed
+        } else {
+            PollStatus::Open
+        }
+    }
+
+    /// Check if a user with the given role may vote in this poll.
+    pub fn can_vote(&self, role: Role) -> bool {
+        role.can_access(self.min_vote_role)
+    }
+}
+
+/// An answer that can be chosen in a poll.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct PollOption {
+    /// Unique option ID.
+    pub id: i64,
+    /// ID of the poll.
+    pub poll_id: i64,
+    /// Option text.
+    pub text: String,
+    /// Display order.
+    pub sort_order: i32,
+    /// Whether a voter added this option as a write-in.
+    pub is_write_in: bool,
+}
+
+/// Data for creating a new poll.
+#[derive(Debug, Clone)]
+pub struct NewPoll {
+    /// The question asked.
+    pub question: String,
+    /// Optional longer explanation.
+    pub description: Option<String>,
+    /// Option texts, in display order.
+    pub options: Vec<String>,
+    /// Whether a ballot may choose several options (defaults to false).
+    pub allow_multiple: bool,
+    /// Whether voters may add their own answer (defaults to false).
+    pub allow_write_in: bool,
+    /// Minimum role required to vote (defaults to Member).
+    pub min_vote_role: Role,
+    /// Start of voting (UTC).
+    pub opens_at: Option<String>,
+    /// End of voting (UTC).
+    pub closes_at: Option<String>,
+    /// Thread to attach the poll to.
+    pub thread_id: Option<i64>,
+}
+
+impl NewPoll {
+    /// Create a new poll with a question and its options.
+    pub fn new<S: Into<String>>(
+        question: impl Into<String>,
+        options: impl IntoIterator<Item = S>,
+    ) -> Self {
+        Self {
+            question: question.into(),
+            description: None,
+            options: options.into_iter().map(Into::into).collect(),
+            allow_multiple: false,
+            allow_write_in: false,
+            min_vote_role: Role::Member,
+            opens_at: None,
+            closes_at: None,
+            thread_id: None,
+        }
+    }
+
+    /// Set the description.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Allow several options per ballot.
+    pub fn with_multiple(mut self, allow_multiple: bool) -> Self {
+        self.allow_multiple = allow_multiple;
+        self
+    }
+
+    /// Allow write-in answers.
+    pub fn with_write_in(mut self, allow_write_in: bool) -> Self {
+        self.allow_write_in = allow_write_in;
+        self
+    }
+
+    /// Set the minimum role required to vote.
+    pub fn with_min_vote_role(mut self, role: Role) -> Self {
+        self.min_vote_role = role;
+        self
+    }
+
+    /// Set the voting period (UTC, database format).
+    pub fn with_period(mut self, opens_at: Option<String>, closes_at: Option<String>) -> Self {
+        self.opens_at = opens_at;
+        self.closes_at = closes_at;
+        self
+    }
+
+    /// Attach the poll to a thread.
+    pub fn with_thread(mut self, thread_id: i64) -> Self {
+        self.thread_id = Some(thread_id);
+        self
+    }
+}
+
+/// A voter's choice: existing options and an optional write-in answer.
+#[derive(Debug, Clone, Default)]
+pub struct Ballot {
+    /// IDs of the chosen options.
+    pub option_ids: Vec<i64>,
+    /// Write-in answer text.
+    pub write_in: Option<String>,
+}
+
+impl Ballot {
+    /// Create a ballot choosing the given options.
+    pub fn new(option_ids: impl Into<Vec<i64>>) -> Self {
+        Self {
+            option_ids: option_ids.into(),
+            write_in: None,
+        }
+    }
+
+    /// Add a write-in answer.
+    pub fn with_write_in(mut self, text: impl Into<String>) -> Self {
+        self.write_in = Some(text.into());
+        self
+    }
+
+    /// Number of choices on the ballot.
+    pub fn len(&self) -> usize {
+        self.option_ids.len() + usize::from(self.write_in.is_some())
+    }
+
+    /// Check if nothing is chosen.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Vote count of one option.
+#[derive(Debug, Clone)]
+pub struct OptionResult {
+    /// The option.
+    pub option: PollOption,
+    /// Number of ballots choosing it.
+    pub votes: i64,
+}
+
+/// A poll with its options, vote counts and the viewer's state.
+#[derive(Debug, Clone)]
+pub struct PollResults {
+    /// The poll.
+    pub poll: Poll,
+    /// Status at the time the results were read.
+    pub status: PollStatus,
+    /// Options with their vote counts, in display order.
+    pub options: Vec<OptionResult>,
+    /// Number of ballots cast.
+    pub voters: i64,
+    /// Options chosen by the viewing user (empty if they have not voted).
+    pub my_votes: Vec<i64>,
+}
+
+impl PollResults {
+    /// Check if the viewing user has voted.
+    pub fn has_voted(&self) -> bool {
+        !self.my_votes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poll(opens_at: Option<&str>, closes_at: Option<&str>) -> Poll {
+        Poll {
+            id: 1,
+            question: "Q".to_string(),
+            description: None,
+            allow_multiple: false,
+            allow_write_in: false,
+            min_vote_role: Role::Member,
+            opens_at: opens_at.map(String::from),
+            closes_at: closes_at.map(String::from),
+            thread_id: None,
+            created_by: None,
+            creator: None,
+            created_at: "2024-01-01 00:00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_poll_status() {
+        let now = "2024-06-01 12:00:00";
+        assert_eq!(poll(None, None).status(now), PollStatus::Open);
+        assert_eq!(
+            poll(Some("2024-06-02 00:00:00"), None).status(now),
+            PollStatus::Upcoming
+        );
+        assert_eq!(
+            poll(None, Some("2024-06-01 12:00:00")).status(now),
+            PollStatus::Closed
+        );
+        assert_eq!(
+            poll(Some("2024-05-01 00:00:00"), Some("2024-07-01 00:00:00")).status(now),
+            PollStatus::Open
+        );
+    }
+
+    #[test]
+    fn test_poll_can_vote() {
+        let poll = poll(None, None);
+        assert!(!poll.can_vote(Role::Guest));
+        assert!(poll.can_vote(Role::Member));
+    }
+
+    #[test]
+    fn test_ballot_len() {
+        assert!(Ballot::default().is_empty());
+        assert_eq!(Ballot::new(vec![1, 2]).with_write_in("x").len(), 3);
+    }
+}
@@ -0,0 +1,16 @@
+//! QWK offline mail module for HOBBS.
+//!
+//! This module provides QWK packet support for offline readers:
+//! - Packet generation from unread posts and private mail
+//! - REP reply packet import into posts and mail
+//!
+//! Conference 0 is private mail; every other conference number is a board ID.
+
+pub mod packet;
+pub mod service;
+
+pub use packet::{
+    parse_messages, read_reply_packet, write_reply_packet, ControlInfo, PacketWriter, QwkMessage,
+    BLOCK_SIZE, MAIL_CONFERENCE,
+};
+pub use service::{ImportResult, PacketOptions, QwkPacket, QwkService};
@@ -0,0 +1,724 @@
+//! QWK packet format.
+//!
+//! Encodes QWK mail packets (CONTROL.DAT, MESSAGES.DAT and NDX files) and
+//! decodes REP reply packets. This module only deals with bytes; turning
+//! posts and mail into messages is done by [`super::QwkService`].
+
+use std::collections::BTreeMap;
+use std::io::{Cursor, Read, Write};
+
+use chrono::NaiveDateTime;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::server::encoding::{decode_from_client, encode_for_client, CharacterEncoding};
+use crate::{HobbsError, Result};
+
+/// Size of a MESSAGES.DAT block in bytes.
+pub const BLOCK_SIZE: usize = 128;
+
+/// QWK line terminator (the CP437 π character).
+pub const QWK_LINE_END: u8 = 0xE3;
+
+/// Conference number used for private mail.
+pub const MAIL_CONFERENCE: u16 = 0;
+
+/// Maximum length of a conference name in CONTROL.DAT.
+const CONFERENCE_NAME_WIDTH: usize = 13;
+
+/// Header byte marking an active message.
+const ACTIVE: u8 = 0xE1;
+
+/// Header byte marking a deleted message.
+const DELETED: u8 = 0xE2;
+
+/// XMODEM pads the last block with SUB characters.
+const XMODEM_PAD: u8 = 0x1A;
+
+/// A message in a QWK or REP packet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QwkMessage {
+    /// Conference number (board ID, or 0 for private mail).
+    pub conference: u16,
+    /// Message number (post ID or mail ID).
+    pub number: i64,
+    /// Whether the message is private.
+    pub private: bool,
+    /// Whether the message has already been read.
+    pub read: bool,
+    /// Local date and time of the message.
+    ///
+    /// Dates in reply packets are not trusted and fall back to the epoch
+    /// when they cannot be parsed.
+    pub date: NaiveDateTime,
+    /// Recipient name.
+    pub to: String,
+    /// Sender name.
+    pub from: String,
+    /// Subject.
+    pub subject: String,
+    /// Number of the message this one replies to (0 for none).
+    pub reference: i64,
+    /// Message body with `\n` line endings.
+    pub body: String,
+}
+
+impl QwkMessage {
+    fn status(&self) -> u8 {
+        match (self.private, self.read) {
+            (false, false) => b' ',
+            (false, true) => b'-',
+            (true, false) => b'+',
+            (true, true) => b'*',
+        }
+    }
+}
+
+/// Information written to CONTROL.DAT.
+#[derive(Debug, Clone)]
+pub struct ControlInfo {
+    /// BBS name.
+    pub bbs_name: String,
+    /// Packet ID (also the base name of the .QWK and .REP files).
+    pub bbs_id: String,
+    /// SysOp name.
+    pub sysop_name: String,
+    /// Name of the user the packet is for.
+    pub user_name: String,
+    /// Packet creation time.
+    pub created: NaiveDateTime,
+    /// Conferences as (number, name), in display order.
+    pub conferences: Vec<(u16, String)>,
+}
+
+/// Builds a QWK packet one message at a time.
+pub struct PacketWriter {
+    encoding: CharacterEncoding,
+    messages: Vec<u8>,
+    indexes: BTreeMap<u16, Vec<u8>>,
+    personal: Vec<u8>,
+    count: usize,
+}
+
+impl PacketWriter {
+    /// Create an empty packet whose text is encoded with `encoding`.
+    pub fn new(producer: &str, encoding: CharacterEncoding) -> Self {
+        let mut messages = vec![b' '; BLOCK_SIZE];
+        put_field(&mut messages, &fit(producer, BLOCK_SIZE, encoding));
+        Self {
+            encoding,
+            messages,
+            indexes: BTreeMap::new(),
+            personal: Vec::new(),
+            count: 0,
+        }
+    }
+
+    /// Number of messages added so far.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Check whether no messages have been added.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Append a message. `personal` also indexes it in PERSONAL.NDX.
+    pub fn add(&mut self, message: &QwkMessage, personal: bool) {
+        let block = (self.messages.len() / BLOCK_SIZE + 1) as u32;
+        self.count += 1;
+        let logical = self.count.min(u16::MAX as usize) as u16;
+        self.messages
+            .extend(encode_message(message, logical, self.encoding));
+
+        let record = ndx_record(block, message.conference);
+        self.indexes
+            .entry(message.conference)
+            .or_default()
+            .extend_from_slice(&record);
+        if personal {
+            self.personal.extend_from_slice(&record);
+        }
+    }
+
+    /// Finish the packet and return the zipped .QWK file.
+    pub fn finish(self, control: &ControlInfo) -> Result<Vec<u8>> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        add_zip_entry(
+            &mut zip,
+            "CONTROL.DAT",
+            &control_dat(control, self.count, self.encoding),
+        )?;
+        add_zip_entry(&mut zip, "MESSAGES.DAT", &self.messages)?;
+        for (conference, index) in &self.indexes {
+            add_zip_entry(&mut zip, &format!("{:03}.NDX", conference), index)?;
+        }
+        if !self.personal.is_empty() {
+            add_zip_entry(&mut zip, "PERSONAL.NDX", &self.personal)?;
+        }
+        let cursor = zip
+            .finish()
+            .map_err(|e| HobbsError::Qwk(format!("failed to write packet: {}", e)))?;
+        Ok(cursor.into_inner())
+    }
+}
+
+/// Build a zipped REP packet containing `messages`.
+///
+/// Mainly useful for tests and tools; the BBS itself only reads REP packets.
+pub fn write_reply_packet(
+    bbs_id: &str,
+    messages: &[QwkMessage],
+    encoding: CharacterEncoding,
+) -> Result<Vec<u8>> {
+    let mut data = vec![b' '; BLOCK_SIZE];
+    put_field(&mut data, bbs_id.to_uppercase().as_bytes());
+    for message in messages {
+        let mut encoded = encode_message(message, 0, encoding);
+        // Reply packets carry the conference in the message number field.
+        encoded[1..8].fill(b' ');
+        put_field(&mut encoded[1..8], message.conference.to_string().as_bytes());
+        data.extend(encoded);
+    }
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    add_zip_entry(&mut zip, &format!("{}.MSG", bbs_id.to_uppercase()), &data)?;
+    let cursor = zip
+        .finish()
+        .map_err(|e| HobbsError::Qwk(format!("failed to write packet: {}", e)))?;
+    Ok(cursor.into_inner())
+}
+
+/// Read the messages from a zipped REP packet.
+///
+/// Trailing XMODEM padding is ignored. The packet must contain
+/// `<bbs_id>.MSG` and its first block must name the same BBS.
+pub fn read_reply_packet(
+    data: &[u8],
+    bbs_id: &str,
+    encoding: CharacterEncoding,
+) -> Result<Vec<QwkMessage>> {
+    let end = data
+        .iter()
+        .rposition(|&b| b != XMODEM_PAD)
+        .map_or(0, |i| i + 1);
+    let mut archive = ZipArchive::new(Cursor::new(&data[..end]))
+        .map_err(|e| HobbsError::Qwk(format!("invalid REP packet: {}", e)))?;
+
+    let wanted = format!("{}.MSG", bbs_id);
+    let name = archive
+        .file_names()
+        .find(|name| name.eq_ignore_ascii_case(&wanted))
+        .map(str::to_string)
+        .ok_or_else(|| HobbsError::Qwk(format!("{} not found in REP packet", wanted)))?;
+
+    let mut content = Vec::new();
+    archive
+        .by_name(&name)
+        .and_then(|mut file| file.read_to_end(&mut content).map_err(Into::into))
+        .map_err(|e| HobbsError::Qwk(format!("failed to read {}: {}", name, e)))?;
+
+    if content.len() < BLOCK_SIZE {
+        return Err(HobbsError::Qwk(format!("{} is truncated", name)));
+    }
+    let packet_id = String::from_utf8_lossy(&content[..8]);
+    if !packet_id.trim().eq_ignore_ascii_case(bbs_id) {
+        return Err(HobbsError::Qwk(format!(
+            "REP packet is for {}, not {}",
+            packet_id.trim(),
+            bbs_id
+        )));
+    }
+
+    let mut messages = parse_messages(&content[BLOCK_SIZE..], encoding)?;
+    // Reply packets carry the conference in the message number field;
+    // some readers leave the binary conference field empty.
+    for message in &mut messages {
+        if message.conference == 0 {
+            message.conference = u16::try_from(message.number).unwrap_or(0);
+        }
+    }
+    Ok(messages)
+}
+
+/// Parse a sequence of message blocks.
+///
+/// Deleted messages are skipped.
+pub fn parse_messages(data: &[u8], encoding: CharacterEncoding) -> Result<Vec<QwkMessage>> {
+    let mut messages = Vec::new();
+    let mut offset = 0;
+
+    while offset + BLOCK_SIZE <= data.len() {
+        let header = &data[offset..offset + BLOCK_SIZE];
+        // The block count comes from an uploaded packet, so it must not
+        // reach past the end of the data.
+        let end = String::from_utf8_lossy(&header[116..122])
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .filter(|&blocks| blocks > 0 && blocks <= (data.len() - offset) / BLOCK_SIZE)
+            .and_then(|blocks| blocks.checked_mul(BLOCK_SIZE))
+            .and_then(|size| size.checked_add(offset))
+            .ok_or_else(|| HobbsError::Qwk(format!("invalid block count at offset {}", offset)))?;
+        let body = &data[offset + BLOCK_SIZE..end];
+        offset = end;
+
+        if header[122] == DELETED {
+            continue;
+        }
+
+        messages.push(QwkMessage {
+            conference: u16::from_le_bytes([header[123], header[124]]),
+            number: parse_number(&header[1..8]),
+            private: matches!(header[0], b'+' | b'*'),
+            read: matches!(header[0], b'-' | b'*'),
+            date: parse_date(&header[8..16], &header[16..21]),
+            to: decode_field(&header[21..46], encoding),
+            from: decode_field(&header[46..71], encoding),
+            subject: decode_field(&header[71..96], encoding),
+            reference: parse_number(&header[108..116]),
+            body: decode_body(body, encoding),
+        });
+    }
+
+    Ok(messages)
+}
+
+/// Convert a value to Microsoft Binary Format, as used by NDX files.
+pub fn to_msbin(value: f32) -> [u8; 4] {
+    let ieee = value.to_le_bytes();
+    let exponent = ((ieee[3] << 1) | (ieee[2] >> 7)) as u16;
+    if exponent == 0 {
+        return [0; 4];
+    }
+    let sign = ieee[3] & 0x80;
+    [
+        ieee[0],
+        ieee[1],
+        sign | (ieee[2] & 0x7F),
+        (exponent + 2) as u8,
+    ]
+}
+
+/// Build a 5-byte NDX record for a message header at `block` (1-based).
+pub fn ndx_record(block: u32, conference: u16) -> [u8; 5] {
+    let msbin = to_msbin(block as f32);
+    [msbin[0], msbin[1], msbin[2], msbin[3], conference as u8]
+}
+
+/// Build the CONTROL.DAT file.
+fn control_dat(info: &ControlInfo, message_count: usize, encoding: CharacterEncoding) -> Vec<u8> {
+    let mut lines = vec![
+        info.bbs_name.clone(),
+        String::new(),
+        String::new(),
+        info.sysop_name.clone(),
+        format!("00000,{}", info.bbs_id),
+        info.created.format("%m-%d-%Y,%H:%M:%S").to_string(),
+        info.user_name.to_uppercase(),
+        String::new(),
+        "0".to_string(),
+        message_count.to_string(),
+        info.conferences.len().saturating_sub(1).to_string(),
+    ];
+    for (number, name) in &info.conferences {
+        lines.push(number.to_string());
+        let name = fit(name, CONFERENCE_NAME_WIDTH, encoding);
+        lines.push(decode_from_client(&name, encoding));
+    }
+    // HELLO, NEWS and GOODBYE files are not provided.
+    lines.extend([String::new(), String::new(), String::new()]);
+
+    let mut data = Vec::new();
+    for line in lines {
+        data.extend(encode_for_client(&line, encoding));
+        data.extend_from_slice(b"\r\n");
+    }
+    data
+}
+
+/// Encode a message header and body as MESSAGES.DAT blocks.
+fn encode_message(message: &QwkMessage, logical: u16, encoding: CharacterEncoding) -> Vec<u8> {
+    let body = encode_body(&message.body, encoding);
+    let blocks = 1 + body.len().div_ceil(BLOCK_SIZE);
+    let mut data = vec![b' '; blocks * BLOCK_SIZE];
+
+    data[0] = message.status();
+    put_field(&mut data[1..8], message.number.to_string().as_bytes());
+    put_field(&mut data[8..16], message.date.format("%m-%d-%y").to_string().as_bytes());
+    put_field(&mut data[16..21], message.date.format("%H:%M").to_string().as_bytes());
+    put_field(&mut data[21..46], &fit(&message.to, 25, encoding));
+    put_field(&mut data[46..71], &fit(&message.from, 25, encoding));
+    put_field(&mut data[71..96], &fit(&message.subject, 25, encoding));
+    if message.reference > 0 {
+        put_field(&mut data[108..116], message.reference.to_string().as_bytes());
+    }
+    put_field(&mut data[116..122], blocks.to_string().as_bytes());
+    data[122] = ACTIVE;
+    data[123..125].copy_from_slice(&message.conference.to_le_bytes());
+    data[125..127].copy_from_slice(&logical.to_le_bytes());
+    data[BLOCK_SIZE..BLOCK_SIZE + body.len()].copy_from_slice(&body);
+    data
+}
+
+/// Line terminator for message bodies.
+///
+/// 0xE3 is a valid lead byte in Shift_JIS and UTF-8, so those packets use
+/// CRLF instead; Japanese QWK readers expect this.
+fn line_end(encoding: CharacterEncoding) -> &'static [u8] {
+    match encoding {
+        CharacterEncoding::ShiftJIS | CharacterEncoding::Utf8 => b"\r\n",
+        CharacterEncoding::Cp437 | CharacterEncoding::Petscii => &[QWK_LINE_END],
+    }
+}
+
+fn encode_body(body: &str, encoding: CharacterEncoding) -> Vec<u8> {
+    let mut data = Vec::new();
+    for line in body.lines() {
+        data.extend(encode_for_client(line, encoding));
+        data.extend_from_slice(line_end(encoding));
+    }
+    data
+}
+
+fn decode_body(data: &[u8], encoding: CharacterEncoding) -> String {
+    let lines: Vec<String> = split_lines(data, encoding)
+        .into_iter()
+        .map(|line| decode_from_client(line, encoding).trim_end().to_string())
+        .collect();
+    let end = lines
+        .iter()
+        .rposition(|line| !line.is_empty())
+        .map_or(0, |i| i + 1);
+    lines[..end].join("\n")
+}
+
+/// Split a body on 0xE3, CRLF or LF.
+///
+/// For multi-byte encodings 0xE3 only counts as a line break where it
+/// cannot be the start of a character.
+fn split_lines(data: &[u8], encoding: CharacterEncoding) -> Vec<&[u8]> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+
+    while i < data.len() {
+        let byte = data[i];
+        let next = data.get(i + 1).copied();
+        match byte {
+            b'\n' => {
+                let end = if i > start && data[i - 1] == b'\r' { i - 1 } else { i };
+                lines.push(&data[start..end]);
+                start = i + 1;
+            }
+            QWK_LINE_END if !is_lead_byte(byte, next, encoding) => {
+                lines.push(&data[start..i]);
+                start = i + 1;
+            }
+            _ if encoding == CharacterEncoding::ShiftJIS && is_lead_byte(byte, next, encoding) => {
+                i += 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    lines.push(&data[start..]);
+    lines
+}
+
+/// Check whether `byte` starts a multi-byte character followed by `next`.
+fn is_lead_byte(byte: u8, next: Option<u8>, encoding: CharacterEncoding) -> bool {
+    let Some(next) = next else {
+        return false;
+    };
+    match encoding {
+        CharacterEncoding::ShiftJIS => {
+            matches!(byte, 0x81..=0x9F | 0xE0..=0xFC) && matches!(next, 0x40..=0x7E | 0x80..=0xFC)
+        }
+        CharacterEncoding::Utf8 => byte >= 0xC2 && matches!(next, 0x80..=0xBF),
+        CharacterEncoding::Cp437 | CharacterEncoding::Petscii => false,
+    }
+}
+
+/// Encode `text` and truncate it to `width` bytes on a character boundary.
+fn fit(text: &str, width: usize, encoding: CharacterEncoding) -> Vec<u8> {
+    let mut data = Vec::new();
+    let mut buf = [0u8; 4];
+    for c in text.chars() {
+        let encoded = encode_for_client(c.encode_utf8(&mut buf), encoding);
+        if data.len() + encoded.len() > width {
+            break;
+        }
+        data.extend(encoded);
+    }
+    data
+}
+
+/// Copy `value` into a space-padded field, truncating if necessary.
+fn put_field(field: &mut [u8], value: &[u8]) {
+    let len = value.len().min(field.len());
+    field[..len].copy_from_slice(&value[..len]);
+}
+
+fn decode_field(field: &[u8], encoding: CharacterEncoding) -> String {
+    decode_from_client(field, encoding)
+        .trim_matches(|c: char| c == ' ' || c == '\0')
+        .to_string()
+}
+
+fn parse_number(field: &[u8]) -> i64 {
+    String::from_utf8_lossy(field).trim().parse().unwrap_or(0)
+}
+
+fn parse_date(date: &[u8], time: &[u8]) -> NaiveDateTime {
+    let text = format!(
+        "{} {}",
+        String::from_utf8_lossy(date).trim(),
+        String::from_utf8_lossy(time).trim()
+    );
+    NaiveDateTime::parse_from_str(&text, "%m-%d-%y %H:%M").unwrap_or_default()
+}
+
+fn add_zip_entry(
+    zip: &mut ZipWriter<Cursor<Vec<u8>>>,
+    name: &str,
+    data: &[u8],
+) -> Result<()> {
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    zip.start_file(name, options)
+        .map_err(|e| HobbsError::Qwk(format!("failed to write {}: {}", name, e)))?;
+    zip.write_all(data)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn sample_date() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 3, 5)
+            .unwrap()
+            .and_hms_opt(14, 30, 0)
+            .unwrap()
+    }
+
+    fn sample_message(conference: u16, body: &str) -> QwkMessage {
+        QwkMessage {
+            conference,
+            number: 42,
+            private: false,
+            read: false,
+            date: sample_date(),
+            to: "ALL".to_string(),
+            from: "alice".to_string(),
+            subject: "Hello".to_string(),
+            reference: 0,
+            body: body.to_string(),
+        }
+    }
+
+    fn unzip(data: &[u8]) -> BTreeMap<String, Vec<u8>> {
+        let mut archive = ZipArchive::new(Cursor::new(data)).unwrap();
+        let mut files = BTreeMap::new();
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i).unwrap();
+            let mut content = Vec::new();
+            file.read_to_end(&mut content).unwrap();
+            files.insert(file.name().to_string(), content);
+        }
+        files
+    }
+
+    #[test]
+    fn test_to_msbin() {
+        assert_eq!(to_msbin(0.0), [0, 0, 0, 0]);
+        assert_eq!(to_msbin(1.0), [0x00, 0x00, 0x00, 0x81]);
+        assert_eq!(to_msbin(2.0), [0x00, 0x00, 0x00, 0x82]);
+        assert_eq!(to_msbin(3.0), [0x00, 0x00, 0x40, 0x82]);
+        assert_eq!(ndx_record(1, 7), [0x00, 0x00, 0x00, 0x81, 7]);
+    }
+
+    #[test]
+    fn test_encode_message_header() {
+        let message = QwkMessage {
+            reference: 40,
+            ..sample_message(3, "line one\nline two")
+        };
+        let data = encode_message(&message, 1, CharacterEncoding::Cp437);
+
+        assert_eq!(data.len(), 2 * BLOCK_SIZE);
+        assert_eq!(data[0], b' ');
+        assert_eq!(&data[1..8], b"42     ");
+        assert_eq!(&data[8..16], b"03-05-24");
+        assert_eq!(&data[16..21], b"14:30");
+        assert_eq!(&data[21..24], b"ALL");
+        assert_eq!(&data[46..51], b"alice");
+        assert_eq!(&data[108..110], b"40");
+        assert_eq!(&data[116..122], b"2     ");
+        assert_eq!(data[122], ACTIVE);
+        assert_eq!(&data[123..125], &[3, 0]);
+        assert_eq!(&data[125..127], &[1, 0]);
+        assert_eq!(&data[128..146], b"line one\xE3line two\xE3");
+    }
+
+    #[test]
+    fn test_shiftjis_body_uses_crlf() {
+        let message = sample_message(1, "こんにちは\n世界");
+        let data = encode_message(&message, 1, CharacterEncoding::ShiftJIS);
+        let body = &data[BLOCK_SIZE..];
+        assert!(body.windows(2).any(|w| w == b"\r\n"));
+
+        let parsed = parse_messages(&data, CharacterEncoding::ShiftJIS).unwrap();
+        assert_eq!(parsed[0].body, "こんにちは\n世界");
+    }
+
+    #[test]
+    fn test_split_lines_keeps_multibyte_characters() {
+        // 0xE3 0x81 is a kanji in Shift_JIS; a lone 0xE3 is a line break.
+        let data = [0xE3, 0x81, b'a', 0xE3];
+        let lines = split_lines(&data, CharacterEncoding::ShiftJIS);
+        assert_eq!(lines, vec![&[0xE3, 0x81, b'a'][..], &[][..]]);
+
+        // "あ" is 0xE3 0x81 0x82 in UTF-8.
+        let data = [0xE3, 0x81, 0x82, 0xE3, b'x'];
+        let lines = split_lines(&data, CharacterEncoding::Utf8);
+        assert_eq!(lines, vec![&[0xE3, 0x81, 0x82][..], &[b'x'][..]]);
+
+        let lines = split_lines(b"one\xE3two\r\nthree", CharacterEncoding::Cp437);
+        assert_eq!(lines, vec![&b"one"[..], &b"two"[..], &b"three"[..]]);
+    }
+
+    #[test]
+    fn test_fit_truncates_on_character_boundary() {
+        let data = fit("あいう", 5, CharacterEncoding::ShiftJIS);
+        assert_eq!(data.len(), 4);
+        assert_eq!(fit("abcdef", 3, CharacterEncoding::Cp437), b"abc");
+    }
+
+    #[test]
+    fn test_packet_writer() {
+        let mut writer = PacketWriter::new("HOBBS", CharacterEncoding::Cp437);
+        assert!(writer.is_empty());
+        writer.add(&sample_message(1, "first"), false);
+        writer.add(&sample_message(2, "second"), false);
+        let mail = QwkMessage {
+            private: true,
+            to: "bob".to_string(),
+            ..sample_message(MAIL_CONFERENCE, "private")
+        };
+        writer.add(&mail, true);
+        assert_eq!(writer.len(), 3);
+
+        let control = ControlInfo {
+            bbs_name: "Test BBS".to_string(),
+            bbs_id: "HOBBS".to_string(),
+            sysop_name: "SysOp".to_string(),
+            user_name: "bob".to_string(),
+            created: sample_date(),
+            conferences: vec![
+                (0, "Mail".to_string()),
+                (1, "General".to_string()),
+                (2, "A very long board name".to_string()),
+            ],
+        };
+        let files = unzip(&writer.finish(&control).unwrap());
+
+        let names: Vec<&str> = files.keys().map(String::as_str).collect();
+        assert_eq!(
+            names,
+            vec!["000.NDX", "001.NDX", "002.NDX", "CONTROL.DAT", "MESSAGES.DAT", "PERSONAL.NDX"]
+        );
+
+        let control = String::from_utf8(files["CONTROL.DAT"].clone()).unwrap();
+        let lines: Vec<&str> = control.split("\r\n").collect();
+        assert_eq!(lines[0], "Test BBS");
+        assert_eq!(lines[4], "00000,HOBBS");
+        assert_eq!(lines[5], "03-05-2024,14:30:00");
+        assert_eq!(lines[6], "BOB");
+        assert_eq!(lines[9], "3");
+        assert_eq!(lines[10], "2");
+        assert_eq!(&lines[11..17], &["0", "Mail", "1", "General", "2", "A very long b"]);
+
+        let messages = &files["MESSAGES.DAT"];
+        assert_eq!(messages.len(), 7 * BLOCK_SIZE);
+        assert!(messages.starts_with(b"HOBBS"));
+        // Messages start at blocks 2, 4 and 6 (1-based).
+        assert_eq!(files["001.NDX"], ndx_record(2, 1));
+        assert_eq!(files["002.NDX"], ndx_record(4, 2));
+        assert_eq!(files["000.NDX"], ndx_record(6, 0));
+        assert_eq!(files["PERSONAL.NDX"], ndx_record(6, 0));
+
+        let parsed = parse_messages(&messages[BLOCK_SIZE..], CharacterEncoding::Cp437).unwrap();
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[0], sample_message(1, "first"));
+        assert_eq!(parsed[2].body, "private");
+        assert!(parsed[2].private);
+    }
+
+    #[test]
+    fn test_reply_packet_roundtrip() {
+        let messages = vec![
+            QwkMessage {
+                reference: 10,
+                ..sample_message(5, "a reply\n\nwith a blank line")
+            },
+            QwkMessage {
+                private: true,
+                to: "carol".to_string(),
+                ..sample_message(MAIL_CONFERENCE, "hi")
+            },
+        ];
+        let data = write_reply_packet("hobbs", &messages, CharacterEncoding::Utf8).unwrap();
+
+        // XMODEM pads uploads to a full block.
+        let mut padded = data.clone();
+        padded.extend(vec![XMODEM_PAD; 100]);
+
+        let parsed = read_reply_packet(&padded, "HOBBS", CharacterEncoding::Utf8).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].conference, 5);
+        assert_eq!(parsed[0].reference, 10);
+        assert_eq!(parsed[0].body, "a reply\n\nwith a blank line");
+        assert_eq!(parsed[0].date, sample_date());
+        assert_eq!(parsed[1].conference, MAIL_CONFERENCE);
+        assert_eq!(parsed[1].to, "carol");
+        assert!(parsed[1].private);
+    }
+
+    #[test]
+    fn test_read_reply_packet_rejects_other_bbs() {
+        let data = write_reply_packet("OTHER", &[], CharacterEncoding::Utf8).unwrap();
+        let result = read_reply_packet(&data, "HOBBS", CharacterEncoding::Utf8);
+        assert!(matches!(result, Err(HobbsError::Qwk(_))));
+
+        let result = read_reply_packet(b"not a zip", "HOBBS", CharacterEncoding::Utf8);
+        assert!(matches!(result, Err(HobbsError::Qwk(_))));
+    }
+
+    #[test]
+    fn test_parse_messages_skips_deleted() {
+        let mut data = encode_message(&sample_message(1, "gone"), 1, CharacterEncoding::Cp437);
+        data[122] = DELETED;
+        data.extend(encode_message(
+            &sample_message(1, "kept"),
+            2,
+            CharacterEncoding::Cp437,
+        ));
+        let parsed = parse_messages(&data, CharacterEncoding::Cp437).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].body, "kept");
+    }
+
+    #[test]
+    fn test_parse_messages_rejects_bad_block_count() {
+        let message = encode_message(&sample_message(1, "hi"), 1, CharacterEncoding::Cp437);
+        for count in [b"    -1", b"999999", b"     0"] {
+            let mut data = message.clone();
+            data[116..122].copy_from_slice(count);
+            let result = parse_messages(&data, CharacterEncoding::Cp437);
+            assert!(matches!(result, Err(HobbsError::Qwk(_))));
+        }
+    }
+}
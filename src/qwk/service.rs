@@ -0,0 +1,637 @@
+//! QWK service for HOBBS.
+//!
+//! Builds QWK packets from a user's unread posts and mail, and turns REP
+//! reply packets back into posts and mail.
+
+use std::collections::HashMap;
+
+use chrono::{NaiveDateTime, Utc};
+
+use super::packet::{read_reply_packet, ControlInfo, PacketWriter, QwkMessage, MAIL_CONFERENCE};
+use crate::board::{
+    BoardRepository, BoardService, BoardType, PostRepository, Submission, ThreadRepository,
+    UnreadRepository,
+};
+use crate::config::Config;
+use crate::datetime::{format_datetime, format_utc_datetime};
+use crate::db::{Database, User, UserRepository};
+use crate::mail::{MailRepository, MailService, SendMailRequest};
+use crate::twit::TwitFilterService;
+use crate::{HobbsError, Result};
+
+/// Date format used to move timestamps into the configured timezone.
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Settings used when building packets.
+#[derive(Debug, Clone)]
+pub struct PacketOptions {
+    /// Packet ID (base name of the .QWK and .REP files).
+    pub bbs_id: String,
+    /// BBS name written to CONTROL.DAT.
+    pub bbs_name: String,
+    /// SysOp name written to CONTROL.DAT.
+    pub sysop_name: String,
+    /// Timezone for message dates.
+    pub timezone: String,
+    /// Maximum number of messages in one packet.
+    pub max_messages: usize,
+}
+
+impl PacketOptions {
+    /// Build packet options from the application configuration.
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            bbs_id: config.qwk.bbs_id.to_uppercase(),
+            bbs_name: config.bbs.name.clone(),
+            sysop_name: config.bbs.sysop_name.clone(),
+            timezone: config.server.timezone.clone(),
+            max_messages: config.qwk.max_messages,
+        }
+    }
+}
+
+impl Default for PacketOptions {
+    fn default() -> Self {
+        Self::from_config(&Config::default())
+    }
+}
+
+/// A generated QWK packet.
+#[derive(Debug, Clone)]
+pub struct QwkPacket {
+    /// File name of the packet (e.g. `HOBBS.QWK`).
+    pub filename: String,
+    /// Zipped packet contents.
+    pub data: Vec<u8>,
+    /// Number of messages in the packet.
+    pub message_count: usize,
+    /// Last post ID included per board, as (board_id, post_id).
+    pub pointers: Vec<(i64, i64)>,
+    /// IDs of the mail included in the packet.
+    pub mail_ids: Vec<i64>,
+}
+
+/// Result of importing a REP packet.
+#[derive(Debug, Clone, Default)]
+pub struct ImportResult {
+    /// Number of posts created.
+    pub posts: usize,
+    /// Number of posts queued for moderator approval.
+    pub pending: usize,
+    /// Number of mails sent.
+    pub mails: usize,
+    /// Messages that could not be imported, with the reason.
+    pub failed: Vec<String>,
+}
+
+/// Service for QWK offline mail.
+pub struct QwkService<'a> {
+    db: &'a Database,
+}
+
+impl<'a> QwkService<'a> {
+    /// Create a new QwkService with the given database reference.
+    pub fn new(db: &'a Database) -> Self {
+        Self { db }
+    }
+
+    /// Build a QWK packet with the user's unread mail and posts.
+    ///
+    /// Conference 0 holds private mail; other conference numbers are board
+    /// IDs. Read pointers are not changed; call [`Self::commit`] once the
+    /// packet has been delivered.
+    pub async fn build_packet(&self, user: &User, options: &PacketOptions) -> Result<QwkPacket> {
+        let mut names = NameCache::new(self.db);
+        let mut writer = PacketWriter::new(&options.bbs_name, user.encoding);
+
+        // Private mail first, oldest first
+        let mail_repo = MailRepository::new(self.db.pool());
+        let mut mail_ids = Vec::new();
+        for mail in mail_repo.list_inbox(user.id).await?.into_iter().rev() {
+            if mail.is_read || writer.len() >= options.max_messages {
+                continue;
+            }
+            let message = QwkMessage {
+                conference: MAIL_CONFERENCE,
+                number: mail.id,
+                private: true,
+                read: false,
+                date: parse_local(&format_utc_datetime(
+                    &mail.created_at,
+                    &options.timezone,
+                    DATE_FORMAT,
+                )),
+                to: user.username.clone(),
+                from: names.username(mail.sender_id).await?,
+                subject: mail.subject.clone(),
+                reference: 0,
+                body: mail.body.clone(),
+            };
+            writer.add(&message, true);
+            mail_ids.push(mail.id);
+        }
+
+        // Then unread posts, in board order, without those the user's twit
+        // filters hide
+        let kill_file = TwitFilterService::new(self.db.pool())
+            .kill_file(user.id)
+            .await?;
+        let unread_repo = UnreadRepository::new(self.db.pool()).with_kill_file(&kill_file);
+        let post_repo = PostRepository::new(self.db.pool());
+        let thread_repo = ThreadRepository::new(self.db.pool());
+        let mut thread_titles: HashMap<i64, String> = HashMap::new();
+        let mut pointers: Vec<(i64, i64)> = Vec::new();
+
+        for unread in unread_repo.get_all_unread_posts(user.id, user.role).await? {
+            if writer.len() >= options.max_messages {
+                break;
+            }
+            let post = unread.post;
+            let Ok(conference) = u16::try_from(post.board_id) else {
+                continue;
+            };
+
+            let subject = match post.thread_id {
+                Some(thread_id) => match thread_titles.get(&thread_id) {
+                    Some(title) => title.clone(),
+                    None => {
+                        let title = thread_repo
+                            .get_by_id(thread_id)
+                            .await?
+                            .map(|t| t.title)
+                            .unwrap_or_default();
+                        thread_titles.insert(thread_id, title.clone());
+                        title
+                    }
+                },
+                None => post.title.clone().unwrap_or_default(),
+            };
+
+            let (to, reference) = match post.parent_id {
+                Some(parent_id) => match post_repo.get_by_id(parent_id).await? {
+                    Some(parent) => (names.username(parent.author_id).await?, parent_id),
+                    None => ("ALL".to_string(), 0),
+                },
+                None => ("ALL".to_string(), 0),
+            };
+            let personal = to.eq_ignore_ascii_case(&user.username);
+
+            let message = QwkMessage {
+                conference,
+                number: post.id,
+                private: false,
+                read: false,
+                date: parse_local(&format_datetime(
+                    &post.created_at,
+                    &options.timezone,
+                    DATE_FORMAT,
+                )),
+                to,
+                from: names.username(post.author_id).await?,
+                subject,
+                reference,
+                body: post.body.clone(),
+            };
+            writer.add(&message, personal);
+
+            match pointers.last_mut() {
+                Some((board_id, last)) if *board_id == post.board_id => *last = post.id,
+                _ => pointers.push((post.board_id, post.id)),
+            }
+        }
+
+        let mut conferences = vec![(MAIL_CONFERENCE, "Mail".to_string())];
+        let boards = BoardService::new(self.db).list_boards(user.role).await?;
+        for board in boards {
+            if let Ok(number) = u16::try_from(board.id) {
+                conferences.push((number, board.name));
+            }
+        }
+
+        let message_count = writer.len();
+        let control = ControlInfo {
+            bbs_name: options.bbs_name.clone(),
+            bbs_id: options.bbs_id.clone(),
+            sysop_name: options.sysop_name.clone(),
+            user_name: user.username.clone(),
+            created: parse_local(&format_utc_datetime(
+                &Utc::now(),
+                &options.timezone,
+                DATE_FORMAT,
+            )),
+            conferences,
+        };
+
+        Ok(QwkPacket {
+            filename: format!("{}.QWK", options.bbs_id),
+            data: writer.finish(&control)?,
+            message_count,
+            pointers,
+            mail_ids,
+        })
+    }
+
+    /// Advance read pointers past the messages in a delivered packet.
+    pub async fn commit(&self, user_id: i64, packet: &QwkPacket) -> Result<()> {
+        let unread_repo = UnreadRepository::new(self.db.pool());
+        for &(board_id, post_id) in &packet.pointers {
+            unread_repo.mark_as_read(user_id, board_id, post_id).await?;
+        }
+
+        let mail_repo = MailRepository::new(self.db.pool());
+        for &mail_id in &packet.mail_ids {
+            mail_repo.mark_as_read(mail_id).await?;
+        }
+        Ok(())
+    }
+
+    /// Advance read pointers to a snapshot returned by a client.
+    ///
+    /// Used for Web downloads, where the server cannot tell whether the
+    /// packet arrived. Pointers to posts the user cannot read and mail
+    /// addressed to someone else are ignored; pointers never move back.
+    pub async fn confirm(
+        &self,
+        user: &User,
+        pointers: &[(i64, i64)],
+        mail_ids: &[i64],
+    ) -> Result<()> {
+        let board_repo = BoardRepository::new(self.db.pool());
+        let post_repo = PostRepository::new(self.db.pool());
+        let unread_repo = UnreadRepository::new(self.db.pool());
+        for &(board_id, post_id) in pointers {
+            let Some(post) = post_repo.get_by_id(post_id).await? else {
+                continue;
+            };
+            let readable = board_repo
+                .get_by_id(board_id)
+                .await?
+                .is_some_and(|board| board.can_read(user.role));
+            if post.board_id == board_id && readable {
+                unread_repo.mark_as_read(user.id, board_id, post_id).await?;
+            }
+        }
+
+        let mail_repo = MailRepository::new(self.db.pool());
+        for &mail_id in mail_ids {
+            if let Some(mail) = mail_repo.get_by_id(mail_id).await? {
+                if mail.recipient_id == user.id {
+                    mail_repo.mark_as_read(mail_id).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Import a REP packet uploaded by the user.
+    ///
+    /// Conference 0 messages are sent as mail to the addressee. On thread
+    /// boards a message that references a post in the same board becomes a
+    /// reply in that thread; anything else starts a new thread titled with
+    /// the subject. On flat boards each message becomes a post.
+    ///
+    /// Errors in individual messages are collected in the result; only an
+    /// unreadable packet fails as a whole.
+    pub async fn import_reply(
+        &self,
+        user: &User,
+        options: &PacketOptions,
+        data: &[u8],
+    ) -> Result<ImportResult> {
+        let messages = read_reply_packet(data, &options.bbs_id, user.encoding)?;
+        let mut result = ImportResult::default();
+
+        for message in messages {
+            if message.conference == MAIL_CONFERENCE {
+                match self.import_mail(user, &message).await {
+                    Ok(()) => result.mails += 1,
+                    Err(e) => result.failed.push(describe_failure(&message, &e)),
+                }
+            } else {
+                match self.import_post(user, &message).await {
+                    Ok(Submission::Posted(_)) => result.posts += 1,
+                    Ok(Submission::Pending(_)) => result.pending += 1,
+                    Err(e) => result.failed.push(describe_failure(&message, &e)),
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    async fn import_mail(&self, user: &User, message: &QwkMessage) -> Result<()> {
+        let request = SendMailRequest::new(
+            user.id,
+            message.to.clone(),
+            message.subject.clone(),
+            message.body.clone(),
+        );
+        MailService::new(self.db).send_mail(&request).await?;
+        Ok(())
+    }
+
+    async fn import_post(&self, user: &User, message: &QwkMessage) -> Result<Submission> {
+        let service = BoardService::new(self.db);
+        let board_id = i64::from(message.conference);
+        let board = service.get_board(board_id, user.role).await?;
+
+        let submission = match board.board_type {
+            BoardType::Thread => {
+                let parent = if message.reference > 0 {
+                    PostRepository::new(self.db.pool())
+                        .get_by_id(message.reference)
+                        .await?
+                        .filter(|p| p.board_id == board_id && p.thread_id.is_some())
+                } else {
+                    None
+                };
+
+                match parent {
+                    Some(parent) => {
+                        let thread_id = parent.thread_id.unwrap_or_default();
+                        service
+                            .submit_reply(
+                                thread_id,
+                                Some(parent.id),
+                                user.id,
+                                message.body.clone(),
+                                user.role,
+                            )
+                            .await?
+                    }
+                    None => {
+                        service
+                            .submit_thread(
+                                board_id,
+                                message.subject.clone(),
+                                user.id,
+                                message.body.clone(),
+                                user.role,
+                            )
+                            .await?
+                    }
+                }
+            }
+            BoardType::Flat => {
+                service
+                    .submit_flat_post(
+                        board_id,
+                        user.id,
+                        message.subject.clone(),
+                        message.body.clone(),
+                        user.role,
+                    )
+                    .await?
+            }
+        };
+        Ok(submission)
+    }
+}
+
+/// Looks up usernames by user ID, caching the results.
+struct NameCache<'a> {
+    repo: UserRepository<'a>,
+    names: HashMap<i64, String>,
+}
+
+impl<'a> NameCache<'a> {
+    fn new(db: &'a Database) -> Self {
+        Self {
+            repo: UserRepository::new(db.pool()),
+            names: HashMap::new(),
+        }
+    }
+
+    async fn username(&mut self, user_id: i64) -> Result<String> {
+        if let Some(name) = self.names.get(&user_id) {
+            return Ok(name.clone());
+        }
+        let name = self
+            .repo
+            .get_by_id(user_id)
+            .await?
+            .map(|u| u.username)
+            .unwrap_or_else(|| "unknown".to_string());
+        self.names.insert(user_id, name.clone());
+        Ok(name)
+    }
+}
+
+fn parse_local(text: &str) -> NaiveDateTime {
+    NaiveDateTime::parse_from_str(text, DATE_FORMAT).unwrap_or_default()
+}
+
+fn describe_failure(message: &QwkMessage, error: &HobbsError) -> String {
+    format!("[{}] {}: {}", message.conference, message.subject, error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::{BoardRepository, NewBoard};
+    use crate::db::{NewUser, Role};
+    use crate::qwk::packet::write_reply_packet;
+    use crate::server::encoding::CharacterEncoding;
+    use std::io::{Cursor, Read};
+
+    async fn setup_db() -> Database {
+        Database::open_in_memory().await.unwrap()
+    }
+
+    async fn create_user(db: &Database, name: &str) -> User {
+        UserRepository::new(db.pool())
+            .create(&NewUser::new(name, "hash", name).with_role(Role::Member))
+            .await
+            .unwrap()
+    }
+
+    fn messages_dat(packet: &QwkPacket) -> Vec<u8> {
+        let mut archive = zip::ZipArchive::new(Cursor::new(&packet.data)).unwrap();
+        let mut data = Vec::new();
+        archive
+            .by_name("MESSAGES.DAT")
+            .unwrap()
+            .read_to_end(&mut data)
+            .unwrap();
+        data
+    }
+
+    #[tokio::test]
+    async fn test_build_packet_and_commit() {
+        let db = setup_db().await;
+        let alice = create_user(&db, "alice").await;
+        let bob = create_user(&db, "bob").await;
+        let board = BoardRepository::new(db.pool())
+            .create(&NewBoard::new("general"))
+            .await
+            .unwrap();
+
+        let service = BoardService::new(&db);
+        let thread = service
+            .create_thread(board.id, "Hello", alice.id, Role::Member)
+            .await
+            .unwrap();
+        let first = service
+            .create_thread_post(thread.id, alice.id, "first post", Role::Member)
+            .await
+            .unwrap();
+        service
+            .create_reply(thread.id, Some(first.id), alice.id, "reply", Role::Member)
+            .await
+            .unwrap();
+        MailService::new(&db)
+            .send_mail(&SendMailRequest::new(alice.id, "bob", "Hi", "private"))
+            .await
+            .unwrap();
+
+        let qwk = QwkService::new(&db);
+        let options = PacketOptions::default();
+        let packet = qwk.build_packet(&bob, &options).await.unwrap();
+        assert_eq!(packet.filename, "HOBBS.QWK");
+        assert_eq!(packet.message_count, 3);
+        assert_eq!(packet.mail_ids.len(), 1);
+        assert_eq!(packet.pointers, vec![(board.id, first.id + 1)]);
+
+        let messages =
+            crate::qwk::parse_messages(&messages_dat(&packet)[128..], bob.encoding).unwrap();
+        assert_eq!(messages[0].conference, MAIL_CONFERENCE);
+        assert_eq!(messages[0].from, "alice");
+        assert_eq!(messages[1].conference as i64, board.id);
+        assert_eq!(messages[1].subject, "Hello");
+        assert_eq!(messages[1].to, "ALL");
+        assert_eq!(messages[2].to, "alice");
+        assert_eq!(messages[2].reference, first.id);
+
+        // Nothing changes until the packet is committed
+        let again = qwk.build_packet(&bob, &options).await.unwrap();
+        assert_eq!(again.message_count, 3);
+
+        qwk.commit(bob.id, &packet).await.unwrap();
+        let empty = qwk.build_packet(&bob, &options).await.unwrap();
+        assert_eq!(empty.message_count, 0);
+        assert!(empty.pointers.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_build_packet_respects_max_messages() {
+        let db = setup_db().await;
+        let alice = create_user(&db, "alice").await;
+        let board = BoardRepository::new(db.pool())
+            .create(&NewBoard::new("general"))
+            .await
+            .unwrap();
+        let service = BoardService::new(&db);
+        let thread = service
+            .create_thread(board.id, "Many", alice.id, Role::Member)
+            .await
+            .unwrap();
+        let mut ids = Vec::new();
+        for i in 0..3 {
+            let post = service
+                .create_thread_post(thread.id, alice.id, format!("post {}", i), Role::Member)
+                .await
+                .unwrap();
+            ids.push(post.id);
+        }
+
+        let qwk = QwkService::new(&db);
+        let options = PacketOptions {
+            max_messages: 2,
+            ..PacketOptions::default()
+        };
+        let packet = qwk.build_packet(&alice, &options).await.unwrap();
+        assert_eq!(packet.message_count, 2);
+        assert_eq!(packet.pointers, vec![(board.id, ids[1])]);
+
+        qwk.commit(alice.id, &packet).await.unwrap();
+        let rest = qwk.build_packet(&alice, &options).await.unwrap();
+        assert_eq!(rest.message_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_import_reply() {
+        let db = setup_db().await;
+        let alice = create_user(&db, "alice").await;
+        let bob = create_user(&db, "bob").await;
+        let board_repo = BoardRepository::new(db.pool());
+        let threads = board_repo.create(&NewBoard::new("threads")).await.unwrap();
+        let flat = board_repo
+            .create(&NewBoard::new("flat").with_board_type(BoardType::Flat))
+            .await
+            .unwrap();
+        let service = BoardService::new(&db);
+        let thread = service
+            .create_thread(threads.id, "Topic", alice.id, Role::Member)
+            .await
+            .unwrap();
+        let first = service
+            .create_thread_post(thread.id, alice.id, "question", Role::Member)
+            .await
+            .unwrap();
+
+        let message = |conference: i64, to: &str, subject: &str, reference: i64, body: &str| {
+            QwkMessage {
+                conference: conference as u16,
+                number: 0,
+                private: conference == 0,
+                read: false,
+                date: NaiveDateTime::default(),
+                to: to.to_string(),
+                from: "bob".to_string(),
+                subject: subject.to_string(),
+                reference,
+                body: body.to_string(),
+            }
+        };
+        let messages = vec![
+            message(threads.id, "alice", "Re: Topic", first.id, "answer"),
+            message(threads.id, "ALL", "New topic", 0, "new thread"),
+            message(flat.id, "ALL", "Flat", 0, "flat post"),
+            message(0, "alice", "Private", 0, "hello alice"),
+            message(0, "nobody", "Lost", 0, "no such user"),
+            message(999, "ALL", "Missing", 0, "no such board"),
+        ];
+        let data = write_reply_packet("HOBBS", &messages, CharacterEncoding::ShiftJIS).unwrap();
+
+        let qwk = QwkService::new(&db);
+        let result = qwk
+            .import_reply(&bob, &PacketOptions::default(), &data)
+            .await
+            .unwrap();
+        assert_eq!(result.posts, 3);
+        assert_eq!(result.mails, 1);
+        assert_eq!(result.failed.len(), 2);
+
+        let posts = service
+            .list_all_posts_in_thread(thread.id, Role::Member)
+            .await
+            .unwrap();
+        assert_eq!(posts.len(), 2);
+        let answer = posts.iter().find(|p| p.body == "answer").unwrap();
+        assert_eq!(answer.parent_id, Some(first.id));
+
+        let all_threads = service.list_all_threads(threads.id, Role::Member).await.unwrap();
+        assert!(all_threads.iter().any(|t| t.title == "New topic"));
+
+        let flat_posts = service
+            .list_all_posts_in_flat_board(flat.id, Role::Member)
+            .await
+            .unwrap();
+        assert_eq!(flat_posts[0].title.as_deref(), Some("Flat"));
+
+        let inbox = MailService::new(&db).list_inbox(alice.id).await.unwrap();
+        assert_eq!(inbox[0].body, "hello alice");
+    }
+
+    #[tokio::test]
+    async fn test_import_reply_wrong_bbs_id() {
+        let db = setup_db().await;
+        let bob = create_user(&db, "bob").await;
+        let data = write_reply_packet("OTHER", &[], CharacterEncoding::ShiftJIS).unwrap();
+
+        let result = QwkService::new(&db)
+            .import_reply(&bob, &PacketOptions::default(), &data)
+            .await;
+        assert!(matches!(result, Err(HobbsError::Qwk(_))));
+    }
+}
@@ -0,0 +1,18 @@
+//! Board retention module for HOBBS.
+//!
+//! Boards can limit how many posts they keep and how old posts may get.
+//! A background job prunes the oldest posts of such boards:
+//! - Posts in pinned threads are never pruned
+//! - Thread post counts are recomputed and emptied threads removed
+//! - Read positions on pruned posts move back to the nearest kept post
+//! - Boards in archive mode write pruned posts to a JSON archive first
+//!
+//! A report of each run that pruned anything is mailed to the SysOps.
+
+pub mod repository;
+pub mod service;
+pub mod updater;
+
+pub use repository::RetentionRepository;
+pub use service::{report_body, PruneResult, RetentionService, REPORT_MAIL_SUBJECT};
+pub use updater::{start_retention_with_config, RetentionUpdater};
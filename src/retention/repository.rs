@@ -0,0 +1,212 @@
+//! Retention repository for HOBBS.
+//!
+//! Selecting and deleting the posts removed by a board's retention rules.
+//! Every query takes the board ID as `$1`, the age cutoff as `$2` and the
+//! highest post ID pruned by the post limit as `$3`; either rule may be
+//! NULL.
+
+use crate::board::Post;
+use crate::db::{DbPool, SQL_TRUE};
+use crate::{HobbsError, Result};
+
+/// Condition matching posts that the rules prune, for the posts table
+/// aliased as `alias`.
+///
+/// Posts in pinned threads never match.
+fn prunable(alias: &str) -> String {
+    format!(
+        "({a}.created_at < COALESCE($2, '') OR {a}.id <= COALESCE($3, 0))
+         AND NOT EXISTS (SELECT 1 FROM threads pt
+                         WHERE pt.id = {a}.thread_id AND pt.is_pinned = {t})",
+        a = alias,
+        t = SQL_TRUE
+    )
+}
+
+/// Repository for pruning boards.
+pub struct RetentionRepository<'a> {
+    pool: &'a DbPool,
+}
+
+impl<'a> RetentionRepository<'a> {
+    /// Create a new RetentionRepository with the given database pool.
+    pub fn new(pool: &'a DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Count the posts of a board the rules prune.
+    pub async fn count_prunable(
+        &self,
+        board_id: i64,
+        cutoff: Option<&str>,
+        up_to: Option<i64>,
+    ) -> Result<i64> {
+        let query = format!(
+            "SELECT COUNT(*) FROM posts p WHERE p.board_id = $1 AND {}",
+            prunable("p")
+        );
+        sqlx::query_scalar(&query)
+            .bind(board_id)
+            .bind(cutoff)
+            .bind(up_to)
+            .fetch_one(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// Find the highest post ID to prune so that `excess` more posts go.
+    ///
+    /// Only posts kept by the age cutoff and outside pinned threads count,
+    /// oldest first. Returns None when there are no such posts.
+    pub async fn excess_threshold(
+        &self,
+        board_id: i64,
+        cutoff: Option<&str>,
+        excess: i64,
+    ) -> Result<Option<i64>> {
+        let query = format!(
+            "SELECT p.id FROM posts p
+             WHERE p.board_id = $1 AND p.created_at >= COALESCE($2, '')
+               AND NOT EXISTS (SELECT 1 FROM threads pt
+                               WHERE pt.id = p.thread_id AND pt.is_pinned = {})
+             ORDER BY p.id ASC LIMIT $3",
+            SQL_TRUE
+        );
+        let ids: Vec<i64> = sqlx::query_scalar(&query)
+            .bind(board_id)
+            .bind(cutoff)
+            .bind(excess)
+            .fetch_all(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(ids.last().copied())
+    }
+
+    /// List the posts of a board the rules prune, oldest first.
+    pub async fn list_prunable(
+        &self,
+        board_id: i64,
+        cutoff: Option<&str>,
+        up_to: Option<i64>,
+    ) -> Result<Vec<Post>> {
+        let query = format!(
+            "SELECT p.id, p.board_id, p.thread_id, p.parent_id, p.author_id, p.title, p.body,
+                    p.created_at, p.edited_at, p.edited_by
+             FROM posts p WHERE p.board_id = $1 AND {}
+             ORDER BY p.id ASC",
+            prunable("p")
+        );
+        sqlx::query_as::<_, Post>(&query)
+            .bind(board_id)
+            .bind(cutoff)
+            .bind(up_to)
+            .fetch_all(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// Delete the posts of a board the rules prune.
+    ///
+    /// Read positions on pruned posts move back to the newest kept post
+    /// before them, or are removed when there is none, so no kept post
+    /// becomes unread. The post counts of affected threads are recomputed
+    /// and threads left without posts are deleted.
+    ///
+    /// Returns the number of deleted posts and threads.
+    pub async fn prune(
+        &self,
+        board_id: i64,
+        cutoff: Option<&str>,
+        up_to: Option<i64>,
+    ) -> Result<(u64, u64)> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        let pruned_ids = format!(
+            "SELECT p.id FROM posts p WHERE p.board_id = $1 AND {}",
+            prunable("p")
+        );
+        let kept_before = format!(
+            "FROM posts k WHERE k.board_id = $1
+               AND k.id <= read_positions.last_read_post_id AND NOT ({})",
+            prunable("k")
+        );
+
+        let query = format!(
+            "DELETE FROM read_positions
+             WHERE board_id = $1 AND last_read_post_id IN ({pruned_ids})
+               AND NOT EXISTS (SELECT 1 {kept_before})"
+        );
+        sqlx::query(&query)
+            .bind(board_id)
+            .bind(cutoff)
+            .bind(up_to)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        let query = format!(
+            "UPDATE read_positions SET last_read_post_id = (SELECT MAX(k.id) {kept_before})
+             WHERE board_id = $1 AND last_read_post_id IN ({pruned_ids})"
+        );
+        sqlx::query(&query)
+            .bind(board_id)
+            .bind(cutoff)
+            .bind(up_to)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        let query = format!(
+            "SELECT DISTINCT p.thread_id FROM posts p
+             WHERE p.board_id = $1 AND p.thread_id IS NOT NULL AND {}",
+            prunable("p")
+        );
+        let thread_ids: Vec<i64> = sqlx::query_scalar(&query)
+            .bind(board_id)
+            .bind(cutoff)
+            .bind(up_to)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        let query = format!("DELETE FROM posts WHERE id IN ({pruned_ids})");
+        let posts = sqlx::query(&query)
+            .bind(board_id)
+            .bind(cutoff)
+            .bind(up_to)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?
+            .rows_affected();
+
+        let mut threads = 0;
+        for thread_id in thread_ids {
+            sqlx::query(
+                "UPDATE threads SET post_count = (SELECT COUNT(*) FROM posts WHERE thread_id = $1)
+                 WHERE id = $1",
+            )
+            .bind(thread_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+            threads += sqlx::query("DELETE FROM threads WHERE id = $1 AND post_count = 0")
+                .bind(thread_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| HobbsError::Database(e.to_string()))?
+                .rows_affected();
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok((posts, threads))
+    }
+}
@@ -0,0 +1,471 @@
+//! Retention service for HOBBS.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{Duration, Utc};
+use tracing::{info, warn};
+
+use super::repository::RetentionRepository;
+use crate::archive::ArchiveService;
+use crate::board::{Board, BoardRepository, PostRepository};
+use crate::db::Database;
+use crate::mail::SystemMailService;
+use crate::{HobbsError, Result};
+
+/// Subject of the pruning report mailed to the SysOps.
+pub const REPORT_MAIL_SUBJECT: &str = "掲示板の自動整理レポート";
+
+/// What was pruned from one board.
+#[derive(Debug, Clone)]
+pub struct PruneResult {
+    /// Board ID.
+    pub board_id: i64,
+    /// Board name.
+    pub board_name: String,
+    /// Number of deleted posts.
+    pub posts: u64,
+    /// Number of deleted threads.
+    pub threads: u64,
+    /// Archive file holding the pruned posts (archive mode only).
+    pub archive_path: Option<PathBuf>,
+}
+
+/// Service applying board retention rules.
+pub struct RetentionService<'a> {
+    db: &'a Database,
+}
+
+impl<'a> RetentionService<'a> {
+    /// Create a new RetentionService.
+    pub fn new(db: &'a Database) -> Self {
+        Self { db }
+    }
+
+    /// Prune every board that has retention rules.
+    ///
+    /// A board that fails is logged and skipped. Returns the boards that
+    /// had posts pruned.
+    pub async fn prune_all(&self, archive_dir: &Path) -> Result<Vec<PruneResult>> {
+        let boards = BoardRepository::new(self.db.pool()).list_all().await?;
+        let mut results = Vec::new();
+        for board in boards.iter().filter(|b| b.has_retention()) {
+            match self.prune_board(board, archive_dir).await {
+                Ok(Some(result)) => results.push(result),
+                Ok(None) => {}
+                Err(e) => warn!("Failed to prune board {}: {}", board.name, e),
+            }
+        }
+        Ok(results)
+    }
+
+    /// Prune the posts of a board its retention rules no longer keep.
+    ///
+    /// Posts older than `max_age_days` go first, then the oldest posts
+    /// until at most `max_posts` remain. Posts in pinned threads are kept
+    /// and still count towards the limit. Boards in archive mode write the
+    /// posts to `archive_dir` before they are deleted.
+    ///
+    /// Returns None when nothing was pruned.
+    pub async fn prune_board(
+        &self,
+        board: &Board,
+        archive_dir: &Path,
+    ) -> Result<Option<PruneResult>> {
+        let repo = RetentionRepository::new(self.db.pool());
+
+        let cutoff = board.max_age_days.map(|days| {
+            (Utc::now() - Duration::days(days))
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        });
+        let up_to = match board.max_posts {
+            Some(max_posts) => {
+                let total = PostRepository::new(self.db.pool())
+                    .count_by_board(board.id)
+                    .await?;
+                let expired = match cutoff {
+                    Some(ref cutoff) => repo.count_prunable(board.id, Some(cutoff), None).await?,
+                    None => 0,
+                };
+                let excess = total - expired - max_posts;
+                if excess > 0 {
+                    repo.excess_threshold(board.id, cutoff.as_deref(), excess)
+                        .await?
+                } else {
+                    None
+                }
+            }
+            None => None,
+        };
+        if cutoff.is_none() && up_to.is_none() {
+            return Ok(None);
+        }
+
+        let archive_path = if board.archive_pruned {
+            let posts = repo
+                .list_prunable(board.id, cutoff.as_deref(), up_to)
+                .await?;
+            if posts.is_empty() {
+                return Ok(None);
+            }
+            let archive = ArchiveService::new(self.db)
+                .export_posts(board, posts)
+                .await?;
+            let path = archive_dir.join(format!(
+                "board-{}-{}.json",
+                board.id,
+                Utc::now().format("%Y%m%d-%H%M%S")
+            ));
+            std::fs::create_dir_all(archive_dir).map_err(HobbsError::Io)?;
+            std::fs::write(&path, archive.to_json()?).map_err(HobbsError::Io)?;
+            Some(path)
+        } else {
+            None
+        };
+
+        let (posts, threads) = repo.prune(board.id, cutoff.as_deref(), up_to).await?;
+        if posts == 0 {
+            return Ok(None);
+        }
+        info!(
+            "Pruned {} posts and {} threads from board {}",
+            posts, threads, board.name
+        );
+
+        Ok(Some(PruneResult {
+            board_id: board.id,
+            board_name: board.name.clone(),
+            posts,
+            threads,
+            archive_path,
+        }))
+    }
+
+    /// Mail a report of pruned boards to the SysOps.
+    ///
+    /// Returns the number of mails sent.
+    pub async fn send_report(&self, results: &[PruneResult]) -> Result<usize> {
+        if results.is_empty() {
+            return Ok(0);
+        }
+        SystemMailService::new(self.db)
+            .notify_sysops(REPORT_MAIL_SUBJECT, &report_body(results))
+            .await
+    }
+}
+
+/// Build the body of the pruning report mail.
+pub fn report_body(results: &[PruneResult]) -> String {
+    let mut body = String::from("保持ルールにより以下の掲示板を整理しました。\n\n");
+    for result in results {
+        body.push_str(&format!("■ {}\n", result.board_name));
+        body.push_str(&format!("  削除した投稿: {}件\n", result.posts));
+        body.push_str(&format!("  削除したスレッド: {}件\n", result.threads));
+        if let Some(ref path) = result.archive_path {
+            body.push_str(&format!("  アーカイブ: {}\n", path.display()));
+        }
+        body.push('\n');
+    }
+    body.push_str("--\nHOBBS System\n");
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::archive::BoardArchive;
+    use crate::board::{
+        BoardService, BoardType, BoardUpdate, NewBoard, ThreadRepository, ThreadUpdate,
+        UnreadRepository,
+    };
+    use crate::db::{NewUser, Role, User, UserRepository};
+    use crate::mail::MailRepository;
+
+    async fn create_user(db: &Database, name: &str, role: Role) -> User {
+        UserRepository::new(db.pool())
+            .create(&NewUser::new(name, "hash", name).with_role(role))
+            .await
+            .unwrap()
+    }
+
+    async fn set_created_at(db: &Database, post_id: i64, created_at: &str) {
+        sqlx::query("UPDATE posts SET created_at = $1 WHERE id = $2")
+            .bind(created_at)
+            .bind(post_id)
+            .execute(db.pool())
+            .await
+            .unwrap();
+    }
+
+    fn no_archive() -> PathBuf {
+        PathBuf::from("unused")
+    }
+
+    #[tokio::test]
+    async fn test_prune_board_without_rules() {
+        let db = Database::open_in_memory().await.unwrap();
+        let board = BoardRepository::new(db.pool())
+            .create(&NewBoard::new("general"))
+            .await
+            .unwrap();
+
+        let result = RetentionService::new(&db)
+            .prune_board(&board, &no_archive())
+            .await
+            .unwrap();
+        assert!(result.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_prune_max_posts_keeps_counts_consistent() {
+        let db = Database::open_in_memory().await.unwrap();
+        let alice = create_user(&db, "alice", Role::Member).await;
+        let board_repo = BoardRepository::new(db.pool());
+        let board = board_repo.create(&NewBoard::new("general")).await.unwrap();
+        let service = BoardService::new(&db);
+
+        // Thread A: 2 posts, thread B: 2 posts
+        let a = service
+            .create_thread(board.id, "A", alice.id, Role::Member)
+            .await
+            .unwrap();
+        let a1 = service
+            .create_thread_post(a.id, alice.id, "a1", Role::Member)
+            .await
+            .unwrap();
+        let a2 = service
+            .create_thread_post(a.id, alice.id, "a2", Role::Member)
+            .await
+            .unwrap();
+        let b = service
+            .create_thread(board.id, "B", alice.id, Role::Member)
+            .await
+            .unwrap();
+        let b1 = service
+            .create_thread_post(b.id, alice.id, "b1", Role::Member)
+            .await
+            .unwrap();
+        service
+            .create_thread_post(b.id, alice.id, "b2", Role::Member)
+            .await
+            .unwrap();
+
+        // Read positions on a pruned post, and before any kept post
+        let unread = UnreadRepository::new(db.pool());
+        let bob = create_user(&db, "bob", Role::Member).await;
+        let carol = create_user(&db, "carol", Role::Member).await;
+        unread.mark_as_read(bob.id, board.id, b1.id).await.unwrap();
+        unread
+            .mark_as_read(carol.id, board.id, a2.id)
+            .await
+            .unwrap();
+
+        let board = board_repo
+            .update(board.id, &BoardUpdate::new().max_posts(Some(1)))
+            .await
+            .unwrap()
+            .unwrap();
+        let result = RetentionService::new(&db)
+            .prune_board(&board, &no_archive())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.posts, 3);
+        assert_eq!(result.threads, 1);
+        assert!(result.archive_path.is_none());
+
+        let thread_repo = ThreadRepository::new(db.pool());
+        assert!(thread_repo.get_by_id(a.id).await.unwrap().is_none());
+        assert_eq!(
+            thread_repo
+                .get_by_id(b.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .post_count,
+            1
+        );
+        assert!(PostRepository::new(db.pool())
+            .get_by_id(a1.id)
+            .await
+            .unwrap()
+            .is_none());
+
+        // No kept post is older than the read positions, so both start over
+        assert!(unread
+            .get_read_position(bob.id, board.id)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(unread
+            .get_read_position(carol.id, board.id)
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(unread.get_unread_count(bob.id, board.id).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_prune_max_age_moves_read_positions() {
+        let db = Database::open_in_memory().await.unwrap();
+        let alice = create_user(&db, "alice", Role::Member).await;
+        let board_repo = BoardRepository::new(db.pool());
+        let board = board_repo
+            .create(&NewBoard::new("news").with_board_type(BoardType::Flat))
+            .await
+            .unwrap();
+        let service = BoardService::new(&db);
+        let mut posts = Vec::new();
+        for title in ["p1", "p2", "p3"] {
+            posts.push(
+                service
+                    .create_flat_post(board.id, alice.id, title, "body", Role::Member)
+                    .await
+                    .unwrap(),
+            );
+        }
+        // p2 is old, p1 and p3 are recent
+        set_created_at(&db, posts[1].id, "2000-01-01 00:00:00").await;
+
+        let bob = create_user(&db, "bob", Role::Member).await;
+        let unread = UnreadRepository::new(db.pool());
+        unread
+            .mark_as_read(bob.id, board.id, posts[1].id)
+            .await
+            .unwrap();
+
+        let board = board_repo
+            .update(board.id, &BoardUpdate::new().max_age_days(Some(30)))
+            .await
+            .unwrap()
+            .unwrap();
+        let result = RetentionService::new(&db)
+            .prune_board(&board, &no_archive())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.posts, 1);
+        assert_eq!(result.threads, 0);
+
+        let position = unread
+            .get_read_position(bob.id, board.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(position.last_read_post_id, posts[0].id);
+        assert_eq!(unread.get_unread_count(bob.id, board.id).await.unwrap(), 1);
+
+        // Running again prunes nothing
+        let result = RetentionService::new(&db)
+            .prune_board(&board, &no_archive())
+            .await
+            .unwrap();
+        assert!(result.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_prune_skips_pinned_threads() {
+        let db = Database::open_in_memory().await.unwrap();
+        let alice = create_user(&db, "alice", Role::Member).await;
+        let board = BoardRepository::new(db.pool())
+            .create(&NewBoard::new("general").with_retention(Some(1), Some(30), false))
+            .await
+            .unwrap();
+        let service = BoardService::new(&db);
+        let rules = service
+            .create_thread(board.id, "Rules", alice.id, Role::Member)
+            .await
+            .unwrap();
+        let rule = service
+            .create_thread_post(rules.id, alice.id, "be nice", Role::Member)
+            .await
+            .unwrap();
+        set_created_at(&db, rule.id, "2000-01-01 00:00:00").await;
+        ThreadRepository::new(db.pool())
+            .update(rules.id, &ThreadUpdate::new().pinned(true))
+            .await
+            .unwrap();
+        let chat = service
+            .create_thread(board.id, "Chat", alice.id, Role::Member)
+            .await
+            .unwrap();
+        service
+            .create_thread_post(chat.id, alice.id, "hi", Role::Member)
+            .await
+            .unwrap();
+
+        let result = RetentionService::new(&db)
+            .prune_board(&board, &no_archive())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.posts, 1);
+        assert!(PostRepository::new(db.pool())
+            .get_by_id(rule.id)
+            .await
+            .unwrap()
+            .is_some());
+        assert!(ThreadRepository::new(db.pool())
+            .get_by_id(chat.id)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_prune_archive_mode_and_report() {
+        let db = Database::open_in_memory().await.unwrap();
+        let sysop = create_user(&db, "sysop", Role::SysOp).await;
+        let alice = create_user(&db, "alice", Role::Member).await;
+        let board = BoardRepository::new(db.pool())
+            .create(
+                &NewBoard::new("news")
+                    .with_board_type(BoardType::Flat)
+                    .with_retention(Some(1), None, true),
+            )
+            .await
+            .unwrap();
+        let service = BoardService::new(&db);
+        for title in ["old", "new"] {
+            service
+                .create_flat_post(board.id, alice.id, title, "body", Role::Member)
+                .await
+                .unwrap();
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let retention = RetentionService::new(&db);
+        let results = retention.prune_all(dir.path()).await.unwrap();
+        assert_eq!(results.len(), 1);
+        let path = results[0].archive_path.clone().unwrap();
+        assert!(path.starts_with(dir.path()));
+
+        let archive = BoardArchive::from_json(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(archive.boards[0].name, "news");
+        assert_eq!(archive.boards[0].posts.len(), 1);
+        assert_eq!(archive.boards[0].posts[0].title.as_deref(), Some("old"));
+        assert_eq!(archive.boards[0].posts[0].author, "alice");
+
+        assert_eq!(retention.send_report(&results).await.unwrap(), 1);
+        let inbox = MailRepository::new(db.pool())
+            .list_inbox(sysop.id)
+            .await
+            .unwrap();
+        assert_eq!(inbox[0].subject, REPORT_MAIL_SUBJECT);
+        assert!(inbox[0].body.contains("■ news"));
+        assert!(inbox[0].body.contains("削除した投稿: 1件"));
+    }
+
+    #[test]
+    fn test_report_body() {
+        let body = report_body(&[PruneResult {
+            board_id: 1,
+            board_name: "general".to_string(),
+            posts: 12,
+            threads: 3,
+            archive_path: None,
+        }]);
+        assert!(body.contains("■ general\n  削除した投稿: 12件\n  削除したスレッド: 3件\n"));
+        assert!(!body.contains("アーカイブ"));
+    }
+}
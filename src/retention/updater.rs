@@ -0,0 +1,151 @@
+//! Retention background task for HOBBS.
+//!
+//! Periodically applies the boards' retention rules and mails a report of
+//! what was pruned to the SysOps.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use tokio::time::{interval, Duration};
+use tracing::{error, info};
+
+use super::service::{PruneResult, RetentionService};
+use crate::config::Config;
+use crate::db::Database;
+
+/// Retention background updater.
+pub struct RetentionUpdater {
+    db: Arc<Database>,
+    interval: Duration,
+    archive_dir: PathBuf,
+}
+
+impl RetentionUpdater {
+    /// Create a new RetentionUpdater.
+    pub fn new(db: Arc<Database>, interval_secs: u64, archive_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            db,
+            interval: Duration::from_secs(interval_secs),
+            archive_dir: archive_dir.into(),
+        }
+    }
+
+    /// Run the updater loop.
+    pub async fn run(&self) {
+        info!(
+            "Retention updater started (interval: {} seconds)",
+            self.interval.as_secs()
+        );
+
+        let mut timer = interval(self.interval);
+        loop {
+            timer.tick().await;
+            self.run_once().await;
+        }
+    }
+
+    /// Prune all boards and report the result to the SysOps.
+    pub async fn run_once(&self) -> Vec<PruneResult> {
+        let service = RetentionService::new(&self.db);
+
+        let results = match service.prune_all(&self.archive_dir).await {
+            Ok(results) => results,
+            Err(e) => {
+                error!("Failed to prune boards: {}", e);
+                return Vec::new();
+            }
+        };
+
+        if let Err(e) = service.send_report(&results).await {
+            error!("Failed to send retention report: {}", e);
+        }
+        results
+    }
+}
+
+/// Start the retention updater with configuration.
+///
+/// This function checks if retention is enabled in the config before
+/// starting. Returns true if the updater was started, false if it is
+/// disabled.
+pub fn start_retention_with_config(db: Arc<Database>, config: &Config) -> bool {
+    if !config.retention.enabled {
+        info!("Board retention is disabled in configuration");
+        return false;
+    }
+
+    let updater = RetentionUpdater::new(
+        db,
+        config.retention.interval_secs,
+        config.retention.archive_dir.clone(),
+    );
+    tokio::task::spawn_local(async move {
+        updater.run().await;
+    });
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_start_disabled() {
+        let db = Arc::new(Database::open_in_memory().await.unwrap());
+        let mut config = Config::default();
+        config.retention.enabled = false;
+        assert!(!start_retention_with_config(db, &config));
+    }
+
+    #[tokio::test]
+    async fn test_run_once_prunes_boards() {
+        use crate::board::{BoardRepository, BoardService, BoardType, NewBoard};
+        use crate::db::{NewUser, Role, UserRepository};
+        use crate::mail::MailRepository;
+
+        let db = Arc::new(Database::open_in_memory().await.unwrap());
+        let users = UserRepository::new(db.pool());
+        let sysop = users
+            .create(&NewUser::new("sysop", "hash", "sysop").with_role(Role::SysOp))
+            .await
+            .unwrap();
+        let board = BoardRepository::new(db.pool())
+            .create(
+                &NewBoard::new("general")
+                    .with_board_type(BoardType::Flat)
+                    .with_retention(Some(2), None, false),
+            )
+            .await
+            .unwrap();
+        for title in ["a", "b", "c"] {
+            BoardService::new(&db)
+                .create_flat_post(board.id, sysop.id, title, "body", Role::SysOp)
+                .await
+                .unwrap();
+        }
+        let updater = RetentionUpdater::new(db.clone(), 3600, "unused");
+
+        let results = updater.run_once().await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].posts, 1);
+        assert_eq!(
+            MailRepository::new(db.pool())
+                .list_inbox(sysop.id)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // Nothing left to prune, so no second report
+        assert!(updater.run_once().await.is_empty());
+        assert_eq!(
+            MailRepository::new(db.pool())
+                .list_inbox(sysop.id)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,437 @@
+//! Lightbar menu implementation.
+//!
+//! A lightbar is a vertical menu where the current selection is shown in
+//! reverse video and moved with the cursor keys. It is drawn directly above
+//! the prompt line and redraws individual rows with relative cursor movement,
+//! so it works below any amount of previously rendered content.
+//!
+//! The lightbar resolves every interaction to the same string the user
+//! would have typed at a hotkey prompt, so callers can feed the result into
+//! their existing command handling unchanged. Typing still works as it does
+//! at the prompt: typed text is echoed, highlights a matching entry, and is
+//! submitted with Enter.
+
+use super::Screen;
+use crate::server::Key;
+use crate::template::pad_to_width;
+
+/// A selectable lightbar entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LightbarItem {
+    /// Value returned when this item is chosen (e.g. "B" or "3").
+    pub value: String,
+    /// Text shown in the menu row.
+    pub label: String,
+    /// Key that highlights this item when typed.
+    pub hotkey: Option<char>,
+}
+
+impl LightbarItem {
+    /// Create a new item.
+    pub fn new(value: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            label: label.into(),
+            hotkey: None,
+        }
+    }
+
+    /// Set the key that highlights this item when typed.
+    pub fn with_hotkey(mut self, hotkey: char) -> Self {
+        self.hotkey = Some(hotkey);
+        self
+    }
+}
+
+/// Result of handling a key press.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LightbarEvent {
+    /// Send this output to update the display and keep reading keys.
+    Output(String),
+    /// The user made a choice; the value is the equivalent hotkey input.
+    Choose(String),
+    /// The key had no effect.
+    Ignore,
+}
+
+/// Lightbar menu state.
+#[derive(Debug, Clone)]
+pub struct Lightbar {
+    /// Menu entries.
+    items: Vec<LightbarItem>,
+    /// Index of the highlighted item.
+    selected: usize,
+    /// Index of the first visible item.
+    top: usize,
+    /// Number of rows shown at once.
+    visible: usize,
+    /// Row width in columns.
+    width: usize,
+    /// Display width of CJK characters.
+    cjk_width: usize,
+    /// Text typed so far.
+    typed: String,
+    /// Keys that resolve directly to a value (e.g. PgDn → "n").
+    bindings: Vec<(Key, String)>,
+}
+
+impl Lightbar {
+    /// Create a new lightbar.
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - Menu entries.
+    /// * `width` - Row width in columns.
+    /// * `visible` - Maximum number of rows shown at once.
+    /// * `cjk_width` - Display width of CJK characters (1 or 2).
+    pub fn new(items: Vec<LightbarItem>, width: usize, visible: usize, cjk_width: usize) -> Self {
+        let visible = visible.max(1).min(items.len().max(1));
+        Self {
+            items,
+            selected: 0,
+            top: 0,
+            visible,
+            width,
+            cjk_width,
+            typed: String::new(),
+            bindings: Vec::new(),
+        }
+    }
+
+    /// Bind a key to a fixed value.
+    pub fn bind(mut self, key: Key, value: impl Into<String>) -> Self {
+        self.bindings.push((key, value.into()));
+        self
+    }
+
+    /// Get the menu entries.
+    pub fn items(&self) -> &[LightbarItem] {
+        &self.items
+    }
+
+    /// Get the index of the highlighted item.
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Highlight the given item (without producing output).
+    pub fn select(&mut self, index: usize) {
+        if index < self.items.len() {
+            self.selected = index;
+            self.scroll_to_selected();
+        }
+    }
+
+    /// Render all visible rows. Each row ends with CRLF.
+    pub fn render(&self, screen: &dyn Screen) -> String {
+        let mut output = String::new();
+        for index in self.visible_range() {
+            output.push_str(&self.render_row(index, screen));
+            output.push_str("\r\n");
+        }
+        output
+    }
+
+    /// Handle a key press.
+    ///
+    /// Output in [`LightbarEvent::Output`] assumes the cursor is on the line
+    /// directly below the rows produced by [`Lightbar::render`].
+    pub fn handle_key(&mut self, key: Key, screen: &dyn Screen) -> LightbarEvent {
+        if let Some((_, value)) = self.bindings.iter().find(|(k, _)| *k == key) {
+            return LightbarEvent::Choose(value.clone());
+        }
+
+        if key.is_navigation() && !self.typed.is_empty() {
+            // Moving the highlight discards typed text
+            let erase = "\x08 \x08".repeat(self.typed.chars().count());
+            self.typed.clear();
+            return match self.handle_key(key, screen) {
+                LightbarEvent::Output(output) => LightbarEvent::Output(erase + &output),
+                _ => LightbarEvent::Output(erase),
+            };
+        }
+
+        match key {
+            Key::Up => self.move_to(self.wrap(self.selected, -1), screen),
+            Key::Down => self.move_to(self.wrap(self.selected, 1), screen),
+            Key::Home => self.move_to(0, screen),
+            Key::End => self.move_to(self.items.len().saturating_sub(1), screen),
+            Key::PageUp => self.move_to(self.selected.saturating_sub(self.visible), screen),
+            Key::PageDown => {
+                let last = self.items.len().saturating_sub(1);
+                self.move_to((self.selected + self.visible).min(last), screen)
+            }
+            Key::Enter => {
+                if !self.typed.is_empty() {
+                    LightbarEvent::Choose(std::mem::take(&mut self.typed))
+                } else {
+                    LightbarEvent::Choose(
+                        self.items
+                            .get(self.selected)
+                            .map(|item| item.value.clone())
+                            .unwrap_or_default(),
+                    )
+                }
+            }
+            Key::Backspace if !self.typed.is_empty() => {
+                self.typed.pop();
+                LightbarEvent::Output("\x08 \x08".to_string())
+            }
+            Key::Char(c) => {
+                self.typed.push(c);
+                let typed = &self.typed;
+                let matched = self.items.iter().position(|item| {
+                    item.value.eq_ignore_ascii_case(typed)
+                        || (typed.len() == 1
+                            && item.hotkey.is_some_and(|h| h.eq_ignore_ascii_case(&c)))
+                });
+                let mut output = match matched.map(|index| self.move_to(index, screen)) {
+                    Some(LightbarEvent::Output(s)) => s,
+                    _ => String::new(),
+                };
+                output.push(c);
+                LightbarEvent::Output(output)
+            }
+            _ => LightbarEvent::Ignore,
+        }
+    }
+
+    /// Range of item indices currently on screen.
+    fn visible_range(&self) -> std::ops::Range<usize> {
+        self.top..(self.top + self.visible).min(self.items.len())
+    }
+
+    /// Step through the items with wrap-around.
+    fn wrap(&self, index: usize, delta: isize) -> usize {
+        let len = self.items.len() as isize;
+        if len == 0 {
+            return 0;
+        }
+        (index as isize + delta).rem_euclid(len) as usize
+    }
+
+    /// Adjust the viewport so the selection is visible. Returns true if it scrolled.
+    fn scroll_to_selected(&mut self) -> bool {
+        let old_top = self.top;
+        if self.selected < self.top {
+            self.top = self.selected;
+        } else if self.selected >= self.top + self.visible {
+            self.top = self.selected + 1 - self.visible;
+        }
+        self.top != old_top
+    }
+
+    /// Move the highlight and produce the redraw output.
+    fn move_to(&mut self, index: usize, screen: &dyn Screen) -> LightbarEvent {
+        if self.items.is_empty() || index == self.selected {
+            return LightbarEvent::Ignore;
+        }
+
+        let previous = self.selected;
+        self.selected = index;
+        let rows: Vec<usize> = if self.scroll_to_selected() {
+            self.visible_range().collect()
+        } else {
+            vec![previous, index]
+        };
+
+        let mut output = String::new();
+        for row in rows {
+            output.push_str(&self.redraw_row(row, screen));
+        }
+        LightbarEvent::Output(output)
+    }
+
+    /// Redraw one row in place, returning the cursor to the prompt.
+    fn redraw_row(&self, index: usize, screen: &dyn Screen) -> String {
+        let lines_up = (self.top + self.visible - index) as u16;
+        format!(
+            "{}{}\r{}{}",
+            screen.save_cursor(),
+            screen.cursor_up(lines_up),
+            self.render_row(index, screen),
+            screen.restore_cursor()
+        )
+    }
+
+    /// Render a single row, highlighted if it is selected.
+    fn render_row(&self, index: usize, screen: &dyn Screen) -> String {
+        let label = pad_to_width(
+            &self.items[index].label,
+            self.width.saturating_sub(2),
+            self.cjk_width,
+        );
+        if index == self.selected {
+            format!("{} {} {}", screen.reverse(), label, screen.reset())
+        } else {
+            format!(" {} ", label)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::screen::AnsiScreen;
+
+    fn items(n: usize) -> Vec<LightbarItem> {
+        (1..=n)
+            .map(|i| LightbarItem::new(i.to_string(), format!("Item {}", i)))
+            .collect()
+    }
+
+    #[test]
+    fn test_render_highlights_selection() {
+        let bar = Lightbar::new(items(3), 12, 10, 2);
+        let output = bar.render(&AnsiScreen);
+        let lines: Vec<&str> = output.split("\r\n").collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "\x1b[7m Item 1     \x1b[0m");
+        assert_eq!(lines[1], " Item 2     ");
+        assert_eq!(lines[3], "");
+    }
+
+    #[test]
+    fn test_arrow_navigation_wraps() {
+        let mut bar = Lightbar::new(items(3), 12, 10, 2);
+        assert!(matches!(
+            bar.handle_key(Key::Down, &AnsiScreen),
+            LightbarEvent::Output(_)
+        ));
+        assert_eq!(bar.selected(), 1);
+        bar.handle_key(Key::Up, &AnsiScreen);
+        bar.handle_key(Key::Up, &AnsiScreen);
+        assert_eq!(bar.selected(), 2);
+        bar.handle_key(Key::Home, &AnsiScreen);
+        assert_eq!(bar.selected(), 0);
+        bar.handle_key(Key::End, &AnsiScreen);
+        assert_eq!(bar.selected(), 2);
+    }
+
+    #[test]
+    fn test_redraw_uses_relative_cursor_movement() {
+        let mut bar = Lightbar::new(items(3), 12, 10, 2);
+        let LightbarEvent::Output(output) = bar.handle_key(Key::Down, &AnsiScreen) else {
+            panic!("expected output");
+        };
+        // Row 0 is 3 lines above the prompt, row 1 is 2 lines above
+        assert!(output.contains("\x1b[3A\r Item 1     "));
+        assert!(output.contains("\x1b[2A\r\x1b[7m Item 2"));
+    }
+
+    #[test]
+    fn test_enter_chooses_value() {
+        let mut bar = Lightbar::new(items(3), 12, 10, 2);
+        bar.handle_key(Key::Down, &AnsiScreen);
+        assert_eq!(
+            bar.handle_key(Key::Enter, &AnsiScreen),
+            LightbarEvent::Choose("2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_hotkey_highlights_and_enter_submits() {
+        let mut bar = Lightbar::new(
+            vec![
+                LightbarItem::new("B", "Board").with_hotkey('B'),
+                LightbarItem::new("C", "Chat").with_hotkey('C'),
+            ],
+            20,
+            10,
+            2,
+        );
+        let LightbarEvent::Output(output) = bar.handle_key(Key::Char('c'), &AnsiScreen) else {
+            panic!("expected output");
+        };
+        assert!(output.ends_with('c'));
+        assert_eq!(bar.selected(), 1);
+        assert_eq!(
+            bar.handle_key(Key::Enter, &AnsiScreen),
+            LightbarEvent::Choose("c".to_string())
+        );
+        assert_eq!(
+            bar.handle_key(Key::Right, &AnsiScreen),
+            LightbarEvent::Ignore
+        );
+    }
+
+    #[test]
+    fn test_unmatched_text_is_passed_through() {
+        let mut bar = Lightbar::new(items(3), 12, 10, 2);
+        bar.handle_key(Key::Char('x'), &AnsiScreen);
+        assert_eq!(bar.selected(), 0);
+        assert_eq!(
+            bar.handle_key(Key::Enter, &AnsiScreen),
+            LightbarEvent::Choose("x".to_string())
+        );
+    }
+
+    #[test]
+    fn test_bindings_take_priority() {
+        let mut bar = Lightbar::new(items(3), 12, 10, 2)
+            .bind(Key::PageDown, "n")
+            .bind(Key::Escape, "q");
+        assert_eq!(
+            bar.handle_key(Key::PageDown, &AnsiScreen),
+            LightbarEvent::Choose("n".to_string())
+        );
+        assert_eq!(
+            bar.handle_key(Key::Escape, &AnsiScreen),
+            LightbarEvent::Choose("q".to_string())
+        );
+    }
+
+    #[test]
+    fn test_navigation_discards_typed_text() {
+        let mut bar = Lightbar::new(items(3), 12, 10, 2);
+        bar.handle_key(Key::Char('x'), &AnsiScreen);
+        let LightbarEvent::Output(output) = bar.handle_key(Key::Down, &AnsiScreen) else {
+            panic!("expected output");
+        };
+        assert!(output.starts_with("\x08 \x08"));
+        assert_eq!(
+            bar.handle_key(Key::Enter, &AnsiScreen),
+            LightbarEvent::Choose("2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_typed_numbers() {
+        let mut bar = Lightbar::new(items(12), 12, 20, 2);
+        bar.handle_key(Key::Char('1'), &AnsiScreen);
+        assert_eq!(bar.selected(), 0);
+        bar.handle_key(Key::Char('2'), &AnsiScreen);
+        assert_eq!(bar.selected(), 11);
+        bar.handle_key(Key::Backspace, &AnsiScreen);
+        assert_eq!(
+            bar.handle_key(Key::Enter, &AnsiScreen),
+            LightbarEvent::Choose("1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_scrolling_viewport() {
+        let mut bar = Lightbar::new(items(10), 12, 3, 2);
+        assert_eq!(bar.render(&AnsiScreen).matches("\r\n").count(), 3);
+        bar.handle_key(Key::End, &AnsiScreen);
+        assert_eq!(bar.selected(), 9);
+        assert_eq!(bar.visible_range(), 7..10);
+        bar.handle_key(Key::PageUp, &AnsiScreen);
+        assert_eq!(bar.selected(), 6);
+        assert_eq!(bar.visible_range(), 6..9);
+    }
+
+    #[test]
+    fn test_empty_lightbar() {
+        let mut bar = Lightbar::new(Vec::new(), 12, 10, 2);
+        assert_eq!(bar.render(&AnsiScreen), "");
+        assert_eq!(
+            bar.handle_key(Key::Down, &AnsiScreen),
+            LightbarEvent::Ignore
+        );
+        assert_eq!(
+            bar.handle_key(Key::Enter, &AnsiScreen),
+            LightbarEvent::Choose(String::new())
+        );
+    }
+}
@@ -1,11 +1,14 @@
 //! Screen display module.
 //!
-//! Provides ANSI escape sequences for screen decoration and a plain text fallback.
+//! Provides ANSI escape sequences for screen decoration, a plain text fallback,
+//! and lightbar menus for ANSI terminals.
 
 mod ansi;
+mod lightbar;
 mod plain;
 
 pub use ansi::AnsiScreen;
+pub use lightbar::{Lightbar, LightbarEvent, LightbarItem};
 pub use plain::PlainScreen;
 
 /// Terminal colors (ANSI standard 8 colors).
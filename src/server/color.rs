@@ -0,0 +1,226 @@
+//! Portable color markup.
+//!
+//! Templates and user content may use BBS-style color codes instead of raw
+//! ANSI escapes:
+//!
+//! - Renegade/Telegard pipe codes: `|00`–`|15` select the foreground color
+//!   and `|16`–`|23` select the background color (PC color order).
+//! - PCBoard `@X` codes: `@Xbf` where `b` and `f` are hex digits for the
+//!   background and foreground (bit 3 of each enables blink/bright).
+//!
+//! Markup is translated into ANSI SGR sequences, one attribute per sequence,
+//! so the per-session output mode processing can later convert them to
+//! PETSCII control codes or strip them for plain terminals.
+
+use super::encoding::{convert_caret_escape, strip_ansi_sequences};
+
+/// ANSI color index for each PC color (0-7).
+const PC_TO_ANSI: [u8; 8] = [0, 4, 2, 6, 1, 5, 3, 7];
+
+/// A color code found in the markup.
+enum ColorCode {
+    /// Pipe code `|nn`.
+    Pipe(u8),
+    /// PCBoard attribute `@Xbf`.
+    Attribute(u8),
+}
+
+/// Try to parse a color code at the start of `s`.
+///
+/// Returns the code and the number of bytes it occupies.
+fn parse_code(s: &str) -> Option<(ColorCode, usize)> {
+    let bytes = s.as_bytes();
+    match bytes.first() {
+        Some(b'|') if bytes.len() >= 3 => {
+            let (hi, lo) = (bytes[1], bytes[2]);
+            if !hi.is_ascii_digit() || !lo.is_ascii_digit() {
+                return None;
+            }
+            let n = (hi - b'0') * 10 + (lo - b'0');
+            (n <= 23).then_some((ColorCode::Pipe(n), 3))
+        }
+        Some(b'@') if bytes.len() >= 4 && bytes[1] == b'X' => {
+            let hex = std::str::from_utf8(&bytes[2..4]).ok()?;
+            let attr = u8::from_str_radix(hex, 16).ok()?;
+            Some((ColorCode::Attribute(attr), 4))
+        }
+        _ => None,
+    }
+}
+
+/// Build the ANSI sequences for a color code.
+fn code_to_ansi(code: ColorCode) -> String {
+    match code {
+        ColorCode::Pipe(n) if n < 8 => {
+            format!("\x1b[22m\x1b[{}m", 30 + PC_TO_ANSI[n as usize])
+        }
+        ColorCode::Pipe(n) if n < 16 => {
+            format!("\x1b[1m\x1b[{}m", 30 + PC_TO_ANSI[(n - 8) as usize])
+        }
+        ColorCode::Pipe(n) => format!("\x1b[{}m", 40 + PC_TO_ANSI[(n - 16) as usize]),
+        ColorCode::Attribute(attr) => {
+            let fg = attr & 0x0f;
+            let bg = attr >> 4;
+            let mut out = String::from("\x1b[0m");
+            if fg >= 8 {
+                out.push_str("\x1b[1m");
+            }
+            if bg >= 8 {
+                out.push_str("\x1b[5m");
+            }
+            out.push_str(&format!(
+                "\x1b[{}m\x1b[{}m",
+                30 + PC_TO_ANSI[(fg & 7) as usize],
+                40 + PC_TO_ANSI[(bg & 7) as usize]
+            ));
+            out
+        }
+    }
+}
+
+/// Replace every color code in `text` using `f`.
+fn map_codes(text: &str, f: impl Fn(ColorCode) -> String) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(pos) = rest.find(['|', '@']) {
+        result.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        match parse_code(rest) {
+            Some((code, len)) => {
+                result.push_str(&f(code));
+                rest = &rest[len..];
+            }
+            None => {
+                result.push_str(&rest[..1]);
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+
+    result
+}
+
+/// Convert pipe and `@X` color codes to ANSI escape sequences.
+///
+/// # Example
+///
+/// ```
+/// use hobbs::server::color::convert_color_codes;
+///
+/// assert_eq!(convert_color_codes("|04Red"), "\x1b[22m\x1b[31mRed");
+/// assert_eq!(convert_color_codes("a|b"), "a|b");
+/// ```
+pub fn convert_color_codes(text: &str) -> String {
+    map_codes(text, code_to_ansi)
+}
+
+/// Remove pipe and `@X` color codes from text.
+///
+/// # Example
+///
+/// ```
+/// use hobbs::server::color::strip_color_codes;
+///
+/// assert_eq!(strip_color_codes("|14Hello|07 @X1Fworld"), "Hello world");
+/// ```
+pub fn strip_color_codes(text: &str) -> String {
+    map_codes(text, |_| String::new())
+}
+
+/// Prepare user-supplied text (post bodies, mail) for display.
+///
+/// When `allow_color` is true, caret escapes and color markup are converted
+/// to ANSI. Otherwise all color codes, including raw ANSI sequences, are
+/// removed.
+pub fn render_user_colors(text: &str, allow_color: bool) -> String {
+    if allow_color {
+        convert_color_codes(&convert_caret_escape(text))
+    } else {
+        strip_ansi_sequences(&strip_color_codes(&convert_caret_escape(text)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::encoding::{process_output_mode, OutputMode};
+
+    #[test]
+    fn test_pipe_foreground_colors() {
+        assert_eq!(convert_color_codes("|07"), "\x1b[22m\x1b[37m");
+        assert_eq!(convert_color_codes("|01"), "\x1b[22m\x1b[34m");
+        assert_eq!(convert_color_codes("|15"), "\x1b[1m\x1b[37m");
+        assert_eq!(convert_color_codes("|12"), "\x1b[1m\x1b[31m");
+    }
+
+    #[test]
+    fn test_pipe_background_colors() {
+        assert_eq!(convert_color_codes("|16"), "\x1b[40m");
+        assert_eq!(convert_color_codes("|20"), "\x1b[41m");
+        assert_eq!(convert_color_codes("|23"), "\x1b[47m");
+    }
+
+    #[test]
+    fn test_invalid_pipe_codes_are_literal() {
+        assert_eq!(convert_color_codes("|24"), "|24");
+        assert_eq!(convert_color_codes("|7"), "|7");
+        assert_eq!(convert_color_codes("a | b"), "a | b");
+        assert_eq!(convert_color_codes("end|"), "end|");
+    }
+
+    #[test]
+    fn test_at_x_codes() {
+        assert_eq!(
+            convert_color_codes("@X1F"),
+            "\x1b[0m\x1b[1m\x1b[37m\x1b[44m"
+        );
+        assert_eq!(convert_color_codes("@X07"), "\x1b[0m\x1b[37m\x1b[40m");
+        assert_eq!(
+            convert_color_codes("@X8e"),
+            "\x1b[0m\x1b[1m\x1b[5m\x1b[33m\x1b[40m"
+        );
+    }
+
+    #[test]
+    fn test_at_x_invalid_is_literal() {
+        assert_eq!(convert_color_codes("user@XG1"), "user@XG1");
+        assert_eq!(convert_color_codes("mail@example.com"), "mail@example.com");
+    }
+
+    #[test]
+    fn test_multibyte_text_preserved() {
+        assert_eq!(
+            convert_color_codes("|14こんにちは|07世界"),
+            "\x1b[1m\x1b[33mこんにちは\x1b[22m\x1b[37m世界"
+        );
+    }
+
+    #[test]
+    fn test_strip_color_codes() {
+        assert_eq!(strip_color_codes("|04Red|07 text"), "Red text");
+        assert_eq!(strip_color_codes("@X4EWarn@X07"), "Warn");
+        assert_eq!(strip_color_codes("a|99b"), "a|99b");
+    }
+
+    #[test]
+    fn test_render_user_colors() {
+        let body = "|04Red ^[[32mGreen";
+        assert_eq!(
+            render_user_colors(body, true),
+            "\x1b[22m\x1b[31mRed \x1b[32mGreen"
+        );
+        assert_eq!(render_user_colors(body, false), "Red Green");
+    }
+
+    #[test]
+    fn test_output_modes() {
+        let ansi = convert_color_codes("|04Red");
+        assert_eq!(process_output_mode(&ansi, OutputMode::Plain), "Red");
+        assert_eq!(
+            process_output_mode(&ansi, OutputMode::PetsciiCtrl),
+            "\x1cRed"
+        );
+    }
+}
@@ -48,6 +48,25 @@ pub struct LineBuffer {
     /// Whether the last byte processed was CR.
     /// Used to handle CR+LF as a single newline.
     last_was_cr: bool,
+    /// Progress through an escape sequence that is being discarded.
+    escape: EscapeState,
+}
+
+/// Progress through an incoming escape sequence.
+///
+/// Line input has no use for cursor or function keys, so their sequences
+/// are swallowed instead of leaking `[A` and friends into the buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum EscapeState {
+    /// Not inside an escape sequence.
+    #[default]
+    None,
+    /// Received ESC.
+    Escape,
+    /// Inside `ESC [` (CSI), waiting for the final byte.
+    Csi,
+    /// Inside `ESC O` (SS3), waiting for one more byte.
+    Ss3,
 }
 
 impl LineBuffer {
@@ -60,6 +79,7 @@ impl LineBuffer {
             encoding: CharacterEncoding::default(),
             pending_echo: Vec::with_capacity(4),
             last_was_cr: false,
+            escape: EscapeState::None,
         }
     }
 
@@ -72,6 +92,7 @@ impl LineBuffer {
             encoding,
             pending_echo: Vec::with_capacity(4),
             last_was_cr: false,
+            escape: EscapeState::None,
         }
     }
 
@@ -133,6 +154,7 @@ impl LineBuffer {
         self.buffer.clear();
         self.pending_echo.clear();
         self.last_was_cr = false;
+        self.escape = EscapeState::None;
     }
 
     /// Calculate the number of bytes to delete for a backspace operation.
@@ -248,6 +270,33 @@ impl LineBuffer {
     ///
     /// Returns the input result and any bytes that should be echoed back.
     pub fn process_byte(&mut self, byte: u8) -> (InputResult, Vec<u8>) {
+        match self.escape {
+            EscapeState::None => {}
+            EscapeState::Escape => match byte {
+                b'[' => {
+                    self.escape = EscapeState::Csi;
+                    return (InputResult::Buffering, vec![]);
+                }
+                b'O' => {
+                    self.escape = EscapeState::Ss3;
+                    return (InputResult::Buffering, vec![]);
+                }
+                // Not a sequence: process the byte normally
+                _ => self.escape = EscapeState::None,
+            },
+            EscapeState::Csi => {
+                // Parameter and intermediate bytes continue the sequence
+                if !(0x20..=0x3F).contains(&byte) {
+                    self.escape = EscapeState::None;
+                }
+                return (InputResult::Buffering, vec![]);
+            }
+            EscapeState::Ss3 => {
+                self.escape = EscapeState::None;
+                return (InputResult::Buffering, vec![]);
+            }
+        }
+
         match byte {
             control::CR => {
                 // CR - end of line
@@ -321,9 +370,9 @@ impl LineBuffer {
                 (InputResult::Eof, vec![])
             }
             control::ESC => {
-                // Start of escape sequence - ignore for now
-                // TODO: Handle ANSI escape sequences
+                // Start of escape sequence - discard it
                 self.last_was_cr = false;
+                self.escape = EscapeState::Escape;
                 (InputResult::Buffering, vec![])
             }
             control::NUL => {
@@ -749,6 +798,19 @@ mod tests {
         assert_eq!(buffer.contents(), b"H");
     }
 
+    #[test]
+    fn test_line_buffer_discards_escape_sequences() {
+        let mut buffer = LineBuffer::new(100);
+
+        // Arrow keys (CSI and SS3), a VT key with parameters, and an F-key
+        buffer.process_bytes(b"a\x1b[Ab\x1bOBc\x1b[5~d\x1b[1;5Ce");
+        assert_eq!(buffer.contents(), b"abcde");
+
+        // A lone ESC followed by a regular character keeps the character
+        buffer.process_bytes(b"\x1bf");
+        assert_eq!(buffer.contents(), b"abcdef");
+    }
+
     #[test]
     fn test_multi_line_buffer() {
         let mut buffer = MultiLineBuffer::new(".");
@@ -0,0 +1,405 @@
+//! Key input decoding for Telnet sessions.
+//!
+//! This module turns the raw byte stream sent by a terminal into logical
+//! key presses. Cursor keys and function keys arrive as escape sequences,
+//! and different terminal families encode them differently:
+//!
+//! - ANSI/xterm CSI sequences: `ESC [ A`, `ESC [ 5 ~`, `ESC [ 1 ; 5 C`
+//! - VT100 application mode (SS3): `ESC O A`, `ESC O P`
+//! - Linux console function keys: `ESC [ [ A`
+//! - SyncTERM / ANSI-BBS: `ESC [ H` (Home), `ESC [ K` (End),
+//!   `ESC [ V` (PgUp), `ESC [ U` (PgDn), `ESC [ @` (Insert)
+
+use super::telnet::control;
+
+/// Maximum length of a CSI parameter string before the sequence is discarded.
+const MAX_SEQUENCE_LEN: usize = 16;
+
+/// A logical key press.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    /// A printable ASCII character.
+    Char(char),
+    /// Enter (CR, LF or CR+LF).
+    Enter,
+    /// Backspace (BS or DEL).
+    Backspace,
+    /// Tab.
+    Tab,
+    /// A lone Escape key.
+    Escape,
+    /// Cursor up.
+    Up,
+    /// Cursor down.
+    Down,
+    /// Cursor left.
+    Left,
+    /// Cursor right.
+    Right,
+    /// Home.
+    Home,
+    /// End.
+    End,
+    /// Page Up.
+    PageUp,
+    /// Page Down.
+    PageDown,
+    /// Insert.
+    Insert,
+    /// Delete (forward delete, not backspace).
+    Delete,
+    /// Function key F1-F12.
+    Function(u8),
+    /// Control character (Ctrl+letter), e.g. `Ctrl('C')` for 0x03.
+    Ctrl(char),
+    /// Escape followed by a printable character (Alt/Meta + key).
+    Alt(char),
+    /// An escape sequence that could not be recognized.
+    Unknown,
+}
+
+impl Key {
+    /// Check if this key moves a selection (arrows, Home/End, PgUp/PgDn).
+    pub fn is_navigation(&self) -> bool {
+        matches!(
+            self,
+            Key::Up
+                | Key::Down
+                | Key::Left
+                | Key::Right
+                | Key::Home
+                | Key::End
+                | Key::PageUp
+                | Key::PageDown
+        )
+    }
+}
+
+/// Decoder state.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+enum State {
+    /// Not inside an escape sequence.
+    #[default]
+    Ground,
+    /// Received ESC.
+    Escape,
+    /// Received `ESC [`, collecting parameters.
+    Csi(Vec<u8>),
+    /// Received `ESC [ [` (Linux console function keys).
+    CsiBracket,
+    /// Received `ESC O` (VT100 application mode).
+    Ss3,
+}
+
+/// Incremental key decoder.
+///
+/// Feed bytes one at a time with [`KeyDecoder::feed`]. A lone ESC cannot be
+/// distinguished from the start of a sequence until more input arrives (or
+/// does not), so callers should call [`KeyDecoder::flush`] after a short
+/// idle timeout while [`KeyDecoder::is_pending`] is true.
+///
+/// A fresh decoder behaves as if the previous input ended with CR, so the
+/// LF half of a CR+LF pair left over from earlier line input is not
+/// mistaken for a second Enter.
+#[derive(Debug)]
+pub struct KeyDecoder {
+    /// Current parse state.
+    state: State,
+    /// Whether the last byte was CR (to fold CR+LF and CR+NUL into one Enter).
+    last_was_cr: bool,
+}
+
+impl KeyDecoder {
+    /// Create a new key decoder.
+    pub fn new() -> Self {
+        Self {
+            state: State::Ground,
+            last_was_cr: true,
+        }
+    }
+
+    /// Check if the decoder is in the middle of an escape sequence.
+    pub fn is_pending(&self) -> bool {
+        self.state != State::Ground
+    }
+
+    /// Resolve an incomplete sequence after an idle timeout.
+    ///
+    /// A bare ESC becomes [`Key::Escape`]; any other partial sequence is
+    /// reported as [`Key::Unknown`].
+    pub fn flush(&mut self) -> Option<Key> {
+        match std::mem::take(&mut self.state) {
+            State::Ground => None,
+            State::Escape => Some(Key::Escape),
+            _ => Some(Key::Unknown),
+        }
+    }
+
+    /// Feed a single byte. Returns a key once one is complete.
+    pub fn feed(&mut self, byte: u8) -> Option<Key> {
+        match std::mem::take(&mut self.state) {
+            State::Ground => self.feed_ground(byte),
+            State::Escape => self.feed_escape(byte),
+            State::Csi(params) => self.feed_csi(params, byte),
+            State::CsiBracket => match byte {
+                b'A'..=b'E' => Some(Key::Function(byte - b'A' + 1)),
+                _ => Some(Key::Unknown),
+            },
+            State::Ss3 => Some(match byte {
+                b'A' => Key::Up,
+                b'B' => Key::Down,
+                b'C' => Key::Right,
+                b'D' => Key::Left,
+                b'H' => Key::Home,
+                b'F' => Key::End,
+                b'P'..=b'S' => Key::Function(byte - b'P' + 1),
+                b'M' => Key::Enter,
+                _ => Key::Unknown,
+            }),
+        }
+    }
+
+    /// Handle a byte outside of an escape sequence.
+    fn feed_ground(&mut self, byte: u8) -> Option<Key> {
+        let after_cr = std::mem::replace(&mut self.last_was_cr, false);
+        match byte {
+            control::CR => {
+                self.last_was_cr = true;
+                Some(Key::Enter)
+            }
+            control::LF | control::NUL if after_cr => None,
+            control::LF => Some(Key::Enter),
+            control::NUL => None,
+            control::ESC => {
+                self.state = State::Escape;
+                None
+            }
+            control::BS | control::DEL => Some(Key::Backspace),
+            b'\t' => Some(Key::Tab),
+            0x01..=0x1A => Some(Key::Ctrl((b'A' + byte - 1) as char)),
+            0x20..=0x7E => Some(Key::Char(byte as char)),
+            _ => None,
+        }
+    }
+
+    /// Handle the byte following ESC.
+    fn feed_escape(&mut self, byte: u8) -> Option<Key> {
+        match byte {
+            b'[' => {
+                self.state = State::Csi(Vec::new());
+                None
+            }
+            b'O' => {
+                self.state = State::Ss3;
+                None
+            }
+            control::ESC => {
+                // ESC ESC: report the first one, keep waiting on the second
+                self.state = State::Escape;
+                Some(Key::Escape)
+            }
+            0x20..=0x7E => Some(Key::Alt(byte as char)),
+            _ => Some(Key::Escape),
+        }
+    }
+
+    /// Handle a byte inside a CSI sequence.
+    fn feed_csi(&mut self, mut params: Vec<u8>, byte: u8) -> Option<Key> {
+        match byte {
+            b'[' if params.is_empty() => {
+                self.state = State::CsiBracket;
+                None
+            }
+            0x20..=0x3F => {
+                if params.len() >= MAX_SEQUENCE_LEN {
+                    return Some(Key::Unknown);
+                }
+                params.push(byte);
+                self.state = State::Csi(params);
+                None
+            }
+            0x40..=0x7E => Some(decode_csi(&params, byte)),
+            _ => Some(Key::Unknown),
+        }
+    }
+}
+
+impl Default for KeyDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Decode a complete CSI sequence from its parameter bytes and final byte.
+fn decode_csi(params: &[u8], final_byte: u8) -> Key {
+    // Only the first parameter matters; the rest are modifiers (e.g. "1;5").
+    let first: Option<u32> = std::str::from_utf8(params)
+        .ok()
+        .and_then(|s| s.split(';').next())
+        .and_then(|s| s.parse().ok());
+
+    match final_byte {
+        b'A' => Key::Up,
+        b'B' => Key::Down,
+        b'C' => Key::Right,
+        b'D' => Key::Left,
+        b'H' => Key::Home,
+        b'F' => Key::End,
+        // SyncTERM / ANSI-BBS keys
+        b'K' => Key::End,
+        b'V' => Key::PageUp,
+        b'U' => Key::PageDown,
+        b'@' => Key::Insert,
+        // xterm with modifiers: ESC [ 1 ; 2 P
+        b'P'..=b'S' => Key::Function(final_byte - b'P' + 1),
+        b'~' => match first {
+            Some(1) | Some(7) => Key::Home,
+            Some(2) => Key::Insert,
+            Some(3) => Key::Delete,
+            Some(4) | Some(8) => Key::End,
+            Some(5) => Key::PageUp,
+            Some(6) => Key::PageDown,
+            Some(n @ 11..=15) => Key::Function((n - 10) as u8),
+            Some(n @ 17..=21) => Key::Function((n - 11) as u8),
+            Some(n @ 23..=24) => Key::Function((n - 12) as u8),
+            _ => Key::Unknown,
+        },
+        _ => Key::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(bytes: &[u8]) -> Vec<Key> {
+        let mut decoder = KeyDecoder::new();
+        let mut keys: Vec<Key> = bytes.iter().filter_map(|&b| decoder.feed(b)).collect();
+        keys.extend(decoder.flush());
+        keys
+    }
+
+    #[test]
+    fn test_plain_characters() {
+        assert_eq!(
+            decode(b"ab1"),
+            vec![Key::Char('a'), Key::Char('b'), Key::Char('1')]
+        );
+    }
+
+    #[test]
+    fn test_enter_variants() {
+        assert_eq!(decode(b"\r"), vec![Key::Enter]);
+        assert_eq!(decode(b"a\n"), vec![Key::Char('a'), Key::Enter]);
+        assert_eq!(decode(b"\r\n"), vec![Key::Enter]);
+        assert_eq!(decode(b"\r\0"), vec![Key::Enter]);
+        assert_eq!(decode(b"\r\r"), vec![Key::Enter, Key::Enter]);
+    }
+
+    #[test]
+    fn test_leftover_lf_ignored_by_fresh_decoder() {
+        assert_eq!(decode(b"\n"), vec![]);
+        assert_eq!(decode(b"\n\n"), vec![Key::Enter]);
+    }
+
+    #[test]
+    fn test_backspace_and_control() {
+        assert_eq!(decode(&[0x08, 0x7F]), vec![Key::Backspace, Key::Backspace]);
+        assert_eq!(decode(&[0x03]), vec![Key::Ctrl('C')]);
+        assert_eq!(decode(b"\t"), vec![Key::Tab]);
+    }
+
+    #[test]
+    fn test_ansi_arrows() {
+        assert_eq!(
+            decode(b"\x1b[A\x1b[B\x1b[C\x1b[D"),
+            vec![Key::Up, Key::Down, Key::Right, Key::Left]
+        );
+    }
+
+    #[test]
+    fn test_vt100_application_mode() {
+        assert_eq!(
+            decode(b"\x1bOA\x1bOB\x1bOH\x1bOF"),
+            vec![Key::Up, Key::Down, Key::Home, Key::End]
+        );
+        assert_eq!(
+            decode(b"\x1bOP\x1bOS"),
+            vec![Key::Function(1), Key::Function(4)]
+        );
+    }
+
+    #[test]
+    fn test_vt_tilde_keys() {
+        assert_eq!(
+            decode(b"\x1b[1~\x1b[2~\x1b[3~\x1b[4~\x1b[5~\x1b[6~"),
+            vec![
+                Key::Home,
+                Key::Insert,
+                Key::Delete,
+                Key::End,
+                Key::PageUp,
+                Key::PageDown
+            ]
+        );
+    }
+
+    #[test]
+    fn test_function_keys() {
+        assert_eq!(decode(b"\x1b[11~"), vec![Key::Function(1)]);
+        assert_eq!(decode(b"\x1b[15~"), vec![Key::Function(5)]);
+        assert_eq!(decode(b"\x1b[17~"), vec![Key::Function(6)]);
+        assert_eq!(decode(b"\x1b[21~"), vec![Key::Function(10)]);
+        assert_eq!(decode(b"\x1b[24~"), vec![Key::Function(12)]);
+        assert_eq!(
+            decode(b"\x1b[[A\x1b[[E"),
+            vec![Key::Function(1), Key::Function(5)]
+        );
+        assert_eq!(decode(b"\x1b[1;2P"), vec![Key::Function(1)]);
+    }
+
+    #[test]
+    fn test_syncterm_keys() {
+        assert_eq!(
+            decode(b"\x1b[H\x1b[K\x1b[V\x1b[U\x1b[@"),
+            vec![Key::Home, Key::End, Key::PageUp, Key::PageDown, Key::Insert]
+        );
+    }
+
+    #[test]
+    fn test_modified_arrows() {
+        assert_eq!(decode(b"\x1b[1;5C"), vec![Key::Right]);
+    }
+
+    #[test]
+    fn test_lone_escape() {
+        let mut decoder = KeyDecoder::new();
+        assert_eq!(decoder.feed(0x1b), None);
+        assert!(decoder.is_pending());
+        assert_eq!(decoder.flush(), Some(Key::Escape));
+        assert!(!decoder.is_pending());
+        assert_eq!(decoder.flush(), None);
+    }
+
+    #[test]
+    fn test_alt_and_double_escape() {
+        assert_eq!(decode(b"\x1bx"), vec![Key::Alt('x')]);
+        assert_eq!(decode(b"\x1b\x1b"), vec![Key::Escape, Key::Escape]);
+    }
+
+    #[test]
+    fn test_unknown_sequences() {
+        assert_eq!(decode(b"\x1b[99~"), vec![Key::Unknown]);
+        assert_eq!(decode(b"\x1b[Z"), vec![Key::Unknown]);
+        let overlong = [b"\x1b[".as_slice(), &[b'1'; 20], b"~"].concat();
+        assert_eq!(decode(&overlong)[0], Key::Unknown);
+    }
+
+    #[test]
+    fn test_is_navigation() {
+        assert!(Key::Up.is_navigation());
+        assert!(Key::PageDown.is_navigation());
+        assert!(!Key::Enter.is_navigation());
+        assert!(!Key::Char('a').is_navigation());
+    }
+}
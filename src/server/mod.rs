@@ -3,13 +3,16 @@
 //! This module provides the TCP listener and connection handling for the
 //! Telnet server, and the SSH tunnel server.
 
+pub mod color;
 pub mod encoding;
 pub mod input;
+pub mod keys;
 mod listener;
 mod session;
 pub mod ssh;
 pub mod telnet;
 
+pub use color::{convert_color_codes, render_user_colors, strip_color_codes};
 pub use encoding::{
     convert_ansi_to_petscii_ctrl, convert_caret_escape, decode_cp437, decode_from_client,
     decode_from_client_detailed, decode_petscii, decode_shiftjis, decode_shiftjis_strict,
@@ -18,6 +21,7 @@ pub use encoding::{
     DecodeResult, EncodeResult, OutputMode,
 };
 pub use input::{EchoMode, InputResult, LineBuffer, MultiLineBuffer};
+pub use keys::{Key, KeyDecoder};
 pub use listener::{ConnectionPermit, TelnetServer};
 pub use session::{SessionInfo, SessionManager, SessionState, TelnetSession};
 pub use telnet::{iac, initial_negotiation, option, NegotiationState, TelnetCommand, TelnetParser};
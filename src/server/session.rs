@@ -11,6 +11,7 @@ use tracing::{debug, info};
 use uuid::Uuid;
 
 use super::encoding::{CharacterEncoding, OutputMode};
+use crate::notify::NotificationHub;
 
 /// Session state representing the current phase of the connection.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -69,6 +70,8 @@ pub struct TelnetSession {
     output_mode: OutputMode,
     /// Whether this is a guest session (not logged in but accessing menu).
     is_guest: bool,
+    /// Whether content hidden by the user's twit filters is shown.
+    show_hidden: bool,
 }
 
 impl TelnetSession {
@@ -88,6 +91,7 @@ impl TelnetSession {
             encoding: CharacterEncoding::default(),
             output_mode: OutputMode::default(),
             is_guest: false,
+            show_hidden: false,
         }
     }
 
@@ -114,6 +118,7 @@ impl TelnetSession {
             encoding,
             output_mode: OutputMode::default(),
             is_guest: false,
+            show_hidden: false,
         }
     }
 
@@ -141,6 +146,7 @@ impl TelnetSession {
             encoding,
             output_mode,
             is_guest: false,
+            show_hidden: false,
         }
     }
 
@@ -241,9 +247,20 @@ impl TelnetSession {
         }
         self.user_id = None;
         self.username = None;
+        self.show_hidden = false;
         self.touch();
     }
 
+    /// Check whether content hidden by twit filters is shown.
+    pub fn show_hidden(&self) -> bool {
+        self.show_hidden
+    }
+
+    /// Show or hide content hidden by twit filters.
+    pub fn set_show_hidden(&mut self, show_hidden: bool) {
+        self.show_hidden = show_hidden;
+    }
+
     /// Get the character encoding for this session.
     pub fn encoding(&self) -> CharacterEncoding {
         self.encoding
@@ -324,6 +341,7 @@ pub struct SessionInfo {
 pub struct SessionManager {
     sessions: Arc<RwLock<HashMap<Uuid, SessionInfo>>>,
     idle_timeout: Duration,
+    notification_hub: Arc<NotificationHub>,
 }
 
 impl SessionManager {
@@ -332,6 +350,7 @@ impl SessionManager {
         Self {
             sessions: Arc::new(RwLock::new(HashMap::new())),
             idle_timeout: Duration::from_secs(idle_timeout_secs),
+            notification_hub: Arc::new(NotificationHub::new()),
         }
     }
 
@@ -340,6 +359,11 @@ impl SessionManager {
         self.idle_timeout
     }
 
+    /// Get the hub that delivers subscription notifications to online users.
+    pub fn notification_hub(&self) -> &Arc<NotificationHub> {
+        &self.notification_hub
+    }
+
     /// Register a new session.
     pub async fn register(&self, session: &TelnetSession) {
         let info = SessionInfo {
@@ -483,6 +507,7 @@ impl Clone for SessionManager {
         Self {
             sessions: Arc::clone(&self.sessions),
             idle_timeout: self.idle_timeout,
+            notification_hub: Arc::clone(&self.notification_hub),
         }
     }
 }
@@ -0,0 +1,407 @@
+//! Condition expressions for `{{#if}}` and `{{#unless}}`.
+//!
+//! Supported syntax:
+//!
+//! - Operands: variables (`user.unread_mail`, `@index`), numbers (`10`,
+//!   `-1`, `1.5`), strings (`"sysop"`) and `true` / `false` / `null`
+//! - Comparison: `==`, `!=`, `<`, `<=`, `>`, `>=`
+//! - Boolean: `!`, `&&`, `||` and parentheses
+//!
+//! A bare variable keeps its old meaning and is tested for truthiness.
+
+use super::{Result, TemplateContext, TemplateError, Value};
+
+/// A parsed condition expression.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Expr {
+    /// Literal value.
+    Literal(Value),
+    /// Variable reference.
+    Variable(String),
+    /// Logical negation.
+    Not(Box<Expr>),
+    /// Logical conjunction.
+    And(Box<Expr>, Box<Expr>),
+    /// Logical disjunction.
+    Or(Box<Expr>, Box<Expr>),
+    /// Comparison of two operands.
+    Compare(Box<Expr>, CompareOp, Box<Expr>),
+}
+
+/// Comparison operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// Lexical token of an expression.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(String),
+    Str(String),
+    Op(&'static str),
+    LParen,
+    RParen,
+}
+
+/// Operators, longest first so `<=` is not read as `<`.
+const OPERATORS: [&str; 9] = ["==", "!=", "<=", ">=", "&&", "||", "<", ">", "!"];
+
+/// Split an expression into tokens.
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(i, ch)) = chars.peek() {
+        if ch.is_whitespace() {
+            chars.next();
+        } else if ch == '(' {
+            tokens.push(Token::LParen);
+            chars.next();
+        } else if ch == ')' {
+            tokens.push(Token::RParen);
+            chars.next();
+        } else if ch == '"' {
+            chars.next();
+            let mut s = String::new();
+            let mut closed = false;
+            while let Some((_, c)) = chars.next() {
+                match c {
+                    '"' => {
+                        closed = true;
+                        break;
+                    }
+                    '\\' => {
+                        if let Some((_, escaped)) = chars.next() {
+                            s.push(escaped);
+                        }
+                    }
+                    _ => s.push(c),
+                }
+            }
+            if !closed {
+                return Err(TemplateError::Parse("Unterminated string".to_string()));
+            }
+            tokens.push(Token::Str(s));
+        } else if ch.is_ascii_digit()
+            || (ch == '-' && input[i + 1..].starts_with(|c: char| c.is_ascii_digit()))
+        {
+            let mut s = String::new();
+            s.push(ch);
+            chars.next();
+            while let Some(&(_, c)) = chars.peek() {
+                if c.is_ascii_digit() || c == '.' {
+                    s.push(c);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token::Number(s));
+        } else if ch.is_alphanumeric() || ch == '_' || ch == '@' {
+            let mut s = String::new();
+            while let Some(&(_, c)) = chars.peek() {
+                if c.is_alphanumeric() || matches!(c, '_' | '.' | '-' | '@') {
+                    s.push(c);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token::Ident(s));
+        } else if let Some(op) = OPERATORS.iter().find(|op| input[i..].starts_with(**op)) {
+            for _ in 0..op.len() {
+                chars.next();
+            }
+            tokens.push(Token::Op(op));
+        } else {
+            return Err(TemplateError::Parse(format!(
+                "Unexpected character '{ch}' in condition"
+            )));
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive-descent parser over the token list.
+struct ExprParser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl ExprParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat_op(&mut self, op: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Op(o)) if *o == op) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr> {
+        let mut left = self.parse_and()?;
+        while self.eat_op("||") {
+            let right = self.parse_and()?;
+            left = Expr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr> {
+        let mut left = self.parse_not()?;
+        while self.eat_op("&&") {
+            let right = self.parse_not()?;
+            left = Expr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr> {
+        if self.eat_op("!") {
+            return Ok(Expr::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_compare()
+    }
+
+    fn parse_compare(&mut self) -> Result<Expr> {
+        let left = self.parse_operand()?;
+        let op = match self.peek() {
+            Some(Token::Op("==")) => CompareOp::Eq,
+            Some(Token::Op("!=")) => CompareOp::Ne,
+            Some(Token::Op("<")) => CompareOp::Lt,
+            Some(Token::Op("<=")) => CompareOp::Le,
+            Some(Token::Op(">")) => CompareOp::Gt,
+            Some(Token::Op(">=")) => CompareOp::Ge,
+            _ => return Ok(left),
+        };
+        self.pos += 1;
+        let right = self.parse_operand()?;
+        Ok(Expr::Compare(Box::new(left), op, Box::new(right)))
+    }
+
+    fn parse_operand(&mut self) -> Result<Expr> {
+        match self.next() {
+            Some(Token::LParen) => {
+                let expr = self.parse_or()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(expr),
+                    _ => Err(TemplateError::Parse(
+                        "Expected ')' in condition".to_string(),
+                    )),
+                }
+            }
+            Some(Token::Str(s)) => Ok(Expr::Literal(Value::String(s))),
+            Some(Token::Number(n)) => parse_number(&n).map(Expr::Literal),
+            Some(Token::Ident(name)) => Ok(match name.as_str() {
+                "true" => Expr::Literal(Value::Bool(true)),
+                "false" => Expr::Literal(Value::Bool(false)),
+                "null" => Expr::Literal(Value::Null),
+                _ => Expr::Variable(name),
+            }),
+            Some(token) => Err(TemplateError::Parse(format!(
+                "Unexpected {token:?} in condition"
+            ))),
+            None => Err(TemplateError::Parse(
+                "Unexpected end of condition".to_string(),
+            )),
+        }
+    }
+}
+
+/// Parse a numeric literal.
+fn parse_number(s: &str) -> Result<Value> {
+    if let Ok(n) = s.parse::<i64>() {
+        return Ok(Value::Number(n));
+    }
+    s.parse::<f64>()
+        .map(Value::Float)
+        .map_err(|_| TemplateError::Parse(format!("Invalid number '{s}' in condition")))
+}
+
+/// Parse a condition expression.
+pub(super) fn parse(input: &str) -> Result<Expr> {
+    let tokens = tokenize(input)?;
+    if tokens.is_empty() {
+        return Err(TemplateError::Parse("Empty condition".to_string()));
+    }
+
+    let mut parser = ExprParser { tokens, pos: 0 };
+    let expr = parser.parse_or()?;
+    if let Some(token) = parser.peek() {
+        return Err(TemplateError::Parse(format!(
+            "Unexpected {token:?} in condition '{input}'"
+        )));
+    }
+    Ok(expr)
+}
+
+/// Evaluate a condition to a boolean.
+pub(super) fn evaluate(expr: &Expr, context: &TemplateContext) -> bool {
+    match expr {
+        Expr::Literal(value) => value.is_truthy(),
+        Expr::Variable(name) => context.get(name).is_some_and(Value::is_truthy),
+        Expr::Not(inner) => !evaluate(inner, context),
+        Expr::And(left, right) => evaluate(left, context) && evaluate(right, context),
+        Expr::Or(left, right) => evaluate(left, context) || evaluate(right, context),
+        Expr::Compare(left, op, right) => {
+            let left = operand_value(left, context);
+            let right = operand_value(right, context);
+            compare(&left, *op, &right)
+        }
+    }
+}
+
+/// Resolve an operand to a value. Nested boolean expressions become `Bool`.
+fn operand_value(expr: &Expr, context: &TemplateContext) -> Value {
+    match expr {
+        Expr::Literal(value) => value.clone(),
+        Expr::Variable(name) => context.get(name).cloned().unwrap_or(Value::Null),
+        other => Value::Bool(evaluate(other, context)),
+    }
+}
+
+/// Numeric view of a value, if it has one.
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => Some(*n as f64),
+        Value::Float(f) => Some(*f),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Compare two values.
+///
+/// Values are compared numerically when both sides are numbers (or numeric
+/// strings), as booleans when either side is a boolean, and as strings
+/// otherwise. A missing variable is only equal to `null` or an empty string.
+fn compare(left: &Value, op: CompareOp, right: &Value) -> bool {
+    use std::cmp::Ordering;
+
+    let ordering = match (left, right) {
+        (Value::Null, Value::Null) => Some(Ordering::Equal),
+        (Value::Null, other) | (other, Value::Null) => {
+            if other.to_display_string().is_empty() {
+                Some(Ordering::Equal)
+            } else {
+                None
+            }
+        }
+        (Value::Bool(_), _) | (_, Value::Bool(_)) => Some(left.is_truthy().cmp(&right.is_truthy())),
+        _ => match (as_number(left), as_number(right)) {
+            (Some(l), Some(r)) => l.partial_cmp(&r),
+            _ => Some(left.to_display_string().cmp(&right.to_display_string())),
+        },
+    };
+
+    match (op, ordering) {
+        (CompareOp::Eq, Some(o)) => o == Ordering::Equal,
+        (CompareOp::Ne, Some(o)) => o != Ordering::Equal,
+        (CompareOp::Ne, None) => true,
+        (CompareOp::Lt, Some(o)) => o == Ordering::Less,
+        (CompareOp::Le, Some(o)) => o != Ordering::Greater,
+        (CompareOp::Gt, Some(o)) => o == Ordering::Greater,
+        (CompareOp::Ge, Some(o)) => o != Ordering::Less,
+        (_, None) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::I18n;
+    use std::sync::Arc;
+
+    fn context() -> TemplateContext {
+        let mut context = TemplateContext::new(Arc::new(I18n::empty("ja")));
+        context.set("unread", Value::Number(3));
+        context.set("zero", Value::Number(0));
+        context.set("role", Value::String("sysop".to_string()));
+        context.set("ratio", Value::Float(0.5));
+        context.set("admin", Value::Bool(true));
+        context
+    }
+
+    fn eval(input: &str) -> bool {
+        evaluate(&parse(input).unwrap(), &context())
+    }
+
+    #[test]
+    fn test_parse_simple_variable() {
+        assert_eq!(parse("show").unwrap(), Expr::Variable("show".to_string()));
+        assert_eq!(
+            parse("@first").unwrap(),
+            Expr::Variable("@first".to_string())
+        );
+    }
+
+    #[test]
+    fn test_numeric_comparison() {
+        assert!(eval("unread > 0"));
+        assert!(eval("unread >= 3"));
+        assert!(!eval("unread < 3"));
+        assert!(eval("zero <= 0"));
+        assert!(eval("ratio < 1"));
+        assert!(eval("unread != 4"));
+        assert!(eval("unread > -1"));
+    }
+
+    #[test]
+    fn test_string_equality() {
+        assert!(eval(r#"role == "sysop""#));
+        assert!(!eval(r#"role == "member""#));
+        assert!(eval(r#"role != "guest""#));
+    }
+
+    #[test]
+    fn test_boolean_operators() {
+        assert!(eval("unread > 0 && admin"));
+        assert!(eval("zero || admin"));
+        assert!(!eval("!admin"));
+        assert!(eval("!(unread > 5) && (role == \"sysop\" || zero)"));
+        assert!(eval("admin == true"));
+    }
+
+    #[test]
+    fn test_missing_variable() {
+        assert!(!eval("missing"));
+        assert!(!eval("missing > 0"));
+        assert!(eval("missing == null"));
+        assert!(eval("missing != 1"));
+    }
+
+    #[test]
+    fn test_precedence() {
+        // && binds tighter than ||
+        assert!(eval("admin || zero && missing"));
+        assert!(!eval("(admin || zero) && missing"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("").is_err());
+        assert!(parse("a >").is_err());
+        assert!(parse("(a").is_err());
+        assert!(parse("a b").is_err());
+        assert!(parse("a = b").is_err());
+        assert!(parse("\"open").is_err());
+    }
+}
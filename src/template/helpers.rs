@@ -0,0 +1,194 @@
+//! Formatting helpers available in templates.
+//!
+//! | Helper | Example | Output |
+//! |--------|---------|--------|
+//! | `date` | `{{date created_at "%m/%d"}}` | `01/15` (in the BBS timezone) |
+//! | `number` | `{{number count}}` | `12,345` |
+//! | `bytes` | `{{bytes size}}` | `1.5 KB` |
+//! | `truncate` | `{{truncate title 20}}` | `Long title her...` |
+//! | `upper` / `lower` | `{{upper name}}` | `TARO` |
+//! | `right` / `center` | `{{right count 6}}` | `    42` |
+//!
+//! Width arguments are display columns, so CJK characters count as the
+//! context's CJK width.
+
+use super::{display_width, truncate_to_width, Value};
+use crate::datetime::{format_datetime, format_utc_datetime};
+
+/// Names of the helpers understood by the parser.
+pub(super) const HELPERS: [&str; 8] = [
+    "date", "number", "bytes", "truncate", "upper", "lower", "right", "center",
+];
+
+/// Default format of the `date` helper.
+pub const DEFAULT_DATE_FORMAT: &str = "%Y/%m/%d %H:%M";
+
+/// Format a date value in the given timezone.
+///
+/// Strings are parsed as RFC3339 or SQLite datetimes (UTC); numbers are
+/// taken as Unix timestamps. Anything else is displayed as-is.
+pub fn format_date(value: &Value, timezone: &str, format: &str) -> String {
+    match value {
+        Value::Number(ts) => match chrono::DateTime::from_timestamp(*ts, 0) {
+            Some(dt) => format_utc_datetime(&dt, timezone, format),
+            None => ts.to_string(),
+        },
+        Value::String(s) => format_datetime(s, timezone, format),
+        other => other.to_display_string(),
+    }
+}
+
+/// Format an integer with thousands separators.
+///
+/// # Example
+///
+/// ```
+/// use hobbs::template::format_number;
+///
+/// assert_eq!(format_number(1234567), "1,234,567");
+/// assert_eq!(format_number(-1000), "-1,000");
+/// ```
+pub fn format_number(n: i64) -> String {
+    let digits = n.unsigned_abs().to_string();
+    let mut result = String::with_capacity(digits.len() + digits.len() / 3 + 1);
+    if n < 0 {
+        result.push('-');
+    }
+    for (i, ch) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            result.push(',');
+        }
+        result.push(ch);
+    }
+    result
+}
+
+/// Format a byte count as a human-readable size.
+///
+/// # Example
+///
+/// ```
+/// use hobbs::template::format_bytes;
+///
+/// assert_eq!(format_bytes(500), "500 B");
+/// assert_eq!(format_bytes(1536), "1.5 KB");
+/// ```
+pub fn format_bytes(size: i64) -> String {
+    const KB: i64 = 1024;
+    const MB: i64 = KB * 1024;
+    const GB: i64 = MB * 1024;
+
+    if size >= GB {
+        format!("{:.1} GB", size as f64 / GB as f64)
+    } else if size >= MB {
+        format!("{:.1} MB", size as f64 / MB as f64)
+    } else if size >= KB {
+        format!("{:.1} KB", size as f64 / KB as f64)
+    } else {
+        format!("{size} B")
+    }
+}
+
+/// Truncate text to a display width, ending with `...` when cut.
+pub fn truncate_with_ellipsis(s: &str, width: usize, cjk_width: usize) -> String {
+    if display_width(s, cjk_width) <= width {
+        return s.to_string();
+    }
+    if width <= 3 {
+        return ".".repeat(width);
+    }
+    format!("{}...", truncate_to_width(s, width - 3, cjk_width))
+}
+
+/// Right-align text within a display width. Longer text is truncated.
+pub fn align_right(s: &str, width: usize, cjk_width: usize) -> String {
+    let text = truncate_to_width(s, width, cjk_width);
+    let pad = width - display_width(&text, cjk_width);
+    format!("{}{}", " ".repeat(pad), text)
+}
+
+/// Center text within a display width. Longer text is truncated.
+///
+/// When the padding is odd, the extra space goes on the right.
+pub fn align_center(s: &str, width: usize, cjk_width: usize) -> String {
+    let text = truncate_to_width(s, width, cjk_width);
+    let pad = width - display_width(&text, cjk_width);
+    let left = pad / 2;
+    format!("{}{}{}", " ".repeat(left), text, " ".repeat(pad - left))
+}
+
+/// Numeric view of a value for `number` and `bytes`.
+pub(super) fn as_integer(value: &Value) -> Option<i64> {
+    match value {
+        Value::Number(n) => Some(*n),
+        Value::Float(f) => Some(f.round() as i64),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number(0), "0");
+        assert_eq!(format_number(999), "999");
+        assert_eq!(format_number(1000), "1,000");
+        assert_eq!(format_number(1234567), "1,234,567");
+        assert_eq!(format_number(-987654), "-987,654");
+        assert_eq!(format_number(i64::MIN), "-9,223,372,036,854,775,808");
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1024), "1.0 KB");
+        assert_eq!(format_bytes(1048576), "1.0 MB");
+        assert_eq!(format_bytes(1073741824), "1.0 GB");
+    }
+
+    #[test]
+    fn test_truncate_with_ellipsis() {
+        assert_eq!(truncate_with_ellipsis("Hello", 10, 2), "Hello");
+        assert_eq!(truncate_with_ellipsis("Hello, World", 8, 2), "Hello...");
+        assert_eq!(truncate_with_ellipsis("こんにちは世界", 9, 2), "こんに...");
+        assert_eq!(truncate_with_ellipsis("こんにちは世界", 8, 2), "こん...");
+        assert_eq!(
+            truncate_with_ellipsis("こんにちは世界", 9, 1),
+            "こんにちは世界"
+        );
+        assert_eq!(truncate_with_ellipsis("Hello", 2, 2), "..");
+    }
+
+    #[test]
+    fn test_align_right() {
+        assert_eq!(align_right("42", 5, 2), "   42");
+        assert_eq!(align_right("日本", 6, 2), "  日本");
+        assert_eq!(align_right("toolong", 4, 2), "tool");
+    }
+
+    #[test]
+    fn test_align_center() {
+        assert_eq!(align_center("ab", 6, 2), "  ab  ");
+        assert_eq!(align_center("ab", 5, 2), " ab  ");
+        assert_eq!(align_center("漢字", 8, 2), "  漢字  ");
+    }
+
+    #[test]
+    fn test_format_date() {
+        let value = Value::String("2025-01-15 12:30:00".to_string());
+        assert_eq!(
+            format_date(&value, "Asia/Tokyo", DEFAULT_DATE_FORMAT),
+            "2025/01/15 21:30"
+        );
+        assert_eq!(format_date(&value, "UTC", "%m/%d"), "01/15");
+
+        let ts = Value::Number(0);
+        assert_eq!(format_date(&ts, "UTC", "%Y-%m-%d"), "1970-01-01");
+
+        let text = Value::String("yesterday".to_string());
+        assert_eq!(format_date(&text, "UTC", "%Y"), "yesterday");
+    }
+}
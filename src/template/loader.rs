@@ -1,13 +1,40 @@
 //! Template loader module.
 //!
 //! Provides automatic template loading based on terminal width.
+//!
+//! A width directory may contain per-language overrides in a subdirectory
+//! named after the locale (`80/ja/board/list.txt`). When rendering, a
+//! template is looked up in this order:
+//!
+//! 1. `<width>/<locale>/<name>.txt`
+//! 2. `<width>/<name>.txt`
+//! 3. the same two paths under the other width
+//!
+//! The locale is taken from the context's [`I18n`], i.e. the session's
+//! current language.
+//!
+//! A screen that has an 80-column template but no hand-written 40-column
+//! one is rendered for narrow terminals by reflowing the 80-column
+//! template (see the `reflow` module).
+//!
+//! Parsed templates are cached. With hot reload enabled (the default), a
+//! cached template is re-read whenever its file's modification time or size
+//! changes, so edits take effect without a restart.
+//!
+//! Caret escapes and pipe/`@X` color codes in template text are converted
+//! to ANSI when a template is parsed. Interpolated values are inserted as
+//! they are, so markup in user data is never turned into color here.
 
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
 
-use super::{Result, TemplateContext, TemplateEngine, TemplateError};
+use super::reflow::{reflow_text, shrink_nodes};
+use super::{Node, Parser, PartialSource, Renderer, Result, TemplateContext, TemplateError};
 use crate::i18n::I18n;
+use crate::server::{convert_caret_escape, convert_color_codes};
 
 /// Default template directory for 80-column terminals.
 pub const WIDTH_80: u16 = 80;
@@ -20,6 +47,25 @@ pub const WIDTH_40: u16 = 40;
 pub struct TemplateLoader {
     /// Base path for templates.
     base_path: PathBuf,
+    /// Re-read templates whose files changed since they were cached.
+    hot_reload: bool,
+    /// Parsed templates keyed by file path.
+    cache: RwLock<HashMap<PathBuf, CachedTemplate>>,
+}
+
+/// A parsed template and the file state it was parsed from.
+#[derive(Debug)]
+struct CachedTemplate {
+    /// File modification time and size when parsed.
+    stamp: Option<(SystemTime, u64)>,
+    /// Parsed nodes.
+    nodes: Arc<Vec<Node>>,
+}
+
+/// Modification time and size of a file, if available.
+fn file_stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
 }
 
 impl TemplateLoader {
@@ -31,9 +77,25 @@ impl TemplateLoader {
     pub fn new<P: AsRef<Path>>(base_path: P) -> Self {
         Self {
             base_path: base_path.as_ref().to_path_buf(),
+            hot_reload: true,
+            cache: RwLock::new(HashMap::new()),
         }
     }
 
+    /// Enable or disable hot reload.
+    ///
+    /// When disabled, each template is read once and served from the cache
+    /// until the process restarts.
+    pub fn with_hot_reload(mut self, enabled: bool) -> Self {
+        self.hot_reload = enabled;
+        self
+    }
+
+    /// Whether hot reload is enabled.
+    pub fn hot_reload(&self) -> bool {
+        self.hot_reload
+    }
+
     /// Get the template directory for a given width.
     fn get_width_dir(&self, width: u16) -> PathBuf {
         let dir_name = if width >= WIDTH_80 { "80" } else { "40" };
@@ -41,11 +103,17 @@ impl TemplateLoader {
     }
 
     /// Get the full path to a template file.
-    fn get_template_path(&self, name: &str, width: u16) -> PathBuf {
+    pub(super) fn get_template_path(&self, name: &str, width: u16) -> PathBuf {
         let width_dir = self.get_width_dir(width);
         width_dir.join(format!("{name}.txt"))
     }
 
+    /// Get the full path to a locale override of a template.
+    pub(super) fn get_locale_template_path(&self, name: &str, width: u16, locale: &str) -> PathBuf {
+        let width_dir = self.get_width_dir(width);
+        width_dir.join(locale).join(format!("{name}.txt"))
+    }
+
     /// Load a template for the given width.
     ///
     /// # Arguments
@@ -80,19 +148,77 @@ impl TemplateLoader {
     /// * `name` - Template name
     /// * `width` - Terminal width in columns
     pub fn load_with_fallback(&self, name: &str, width: u16) -> Result<String> {
-        // Try primary width first
-        match self.load(name, width) {
-            Ok(content) => Ok(content),
-            Err(_) => {
-                // Fallback to other width
-                let fallback_width = if width >= WIDTH_80 {
-                    WIDTH_40
-                } else {
-                    WIDTH_80
-                };
-                self.load(name, fallback_width)
+        let path = self.resolve_path(name, width, None)?;
+        fs::read_to_string(&path)
+            .map_err(|e| TemplateError::Render(format!("Failed to read template '{name}': {e}")))
+    }
+
+    /// Resolve the file for a template.
+    ///
+    /// Tries the locale override and then the plain template for `width`,
+    /// then the same for the other width.
+    fn resolve_path(&self, name: &str, width: u16, locale: Option<&str>) -> Result<PathBuf> {
+        let fallback_width = if width >= WIDTH_80 {
+            WIDTH_40
+        } else {
+            WIDTH_80
+        };
+        let locale = locale.filter(|locale| is_locale_dir(locale));
+
+        for w in [width, fallback_width] {
+            if let Some(locale) = locale {
+                let path = self.get_locale_template_path(name, w, locale);
+                if path.exists() {
+                    return Ok(path);
+                }
+            }
+            let path = self.get_template_path(name, w);
+            if path.exists() {
+                return Ok(path);
             }
         }
+
+        Err(TemplateError::NotFound(format!(
+            "Template '{name}' not found at {:?}",
+            self.get_template_path(name, width)
+        )))
+    }
+
+    /// Load and parse a template, using the cache when it is still fresh.
+    fn load_nodes(&self, name: &str, width: u16, locale: Option<&str>) -> Result<Arc<Vec<Node>>> {
+        let path = self.resolve_path(name, width, locale)?;
+        let stamp = if self.hot_reload {
+            file_stamp(&path)
+        } else {
+            None
+        };
+
+        if let Ok(cache) = self.cache.read() {
+            if let Some(cached) = cache.get(&path) {
+                if !self.hot_reload || cached.stamp == stamp {
+                    return Ok(Arc::clone(&cached.nodes));
+                }
+            }
+        }
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| TemplateError::Render(format!("Failed to read template '{name}': {e}")))?;
+        let nodes = Parser::new(&content)
+            .parse()
+            .map_err(|e| TemplateError::Parse(format!("{}: {e}", path.display())))?;
+        let nodes = Arc::new(convert_markup(&nodes));
+
+        if let Ok(mut cache) = self.cache.write() {
+            cache.insert(
+                path,
+                CachedTemplate {
+                    stamp,
+                    nodes: Arc::clone(&nodes),
+                },
+            );
+        }
+
+        Ok(nodes)
     }
 
     /// Render a template for the given width.
@@ -102,12 +228,62 @@ impl TemplateLoader {
     /// * `name` - Template name
     /// * `width` - Terminal width in columns
     /// * `context` - Template context with variables
+    ///
+    /// The template is resolved for the context's locale first. Partials
+    /// and layouts referenced by the template are loaded with the same
+    /// locale, width and fallback as the template itself.
+    ///
+    /// Below 80 columns, a template that exists only as an 80-column file is
+    /// reflowed to 40 columns instead of being shown as-is.
     pub fn render(&self, name: &str, width: u16, context: &TemplateContext) -> Result<String> {
-        let content = self.load_with_fallback(name, width)?;
-        TemplateEngine::render_string(&content, context)
+        let locale = context.i18n().locale();
+        if width < WIDTH_80
+            && !self.has_own_template(name, width, locale)
+            && self.has_own_template(name, WIDTH_80, locale)
+        {
+            return self.render_reflowed(name, context);
+        }
+
+        let source = WidthSource {
+            loader: self,
+            width,
+            locale,
+        };
+        let nodes = source.load_partial(name)?;
+        Renderer::new(context)
+            .with_partials(&source)
+            .render_template(name, &nodes)
+    }
+
+    /// Render an 80-column template reflowed to 40 columns.
+    fn render_reflowed(&self, name: &str, context: &TemplateContext) -> Result<String> {
+        let source = ReflowSource {
+            loader: self,
+            locale: context.i18n().locale(),
+        };
+        let nodes = source.load_partial(name)?;
+        let rendered = Renderer::new(context)
+            .with_partials(&source)
+            .render_template(name, &nodes)?;
+        Ok(reflow_text(
+            &rendered,
+            WIDTH_80,
+            WIDTH_40,
+            context.cjk_width(),
+        ))
+    }
+
+    /// Whether a template exists in the width directory itself, either as a
+    /// locale override or a plain file.
+    fn has_own_template(&self, name: &str, width: u16, locale: &str) -> bool {
+        (is_locale_dir(locale) && self.get_locale_template_path(name, width, locale).exists())
+            || self.has_template(name, width)
     }
 
     /// List available templates for a given width.
+    ///
+    /// Locale override directories are not included; see
+    /// [`list_locale_templates`](Self::list_locale_templates).
     pub fn list_templates(&self, width: u16) -> Result<Vec<String>> {
         let width_dir = self.get_width_dir(width);
 
@@ -117,6 +293,48 @@ impl TemplateLoader {
 
         let mut templates = Vec::new();
         self.collect_templates(&width_dir, "", &mut templates)?;
+        templates.retain(|name| {
+            name.split_once('/')
+                .is_none_or(|(dir, _)| !is_locale_dir(dir))
+        });
+        templates.sort();
+        Ok(templates)
+    }
+
+    /// List the locales that have override directories for a given width.
+    pub fn list_locales(&self, width: u16) -> Result<Vec<String>> {
+        let width_dir = self.get_width_dir(width);
+
+        if !width_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let entries = fs::read_dir(&width_dir).map_err(|e| {
+            TemplateError::Render(format!("Failed to read directory {width_dir:?}: {e}"))
+        })?;
+        let mut locales = Vec::new();
+        for entry in entries {
+            let entry =
+                entry.map_err(|e| TemplateError::Render(format!("Failed to read entry: {e}")))?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            if entry.path().is_dir() && is_locale_dir(&name) {
+                locales.push(name);
+            }
+        }
+        locales.sort();
+        Ok(locales)
+    }
+
+    /// List the templates overridden for a locale at a given width.
+    pub fn list_locale_templates(&self, width: u16, locale: &str) -> Result<Vec<String>> {
+        let locale_dir = self.get_width_dir(width).join(locale);
+
+        if !is_locale_dir(locale) || !locale_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut templates = Vec::new();
+        self.collect_templates(&locale_dir, "", &mut templates)?;
         templates.sort();
         Ok(templates)
     }
@@ -132,6 +350,96 @@ impl TemplateLoader {
     }
 }
 
+/// Convert color markup in the text nodes of a template.
+fn convert_markup(nodes: &[Node]) -> Vec<Node> {
+    nodes
+        .iter()
+        .map(|node| match node {
+            Node::Text(text) => Node::Text(convert_color_codes(&convert_caret_escape(text))),
+            Node::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => Node::If {
+                condition: condition.clone(),
+                then_branch: convert_markup(then_branch),
+                else_branch: convert_markup(else_branch),
+            },
+            Node::Each {
+                variable,
+                item_name,
+                body,
+            } => Node::Each {
+                variable: variable.clone(),
+                item_name: item_name.clone(),
+                body: convert_markup(body),
+            },
+            Node::Unless { condition, body } => Node::Unless {
+                condition: condition.clone(),
+                body: convert_markup(body),
+            },
+            Node::With { variable, body } => Node::With {
+                variable: variable.clone(),
+                body: convert_markup(body),
+            },
+            Node::Block { name, body } => Node::Block {
+                name: name.clone(),
+                body: convert_markup(body),
+            },
+            other => other.clone(),
+        })
+        .collect()
+}
+
+/// Partial source resolving names against one terminal width.
+struct WidthSource<'a> {
+    loader: &'a TemplateLoader,
+    width: u16,
+    locale: &'a str,
+}
+
+impl PartialSource for WidthSource<'_> {
+    fn load_partial(&self, name: &str) -> Result<Arc<Vec<Node>>> {
+        self.loader.load_nodes(name, self.width, Some(self.locale))
+    }
+}
+
+/// Partial source for reflowed rendering.
+///
+/// Hand-written 40-column partials are used as they are; anything else is
+/// loaded from the 80-column directory with its widths scaled down.
+struct ReflowSource<'a> {
+    loader: &'a TemplateLoader,
+    locale: &'a str,
+}
+
+impl PartialSource for ReflowSource<'_> {
+    fn load_partial(&self, name: &str) -> Result<Arc<Vec<Node>>> {
+        if self.loader.has_own_template(name, WIDTH_40, self.locale) {
+            return self.loader.load_nodes(name, WIDTH_40, Some(self.locale));
+        }
+        let nodes = self.loader.load_nodes(name, WIDTH_80, Some(self.locale))?;
+        Ok(Arc::new(shrink_nodes(&nodes, WIDTH_80, WIDTH_40)))
+    }
+}
+
+/// Whether a directory name is a locale override directory.
+///
+/// Locale directories are named by a two-letter language code, optionally
+/// followed by a region (`ja`, `en`, `pt-BR`, `zh_TW`), so template
+/// directories must not use such names.
+pub fn is_locale_dir(name: &str) -> bool {
+    let (lang, region) = match name.split_once(['-', '_']) {
+        Some((lang, region)) => (lang, Some(region)),
+        None => (name, None),
+    };
+    lang.len() == 2
+        && lang.bytes().all(|b| b.is_ascii_lowercase())
+        && region.is_none_or(|r| {
+            (2..=4).contains(&r.len()) && r.bytes().all(|b| b.is_ascii_alphanumeric())
+        })
+}
+
 /// Recursively collect template names from a directory.
 fn collect_templates_recursive(
     dir: &Path,
@@ -179,6 +487,12 @@ impl TemplateLoader {
         self.has_template(name, WIDTH_80) || self.has_template(name, WIDTH_40)
     }
 
+    /// Check if a template resolves for a locale at any width, counting both
+    /// locale overrides and plain templates.
+    pub fn has_template_for_locale(&self, name: &str, locale: &str) -> bool {
+        self.resolve_path(name, WIDTH_80, Some(locale)).is_ok()
+    }
+
     /// Get the base path.
     pub fn base_path(&self) -> &Path {
         &self.base_path
@@ -352,6 +666,25 @@ mod tests {
         assert_eq!(result, "Hello, たろう!\nYou have 5 unread messages.\n");
     }
 
+    #[test]
+    fn test_render_converts_markup_in_template_text_only() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir_80 = temp_dir.path().join("80");
+        fs::create_dir_all(&dir_80).unwrap();
+        fs::write(dir_80.join("title.txt"), "|14{{title}}^[[0m").unwrap();
+
+        let loader = TemplateLoader::new(temp_dir.path());
+        let mut context = TemplateContext::new(Arc::new(I18n::empty("en")));
+        // A title from a board without color, already stripped of its codes.
+        let title = crate::server::render_user_colors("||0404 @X1F^[[31m", false);
+        context.set("title", super::super::Value::String(title));
+
+        assert_eq!(
+            loader.render("title", 80, &context).unwrap(),
+            "\x1b[1m\x1b[33m|04 \x1b[0m"
+        );
+    }
+
     #[test]
     fn test_list_templates() {
         let temp_dir = TempDir::new().unwrap();
@@ -417,4 +750,255 @@ mod tests {
         assert!(context.get("system.time").is_some());
         assert!(context.get("system.datetime").is_some());
     }
+
+    #[test]
+    fn test_render_partial_uses_width() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir_80 = temp_dir.path().join("80");
+        let dir_40 = temp_dir.path().join("40");
+        fs::create_dir_all(dir_80.join("partials")).unwrap();
+        fs::create_dir_all(&dir_40).unwrap();
+        fs::write(dir_80.join("page.txt"), "{{> partials/rule}}\nbody\n").unwrap();
+        fs::write(dir_80.join("partials/rule.txt"), "--------\n").unwrap();
+        fs::write(dir_40.join("page.txt"), "{{> partials/rule}}\nbody40\n").unwrap();
+
+        let loader = TemplateLoader::new(temp_dir.path());
+        let context = TemplateContext::new(Arc::new(I18n::empty("ja")));
+
+        assert_eq!(
+            loader.render("page", 80, &context).unwrap(),
+            "--------\nbody\n"
+        );
+        // The 40-column page falls back to the 80-column partial
+        assert_eq!(
+            loader.render("page", 40, &context).unwrap(),
+            "--------\nbody40\n"
+        );
+    }
+
+    #[test]
+    fn test_render_layout() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir_80 = temp_dir.path().join("80");
+        fs::create_dir_all(dir_80.join("layouts")).unwrap();
+        fs::write(
+            dir_80.join("layouts/frame.txt"),
+            "=== {{#block title}}{{/block}} ===\n{{#block content}}\n{{/block}}\n",
+        )
+        .unwrap();
+        fs::write(
+            dir_80.join("screen.txt"),
+            "{{extends \"layouts/frame\"}}\n{{#block title}}{{name}}{{/block}}\n{{#block content}}\nline\n{{/block}}\n",
+        )
+        .unwrap();
+
+        let loader = TemplateLoader::new(temp_dir.path());
+        let mut context = TemplateContext::new(Arc::new(I18n::empty("ja")));
+        context.set("name", super::super::Value::String("Top".to_string()));
+
+        assert_eq!(
+            loader.render("screen", 80, &context).unwrap(),
+            "=== Top ===\nline\n"
+        );
+    }
+
+    #[test]
+    fn test_render_recursive_partial() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir_80 = temp_dir.path().join("80");
+        fs::create_dir_all(&dir_80).unwrap();
+        fs::write(dir_80.join("loop.txt"), "x{{> loop}}").unwrap();
+
+        let loader = TemplateLoader::new(temp_dir.path());
+        let context = TemplateContext::new(Arc::new(I18n::empty("ja")));
+
+        let result = loader.render("loop", 80, &context);
+        assert!(matches!(result, Err(TemplateError::Render(_))));
+    }
+
+    #[test]
+    fn test_hot_reload_picks_up_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir_80 = temp_dir.path().join("80");
+        fs::create_dir_all(&dir_80).unwrap();
+        fs::write(dir_80.join("page.txt"), "v1").unwrap();
+
+        let loader = TemplateLoader::new(temp_dir.path());
+        let context = TemplateContext::new(Arc::new(I18n::empty("ja")));
+        assert!(loader.hot_reload());
+        assert_eq!(loader.render("page", 80, &context).unwrap(), "v1");
+
+        fs::write(dir_80.join("page.txt"), "version 2").unwrap();
+        assert_eq!(loader.render("page", 80, &context).unwrap(), "version 2");
+    }
+
+    #[test]
+    fn test_cache_without_hot_reload() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir_80 = temp_dir.path().join("80");
+        fs::create_dir_all(&dir_80).unwrap();
+        fs::write(dir_80.join("page.txt"), "v1").unwrap();
+
+        let loader = TemplateLoader::new(temp_dir.path()).with_hot_reload(false);
+        let context = TemplateContext::new(Arc::new(I18n::empty("ja")));
+        assert_eq!(loader.render("page", 80, &context).unwrap(), "v1");
+
+        fs::write(dir_80.join("page.txt"), "version 2").unwrap();
+        assert_eq!(loader.render("page", 80, &context).unwrap(), "v1");
+    }
+
+    #[test]
+    fn test_render_parse_error_names_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir_80 = temp_dir.path().join("80");
+        fs::create_dir_all(&dir_80).unwrap();
+        fs::write(dir_80.join("broken.txt"), "ok\n{{#if x}}").unwrap();
+
+        let loader = TemplateLoader::new(temp_dir.path());
+        let context = TemplateContext::new(Arc::new(I18n::empty("ja")));
+
+        match loader.render("broken", 80, &context) {
+            Err(TemplateError::Parse(msg)) => {
+                assert!(msg.contains("broken.txt"));
+                assert!(msg.contains("line 2"));
+            }
+            other => panic!("expected parse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_render_locale_override() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir_80 = temp_dir.path().join("80");
+        let dir_40 = temp_dir.path().join("40");
+        fs::create_dir_all(dir_80.join("ja/board")).unwrap();
+        fs::create_dir_all(dir_80.join("board")).unwrap();
+        fs::create_dir_all(dir_40.join("ja")).unwrap();
+        fs::write(dir_80.join("board/list.txt"), "list").unwrap();
+        fs::write(dir_80.join("ja/board/list.txt"), "一覧").unwrap();
+        fs::write(dir_80.join("help.txt"), "help").unwrap();
+        fs::write(dir_40.join("ja/help.txt"), "ヘルプ40").unwrap();
+
+        let loader = TemplateLoader::new(temp_dir.path());
+        let ja = TemplateContext::new(Arc::new(I18n::empty("ja")));
+        let en = TemplateContext::new(Arc::new(I18n::empty("en")));
+
+        // locale + width, then width
+        assert_eq!(loader.render("board/list", 80, &ja).unwrap(), "一覧");
+        assert_eq!(loader.render("board/list", 80, &en).unwrap(), "list");
+        assert_eq!(loader.render("board/list", 40, &ja).unwrap(), "一覧");
+        // The plain template for the width wins over the other width's override
+        assert_eq!(loader.render("help", 80, &ja).unwrap(), "help");
+        assert_eq!(loader.render("help", 40, &ja).unwrap(), "ヘルプ40");
+        assert_eq!(loader.render("help", 40, &en).unwrap(), "help");
+    }
+
+    #[test]
+    fn test_render_locale_override_partial() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir_80 = temp_dir.path().join("80");
+        fs::create_dir_all(dir_80.join("en/partials")).unwrap();
+        fs::create_dir_all(dir_80.join("partials")).unwrap();
+        fs::write(dir_80.join("page.txt"), "[{{> partials/title}}]").unwrap();
+        fs::write(dir_80.join("partials/title.txt"), "掲示板").unwrap();
+        fs::write(dir_80.join("en/partials/title.txt"), "Boards").unwrap();
+
+        let loader = TemplateLoader::new(temp_dir.path());
+        let ja = TemplateContext::new(Arc::new(I18n::empty("ja")));
+        let en = TemplateContext::new(Arc::new(I18n::empty("en")));
+
+        assert_eq!(loader.render("page", 80, &ja).unwrap(), "[掲示板]");
+        assert_eq!(loader.render("page", 80, &en).unwrap(), "[Boards]");
+    }
+
+    #[test]
+    fn test_list_templates_skips_locale_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_templates(temp_dir.path());
+        let dir_80 = temp_dir.path().join("80");
+        fs::create_dir_all(dir_80.join("ja/board")).unwrap();
+        fs::create_dir_all(dir_80.join("en")).unwrap();
+        fs::write(dir_80.join("ja/board/list.txt"), "一覧").unwrap();
+        fs::write(dir_80.join("en/welcome.txt"), "Hi").unwrap();
+
+        let loader = TemplateLoader::new(temp_dir.path());
+
+        let templates = loader.list_templates(80).unwrap();
+        assert!(templates.contains(&"board/list".to_string()));
+        assert!(!templates.iter().any(|name| name.starts_with("ja/")));
+        assert_eq!(loader.list_locales(80).unwrap(), vec!["en", "ja"]);
+        assert!(loader.list_locales(40).unwrap().is_empty());
+        assert_eq!(
+            loader.list_locale_templates(80, "ja").unwrap(),
+            vec!["board/list"]
+        );
+        assert!(loader.has_template_for_locale("welcome", "ja"));
+        assert!(!loader.has_template_for_locale("nonexistent", "en"));
+    }
+
+    #[test]
+    fn test_is_locale_dir() {
+        assert!(is_locale_dir("ja"));
+        assert!(is_locale_dir("en"));
+        assert!(is_locale_dir("pt-BR"));
+        assert!(is_locale_dir("zh_TW"));
+        assert!(!is_locale_dir("board"));
+        assert!(!is_locale_dir("rss"));
+        assert!(!is_locale_dir("JA"));
+        assert!(!is_locale_dir(".."));
+        assert!(!is_locale_dir(""));
+    }
+
+    #[test]
+    fn test_render_reflows_80_column_template() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir_80 = temp_dir.path().join("80");
+        fs::create_dir_all(&dir_80).unwrap();
+        fs::write(
+            dir_80.join("list.txt"),
+            format!(
+                "{}\n{{{{#each items}}}}  [{{{{pad name 20}}}}]\n{{{{/each}}}}",
+                "=".repeat(80)
+            ),
+        )
+        .unwrap();
+
+        let loader = TemplateLoader::new(temp_dir.path());
+        let mut context = TemplateContext::new(Arc::new(I18n::empty("en")));
+        context.set(
+            "items",
+            super::super::Value::list(vec![super::super::Value::object(
+                [("name".to_string(), super::super::Value::string("General"))].into(),
+            )]),
+        );
+
+        assert_eq!(
+            loader.render("list", 80, &context).unwrap(),
+            format!("{}\n  [General             ]\n", "=".repeat(80))
+        );
+        assert_eq!(
+            loader.render("list", 40, &context).unwrap(),
+            format!("{}\n  [General   ]\n", "=".repeat(40))
+        );
+    }
+
+    #[test]
+    fn test_reflow_prefers_hand_written_40_column_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir_80 = temp_dir.path().join("80");
+        let dir_40 = temp_dir.path().join("40");
+        fs::create_dir_all(dir_80.join("partials")).unwrap();
+        fs::create_dir_all(dir_40.join("partials")).unwrap();
+        fs::write(dir_80.join("page.txt"), "{{> partials/title}}{{pad x 10}}|").unwrap();
+        fs::write(dir_80.join("partials/title.txt"), "{{pad x 40}}|").unwrap();
+        fs::write(dir_40.join("partials/title.txt"), "40:").unwrap();
+        fs::write(dir_80.join("menu.txt"), "80").unwrap();
+        fs::write(dir_40.join("menu.txt"), "40").unwrap();
+
+        let loader = TemplateLoader::new(temp_dir.path());
+        let context = TemplateContext::new(Arc::new(I18n::empty("en")));
+
+        assert_eq!(loader.render("menu", 40, &context).unwrap(), "40");
+        assert_eq!(loader.render("page", 40, &context).unwrap(), "40:     |");
+    }
 }
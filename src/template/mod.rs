@@ -6,8 +6,13 @@
 //!
 //! - Variable expansion: `{{variable}}`
 //! - Translation reference: `{{t "key"}}` or `{{t "key" name=value}}`
-//! - Conditionals: `{{#if condition}}...{{else}}...{{/if}}`
+//! - Conditionals: `{{#if condition}}...{{else}}...{{/if}}`, where the
+//!   condition may use `==`, `!=`, `<`, `<=`, `>`, `>=`, `!`, `&&` and `||`
+//! - Helpers: `{{date}}`, `{{number}}`, `{{bytes}}`, `{{truncate}}`,
+//!   `{{upper}}`, `{{lower}}`, `{{right}}`, `{{center}}` and `{{pad}}`
 //! - Loops: `{{#each items}}...{{/each}}`
+//! - Partials: `{{> partials/header}}` or `{{> name param=value}}`
+//! - Layouts: `{{extends "layouts/screen"}}` with `{{#block name}}...{{/block}}`
 //! - Escaping: `\{{` to output literal `{{`
 //!
 //! # Example
@@ -28,9 +33,13 @@
 //! assert_eq!(result, "Hello, World!");
 //! ```
 
+mod expr;
+mod helpers;
 mod loader;
 mod parser;
+mod reflow;
 mod renderer;
+mod validate;
 
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -39,9 +48,14 @@ use thiserror::Error;
 
 use crate::i18n::I18n;
 
-pub use loader::{create_system_context, TemplateLoader, WIDTH_40, WIDTH_80};
-pub use parser::{Node, Parser};
-pub use renderer::Renderer;
+pub use helpers::{
+    align_center, align_right, format_bytes, format_date, format_number, truncate_with_ellipsis,
+    DEFAULT_DATE_FORMAT,
+};
+pub use loader::{create_system_context, is_locale_dir, TemplateLoader, WIDTH_40, WIDTH_80};
+pub use parser::{Node, Parser, SyntaxError};
+pub use renderer::{PartialSource, Renderer, MAX_INCLUDE_DEPTH};
+pub use validate::{validate_templates, IssueKind, TemplateIssue};
 
 /// Calculate the display width of a string considering CJK character width.
 pub fn display_width(s: &str, cjk_width: usize) -> usize {
@@ -75,6 +89,25 @@ pub fn truncate_to_width(s: &str, max_width: usize, cjk_width: usize) -> String
     result
 }
 
+/// Pad or truncate a string to exactly the specified display width.
+///
+/// Text that does not fit is truncated and marked with a trailing `~`.
+pub fn pad_to_width(s: &str, width: usize, cjk_width: usize) -> String {
+    if width == 0 {
+        return String::new();
+    }
+
+    let display_w = display_width(s, cjk_width);
+    if display_w <= width {
+        format!("{}{}", s, " ".repeat(width - display_w))
+    } else {
+        // Truncate to (width - 1) and append '~'
+        let truncated = truncate_to_width(s, width - 1, cjk_width);
+        let truncated_w = display_width(&truncated, cjk_width);
+        format!("{}{}~", truncated, " ".repeat(width - 1 - truncated_w))
+    }
+}
+
 /// Template-related errors.
 #[derive(Error, Debug)]
 pub enum TemplateError {
@@ -236,6 +269,8 @@ pub struct TemplateContext {
     i18n: Arc<I18n>,
     /// CJK character width (1 or 2). Used by {{pad}} helper.
     cjk_width: usize,
+    /// Timezone used by the {{date}} helper.
+    timezone: String,
 }
 
 impl TemplateContext {
@@ -245,6 +280,7 @@ impl TemplateContext {
             variables: HashMap::new(),
             i18n,
             cjk_width: 2,
+            timezone: "UTC".to_string(),
         }
     }
 
@@ -258,6 +294,16 @@ impl TemplateContext {
         self.cjk_width
     }
 
+    /// Set the timezone used for date formatting (e.g., "Asia/Tokyo").
+    pub fn set_timezone(&mut self, timezone: impl Into<String>) {
+        self.timezone = timezone.into();
+    }
+
+    /// Get the timezone used for date formatting.
+    pub fn timezone(&self) -> &str {
+        &self.timezone
+    }
+
     /// Set a variable in the context.
     pub fn set(&mut self, name: impl Into<String>, value: Value) {
         self.variables.insert(name.into(), value);
@@ -306,6 +352,7 @@ impl TemplateContext {
             variables: self.variables.clone(),
             i18n: Arc::clone(&self.i18n),
             cjk_width: self.cjk_width,
+            timezone: self.timezone.clone(),
         }
     }
 }
@@ -340,8 +387,8 @@ impl TemplateEngine {
             .get(name)
             .ok_or_else(|| TemplateError::NotFound(name.to_string()))?;
 
-        let renderer = Renderer::new(context);
-        renderer.render(nodes)
+        let renderer = Renderer::new(context).with_partials(self);
+        renderer.render_template(name, nodes)
     }
 
     /// Render a template string directly without loading.
@@ -373,6 +420,15 @@ impl TemplateEngine {
     }
 }
 
+impl PartialSource for TemplateEngine {
+    fn load_partial(&self, name: &str) -> Result<Arc<Vec<Node>>> {
+        self.templates
+            .get(name)
+            .map(|nodes| Arc::new(nodes.clone()))
+            .ok_or_else(|| TemplateError::NotFound(name.to_string()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -610,4 +666,17 @@ mod tests {
         assert!(!engine.has_template("a"));
         assert!(!engine.has_template("b"));
     }
+
+    #[test]
+    fn test_engine_render_partial() {
+        let mut engine = TemplateEngine::new();
+        engine.load("header", "[{{title}}]").unwrap();
+        engine
+            .load("page", r#"{{> header title="Top"}} body"#)
+            .unwrap();
+
+        let context = create_context();
+        let result = engine.render("page", &context).unwrap();
+        assert_eq!(result, "[Top] body");
+    }
 }
@@ -2,6 +2,8 @@
 //!
 //! Parses template strings into an AST (Abstract Syntax Tree) of nodes.
 
+use super::expr;
+use super::helpers::HELPERS;
 use super::{Result, TemplateError};
 
 /// A node in the template AST.
@@ -20,6 +22,8 @@ pub enum Node {
     },
 
     /// Conditional block: `{{#if condition}}...{{else}}...{{/if}}`
+    ///
+    /// The condition is an expression such as `unread > 0 && !@last`.
     If {
         condition: String,
         then_branch: Vec<Node>,
@@ -42,6 +46,51 @@ pub enum Node {
     /// Pad helper: `{{pad variable width}}`
     /// Pads or truncates a variable to a fixed display width.
     Pad { variable: String, width: String },
+
+    /// Formatting helper: `{{number count}}`, `{{date created_at "%m/%d"}}`
+    ///
+    /// Quoted arguments keep their quotes to mark them as literals.
+    Helper { name: String, args: Vec<String> },
+
+    /// Partial include: `{{> name}}` or `{{> name param=value}}`
+    Partial {
+        name: String,
+        params: Vec<(String, String)>,
+    },
+
+    /// Named block: `{{#block name}}...{{/block}}`
+    /// Renders its body unless a child template overrides it.
+    Block { name: String, body: Vec<Node> },
+
+    /// Layout reference: `{{extends "layouts/screen"}}`
+    Extends(String),
+}
+
+/// A syntax error with its position in the template source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxError {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column number, in characters.
+    pub column: usize,
+    /// Description of the error.
+    pub message: String,
+}
+
+impl std::fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl From<SyntaxError> for TemplateError {
+    fn from(err: SyntaxError) -> Self {
+        TemplateError::Parse(err.to_string())
+    }
 }
 
 /// Template parser.
@@ -57,8 +106,29 @@ impl<'a> Parser<'a> {
     }
 
     /// Parse the template into a list of nodes.
-    pub fn parse(mut self) -> Result<Vec<Node>> {
-        self.parse_nodes(None)
+    pub fn parse(self) -> Result<Vec<Node>> {
+        self.parse_checked().map_err(TemplateError::from)
+    }
+
+    /// Parse the template, reporting where parsing stopped on error.
+    pub fn parse_checked(mut self) -> std::result::Result<Vec<Node>, SyntaxError> {
+        self.parse_nodes(None).map_err(|err| {
+            let (line, column) = self.line_column();
+            let message = match err {
+                TemplateError::Parse(message) => message,
+                other => other.to_string(),
+            };
+            SyntaxError {
+                line,
+                column,
+                message,
+            }
+        })
+    }
+
+    /// Line and column of the current position.
+    fn line_column(&self) -> (usize, usize) {
+        line_column(self.input, self.pos)
     }
 
     /// Parse nodes until reaching a closing tag or end of input.
@@ -82,8 +152,20 @@ impl<'a> Parser<'a> {
                 // Escaped opening brace
                 self.pos += 3; // Skip \{{
                 nodes.push(Node::Text("{{".to_string()));
+            } else if self.peek_str("{{/") {
+                // A closing tag that does not match the open block
+                let rest = &self.input[self.pos..];
+                let tag = rest.find("}}").map_or(rest, |end| &rest[..end + 2]);
+                return Err(TemplateError::Parse(format!(
+                    "Unexpected closing tag '{tag}'"
+                )));
             } else if self.peek_str("{{") {
                 let node = self.parse_tag()?;
+                if end_tag.is_some() && matches!(node, Node::Extends(_)) {
+                    return Err(TemplateError::Parse(
+                        "extends must be used at the top level".to_string(),
+                    ));
+                }
                 nodes.push(node);
             } else {
                 // Collect text until next tag or escape
@@ -99,6 +181,7 @@ impl<'a> Parser<'a> {
 
     /// Parse a single tag.
     fn parse_tag(&mut self) -> Result<Node> {
+        let tag_start = self.pos;
         self.expect("{{")?;
         self.skip_whitespace();
 
@@ -106,7 +189,19 @@ impl<'a> Parser<'a> {
         if self.peek_char() == Some('#') {
             self.advance(); // Skip #
             self.skip_whitespace();
-            return self.parse_block_tag();
+            return self.parse_block_tag(tag_start);
+        }
+
+        // Check for partial include
+        if self.peek_char() == Some('>') {
+            self.advance(); // Skip >
+            self.skip_whitespace();
+            return self.parse_partial(tag_start);
+        }
+
+        // Check for layout reference
+        if self.peek_str("extends ") {
+            return self.parse_extends(tag_start);
         }
 
         // Check for translation
@@ -119,16 +214,64 @@ impl<'a> Parser<'a> {
             return self.parse_pad();
         }
 
-        // Parse variable
+        // Parse variable or helper call
         let name = self.parse_identifier()?;
         self.skip_whitespace();
+        if self.peek_str("}}") {
+            self.expect("}}")?;
+            return Ok(Node::Variable(name));
+        }
+
+        if !HELPERS.contains(&name.as_str()) {
+            return Err(TemplateError::Parse(format!("Unknown helper: {name}")));
+        }
+        let mut args = Vec::new();
+        while !self.peek_str("}}") && self.pos < self.input.len() {
+            args.push(self.parse_argument()?);
+            self.skip_whitespace();
+        }
         self.expect("}}")?;
 
-        Ok(Node::Variable(name))
+        Ok(Node::Helper { name, args })
+    }
+
+    /// Parse a helper argument: a quoted literal (quotes kept) or an identifier.
+    fn parse_argument(&mut self) -> Result<String> {
+        if self.peek_char() == Some('"') {
+            Ok(format!("\"{}\"", self.parse_quoted_string()?))
+        } else {
+            self.parse_identifier()
+        }
     }
 
-    /// Parse a block tag (if, each, unless, with).
-    fn parse_block_tag(&mut self) -> Result<Node> {
+    /// Parse a condition expression up to the closing `}}`.
+    fn parse_condition(&mut self) -> Result<String> {
+        let start = self.pos;
+        let mut in_string = false;
+
+        while self.pos < self.input.len() {
+            let ch = self.current_char();
+            if in_string {
+                if ch == '\\' {
+                    self.advance();
+                } else if ch == '"' {
+                    in_string = false;
+                }
+            } else if ch == '"' {
+                in_string = true;
+            } else if self.peek_str("}}") {
+                break;
+            }
+            self.advance();
+        }
+
+        let condition = self.input[start..self.pos].trim().to_string();
+        expr::parse(&condition)?;
+        Ok(condition)
+    }
+
+    /// Parse a block tag (if, each, unless, with, block).
+    fn parse_block_tag(&mut self, tag_start: usize) -> Result<Node> {
         let tag_name = self.parse_identifier()?;
         self.skip_whitespace();
 
@@ -137,6 +280,7 @@ impl<'a> Parser<'a> {
             "each" => self.parse_each_block(),
             "unless" => self.parse_unless_block(),
             "with" => self.parse_with_block(),
+            "block" => self.parse_named_block(tag_start),
             _ => Err(TemplateError::Parse(format!(
                 "Unknown block tag: {tag_name}"
             ))),
@@ -145,8 +289,7 @@ impl<'a> Parser<'a> {
 
     /// Parse an if block.
     fn parse_if_block(&mut self) -> Result<Node> {
-        let condition = self.parse_identifier()?;
-        self.skip_whitespace();
+        let condition = self.parse_condition()?;
         self.expect("}}")?;
 
         let then_branch = self.parse_nodes(Some("if"))?;
@@ -196,8 +339,7 @@ impl<'a> Parser<'a> {
 
     /// Parse an unless block.
     fn parse_unless_block(&mut self) -> Result<Node> {
-        let condition = self.parse_identifier()?;
-        self.skip_whitespace();
+        let condition = self.parse_condition()?;
         self.expect("}}")?;
 
         let body = self.parse_nodes(Some("unless"))?;
@@ -218,16 +360,74 @@ impl<'a> Parser<'a> {
         Ok(Node::With { variable, body })
     }
 
-    /// Parse a translation tag.
-    fn parse_translation(&mut self) -> Result<Node> {
-        self.expect("t")?;
+    /// Parse a named block.
+    fn parse_named_block(&mut self, tag_start: usize) -> Result<Node> {
+        let name = self.parse_identifier()?;
         self.skip_whitespace();
+        self.expect("}}")?;
+        self.skip_standalone_newline(tag_start);
 
-        // Parse the key (quoted string)
-        let key = self.parse_quoted_string()?;
+        let body = self.parse_nodes(Some("block"))?;
+        let close_start = self.pos;
+        self.expect("{{/block}}")?;
+        self.skip_standalone_newline(close_start);
+
+        Ok(Node::Block { name, body })
+    }
+
+    /// Parse a partial include: `{{> name}}` or `{{> "name" param=value}}`
+    fn parse_partial(&mut self, tag_start: usize) -> Result<Node> {
+        let name = self.parse_template_name()?;
         self.skip_whitespace();
 
-        // Parse optional parameters
+        let params = self.parse_params()?;
+        self.expect("}}")?;
+        self.skip_standalone_newline(tag_start);
+
+        Ok(Node::Partial { name, params })
+    }
+
+    /// Parse a layout reference: `{{extends "layouts/screen"}}`
+    fn parse_extends(&mut self, tag_start: usize) -> Result<Node> {
+        self.expect("extends")?;
+        self.skip_whitespace();
+
+        let layout = self.parse_template_name()?;
+        self.skip_whitespace();
+        self.expect("}}")?;
+        self.skip_standalone_newline(tag_start);
+
+        Ok(Node::Extends(layout))
+    }
+
+    /// Parse a template name, either quoted or a bare path like `layouts/screen`.
+    fn parse_template_name(&mut self) -> Result<String> {
+        if self.peek_char() == Some('"') {
+            return self.parse_quoted_string();
+        }
+
+        let start = self.pos;
+        while self.pos < self.input.len() {
+            let ch = self.current_char();
+            if ch.is_alphanumeric() || matches!(ch, '_' | '.' | '-' | '/') {
+                self.advance();
+            } else {
+                break;
+            }
+        }
+
+        if self.pos == start {
+            return Err(TemplateError::Parse("Expected template name".to_string()));
+        }
+
+        Ok(self.input[start..self.pos].to_string())
+    }
+
+    /// Parse `name=value` parameters up to the closing `}}`.
+    ///
+    /// Quoted values keep their quotes so the renderer can tell literals
+    /// from variable references.
+    fn parse_params(&mut self) -> Result<Vec<(String, String)>> {
         let mut params = Vec::new();
         while self.peek_char() != Some('}') {
             let param_name = self.parse_identifier()?;
@@ -236,7 +436,6 @@ impl<'a> Parser<'a> {
             self.skip_whitespace();
 
             let param_value = if self.peek_char() == Some('"') {
-                // Keep quotes to indicate literal string in renderer
                 format!("\"{}\"", self.parse_quoted_string()?)
             } else {
                 self.parse_identifier()?
@@ -245,7 +444,35 @@ impl<'a> Parser<'a> {
             params.push((param_name, param_value));
             self.skip_whitespace();
         }
+        Ok(params)
+    }
+
+    /// Consume the line break after a tag that stands alone on its line.
+    ///
+    /// This keeps layout and include markup from leaving blank lines behind.
+    fn skip_standalone_newline(&mut self, tag_start: usize) {
+        let at_line_start = tag_start == 0 || self.input[..tag_start].ends_with('\n');
+        if !at_line_start {
+            return;
+        }
+        if self.peek_str("\r\n") {
+            self.pos += 2;
+        } else if self.peek_str("\n") {
+            self.pos += 1;
+        }
+    }
+
+    /// Parse a translation tag.
+    fn parse_translation(&mut self) -> Result<Node> {
+        self.expect("t")?;
+        self.skip_whitespace();
+
+        // Parse the key (quoted string)
+        let key = self.parse_quoted_string()?;
+        self.skip_whitespace();
 
+        // Parse optional parameters
+        let params = self.parse_params()?;
         self.expect("}}")?;
 
         Ok(Node::Translation { key, params })
@@ -293,13 +520,15 @@ impl<'a> Parser<'a> {
         Err(TemplateError::Parse("Unterminated string".to_string()))
     }
 
-    /// Parse an identifier (variable name, including dot notation).
+    /// Parse an identifier (variable name, including dot notation and
+    /// loop variables such as `@index`).
     fn parse_identifier(&mut self) -> Result<String> {
         let start = self.pos;
 
         while self.pos < self.input.len() {
             let ch = self.current_char();
-            if ch.is_alphanumeric() || ch == '_' || ch == '.' || ch == '-' {
+            let loop_var = ch == '@' && self.pos == start;
+            if ch.is_alphanumeric() || ch == '_' || ch == '.' || ch == '-' || loop_var {
                 self.advance();
             } else {
                 break;
@@ -370,6 +599,15 @@ impl<'a> Parser<'a> {
     }
 }
 
+/// 1-based line and column of a byte offset in `input`.
+pub(super) fn line_column(input: &str, pos: usize) -> (usize, usize) {
+    let before = &input[..pos.min(input.len())];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -714,4 +952,205 @@ User Menu:
 
         assert!(matches!(result, Err(TemplateError::Parse(_))));
     }
+
+    #[test]
+    fn test_parse_partial() {
+        let parser = Parser::new("{{> partials/header}}");
+        let nodes = parser.parse().unwrap();
+
+        assert_eq!(
+            nodes,
+            vec![Node::Partial {
+                name: "partials/header".to_string(),
+                params: vec![],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_partial_with_params() {
+        let parser = Parser::new(r#"{{> "partials/title" text="Hi" user=user.name}}"#);
+        let nodes = parser.parse().unwrap();
+
+        assert_eq!(
+            nodes,
+            vec![Node::Partial {
+                name: "partials/title".to_string(),
+                params: vec![
+                    ("text".to_string(), "\"Hi\"".to_string()),
+                    ("user".to_string(), "user.name".to_string()),
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_standalone_partial_consumes_newline() {
+        let parser = Parser::new("a\n{{> footer}}\nb {{> inline}}\nc");
+        let nodes = parser.parse().unwrap();
+
+        assert_eq!(
+            nodes,
+            vec![
+                Node::Text("a\n".to_string()),
+                Node::Partial {
+                    name: "footer".to_string(),
+                    params: vec![],
+                },
+                Node::Text("b ".to_string()),
+                Node::Partial {
+                    name: "inline".to_string(),
+                    params: vec![],
+                },
+                Node::Text("\nc".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_block() {
+        let parser = Parser::new("{{#block title}}Default{{/block}}");
+        let nodes = parser.parse().unwrap();
+
+        assert_eq!(
+            nodes,
+            vec![Node::Block {
+                name: "title".to_string(),
+                body: vec![Node::Text("Default".to_string())],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_block_standalone_lines() {
+        let parser = Parser::new("{{#block content}}\nline\n{{/block}}\nafter");
+        let nodes = parser.parse().unwrap();
+
+        assert_eq!(
+            nodes,
+            vec![
+                Node::Block {
+                    name: "content".to_string(),
+                    body: vec![Node::Text("line\n".to_string())],
+                },
+                Node::Text("after".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_extends() {
+        let parser = Parser::new("{{extends \"layouts/screen\"}}\n{{#block title}}T{{/block}}");
+        let nodes = parser.parse().unwrap();
+
+        assert_eq!(
+            nodes,
+            vec![
+                Node::Extends("layouts/screen".to_string()),
+                Node::Block {
+                    name: "title".to_string(),
+                    body: vec![Node::Text("T".to_string())],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_error_nested_extends() {
+        let parser = Parser::new("{{#if a}}{{extends \"layout\"}}{{/if}}");
+        let result = parser.parse();
+
+        assert!(matches!(result, Err(TemplateError::Parse(_))));
+    }
+
+    #[test]
+    fn test_parse_loop_variable() {
+        let parser = Parser::new("{{@index}}");
+        let nodes = parser.parse().unwrap();
+
+        assert_eq!(nodes, vec![Node::Variable("@index".to_string())]);
+    }
+
+    #[test]
+    fn test_parse_if_expression() {
+        let parser = Parser::new(r#"{{#if unread > 0 && role != "guest"}}x{{/if}}"#);
+        let nodes = parser.parse().unwrap();
+
+        assert_eq!(
+            nodes,
+            vec![Node::If {
+                condition: r#"unread > 0 && role != "guest""#.to_string(),
+                then_branch: vec![Node::Text("x".to_string())],
+                else_branch: vec![],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_condition_with_braces_in_string() {
+        let parser = Parser::new(r#"{{#unless title == "}}"}}x{{/unless}}"#);
+        let nodes = parser.parse().unwrap();
+
+        assert_eq!(
+            nodes,
+            vec![Node::Unless {
+                condition: r#"title == "}}""#.to_string(),
+                body: vec![Node::Text("x".to_string())],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_error_invalid_condition() {
+        assert!(Parser::new("{{#if a >}}x{{/if}}").parse().is_err());
+        assert!(Parser::new("{{#if}}x{{/if}}").parse().is_err());
+    }
+
+    #[test]
+    fn test_parse_helper() {
+        let parser = Parser::new(r#"{{date created_at "%m/%d"}} {{number count}}"#);
+        let nodes = parser.parse().unwrap();
+
+        assert_eq!(
+            nodes,
+            vec![
+                Node::Helper {
+                    name: "date".to_string(),
+                    args: vec!["created_at".to_string(), "\"%m/%d\"".to_string()],
+                },
+                Node::Text(" ".to_string()),
+                Node::Helper {
+                    name: "number".to_string(),
+                    args: vec!["count".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_error_unknown_helper() {
+        let result = Parser::new("{{shout name}}").parse();
+
+        assert!(matches!(result, Err(TemplateError::Parse(_))));
+    }
+
+    #[test]
+    fn test_parse_error_position() {
+        let parser = Parser::new("line one\n  {{#if show}}\n  {{name x}}\n{{/if}}");
+        let err = parser.parse_checked().unwrap_err();
+
+        assert_eq!(err.line, 3);
+        assert_eq!(err.column, 10);
+        assert!(err.message.contains("Unknown helper"));
+    }
+
+    #[test]
+    fn test_parse_error_message_includes_position() {
+        let result = Parser::new("日本語{{name").parse();
+
+        match result {
+            Err(TemplateError::Parse(msg)) => assert!(msg.starts_with("line 1, column 10:")),
+            other => panic!("expected parse error, got {other:?}"),
+        }
+    }
 }
@@ -0,0 +1,482 @@
+//! Reflow of 80-column templates for narrower terminals.
+//!
+//! When a screen has no hand-written 40-column template, the 80-column one
+//! is rendered in a narrowed form:
+//!
+//! - literal `{{pad}}`, `{{truncate}}`, `{{right}}` and `{{center}}` widths
+//!   are scaled by the ratio of the two widths,
+//! - box-drawing frames collapse: borders become plain rules and side
+//!   borders are removed,
+//! - rules wider than the terminal are cut to its width,
+//! - other lines wider than the terminal get their indentation and spacing
+//!   scaled down, then are word-wrapped.
+//!
+//! Caret escapes (`^[[1m`), ANSI sequences and pipe/`@X` color codes are
+//! treated as zero-width so that colored lines wrap at the right column.
+
+use super::{display_width, Node};
+
+/// Helpers whose second argument is a width.
+const WIDTH_HELPERS: [&str; 3] = ["truncate", "right", "center"];
+
+/// Characters that fill horizontal rules and frame borders.
+const RULE_FILLS: [char; 6] = ['-', '=', '─', '━', '═', '*'];
+
+/// Corners and junctions of box-drawing frames.
+const FRAME_CORNERS: [char; 24] = [
+    '+', '┌', '┐', '└', '┘', '├', '┤', '┬', '┴', '┼', '╔', '╗', '╚', '╝', '╠', '╣', '╦', '╩', '╬',
+    '┏', '┓', '┗', '┛', '╋',
+];
+
+/// Vertical frame sides.
+const FRAME_SIDES: [char; 4] = ['│', '║', '┃', '|'];
+
+/// Scale the literal widths in a template from `from` to `to` columns.
+pub(super) fn shrink_nodes(nodes: &[Node], from: u16, to: u16) -> Vec<Node> {
+    nodes
+        .iter()
+        .map(|node| shrink_node(node, from, to))
+        .collect()
+}
+
+fn shrink_node(node: &Node, from: u16, to: u16) -> Node {
+    match node {
+        Node::Pad { variable, width } => Node::Pad {
+            variable: variable.clone(),
+            width: scale_arg(width, from, to),
+        },
+        Node::Helper { name, args } if WIDTH_HELPERS.contains(&name.as_str()) => {
+            let mut args = args.clone();
+            if let Some(width) = args.get_mut(1) {
+                *width = scale_arg(width, from, to);
+            }
+            Node::Helper {
+                name: name.clone(),
+                args,
+            }
+        }
+        Node::If {
+            condition,
+            then_branch,
+            else_branch,
+        } => Node::If {
+            condition: condition.clone(),
+            then_branch: shrink_nodes(then_branch, from, to),
+            else_branch: shrink_nodes(else_branch, from, to),
+        },
+        Node::Each {
+            variable,
+            item_name,
+            body,
+        } => Node::Each {
+            variable: variable.clone(),
+            item_name: item_name.clone(),
+            body: shrink_nodes(body, from, to),
+        },
+        Node::Unless { condition, body } => Node::Unless {
+            condition: condition.clone(),
+            body: shrink_nodes(body, from, to),
+        },
+        Node::With { variable, body } => Node::With {
+            variable: variable.clone(),
+            body: shrink_nodes(body, from, to),
+        },
+        Node::Block { name, body } => Node::Block {
+            name: name.clone(),
+            body: shrink_nodes(body, from, to),
+        },
+        other => other.clone(),
+    }
+}
+
+/// Scale a width argument if it is a literal number.
+fn scale_arg(arg: &str, from: u16, to: u16) -> String {
+    if !arg.is_empty() && arg.bytes().all(|b| b.is_ascii_digit()) {
+        if let Ok(width) = arg.parse() {
+            return scale(width, from, to).to_string();
+        }
+    }
+    arg.to_string()
+}
+
+/// Scale a column count, rounding to nearest and keeping it at least 1.
+fn scale(n: usize, from: u16, to: u16) -> usize {
+    if n == 0 || from == 0 {
+        return n;
+    }
+    ((n * to as usize + from as usize / 2) / from as usize).max(1)
+}
+
+/// Reflow rendered output from `from` columns to `to` columns.
+pub(super) fn reflow_text(text: &str, from: u16, to: u16, cjk_width: usize) -> String {
+    let mut result = String::with_capacity(text.len());
+    for line in text.split_inclusive('\n') {
+        let (body, newline) = match line.strip_suffix('\n') {
+            Some(body) => (body, "\n"),
+            None => (line, ""),
+        };
+        result.push_str(&reflow_line(body, from, to, cjk_width).join("\n"));
+        result.push_str(newline);
+    }
+    result
+}
+
+/// Reflow one line of output into one or more lines.
+fn reflow_line(line: &str, from: u16, to: u16, cjk_width: usize) -> Vec<String> {
+    let width = to as usize;
+
+    if let Some(fill) = rule_fill(line) {
+        let indent = scale(leading_spaces(line), from, to).min(width / 2);
+        let fill_width = display_width(fill.encode_utf8(&mut [0; 4]), cjk_width).max(1);
+        let length = visible_width(line.trim(), cjk_width).min(width - indent);
+        return vec![format!(
+            "{}{}",
+            " ".repeat(indent),
+            fill.to_string().repeat(length / fill_width)
+        )];
+    }
+
+    let line = strip_frame(line).unwrap_or(line);
+    if visible_width(line, cjk_width) <= width {
+        return vec![line.to_string()];
+    }
+
+    let line = shrink_spacing(line, from, to);
+    if visible_width(&line, cjk_width) <= width {
+        return vec![line];
+    }
+
+    wrap(&line, width, cjk_width)
+}
+
+/// The fill character if the line is a horizontal rule or frame border.
+fn rule_fill(line: &str) -> Option<char> {
+    let trimmed = line.trim();
+    let mut fill = None;
+    let mut fills = 0;
+    for c in trimmed.chars() {
+        if RULE_FILLS.contains(&c) {
+            fill.get_or_insert(c);
+            fills += 1;
+        } else if !FRAME_CORNERS.contains(&c) {
+            return None;
+        }
+    }
+    if fills >= 3 {
+        fill
+    } else {
+        None
+    }
+}
+
+/// The inside of a line framed by vertical borders, with trailing spaces
+/// removed.
+fn strip_frame(line: &str) -> Option<&str> {
+    let trimmed = line.trim();
+    let first = trimmed.chars().next()?;
+    let last = trimmed.chars().last()?;
+    if !FRAME_SIDES.contains(&first) || first != last || trimmed.chars().count() < 2 {
+        return None;
+    }
+    // A plain `|` only counts as a frame when separated from the text, so
+    // that pipe color codes (`|07`) are left alone.
+    let inner = &trimmed[first.len_utf8()..trimmed.len() - last.len_utf8()];
+    if first == '|' && !(inner.starts_with(' ') && inner.ends_with(' ')) {
+        return None;
+    }
+    Some(inner.trim_end())
+}
+
+fn leading_spaces(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+/// Scale the indentation and runs of two or more spaces inside a line.
+fn shrink_spacing(line: &str, from: u16, to: u16) -> String {
+    let indent = leading_spaces(line);
+    let mut result = " ".repeat(scale(indent, from, to));
+    let mut spaces = 0;
+    for c in line[indent..].chars() {
+        if c == ' ' {
+            spaces += 1;
+            continue;
+        }
+        if spaces > 0 {
+            let kept = if spaces == 1 {
+                1
+            } else {
+                scale(spaces, from, to)
+            };
+            result.push_str(&" ".repeat(kept));
+            spaces = 0;
+        }
+        result.push(c);
+    }
+    result
+}
+
+/// Split a string into characters and zero-width markup sequences, each
+/// with its display width.
+fn segments(s: &str, cjk_width: usize) -> Vec<(&str, usize)> {
+    let mut segments = Vec::new();
+    let mut rest = s;
+    while let Some(c) = rest.chars().next() {
+        let len = markup_len(rest).unwrap_or(c.len_utf8());
+        let (segment, tail) = rest.split_at(len);
+        let width = if len == c.len_utf8() {
+            display_width(segment, cjk_width)
+        } else {
+            0
+        };
+        segments.push((segment, width));
+        rest = tail;
+    }
+    segments
+}
+
+/// Length in bytes of a markup sequence at the start of `s`.
+fn markup_len(s: &str) -> Option<usize> {
+    let bytes = s.as_bytes();
+    let csi_start = if s.starts_with("\x1b[") {
+        Some(2)
+    } else if s.starts_with("^[[") {
+        Some(3)
+    } else {
+        None
+    };
+    if let Some(start) = csi_start {
+        let end = bytes[start..].iter().position(u8::is_ascii_alphabetic)?;
+        return Some(start + end + 1);
+    }
+    if bytes.len() >= 3 && bytes[0] == b'|' && bytes[1..3].iter().all(u8::is_ascii_digit) {
+        return Some(3);
+    }
+    if bytes.len() >= 4 && s.starts_with("@X") && bytes[2..4].iter().all(u8::is_ascii_hexdigit) {
+        return Some(4);
+    }
+    None
+}
+
+/// Display width of a string, ignoring markup.
+fn visible_width(s: &str, cjk_width: usize) -> usize {
+    segments(s, cjk_width).iter().map(|(_, width)| width).sum()
+}
+
+/// A run of text that is kept on one line when possible.
+struct Word<'a> {
+    space_before: bool,
+    segments: Vec<(&'a str, usize)>,
+}
+
+impl Word<'_> {
+    fn width(&self) -> usize {
+        self.segments.iter().map(|(_, width)| width).sum()
+    }
+}
+
+/// Split text into words. Wide (CJK) characters are words of their own, so
+/// lines may break between them.
+fn words(s: &str, cjk_width: usize) -> Vec<Word<'_>> {
+    let mut words: Vec<Word> = Vec::new();
+    let mut space = false;
+    let mut in_word = false;
+    for (segment, width) in segments(s, cjk_width) {
+        if segment == " " {
+            space = true;
+            in_word = false;
+        } else if width > 1 {
+            words.push(Word {
+                space_before: space,
+                segments: vec![(segment, width)],
+            });
+            space = false;
+            in_word = false;
+        } else if in_word {
+            if let Some(word) = words.last_mut() {
+                word.segments.push((segment, width));
+            }
+        } else {
+            words.push(Word {
+                space_before: space,
+                segments: vec![(segment, width)],
+            });
+            space = false;
+            in_word = true;
+        }
+    }
+    words
+}
+
+/// Word-wrap a line to `width` columns.
+///
+/// Continuation lines keep the line's indentation unless it would take up
+/// more than half the width. Words longer than a line are split.
+fn wrap(line: &str, width: usize, cjk_width: usize) -> Vec<String> {
+    let indent = leading_spaces(line);
+    let continuation = if indent * 2 > width { 0 } else { indent };
+
+    let mut lines = Vec::new();
+    let mut current = " ".repeat(indent.min(width));
+    let mut current_width = indent.min(width);
+    let mut empty = true;
+
+    for word in words(&line[indent..], cjk_width) {
+        let separator = usize::from(!empty && word.space_before);
+        if !empty && current_width + separator + word.width() > width {
+            lines.push(current.trim_end().to_string());
+            current = " ".repeat(continuation);
+            current_width = continuation;
+            empty = true;
+        } else if separator == 1 {
+            current.push(' ');
+            current_width += 1;
+        }
+
+        for (segment, segment_width) in word.segments {
+            if !empty && current_width + segment_width > width {
+                lines.push(current.trim_end().to_string());
+                current = " ".repeat(continuation);
+                current_width = continuation;
+            }
+            current.push_str(segment);
+            current_width += segment_width;
+            empty = false;
+        }
+    }
+
+    lines.push(current.trim_end().to_string());
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::Parser;
+
+    #[test]
+    fn test_shrink_widths() {
+        let nodes = Parser::new(
+            "{{pad name 30}}{{pad count n}}{{#each items}}{{right size 10}}{{/each}}{{date at \"%Y\"}}",
+        )
+        .parse()
+        .unwrap();
+        let shrunk = shrink_nodes(&nodes, 80, 40);
+
+        assert_eq!(
+            shrunk[0],
+            Node::Pad {
+                variable: "name".to_string(),
+                width: "15".to_string()
+            }
+        );
+        // Variable widths are left alone
+        assert_eq!(shrunk[1], nodes[1]);
+        match &shrunk[2] {
+            Node::Each { body, .. } => assert_eq!(
+                body[0],
+                Node::Helper {
+                    name: "right".to_string(),
+                    args: vec!["size".to_string(), "5".to_string()]
+                }
+            ),
+            other => panic!("unexpected node {other:?}"),
+        }
+        assert_eq!(shrunk[3], nodes[3]);
+    }
+
+    #[test]
+    fn test_scale_rounds_and_keeps_one() {
+        assert_eq!(scale(4, 80, 40), 2);
+        assert_eq!(scale(5, 80, 40), 3);
+        assert_eq!(scale(1, 80, 40), 1);
+        assert_eq!(scale(0, 80, 40), 0);
+    }
+
+    #[test]
+    fn test_rules_are_cut() {
+        let text = format!(
+            "{}\n    {}\n{}\n",
+            "=".repeat(80),
+            "-".repeat(40),
+            "-".repeat(20)
+        );
+        assert_eq!(
+            reflow_text(&text, 80, 40, 2),
+            format!(
+                "{}\n  {}\n{}\n",
+                "=".repeat(40),
+                "-".repeat(38),
+                "-".repeat(20)
+            )
+        );
+    }
+
+    #[test]
+    fn test_frames_collapse() {
+        let text = format!(
+            "┌{}┐\n│  Main Menu{}│\n└{}┘\n",
+            "─".repeat(60),
+            " ".repeat(49),
+            "─".repeat(60)
+        );
+        // Box-drawing characters are two columns wide with CJK width 2
+        assert_eq!(
+            reflow_text(&text, 80, 40, 2),
+            format!("{}\n  Main Menu\n{}\n", "─".repeat(20), "─".repeat(20))
+        );
+        assert_eq!(
+            reflow_text("| Name | Size |\n|07Hi|\n", 80, 40, 1),
+            " Name | Size\n|07Hi|\n"
+        );
+    }
+
+    #[test]
+    fn test_short_lines_are_unchanged() {
+        let text = "    [B] Boards    - Read and post\n\nend";
+        assert_eq!(reflow_text(text, 80, 40, 2), text);
+    }
+
+    #[test]
+    fn test_spacing_shrinks_before_wrapping() {
+        let line = format!("    Main Menu{}User: taro", " ".repeat(26));
+        assert_eq!(
+            reflow_text(&line, 80, 40, 2),
+            format!("  Main Menu{}User: taro", " ".repeat(13))
+        );
+    }
+
+    #[test]
+    fn test_wrap_words() {
+        assert_eq!(
+            wrap(
+                "  The quick brown fox jumps over the lazy dog and keeps running",
+                20,
+                1
+            ),
+            vec![
+                "  The quick brown",
+                "  fox jumps over the",
+                "  lazy dog and keeps",
+                "  running"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wrap_cjk_and_long_words() {
+        assert_eq!(
+            wrap("これは長い日本語の文章です", 10, 2),
+            vec!["これは長い", "日本語の文", "章です"]
+        );
+        assert_eq!(wrap("abcdefghij", 4, 1), vec!["abcd", "efgh", "ij"]);
+    }
+
+    #[test]
+    fn test_markup_is_zero_width() {
+        assert_eq!(visible_width("^[[1;33mHi^[[0m |07x@X1Fy", 2), 5);
+        assert_eq!(
+            wrap("^[[1mbold^[[0m word", 5, 1),
+            vec!["^[[1mbold^[[0m", "word"]
+        );
+    }
+}
@@ -2,22 +2,73 @@
 //!
 //! Renders parsed template nodes with the given context.
 
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use super::expr;
+use super::helpers::{
+    align_center, align_right, as_integer, format_bytes, format_date, format_number,
+    truncate_with_ellipsis, DEFAULT_DATE_FORMAT,
+};
 use super::parser::Node;
-use super::{display_width, truncate_to_width, Result, TemplateContext, TemplateError, Value};
+use super::{pad_to_width, Result, TemplateContext, TemplateError, Value};
+
+/// Maximum nesting depth of partials and layouts.
+pub const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// Source of templates referenced by `{{> partial}}` and `{{extends}}`.
+pub trait PartialSource {
+    /// Load and parse the template with the given name.
+    fn load_partial(&self, name: &str) -> Result<Arc<Vec<Node>>>;
+}
 
 /// Template renderer.
 pub struct Renderer<'a> {
     context: &'a TemplateContext,
+    /// Where partials and layouts are loaded from.
+    partials: Option<&'a dyn PartialSource>,
+    /// Block overrides supplied by templates that extend a layout.
+    blocks: Rc<HashMap<String, Vec<Node>>>,
+    /// Names of the templates currently being rendered, outermost first.
+    stack: Vec<String>,
 }
 
 impl<'a> Renderer<'a> {
     /// Create a new renderer with the given context.
     pub fn new(context: &'a TemplateContext) -> Self {
-        Self { context }
+        Self {
+            context,
+            partials: None,
+            blocks: Rc::new(HashMap::new()),
+            stack: Vec::new(),
+        }
+    }
+
+    /// Resolve partials and layouts through the given source.
+    pub fn with_partials(mut self, source: &'a dyn PartialSource) -> Self {
+        self.partials = Some(source);
+        self
+    }
+
+    /// Render the nodes of a named template.
+    ///
+    /// The name is recorded so that a template including itself is reported
+    /// as a recursive include.
+    pub fn render_template(&self, name: &str, nodes: &[Node]) -> Result<String> {
+        self.enter(name, self.context, self.blocks.clone())?
+            .render(nodes)
     }
 
     /// Render a list of nodes to a string.
     pub fn render(&self, nodes: &[Node]) -> Result<String> {
+        if let Some(layout) = nodes.iter().find_map(|node| match node {
+            Node::Extends(layout) => Some(layout),
+            _ => None,
+        }) {
+            return self.render_layout(layout, nodes);
+        }
+
         let mut output = String::new();
 
         for node in nodes {
@@ -27,6 +78,62 @@ impl<'a> Renderer<'a> {
         Ok(output)
     }
 
+    /// Create a renderer for a nested scope sharing partials, blocks and
+    /// the include stack.
+    fn scoped<'b>(&self, context: &'b TemplateContext) -> Renderer<'b>
+    where
+        'a: 'b,
+    {
+        Renderer {
+            context,
+            partials: self.partials,
+            blocks: self.blocks.clone(),
+            stack: self.stack.clone(),
+        }
+    }
+
+    /// Create a renderer for an included template, checking for recursion.
+    fn enter<'b>(
+        &self,
+        name: &str,
+        context: &'b TemplateContext,
+        blocks: Rc<HashMap<String, Vec<Node>>>,
+    ) -> Result<Renderer<'b>>
+    where
+        'a: 'b,
+    {
+        if self.stack.iter().any(|n| n == name) {
+            let chain = self.stack.join(" -> ");
+            return Err(TemplateError::Render(format!(
+                "Recursive template include: {chain} -> {name}"
+            )));
+        }
+        if self.stack.len() >= MAX_INCLUDE_DEPTH {
+            return Err(TemplateError::Render(format!(
+                "Template include depth exceeds {MAX_INCLUDE_DEPTH} at '{name}'"
+            )));
+        }
+
+        let mut stack = self.stack.clone();
+        stack.push(name.to_string());
+        Ok(Renderer {
+            context,
+            partials: self.partials,
+            blocks,
+            stack,
+        })
+    }
+
+    /// Load a partial or layout from the configured source.
+    fn load(&self, name: &str) -> Result<Arc<Vec<Node>>> {
+        match self.partials {
+            Some(source) => source.load_partial(name),
+            None => Err(TemplateError::Render(format!(
+                "Cannot include '{name}': no partial source"
+            ))),
+        }
+    }
+
     /// Render a single node.
     fn render_node(&self, node: &Node) -> Result<String> {
         match node {
@@ -46,6 +153,11 @@ impl<'a> Renderer<'a> {
             Node::Unless { condition, body } => self.render_unless(condition, body),
             Node::With { variable, body } => self.render_with(variable, body),
             Node::Pad { variable, width } => self.render_pad(variable, width),
+            Node::Helper { name, args } => self.render_helper(name, args),
+            Node::Partial { name, params } => self.render_partial(name, params),
+            Node::Block { name, body } => self.render_block(name, body),
+            // Handled by `render` before individual nodes are visited.
+            Node::Extends(_) => Ok(String::new()),
         }
     }
 
@@ -70,19 +182,7 @@ impl<'a> Renderer<'a> {
             // Build parameter list, resolving variable references
             let resolved_params: Vec<(&str, String)> = params
                 .iter()
-                .map(|(name, value)| {
-                    let resolved = if value.starts_with('"') && value.ends_with('"') {
-                        // Literal string - strip the quotes
-                        value[1..value.len() - 1].to_string()
-                    } else {
-                        // Variable reference
-                        self.context
-                            .get(value)
-                            .map(|v| v.to_display_string())
-                            .unwrap_or_default()
-                    };
-                    (name.as_str(), resolved)
-                })
+                .map(|(name, value)| (name.as_str(), self.resolve_param(value)))
                 .collect();
 
             let param_refs: Vec<(&str, &str)> = resolved_params
@@ -94,6 +194,123 @@ impl<'a> Renderer<'a> {
         }
     }
 
+    /// Resolve a parameter value: quoted literals lose their quotes,
+    /// anything else is looked up as a variable.
+    fn resolve_param(&self, value: &str) -> String {
+        if value.starts_with('"') && value.ends_with('"') {
+            // Literal string - strip the quotes
+            value[1..value.len() - 1].to_string()
+        } else {
+            // Variable reference
+            self.context
+                .get(value)
+                .map(|v| v.to_display_string())
+                .unwrap_or_default()
+        }
+    }
+
+    /// Render a partial include.
+    ///
+    /// The partial sees the current context plus any parameters passed to it.
+    fn render_partial(&self, name: &str, params: &[(String, String)]) -> Result<String> {
+        let nodes = self.load(name)?;
+
+        if params.is_empty() {
+            return self
+                .enter(name, self.context, self.blocks.clone())?
+                .render(&nodes);
+        }
+
+        let mut child_context = self.context.child();
+        for (param, value) in params {
+            child_context.set(param.clone(), Value::String(self.resolve_param(value)));
+        }
+        self.enter(name, &child_context, self.blocks.clone())?
+            .render(&nodes)
+    }
+
+    /// Render a named block, preferring an override from an extending template.
+    fn render_block(&self, name: &str, body: &[Node]) -> Result<String> {
+        match self.blocks.get(name) {
+            Some(override_body) => self.render(override_body),
+            None => self.render(body),
+        }
+    }
+
+    /// Render a template that extends a layout.
+    ///
+    /// Top-level blocks of the template replace the layout's blocks of the
+    /// same name; everything else in the template is ignored. Overrides from
+    /// templates further down the chain take precedence.
+    fn render_layout(&self, layout: &str, nodes: &[Node]) -> Result<String> {
+        let mut blocks = (*self.blocks).clone();
+        for node in nodes {
+            if let Node::Block { name, body } = node {
+                blocks.entry(name.clone()).or_insert_with(|| body.clone());
+            }
+        }
+
+        let layout_nodes = self.load(layout)?;
+        self.enter(layout, self.context, Rc::new(blocks))?
+            .render(&layout_nodes)
+    }
+
+    /// Evaluate a condition expression.
+    fn evaluate(&self, condition: &str) -> Result<bool> {
+        let expr = expr::parse(condition)?;
+        Ok(expr::evaluate(&expr, self.context))
+    }
+
+    /// Render a formatting helper.
+    fn render_helper(&self, name: &str, args: &[String]) -> Result<String> {
+        let value = match args.first() {
+            Some(arg) if arg.starts_with('"') && arg.ends_with('"') => {
+                Value::String(arg[1..arg.len() - 1].to_string())
+            }
+            Some(arg) => self.context.get(arg).cloned().unwrap_or(Value::Null),
+            None => {
+                return Err(TemplateError::Render(format!(
+                    "Helper '{name}' needs an argument"
+                )));
+            }
+        };
+        let text = value.to_display_string();
+        let cjk_width = self.context.cjk_width();
+
+        let width = || -> Result<usize> {
+            let arg = args
+                .get(1)
+                .ok_or_else(|| TemplateError::Render(format!("Helper '{name}' needs a width")))?;
+            // A literal width, or a variable holding one
+            let width = if arg.chars().all(|c| c.is_ascii_digit()) {
+                arg.clone()
+            } else {
+                self.resolve_param(arg)
+            };
+            width
+                .parse()
+                .map_err(|_| TemplateError::Render(format!("Invalid {name} width: {arg}")))
+        };
+
+        Ok(match name {
+            "date" => {
+                let format = args
+                    .get(1)
+                    .map(|arg| self.resolve_param(arg))
+                    .unwrap_or_else(|| DEFAULT_DATE_FORMAT.to_string());
+                format_date(&value, self.context.timezone(), &format)
+            }
+            "number" => as_integer(&value).map(format_number).unwrap_or(text),
+            "bytes" => as_integer(&value).map(format_bytes).unwrap_or(text),
+            "truncate" => truncate_with_ellipsis(&text, width()?, cjk_width),
+            "upper" => text.to_uppercase(),
+            "lower" => text.to_lowercase(),
+            "right" => align_right(&text, width()?, cjk_width),
+            "center" => align_center(&text, width()?, cjk_width),
+            _ => return Err(TemplateError::Render(format!("Unknown helper: {name}"))),
+        })
+    }
+
     /// Render an if block.
     fn render_if(
         &self,
@@ -101,13 +318,7 @@ impl<'a> Renderer<'a> {
         then_branch: &[Node],
         else_branch: &[Node],
     ) -> Result<String> {
-        let is_truthy = self
-            .context
-            .get(condition)
-            .map(|v| v.is_truthy())
-            .unwrap_or(false);
-
-        if is_truthy {
+        if self.evaluate(condition)? {
             self.render(then_branch)
         } else {
             self.render(else_branch)
@@ -147,7 +358,7 @@ impl<'a> Renderer<'a> {
                 }
             }
 
-            let child_renderer = Renderer::new(&child_context);
+            let child_renderer = self.scoped(&child_context);
             output.push_str(&child_renderer.render(body)?);
         }
 
@@ -156,13 +367,7 @@ impl<'a> Renderer<'a> {
 
     /// Render an unless block.
     fn render_unless(&self, condition: &str, body: &[Node]) -> Result<String> {
-        let is_truthy = self
-            .context
-            .get(condition)
-            .map(|v| v.is_truthy())
-            .unwrap_or(false);
-
-        if !is_truthy {
+        if !self.evaluate(condition)? {
             self.render(body)
         } else {
             Ok(String::new())
@@ -188,24 +393,7 @@ impl<'a> Renderer<'a> {
             TemplateError::Render(format!("Invalid pad width: {width_str}"))
         })?;
 
-        if target_width == 0 {
-            return Ok(String::new());
-        }
-
-        let cjk_width = self.context.cjk_width();
-        let display_w = display_width(&text, cjk_width);
-
-        if display_w <= target_width {
-            // Pad with spaces
-            let padding = target_width - display_w;
-            Ok(format!("{}{}", text, " ".repeat(padding)))
-        } else {
-            // Truncate to (target_width - 1) and append '~'
-            let truncated = truncate_to_width(&text, target_width - 1, cjk_width);
-            let truncated_w = display_width(&truncated, cjk_width);
-            let padding = target_width - 1 - truncated_w;
-            Ok(format!("{}{}~", truncated, " ".repeat(padding)))
-        }
+        Ok(pad_to_width(&text, target_width, self.context.cjk_width()))
     }
 
     /// Render a with block.
@@ -227,7 +415,7 @@ impl<'a> Renderer<'a> {
         // Also expose the value as "this"
         child_context.set("this", value);
 
-        let child_renderer = Renderer::new(&child_context);
+        let child_renderer = self.scoped(&child_context);
         child_renderer.render(body)
     }
 }
@@ -793,4 +981,296 @@ message = "こんにちは、{{name}}さん"
 
         assert_eq!(result, "[FIRST]a b c[LAST] ");
     }
+
+    /// In-memory partial source for tests.
+    struct MapSource(HashMap<&'static str, &'static str>);
+
+    impl PartialSource for MapSource {
+        fn load_partial(&self, name: &str) -> Result<Arc<Vec<Node>>> {
+            let content = self
+                .0
+                .get(name)
+                .ok_or_else(|| TemplateError::NotFound(name.to_string()))?;
+            crate::template::Parser::new(content).parse().map(Arc::new)
+        }
+    }
+
+    fn render_with_source(
+        context: &TemplateContext,
+        source: &MapSource,
+        template: &str,
+    ) -> Result<String> {
+        let nodes = crate::template::Parser::new(template).parse()?;
+        Renderer::new(context)
+            .with_partials(source)
+            .render_template("main", &nodes)
+    }
+
+    #[test]
+    fn test_render_partial() {
+        let mut context = create_context();
+        context.set("name", Value::String("Alice".to_string()));
+        let source = MapSource(HashMap::from([("greet", "Hi {{name}}\n")]));
+
+        let result = render_with_source(&context, &source, "{{> greet}}\nBye\n").unwrap();
+
+        assert_eq!(result, "Hi Alice\nBye\n");
+    }
+
+    #[test]
+    fn test_render_partial_with_params() {
+        let mut context = create_context();
+        context.set("user", Value::String("Bob".to_string()));
+        let source = MapSource(HashMap::from([("title", "[{{label}}:{{who}}]")]));
+
+        let result =
+            render_with_source(&context, &source, r#"{{> title label="Top" who=user}}"#).unwrap();
+
+        assert_eq!(result, "[Top:Bob]");
+    }
+
+    #[test]
+    fn test_render_partial_in_each() {
+        let mut context = create_context();
+        context.set(
+            "items",
+            Value::List(vec![Value::string("a"), Value::string("b")]),
+        );
+        let source = MapSource(HashMap::from([("item", "<{{this}}>")]));
+
+        let result = render_with_source(&context, &source, "{{#each items}}{{> item}}{{/each}}");
+
+        assert_eq!(result.unwrap(), "<a><b>");
+    }
+
+    #[test]
+    fn test_render_partial_recursion_detected() {
+        let context = create_context();
+        let source = MapSource(HashMap::from([("a", "{{> b}}"), ("b", "{{> a}}")]));
+
+        let result = render_with_source(&context, &source, "{{> a}}");
+
+        match result {
+            Err(TemplateError::Render(msg)) => assert!(msg.contains("main -> a -> b -> a")),
+            other => panic!("expected recursion error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_render_partial_self_include() {
+        let context = create_context();
+        let source = MapSource(HashMap::from([("main", "loop")]));
+
+        let result = render_with_source(&context, &source, "{{> main}}");
+
+        assert!(matches!(result, Err(TemplateError::Render(_))));
+    }
+
+    #[test]
+    fn test_render_partial_missing() {
+        let context = create_context();
+        let source = MapSource(HashMap::new());
+
+        let result = render_with_source(&context, &source, "{{> nothing}}");
+
+        assert!(matches!(result, Err(TemplateError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_render_partial_without_source() {
+        let context = create_context();
+        let renderer = Renderer::new(&context);
+
+        let nodes = vec![Node::Partial {
+            name: "header".to_string(),
+            params: vec![],
+        }];
+
+        assert!(matches!(
+            renderer.render(&nodes),
+            Err(TemplateError::Render(_))
+        ));
+    }
+
+    #[test]
+    fn test_render_block_default() {
+        let context = create_context();
+        let renderer = Renderer::new(&context);
+
+        let nodes = vec![Node::Block {
+            name: "title".to_string(),
+            body: vec![Node::Text("Default".to_string())],
+        }];
+
+        assert_eq!(renderer.render(&nodes).unwrap(), "Default");
+    }
+
+    #[test]
+    fn test_render_layout() {
+        let mut context = create_context();
+        context.set("name", Value::String("Board".to_string()));
+        let layout = "{{#block header}}\n=== {{#block title}}{{/block}} ===\n{{/block}}\n{{#block content}}\n(empty)\n{{/block}}\n-- end --\n";
+        let source = MapSource(HashMap::from([("layout", layout)]));
+
+        let child = "{{extends \"layout\"}}\nignored\n{{#block title}}{{name}}{{/block}}\n";
+        let result = render_with_source(&context, &source, child).unwrap();
+        assert_eq!(result, "=== Board ===\n(empty)\n-- end --\n");
+
+        let child = "{{extends \"layout\"}}\n{{#block header}}\n** top **\n{{/block}}\n{{#block content}}\nbody\n{{/block}}\n";
+        let result = render_with_source(&context, &source, child).unwrap();
+        assert_eq!(result, "** top **\nbody\n-- end --\n");
+    }
+
+    #[test]
+    fn test_render_layout_chain() {
+        let context = create_context();
+        let source = MapSource(HashMap::from([
+            (
+                "base",
+                "<{{#block a}}base-a{{/block}}|{{#block b}}base-b{{/block}}>",
+            ),
+            (
+                "middle",
+                "{{extends \"base\"}}{{#block a}}mid-a{{/block}}{{#block b}}mid-b{{/block}}",
+            ),
+        ]));
+
+        let result = render_with_source(
+            &context,
+            &source,
+            "{{extends \"middle\"}}{{#block b}}child-b{{/block}}",
+        );
+
+        assert_eq!(result.unwrap(), "<mid-a|child-b>");
+    }
+
+    #[test]
+    fn test_render_layout_recursion_detected() {
+        let context = create_context();
+        let source = MapSource(HashMap::from([("layout", "{{extends \"layout\"}}")]));
+
+        let result = render_with_source(&context, &source, "{{extends \"layout\"}}");
+
+        assert!(matches!(result, Err(TemplateError::Render(_))));
+    }
+
+    fn render_str(context: &TemplateContext, template: &str) -> String {
+        let nodes = crate::template::Parser::new(template).parse().unwrap();
+        Renderer::new(context).render(&nodes).unwrap()
+    }
+
+    #[test]
+    fn test_render_if_comparison() {
+        let mut context = create_context();
+        context.set("unread", Value::Number(2));
+        context.set("role", Value::string("subop"));
+
+        assert_eq!(render_str(&context, "{{#if unread > 0}}new{{/if}}"), "new");
+        assert_eq!(
+            render_str(&context, "{{#if unread > 5}}many{{else}}few{{/if}}"),
+            "few"
+        );
+        assert_eq!(
+            render_str(
+                &context,
+                r#"{{#if role == "sysop" || role == "subop"}}staff{{/if}}"#
+            ),
+            "staff"
+        );
+        assert_eq!(
+            render_str(&context, "{{#unless unread == 0}}has mail{{/unless}}"),
+            "has mail"
+        );
+    }
+
+    #[test]
+    fn test_render_each_loop_variables() {
+        let mut context = create_context();
+        context.set(
+            "items",
+            Value::List(vec![
+                Value::string("a"),
+                Value::string("b"),
+                Value::string("c"),
+            ]),
+        );
+
+        let result = render_str(
+            &context,
+            "{{#each items}}{{@index}}:{{this}}{{#if !@last}}, {{/if}}{{/each}}",
+        );
+        assert_eq!(result, "0:a, 1:b, 2:c");
+
+        let result = render_str(
+            &context,
+            "{{#each items}}{{#if @index >= 1 && !@last}}[{{this}}]{{/if}}{{/each}}",
+        );
+        assert_eq!(result, "[b]");
+    }
+
+    #[test]
+    fn test_render_helpers() {
+        let mut context = create_context();
+        context.set("count", Value::Number(1234567));
+        context.set("size", Value::Number(2048));
+        context.set("name", Value::string("Taro"));
+        context.set("title", Value::string("こんにちは世界"));
+
+        assert_eq!(render_str(&context, "{{number count}}"), "1,234,567");
+        assert_eq!(render_str(&context, "{{bytes size}}"), "2.0 KB");
+        assert_eq!(render_str(&context, "{{upper name}}"), "TARO");
+        assert_eq!(render_str(&context, "{{lower name}}"), "taro");
+        assert_eq!(render_str(&context, "[{{right name 6}}]"), "[  Taro]");
+        assert_eq!(render_str(&context, "[{{center name 8}}]"), "[  Taro  ]");
+        assert_eq!(render_str(&context, "{{truncate title 8}}"), "こん...");
+        assert_eq!(render_str(&context, r#"{{upper "abc"}}"#), "ABC");
+    }
+
+    #[test]
+    fn test_render_helper_cjk_width() {
+        let mut context = create_context();
+        context.set_cjk_width(1);
+        context.set("title", Value::string("こんにちは世界"));
+
+        assert_eq!(render_str(&context, "{{truncate title 6}}"), "こんに...");
+        assert_eq!(
+            render_str(&context, "[{{right title 8}}]"),
+            "[ こんにちは世界]"
+        );
+    }
+
+    #[test]
+    fn test_render_date_helper_timezone() {
+        let mut context = create_context();
+        context.set("created_at", Value::string("2025-01-15 12:30:00"));
+
+        assert_eq!(
+            render_str(&context, "{{date created_at}}"),
+            "2025/01/15 12:30"
+        );
+
+        context.set_timezone("Asia/Tokyo");
+        assert_eq!(
+            render_str(&context, "{{date created_at}}"),
+            "2025/01/15 21:30"
+        );
+        assert_eq!(
+            render_str(&context, r#"{{date created_at "%H:%M"}}"#),
+            "21:30"
+        );
+    }
+
+    #[test]
+    fn test_render_helper_missing_width() {
+        let mut context = create_context();
+        context.set("name", Value::string("Taro"));
+        let nodes = crate::template::Parser::new("{{right name}}")
+            .parse()
+            .unwrap();
+
+        assert!(matches!(
+            Renderer::new(&context).render(&nodes),
+            Err(TemplateError::Render(_))
+        ));
+    }
 }
@@ -0,0 +1,368 @@
+//! Template validation.
+//!
+//! Parses every template file so that mistakes are reported at startup
+//! (or by `hobbs check-templates`) instead of when a caller first opens the
+//! screen.
+
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+
+use super::parser::line_column;
+use super::{Node, Parser, Result, TemplateLoader, WIDTH_40, WIDTH_80};
+use crate::i18n::I18n;
+
+/// Kind of problem found in a template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueKind {
+    /// The template could not be parsed.
+    Syntax,
+    /// `{{t "key"}}` (or `{{pad "key" n}}`) refers to a key missing from a locale.
+    UnknownTranslation,
+    /// A partial or layout that does not exist.
+    MissingTemplate,
+}
+
+/// A problem found in a template file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateIssue {
+    /// Path of the template file.
+    pub path: PathBuf,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column number.
+    pub column: usize,
+    /// Kind of problem.
+    pub kind: IssueKind,
+    /// Description of the problem.
+    pub message: String,
+}
+
+impl TemplateIssue {
+    /// Whether the template cannot be rendered at all.
+    pub fn is_error(&self) -> bool {
+        self.kind == IssueKind::Syntax
+    }
+}
+
+impl fmt::Display for TemplateIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}",
+            self.path.display(),
+            self.line,
+            self.column,
+            self.message
+        )
+    }
+}
+
+/// Validate every template for both widths.
+///
+/// Translation keys are checked against each of `locales`; a key missing
+/// from any of them is reported once with the locales that lack it.
+/// Locale overrides (`80/ja/...`) are checked only against their own
+/// locale when it is among `locales`.
+pub fn validate_templates(
+    loader: &TemplateLoader,
+    locales: &[&I18n],
+) -> Result<Vec<TemplateIssue>> {
+    let mut issues = Vec::new();
+
+    for width in [WIDTH_80, WIDTH_40] {
+        for name in loader.list_templates(width)? {
+            let path = loader.get_template_path(&name, width);
+            issues.extend(validate_file(loader, locales, None, path));
+        }
+
+        for locale in loader.list_locales(width)? {
+            let own: Vec<&I18n> = locales
+                .iter()
+                .copied()
+                .filter(|i18n| i18n.locale() == locale)
+                .collect();
+            let checked = if own.is_empty() { locales } else { &own };
+            for name in loader.list_locale_templates(width, &locale)? {
+                let path = loader.get_locale_template_path(&name, width, &locale);
+                issues.extend(validate_file(loader, checked, Some(&locale), path));
+            }
+        }
+    }
+
+    Ok(issues)
+}
+
+/// Read and validate a single template file.
+fn validate_file(
+    loader: &TemplateLoader,
+    locales: &[&I18n],
+    locale: Option<&str>,
+    path: PathBuf,
+) -> Vec<TemplateIssue> {
+    match fs::read_to_string(&path) {
+        Ok(content) => validate_content(loader, locales, locale, path, &content),
+        Err(e) => vec![TemplateIssue {
+            path,
+            line: 1,
+            column: 1,
+            kind: IssueKind::Syntax,
+            message: format!("cannot read file: {e}"),
+        }],
+    }
+}
+
+/// Validate the source of a single template.
+fn validate_content(
+    loader: &TemplateLoader,
+    locales: &[&I18n],
+    locale: Option<&str>,
+    path: PathBuf,
+    content: &str,
+) -> Vec<TemplateIssue> {
+    let nodes = match Parser::new(content).parse_checked() {
+        Ok(nodes) => nodes,
+        Err(err) => {
+            return vec![TemplateIssue {
+                path,
+                line: err.line,
+                column: err.column,
+                kind: IssueKind::Syntax,
+                message: err.message,
+            }];
+        }
+    };
+
+    let mut refs = Vec::new();
+    collect_references(&nodes, &mut refs);
+
+    let mut issues = Vec::new();
+    for reference in refs {
+        let (kind, message, needle) = match reference {
+            Reference::Translation(key) => {
+                let missing: Vec<&str> = locales
+                    .iter()
+                    .filter(|i18n| !i18n.has_key(key))
+                    .map(|i18n| i18n.locale())
+                    .collect();
+                if missing.is_empty() {
+                    continue;
+                }
+                (
+                    IssueKind::UnknownTranslation,
+                    format!(
+                        "unknown translation key '{key}' (missing in: {})",
+                        missing.join(", ")
+                    ),
+                    format!("\"{key}\""),
+                )
+            }
+            Reference::Template(name) => {
+                let found = match locale {
+                    Some(locale) => loader.has_template_for_locale(name, locale),
+                    None => loader.has_template_any(name),
+                };
+                if found {
+                    continue;
+                }
+                (
+                    IssueKind::MissingTemplate,
+                    format!("template '{name}' not found"),
+                    name.to_string(),
+                )
+            }
+        };
+
+        // Nodes carry no positions; point at the first use of the name.
+        let (line, column) = content
+            .find(&needle)
+            .map_or((1, 1), |pos| line_column(content, pos));
+        issues.push(TemplateIssue {
+            path: path.clone(),
+            line,
+            column,
+            kind,
+            message,
+        });
+    }
+
+    issues
+}
+
+/// A name referenced by a template.
+#[derive(Debug, PartialEq, Eq)]
+enum Reference<'a> {
+    Translation(&'a str),
+    Template(&'a str),
+}
+
+/// Collect translation keys and included templates, without duplicates.
+fn collect_references<'a>(nodes: &'a [Node], refs: &mut Vec<Reference<'a>>) {
+    fn push<'a>(reference: Reference<'a>, refs: &mut Vec<Reference<'a>>) {
+        if !refs.contains(&reference) {
+            refs.push(reference);
+        }
+    }
+
+    for node in nodes {
+        match node {
+            Node::Translation { key, .. } => push(Reference::Translation(key), refs),
+            Node::Pad { variable, .. } if variable.starts_with('"') && variable.len() >= 2 => {
+                push(
+                    Reference::Translation(&variable[1..variable.len() - 1]),
+                    refs,
+                );
+            }
+            Node::Partial { name, .. } | Node::Extends(name) => {
+                push(Reference::Template(name), refs);
+            }
+            Node::If {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                collect_references(then_branch, refs);
+                collect_references(else_branch, refs);
+            }
+            Node::Each { body, .. }
+            | Node::Unless { body, .. }
+            | Node::With { body, .. }
+            | Node::Block { body, .. } => collect_references(body, refs),
+            Node::Text(_) | Node::Variable(_) | Node::Pad { .. } | Node::Helper { .. } => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, name: &str, content: &str) {
+        let path = dir.join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn locales() -> (I18n, I18n) {
+        let ja = I18n::from_str("ja", "[menu]\nmain = \"メイン\"\nboard = \"掲示板\"\n").unwrap();
+        let en = I18n::from_str("en", "[menu]\nmain = \"Main\"\n").unwrap();
+        (ja, en)
+    }
+
+    #[test]
+    fn test_valid_templates() {
+        let temp_dir = TempDir::new().unwrap();
+        write(
+            temp_dir.path(),
+            "80/main.txt",
+            "{{t \"menu.main\"}}\n{{> part}}\n",
+        );
+        write(temp_dir.path(), "80/part.txt", "{{name}}\n");
+        let loader = TemplateLoader::new(temp_dir.path());
+        let (ja, en) = locales();
+
+        let issues = validate_templates(&loader, &[&ja, &en]).unwrap();
+        assert!(issues.is_empty(), "{issues:?}");
+    }
+
+    #[test]
+    fn test_syntax_error_position() {
+        let temp_dir = TempDir::new().unwrap();
+        write(temp_dir.path(), "40/bad.txt", "ok\n{{#if a}}\n{{/each}}\n");
+        let loader = TemplateLoader::new(temp_dir.path());
+
+        let issues = validate_templates(&loader, &[]).unwrap();
+        assert_eq!(issues.len(), 1);
+        let issue = &issues[0];
+        assert_eq!(issue.kind, IssueKind::Syntax);
+        assert!(issue.is_error());
+        assert!(issue.path.ends_with("40/bad.txt"));
+        assert_eq!((issue.line, issue.column), (3, 1));
+        assert!(issue.message.contains("{{/each}}"));
+    }
+
+    #[test]
+    fn test_unknown_translation_keys() {
+        let temp_dir = TempDir::new().unwrap();
+        write(
+            temp_dir.path(),
+            "80/menu.txt",
+            "{{t \"menu.main\"}}\n  {{t \"menu.board\"}}\n{{pad \"menu.nope\" 4}}\n",
+        );
+        let loader = TemplateLoader::new(temp_dir.path());
+        let (ja, en) = locales();
+
+        let issues = validate_templates(&loader, &[&ja, &en]).unwrap();
+        assert_eq!(issues.len(), 2);
+
+        assert_eq!(issues[0].kind, IssueKind::UnknownTranslation);
+        assert_eq!((issues[0].line, issues[0].column), (2, 7));
+        assert!(issues[0].message.contains("missing in: en"));
+
+        assert!(issues[1].message.contains("menu.nope"));
+        assert!(issues[1].message.contains("missing in: ja, en"));
+        assert!(!issues[1].is_error());
+    }
+
+    #[test]
+    fn test_missing_partial_and_layout() {
+        let temp_dir = TempDir::new().unwrap();
+        write(
+            temp_dir.path(),
+            "80/page.txt",
+            "{{extends \"layouts/none\"}}\n{{#block content}}{{> partials/gone}}{{/block}}\n",
+        );
+        let loader = TemplateLoader::new(temp_dir.path());
+
+        let issues = validate_templates(&loader, &[]).unwrap();
+        assert_eq!(issues.len(), 2);
+        assert!(issues
+            .iter()
+            .all(|issue| issue.kind == IssueKind::MissingTemplate));
+        assert_eq!((issues[1].line, issues[1].column), (2, 23));
+    }
+
+    #[test]
+    fn test_locale_overrides_are_validated() {
+        let temp_dir = TempDir::new().unwrap();
+        write(temp_dir.path(), "80/main.txt", "{{t \"menu.main\"}}\n");
+        // Only checked against ja, which has the key
+        write(temp_dir.path(), "80/ja/main.txt", "{{t \"menu.board\"}}\n");
+        // Partials may live in the override directory
+        write(
+            temp_dir.path(),
+            "80/ja/page.txt",
+            "{{> local}}\n{{> main}}\n",
+        );
+        write(temp_dir.path(), "80/ja/local.txt", "x\n");
+        write(temp_dir.path(), "80/en/bad.txt", "{{#if a}}\n");
+        let loader = TemplateLoader::new(temp_dir.path());
+        let (ja, en) = locales();
+
+        let issues = validate_templates(&loader, &[&ja, &en]).unwrap();
+        assert_eq!(issues.len(), 1, "{issues:?}");
+        assert_eq!(issues[0].kind, IssueKind::Syntax);
+        assert!(issues[0].path.ends_with("80/en/bad.txt"));
+    }
+
+    #[test]
+    fn test_repository_templates_are_valid() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let loader = TemplateLoader::new(root.join("templates"));
+        let ja = I18n::load("ja", root.join("locales")).unwrap();
+        let en = I18n::load("en", root.join("locales")).unwrap();
+
+        let issues = validate_templates(&loader, &[&ja, &en]).unwrap();
+        assert!(
+            issues.is_empty(),
+            "{}",
+            issues
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+}
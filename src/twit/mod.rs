@@ -0,0 +1,18 @@
+//! Twit filters (kill files) for HOBBS.
+//!
+//! Users can hide content they do not want to see:
+//! - Everything written by a given user
+//! - Posts, threads and mail whose title or body contains a keyword, and
+//!   chat lines containing it
+//!
+//! Hidden content is left out of thread and post lists, unread scans and
+//! counts, the mail inbox and chat. Telnet screens and the web API can show
+//! it again on request.
+
+pub mod repository;
+pub mod service;
+pub mod types;
+
+pub use repository::TwitFilterRepository;
+pub use service::{TwitFilterService, MAX_KEYWORD_LENGTH, MAX_TWIT_FILTERS};
+pub use types::{KillFile, TwitFilter, TwitTarget};
@@ -0,0 +1,93 @@
+//! Twit filter repository for HOBBS.
+
+use super::types::TwitFilter;
+use crate::db::DbPool;
+use crate::{HobbsError, Result};
+
+const FILTER_SELECT: &str =
+    "SELECT f.id, f.user_id, f.target_user_id, u.username AS target_username,
+            f.keyword, f.created_at
+     FROM twit_filters f
+     LEFT JOIN users u ON u.id = f.target_user_id";
+
+/// Repository for users' twit filters.
+pub struct TwitFilterRepository<'a> {
+    pool: &'a DbPool,
+}
+
+impl<'a> TwitFilterRepository<'a> {
+    /// Create a new TwitFilterRepository with the given database pool.
+    pub fn new(pool: &'a DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Add a filter hiding another user.
+    pub async fn add_user(&self, user_id: i64, target_user_id: i64) -> Result<i64> {
+        sqlx::query_scalar(
+            "INSERT INTO twit_filters (user_id, target_user_id) VALUES ($1, $2) RETURNING id",
+        )
+        .bind(user_id)
+        .bind(target_user_id)
+        .fetch_one(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// Add a filter hiding a keyword.
+    pub async fn add_keyword(&self, user_id: i64, keyword: &str) -> Result<i64> {
+        sqlx::query_scalar(
+            "INSERT INTO twit_filters (user_id, keyword) VALUES ($1, $2) RETURNING id",
+        )
+        .bind(user_id)
+        .bind(keyword)
+        .fetch_one(self.pool)
+        .await
+        .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// Get a filter by ID.
+    pub async fn get(&self, id: i64) -> Result<Option<TwitFilter>> {
+        let query = format!("{FILTER_SELECT} WHERE f.id = $1");
+        sqlx::query_as(&query)
+            .bind(id)
+            .fetch_optional(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// List a user's filters, users first, then keywords, in the order added.
+    pub async fn list(&self, user_id: i64) -> Result<Vec<TwitFilter>> {
+        let query = format!(
+            "{FILTER_SELECT} WHERE f.user_id = $1
+             ORDER BY CASE WHEN f.target_user_id IS NULL THEN 1 ELSE 0 END, f.id"
+        );
+        sqlx::query_as(&query)
+            .bind(user_id)
+            .fetch_all(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// Count a user's filters.
+    pub async fn count(&self, user_id: i64) -> Result<i64> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM twit_filters WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_one(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))
+    }
+
+    /// Delete one of a user's filters.
+    ///
+    /// Returns false if the user has no filter with that ID.
+    pub async fn delete(&self, user_id: i64, id: i64) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM twit_filters WHERE id = $1 AND user_id = $2")
+            .bind(id)
+            .bind(user_id)
+            .execute(self.pool)
+            .await
+            .map_err(|e| HobbsError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
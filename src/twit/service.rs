@@ -0,0 +1,197 @@
+//! Twit filter service for HOBBS.
+//!
+//! Each user keeps a list of authors and keywords they do not want to see.
+//! Hidden content is left out of board lists, unread scans and counts, the
+//! mail inbox and chat, unless the user asks to show it.
+
+use super::repository::TwitFilterRepository;
+use super::types::{KillFile, TwitFilter};
+use crate::db::{DbPool, UserRepository};
+use crate::{HobbsError, Result};
+
+/// Maximum number of filters per user.
+pub const MAX_TWIT_FILTERS: i64 = 100;
+
+/// Maximum length of a keyword in characters.
+pub const MAX_KEYWORD_LENGTH: usize = 50;
+
+/// Service for users' twit filters.
+pub struct TwitFilterService<'a> {
+    pool: &'a DbPool,
+}
+
+impl<'a> TwitFilterService<'a> {
+    /// Create a new TwitFilterService.
+    pub fn new(pool: &'a DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// List a user's filters.
+    pub async fn list(&self, user_id: i64) -> Result<Vec<TwitFilter>> {
+        TwitFilterRepository::new(self.pool).list(user_id).await
+    }
+
+    /// Hide everything written by a user, given by username.
+    pub async fn add_user(&self, user_id: i64, username: &str) -> Result<TwitFilter> {
+        let target = UserRepository::new(self.pool)
+            .get_by_username(username.trim())
+            .await?
+            .ok_or_else(|| HobbsError::NotFound("ユーザー".to_string()))?;
+        if target.id == user_id {
+            return Err(HobbsError::Validation(
+                "自分自身は非表示にできません".to_string(),
+            ));
+        }
+
+        let repo = TwitFilterRepository::new(self.pool);
+        let filters = repo.list(user_id).await?;
+        if filters.iter().any(|f| f.target_user_id == Some(target.id)) {
+            return Err(HobbsError::Validation(
+                "このユーザーは既に非表示にしています".to_string(),
+            ));
+        }
+        self.check_limit(filters.len())?;
+
+        let id = repo.add_user(user_id, target.id).await?;
+        self.get(id).await
+    }
+
+    /// Hide content whose title or body contains a keyword.
+    pub async fn add_keyword(&self, user_id: i64, keyword: &str) -> Result<TwitFilter> {
+        let keyword = keyword.trim();
+        if keyword.is_empty() {
+            return Err(HobbsError::Validation(
+                "キーワードを入力してください".to_string(),
+            ));
+        }
+        if keyword.chars().count() > MAX_KEYWORD_LENGTH {
+            return Err(HobbsError::Validation(format!(
+                "キーワードは{MAX_KEYWORD_LENGTH}文字以内にしてください"
+            )));
+        }
+
+        let repo = TwitFilterRepository::new(self.pool);
+        let filters = repo.list(user_id).await?;
+        if filters
+            .iter()
+            .filter_map(|f| f.keyword.as_deref())
+            .any(|k| k.eq_ignore_ascii_case(keyword))
+        {
+            return Err(HobbsError::Validation(
+                "このキーワードは既に登録されています".to_string(),
+            ));
+        }
+        self.check_limit(filters.len())?;
+
+        let id = repo.add_keyword(user_id, keyword).await?;
+        self.get(id).await
+    }
+
+    /// Remove one of a user's filters.
+    pub async fn remove(&self, user_id: i64, filter_id: i64) -> Result<()> {
+        if !TwitFilterRepository::new(self.pool)
+            .delete(user_id, filter_id)
+            .await?
+        {
+            return Err(HobbsError::NotFound("フィルタ".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Load a user's kill file.
+    pub async fn kill_file(&self, user_id: i64) -> Result<KillFile> {
+        Ok(KillFile::new(&self.list(user_id).await?))
+    }
+
+    async fn get(&self, id: i64) -> Result<TwitFilter> {
+        TwitFilterRepository::new(self.pool)
+            .get(id)
+            .await?
+            .ok_or_else(|| HobbsError::NotFound("フィルタ".to_string()))
+    }
+
+    fn check_limit(&self, count: usize) -> Result<()> {
+        if count as i64 >= MAX_TWIT_FILTERS {
+            return Err(HobbsError::Validation(format!(
+                "フィルタは{MAX_TWIT_FILTERS}件までです"
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{Database, NewUser};
+
+    async fn setup() -> (Database, i64, i64) {
+        let db = Database::open_in_memory().await.unwrap();
+        let repo = UserRepository::new(db.pool());
+        let alice = repo
+            .create(&NewUser::new("alice", "hash", "Alice"))
+            .await
+            .unwrap();
+        let troll = repo
+            .create(&NewUser::new("troll", "hash", "Troll"))
+            .await
+            .unwrap();
+        (db, alice.id, troll.id)
+    }
+
+    #[tokio::test]
+    async fn test_add_and_remove() {
+        let (db, alice, troll) = setup().await;
+        let service = TwitFilterService::new(db.pool());
+
+        let keyword = service.add_keyword(alice, "  Spoiler ").await.unwrap();
+        assert_eq!(keyword.keyword.as_deref(), Some("Spoiler"));
+        let user = service.add_user(alice, "TROLL").await.unwrap();
+        assert_eq!(user.target_user_id, Some(troll));
+        assert_eq!(user.value(), "troll");
+
+        let filters = service.list(alice).await.unwrap();
+        assert_eq!(filters.len(), 2);
+        assert_eq!(filters[0].id, user.id);
+        assert!(service.list(troll).await.unwrap().is_empty());
+
+        let kill_file = service.kill_file(alice).await.unwrap();
+        assert!(kill_file.hides_text("no spoilers"));
+
+        // Only the owner can remove a filter.
+        assert!(matches!(
+            service.remove(troll, user.id).await,
+            Err(HobbsError::NotFound(_))
+        ));
+        service.remove(alice, user.id).await.unwrap();
+        assert_eq!(service.list(alice).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_validation() {
+        let (db, alice, _) = setup().await;
+        let service = TwitFilterService::new(db.pool());
+
+        assert!(matches!(
+            service.add_user(alice, "nobody").await,
+            Err(HobbsError::NotFound(_))
+        ));
+        assert!(matches!(
+            service.add_user(alice, "alice").await,
+            Err(HobbsError::Validation(_))
+        ));
+        service.add_user(alice, "troll").await.unwrap();
+        assert!(matches!(
+            service.add_user(alice, "troll").await,
+            Err(HobbsError::Validation(_))
+        ));
+
+        assert!(service.add_keyword(alice, "   ").await.is_err());
+        assert!(service.add_keyword(alice, &"x".repeat(51)).await.is_err());
+        service.add_keyword(alice, "spam").await.unwrap();
+        assert!(matches!(
+            service.add_keyword(alice, "SPAM").await,
+            Err(HobbsError::Validation(_))
+        ));
+    }
+}
@@ -0,0 +1,271 @@
+//! Twit filter types.
+
+use std::collections::HashSet;
+
+use crate::board::{Post, Thread};
+use crate::chat::MessageType;
+use crate::mail::Mail;
+
+/// What a twit filter hides.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TwitTarget {
+    /// Everything written by a user.
+    User(i64),
+    /// Content whose title or body contains a keyword (ignoring the case
+    /// of ASCII letters).
+    Keyword(String),
+}
+
+/// A filter on a user's twit list.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct TwitFilter {
+    /// Unique filter ID.
+    pub id: i64,
+    /// ID of the user owning the filter.
+    pub user_id: i64,
+    /// Hidden user (None for keyword filters).
+    pub target_user_id: Option<i64>,
+    /// Username of the hidden user.
+    pub target_username: Option<String>,
+    /// Hidden keyword (None for user filters).
+    pub keyword: Option<String>,
+    /// Creation timestamp.
+    pub created_at: String,
+}
+
+impl TwitFilter {
+    /// Get what the filter hides.
+    pub fn target(&self) -> TwitTarget {
+        match (self.target_user_id, &self.keyword) {
+            (Some(user_id), _) => TwitTarget::User(user_id),
+            (None, keyword) => TwitTarget::Keyword(keyword.clone().unwrap_or_default()),
+        }
+    }
+
+    /// Get the username or keyword, for display.
+    pub fn value(&self) -> &str {
+        self.target_username
+            .as_deref()
+            .or(self.keyword.as_deref())
+            .unwrap_or_default()
+    }
+}
+
+/// A user's twit filters, ready for matching.
+///
+/// An empty kill file hides nothing; it is also what a user who asked to
+/// see hidden content gets.
+#[derive(Debug, Clone, Default)]
+pub struct KillFile {
+    user_ids: HashSet<i64>,
+    usernames: HashSet<String>,
+    keywords: Vec<String>,
+}
+
+impl KillFile {
+    /// Build a kill file from a user's filters.
+    pub fn new(filters: &[TwitFilter]) -> Self {
+        let mut kill_file = Self::default();
+        for filter in filters {
+            if let Some(user_id) = filter.target_user_id {
+                kill_file.user_ids.insert(user_id);
+            }
+            if let Some(ref username) = filter.target_username {
+                kill_file.usernames.insert(username.to_lowercase());
+            }
+            if let Some(ref keyword) = filter.keyword {
+                kill_file.keywords.push(keyword.to_ascii_lowercase());
+            }
+        }
+        kill_file
+    }
+
+    /// Check whether the kill file hides nothing.
+    pub fn is_empty(&self) -> bool {
+        self.user_ids.is_empty() && self.keywords.is_empty()
+    }
+
+    /// Check whether a text contains a hidden keyword.
+    ///
+    /// Only ASCII letters are case-folded, the same as SQLite's `LOWER()`,
+    /// so that lists and unread counts agree.
+    pub fn hides_text(&self, text: &str) -> bool {
+        if self.keywords.is_empty() {
+            return false;
+        }
+        let text = text.to_ascii_lowercase();
+        self.keywords.iter().any(|k| text.contains(k.as_str()))
+    }
+
+    /// Check whether a thread is hidden, by its starter or its title.
+    ///
+    /// The posts of a hidden thread are hidden with it.
+    pub fn hides_thread(&self, thread: &Thread) -> bool {
+        self.user_ids.contains(&thread.author_id) || self.hides_text(&thread.title)
+    }
+
+    /// Check whether a post is hidden, by its author, title or body.
+    pub fn hides_post(&self, post: &Post) -> bool {
+        self.user_ids.contains(&post.author_id)
+            || post.title.as_deref().is_some_and(|t| self.hides_text(t))
+            || self.hides_text(&post.body)
+    }
+
+    /// Check whether a mail is hidden, by its sender, subject or body.
+    pub fn hides_mail(&self, mail: &Mail) -> bool {
+        self.user_ids.contains(&mail.sender_id)
+            || self.hides_text(&mail.subject)
+            || self.hides_text(&mail.body)
+    }
+
+    /// Check whether a chat message is hidden.
+    ///
+    /// Everything from a hidden user is hidden, including their joining and
+    /// leaving; keywords only apply to what is said.
+    pub fn hides_chat(&self, sender_name: &str, message_type: MessageType, content: &str) -> bool {
+        if !sender_name.is_empty() && self.usernames.contains(&sender_name.to_lowercase()) {
+            return true;
+        }
+        matches!(message_type, MessageType::Chat | MessageType::Action) && self.hides_text(content)
+    }
+
+    /// Build a SQL condition that leaves out hidden posts.
+    ///
+    /// `posts` is the name or alias of the posts table in the query and
+    /// `first_param` the number of the first free placeholder. Returns the
+    /// condition, to be appended after a `WHERE` clause, and the values to
+    /// bind to the placeholders it uses, in order.
+    pub fn visible_posts_sql(&self, posts: &str, first_param: usize) -> (String, Vec<String>) {
+        if self.is_empty() {
+            return (String::new(), Vec::new());
+        }
+
+        let mut thread_hidden = Vec::new();
+        let mut sql = String::new();
+        if !self.user_ids.is_empty() {
+            let mut ids: Vec<i64> = self.user_ids.iter().copied().collect();
+            ids.sort_unstable();
+            let ids = ids
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            sql.push_str(&format!(" AND {posts}.author_id NOT IN ({ids})"));
+            thread_hidden.push(format!("kt.author_id IN ({ids})"));
+        }
+
+        let mut binds = Vec::new();
+        for (i, keyword) in self.keywords.iter().enumerate() {
+            let param = first_param + i;
+            sql.push_str(&format!(
+                " AND {} NOT LIKE ${param} ESCAPE '\\'
+                  AND {} NOT LIKE ${param} ESCAPE '\\'",
+                ascii_lower(&format!("{posts}.body")),
+                ascii_lower(&format!("COALESCE({posts}.title, '')"))
+            ));
+            thread_hidden.push(format!(
+                "{} LIKE ${param} ESCAPE '\\'",
+                ascii_lower("kt.title")
+            ));
+            binds.push(like_pattern(keyword));
+        }
+
+        sql.push_str(&format!(
+            " AND NOT EXISTS (SELECT 1 FROM threads kt WHERE kt.id = {posts}.thread_id AND ({}))",
+            thread_hidden.join(" OR ")
+        ));
+
+        (sql, binds)
+    }
+}
+
+/// Lowercase the ASCII letters of a SQL expression, leaving other
+/// characters alone.
+#[cfg(feature = "sqlite")]
+fn ascii_lower(expr: &str) -> String {
+    format!("LOWER({expr})")
+}
+
+/// Lowercase the ASCII letters of a SQL expression, leaving other
+/// characters alone.
+///
+/// PostgreSQL's `LOWER()` folds every letter, so it cannot be used here.
+#[cfg(feature = "postgres")]
+fn ascii_lower(expr: &str) -> String {
+    format!("TRANSLATE({expr}, 'ABCDEFGHIJKLMNOPQRSTUVWXYZ', 'abcdefghijklmnopqrstuvwxyz')")
+}
+
+/// Build a LIKE pattern matching text that contains a keyword.
+fn like_pattern(keyword: &str) -> String {
+    let escaped = keyword
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{escaped}%")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user_filter(user_id: i64, username: &str) -> TwitFilter {
+        TwitFilter {
+            id: user_id,
+            user_id: 1,
+            target_user_id: Some(user_id),
+            target_username: Some(username.to_string()),
+            keyword: None,
+            created_at: String::new(),
+        }
+    }
+
+    fn keyword_filter(keyword: &str) -> TwitFilter {
+        TwitFilter {
+            id: 0,
+            user_id: 1,
+            target_user_id: None,
+            target_username: None,
+            keyword: Some(keyword.to_string()),
+            created_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_kill_file_matching() {
+        let kill_file = KillFile::new(&[user_filter(7, "Troll"), keyword_filter("Spoiler")]);
+        assert!(!kill_file.is_empty());
+        assert!(kill_file.hides_text("major SPOILERS ahead"));
+        assert!(!kill_file.hides_text("nothing to see"));
+
+        assert!(kill_file.hides_chat("troll", MessageType::Join, "troll joined"));
+        assert!(kill_file.hides_chat("alice", MessageType::Chat, "a spoiler"));
+        assert!(!kill_file.hides_chat("", MessageType::System, "spoiler"));
+        assert!(!kill_file.hides_chat("alice", MessageType::Chat, "hello"));
+
+        // Only ASCII letters are case-folded.
+        let kill_file = KillFile::new(&[keyword_filter("Ｎｅｗｓ")]);
+        assert!(kill_file.hides_text("Ｎｅｗｓ today"));
+        assert!(!kill_file.hides_text("ｎｅｗｓ today"));
+
+        assert!(KillFile::default().is_empty());
+        assert!(!KillFile::default().hides_text("spoiler"));
+    }
+
+    #[test]
+    fn test_visible_posts_sql() {
+        assert_eq!(
+            KillFile::default().visible_posts_sql("p", 3),
+            (String::new(), vec![])
+        );
+
+        let kill_file = KillFile::new(&[user_filter(7, "troll"), keyword_filter("50%_off")]);
+        let (sql, binds) = kill_file.visible_posts_sql("p", 3);
+        assert!(sql.contains("p.author_id NOT IN (7)"));
+        assert!(sql.contains(&format!("{} NOT LIKE $3", ascii_lower("p.body"))));
+        assert!(sql.contains(&format!(
+            "kt.author_id IN (7) OR {} LIKE $3",
+            ascii_lower("kt.title")
+        )));
+        assert_eq!(binds, vec!["%50\\%\\_off%".to_string()]);
+    }
+}
@@ -120,6 +120,64 @@ impl PaginationQuery {
 // Board DTOs
 // ============================================================================
 
+/// Thread post list query parameters.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct ThreadPostsQuery {
+    /// Page number (1-indexed).
+    #[serde(default = "default_page")]
+    pub page: u32,
+    /// Items per page.
+    #[serde(default = "default_per_page")]
+    pub per_page: u32,
+    /// List posts as a reply tree (oldest first) instead of newest first.
+    #[serde(default)]
+    pub tree: bool,
+}
+
+impl ThreadPostsQuery {
+    /// Pagination part of the query.
+    pub fn pagination(&self) -> PaginationQuery {
+        PaginationQuery {
+            page: self.page,
+            per_page: self.per_page,
+        }
+    }
+}
+
+/// Post diff query parameters.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PostDiffQuery {
+    /// Older version number (defaults to the version before `to`).
+    pub from: Option<usize>,
+    /// Newer version number (defaults to the current version).
+    pub to: Option<usize>,
+}
+
+/// Post search query parameters.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct SearchQuery {
+    /// Search words.
+    pub q: String,
+    /// Restrict the search to one board.
+    pub board_id: Option<i64>,
+    /// Page number (1-indexed).
+    #[serde(default = "default_page")]
+    pub page: u32,
+    /// Items per page.
+    #[serde(default = "default_per_page")]
+    pub per_page: u32,
+}
+
+impl SearchQuery {
+    /// Pagination part of the query.
+    pub fn pagination(&self) -> PaginationQuery {
+        PaginationQuery {
+            page: self.page,
+            per_page: self.per_page,
+        }
+    }
+}
+
 /// Create thread request.
 #[derive(Debug, Deserialize, ToSchema, Validate)]
 pub struct CreateThreadRequest {
@@ -141,6 +199,9 @@ pub struct CreatePostRequest {
     #[validate(length(min = 1, max = 10000, message = "Body must be 1-10000 characters"))]
     #[validate(custom(function = "not_empty_trimmed"))]
     pub body: String,
+    /// ID of the post being replied to (must be in the same thread).
+    #[serde(default)]
+    pub parent_id: Option<i64>,
 }
 
 /// Create flat post request (for flat boards).
@@ -180,6 +241,17 @@ pub struct UpdateThreadRequest {
     pub title: String,
 }
 
+/// Update thread status request (SubOp or higher).
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateThreadStatusRequest {
+    /// Pin the thread to the top of the list.
+    #[serde(default)]
+    pub is_pinned: Option<bool>,
+    /// Close the thread to new posts.
+    #[serde(default)]
+    pub is_locked: Option<bool>,
+}
+
 // ============================================================================
 // Mail DTOs
 // ============================================================================
@@ -202,6 +274,186 @@ pub struct SendMailRequest {
     pub body: String,
 }
 
+/// Read pointer reached by a QWK packet.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct QwkPointer {
+    /// Board ID (the conference number).
+    pub board_id: i64,
+    /// Last post of the board included in the packet.
+    pub post_id: i64,
+}
+
+/// Confirm a downloaded QWK packet request.
+///
+/// The client sends back the `X-QWK-Pointers` header of the download.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct QwkCommitRequest {
+    /// Read pointers per board.
+    #[serde(default)]
+    pub pointers: Vec<QwkPointer>,
+    /// Mail included in the packet.
+    #[serde(default)]
+    pub mail_ids: Vec<i64>,
+}
+
+// ============================================================================
+// Subscription DTOs
+// ============================================================================
+
+/// Subscribe request. Exactly one of `board_id` and `thread_id` is set.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateSubscriptionRequest {
+    /// Board to subscribe to.
+    #[serde(default)]
+    pub board_id: Option<i64>,
+    /// Thread to subscribe to.
+    #[serde(default)]
+    pub thread_id: Option<i64>,
+    /// Bundle notifications into a daily digest mail.
+    #[serde(default)]
+    pub digest: bool,
+}
+
+/// Update subscription request.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateSubscriptionRequest {
+    /// Bundle notifications into a daily digest mail.
+    pub digest: bool,
+}
+
+// ============================================================================
+// Bookmark DTOs
+// ============================================================================
+
+/// Add bookmark request. Exactly one of `thread_id` and `post_id` is set.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateBookmarkRequest {
+    /// Thread to bookmark.
+    #[serde(default)]
+    pub thread_id: Option<i64>,
+    /// Post to bookmark.
+    #[serde(default)]
+    pub post_id: Option<i64>,
+    /// Optional note.
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// Update bookmark request.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateBookmarkRequest {
+    /// New note (null or empty removes the note).
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+// ============================================================================
+// Twit Filter DTOs
+// ============================================================================
+
+/// Add twit filter request. Exactly one of `username` and `keyword` is set.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateTwitFilterRequest {
+    /// User whose posts, mail and chat lines are hidden.
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Keyword hiding content whose title or body contains it.
+    #[serde(default)]
+    pub keyword: Option<String>,
+}
+
+/// Query parameter for lists that apply the user's twit filters.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct ShowHiddenQuery {
+    /// Include content hidden by the user's twit filters.
+    #[serde(default)]
+    pub show_hidden: bool,
+}
+
+// ============================================================================
+// Newscan DTOs
+// ============================================================================
+
+/// Board in a newscan update, listed in scan order.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct NewscanBoardRequest {
+    /// Board ID.
+    pub board_id: i64,
+    /// Whether the board is scanned.
+    pub included: bool,
+}
+
+/// Update newscan settings request.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateNewscanRequest {
+    /// Boards in scan order. Boards left out are scanned after these.
+    #[serde(default)]
+    pub boards: Option<Vec<NewscanBoardRequest>>,
+    /// Run the newscan after Telnet login.
+    #[serde(default)]
+    pub scan_on_login: Option<bool>,
+}
+
+/// Reset read pointers request.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ResetNewscanRequest {
+    /// Posts before this time become read and later ones unread
+    /// (RFC3339, or YYYY-MM-DD in UTC).
+    pub since: String,
+}
+
+// ============================================================================
+// Poll DTOs
+// ============================================================================
+
+/// Create poll request.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreatePollRequest {
+    /// The question asked.
+    pub question: String,
+    /// Optional longer explanation.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Option texts, in display order (2-20).
+    pub options: Vec<String>,
+    /// Whether a ballot may choose several options.
+    #[serde(default)]
+    pub allow_multiple: bool,
+    /// Whether voters may add their own answer.
+    #[serde(default)]
+    pub allow_write_in: bool,
+    /// Minimum role required to vote (guest, member, subop, sysop). Defaults to member.
+    #[serde(default)]
+    pub min_vote_role: Option<String>,
+    /// Start of voting (RFC 3339). Defaults to now.
+    #[serde(default)]
+    pub opens_at: Option<String>,
+    /// End of voting (RFC 3339). Defaults to no end.
+    #[serde(default)]
+    pub closes_at: Option<String>,
+    /// Thread to attach the poll to.
+    #[serde(default)]
+    pub thread_id: Option<i64>,
+}
+
+/// Vote request.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct VoteRequest {
+    /// IDs of the chosen options.
+    #[serde(default)]
+    pub option_ids: Vec<i64>,
+    /// Write-in answer (polls allowing write-ins only).
+    #[serde(default)]
+    pub write_in: Option<String>,
+}
+
+/// Poll list query parameters.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PollListQuery {
+    /// Only the poll attached to this thread.
+    pub thread_id: Option<i64>,
+}
+
 // ============================================================================
 // User DTOs
 // ============================================================================
@@ -313,6 +565,24 @@ pub struct AdminCreateBoardRequest {
     /// Whether auto-paging is disabled for this board.
     #[serde(default)]
     pub disable_paging: bool,
+    /// Whether color codes in posts are displayed.
+    #[serde(default = "default_true")]
+    pub allow_color: bool,
+    /// Whether posts by members wait for approval.
+    #[serde(default)]
+    pub is_moderated: bool,
+    /// Board group ID.
+    #[serde(default)]
+    pub group_id: Option<i64>,
+    /// Maximum number of posts kept (unlimited when omitted).
+    #[serde(default)]
+    pub max_posts: Option<i64>,
+    /// Maximum age of posts in days (unlimited when omitted).
+    #[serde(default)]
+    pub max_age_days: Option<i64>,
+    /// Whether pruned posts are archived before deletion.
+    #[serde(default)]
+    pub archive_pruned: bool,
 }
 
 fn default_board_type() -> String {
@@ -327,6 +597,10 @@ fn default_member_role() -> String {
     "member".to_string()
 }
 
+fn default_true() -> bool {
+    true
+}
+
 /// Update board request (admin).
 #[derive(Debug, Deserialize, ToSchema, Validate)]
 pub struct AdminUpdateBoardRequest {
@@ -358,6 +632,136 @@ pub struct AdminUpdateBoardRequest {
     /// Whether auto-paging is disabled for this board.
     #[serde(default)]
     pub disable_paging: Option<bool>,
+    /// Whether color codes in posts are displayed.
+    #[serde(default)]
+    pub allow_color: Option<bool>,
+    /// Whether posts by members wait for approval.
+    #[serde(default)]
+    pub is_moderated: Option<bool>,
+    /// Board group ID (null removes the board from its group).
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub group_id: Option<Option<i64>>,
+    /// Maximum number of posts kept (null removes the limit).
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub max_posts: Option<Option<i64>>,
+    /// Maximum age of posts in days (null removes the limit).
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub max_age_days: Option<Option<i64>>,
+    /// Whether pruned posts are archived before deletion.
+    #[serde(default)]
+    pub archive_pruned: Option<bool>,
+}
+
+/// Deserialize a present field as `Some`, so that an explicit `null`
+/// becomes `Some(None)` while a missing field stays `None`.
+fn deserialize_some<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+/// Create board group request (admin).
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct AdminCreateBoardGroupRequest {
+    /// Group name.
+    #[validate(length(min = 1, max = 50, message = "Group name must be 1-50 characters"))]
+    #[validate(custom(function = "no_control_chars"))]
+    #[validate(custom(function = "not_empty_trimmed"))]
+    pub name: String,
+    /// Group description.
+    #[serde(default)]
+    #[validate(length(max = 500, message = "Description must be 500 characters or less"))]
+    pub description: Option<String>,
+    /// Minimum role required to read the group and its boards.
+    #[serde(default = "default_guest_role")]
+    #[validate(length(min = 1, message = "Minimum read role is required"))]
+    pub min_read_role: String,
+    /// Sort order.
+    #[serde(default)]
+    pub sort_order: i32,
+}
+
+/// Update board group request (admin).
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct AdminUpdateBoardGroupRequest {
+    /// Group name.
+    #[serde(default)]
+    #[validate(length(min = 1, max = 50, message = "Group name must be 1-50 characters"))]
+    pub name: Option<String>,
+    /// Group description (null clears it).
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub description: Option<Option<String>>,
+    /// Minimum role required to read the group and its boards.
+    #[serde(default)]
+    #[validate(length(min = 1, message = "Minimum read role must not be empty"))]
+    pub min_read_role: Option<String>,
+    /// Sort order.
+    #[serde(default)]
+    pub sort_order: Option<i32>,
+}
+
+/// Reject a pending post request (admin).
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct AdminRejectPostRequest {
+    /// Reason mailed to the author.
+    #[validate(length(min = 1, max = 1000, message = "Reason must be 1-1000 characters"))]
+    pub reason: String,
+}
+
+/// Move a thread to another board request (admin).
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AdminMoveThreadRequest {
+    /// Destination thread board.
+    pub board_id: i64,
+}
+
+/// Merge a thread into another request (admin).
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AdminMergeThreadRequest {
+    /// Thread that receives the posts and keeps its title.
+    pub target_thread_id: i64,
+}
+
+/// Split a thread request (admin).
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct AdminSplitThreadRequest {
+    /// First post of the new thread; later posts move with it.
+    pub post_id: i64,
+    /// Title of the new thread.
+    #[validate(length(min = 1, max = 50, message = "Title must be 1-50 characters"))]
+    #[validate(custom(function = "no_control_chars"))]
+    #[validate(custom(function = "not_empty_trimmed"))]
+    pub title: String,
+}
+
+/// Convert a thread into flat posts request (admin).
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AdminConvertThreadRequest {
+    /// Destination flat board.
+    pub board_id: i64,
+}
+
+/// Convert a flat post into a thread request (admin).
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct AdminConvertPostRequest {
+    /// Destination thread board.
+    pub board_id: i64,
+    /// Thread title (the post title when omitted).
+    #[serde(default)]
+    #[validate(length(min = 1, max = 50, message = "Title must be 1-50 characters"))]
+    #[validate(custom(function = "no_control_chars"))]
+    pub title: Option<String>,
+}
+
+/// Board export query parameters (admin).
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AdminExportBoardsQuery {
+    /// Board to export (all boards when omitted).
+    pub board_id: Option<i64>,
+    /// Output format: "json" (default) or "mbox".
+    pub format: Option<String>,
 }
 
 /// Create folder request (admin).
@@ -430,3 +834,22 @@ pub struct AdminAddFeedRequest {
     #[validate(length(max = 100, message = "Title must be 100 characters or less"))]
     pub title: Option<String>,
 }
+
+/// Add content filter rule request (admin).
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct AdminCreateFilterRuleRequest {
+    /// Rule type: "word", "regex", "links" or "new_account".
+    pub rule_type: String,
+    /// Comma-separated words, regular expression, link limit or account age in hours.
+    #[validate(length(min = 1, max = 2000, message = "Pattern must be 1-2000 characters"))]
+    pub pattern: String,
+    /// Action: "reject", "hold" or "mask".
+    pub action: String,
+}
+
+/// Update content filter rule request (admin).
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AdminUpdateFilterRuleRequest {
+    /// Whether the rule is applied.
+    pub is_active: bool,
+}
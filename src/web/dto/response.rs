@@ -150,10 +150,42 @@ pub struct BoardResponse {
     pub can_read: bool,
     /// Whether user can write to this board.
     pub can_write: bool,
+    /// Board group ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<i64>,
+    /// Unread posts for the signed-in user (board tree only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unread_count: Option<i64>,
     /// Creation timestamp.
     pub created_at: String,
 }
 
+/// Board group with its boards, as listed in the board tree.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BoardGroupResponse {
+    /// Group ID.
+    pub id: i64,
+    /// Group name.
+    pub name: String,
+    /// Group description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Unread posts in the group's boards for the signed-in user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unread_count: Option<i64>,
+    /// Boards in the group.
+    pub boards: Vec<BoardResponse>,
+}
+
+/// Board tree: groups with their boards, then boards without a group.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BoardTreeResponse {
+    /// Board groups.
+    pub groups: Vec<BoardGroupResponse>,
+    /// Boards without a group.
+    pub boards: Vec<BoardResponse>,
+}
+
 /// Thread response.
 #[derive(Debug, Serialize, ToSchema)]
 pub struct ThreadResponse {
@@ -173,6 +205,10 @@ pub struct ThreadResponse {
     pub created_at: String,
     /// Last update timestamp.
     pub updated_at: String,
+    /// Whether the thread is pinned to the top of the list.
+    pub is_pinned: bool,
+    /// Whether the thread is closed to new posts.
+    pub is_locked: bool,
 }
 
 /// Post response.
@@ -185,6 +221,12 @@ pub struct PostResponse {
     /// Thread ID (None for flat boards).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub thread_id: Option<i64>,
+    /// ID of the post this one replies to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<i64>,
+    /// Nesting level in the reply tree (only when listed with `tree=true`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth: Option<usize>,
     /// Author info.
     pub author: AuthorInfo,
     /// Post title (for flat boards).
@@ -194,6 +236,88 @@ pub struct PostResponse {
     pub body: String,
     /// Creation timestamp.
     pub created_at: String,
+    /// Timestamp of the last edit (None if never edited).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edited_at: Option<String>,
+}
+
+/// Post waiting for moderator approval.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PendingPostResponse {
+    /// Pending post ID.
+    pub id: i64,
+    /// Board ID.
+    pub board_id: i64,
+    /// Board name.
+    pub board_name: String,
+    /// Thread the post replies in (None for new threads and flat posts).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_id: Option<i64>,
+    /// Title of the thread the post replies in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_title: Option<String>,
+    /// ID of the post this one replies to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<i64>,
+    /// Author info.
+    pub author: AuthorInfo,
+    /// Thread title for a new thread, or post title on a flat board.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Post body.
+    pub body: String,
+    /// Submission timestamp.
+    pub created_at: String,
+}
+
+/// Post search result.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SearchResultResponse {
+    /// Matching post.
+    pub post: PostResponse,
+    /// Name of the board the post belongs to.
+    pub board_name: String,
+    /// Thread title (None for flat boards).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_title: Option<String>,
+}
+
+/// One version of an edited post.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PostVersionResponse {
+    /// Version number (the original post is 1).
+    pub number: usize,
+    /// Title of this version.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Body of this version.
+    pub body: String,
+    /// User who wrote this version (None if the user was deleted).
+    pub editor: Option<AuthorInfo>,
+    /// When this version was written.
+    pub created_at: String,
+    /// Whether this is the current content of the post.
+    pub current: bool,
+}
+
+/// Line diff between two versions of a post.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PostDiffResponse {
+    /// Older version number.
+    pub from: usize,
+    /// Newer version number.
+    pub to: usize,
+    /// Diff lines.
+    pub lines: Vec<DiffLineResponse>,
+}
+
+/// A line in a post diff.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DiffLineResponse {
+    /// `" "` (unchanged), `"-"` (removed) or `"+"` (added).
+    pub op: String,
+    /// Line text.
+    pub text: String,
 }
 
 /// Author information.
@@ -254,6 +378,211 @@ pub struct UnreadCountResponse {
     pub count: u64,
 }
 
+/// QWK reply packet import response.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct QwkImportResponse {
+    /// Number of posts created.
+    pub posts: usize,
+    /// Number of posts queued for moderator approval.
+    pub pending: usize,
+    /// Number of mails sent.
+    pub mails: usize,
+    /// Messages that could not be imported, with the reason.
+    pub failed: Vec<String>,
+}
+
+// ============================================================================
+// Subscription DTOs
+// ============================================================================
+
+/// Subscription response.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SubscriptionResponse {
+    /// Subscription ID.
+    pub id: i64,
+    /// Subscribed board (board subscriptions only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub board_id: Option<i64>,
+    /// Subscribed thread (thread subscriptions only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_id: Option<i64>,
+    /// Board name or thread title.
+    pub name: String,
+    /// Whether notifications go to the daily digest mail.
+    pub digest: bool,
+    /// Creation timestamp.
+    pub created_at: String,
+}
+
+/// Notification about a new post in a subscribed board or thread.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct NotificationResponse {
+    /// Notification ID.
+    pub id: i64,
+    /// ID of the new post.
+    pub post_id: i64,
+    /// Board ID.
+    pub board_id: i64,
+    /// Board name.
+    pub board_name: String,
+    /// Thread ID (thread-based boards only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_id: Option<i64>,
+    /// Thread title, or post title on flat boards.
+    pub title: String,
+    /// Nickname of the post author.
+    pub author: String,
+    /// Creation timestamp.
+    pub created_at: String,
+}
+
+// ============================================================================
+// Bookmark DTOs
+// ============================================================================
+
+/// Bookmark response.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BookmarkResponse {
+    /// Bookmark ID.
+    pub id: i64,
+    /// Bookmarked thread (thread bookmarks only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_id: Option<i64>,
+    /// Bookmarked post (post bookmarks only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_id: Option<i64>,
+    /// Thread containing a bookmarked post (thread boards only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_thread_id: Option<i64>,
+    /// Board the target is in (absent once deleted).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub board_id: Option<i64>,
+    /// Name of that board (absent once deleted).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub board_name: Option<String>,
+    /// Thread or post title.
+    pub title: String,
+    /// The user's note.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Whether the target was deleted or can no longer be read.
+    pub deleted: bool,
+    /// Creation timestamp.
+    pub created_at: String,
+}
+
+// ============================================================================
+// Twit Filter DTOs
+// ============================================================================
+
+/// Twit filter response.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TwitFilterResponse {
+    /// Filter ID.
+    pub id: i64,
+    /// What the filter hides: "user" or "keyword".
+    pub kind: String,
+    /// Hidden user (user filters only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<i64>,
+    /// Username of the hidden user, or the keyword.
+    pub value: String,
+    /// Creation timestamp.
+    pub created_at: String,
+}
+
+// ============================================================================
+// Newscan DTOs
+// ============================================================================
+
+/// Board in the user's newscan.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NewscanBoardResponse {
+    /// Board ID.
+    pub board_id: i64,
+    /// Board name.
+    pub name: String,
+    /// Whether the board is scanned.
+    pub included: bool,
+}
+
+/// Newscan settings response.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NewscanResponse {
+    /// Readable boards in scan order.
+    pub boards: Vec<NewscanBoardResponse>,
+    /// Whether the newscan runs after Telnet login.
+    pub scan_on_login: bool,
+}
+
+/// Reset read pointers response.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ResetNewscanResponse {
+    /// Number of boards reset.
+    pub boards: usize,
+}
+
+// ============================================================================
+// Poll DTOs
+// ============================================================================
+
+/// Poll option with its vote count.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PollOptionResponse {
+    /// Option ID.
+    pub id: i64,
+    /// Option text.
+    pub text: String,
+    /// Whether a voter added this option as a write-in.
+    pub is_write_in: bool,
+    /// Number of ballots choosing this option.
+    pub votes: i64,
+}
+
+/// Poll with its results.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PollResponse {
+    /// Poll ID.
+    pub id: i64,
+    /// The question asked.
+    pub question: String,
+    /// Optional longer explanation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether a ballot may choose several options.
+    pub allow_multiple: bool,
+    /// Whether voters may add their own answer.
+    pub allow_write_in: bool,
+    /// Minimum role required to vote.
+    pub min_vote_role: String,
+    /// Start of voting.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opens_at: Option<String>,
+    /// End of voting.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub closes_at: Option<String>,
+    /// Status (upcoming, open, closed).
+    pub status: String,
+    /// Thread the poll is attached to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_id: Option<i64>,
+    /// Nickname of the creator.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creator: Option<String>,
+    /// Options with their vote counts.
+    pub options: Vec<PollOptionResponse>,
+    /// Number of ballots cast.
+    pub voters: i64,
+    /// Options chosen by the current user (empty if not voted).
+    pub my_votes: Vec<i64>,
+    /// Whether the current user may vote now.
+    pub can_vote: bool,
+    /// Whether the current user may close or delete the poll.
+    pub can_manage: bool,
+    /// Creation timestamp.
+    pub created_at: String,
+}
+
 // ============================================================================
 // User DTOs
 // ============================================================================
@@ -449,10 +778,67 @@ pub struct AdminBoardResponse {
     pub is_active: bool,
     /// Whether auto-paging is disabled for this board.
     pub disable_paging: bool,
+    /// Whether color codes in posts are displayed.
+    pub allow_color: bool,
+    /// Whether posts by members wait for approval.
+    pub is_moderated: bool,
+    /// Board group ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<i64>,
+    /// Maximum number of posts kept.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_posts: Option<i64>,
+    /// Maximum age of posts in days.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_age_days: Option<i64>,
+    /// Whether pruned posts are archived before deletion.
+    pub archive_pruned: bool,
+    /// Creation timestamp.
+    pub created_at: String,
+}
+
+/// Admin board group response.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminBoardGroupResponse {
+    /// Group ID.
+    pub id: i64,
+    /// Group name.
+    pub name: String,
+    /// Group description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Minimum role required to read the group and its boards.
+    pub min_read_role: String,
+    /// Sort order.
+    pub sort_order: i32,
     /// Creation timestamp.
     pub created_at: String,
 }
 
+/// Board archive import response (admin).
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminImportBoardsResponse {
+    /// Number of boards created.
+    pub boards: usize,
+    /// Number of threads created.
+    pub threads: usize,
+    /// Number of posts created.
+    pub posts: usize,
+    /// Usernames of the inactive placeholder users created for missing authors.
+    pub placeholders: Vec<String>,
+    /// Boards that could not be imported, with the reason.
+    pub failed: Vec<String>,
+}
+
+/// Convert a thread into flat posts response (admin).
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminConvertThreadResponse {
+    /// Flat board the posts moved to.
+    pub board_id: i64,
+    /// Number of posts converted.
+    pub posts: u64,
+}
+
 /// Admin folder response (includes more details).
 #[derive(Debug, Serialize, ToSchema)]
 pub struct AdminFolderResponse {
@@ -477,3 +863,41 @@ pub struct AdminFolderResponse {
     /// Creation timestamp.
     pub created_at: String,
 }
+
+/// Admin content filter rule response.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminFilterRuleResponse {
+    /// Rule ID.
+    pub id: i64,
+    /// Rule type: "word", "regex", "links" or "new_account".
+    pub rule_type: String,
+    /// Word list, regular expression, link limit or account age in hours.
+    pub pattern: String,
+    /// Action: "reject", "hold" or "mask".
+    pub action: String,
+    /// Whether the rule is applied.
+    pub is_active: bool,
+    /// Creation timestamp.
+    pub created_at: String,
+}
+
+/// Admin content filter hit response.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminFilterHitResponse {
+    /// Hit ID.
+    pub id: i64,
+    /// Rule that matched (absent once the rule is deleted).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule_id: Option<i64>,
+    /// Username of the author (absent for guests).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// Kind of content: "post", "mail" or "chat".
+    pub target: String,
+    /// Action taken: "reject", "hold" or "mask".
+    pub action: String,
+    /// The matched text, or a description for link and account rules.
+    pub detail: String,
+    /// Hit timestamp.
+    pub created_at: String,
+}
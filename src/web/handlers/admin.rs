@@ -1,26 +1,45 @@
 //! Admin handlers for Web API.
 
 use axum::{
+    body::Body,
     extract::{Path, Query, State},
+    http::header,
+    response::Response,
     Json,
 };
 use std::sync::Arc;
 use utoipa;
+use validator::Validate;
 
+use crate::admin::{AdminError, ContentAdminService, ModerationAdminService};
+use crate::archive::{write_mbox, ArchiveService, BoardArchive};
 use crate::auth::hash_password;
-use crate::board::{BoardRepository, BoardType, BoardUpdate, NewBoard};
+use crate::board::{
+    BoardGroup, BoardGroupRepository, BoardGroupUpdate, BoardRepository, BoardType, BoardUpdate,
+    NewBoard, NewBoardGroup, Thread,
+};
 use crate::datetime::to_rfc3339;
-use crate::db::{Role, UserRepository, UserUpdate};
+use crate::db::{Role, User, UserRepository, UserUpdate};
 use crate::file::{FileRepository, FolderRepository, FolderUpdate, NewFolder};
+use crate::filter::{
+    ContentFilterService, FilterAction, FilterRule, FilterRuleType, NewFilterRule,
+};
 use crate::web::dto::{
-    AdminBoardResponse, AdminCreateBoardRequest, AdminCreateFolderRequest, AdminFolderResponse,
-    AdminResetPasswordRequest, AdminUpdateBoardRequest, AdminUpdateFolderRequest,
-    AdminUpdateRoleRequest, AdminUpdateStatusRequest, AdminUpdateUserRequest, AdminUserResponse,
-    ApiResponse, PaginatedResponse, PaginationQuery,
+    AdminBoardGroupResponse, AdminBoardResponse, AdminConvertPostRequest,
+    AdminConvertThreadRequest, AdminConvertThreadResponse, AdminCreateBoardGroupRequest,
+    AdminCreateBoardRequest, AdminCreateFilterRuleRequest, AdminCreateFolderRequest,
+    AdminExportBoardsQuery, AdminFilterHitResponse, AdminFilterRuleResponse, AdminFolderResponse,
+    AdminImportBoardsResponse, AdminMergeThreadRequest, AdminMoveThreadRequest,
+    AdminRejectPostRequest, AdminResetPasswordRequest, AdminSplitThreadRequest,
+    AdminUpdateBoardGroupRequest, AdminUpdateBoardRequest, AdminUpdateFilterRuleRequest,
+    AdminUpdateFolderRequest, AdminUpdateRoleRequest, AdminUpdateStatusRequest,
+    AdminUpdateUserRequest, AdminUserResponse, ApiResponse, AuthorInfo, PaginatedResponse,
+    PaginationQuery, PendingPostResponse, PostResponse, ThreadResponse,
 };
 use crate::web::error::ApiError;
-use crate::web::handlers::AppState;
+use crate::web::handlers::{pending_post_response, AppState};
 use crate::web::middleware::AuthUser;
+use crate::HobbsError;
 
 /// Helper to check SubOp or higher permission
 fn require_subop(claims: &crate::web::middleware::JwtClaims) -> Result<Role, ApiError> {
@@ -408,6 +427,12 @@ pub async fn admin_list_boards(
             sort_order: b.sort_order,
             is_active: b.is_active,
             disable_paging: b.disable_paging,
+            allow_color: b.allow_color,
+            is_moderated: b.is_moderated,
+            group_id: b.group_id,
+            max_posts: b.max_posts,
+            max_age_days: b.max_age_days,
+            archive_pruned: b.archive_pruned,
             created_at: to_rfc3339(&b.created_at),
         })
         .collect();
@@ -463,12 +488,24 @@ pub async fn admin_create_board(
         .with_min_read_role(min_read_role)
         .with_min_write_role(min_write_role)
         .with_sort_order(req.sort_order)
-        .with_disable_paging(req.disable_paging);
+        .with_disable_paging(req.disable_paging)
+        .with_allow_color(req.allow_color)
+        .with_moderated(req.is_moderated)
+        .with_retention(
+            check_retention_limit(req.max_posts, "max_posts")?,
+            check_retention_limit(req.max_age_days, "max_age_days")?,
+            req.archive_pruned,
+        );
 
     if let Some(ref desc) = req.description {
         new_board = new_board.with_description(desc);
     }
 
+    if let Some(group_id) = req.group_id {
+        check_group_exists(&state, group_id).await?;
+        new_board = new_board.with_group(group_id);
+    }
+
     let board_repo = BoardRepository::new(state.db.pool());
     let board = board_repo.create(&new_board).await.map_err(|e| {
         tracing::error!("Failed to create board: {}", e);
@@ -485,6 +522,12 @@ pub async fn admin_create_board(
         sort_order: board.sort_order,
         is_active: board.is_active,
         disable_paging: board.disable_paging,
+        allow_color: board.allow_color,
+        is_moderated: board.is_moderated,
+        group_id: board.group_id,
+        max_posts: board.max_posts,
+        max_age_days: board.max_age_days,
+        archive_pruned: board.archive_pruned,
         created_at: to_rfc3339(&board.created_at),
     };
 
@@ -564,6 +607,27 @@ pub async fn admin_update_board(
     if let Some(disable_paging) = req.disable_paging {
         update = update.disable_paging(disable_paging);
     }
+    if let Some(allow_color) = req.allow_color {
+        update = update.allow_color(allow_color);
+    }
+    if let Some(is_moderated) = req.is_moderated {
+        update = update.is_moderated(is_moderated);
+    }
+    if let Some(group_id) = req.group_id {
+        if let Some(group_id) = group_id {
+            check_group_exists(&state, group_id).await?;
+        }
+        update = update.group_id(group_id);
+    }
+    if let Some(max_posts) = req.max_posts {
+        update = update.max_posts(check_retention_limit(max_posts, "max_posts")?);
+    }
+    if let Some(max_age_days) = req.max_age_days {
+        update = update.max_age_days(check_retention_limit(max_age_days, "max_age_days")?);
+    }
+    if let Some(archive_pruned) = req.archive_pruned {
+        update = update.archive_pruned(archive_pruned);
+    }
 
     let board_repo = BoardRepository::new(state.db.pool());
     let board = board_repo
@@ -585,6 +649,12 @@ pub async fn admin_update_board(
         sort_order: board.sort_order,
         is_active: board.is_active,
         disable_paging: board.disable_paging,
+        allow_color: board.allow_color,
+        is_moderated: board.is_moderated,
+        group_id: board.group_id,
+        max_posts: board.max_posts,
+        max_age_days: board.max_age_days,
+        archive_pruned: board.archive_pruned,
         created_at: to_rfc3339(&board.created_at),
     };
 
@@ -629,6 +699,369 @@ pub async fn admin_delete_board(
     Ok(Json(ApiResponse::new(())))
 }
 
+// ============================================================================
+// Board Groups
+// ============================================================================
+
+fn group_response(group: BoardGroup) -> AdminBoardGroupResponse {
+    AdminBoardGroupResponse {
+        id: group.id,
+        name: group.name,
+        description: group.description,
+        min_read_role: group.min_read_role.as_str().to_string(),
+        sort_order: group.sort_order,
+        created_at: to_rfc3339(&group.created_at),
+    }
+}
+
+/// Reject a board group ID that does not exist.
+async fn check_group_exists(state: &AppState, group_id: i64) -> Result<(), ApiError> {
+    let group = BoardGroupRepository::new(state.db.pool())
+        .get_by_id(group_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get board group: {}", e);
+            ApiError::internal("Failed to get board group")
+        })?;
+    if group.is_none() {
+        return Err(ApiError::bad_request("Invalid group_id"));
+    }
+    Ok(())
+}
+
+/// Reject a retention limit below 1.
+fn check_retention_limit(limit: Option<i64>, field: &str) -> Result<Option<i64>, ApiError> {
+    match limit {
+        Some(n) if n < 1 => Err(ApiError::bad_request(format!(
+            "{} must be at least 1",
+            field
+        ))),
+        _ => Ok(limit),
+    }
+}
+
+/// GET /api/admin/board-groups - List all board groups (admin).
+#[utoipa::path(
+    get,
+    path = "/admin/board-groups",
+    tag = "admin",
+    responses(
+        (status = 200, description = "List of board groups", body = Vec<AdminBoardGroupResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Admin access required")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn admin_list_board_groups(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+) -> Result<Json<ApiResponse<Vec<AdminBoardGroupResponse>>>, ApiError> {
+    require_subop(&claims)?;
+
+    let groups = BoardGroupRepository::new(state.db.pool())
+        .list_all()
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to list board groups: {}", e);
+            ApiError::internal("Failed to list board groups")
+        })?;
+
+    Ok(Json(ApiResponse::new(
+        groups.into_iter().map(group_response).collect(),
+    )))
+}
+
+/// POST /api/admin/board-groups - Create a board group (admin).
+#[utoipa::path(
+    post,
+    path = "/admin/board-groups",
+    tag = "admin",
+    request_body = AdminCreateBoardGroupRequest,
+    responses(
+        (status = 200, description = "Board group created", body = AdminBoardGroupResponse),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Admin access required")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn admin_create_board_group(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Json(req): Json<AdminCreateBoardGroupRequest>,
+) -> Result<Json<ApiResponse<AdminBoardGroupResponse>>, ApiError> {
+    require_subop(&claims)?;
+
+    if req.name.trim().is_empty() {
+        return Err(ApiError::bad_request("Group name is required"));
+    }
+
+    let min_read_role: Role = req
+        .min_read_role
+        .parse()
+        .map_err(|_| ApiError::bad_request("Invalid min_read_role"))?;
+
+    let repo = BoardGroupRepository::new(state.db.pool());
+    let db_error = |e: HobbsError| {
+        tracing::error!("Failed to create board group: {}", e);
+        ApiError::internal("Failed to create board group")
+    };
+    if repo.name_exists(&req.name).await.map_err(db_error)? {
+        return Err(ApiError::bad_request("Group name already exists"));
+    }
+
+    let mut new_group = NewBoardGroup::new(&req.name)
+        .with_min_read_role(min_read_role)
+        .with_sort_order(req.sort_order);
+    if let Some(ref desc) = req.description {
+        new_group = new_group.with_description(desc);
+    }
+
+    let group = repo.create(&new_group).await.map_err(db_error)?;
+
+    Ok(Json(ApiResponse::new(group_response(group))))
+}
+
+/// PUT /api/admin/board-groups/:id - Update a board group (admin).
+#[utoipa::path(
+    put,
+    path = "/admin/board-groups/{id}",
+    tag = "admin",
+    params(
+        ("id" = i64, Path, description = "Board group ID")
+    ),
+    request_body = AdminUpdateBoardGroupRequest,
+    responses(
+        (status = 200, description = "Board group updated", body = AdminBoardGroupResponse),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Board group not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn admin_update_board_group(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Path(group_id): Path<i64>,
+    Json(req): Json<AdminUpdateBoardGroupRequest>,
+) -> Result<Json<ApiResponse<AdminBoardGroupResponse>>, ApiError> {
+    require_subop(&claims)?;
+
+    let mut update = BoardGroupUpdate::new();
+
+    if let Some(ref name) = req.name {
+        if name.trim().is_empty() {
+            return Err(ApiError::bad_request("Group name cannot be empty"));
+        }
+        update = update.name(name);
+    }
+    if let Some(ref description) = req.description {
+        update = update.description(description.clone());
+    }
+    if let Some(ref min_read_role) = req.min_read_role {
+        let role: Role = min_read_role
+            .parse()
+            .map_err(|_| ApiError::bad_request("Invalid min_read_role"))?;
+        update = update.min_read_role(role);
+    }
+    if let Some(sort_order) = req.sort_order {
+        update = update.sort_order(sort_order);
+    }
+
+    let group = BoardGroupRepository::new(state.db.pool())
+        .update(group_id, &update)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to update board group: {}", e);
+            ApiError::internal("Failed to update board group")
+        })?
+        .ok_or_else(|| ApiError::not_found("Board group not found"))?;
+
+    Ok(Json(ApiResponse::new(group_response(group))))
+}
+
+/// DELETE /api/admin/board-groups/:id - Delete a board group (admin).
+///
+/// The group's boards are kept and move to the top level.
+#[utoipa::path(
+    delete,
+    path = "/admin/board-groups/{id}",
+    tag = "admin",
+    params(
+        ("id" = i64, Path, description = "Board group ID")
+    ),
+    responses(
+        (status = 200, description = "Board group deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Board group not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn admin_delete_board_group(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Path(group_id): Path<i64>,
+) -> Result<Json<ApiResponse<()>>, ApiError> {
+    require_subop(&claims)?;
+
+    let deleted = BoardGroupRepository::new(state.db.pool())
+        .delete(group_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to delete board group: {}", e);
+            ApiError::internal("Failed to delete board group")
+        })?;
+
+    if !deleted {
+        return Err(ApiError::not_found("Board group not found"));
+    }
+
+    Ok(Json(ApiResponse::new(())))
+}
+
+// ============================================================================
+// Board Archive
+// ============================================================================
+
+/// GET /api/admin/boards/export - Export boards (SysOp only).
+///
+/// Exports one board, or all boards when `board_id` is omitted, as a
+/// versioned JSON archive or as an mbox mailbox.
+#[utoipa::path(
+    get,
+    path = "/admin/boards/export",
+    tag = "admin",
+    params(
+        ("board_id" = Option<i64>, Query, description = "Board to export (all when omitted)"),
+        ("format" = Option<String>, Query, description = "json (default) or mbox")
+    ),
+    responses(
+        (status = 200, description = "Board archive", content_type = "application/json"),
+        (status = 400, description = "Unknown format"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "SysOp access required"),
+        (status = 404, description = "Board not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn admin_export_boards(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Query(query): Query<AdminExportBoardsQuery>,
+) -> Result<Response<Body>, ApiError> {
+    require_sysop(&claims)?;
+
+    let mbox = match query.format.as_deref() {
+        None | Some("json") => false,
+        Some("mbox") => true,
+        Some(_) => return Err(ApiError::bad_request("Format must be json or mbox")),
+    };
+
+    let service = ArchiveService::new(&state.db);
+    let archive = match query.board_id {
+        Some(board_id) => service.export_boards(&[board_id]).await,
+        None => service.export_all().await,
+    }
+    .map_err(|e| match e {
+        HobbsError::NotFound(_) => ApiError::not_found("Board not found"),
+        e => {
+            tracing::error!("Failed to export boards: {}", e);
+            ApiError::internal("Failed to export boards")
+        }
+    })?;
+
+    let (content, content_type, extension) = if mbox {
+        (
+            write_mbox(&archive, &state.message_domain),
+            "application/mbox",
+            "mbox",
+        )
+    } else {
+        let json = archive.to_json().map_err(|e| {
+            tracing::error!("Failed to serialize board archive: {}", e);
+            ApiError::internal("Failed to export boards")
+        })?;
+        (json, "application/json", "json")
+    };
+    let filename = match query.board_id {
+        Some(board_id) => format!("board{}.{}", board_id, extension),
+        None => format!("boards.{}", extension),
+    };
+
+    Response::builder()
+        .header(header::CONTENT_TYPE, content_type)
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", filename),
+        )
+        .body(Body::from(content))
+        .map_err(|e| {
+            tracing::error!("Failed to build response: {}", e);
+            ApiError::internal("Failed to build response")
+        })
+}
+
+/// POST /api/admin/boards/import - Import a board archive (SysOp only).
+///
+/// The request body is a JSON archive as produced by the export endpoint.
+/// Authors are matched by username; missing users are created as inactive
+/// placeholders. Boards whose name is already taken are skipped.
+#[utoipa::path(
+    post,
+    path = "/admin/boards/import",
+    tag = "admin",
+    request_body(content = String, content_type = "application/json", description = "Board archive"),
+    responses(
+        (status = 200, description = "Archive imported", body = AdminImportBoardsResponse),
+        (status = 400, description = "Invalid archive"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "SysOp access required")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn admin_import_boards(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    body: String,
+) -> Result<Json<ApiResponse<AdminImportBoardsResponse>>, ApiError> {
+    require_sysop(&claims)?;
+
+    let archive =
+        BoardArchive::from_json(&body).map_err(|e| ApiError::bad_request(e.to_string()))?;
+    let result = ArchiveService::new(&state.db)
+        .import(&archive)
+        .await
+        .map_err(|e| match e {
+            HobbsError::Archive(msg) => ApiError::bad_request(msg),
+            e => {
+                tracing::error!("Failed to import boards: {}", e);
+                ApiError::internal("Failed to import boards")
+            }
+        })?;
+
+    Ok(Json(ApiResponse::new(AdminImportBoardsResponse {
+        boards: result.boards,
+        threads: result.threads,
+        posts: result.posts,
+        placeholders: result.placeholders,
+        failed: result.failed,
+    })))
+}
+
 // ============================================================================
 // Folder Management
 // ============================================================================
@@ -896,5 +1329,654 @@ pub async fn admin_delete_folder(
     Ok(Json(ApiResponse::new(())))
 }
 
+// ============================================================================
+// Moderation Queue
+// ============================================================================
+
+/// Load the user behind the token for the admin services.
+async fn current_admin(
+    state: &AppState,
+    claims: &crate::web::middleware::JwtClaims,
+) -> Result<User, ApiError> {
+    UserRepository::new(state.db.pool())
+        .get_by_id(claims.sub)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get user: {}", e);
+            ApiError::internal("Database error")
+        })?
+        .ok_or_else(|| ApiError::unauthorized("User not found"))
+}
+
+/// Map a moderation service error to an API error.
+fn moderation_error(e: AdminError) -> ApiError {
+    match e {
+        AdminError::Permission(_) => ApiError::forbidden("Admin access required"),
+        AdminError::NotFound(_) => ApiError::not_found("Pending post not found"),
+        AdminError::InvalidOperation(msg) => ApiError::bad_request(msg),
+        AdminError::Hobbs(crate::HobbsError::Validation(msg)) => ApiError::bad_request(msg),
+        AdminError::Hobbs(crate::HobbsError::Permission(msg)) => ApiError::bad_request(msg),
+        e => {
+            tracing::error!("Moderation failed: {}", e);
+            ApiError::internal("Moderation failed")
+        }
+    }
+}
+
+/// GET /api/admin/moderation - List posts waiting for approval (admin).
+#[utoipa::path(
+    get,
+    path = "/admin/moderation",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Posts waiting for approval", body = Vec<PendingPostResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Admin access required")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn admin_list_pending_posts(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+) -> Result<Json<ApiResponse<Vec<PendingPostResponse>>>, ApiError> {
+    require_subop(&claims)?;
+    let admin = current_admin(&state, &claims).await?;
+
+    let pending = ModerationAdminService::new(&state.db)
+        .list_pending(&admin)
+        .await
+        .map_err(moderation_error)?;
+
+    let mut responses = Vec::with_capacity(pending.len());
+    for p in pending {
+        responses.push(pending_post_response(&state.db, p).await?);
+    }
+
+    Ok(Json(ApiResponse::new(responses)))
+}
+
+/// POST /api/admin/moderation/:id/approve - Approve a post (admin).
+#[utoipa::path(
+    post,
+    path = "/admin/moderation/{id}/approve",
+    tag = "admin",
+    params(
+        ("id" = i64, Path, description = "Pending post ID")
+    ),
+    responses(
+        (status = 200, description = "Post published", body = PostResponse),
+        (status = 400, description = "Post can no longer be published"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Pending post not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn admin_approve_post(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Path(pending_id): Path<i64>,
+) -> Result<Json<ApiResponse<PostResponse>>, ApiError> {
+    require_subop(&claims)?;
+    let admin = current_admin(&state, &claims).await?;
+
+    let post = ModerationAdminService::new(&state.db)
+        .approve(pending_id, &admin)
+        .await
+        .map_err(moderation_error)?;
+
+    let author = UserRepository::new(state.db.pool())
+        .get_by_id(post.author_id)
+        .await
+        .ok()
+        .flatten()
+        .map(|u| AuthorInfo {
+            id: u.id,
+            username: u.username,
+            nickname: u.nickname,
+        })
+        .unwrap_or_else(|| AuthorInfo {
+            id: post.author_id,
+            username: "unknown".to_string(),
+            nickname: "Unknown".to_string(),
+        });
+
+    let response = PostResponse {
+        id: post.id,
+        board_id: post.board_id,
+        thread_id: post.thread_id,
+        parent_id: post.parent_id,
+        depth: None,
+        author,
+        title: post.title,
+        body: post.body,
+        created_at: to_rfc3339(&post.created_at),
+        edited_at: post.edited_at.as_deref().map(to_rfc3339),
+    };
+
+    Ok(Json(ApiResponse::new(response)))
+}
+
+/// POST /api/admin/moderation/:id/reject - Reject a post (admin).
+///
+/// The reason is mailed to the author together with the rejected text.
+#[utoipa::path(
+    post,
+    path = "/admin/moderation/{id}/reject",
+    tag = "admin",
+    params(
+        ("id" = i64, Path, description = "Pending post ID")
+    ),
+    request_body = AdminRejectPostRequest,
+    responses(
+        (status = 200, description = "Post rejected"),
+        (status = 422, description = "Reason is required"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Pending post not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn admin_reject_post(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Path(pending_id): Path<i64>,
+    Json(req): Json<AdminRejectPostRequest>,
+) -> Result<Json<ApiResponse<()>>, ApiError> {
+    require_subop(&claims)?;
+    req.validate().map_err(ApiError::from_validation_errors)?;
+    let admin = current_admin(&state, &claims).await?;
+
+    ModerationAdminService::new(&state.db)
+        .reject(pending_id, &req.reason, &admin)
+        .await
+        .map_err(moderation_error)?;
+
+    Ok(Json(ApiResponse::new(())))
+}
+
+// ============================================================================
+// Thread Moderation
+// ============================================================================
+
+/// Map a thread moderation error to an API error.
+fn content_error(e: AdminError) -> ApiError {
+    match e {
+        AdminError::Permission(_) => ApiError::forbidden("Admin access required"),
+        AdminError::NotFound(what) => ApiError::not_found(match what.as_str() {
+            "スレッド" => "Thread not found",
+            "掲示板" => "Board not found",
+            _ => "Post not found",
+        }),
+        AdminError::InvalidOperation(msg) => ApiError::bad_request(msg),
+        AdminError::Hobbs(crate::HobbsError::Validation(msg)) => ApiError::bad_request(msg),
+        e => {
+            tracing::error!("Thread moderation failed: {}", e);
+            ApiError::internal("Thread moderation failed")
+        }
+    }
+}
+
+/// Build the response for a thread changed by a moderation tool.
+async fn admin_thread_response(state: &AppState, thread: Thread) -> ThreadResponse {
+    let author = UserRepository::new(state.db.pool())
+        .get_by_id(thread.author_id)
+        .await
+        .ok()
+        .flatten()
+        .map(|u| AuthorInfo {
+            id: u.id,
+            username: u.username,
+            nickname: u.nickname,
+        })
+        .unwrap_or_else(|| AuthorInfo {
+            id: thread.author_id,
+            username: "unknown".to_string(),
+            nickname: "Unknown".to_string(),
+        });
+
+    ThreadResponse {
+        id: thread.id,
+        board_id: thread.board_id,
+        title: thread.title,
+        author,
+        post_count: thread.post_count,
+        can_write: true,
+        created_at: to_rfc3339(&thread.created_at),
+        updated_at: to_rfc3339(&thread.updated_at),
+        is_pinned: thread.is_pinned,
+        is_locked: thread.is_locked,
+    }
+}
+
+/// POST /api/admin/threads/:id/move - Move a thread to another board (admin).
+#[utoipa::path(
+    post,
+    path = "/admin/threads/{id}/move",
+    tag = "admin",
+    params(
+        ("id" = i64, Path, description = "Thread ID")
+    ),
+    request_body = AdminMoveThreadRequest,
+    responses(
+        (status = 200, description = "Thread moved", body = ThreadResponse),
+        (status = 400, description = "Not a thread board, or the thread is already there"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Thread or board not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn admin_move_thread(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Path(thread_id): Path<i64>,
+    Json(req): Json<AdminMoveThreadRequest>,
+) -> Result<Json<ApiResponse<ThreadResponse>>, ApiError> {
+    require_subop(&claims)?;
+    let admin = current_admin(&state, &claims).await?;
+
+    let thread = ContentAdminService::new(state.db.pool())
+        .move_thread(thread_id, req.board_id, &admin)
+        .await
+        .map_err(content_error)?;
+
+    Ok(Json(ApiResponse::new(
+        admin_thread_response(&state, thread).await,
+    )))
+}
+
+/// POST /api/admin/threads/:id/merge - Merge a thread into another (admin).
+///
+/// The thread is deleted and its posts join the target in time order.
+#[utoipa::path(
+    post,
+    path = "/admin/threads/{id}/merge",
+    tag = "admin",
+    params(
+        ("id" = i64, Path, description = "Thread to merge")
+    ),
+    request_body = AdminMergeThreadRequest,
+    responses(
+        (status = 200, description = "Merged thread", body = ThreadResponse),
+        (status = 400, description = "Cannot merge a thread into itself"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Thread not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn admin_merge_thread(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Path(thread_id): Path<i64>,
+    Json(req): Json<AdminMergeThreadRequest>,
+) -> Result<Json<ApiResponse<ThreadResponse>>, ApiError> {
+    require_subop(&claims)?;
+    let admin = current_admin(&state, &claims).await?;
+
+    let thread = ContentAdminService::new(state.db.pool())
+        .merge_threads(thread_id, req.target_thread_id, &admin)
+        .await
+        .map_err(content_error)?;
+
+    Ok(Json(ApiResponse::new(
+        admin_thread_response(&state, thread).await,
+    )))
+}
+
+/// POST /api/admin/threads/:id/split - Split a thread at a post (admin).
+#[utoipa::path(
+    post,
+    path = "/admin/threads/{id}/split",
+    tag = "admin",
+    params(
+        ("id" = i64, Path, description = "Thread ID")
+    ),
+    request_body = AdminSplitThreadRequest,
+    responses(
+        (status = 200, description = "New thread", body = ThreadResponse),
+        (status = 400, description = "Post is the first post or not in the thread"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Post not found"),
+        (status = 422, description = "Validation error")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn admin_split_thread(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Path(thread_id): Path<i64>,
+    Json(req): Json<AdminSplitThreadRequest>,
+) -> Result<Json<ApiResponse<ThreadResponse>>, ApiError> {
+    require_subop(&claims)?;
+    req.validate().map_err(ApiError::from_validation_errors)?;
+    let admin = current_admin(&state, &claims).await?;
+
+    let service = ContentAdminService::new(state.db.pool());
+    let post = service
+        .get_post(req.post_id, &admin)
+        .await
+        .map_err(content_error)?;
+    if post.thread_id != Some(thread_id) {
+        return Err(ApiError::bad_request("Post is not in this thread"));
+    }
+
+    let thread = service
+        .split_thread(post.id, &req.title, &admin)
+        .await
+        .map_err(content_error)?;
+
+    Ok(Json(ApiResponse::new(
+        admin_thread_response(&state, thread).await,
+    )))
+}
+
+/// POST /api/admin/threads/:id/convert - Convert a thread into flat posts (admin).
+#[utoipa::path(
+    post,
+    path = "/admin/threads/{id}/convert",
+    tag = "admin",
+    params(
+        ("id" = i64, Path, description = "Thread ID")
+    ),
+    request_body = AdminConvertThreadRequest,
+    responses(
+        (status = 200, description = "Thread converted", body = AdminConvertThreadResponse),
+        (status = 400, description = "Not a flat board"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Thread or board not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn admin_convert_thread(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Path(thread_id): Path<i64>,
+    Json(req): Json<AdminConvertThreadRequest>,
+) -> Result<Json<ApiResponse<AdminConvertThreadResponse>>, ApiError> {
+    require_subop(&claims)?;
+    let admin = current_admin(&state, &claims).await?;
+
+    let posts = ContentAdminService::new(state.db.pool())
+        .convert_thread_to_flat(thread_id, req.board_id, &admin)
+        .await
+        .map_err(content_error)?;
+
+    Ok(Json(ApiResponse::new(AdminConvertThreadResponse {
+        board_id: req.board_id,
+        posts,
+    })))
+}
+
+/// POST /api/admin/posts/:id/convert - Convert a flat post into a thread (admin).
+#[utoipa::path(
+    post,
+    path = "/admin/posts/{id}/convert",
+    tag = "admin",
+    params(
+        ("id" = i64, Path, description = "Flat post ID")
+    ),
+    request_body = AdminConvertPostRequest,
+    responses(
+        (status = 200, description = "New thread", body = ThreadResponse),
+        (status = 400, description = "Not a flat post, not a thread board, or no title"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Post or board not found"),
+        (status = 422, description = "Validation error")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn admin_convert_post(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Path(post_id): Path<i64>,
+    Json(req): Json<AdminConvertPostRequest>,
+) -> Result<Json<ApiResponse<ThreadResponse>>, ApiError> {
+    require_subop(&claims)?;
+    req.validate().map_err(ApiError::from_validation_errors)?;
+    let admin = current_admin(&state, &claims).await?;
+
+    let thread = ContentAdminService::new(state.db.pool())
+        .convert_post_to_thread(post_id, req.board_id, req.title.as_deref(), &admin)
+        .await
+        .map_err(content_error)?;
+
+    Ok(Json(ApiResponse::new(
+        admin_thread_response(&state, thread).await,
+    )))
+}
+
+// ============================================================================
+// Content Filter
+// ============================================================================
+
+/// Map a content filter service error to an API error.
+fn filter_error(e: HobbsError) -> ApiError {
+    match e {
+        HobbsError::Permission(_) => ApiError::forbidden("SysOp access required"),
+        HobbsError::NotFound(_) => ApiError::not_found("Filter rule not found"),
+        HobbsError::Validation(msg) => ApiError::bad_request(msg),
+        e => {
+            tracing::error!("Content filter operation failed: {}", e);
+            ApiError::internal("Content filter operation failed")
+        }
+    }
+}
+
+fn filter_rule_response(rule: FilterRule) -> AdminFilterRuleResponse {
+    AdminFilterRuleResponse {
+        id: rule.id,
+        rule_type: rule.rule_type.to_string(),
+        pattern: rule.pattern,
+        action: rule.action.to_string(),
+        is_active: rule.is_active,
+        created_at: to_rfc3339(&rule.created_at),
+    }
+}
+
+/// GET /api/admin/filter-rules - List content filter rules (SysOp only).
+#[utoipa::path(
+    get,
+    path = "/admin/filter-rules",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Content filter rules", body = Vec<AdminFilterRuleResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "SysOp access required")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn admin_list_filter_rules(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+) -> Result<Json<ApiResponse<Vec<AdminFilterRuleResponse>>>, ApiError> {
+    let role = require_sysop(&claims)?;
+
+    let rules = ContentFilterService::new(state.db.pool())
+        .list_rules(role)
+        .await
+        .map_err(filter_error)?;
+
+    Ok(Json(ApiResponse::new(
+        rules.into_iter().map(filter_rule_response).collect(),
+    )))
+}
+
+/// POST /api/admin/filter-rules - Add a content filter rule (SysOp only).
+#[utoipa::path(
+    post,
+    path = "/admin/filter-rules",
+    tag = "admin",
+    request_body = AdminCreateFilterRuleRequest,
+    responses(
+        (status = 200, description = "Rule added", body = AdminFilterRuleResponse),
+        (status = 400, description = "Invalid rule type, action or pattern"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "SysOp access required")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn admin_create_filter_rule(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Json(req): Json<AdminCreateFilterRuleRequest>,
+) -> Result<Json<ApiResponse<AdminFilterRuleResponse>>, ApiError> {
+    let role = require_sysop(&claims)?;
+    req.validate().map_err(ApiError::from_validation_errors)?;
+
+    let rule_type: FilterRuleType = req
+        .rule_type
+        .parse()
+        .map_err(|_| ApiError::bad_request("Invalid rule_type"))?;
+    let action: FilterAction = req
+        .action
+        .parse()
+        .map_err(|_| ApiError::bad_request("Invalid action"))?;
+
+    let rule = ContentFilterService::new(state.db.pool())
+        .add_rule(
+            claims.sub,
+            role,
+            NewFilterRule::new(rule_type, req.pattern, action),
+        )
+        .await
+        .map_err(filter_error)?;
+
+    Ok(Json(ApiResponse::new(filter_rule_response(rule))))
+}
+
+/// PUT /api/admin/filter-rules/:id - Turn a content filter rule on or off (SysOp only).
+#[utoipa::path(
+    put,
+    path = "/admin/filter-rules/{id}",
+    tag = "admin",
+    params(
+        ("id" = i64, Path, description = "Filter rule ID")
+    ),
+    request_body = AdminUpdateFilterRuleRequest,
+    responses(
+        (status = 200, description = "Rule updated", body = AdminFilterRuleResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "SysOp access required"),
+        (status = 404, description = "Filter rule not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn admin_update_filter_rule(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Path(rule_id): Path<i64>,
+    Json(req): Json<AdminUpdateFilterRuleRequest>,
+) -> Result<Json<ApiResponse<AdminFilterRuleResponse>>, ApiError> {
+    let role = require_sysop(&claims)?;
+
+    let rule = ContentFilterService::new(state.db.pool())
+        .set_active(rule_id, req.is_active, role)
+        .await
+        .map_err(filter_error)?;
+
+    Ok(Json(ApiResponse::new(filter_rule_response(rule))))
+}
+
+/// DELETE /api/admin/filter-rules/:id - Delete a content filter rule (SysOp only).
+#[utoipa::path(
+    delete,
+    path = "/admin/filter-rules/{id}",
+    tag = "admin",
+    params(
+        ("id" = i64, Path, description = "Filter rule ID")
+    ),
+    responses(
+        (status = 200, description = "Rule deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "SysOp access required"),
+        (status = 404, description = "Filter rule not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn admin_delete_filter_rule(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Path(rule_id): Path<i64>,
+) -> Result<Json<ApiResponse<()>>, ApiError> {
+    let role = require_sysop(&claims)?;
+
+    ContentFilterService::new(state.db.pool())
+        .delete_rule(rule_id, role)
+        .await
+        .map_err(filter_error)?;
+
+    Ok(Json(ApiResponse::new(())))
+}
+
+/// GET /api/admin/filter-hits - List recent content filter hits (SysOp only).
+#[utoipa::path(
+    get,
+    path = "/admin/filter-hits",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Most recent hits, newest first", body = Vec<AdminFilterHitResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "SysOp access required")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn admin_list_filter_hits(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+) -> Result<Json<ApiResponse<Vec<AdminFilterHitResponse>>>, ApiError> {
+    let role = require_sysop(&claims)?;
+
+    let hits = ContentFilterService::new(state.db.pool())
+        .list_hits(role)
+        .await
+        .map_err(filter_error)?;
+
+    let responses = hits
+        .into_iter()
+        .map(|hit| AdminFilterHitResponse {
+            id: hit.id,
+            rule_id: hit.rule_id,
+            username: hit.username,
+            target: hit.target,
+            action: hit.action,
+            detail: hit.detail,
+            created_at: to_rfc3339(&hit.created_at),
+        })
+        .collect();
+
+    Ok(Json(ApiResponse::new(responses)))
+}
+
 // Note: Admin RSS management has been removed.
 // RSS is now a personal feature where each user manages their own feeds.
@@ -15,6 +15,8 @@ use crate::db::{
 };
 use crate::file::FileStorage;
 use crate::mail::MailRepository;
+use crate::notify::NotificationHub;
+use crate::qwk::PacketOptions;
 use crate::web::dto::{
     ApiResponse, LoginRequest, LoginResponse, LogoutRequest, MeResponse, OneTimeTokenRequest,
     OneTimeTokenResponse, RefreshRequest, RefreshResponse, RegisterRequest, UserInfo,
@@ -51,6 +53,14 @@ pub struct AppState {
     pub sysop_name: String,
     /// Whether Telnet server is enabled.
     pub telnet_enabled: bool,
+    /// QWK packet options.
+    pub qwk: PacketOptions,
+    /// Live delivery of subscription notifications.
+    pub notification_hub: Option<Arc<NotificationHub>>,
+    /// Domain for Message-IDs and addresses in exported mbox files.
+    pub message_domain: String,
+    /// Minimum role for creating polls.
+    pub poll_create_role: Role,
 }
 
 impl AppState {
@@ -73,6 +83,10 @@ impl AppState {
             bbs_description: "A retro BBS system".to_string(),
             sysop_name: "SysOp".to_string(),
             telnet_enabled: true, // Default to true
+            qwk: PacketOptions::default(),
+            notification_hub: None,
+            message_domain: "hobbs.invalid".to_string(),
+            poll_create_role: Role::Member,
         }
     }
 
@@ -108,6 +122,30 @@ impl AppState {
         self
     }
 
+    /// Set QWK packet options.
+    pub fn with_qwk(mut self, options: PacketOptions) -> Self {
+        self.qwk = options;
+        self
+    }
+
+    /// Set the notification hub.
+    pub fn with_notification_hub(mut self, hub: Arc<NotificationHub>) -> Self {
+        self.notification_hub = Some(hub);
+        self
+    }
+
+    /// Set the domain used in exported mbox files.
+    pub fn with_message_domain(mut self, domain: impl Into<String>) -> Self {
+        self.message_domain = domain.into();
+        self
+    }
+
+    /// Set the minimum role for creating polls.
+    pub fn with_poll_create_role(mut self, role: Role) -> Self {
+        self.poll_create_role = role;
+        self
+    }
+
     /// Generate an access token for a user.
     pub fn generate_access_token(
         &self,
@@ -2,25 +2,152 @@
 
 use axum::{
     extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
     Json,
 };
 use std::sync::Arc;
 use utoipa;
 
 use crate::board::{
-    BoardRepository, BoardService, BoardType, NewFlatPost, NewThread, NewThreadPost,
-    PostRepository, ThreadRepository,
+    diff_lines, reply_tree, Board, BoardRepository, BoardService, BoardType, NewFlatPost,
+    NewPendingPost, NewThread, NewThreadPost, PaginatedResult, Pagination, PendingPost,
+    PendingPostRepository, Post, PostRepository, PostVersion, ThreadRepository, UnreadRepository,
 };
 use crate::datetime::to_rfc3339;
-use crate::db::{Role, UserRepository};
+use crate::db::{Database, Role, UserRepository};
+use crate::filter::{ContentFilterService, FilterTarget};
 use crate::web::dto::{
-    ApiResponse, AuthorInfo, BoardResponse, CreateFlatPostRequest, CreatePostRequest,
-    CreateThreadRequest, PaginatedResponse, PaginationQuery, PostResponse, ThreadResponse,
-    UpdatePostRequest, UpdateThreadRequest,
+    ApiResponse, AuthorInfo, BoardGroupResponse, BoardResponse, BoardTreeResponse,
+    CreateFlatPostRequest, CreatePostRequest, CreateThreadRequest, DiffLineResponse,
+    PaginatedResponse, PaginationQuery, PendingPostResponse, PostDiffQuery, PostDiffResponse,
+    PostResponse, PostVersionResponse, SearchQuery, SearchResultResponse, ShowHiddenQuery,
+    ThreadPostsQuery, ThreadResponse, UpdatePostRequest, UpdateThreadRequest,
+    UpdateThreadStatusRequest,
 };
 use crate::web::error::ApiError;
+use crate::web::handlers::twit::load_kill_file;
 use crate::web::handlers::AppState;
-use crate::web::middleware::{AuthUser, OptionalAuthUser};
+use crate::web::middleware::{AuthUser, JwtClaims, OptionalAuthUser};
+
+/// Build the response for a post waiting for moderator approval.
+pub(crate) async fn pending_post_response(
+    db: &Database,
+    pending: PendingPost,
+) -> Result<PendingPostResponse, ApiError> {
+    let db_error = |e: crate::HobbsError| {
+        tracing::error!("Failed to load pending post details: {}", e);
+        ApiError::internal("Database error")
+    };
+
+    let board_name = BoardRepository::new(db.pool())
+        .get_by_id(pending.board_id)
+        .await
+        .map_err(db_error)?
+        .map(|b| b.name)
+        .unwrap_or_default();
+    let thread_title = match pending.thread_id {
+        Some(thread_id) => ThreadRepository::new(db.pool())
+            .get_by_id(thread_id)
+            .await
+            .map_err(db_error)?
+            .map(|t| t.title),
+        None => None,
+    };
+    let author = UserRepository::new(db.pool())
+        .get_by_id(pending.author_id)
+        .await
+        .map_err(db_error)?
+        .map(|u| AuthorInfo {
+            id: u.id,
+            username: u.username,
+            nickname: u.nickname,
+        })
+        .unwrap_or_else(|| AuthorInfo {
+            id: pending.author_id,
+            username: "unknown".to_string(),
+            nickname: "Unknown".to_string(),
+        });
+
+    Ok(PendingPostResponse {
+        id: pending.id,
+        board_id: pending.board_id,
+        board_name,
+        thread_id: pending.thread_id,
+        thread_title,
+        parent_id: pending.parent_id,
+        author,
+        title: pending.title,
+        body: pending.body,
+        created_at: to_rfc3339(&pending.created_at),
+    })
+}
+
+/// Queue a post on a moderated board and answer 202 Accepted.
+async fn queue_post(state: &AppState, new_post: NewPendingPost) -> Result<Response, ApiError> {
+    let pending = PendingPostRepository::new(state.db.pool())
+        .create(&new_post)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to queue post: {}", e);
+            ApiError::internal("Failed to queue post")
+        })?;
+    let response = pending_post_response(&state.db, pending).await?;
+    Ok((StatusCode::ACCEPTED, Json(ApiResponse::new(response))).into_response())
+}
+
+/// Run the content filter over a new post.
+///
+/// Masked text is replaced in `texts`. Returns true if the post must be held
+/// for moderator approval.
+async fn filter_post(
+    state: &AppState,
+    user_id: i64,
+    texts: &mut [String],
+) -> Result<bool, ApiError> {
+    ContentFilterService::new(state.db.pool())
+        .apply(Some(user_id), FilterTarget::Post, texts)
+        .await
+        .map(|verdict| verdict.is_hold())
+        .map_err(|e| match e {
+            crate::HobbsError::Validation(msg) => ApiError::bad_request(msg),
+            e => {
+                tracing::error!("Failed to filter post: {}", e);
+                ApiError::internal("Database error")
+            }
+        })
+}
+
+/// Build the response for a board readable by the user.
+async fn board_response(
+    db: &Database,
+    board: Board,
+    user_role: Role,
+    unread_count: Option<i64>,
+) -> BoardResponse {
+    let thread_count = ThreadRepository::new(db.pool())
+        .count_by_board(board.id)
+        .await
+        .unwrap_or(0);
+    let post_count = PostRepository::new(db.pool())
+        .count_by_board(board.id)
+        .await
+        .unwrap_or(0);
+
+    BoardResponse {
+        id: board.id,
+        can_read: board.can_read(user_role),
+        can_write: board.can_write(user_role),
+        name: board.name,
+        description: board.description,
+        board_type: board.board_type.as_str().to_string(),
+        thread_count,
+        post_count,
+        group_id: board.group_id,
+        unread_count,
+        created_at: to_rfc3339(&board.created_at),
+    }
+}
 
 /// GET /api/boards - List all accessible boards.
 #[utoipa::path(
@@ -45,32 +172,187 @@ pub async fn list_boards(
         ApiError::internal("Failed to list boards")
     })?;
 
-    let responses: Vec<BoardResponse> = {
-        let thread_repo = ThreadRepository::new(state.db.pool());
-        let post_repo = PostRepository::new(state.db.pool());
+    let mut responses = Vec::new();
+    for b in boards {
+        responses.push(board_response(&state.db, b, user_role, None).await);
+    }
+
+    Ok(Json(ApiResponse::new(responses)))
+}
+
+/// GET /api/boards/tree - List accessible boards grouped by board group.
+///
+/// Groups without readable boards are left out. Unread counts are included
+/// for signed-in users, rolled up per group.
+#[utoipa::path(
+    get,
+    path = "/boards/tree",
+    tag = "boards",
+    params(
+        ("show_hidden" = Option<bool>, Query, description = "Count unread posts hidden by twit filters")
+    ),
+    responses(
+        (status = 200, description = "Board groups with their boards", body = BoardTreeResponse)
+    )
+)]
+pub async fn get_board_tree(
+    State(state): State<Arc<AppState>>,
+    OptionalAuthUser(auth): OptionalAuthUser,
+    Query(hidden): Query<ShowHiddenQuery>,
+) -> Result<Json<ApiResponse<BoardTreeResponse>>, ApiError> {
+    let user_role = auth
+        .as_ref()
+        .map(|c| Role::from_str(&c.role).unwrap_or(Role::Guest))
+        .unwrap_or(Role::Guest);
+
+    let service = BoardService::new(&state.db);
+    let db_error = |e: crate::HobbsError| {
+        tracing::error!("Failed to list board tree: {}", e);
+        ApiError::internal("Failed to list boards")
+    };
+    let groups = service.list_groups(user_role).await.map_err(db_error)?;
+    let boards = service.list_boards(user_role).await.map_err(db_error)?;
+    let kill_file =
+        load_kill_file(&state.db, auth.as_ref().map(|c| c.sub), hidden.show_hidden).await?;
+    let unread = match auth {
+        Some(ref claims) => Some(
+            UnreadRepository::new(state.db.pool())
+                .with_kill_file(&kill_file)
+                .get_all_unread_counts(claims.sub, user_role)
+                .await
+                .map_err(db_error)?,
+        ),
+        None => None,
+    };
+
+    let mut tree = BoardTreeResponse {
+        groups: groups
+            .into_iter()
+            .map(|g| BoardGroupResponse {
+                id: g.id,
+                name: g.name,
+                description: g.description,
+                unread_count: unread.as_ref().map(|u| u.group(g.id)),
+                boards: Vec::new(),
+            })
+            .collect(),
+        boards: Vec::new(),
+    };
+    for board in boards {
+        let group_id = board.group_id;
+        let unread_count = unread.as_ref().map(|u| u.board(board.id));
+        let response = board_response(&state.db, board, user_role, unread_count).await;
+        match tree.groups.iter_mut().find(|g| Some(g.id) == group_id) {
+            Some(group) => group.boards.push(response),
+            None => tree.boards.push(response),
+        }
+    }
+    tree.groups.retain(|g| !g.boards.is_empty());
+
+    Ok(Json(ApiResponse::new(tree)))
+}
+
+/// GET /api/boards/search - Search posts in accessible boards.
+#[utoipa::path(
+    get,
+    path = "/boards/search",
+    tag = "boards",
+    params(
+        ("q" = String, Query, description = "Search words"),
+        ("board_id" = Option<i64>, Query, description = "Restrict to a board"),
+        ("page" = Option<u32>, Query, description = "Page number"),
+        ("per_page" = Option<u32>, Query, description = "Items per page")
+    ),
+    responses(
+        (status = 200, description = "Matching posts, newest first", body = Vec<SearchResultResponse>),
+        (status = 400, description = "Empty search words"),
+        (status = 403, description = "Access denied"),
+        (status = 404, description = "Board not found")
+    )
+)]
+pub async fn search_posts(
+    State(state): State<Arc<AppState>>,
+    OptionalAuthUser(auth): OptionalAuthUser,
+    Query(query): Query<SearchQuery>,
+) -> Result<Json<PaginatedResponse<SearchResultResponse>>, ApiError> {
+    let user_role = auth
+        .map(|c| Role::from_str(&c.role).unwrap_or(Role::Guest))
+        .unwrap_or(Role::Guest);
+
+    let pagination = query.pagination();
+    let (offset, limit) = pagination.to_offset_limit();
+
+    let result = {
+        let service = BoardService::new(&state.db);
+
+        service
+            .search(
+                &query.q,
+                query.board_id,
+                user_role,
+                crate::board::Pagination::new(offset, limit),
+            )
+            .await
+            .map_err(|e| match e {
+                crate::HobbsError::NotFound(_) => ApiError::not_found("Board not found"),
+                crate::HobbsError::Permission(_) => ApiError::forbidden("Access denied"),
+                crate::HobbsError::Validation(msg) => ApiError::bad_request(msg),
+                e => {
+                    tracing::error!("Failed to search posts: {}", e);
+                    ApiError::internal("Database error")
+                }
+            })?
+    };
+
+    // Get author info for each post
+    let responses = {
+        let user_repo = UserRepository::new(state.db.pool());
 
         let mut responses = Vec::new();
-        for b in boards {
-            let can_read = b.can_read(user_role);
-            let can_write = b.can_write(user_role);
-            let thread_count = thread_repo.count_by_board(b.id).await.unwrap_or(0);
-            let post_count = post_repo.count_by_board(b.id).await.unwrap_or(0);
-            responses.push(BoardResponse {
-                id: b.id,
-                name: b.name,
-                description: b.description,
-                board_type: b.board_type.as_str().to_string(),
-                thread_count,
-                post_count,
-                can_read,
-                can_write,
-                created_at: to_rfc3339(&b.created_at),
+        for hit in result.items {
+            let p = hit.post;
+            let author = user_repo
+                .get_by_id(p.author_id)
+                .await
+                .ok()
+                .flatten()
+                .map(|u| AuthorInfo {
+                    id: u.id,
+                    username: u.username,
+                    nickname: u.nickname,
+                })
+                .unwrap_or_else(|| AuthorInfo {
+                    id: p.author_id,
+                    username: "unknown".to_string(),
+                    nickname: "Unknown".to_string(),
+                });
+
+            responses.push(SearchResultResponse {
+                post: PostResponse {
+                    id: p.id,
+                    board_id: p.board_id,
+                    thread_id: p.thread_id,
+                    parent_id: p.parent_id,
+                    depth: None,
+                    author,
+                    title: p.title,
+                    body: p.body,
+                    created_at: to_rfc3339(&p.created_at),
+                    edited_at: p.edited_at.as_deref().map(to_rfc3339),
+                },
+                board_name: hit.board_name,
+                thread_title: hit.thread_title,
             });
         }
         responses
     };
 
-    Ok(Json(ApiResponse::new(responses)))
+    Ok(Json(PaginatedResponse::new(
+        responses,
+        pagination.page,
+        pagination.per_page,
+        result.total as u64,
+    )))
 }
 
 /// GET /api/boards/:id - Get board details.
@@ -109,27 +391,7 @@ pub async fn get_board(
         return Err(ApiError::forbidden("Access denied"));
     }
 
-    let can_read = board.can_read(user_role);
-    let can_write = board.can_write(user_role);
-
-    let thread_repo = ThreadRepository::new(state.db.pool());
-    let post_repo = PostRepository::new(state.db.pool());
-    let (thread_count, post_count) = (
-        thread_repo.count_by_board(board.id).await.unwrap_or(0),
-        post_repo.count_by_board(board.id).await.unwrap_or(0),
-    );
-
-    let response = BoardResponse {
-        id: board.id,
-        name: board.name,
-        description: board.description,
-        board_type: board.board_type.as_str().to_string(),
-        thread_count,
-        post_count,
-        can_read,
-        can_write,
-        created_at: to_rfc3339(&board.created_at),
-    };
+    let response = board_response(&state.db, board, user_role, None).await;
 
     Ok(Json(ApiResponse::new(response)))
 }
@@ -142,7 +404,8 @@ pub async fn get_board(
     params(
         ("id" = i64, Path, description = "Board ID"),
         ("page" = Option<u32>, Query, description = "Page number"),
-        ("per_page" = Option<u32>, Query, description = "Items per page")
+        ("per_page" = Option<u32>, Query, description = "Items per page"),
+        ("show_hidden" = Option<bool>, Query, description = "Include content hidden by twit filters")
     ),
     responses(
         (status = 200, description = "List of threads", body = Vec<ThreadResponse>),
@@ -155,7 +418,10 @@ pub async fn list_threads(
     OptionalAuthUser(auth): OptionalAuthUser,
     Path(board_id): Path<i64>,
     Query(pagination): Query<PaginationQuery>,
+    Query(hidden): Query<ShowHiddenQuery>,
 ) -> Result<Json<PaginatedResponse<ThreadResponse>>, ApiError> {
+    let kill_file =
+        load_kill_file(&state.db, auth.as_ref().map(|c| c.sub), hidden.show_hidden).await?;
     let user_role = auth
         .map(|c| Role::from_str(&c.role).unwrap_or(Role::Guest))
         .unwrap_or(Role::Guest);
@@ -183,20 +449,32 @@ pub async fn list_threads(
             return Err(ApiError::bad_request("This board does not support threads"));
         }
 
-        let threads = thread_repo
-            .list_by_board_paginated(board_id, offset, limit)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to list threads: {}", e);
+        let list_error = |e: crate::HobbsError| {
+            tracing::error!("Failed to list threads: {}", e);
+            ApiError::internal("Database error")
+        };
+        if kill_file.is_empty() {
+            let threads = thread_repo
+                .list_by_board_paginated(board_id, offset, limit)
+                .await
+                .map_err(list_error)?;
+
+            let total = thread_repo.count_by_board(board_id).await.map_err(|e| {
+                tracing::error!("Failed to count threads: {}", e);
                 ApiError::internal("Database error")
             })?;
 
-        let total = thread_repo.count_by_board(board_id).await.map_err(|e| {
-            tracing::error!("Failed to count threads: {}", e);
-            ApiError::internal("Database error")
-        })?;
+            (board, threads, total)
+        } else {
+            let mut threads = thread_repo
+                .list_by_board(board_id)
+                .await
+                .map_err(list_error)?;
+            threads.retain(|t| !kill_file.hides_thread(t));
+            let page = PaginatedResult::from_all(threads, Pagination::new(offset, limit));
 
-        (board, threads, total)
+            (board, page.items, page.total)
+        }
     };
 
     // Get author info for each thread
@@ -230,6 +508,8 @@ pub async fn list_threads(
                 can_write: board.can_write(user_role),
                 created_at: to_rfc3339(&t.created_at),
                 updated_at: to_rfc3339(&t.updated_at),
+                is_pinned: t.is_pinned,
+                is_locked: t.is_locked,
             });
         }
         responses
@@ -254,6 +534,7 @@ pub async fn list_threads(
     request_body = CreateThreadRequest,
     responses(
         (status = 200, description = "Thread created", body = ThreadResponse),
+        (status = 202, description = "Queued for moderator approval", body = PendingPostResponse),
         (status = 400, description = "Invalid input"),
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Access denied")
@@ -267,7 +548,7 @@ pub async fn create_thread(
     AuthUser(claims): AuthUser,
     Path(board_id): Path<i64>,
     Json(req): Json<CreateThreadRequest>,
-) -> Result<Json<ApiResponse<ThreadResponse>>, ApiError> {
+) -> Result<Response, ApiError> {
     let user_role = Role::from_str(&claims.role).unwrap_or(Role::Guest);
 
     // Validate input
@@ -302,15 +583,24 @@ pub async fn create_thread(
             return Err(ApiError::bad_request("This board does not support threads"));
         }
 
+        let mut texts = [req.title, req.body];
+        let held = filter_post(&state, claims.sub, &mut texts).await?;
+        let [title, body] = texts;
+
+        if held || board.requires_approval(user_role) {
+            let new_post = NewPendingPost::thread(board_id, claims.sub, &title, &body);
+            return queue_post(&state, new_post).await;
+        }
+
         // Create thread
-        let new_thread = NewThread::new(board_id, &req.title, claims.sub);
+        let new_thread = NewThread::new(board_id, &title, claims.sub);
         let thread = thread_repo.create(&new_thread).await.map_err(|e| {
             tracing::error!("Failed to create thread: {}", e);
             ApiError::internal("Failed to create thread")
         })?;
 
         // Create first post
-        let new_post = NewThreadPost::new(board_id, thread.id, claims.sub, &req.body);
+        let new_post = NewThreadPost::new(board_id, thread.id, claims.sub, &body);
         post_repo.create_thread_post(&new_post).await.map_err(|e| {
             tracing::error!("Failed to create post: {}", e);
             ApiError::internal("Failed to create post")
@@ -355,9 +645,11 @@ pub async fn create_thread(
         can_write: true, // User has write permission (checked above)
         created_at: to_rfc3339(&thread.created_at),
         updated_at: to_rfc3339(&thread.updated_at),
+        is_pinned: thread.is_pinned,
+        is_locked: thread.is_locked,
     };
 
-    Ok(Json(ApiResponse::new(response)))
+    Ok(Json(ApiResponse::new(response)).into_response())
 }
 
 /// GET /api/boards/:id/posts - List posts in a flat board.
@@ -368,7 +660,8 @@ pub async fn create_thread(
     params(
         ("id" = i64, Path, description = "Board ID"),
         ("page" = Option<u32>, Query, description = "Page number"),
-        ("per_page" = Option<u32>, Query, description = "Items per page")
+        ("per_page" = Option<u32>, Query, description = "Items per page"),
+        ("show_hidden" = Option<bool>, Query, description = "Include content hidden by twit filters")
     ),
     responses(
         (status = 200, description = "List of posts", body = Vec<PostResponse>),
@@ -382,7 +675,10 @@ pub async fn list_flat_posts(
     OptionalAuthUser(auth): OptionalAuthUser,
     Path(board_id): Path<i64>,
     Query(pagination): Query<PaginationQuery>,
+    Query(hidden): Query<ShowHiddenQuery>,
 ) -> Result<Json<PaginatedResponse<PostResponse>>, ApiError> {
+    let kill_file =
+        load_kill_file(&state.db, auth.as_ref().map(|c| c.sub), hidden.show_hidden).await?;
     let user_role = auth
         .map(|c| Role::from_str(&c.role).unwrap_or(Role::Guest))
         .unwrap_or(Role::Guest);
@@ -412,20 +708,32 @@ pub async fn list_flat_posts(
             ));
         }
 
-        let posts = post_repo
-            .list_by_flat_board_paginated(board_id, offset, limit)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to list posts: {}", e);
+        let list_error = |e: crate::HobbsError| {
+            tracing::error!("Failed to list posts: {}", e);
+            ApiError::internal("Database error")
+        };
+        if kill_file.is_empty() {
+            let posts = post_repo
+                .list_by_flat_board_paginated(board_id, offset, limit)
+                .await
+                .map_err(list_error)?;
+
+            let total = post_repo.count_by_flat_board(board_id).await.map_err(|e| {
+                tracing::error!("Failed to count posts: {}", e);
                 ApiError::internal("Database error")
             })?;
 
-        let total = post_repo.count_by_flat_board(board_id).await.map_err(|e| {
-            tracing::error!("Failed to count posts: {}", e);
-            ApiError::internal("Database error")
-        })?;
+            (posts, total)
+        } else {
+            let mut posts = post_repo
+                .list_by_flat_board(board_id)
+                .await
+                .map_err(list_error)?;
+            posts.retain(|p| !kill_file.hides_post(p));
+            let page = PaginatedResult::from_all(posts, Pagination::new(offset, limit));
 
-        (posts, total)
+            (page.items, page.total)
+        }
     };
 
     // Get author info for each post
@@ -454,10 +762,13 @@ pub async fn list_flat_posts(
                 id: p.id,
                 board_id: p.board_id,
                 thread_id: p.thread_id,
+                parent_id: p.parent_id,
+                depth: None,
                 author,
                 title: p.title,
                 body: p.body,
                 created_at: to_rfc3339(&p.created_at),
+                edited_at: p.edited_at.as_deref().map(to_rfc3339),
             });
         }
         responses
@@ -482,6 +793,7 @@ pub async fn list_flat_posts(
     request_body = CreateFlatPostRequest,
     responses(
         (status = 200, description = "Post created", body = PostResponse),
+        (status = 202, description = "Queued for moderator approval", body = PendingPostResponse),
         (status = 400, description = "Invalid input or board type mismatch"),
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Access denied")
@@ -495,7 +807,7 @@ pub async fn create_flat_post(
     AuthUser(claims): AuthUser,
     Path(board_id): Path<i64>,
     Json(req): Json<CreateFlatPostRequest>,
-) -> Result<Json<ApiResponse<PostResponse>>, ApiError> {
+) -> Result<Response, ApiError> {
     let user_role = Role::from_str(&claims.role).unwrap_or(Role::Guest);
 
     // Validate input
@@ -531,8 +843,17 @@ pub async fn create_flat_post(
             ));
         }
 
+        let mut texts = [req.title, req.body];
+        let held = filter_post(&state, claims.sub, &mut texts).await?;
+        let [title, body] = texts;
+
+        if held || board.requires_approval(user_role) {
+            let new_post = NewPendingPost::flat(board_id, claims.sub, &title, &body);
+            return queue_post(&state, new_post).await;
+        }
+
         // Create post
-        let new_post = NewFlatPost::new(board_id, claims.sub, &req.title, &req.body);
+        let new_post = NewFlatPost::new(board_id, claims.sub, &title, &body);
         let post = post_repo.create_flat_post(&new_post).await.map_err(|e| {
             tracing::error!("Failed to create post: {}", e);
             ApiError::internal("Failed to create post")
@@ -562,13 +883,16 @@ pub async fn create_flat_post(
         id: post.id,
         board_id: post.board_id,
         thread_id: post.thread_id,
+        parent_id: post.parent_id,
+        depth: None,
         author,
         title: post.title,
         body: post.body,
         created_at: to_rfc3339(&post.created_at),
+        edited_at: post.edited_at.as_deref().map(to_rfc3339),
     };
 
-    Ok(Json(ApiResponse::new(response)))
+    Ok(Json(ApiResponse::new(response)).into_response())
 }
 
 /// GET /api/threads/:id - Get thread details.
@@ -652,6 +976,8 @@ pub async fn get_thread(
         can_write,
         created_at: to_rfc3339(&thread.created_at),
         updated_at: to_rfc3339(&thread.updated_at),
+        is_pinned: thread.is_pinned,
+        is_locked: thread.is_locked,
     };
 
     Ok(Json(ApiResponse::new(response)))
@@ -665,7 +991,9 @@ pub async fn get_thread(
     params(
         ("id" = i64, Path, description = "Thread ID"),
         ("page" = Option<u32>, Query, description = "Page number"),
-        ("per_page" = Option<u32>, Query, description = "Items per page")
+        ("per_page" = Option<u32>, Query, description = "Items per page"),
+        ("tree" = Option<bool>, Query, description = "List as a reply tree, oldest first"),
+        ("show_hidden" = Option<bool>, Query, description = "Include content hidden by twit filters")
     ),
     responses(
         (status = 200, description = "List of posts", body = Vec<PostResponse>),
@@ -677,12 +1005,16 @@ pub async fn list_thread_posts(
     State(state): State<Arc<AppState>>,
     OptionalAuthUser(auth): OptionalAuthUser,
     Path(thread_id): Path<i64>,
-    Query(pagination): Query<PaginationQuery>,
+    Query(query): Query<ThreadPostsQuery>,
+    Query(hidden): Query<ShowHiddenQuery>,
 ) -> Result<Json<PaginatedResponse<PostResponse>>, ApiError> {
+    let kill_file =
+        load_kill_file(&state.db, auth.as_ref().map(|c| c.sub), hidden.show_hidden).await?;
     let user_role = auth
         .map(|c| Role::from_str(&c.role).unwrap_or(Role::Guest))
         .unwrap_or(Role::Guest);
 
+    let pagination = query.pagination();
     let (offset, limit) = pagination.to_offset_limit();
 
     let (posts, total) = {
@@ -713,20 +1045,50 @@ pub async fn list_thread_posts(
             return Err(ApiError::forbidden("Access denied"));
         }
 
-        let posts = post_repo
-            .list_by_thread_paginated(thread_id, offset, limit)
-            .await
-            .map_err(|e| {
+        if query.tree {
+            let posts = post_repo.list_by_thread(thread_id).await.map_err(|e| {
                 tracing::error!("Failed to list posts: {}", e);
                 ApiError::internal("Database error")
             })?;
+            // Replies to a hidden post keep their place in the tree
+            let mut tree = reply_tree(posts);
+            tree.retain(|t| !kill_file.hides_post(&t.post));
+            let total = tree.len() as i64;
+            let page: Vec<(Post, Option<usize>)> = tree
+                .into_iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .map(|t| (t.post, Some(t.depth)))
+                .collect();
+            (page, total)
+        } else if kill_file.is_empty() {
+            let posts = post_repo
+                .list_by_thread_paginated(thread_id, offset, limit)
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to list posts: {}", e);
+                    ApiError::internal("Database error")
+                })?;
 
-        let total = post_repo.count_by_thread(thread_id).await.map_err(|e| {
-            tracing::error!("Failed to count posts: {}", e);
-            ApiError::internal("Database error")
-        })?;
+            let total = post_repo.count_by_thread(thread_id).await.map_err(|e| {
+                tracing::error!("Failed to count posts: {}", e);
+                ApiError::internal("Database error")
+            })?;
 
-        (posts, total)
+            (posts.into_iter().map(|p| (p, None)).collect(), total)
+        } else {
+            let mut posts = post_repo.list_by_thread(thread_id).await.map_err(|e| {
+                tracing::error!("Failed to list posts: {}", e);
+                ApiError::internal("Database error")
+            })?;
+            posts.retain(|p| !kill_file.hides_post(p));
+            let page = PaginatedResult::from_all(posts, Pagination::new(offset, limit));
+
+            (
+                page.items.into_iter().map(|p| (p, None)).collect(),
+                page.total,
+            )
+        }
     };
 
     // Get author info for each post
@@ -734,7 +1096,7 @@ pub async fn list_thread_posts(
         let user_repo = UserRepository::new(state.db.pool());
 
         let mut responses = Vec::new();
-        for p in posts {
+        for (p, depth) in posts {
             let author = user_repo
                 .get_by_id(p.author_id)
                 .await
@@ -755,10 +1117,13 @@ pub async fn list_thread_posts(
                 id: p.id,
                 board_id: p.board_id,
                 thread_id: p.thread_id,
+                parent_id: p.parent_id,
+                depth,
                 author,
                 title: p.title,
                 body: p.body,
                 created_at: to_rfc3339(&p.created_at),
+                edited_at: p.edited_at.as_deref().map(to_rfc3339),
             });
         }
         responses
@@ -783,9 +1148,10 @@ pub async fn list_thread_posts(
     request_body = CreatePostRequest,
     responses(
         (status = 200, description = "Post created", body = PostResponse),
-        (status = 400, description = "Invalid input"),
+        (status = 202, description = "Queued for moderator approval", body = PendingPostResponse),
+        (status = 400, description = "Invalid input or parent post not in this thread"),
         (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Access denied"),
+        (status = 403, description = "Access denied or thread is locked"),
         (status = 404, description = "Thread not found")
     ),
     security(
@@ -797,7 +1163,7 @@ pub async fn create_thread_post(
     AuthUser(claims): AuthUser,
     Path(thread_id): Path<i64>,
     Json(req): Json<CreatePostRequest>,
-) -> Result<Json<ApiResponse<PostResponse>>, ApiError> {
+) -> Result<Response, ApiError> {
     let user_role = Role::from_str(&claims.role).unwrap_or(Role::Guest);
 
     // Validate input
@@ -834,8 +1200,39 @@ pub async fn create_thread_post(
             return Err(ApiError::forbidden("Write access denied"));
         }
 
+        if thread.is_locked {
+            return Err(ApiError::forbidden("Thread is locked"));
+        }
+
+        let mut texts = [req.body];
+        let held = filter_post(&state, claims.sub, &mut texts).await?;
+        let [body] = texts;
+
+        // The post being replied to must be in the same thread
+        let mut new_post = NewThreadPost::new(thread.board_id, thread_id, claims.sub, &body);
+        if let Some(parent_id) = req.parent_id {
+            let parent = post_repo.get_by_id(parent_id).await.map_err(|e| {
+                tracing::error!("Failed to get post: {}", e);
+                ApiError::internal("Database error")
+            })?;
+            if parent.and_then(|p| p.thread_id) != Some(thread_id) {
+                return Err(ApiError::bad_request("Parent post is not in this thread"));
+            }
+            new_post = new_post.reply_to(parent_id);
+        }
+
+        if held || board.requires_approval(user_role) {
+            let pending = NewPendingPost::reply(
+                thread.board_id,
+                thread_id,
+                new_post.parent_id,
+                claims.sub,
+                &body,
+            );
+            return queue_post(&state, pending).await;
+        }
+
         // Create post
-        let new_post = NewThreadPost::new(thread.board_id, thread_id, claims.sub, &req.body);
         let post = post_repo.create_thread_post(&new_post).await.map_err(|e| {
             tracing::error!("Failed to create post: {}", e);
             ApiError::internal("Failed to create post")
@@ -871,13 +1268,16 @@ pub async fn create_thread_post(
         id: post.id,
         board_id: post.board_id,
         thread_id: post.thread_id,
+        parent_id: post.parent_id,
+        depth: None,
         author,
         title: post.title,
         body: post.body,
         created_at: to_rfc3339(&post.created_at),
+        edited_at: post.edited_at.as_deref().map(to_rfc3339),
     };
 
-    Ok(Json(ApiResponse::new(response)))
+    Ok(Json(ApiResponse::new(response)).into_response())
 }
 
 /// DELETE /api/posts/:id - Delete a post.
@@ -1002,6 +1402,8 @@ pub async fn update_post(
     let response = PostResponse {
         id: post.id,
         thread_id: post.thread_id,
+        parent_id: post.parent_id,
+        depth: None,
         board_id: post.board_id,
         title: post.title,
         body: post.body,
@@ -1017,11 +1419,138 @@ pub async fn update_post(
                 nickname: "Unknown".to_string(),
             }),
         created_at: to_rfc3339(&post.created_at),
+        edited_at: post.edited_at.as_deref().map(to_rfc3339),
     };
 
     Ok(Json(ApiResponse::new(response)))
 }
 
+/// GET /api/posts/:id/revisions - List every version of a post.
+#[utoipa::path(
+    get,
+    path = "/posts/{id}/revisions",
+    tag = "posts",
+    params(
+        ("id" = i64, Path, description = "Post ID")
+    ),
+    responses(
+        (status = 200, description = "Versions, oldest first", body = Vec<PostVersionResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Only the author and SubOp+ can view the history"),
+        (status = 404, description = "Post not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn list_post_revisions(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Path(post_id): Path<i64>,
+) -> Result<Json<ApiResponse<Vec<PostVersionResponse>>>, ApiError> {
+    let versions = get_post_history(&state, &claims, post_id).await?;
+
+    let responses = {
+        let user_repo = UserRepository::new(state.db.pool());
+
+        let mut responses = Vec::new();
+        for v in versions {
+            let editor = match v.editor_id {
+                Some(id) => user_repo.get_by_id(id).await.ok().flatten().map(|u| AuthorInfo {
+                    id: u.id,
+                    username: u.username,
+                    nickname: u.nickname,
+                }),
+                None => None,
+            };
+
+            responses.push(PostVersionResponse {
+                number: v.number,
+                title: v.title,
+                body: v.body,
+                editor,
+                created_at: to_rfc3339(&v.created_at),
+                current: v.current,
+            });
+        }
+        responses
+    };
+
+    Ok(Json(ApiResponse::new(responses)))
+}
+
+/// GET /api/posts/:id/revisions/diff - Diff two versions of a post.
+#[utoipa::path(
+    get,
+    path = "/posts/{id}/revisions/diff",
+    tag = "posts",
+    params(
+        ("id" = i64, Path, description = "Post ID"),
+        ("from" = Option<usize>, Query, description = "Older version (default: the one before `to`)"),
+        ("to" = Option<usize>, Query, description = "Newer version (default: current)")
+    ),
+    responses(
+        (status = 200, description = "Line diff", body = PostDiffResponse),
+        (status = 400, description = "Unknown version number"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Only the author and SubOp+ can view the history"),
+        (status = 404, description = "Post not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn diff_post_revisions(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Path(post_id): Path<i64>,
+    Query(query): Query<PostDiffQuery>,
+) -> Result<Json<ApiResponse<PostDiffResponse>>, ApiError> {
+    let versions = get_post_history(&state, &claims, post_id).await?;
+
+    let to = query.to.unwrap_or(versions.len());
+    let from = query.from.unwrap_or(to.saturating_sub(1).max(1));
+    let version = |number: usize| {
+        number
+            .checked_sub(1)
+            .and_then(|idx| versions.get(idx))
+            .ok_or_else(|| ApiError::bad_request(format!("No version {}", number)))
+    };
+    let lines = diff_lines(&version(from)?.body, &version(to)?.body)
+        .into_iter()
+        .map(|line| DiffLineResponse {
+            op: line.prefix().to_string(),
+            text: line.text().to_string(),
+        })
+        .collect();
+
+    Ok(Json(ApiResponse::new(PostDiffResponse { from, to, lines })))
+}
+
+/// Load the versions of a post for the revision endpoints.
+async fn get_post_history(
+    state: &AppState,
+    claims: &JwtClaims,
+    post_id: i64,
+) -> Result<Vec<PostVersion>, ApiError> {
+    let user_role = Role::from_str(&claims.role).unwrap_or(Role::Guest);
+    let service = BoardService::new(&state.db);
+
+    service
+        .post_history(post_id, Some(claims.sub), user_role)
+        .await
+        .map_err(|e| match e {
+            crate::HobbsError::NotFound(_) => ApiError::not_found("Post not found"),
+            crate::HobbsError::Permission(_) => {
+                ApiError::forbidden("Only the author and moderators can view the history")
+            }
+            e => {
+                tracing::error!("Failed to get post history: {}", e);
+                ApiError::internal("Database error")
+            }
+        })
+}
+
 /// PATCH /api/threads/:id - Update a thread title.
 #[utoipa::path(
     patch,
@@ -1102,6 +1631,93 @@ pub async fn update_thread(
         can_write,
         created_at: to_rfc3339(&thread.created_at),
         updated_at: to_rfc3339(&thread.updated_at),
+        is_pinned: thread.is_pinned,
+        is_locked: thread.is_locked,
+    };
+
+    Ok(Json(ApiResponse::new(response)))
+}
+
+/// PUT /api/threads/:id/status - Pin/unpin or lock/unlock a thread.
+#[utoipa::path(
+    put,
+    path = "/threads/{id}/status",
+    tag = "threads",
+    params(
+        ("id" = i64, Path, description = "Thread ID")
+    ),
+    request_body = UpdateThreadStatusRequest,
+    responses(
+        (status = 200, description = "Updated thread", body = ThreadResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - SubOp or higher only"),
+        (status = 404, description = "Thread not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn update_thread_status(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Path(thread_id): Path<i64>,
+    Json(request): Json<UpdateThreadStatusRequest>,
+) -> Result<Json<ApiResponse<ThreadResponse>>, ApiError> {
+    let user_role = Role::from_str(&claims.role).unwrap_or(Role::Guest);
+
+    let thread = {
+        let service = BoardService::new(&state.db);
+
+        service
+            .set_thread_flags(thread_id, user_role, request.is_pinned, request.is_locked)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to update thread status: {}", e);
+                match e {
+                    crate::HobbsError::NotFound(_) => ApiError::not_found("Thread not found"),
+                    crate::HobbsError::Permission(_) => {
+                        ApiError::forbidden("Only moderators can pin or lock threads")
+                    }
+                    _ => ApiError::internal("Failed to update thread"),
+                }
+            })?
+    };
+
+    let (author, can_write) = {
+        let user_repo = UserRepository::new(state.db.pool());
+        let board_repo = BoardRepository::new(state.db.pool());
+        let author = user_repo.get_by_id(thread.author_id).await.ok().flatten();
+        let can_write = board_repo
+            .get_by_id(thread.board_id)
+            .await
+            .ok()
+            .flatten()
+            .map(|b| b.can_write(user_role))
+            .unwrap_or(false);
+        (author, can_write)
+    };
+
+    let response = ThreadResponse {
+        id: thread.id,
+        board_id: thread.board_id,
+        title: thread.title,
+        author: author
+            .map(|u| AuthorInfo {
+                id: u.id,
+                username: u.username,
+                nickname: u.nickname,
+            })
+            .unwrap_or_else(|| AuthorInfo {
+                id: thread.author_id,
+                username: "unknown".to_string(),
+                nickname: "Unknown".to_string(),
+            }),
+        post_count: thread.post_count,
+        can_write,
+        created_at: to_rfc3339(&thread.created_at),
+        updated_at: to_rfc3339(&thread.updated_at),
+        is_pinned: thread.is_pinned,
+        is_locked: thread.is_locked,
     };
 
     Ok(Json(ApiResponse::new(response)))
@@ -0,0 +1,186 @@
+//! Bookmark handlers for Web API.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use std::str::FromStr;
+use std::sync::Arc;
+use utoipa;
+
+use crate::bookmark::{Bookmark, BookmarkService, BookmarkTarget};
+use crate::datetime::to_rfc3339;
+use crate::db::Role;
+use crate::web::dto::{
+    ApiResponse, BookmarkResponse, CreateBookmarkRequest, UpdateBookmarkRequest,
+};
+use crate::web::error::ApiError;
+use crate::web::handlers::AppState;
+use crate::web::middleware::AuthUser;
+use crate::HobbsError;
+
+impl From<Bookmark> for BookmarkResponse {
+    fn from(b: Bookmark) -> Self {
+        Self {
+            deleted: b.is_deleted(),
+            id: b.id,
+            thread_id: b.thread_id,
+            post_id: b.post_id,
+            post_thread_id: b.post_thread_id,
+            board_id: b.board_id,
+            board_name: b.board_name,
+            title: b.title,
+            note: b.note,
+            created_at: to_rfc3339(&b.created_at),
+        }
+    }
+}
+
+/// Map a bookmark service error to an API error.
+fn service_error(not_found: &'static str) -> impl Fn(HobbsError) -> ApiError {
+    move |e| match e {
+        HobbsError::NotFound(_) => ApiError::not_found(not_found),
+        HobbsError::Permission(_) => ApiError::forbidden("Access denied"),
+        HobbsError::Validation(msg) => ApiError::bad_request(msg),
+        e => {
+            tracing::error!("Bookmark service error: {}", e);
+            ApiError::internal("Database error")
+        }
+    }
+}
+
+/// GET /api/bookmarks - List the user's bookmarks.
+///
+/// Bookmarks whose thread or post was deleted are listed with `deleted` set.
+#[utoipa::path(
+    get,
+    path = "/bookmarks",
+    tag = "bookmarks",
+    responses(
+        (status = 200, description = "Bookmarks, oldest first", body = Vec<BookmarkResponse>),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn list_bookmarks(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+) -> Result<Json<ApiResponse<Vec<BookmarkResponse>>>, ApiError> {
+    let user_role = Role::from_str(&claims.role).unwrap_or(Role::Guest);
+    let bookmarks = BookmarkService::new(&state.db)
+        .list(claims.sub, user_role)
+        .await
+        .map_err(service_error("Bookmark not found"))?;
+
+    let responses = bookmarks.into_iter().map(Into::into).collect();
+    Ok(Json(ApiResponse::new(responses)))
+}
+
+/// POST /api/bookmarks - Bookmark a thread or post.
+///
+/// Bookmarking the same target again only changes the note.
+#[utoipa::path(
+    post,
+    path = "/bookmarks",
+    tag = "bookmarks",
+    request_body = CreateBookmarkRequest,
+    responses(
+        (status = 200, description = "Bookmark created or updated", body = BookmarkResponse),
+        (status = 400, description = "Neither or both of thread_id and post_id given, or note too long"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Access denied"),
+        (status = 404, description = "Thread or post not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn create_bookmark(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Json(req): Json<CreateBookmarkRequest>,
+) -> Result<Json<ApiResponse<BookmarkResponse>>, ApiError> {
+    let target = match (req.thread_id, req.post_id) {
+        (Some(thread_id), None) => BookmarkTarget::Thread(thread_id),
+        (None, Some(post_id)) => BookmarkTarget::Post(post_id),
+        _ => {
+            return Err(ApiError::bad_request(
+                "Specify exactly one of thread_id and post_id",
+            ))
+        }
+    };
+    let user_role = Role::from_str(&claims.role).unwrap_or(Role::Guest);
+
+    let bookmark = BookmarkService::new(&state.db)
+        .add(claims.sub, user_role, target, req.note.as_deref())
+        .await
+        .map_err(service_error("Thread or post not found"))?;
+
+    Ok(Json(ApiResponse::new(bookmark.into())))
+}
+
+/// PUT /api/bookmarks/:id - Change the note of a bookmark.
+#[utoipa::path(
+    put,
+    path = "/bookmarks/{id}",
+    tag = "bookmarks",
+    params(
+        ("id" = i64, Path, description = "Bookmark ID")
+    ),
+    request_body = UpdateBookmarkRequest,
+    responses(
+        (status = 200, description = "Bookmark updated", body = BookmarkResponse),
+        (status = 400, description = "Note too long"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Bookmark not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn update_bookmark(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Path(id): Path<i64>,
+    Json(req): Json<UpdateBookmarkRequest>,
+) -> Result<Json<ApiResponse<BookmarkResponse>>, ApiError> {
+    let user_role = Role::from_str(&claims.role).unwrap_or(Role::Guest);
+    let bookmark = BookmarkService::new(&state.db)
+        .set_note(claims.sub, user_role, id, req.note.as_deref())
+        .await
+        .map_err(service_error("Bookmark not found"))?;
+
+    Ok(Json(ApiResponse::new(bookmark.into())))
+}
+
+/// DELETE /api/bookmarks/:id - Remove a bookmark.
+#[utoipa::path(
+    delete,
+    path = "/bookmarks/{id}",
+    tag = "bookmarks",
+    params(
+        ("id" = i64, Path, description = "Bookmark ID")
+    ),
+    responses(
+        (status = 200, description = "Bookmark removed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Bookmark not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn delete_bookmark(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Path(id): Path<i64>,
+) -> Result<Json<ApiResponse<()>>, ApiError> {
+    BookmarkService::new(&state.db)
+        .remove(claims.sub, id)
+        .await
+        .map_err(service_error("Bookmark not found"))?;
+
+    Ok(Json(ApiResponse::new(())))
+}
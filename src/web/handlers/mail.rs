@@ -8,12 +8,14 @@ use std::sync::Arc;
 use utoipa;
 
 use crate::db::UserRepository;
+use crate::filter::{ContentFilterService, FilterTarget};
 use crate::mail::{MailRepository, MailUpdate, NewMail};
 use crate::web::dto::{
     ApiResponse, AuthorInfo, MailDetailResponse, MailListResponse, PaginatedResponse,
-    PaginationQuery, SendMailRequest, UnreadCountResponse,
+    PaginationQuery, SendMailRequest, ShowHiddenQuery, UnreadCountResponse,
 };
 use crate::web::error::ApiError;
+use crate::web::handlers::twit::load_kill_file;
 use crate::web::handlers::AppState;
 use crate::web::middleware::AuthUser;
 
@@ -24,7 +26,8 @@ use crate::web::middleware::AuthUser;
     tag = "mail",
     params(
         ("page" = Option<u32>, Query, description = "Page number"),
-        ("per_page" = Option<u32>, Query, description = "Items per page")
+        ("per_page" = Option<u32>, Query, description = "Items per page"),
+        ("show_hidden" = Option<bool>, Query, description = "Include mail hidden by twit filters")
     ),
     responses(
         (status = 200, description = "List of received mails", body = Vec<MailListResponse>),
@@ -38,14 +41,17 @@ pub async fn list_inbox(
     State(state): State<Arc<AppState>>,
     AuthUser(claims): AuthUser,
     Query(pagination): Query<PaginationQuery>,
+    Query(hidden): Query<ShowHiddenQuery>,
 ) -> Result<Json<PaginatedResponse<MailListResponse>>, ApiError> {
     let (offset, limit) = pagination.to_offset_limit();
 
     let mail_repo = MailRepository::new(state.db.pool());
-    let all_mails = mail_repo.list_inbox(claims.sub).await.map_err(|e| {
+    let mut all_mails = mail_repo.list_inbox(claims.sub).await.map_err(|e| {
         tracing::error!("Failed to list inbox: {}", e);
         ApiError::internal("Failed to list inbox")
     })?;
+    let kill_file = load_kill_file(&state.db, Some(claims.sub), hidden.show_hidden).await?;
+    all_mails.retain(|m| !kill_file.hides_mail(m));
 
     let total = all_mails.len() as i64;
 
@@ -367,8 +373,22 @@ pub async fn send_mail(
         return Err(ApiError::bad_request("Cannot send mail to yourself"));
     }
 
+    // Mail cannot be held for moderation, so a hold rejects it
+    let mut texts = [req.subject, req.body];
+    ContentFilterService::new(state.db.pool())
+        .apply_strict(Some(claims.sub), FilterTarget::Mail, &mut texts)
+        .await
+        .map_err(|e| match e {
+            crate::HobbsError::Validation(msg) => ApiError::bad_request(msg),
+            e => {
+                tracing::error!("Failed to filter mail: {}", e);
+                ApiError::internal("Database error")
+            }
+        })?;
+    let [subject, body] = texts;
+
     // Create mail
-    let new_mail = NewMail::new(claims.sub, recipient.id, &req.subject, &req.body);
+    let new_mail = NewMail::new(claims.sub, recipient.id, &subject, &body);
     let mail_repo = MailRepository::new(state.db.pool());
     let mail = mail_repo.create(&new_mail).await.map_err(|e| {
         tracing::error!("Failed to send mail: {}", e);
@@ -3,17 +3,29 @@
 pub mod admin;
 pub mod auth;
 pub mod board;
+pub mod bookmark;
 pub mod config;
 pub mod file;
 pub mod mail;
+pub mod newscan;
+pub mod notification;
+pub mod poll;
+pub mod qwk;
 pub mod rss;
+pub mod twit;
 pub mod user;
 
 pub use admin::*;
 pub use auth::*;
 pub use board::*;
+pub use bookmark::*;
 pub use config::*;
 pub use file::*;
 pub use mail::*;
+pub use newscan::*;
+pub use notification::*;
+pub use poll::*;
+pub use qwk::*;
 pub use rss::*;
+pub use twit::*;
 pub use user::*;
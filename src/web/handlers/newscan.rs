@@ -0,0 +1,168 @@
+//! Newscan handlers for Web API.
+
+use axum::{extract::State, Json};
+use std::str::FromStr;
+use std::sync::Arc;
+use utoipa;
+
+use crate::board::{NewscanBoard, NewscanEntry, NewscanService};
+use crate::datetime::parse_local_datetime;
+use crate::db::{Database, Role};
+use crate::web::dto::{
+    ApiResponse, NewscanBoardResponse, NewscanResponse, ResetNewscanRequest, ResetNewscanResponse,
+    UpdateNewscanRequest,
+};
+use crate::web::error::ApiError;
+use crate::web::handlers::AppState;
+use crate::web::middleware::AuthUser;
+use crate::HobbsError;
+
+impl From<NewscanBoard> for NewscanBoardResponse {
+    fn from(b: NewscanBoard) -> Self {
+        Self {
+            board_id: b.board.id,
+            name: b.board.name,
+            included: b.included,
+        }
+    }
+}
+
+/// Map a newscan service error to an API error.
+fn service_error(e: HobbsError) -> ApiError {
+    match e {
+        HobbsError::NotFound(_) => ApiError::not_found("Board not found"),
+        HobbsError::Validation(msg) => ApiError::bad_request(msg),
+        e => {
+            tracing::error!("Newscan service error: {}", e);
+            ApiError::internal("Database error")
+        }
+    }
+}
+
+/// Load a user's newscan settings.
+async fn newscan_response(
+    db: &Database,
+    user_id: i64,
+    user_role: Role,
+) -> Result<NewscanResponse, ApiError> {
+    let service = NewscanService::new(db);
+    let boards = service
+        .boards(user_id, user_role)
+        .await
+        .map_err(service_error)?;
+    let scan_on_login = service
+        .scan_on_login(user_id)
+        .await
+        .map_err(service_error)?;
+
+    Ok(NewscanResponse {
+        boards: boards.into_iter().map(Into::into).collect(),
+        scan_on_login,
+    })
+}
+
+/// GET /api/newscan - Get the user's newscan settings.
+#[utoipa::path(
+    get,
+    path = "/newscan",
+    tag = "newscan",
+    responses(
+        (status = 200, description = "Readable boards in scan order", body = NewscanResponse),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn get_newscan(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+) -> Result<Json<ApiResponse<NewscanResponse>>, ApiError> {
+    let user_role = Role::from_str(&claims.role).unwrap_or(Role::Guest);
+    let response = newscan_response(&state.db, claims.sub, user_role).await?;
+    Ok(Json(ApiResponse::new(response)))
+}
+
+/// PUT /api/newscan - Update the user's newscan settings.
+///
+/// `boards` replaces the scan order and selection.
+#[utoipa::path(
+    put,
+    path = "/newscan",
+    tag = "newscan",
+    request_body = UpdateNewscanRequest,
+    responses(
+        (status = 200, description = "Settings updated", body = NewscanResponse),
+        (status = 400, description = "A board is listed twice"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Board not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn update_newscan(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Json(req): Json<UpdateNewscanRequest>,
+) -> Result<Json<ApiResponse<NewscanResponse>>, ApiError> {
+    let user_role = Role::from_str(&claims.role).unwrap_or(Role::Guest);
+    let service = NewscanService::new(&state.db);
+
+    if let Some(boards) = req.boards {
+        let entries: Vec<NewscanEntry> = boards
+            .into_iter()
+            .map(|b| NewscanEntry {
+                board_id: b.board_id,
+                included: b.included,
+            })
+            .collect();
+        service
+            .save(claims.sub, user_role, &entries)
+            .await
+            .map_err(service_error)?;
+    }
+    if let Some(scan_on_login) = req.scan_on_login {
+        service
+            .set_scan_on_login(claims.sub, scan_on_login)
+            .await
+            .map_err(service_error)?;
+    }
+
+    let response = newscan_response(&state.db, claims.sub, user_role).await?;
+    Ok(Json(ApiResponse::new(response)))
+}
+
+/// POST /api/newscan/reset - Reset the newscan boards' read pointers.
+///
+/// Posts created before `since` become read and later ones unread.
+#[utoipa::path(
+    post,
+    path = "/newscan/reset",
+    tag = "newscan",
+    request_body = ResetNewscanRequest,
+    responses(
+        (status = 200, description = "Read pointers reset", body = ResetNewscanResponse),
+        (status = 400, description = "Invalid date"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn reset_newscan(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Json(req): Json<ResetNewscanRequest>,
+) -> Result<Json<ApiResponse<ResetNewscanResponse>>, ApiError> {
+    let since = parse_local_datetime(&req.since, "UTC")
+        .ok_or_else(|| ApiError::bad_request("Invalid date"))?;
+    let user_role = Role::from_str(&claims.role).unwrap_or(Role::Guest);
+
+    let boards = NewscanService::new(&state.db)
+        .reset_pointers(claims.sub, user_role, &since)
+        .await
+        .map_err(service_error)?;
+
+    Ok(Json(ApiResponse::new(ResetNewscanResponse { boards })))
+}
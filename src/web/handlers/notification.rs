@@ -0,0 +1,245 @@
+//! Subscription and notification handlers for Web API.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use std::str::FromStr;
+use std::sync::Arc;
+use utoipa;
+
+use crate::datetime::to_rfc3339;
+use crate::db::Role;
+use crate::notify::{Notification, NotificationService, Subscription, SubscriptionTarget};
+use crate::web::dto::{
+    ApiResponse, CreateSubscriptionRequest, NotificationResponse, SubscriptionResponse,
+    UpdateSubscriptionRequest,
+};
+use crate::web::error::ApiError;
+use crate::web::handlers::AppState;
+use crate::web::middleware::AuthUser;
+use crate::HobbsError;
+
+impl From<Subscription> for SubscriptionResponse {
+    fn from(s: Subscription) -> Self {
+        Self {
+            id: s.id,
+            board_id: s.board_id,
+            thread_id: s.thread_id,
+            name: s.name,
+            digest: s.digest,
+            created_at: to_rfc3339(&s.created_at),
+        }
+    }
+}
+
+impl From<Notification> for NotificationResponse {
+    fn from(n: Notification) -> Self {
+        Self {
+            id: n.id,
+            post_id: n.post_id,
+            board_id: n.board_id,
+            board_name: n.board_name,
+            thread_id: n.thread_id,
+            title: n.title,
+            author: n.author,
+            created_at: to_rfc3339(&n.created_at),
+        }
+    }
+}
+
+/// Map a notification service error to an API error.
+fn service_error(not_found: &'static str) -> impl Fn(HobbsError) -> ApiError {
+    move |e| match e {
+        HobbsError::NotFound(_) => ApiError::not_found(not_found),
+        HobbsError::Permission(_) => ApiError::forbidden("Access denied"),
+        e => {
+            tracing::error!("Notification service error: {}", e);
+            ApiError::internal("Database error")
+        }
+    }
+}
+
+/// GET /api/subscriptions - List the user's subscriptions.
+#[utoipa::path(
+    get,
+    path = "/subscriptions",
+    tag = "subscriptions",
+    responses(
+        (status = 200, description = "Subscriptions, oldest first", body = Vec<SubscriptionResponse>),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn list_subscriptions(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+) -> Result<Json<ApiResponse<Vec<SubscriptionResponse>>>, ApiError> {
+    let subscriptions = NotificationService::new(&state.db)
+        .list_subscriptions(claims.sub)
+        .await
+        .map_err(service_error("Subscription not found"))?;
+
+    let responses = subscriptions.into_iter().map(Into::into).collect();
+    Ok(Json(ApiResponse::new(responses)))
+}
+
+/// POST /api/subscriptions - Subscribe to a board or thread.
+///
+/// Subscribing to the same target again only changes the delivery mode.
+#[utoipa::path(
+    post,
+    path = "/subscriptions",
+    tag = "subscriptions",
+    request_body = CreateSubscriptionRequest,
+    responses(
+        (status = 200, description = "Subscription created or updated", body = SubscriptionResponse),
+        (status = 400, description = "Neither or both of board_id and thread_id given"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Access denied"),
+        (status = 404, description = "Board or thread not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn create_subscription(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Json(req): Json<CreateSubscriptionRequest>,
+) -> Result<Json<ApiResponse<SubscriptionResponse>>, ApiError> {
+    let target = match (req.board_id, req.thread_id) {
+        (Some(board_id), None) => SubscriptionTarget::Board(board_id),
+        (None, Some(thread_id)) => SubscriptionTarget::Thread(thread_id),
+        _ => {
+            return Err(ApiError::bad_request(
+                "Specify exactly one of board_id and thread_id",
+            ))
+        }
+    };
+    let user_role = Role::from_str(&claims.role).unwrap_or(Role::Guest);
+
+    let subscription = NotificationService::new(&state.db)
+        .subscribe(claims.sub, user_role, target, req.digest)
+        .await
+        .map_err(service_error("Board or thread not found"))?;
+
+    Ok(Json(ApiResponse::new(subscription.into())))
+}
+
+/// PUT /api/subscriptions/:id - Change the delivery mode of a subscription.
+#[utoipa::path(
+    put,
+    path = "/subscriptions/{id}",
+    tag = "subscriptions",
+    params(
+        ("id" = i64, Path, description = "Subscription ID")
+    ),
+    request_body = UpdateSubscriptionRequest,
+    responses(
+        (status = 200, description = "Subscription updated", body = SubscriptionResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Subscription not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn update_subscription(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Path(id): Path<i64>,
+    Json(req): Json<UpdateSubscriptionRequest>,
+) -> Result<Json<ApiResponse<SubscriptionResponse>>, ApiError> {
+    let subscription = NotificationService::new(&state.db)
+        .set_digest(claims.sub, id, req.digest)
+        .await
+        .map_err(service_error("Subscription not found"))?;
+
+    Ok(Json(ApiResponse::new(subscription.into())))
+}
+
+/// DELETE /api/subscriptions/:id - Unsubscribe.
+#[utoipa::path(
+    delete,
+    path = "/subscriptions/{id}",
+    tag = "subscriptions",
+    params(
+        ("id" = i64, Path, description = "Subscription ID")
+    ),
+    responses(
+        (status = 200, description = "Subscription deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Subscription not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn delete_subscription(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Path(id): Path<i64>,
+) -> Result<Json<ApiResponse<()>>, ApiError> {
+    NotificationService::new(&state.db)
+        .unsubscribe(claims.sub, id)
+        .await
+        .map_err(service_error("Subscription not found"))?;
+
+    Ok(Json(ApiResponse::new(())))
+}
+
+/// GET /api/notifications - List undelivered notifications.
+///
+/// Digest notifications are not listed; they arrive by mail.
+#[utoipa::path(
+    get,
+    path = "/notifications",
+    tag = "subscriptions",
+    responses(
+        (status = 200, description = "Notifications, oldest first", body = Vec<NotificationResponse>),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn list_notifications(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+) -> Result<Json<ApiResponse<Vec<NotificationResponse>>>, ApiError> {
+    let notifications = NotificationService::new(&state.db)
+        .list_pending(claims.sub)
+        .await
+        .map_err(service_error("Subscription not found"))?;
+
+    let responses = notifications.into_iter().map(Into::into).collect();
+    Ok(Json(ApiResponse::new(responses)))
+}
+
+/// POST /api/notifications/read - Mark all notifications as read.
+#[utoipa::path(
+    post,
+    path = "/notifications/read",
+    tag = "subscriptions",
+    responses(
+        (status = 200, description = "Notifications marked as read"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn mark_notifications_read(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+) -> Result<Json<ApiResponse<()>>, ApiError> {
+    NotificationService::new(&state.db)
+        .mark_all_delivered(claims.sub)
+        .await
+        .map_err(service_error("Subscription not found"))?;
+
+    Ok(Json(ApiResponse::new(())))
+}
@@ -0,0 +1,333 @@
+//! Poll (voting booth) handlers for Web API.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use std::str::FromStr;
+use std::sync::Arc;
+use utoipa;
+
+use crate::datetime::{parse_local_datetime, to_rfc3339};
+use crate::db::Role;
+use crate::poll::{Ballot, NewPoll, PollResults, PollService, PollStatus};
+use crate::web::dto::{
+    ApiResponse, CreatePollRequest, PollListQuery, PollOptionResponse, PollResponse, VoteRequest,
+};
+use crate::web::error::ApiError;
+use crate::web::handlers::AppState;
+use crate::web::middleware::{AuthUser, JwtClaims, OptionalAuthUser};
+use crate::HobbsError;
+
+/// Map a poll service error to an API error.
+fn service_error(e: HobbsError) -> ApiError {
+    match e {
+        HobbsError::NotFound(_) => ApiError::not_found("Poll not found"),
+        HobbsError::Permission(_) => ApiError::forbidden("Access denied"),
+        HobbsError::Validation(msg) => ApiError::bad_request(msg),
+        e => {
+            tracing::error!("Poll service error: {}", e);
+            ApiError::internal("Database error")
+        }
+    }
+}
+
+fn user_role(claims: Option<&JwtClaims>) -> Role {
+    claims
+        .map(|c| Role::from_str(&c.role).unwrap_or(Role::Guest))
+        .unwrap_or(Role::Guest)
+}
+
+fn poll_response(results: PollResults, claims: Option<&JwtClaims>) -> PollResponse {
+    let role = user_role(claims);
+    let user_id = claims.map(|c| c.sub);
+    let poll = results.poll;
+    let can_vote = user_id.is_some()
+        && results.status == PollStatus::Open
+        && poll.can_vote(role)
+        && results.my_votes.is_empty();
+    let can_manage = user_id.is_some_and(|id| PollService::can_manage(&poll, id, role));
+
+    PollResponse {
+        id: poll.id,
+        question: poll.question,
+        description: poll.description,
+        allow_multiple: poll.allow_multiple,
+        allow_write_in: poll.allow_write_in,
+        min_vote_role: poll.min_vote_role.as_str().to_string(),
+        opens_at: poll.opens_at.as_deref().map(to_rfc3339),
+        closes_at: poll.closes_at.as_deref().map(to_rfc3339),
+        status: results.status.as_str().to_string(),
+        thread_id: poll.thread_id,
+        creator: poll.creator,
+        options: results
+            .options
+            .into_iter()
+            .map(|r| PollOptionResponse {
+                id: r.option.id,
+                text: r.option.text,
+                is_write_in: r.option.is_write_in,
+                votes: r.votes,
+            })
+            .collect(),
+        voters: results.voters,
+        my_votes: results.my_votes,
+        can_vote,
+        can_manage,
+        created_at: to_rfc3339(&poll.created_at),
+    }
+}
+
+fn parse_time(value: Option<&str>, field: &str) -> Result<Option<String>, ApiError> {
+    match value.map(str::trim).filter(|v| !v.is_empty()) {
+        None => Ok(None),
+        Some(v) => parse_local_datetime(v, "UTC")
+            .map(Some)
+            .ok_or_else(|| ApiError::bad_request(format!("Invalid {}", field))),
+    }
+}
+
+/// GET /api/polls - List polls, newest first.
+#[utoipa::path(
+    get,
+    path = "/polls",
+    tag = "polls",
+    params(
+        ("thread_id" = Option<i64>, Query, description = "Only the poll attached to this thread")
+    ),
+    responses(
+        (status = 200, description = "Polls with their results", body = Vec<PollResponse>),
+        (status = 403, description = "Access denied"),
+        (status = 404, description = "Thread not found")
+    )
+)]
+pub async fn list_polls(
+    State(state): State<Arc<AppState>>,
+    OptionalAuthUser(auth): OptionalAuthUser,
+    Query(query): Query<PollListQuery>,
+) -> Result<Json<ApiResponse<Vec<PollResponse>>>, ApiError> {
+    let role = user_role(auth.as_ref());
+    let service = PollService::new(&state.db);
+    let polls = match query.thread_id {
+        Some(thread_id) => service
+            .get_thread_poll(thread_id, role)
+            .await
+            .map_err(service_error)?
+            .into_iter()
+            .collect(),
+        None => service.list_polls(role).await.map_err(service_error)?,
+    };
+
+    let mut responses = Vec::with_capacity(polls.len());
+    for poll in polls {
+        let results = service
+            .get_results(poll.id, auth.as_ref().map(|c| c.sub), role)
+            .await
+            .map_err(service_error)?;
+        responses.push(poll_response(results, auth.as_ref()));
+    }
+    Ok(Json(ApiResponse::new(responses)))
+}
+
+/// POST /api/polls - Create a poll.
+#[utoipa::path(
+    post,
+    path = "/polls",
+    tag = "polls",
+    request_body = CreatePollRequest,
+    responses(
+        (status = 200, description = "Poll created", body = PollResponse),
+        (status = 400, description = "Invalid poll"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not allowed to create polls or attach to this thread"),
+        (status = 404, description = "Thread not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn create_poll(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Json(req): Json<CreatePollRequest>,
+) -> Result<Json<ApiResponse<PollResponse>>, ApiError> {
+    let role = user_role(Some(&claims));
+    let min_vote_role = match req.min_vote_role.as_deref() {
+        Some(r) => Role::from_str(r).map_err(|_| ApiError::bad_request("Invalid min_vote_role"))?,
+        None => Role::Member,
+    };
+    let opens_at = parse_time(req.opens_at.as_deref(), "opens_at")?;
+    let closes_at = parse_time(req.closes_at.as_deref(), "closes_at")?;
+
+    let mut new_poll = NewPoll::new(req.question, req.options)
+        .with_multiple(req.allow_multiple)
+        .with_write_in(req.allow_write_in)
+        .with_min_vote_role(min_vote_role)
+        .with_period(opens_at, closes_at);
+    if let Some(description) = req.description.filter(|d| !d.trim().is_empty()) {
+        new_poll = new_poll.with_description(description.trim());
+    }
+    if let Some(thread_id) = req.thread_id {
+        new_poll = new_poll.with_thread(thread_id);
+    }
+
+    let service = PollService::new(&state.db);
+    let poll = service
+        .create_poll(claims.sub, role, state.poll_create_role, new_poll)
+        .await
+        .map_err(service_error)?;
+    let results = service
+        .get_results(poll.id, Some(claims.sub), role)
+        .await
+        .map_err(service_error)?;
+
+    Ok(Json(ApiResponse::new(poll_response(
+        results,
+        Some(&claims),
+    ))))
+}
+
+/// GET /api/polls/:id - Get a poll with its results.
+#[utoipa::path(
+    get,
+    path = "/polls/{id}",
+    tag = "polls",
+    params(
+        ("id" = i64, Path, description = "Poll ID")
+    ),
+    responses(
+        (status = 200, description = "Poll with its results", body = PollResponse),
+        (status = 403, description = "Access denied"),
+        (status = 404, description = "Poll not found")
+    )
+)]
+pub async fn get_poll(
+    State(state): State<Arc<AppState>>,
+    OptionalAuthUser(auth): OptionalAuthUser,
+    Path(id): Path<i64>,
+) -> Result<Json<ApiResponse<PollResponse>>, ApiError> {
+    let results = PollService::new(&state.db)
+        .get_results(id, auth.as_ref().map(|c| c.sub), user_role(auth.as_ref()))
+        .await
+        .map_err(service_error)?;
+
+    Ok(Json(ApiResponse::new(poll_response(
+        results,
+        auth.as_ref(),
+    ))))
+}
+
+/// POST /api/polls/:id/vote - Cast a ballot.
+///
+/// Each user votes once per poll.
+#[utoipa::path(
+    post,
+    path = "/polls/{id}/vote",
+    tag = "polls",
+    params(
+        ("id" = i64, Path, description = "Poll ID")
+    ),
+    request_body = VoteRequest,
+    responses(
+        (status = 200, description = "Ballot cast, updated results", body = PollResponse),
+        (status = 400, description = "Invalid ballot, poll not open or already voted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Role not allowed to vote"),
+        (status = 404, description = "Poll not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn vote(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Path(id): Path<i64>,
+    Json(req): Json<VoteRequest>,
+) -> Result<Json<ApiResponse<PollResponse>>, ApiError> {
+    let mut ballot = Ballot::new(req.option_ids);
+    if let Some(write_in) = req.write_in {
+        ballot = ballot.with_write_in(write_in);
+    }
+
+    let results = PollService::new(&state.db)
+        .vote(id, claims.sub, user_role(Some(&claims)), ballot)
+        .await
+        .map_err(service_error)?;
+
+    Ok(Json(ApiResponse::new(poll_response(
+        results,
+        Some(&claims),
+    ))))
+}
+
+/// POST /api/polls/:id/close - End voting now.
+#[utoipa::path(
+    post,
+    path = "/polls/{id}/close",
+    tag = "polls",
+    params(
+        ("id" = i64, Path, description = "Poll ID")
+    ),
+    responses(
+        (status = 200, description = "Poll closed", body = PollResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Only the creator or a SubOp may close the poll"),
+        (status = 404, description = "Poll not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn close_poll(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Path(id): Path<i64>,
+) -> Result<Json<ApiResponse<PollResponse>>, ApiError> {
+    let role = user_role(Some(&claims));
+    let service = PollService::new(&state.db);
+    service
+        .close_poll(id, claims.sub, role)
+        .await
+        .map_err(service_error)?;
+    let results = service
+        .get_results(id, Some(claims.sub), role)
+        .await
+        .map_err(service_error)?;
+
+    Ok(Json(ApiResponse::new(poll_response(
+        results,
+        Some(&claims),
+    ))))
+}
+
+/// DELETE /api/polls/:id - Delete a poll.
+#[utoipa::path(
+    delete,
+    path = "/polls/{id}",
+    tag = "polls",
+    params(
+        ("id" = i64, Path, description = "Poll ID")
+    ),
+    responses(
+        (status = 200, description = "Poll deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Only the creator or a SubOp may delete the poll"),
+        (status = 404, description = "Poll not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn delete_poll(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Path(id): Path<i64>,
+) -> Result<Json<ApiResponse<()>>, ApiError> {
+    PollService::new(&state.db)
+        .delete_poll(id, claims.sub, user_role(Some(&claims)))
+        .await
+        .map_err(service_error)?;
+
+    Ok(Json(ApiResponse::new(())))
+}
@@ -0,0 +1,200 @@
+//! QWK offline mail handlers for Web API.
+
+use axum::{
+    body::Body,
+    extract::{Multipart, State},
+    http::header,
+    response::Response,
+    Json,
+};
+use serde_json::json;
+use std::sync::Arc;
+use utoipa;
+
+use crate::db::{User, UserRepository};
+use crate::qwk::QwkService;
+use crate::web::dto::{ApiResponse, QwkCommitRequest, QwkImportResponse};
+use crate::web::error::ApiError;
+use crate::web::handlers::AppState;
+use crate::web::middleware::AuthUser;
+use crate::HobbsError;
+
+/// Load the authenticated user.
+async fn current_user(state: &AppState, user_id: i64) -> Result<User, ApiError> {
+    UserRepository::new(state.db.pool())
+        .get_by_id(user_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get user: {}", e);
+            ApiError::internal("Failed to get user")
+        })?
+        .ok_or_else(|| ApiError::unauthorized("User not found"))
+}
+
+/// Response header carrying the read pointers reached by a packet.
+const POINTERS_HEADER: &str = "x-qwk-pointers";
+
+/// GET /api/qwk - Download a QWK packet.
+///
+/// The packet contains unread private mail (conference 0) and unread posts
+/// (conference = board ID). Downloading changes nothing; the read pointers
+/// in the `X-QWK-Pointers` header are applied by `POST /api/qwk/commit`
+/// once the packet has arrived.
+#[utoipa::path(
+    get,
+    path = "/qwk",
+    tag = "qwk",
+    responses(
+        (status = 200, description = "QWK packet; the X-QWK-Pointers header holds the body for POST /api/qwk/commit", content_type = "application/zip"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn download_qwk(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+) -> Result<Response<Body>, ApiError> {
+    let user = current_user(&state, claims.sub).await?;
+
+    let service = QwkService::new(&state.db);
+    let packet = service.build_packet(&user, &state.qwk).await.map_err(|e| {
+        tracing::error!("Failed to build QWK packet: {}", e);
+        ApiError::internal("Failed to build QWK packet")
+    })?;
+
+    let boards: Vec<_> = packet
+        .pointers
+        .iter()
+        .map(|&(board_id, post_id)| json!({ "board_id": board_id, "post_id": post_id }))
+        .collect();
+    let pointers = json!({ "pointers": boards, "mail_ids": packet.mail_ids });
+
+    let response = Response::builder()
+        .header(header::CONTENT_TYPE, "application/zip")
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", packet.filename),
+        )
+        .header(header::CONTENT_LENGTH, packet.data.len())
+        .header(POINTERS_HEADER, pointers.to_string())
+        .body(Body::from(packet.data))
+        .map_err(|e| {
+            tracing::error!("Failed to build response: {}", e);
+            ApiError::internal("Failed to build response")
+        })?;
+
+    Ok(response)
+}
+
+/// POST /api/qwk/commit - Confirm a downloaded QWK packet.
+///
+/// Advances the read pointers and marks the mail read, as given by the
+/// `X-QWK-Pointers` header of the download. Posts made after the download
+/// stay unread. Pointers never move back.
+#[utoipa::path(
+    post,
+    path = "/qwk/commit",
+    tag = "qwk",
+    request_body = QwkCommitRequest,
+    responses(
+        (status = 200, description = "Read pointers updated"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn commit_qwk(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Json(req): Json<QwkCommitRequest>,
+) -> Result<Json<ApiResponse<()>>, ApiError> {
+    let user = current_user(&state, claims.sub).await?;
+
+    let pointers: Vec<(i64, i64)> = req
+        .pointers
+        .iter()
+        .map(|p| (p.board_id, p.post_id))
+        .collect();
+    QwkService::new(&state.db)
+        .confirm(&user, &pointers, &req.mail_ids)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to update read pointers: {}", e);
+            ApiError::internal("Failed to update read pointers")
+        })?;
+
+    Ok(Json(ApiResponse::new(())))
+}
+
+/// POST /api/qwk/rep - Upload a REP reply packet.
+///
+/// Request body: multipart/form-data with a "file" field containing BBSID.REP.
+#[utoipa::path(
+    post,
+    path = "/qwk/rep",
+    tag = "qwk",
+    responses(
+        (status = 200, description = "Replies imported", body = QwkImportResponse),
+        (status = 400, description = "Invalid or missing reply packet"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn upload_rep(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    mut multipart: Multipart,
+) -> Result<Json<ApiResponse<QwkImportResponse>>, ApiError> {
+    let user = current_user(&state, claims.sub).await?;
+
+    let mut content: Option<Vec<u8>> = None;
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        tracing::error!("Failed to read multipart field: {}", e);
+        ApiError::bad_request("Invalid multipart data")
+    })? {
+        if field.name() == Some("file") {
+            content = Some(
+                field
+                    .bytes()
+                    .await
+                    .map_err(|e| {
+                        tracing::error!("Failed to read file content: {}", e);
+                        ApiError::bad_request("Failed to read file")
+                    })?
+                    .to_vec(),
+            );
+        }
+    }
+
+    let content = content.ok_or_else(|| ApiError::bad_request("No file provided"))?;
+    if content.len() as u64 > state.max_upload_size {
+        let max_mb = state.max_upload_size / 1024 / 1024;
+        return Err(ApiError::bad_request(format!(
+            "File too large (max {}MB)",
+            max_mb
+        )));
+    }
+
+    let result = QwkService::new(&state.db)
+        .import_reply(&user, &state.qwk, &content)
+        .await
+        .map_err(|e| match e {
+            HobbsError::Qwk(msg) => ApiError::bad_request(msg),
+            e => {
+                tracing::error!("Failed to import REP packet: {}", e);
+                ApiError::internal("Failed to import REP packet")
+            }
+        })?;
+
+    Ok(Json(ApiResponse::new(QwkImportResponse {
+        posts: result.posts,
+        pending: result.pending,
+        mails: result.mails,
+        failed: result.failed,
+    })))
+}
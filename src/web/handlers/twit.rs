@@ -0,0 +1,155 @@
+//! Twit filter handlers for Web API.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use std::sync::Arc;
+use utoipa;
+
+use crate::datetime::to_rfc3339;
+use crate::db::Database;
+use crate::twit::{KillFile, TwitFilter, TwitFilterService, TwitTarget};
+use crate::web::dto::{ApiResponse, CreateTwitFilterRequest, TwitFilterResponse};
+use crate::web::error::ApiError;
+use crate::web::handlers::AppState;
+use crate::web::middleware::AuthUser;
+use crate::HobbsError;
+
+impl From<TwitFilter> for TwitFilterResponse {
+    fn from(f: TwitFilter) -> Self {
+        let kind = match f.target() {
+            TwitTarget::User(_) => "user",
+            TwitTarget::Keyword(_) => "keyword",
+        };
+        Self {
+            id: f.id,
+            kind: kind.to_string(),
+            user_id: f.target_user_id,
+            value: f.value().to_string(),
+            created_at: to_rfc3339(&f.created_at),
+        }
+    }
+}
+
+/// Map a twit filter service error to an API error.
+fn service_error(not_found: &'static str) -> impl Fn(HobbsError) -> ApiError {
+    move |e| match e {
+        HobbsError::NotFound(_) => ApiError::not_found(not_found),
+        HobbsError::Validation(msg) => ApiError::bad_request(msg),
+        e => {
+            tracing::error!("Twit filter service error: {}", e);
+            ApiError::internal("Database error")
+        }
+    }
+}
+
+/// Load the kill file to apply to a list.
+///
+/// Guests, and users who asked to see hidden content, get an empty kill
+/// file.
+pub(crate) async fn load_kill_file(
+    db: &Database,
+    user_id: Option<i64>,
+    show_hidden: bool,
+) -> Result<KillFile, ApiError> {
+    match user_id {
+        Some(user_id) if !show_hidden => TwitFilterService::new(db.pool())
+            .kill_file(user_id)
+            .await
+            .map_err(service_error("User not found")),
+        _ => Ok(KillFile::default()),
+    }
+}
+
+/// GET /api/twit-filters - List the user's twit filters.
+#[utoipa::path(
+    get,
+    path = "/twit-filters",
+    tag = "twit-filters",
+    responses(
+        (status = 200, description = "Hidden users first, then keywords", body = Vec<TwitFilterResponse>),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn list_twit_filters(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+) -> Result<Json<ApiResponse<Vec<TwitFilterResponse>>>, ApiError> {
+    let filters = TwitFilterService::new(state.db.pool())
+        .list(claims.sub)
+        .await
+        .map_err(service_error("Filter not found"))?;
+
+    let responses = filters.into_iter().map(Into::into).collect();
+    Ok(Json(ApiResponse::new(responses)))
+}
+
+/// POST /api/twit-filters - Hide a user or a keyword.
+#[utoipa::path(
+    post,
+    path = "/twit-filters",
+    tag = "twit-filters",
+    request_body = CreateTwitFilterRequest,
+    responses(
+        (status = 200, description = "Filter added", body = TwitFilterResponse),
+        (status = 400, description = "Neither or both of username and keyword given, duplicate, or limit reached"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "User not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn create_twit_filter(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Json(req): Json<CreateTwitFilterRequest>,
+) -> Result<Json<ApiResponse<TwitFilterResponse>>, ApiError> {
+    let service = TwitFilterService::new(state.db.pool());
+    let filter = match (req.username, req.keyword) {
+        (Some(username), None) => service.add_user(claims.sub, &username).await,
+        (None, Some(keyword)) => service.add_keyword(claims.sub, &keyword).await,
+        _ => {
+            return Err(ApiError::bad_request(
+                "Specify exactly one of username and keyword",
+            ))
+        }
+    }
+    .map_err(service_error("User not found"))?;
+
+    Ok(Json(ApiResponse::new(filter.into())))
+}
+
+/// DELETE /api/twit-filters/:id - Remove a twit filter.
+#[utoipa::path(
+    delete,
+    path = "/twit-filters/{id}",
+    tag = "twit-filters",
+    params(
+        ("id" = i64, Path, description = "Filter ID")
+    ),
+    responses(
+        (status = 200, description = "Filter removed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Filter not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn delete_twit_filter(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Path(id): Path<i64>,
+) -> Result<Json<ApiResponse<()>>, ApiError> {
+    TwitFilterService::new(state.db.pool())
+        .remove(claims.sub, id)
+        .await
+        .map_err(service_error("Filter not found"))?;
+
+    Ok(Json(ApiResponse::new(())))
+}
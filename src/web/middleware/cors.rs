@@ -1,7 +1,7 @@
 //! CORS middleware configuration.
 
 use axum::http::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE};
-use axum::http::{HeaderValue, Method};
+use axum::http::{HeaderName, HeaderValue, Method};
 use tower_http::cors::{Any, CorsLayer};
 
 /// Create a CORS layer from configuration.
@@ -14,6 +14,8 @@ pub fn create_cors_layer(origins: &[String]) -> CorsLayer {
         Method::PATCH,
         Method::OPTIONS,
     ];
+    // Response headers that scripts on other origins may read
+    let exposed = [HeaderName::from_static("x-qwk-pointers")];
 
     // When origins are specified, use credentials mode with explicit headers
     // When no origins specified (dev mode), use permissive mode without credentials
@@ -22,6 +24,7 @@ pub fn create_cors_layer(origins: &[String]) -> CorsLayer {
         CorsLayer::new()
             .allow_methods(methods)
             .allow_headers(Any)
+            .expose_headers(exposed)
             .allow_origin(Any)
     } else {
         // Production mode: specific origins with credentials
@@ -33,11 +36,13 @@ pub fn create_cors_layer(origins: &[String]) -> CorsLayer {
             CorsLayer::new()
                 .allow_methods(methods)
                 .allow_headers(Any)
+                .expose_headers(exposed)
                 .allow_origin(Any)
         } else {
             CorsLayer::new()
                 .allow_methods(methods)
                 .allow_headers([AUTHORIZATION, CONTENT_TYPE, ACCEPT])
+                .expose_headers(exposed)
                 .allow_credentials(true)
                 .allow_origin(parsed_origins)
         }
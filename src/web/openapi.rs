@@ -4,75 +4,142 @@ use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
 use utoipa::{Modify, OpenApi};
 
 use super::dto::request::{
-    AdminAddFeedRequest, AdminCreateBoardRequest, AdminCreateFolderRequest,
-    AdminResetPasswordRequest, AdminUpdateBoardRequest, AdminUpdateFolderRequest,
-    AdminUpdateRoleRequest, AdminUpdateStatusRequest, AdminUpdateUserRequest,
-    ChangePasswordRequest, CreateFlatPostRequest, CreatePostRequest, CreateThreadRequest,
-    LoginRequest, LogoutRequest, PaginationQuery, RefreshRequest, RegisterRequest, SendMailRequest,
-    UpdateProfileRequest,
+    AdminAddFeedRequest, AdminConvertPostRequest, AdminConvertThreadRequest,
+    AdminCreateBoardGroupRequest, AdminCreateBoardRequest, AdminCreateFilterRuleRequest,
+    AdminCreateFolderRequest, AdminExportBoardsQuery, AdminMergeThreadRequest,
+    AdminMoveThreadRequest, AdminRejectPostRequest, AdminResetPasswordRequest,
+    AdminSplitThreadRequest, AdminUpdateBoardGroupRequest, AdminUpdateBoardRequest,
+    AdminUpdateFilterRuleRequest, AdminUpdateFolderRequest, AdminUpdateRoleRequest,
+    AdminUpdateStatusRequest, AdminUpdateUserRequest, ChangePasswordRequest, CreateBookmarkRequest,
+    CreateFlatPostRequest, CreatePollRequest, CreatePostRequest, CreateSubscriptionRequest,
+    CreateThreadRequest, CreateTwitFilterRequest, LoginRequest, LogoutRequest, NewscanBoardRequest,
+    PaginationQuery, PollListQuery, PostDiffQuery, QwkCommitRequest, QwkPointer, RefreshRequest,
+    RegisterRequest, ResetNewscanRequest, SearchQuery, SendMailRequest, ShowHiddenQuery,
+    ThreadPostsQuery, UpdateBookmarkRequest, UpdateNewscanRequest, UpdateProfileRequest,
+    UpdateSubscriptionRequest, UpdateThreadStatusRequest, VoteRequest,
 };
 use super::dto::response::{
-    AdminBoardResponse, AdminFolderResponse, AdminUserResponse, AuthorInfo, BoardResponse,
-    FileResponse, FileUploadResponse, FolderResponse, LoginResponse, MailDetailResponse,
-    MailListResponse, MeResponse, PaginationMeta, PostResponse, RefreshResponse, RssFeedResponse,
-    RssItemResponse, ThreadResponse, UnreadCountResponse, UserDetailResponse, UserInfo,
-    UserListResponse,
+    AdminBoardGroupResponse, AdminBoardResponse, AdminConvertThreadResponse,
+    AdminFilterHitResponse, AdminFilterRuleResponse, AdminFolderResponse,
+    AdminImportBoardsResponse, AdminUserResponse, AuthorInfo, BoardGroupResponse, BoardResponse,
+    BoardTreeResponse, BookmarkResponse, DiffLineResponse, FileResponse, FileUploadResponse,
+    FolderResponse, LoginResponse, MailDetailResponse, MailListResponse, MeResponse,
+    NewscanBoardResponse, NewscanResponse, NotificationResponse, PaginationMeta,
+    PendingPostResponse, PollOptionResponse, PollResponse, PostDiffResponse, PostResponse,
+    PostVersionResponse, QwkImportResponse, RefreshResponse, ResetNewscanResponse, RssFeedResponse,
+    RssItemResponse, SearchResultResponse, SubscriptionResponse, ThreadResponse,
+    TwitFilterResponse, UnreadCountResponse, UserDetailResponse, UserInfo, UserListResponse,
 };
 // Import the __path_ structs generated by utoipa::path macro
 use super::handlers::{
+    __path_admin_approve_post,
+    __path_admin_convert_post,
+    __path_admin_convert_thread,
     __path_admin_create_board,
+    __path_admin_create_board_group,
+    __path_admin_create_filter_rule,
     __path_admin_create_folder,
     __path_admin_delete_board,
+    __path_admin_delete_board_group,
+    __path_admin_delete_filter_rule,
     __path_admin_delete_folder,
+    __path_admin_export_boards,
+    __path_admin_import_boards,
+    __path_admin_list_board_groups,
     __path_admin_list_boards,
+    __path_admin_list_filter_hits,
+    __path_admin_list_filter_rules,
     __path_admin_list_folders,
+    __path_admin_list_pending_posts,
     // Admin paths
     __path_admin_list_users,
+    __path_admin_merge_thread,
+    __path_admin_move_thread,
+    __path_admin_reject_post,
     __path_admin_reset_password,
+    __path_admin_split_thread,
     __path_admin_update_board,
+    __path_admin_update_board_group,
+    __path_admin_update_filter_rule,
     __path_admin_update_folder,
     __path_admin_update_role,
     __path_admin_update_status,
     __path_admin_update_user,
     __path_change_password,
+    __path_close_poll,
+    __path_commit_qwk,
+    // Bookmark paths
+    __path_create_bookmark,
     __path_create_flat_post,
+    // Poll paths
+    __path_create_poll,
+    // Subscription paths
+    __path_create_subscription,
     // Board paths
     __path_create_thread,
     __path_create_thread_post,
+    // Twit filter paths
+    __path_create_twit_filter,
+    __path_delete_bookmark,
     __path_delete_file,
     __path_delete_mail,
+    __path_delete_poll,
     __path_delete_post,
+    __path_delete_subscription,
+    __path_delete_twit_filter,
+    __path_diff_post_revisions,
     __path_download_file,
+    // QWK paths
+    __path_download_qwk,
     __path_get_board,
+    __path_get_board_tree,
     __path_get_file,
     __path_get_folder,
     __path_get_mail,
     __path_get_my_profile,
+    // Newscan paths
+    __path_get_newscan,
+    __path_get_poll,
     __path_get_thread,
     __path_get_unread_count,
     __path_get_user,
     __path_get_user_by_username,
     __path_list_boards,
+    __path_list_bookmarks,
     __path_list_files,
     __path_list_flat_posts,
     // File paths
     __path_list_folders,
     // Mail paths
     __path_list_inbox,
+    __path_list_notifications,
+    __path_list_polls,
+    __path_list_post_revisions,
     __path_list_sent,
+    __path_list_subscriptions,
     __path_list_thread_posts,
     __path_list_threads,
+    __path_list_twit_filters,
     // User paths
     __path_list_users,
     // Auth paths
     __path_login,
     __path_logout,
+    __path_mark_notifications_read,
     __path_me,
     __path_refresh,
     __path_register,
+    __path_reset_newscan,
+    __path_search_posts,
     __path_send_mail,
+    __path_update_bookmark,
     __path_update_my_profile,
+    __path_update_newscan,
+    __path_update_subscription,
+    __path_update_thread_status,
     __path_upload_file,
+    __path_upload_rep,
+    __path_vote,
 };
 
 /// Security scheme modifier for JWT bearer authentication.
@@ -119,6 +186,12 @@ impl Modify for SecurityAddon {
         (name = "threads", description = "Thread management"),
         (name = "posts", description = "Post management"),
         (name = "mail", description = "Private mail"),
+        (name = "subscriptions", description = "Board and thread subscriptions"),
+        (name = "newscan", description = "Newscan settings"),
+        (name = "bookmarks", description = "Thread and post bookmarks"),
+        (name = "twit-filters", description = "Per-user twit filters (kill files)"),
+        (name = "polls", description = "Voting booth"),
+        (name = "qwk", description = "QWK offline mail packets"),
         (name = "users", description = "User management"),
         (name = "rss", description = "RSS feed reader"),
         (name = "folders", description = "File folder management"),
@@ -134,6 +207,8 @@ impl Modify for SecurityAddon {
         me,
         // Boards
         list_boards,
+        get_board_tree,
+        search_posts,
         get_board,
         list_threads,
         create_thread,
@@ -143,8 +218,11 @@ impl Modify for SecurityAddon {
         get_thread,
         list_thread_posts,
         create_thread_post,
+        update_thread_status,
         // Posts
         delete_post,
+        list_post_revisions,
+        diff_post_revisions,
         // Mail
         list_inbox,
         list_sent,
@@ -152,6 +230,37 @@ impl Modify for SecurityAddon {
         send_mail,
         delete_mail,
         get_unread_count,
+        // Subscriptions
+        list_subscriptions,
+        create_subscription,
+        update_subscription,
+        delete_subscription,
+        list_notifications,
+        mark_notifications_read,
+        // Newscan
+        get_newscan,
+        update_newscan,
+        reset_newscan,
+        // Bookmarks
+        list_bookmarks,
+        create_bookmark,
+        update_bookmark,
+        delete_bookmark,
+        // Twit filters
+        list_twit_filters,
+        create_twit_filter,
+        delete_twit_filter,
+        // Polls
+        list_polls,
+        create_poll,
+        get_poll,
+        vote,
+        close_poll,
+        delete_poll,
+        // QWK
+        download_qwk,
+        commit_qwk,
+        upload_rep,
         // Users
         list_users,
         get_user,
@@ -178,10 +287,29 @@ impl Modify for SecurityAddon {
         admin_create_board,
         admin_update_board,
         admin_delete_board,
+        admin_export_boards,
+        admin_import_boards,
+        admin_list_board_groups,
+        admin_create_board_group,
+        admin_update_board_group,
+        admin_delete_board_group,
         admin_list_folders,
         admin_create_folder,
         admin_update_folder,
         admin_delete_folder,
+        admin_list_pending_posts,
+        admin_approve_post,
+        admin_reject_post,
+        admin_move_thread,
+        admin_merge_thread,
+        admin_split_thread,
+        admin_convert_thread,
+        admin_convert_post,
+        admin_list_filter_rules,
+        admin_create_filter_rule,
+        admin_update_filter_rule,
+        admin_delete_filter_rule,
+        admin_list_filter_hits,
     ),
     components(
         schemas(
@@ -191,10 +319,28 @@ impl Modify for SecurityAddon {
             RefreshRequest,
             RegisterRequest,
             PaginationQuery,
+            SearchQuery,
+            ThreadPostsQuery,
+            PostDiffQuery,
             CreateThreadRequest,
+            UpdateThreadStatusRequest,
             CreatePostRequest,
             CreateFlatPostRequest,
             SendMailRequest,
+            CreateSubscriptionRequest,
+            UpdateSubscriptionRequest,
+            UpdateNewscanRequest,
+            NewscanBoardRequest,
+            ResetNewscanRequest,
+            CreateBookmarkRequest,
+            UpdateBookmarkRequest,
+            CreateTwitFilterRequest,
+            ShowHiddenQuery,
+            CreatePollRequest,
+            VoteRequest,
+            PollListQuery,
+            QwkCommitRequest,
+            QwkPointer,
             UpdateProfileRequest,
             ChangePasswordRequest,
             AdminUpdateUserRequest,
@@ -203,9 +349,20 @@ impl Modify for SecurityAddon {
             AdminResetPasswordRequest,
             AdminCreateBoardRequest,
             AdminUpdateBoardRequest,
+            AdminCreateBoardGroupRequest,
+            AdminUpdateBoardGroupRequest,
             AdminCreateFolderRequest,
             AdminUpdateFolderRequest,
             AdminAddFeedRequest,
+            AdminRejectPostRequest,
+            AdminMoveThreadRequest,
+            AdminMergeThreadRequest,
+            AdminSplitThreadRequest,
+            AdminConvertThreadRequest,
+            AdminConvertPostRequest,
+            AdminExportBoardsQuery,
+            AdminCreateFilterRuleRequest,
+            AdminUpdateFilterRuleRequest,
             // Response DTOs
             PaginationMeta,
             LoginResponse,
@@ -213,12 +370,29 @@ impl Modify for SecurityAddon {
             RefreshResponse,
             MeResponse,
             BoardResponse,
+            BoardGroupResponse,
+            BoardTreeResponse,
             ThreadResponse,
             PostResponse,
+            PendingPostResponse,
+            SearchResultResponse,
+            PostVersionResponse,
+            PostDiffResponse,
+            DiffLineResponse,
             AuthorInfo,
             MailListResponse,
             MailDetailResponse,
             UnreadCountResponse,
+            SubscriptionResponse,
+            NotificationResponse,
+            NewscanResponse,
+            NewscanBoardResponse,
+            ResetNewscanResponse,
+            BookmarkResponse,
+            TwitFilterResponse,
+            PollResponse,
+            PollOptionResponse,
+            QwkImportResponse,
             UserListResponse,
             UserDetailResponse,
             RssFeedResponse,
@@ -228,7 +402,12 @@ impl Modify for SecurityAddon {
             FileUploadResponse,
             AdminUserResponse,
             AdminBoardResponse,
+            AdminBoardGroupResponse,
+            AdminImportBoardsResponse,
+            AdminConvertThreadResponse,
             AdminFolderResponse,
+            AdminFilterRuleResponse,
+            AdminFilterHitResponse,
         )
     ),
     modifiers(&SecurityAddon)
@@ -2,6 +2,7 @@
 
 use axum::{
     body::Body,
+    extract::DefaultBodyLimit,
     http::{header, Request, StatusCode},
     middleware::{self, Next},
     response::Response,
@@ -23,40 +24,79 @@ use super::handlers::{
     // RSS handlers
     add_feed,
     // Admin handlers
+    admin_approve_post,
+    admin_convert_post,
+    admin_convert_thread,
     admin_create_board,
+    admin_create_board_group,
+    admin_create_filter_rule,
     admin_create_folder,
     admin_delete_board,
+    admin_delete_board_group,
+    admin_delete_filter_rule,
     admin_delete_folder,
+    admin_export_boards,
+    admin_import_boards,
+    admin_list_board_groups,
     admin_list_boards,
+    admin_list_filter_hits,
+    admin_list_filter_rules,
     admin_list_folders,
+    admin_list_pending_posts,
     admin_list_users,
+    admin_merge_thread,
+    admin_move_thread,
+    admin_reject_post,
     admin_reset_password,
+    admin_split_thread,
     admin_update_board,
+    admin_update_board_group,
+    admin_update_filter_rule,
     admin_update_folder,
     admin_update_role,
     admin_update_status,
     admin_update_user,
     // User handlers
     change_password,
+    close_poll,
+    commit_qwk,
+    // Bookmark handlers
+    create_bookmark,
     // Board handlers
     create_flat_post,
+    create_poll,
+    // Subscription handlers
+    create_subscription,
     create_thread,
     create_thread_post,
+    // Twit filter handlers
+    create_twit_filter,
+    delete_bookmark,
     delete_feed,
     // File handlers
     delete_file,
     // Mail handlers
     delete_mail,
+    delete_poll,
     delete_post,
+    delete_subscription,
+    delete_twit_filter,
+    diff_post_revisions,
     download_file,
     download_file_with_token,
+    // QWK handlers
+    download_qwk,
     get_board,
+    get_board_tree,
     get_feed,
     get_file,
     get_folder,
     get_item,
     get_mail,
     get_my_profile,
+    // Newscan handlers
+    get_newscan,
+    get_poll,
     // Config handlers
     get_public_config,
     get_thread,
@@ -64,29 +104,44 @@ use super::handlers::{
     get_user,
     get_user_by_username,
     list_boards,
+    list_bookmarks,
     list_feeds,
     list_files,
     list_flat_posts,
     list_folders,
     list_inbox,
     list_items,
+    list_notifications,
+    list_polls,
+    list_post_revisions,
     list_sent,
+    list_subscriptions,
     list_thread_posts,
     list_threads,
+    list_twit_filters,
     list_users,
     // Auth handlers
     login,
     logout,
     mark_as_read,
+    mark_notifications_read,
     me,
     one_time_token,
     refresh,
     register,
+    reset_newscan,
+    search_posts,
     send_mail,
+    update_bookmark,
     update_my_profile,
+    update_newscan,
     update_post,
+    update_subscription,
     update_thread,
+    update_thread_status,
     upload_file,
+    upload_rep,
+    vote,
     // State
     AppState,
 };
@@ -95,7 +150,7 @@ use super::middleware::{
     RateLimitState,
 };
 use super::openapi::ApiDoc;
-use super::ws::{chat_ws_handler, ChatWsState};
+use super::ws::{chat_ws_handler, notification_ws_handler, ChatWsState};
 
 /// Create the main API router.
 pub fn create_router(
@@ -142,6 +197,8 @@ pub fn create_router(
     // Board routes
     let board_routes = Router::new()
         .route("/", get(list_boards))
+        .route("/tree", get(get_board_tree))
+        .route("/search", get(search_posts))
         .route("/:id", get(get_board))
         // Thread-based board routes
         .route("/:id/threads", get(list_threads))
@@ -154,13 +211,16 @@ pub fn create_router(
     let thread_routes = Router::new()
         .route("/:id", get(get_thread))
         .route("/:id", patch(update_thread))
+        .route("/:id/status", put(update_thread_status))
         .route("/:id/posts", get(list_thread_posts))
         .route("/:id/posts", post(create_thread_post));
 
     // Post routes
     let post_routes = Router::new()
         .route("/:id", delete(delete_post))
-        .route("/:id", patch(update_post));
+        .route("/:id", patch(update_post))
+        .route("/:id/revisions", get(list_post_revisions))
+        .route("/:id/revisions/diff", get(diff_post_revisions));
 
     // Mail routes
     let mail_routes = Router::new()
@@ -171,6 +231,53 @@ pub fn create_router(
         .route("/:id", get(get_mail))
         .route("/:id", delete(delete_mail));
 
+    // Subscription routes
+    let subscription_routes = Router::new()
+        .route("/", get(list_subscriptions))
+        .route("/", post(create_subscription))
+        .route("/:id", put(update_subscription))
+        .route("/:id", delete(delete_subscription));
+
+    // Bookmark routes
+    let bookmark_routes = Router::new()
+        .route("/", get(list_bookmarks))
+        .route("/", post(create_bookmark))
+        .route("/:id", put(update_bookmark))
+        .route("/:id", delete(delete_bookmark));
+
+    // Twit filter routes
+    let twit_filter_routes = Router::new()
+        .route("/", get(list_twit_filters))
+        .route("/", post(create_twit_filter))
+        .route("/:id", delete(delete_twit_filter));
+
+    // Newscan routes
+    let newscan_routes = Router::new()
+        .route("/", get(get_newscan))
+        .route("/", put(update_newscan))
+        .route("/reset", post(reset_newscan));
+
+    // Notification routes
+    let notification_routes = Router::new()
+        .route("/", get(list_notifications))
+        .route("/read", post(mark_notifications_read))
+        .route("/ws", get(notification_ws_handler));
+
+    // Poll routes
+    let poll_routes = Router::new()
+        .route("/", get(list_polls))
+        .route("/", post(create_poll))
+        .route("/:id", get(get_poll))
+        .route("/:id", delete(delete_poll))
+        .route("/:id/vote", post(vote))
+        .route("/:id/close", post(close_poll));
+
+    // QWK offline mail routes
+    let qwk_routes = Router::new()
+        .route("/", get(download_qwk))
+        .route("/commit", post(commit_qwk))
+        .route("/rep", post(upload_rep));
+
     // User routes
     let user_routes = Router::new()
         .route("/", get(list_users))
@@ -215,20 +322,55 @@ pub fn create_router(
     let admin_board_routes = Router::new()
         .route("/", get(admin_list_boards))
         .route("/", post(admin_create_board))
+        .route("/export", get(admin_export_boards))
+        .route(
+            "/import",
+            post(admin_import_boards)
+                .layer(DefaultBodyLimit::max(app_state.max_upload_size as usize)),
+        )
         .route("/:id", put(admin_update_board))
         .route("/:id", delete(admin_delete_board));
 
+    let admin_board_group_routes = Router::new()
+        .route("/", get(admin_list_board_groups))
+        .route("/", post(admin_create_board_group))
+        .route("/:id", put(admin_update_board_group))
+        .route("/:id", delete(admin_delete_board_group));
+
     let admin_folder_routes = Router::new()
         .route("/", get(admin_list_folders))
         .route("/", post(admin_create_folder))
         .route("/:id", put(admin_update_folder))
         .route("/:id", delete(admin_delete_folder));
 
+    let admin_moderation_routes = Router::new()
+        .route("/", get(admin_list_pending_posts))
+        .route("/:id/approve", post(admin_approve_post))
+        .route("/:id/reject", post(admin_reject_post));
+
+    let admin_filter_rule_routes = Router::new()
+        .route("/", get(admin_list_filter_rules))
+        .route("/", post(admin_create_filter_rule))
+        .route("/:id", put(admin_update_filter_rule))
+        .route("/:id", delete(admin_delete_filter_rule));
+
+    let admin_thread_routes = Router::new()
+        .route("/:id/move", post(admin_move_thread))
+        .route("/:id/merge", post(admin_merge_thread))
+        .route("/:id/split", post(admin_split_thread))
+        .route("/:id/convert", post(admin_convert_thread));
+
     // Note: Admin RSS routes removed - RSS is now personal per-user
     let admin_routes = Router::new()
         .nest("/users", admin_user_routes)
         .nest("/boards", admin_board_routes)
-        .nest("/folders", admin_folder_routes);
+        .nest("/board-groups", admin_board_group_routes)
+        .nest("/folders", admin_folder_routes)
+        .nest("/moderation", admin_moderation_routes)
+        .nest("/threads", admin_thread_routes)
+        .route("/posts/:id/convert", post(admin_convert_post))
+        .nest("/filter-rules", admin_filter_rule_routes)
+        .route("/filter-hits", get(admin_list_filter_hits));
 
     // Chat WebSocket routes (if chat manager is provided)
     let chat_routes = if let Some(ref manager) = chat_manager {
@@ -251,6 +393,13 @@ pub fn create_router(
         .nest("/threads", thread_routes)
         .nest("/posts", post_routes)
         .nest("/mail", mail_routes)
+        .nest("/subscriptions", subscription_routes)
+        .nest("/newscan", newscan_routes)
+        .nest("/bookmarks", bookmark_routes)
+        .nest("/twit-filters", twit_filter_routes)
+        .nest("/notifications", notification_routes)
+        .nest("/polls", poll_routes)
+        .nest("/qwk", qwk_routes)
         .nest("/users", user_routes)
         .nest("/rss", rss_routes)
         .nest("/folders", folder_routes)
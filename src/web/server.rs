@@ -9,8 +9,10 @@ use tower_http::compression::CompressionLayer;
 
 use crate::chat::ChatRoomManager;
 use crate::config::{BbsConfig, FilesConfig, WebConfig};
-use crate::db::{OneTimeTokenRepository, RefreshTokenRepository};
+use crate::db::{OneTimeTokenRepository, RefreshTokenRepository, Role};
 use crate::file::FileStorage;
+use crate::notify::NotificationHub;
+use crate::qwk::PacketOptions;
 use crate::Database;
 
 use super::handlers::{AppState, SharedDatabase};
@@ -24,7 +26,7 @@ pub struct WebServer {
     /// Server address.
     addr: SocketAddr,
     /// Application state.
-    app_state: Arc<AppState>,
+    app_state: AppState,
     /// JWT state.
     jwt_state: Arc<JwtState>,
     /// Web configuration.
@@ -79,7 +81,7 @@ impl WebServer {
 
         Self {
             addr,
-            app_state: Arc::new(app_state),
+            app_state,
             jwt_state,
             web_config: config.clone(),
             chat_manager: None,
@@ -92,6 +94,30 @@ impl WebServer {
         self
     }
 
+    /// Set the QWK packet options.
+    pub fn with_qwk(mut self, options: PacketOptions) -> Self {
+        self.app_state = self.app_state.with_qwk(options);
+        self
+    }
+
+    /// Set the hub that pushes subscription notifications.
+    pub fn with_notification_hub(mut self, hub: Arc<NotificationHub>) -> Self {
+        self.app_state = self.app_state.with_notification_hub(hub);
+        self
+    }
+
+    /// Set the domain used in exported mbox files.
+    pub fn with_message_domain(mut self, domain: impl Into<String>) -> Self {
+        self.app_state = self.app_state.with_message_domain(domain);
+        self
+    }
+
+    /// Set the minimum role for creating polls.
+    pub fn with_poll_create_role(mut self, role: Role) -> Self {
+        self.app_state = self.app_state.with_poll_create_role(role);
+        self
+    }
+
     /// Create a new web server from a raw Database.
     pub fn from_database(config: &WebConfig, db: Database) -> Self {
         Self::new(config, Arc::new(db), None, None, true)
@@ -191,7 +217,7 @@ impl WebServer {
         let db = self.app_state.db.clone();
 
         let mut router = create_router(
-            self.app_state,
+            Arc::new(self.app_state),
             self.jwt_state,
             self.chat_manager,
             &self.web_config,
@@ -233,7 +259,7 @@ impl WebServer {
         let db = self.app_state.db.clone();
 
         let mut router = create_router(
-            self.app_state,
+            Arc::new(self.app_state),
             self.jwt_state,
             self.chat_manager,
             &self.web_config,
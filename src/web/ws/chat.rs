@@ -15,6 +15,9 @@ use tokio::sync::broadcast;
 
 use crate::chat::{ChatMessage, ChatParticipant, ChatRoom, ChatRoomManager, MessageType};
 use crate::db::{DbPool, OneTimeTokenRepository, TokenPurpose, UserRepository};
+use crate::filter::{ContentFilterService, FilterTarget};
+use crate::twit::{KillFile, TwitFilterService};
+use crate::HobbsError;
 
 use super::messages::{ClientMessage, ParticipantInfo, RoomInfo, ServerMessage};
 
@@ -23,6 +26,9 @@ use super::messages::{ClientMessage, ParticipantInfo, RoomInfo, ServerMessage};
 pub struct WsQuery {
     /// One-time token for authentication.
     pub token: String,
+    /// Include messages hidden by the user's twit filters.
+    #[serde(default)]
+    pub show_hidden: bool,
 }
 
 /// State for WebSocket chat handler.
@@ -45,9 +51,9 @@ impl ChatWsState {
 }
 
 /// User info extracted from one-time token validation.
-struct TokenUserInfo {
-    user_id: i64,
-    username: String,
+pub(crate) struct TokenUserInfo {
+    pub(crate) user_id: i64,
+    pub(crate) username: String,
 }
 
 /// WebSocket chat handler.
@@ -78,12 +84,25 @@ pub async fn chat_ws_handler(
         user_info.user_id
     );
 
+    // Messages hidden by the user's twit filters are not forwarded
+    let kill_file = if query.show_hidden {
+        KillFile::default()
+    } else {
+        TwitFilterService::new(&state.db_pool)
+            .kill_file(user_info.user_id)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::error!("Failed to load twit filters: {}", e);
+                KillFile::default()
+            })
+    };
+
     // Upgrade to WebSocket
-    ws.on_upgrade(move |socket| handle_socket(socket, state, user_info))
+    ws.on_upgrade(move |socket| handle_socket(socket, state, user_info, kill_file))
 }
 
 /// Validate a one-time token and return user info.
-async fn validate_one_time_token(
+pub(crate) async fn validate_one_time_token(
     db_pool: &DbPool,
     token: &str,
 ) -> Result<TokenUserInfo, String> {
@@ -115,6 +134,7 @@ async fn handle_socket(
     socket: WebSocket,
     state: Arc<ChatWsState>,
     user_info: TokenUserInfo,
+    kill_file: KillFile,
 ) {
     let session_id = format!("web-{}-{}", user_info.user_id, uuid::Uuid::new_v4());
     let user_id = user_info.user_id;
@@ -202,6 +222,9 @@ async fn handle_socket(
                 }
             } => {
                 if let Some(chat_msg) = msg {
+                    if kill_file.hides_chat(&chat_msg.sender_name, chat_msg.message_type, &chat_msg.content) {
+                        continue;
+                    }
                     let server_msg = chat_message_to_server_message(&chat_msg);
                     if let Ok(json) = serde_json::to_string(&server_msg) {
                         if ws_sender.send(Message::Text(json.into())).await.is_err() {
@@ -218,6 +241,37 @@ async fn handle_socket(
     tracing::debug!("WebSocket session ended: {}", session_id);
 }
 
+/// Run the content filter over a chat message.
+///
+/// Returns None, after sending an error to the client, if the message is not
+/// sent. Chat cannot be held for moderation, so a hold rejects the message.
+async fn filter_message(
+    ws_sender: &mut futures::stream::SplitSink<WebSocket, Message>,
+    state: &ChatWsState,
+    user_id: i64,
+    content: String,
+) -> Option<String> {
+    let mut texts = [content];
+    let error = match ContentFilterService::new(&state.db_pool)
+        .apply_strict(Some(user_id), FilterTarget::Chat, &mut texts)
+        .await
+    {
+        Ok(()) => {
+            let [content] = texts;
+            return Some(content);
+        }
+        Err(HobbsError::Validation(msg)) => ServerMessage::error("content_rejected", msg),
+        Err(e) => {
+            tracing::error!("Failed to filter chat message: {}", e);
+            ServerMessage::error("internal_error", "Failed to send message")
+        }
+    };
+    if let Ok(json) = serde_json::to_string(&error) {
+        let _ = ws_sender.send(Message::Text(json)).await;
+    }
+    None
+}
+
 /// Handle a client message.
 #[allow(clippy::too_many_arguments)]
 async fn handle_client_message(
@@ -297,6 +351,9 @@ async fn handle_client_message(
 
         ClientMessage::Message { content } => {
             if let Some(ref room) = current_room {
+                let Some(content) = filter_message(ws_sender, state, user_id, content).await else {
+                    return;
+                };
                 // Send message to room
                 room.send_message(session_id, &content).await;
             } else {
@@ -309,6 +366,9 @@ async fn handle_client_message(
 
         ClientMessage::Action { content } => {
             if let Some(ref room) = current_room {
+                let Some(content) = filter_message(ws_sender, state, user_id, content).await else {
+                    return;
+                };
                 // Send action to room
                 room.send_action(session_id, &content).await;
             } else {
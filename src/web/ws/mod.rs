@@ -3,9 +3,12 @@
 //! This module provides WebSocket support for:
 //! - Real-time chat communication
 //! - Interoperability with Telnet clients
+//! - Live notifications about subscribed boards and threads
 
 pub mod chat;
 pub mod messages;
+pub mod notifications;
 
 pub use chat::{chat_ws_handler, ChatWsState};
 pub use messages::{ClientMessage, ServerMessage};
+pub use notifications::notification_ws_handler;
@@ -0,0 +1,140 @@
+//! Notification WebSocket handler.
+//!
+//! Pushes notifications about new posts in subscribed boards and threads to
+//! the browser as they are queued.
+
+use axum::{
+    extract::{
+        ws::{Message, WebSocket},
+        Query, State, WebSocketUpgrade,
+    },
+    response::Response,
+};
+use futures::{SinkExt, StreamExt};
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::notify::{NotificationHub, NotificationService};
+use crate::web::dto::NotificationResponse;
+use crate::web::handlers::AppState;
+
+use super::chat::{validate_one_time_token, WsQuery};
+
+/// Message pushed to the client.
+#[derive(Debug, serde::Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum NotificationMessage {
+    /// A new post in a subscribed board or thread.
+    Notification {
+        /// The notification.
+        notification: NotificationResponse,
+    },
+}
+
+/// WebSocket notification handler.
+///
+/// GET /api/notifications/ws?token={one_time_token}
+///
+/// The token must be obtained from POST /api/auth/one-time-token with purpose "websocket".
+/// Pushed notifications are marked as delivered.
+pub async fn notification_ws_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<WsQuery>,
+) -> Response {
+    let Some(hub) = state.notification_hub.clone() else {
+        return Response::builder()
+            .status(503)
+            .body("Notifications unavailable".into())
+            .unwrap();
+    };
+
+    let user_info = match validate_one_time_token(state.db.pool(), &query.token).await {
+        Ok(info) => info,
+        Err(e) => {
+            tracing::debug!("Notification WebSocket rejected: {}", e);
+            return Response::builder()
+                .status(401)
+                .body("Unauthorized".into())
+                .unwrap();
+        }
+    };
+
+    let user_id = user_info.user_id;
+    ws.on_upgrade(move |socket| handle_socket(socket, state, hub, user_id))
+}
+
+/// Forward the user's notifications until the socket closes.
+async fn handle_socket(
+    socket: WebSocket,
+    state: Arc<AppState>,
+    hub: Arc<NotificationHub>,
+    user_id: i64,
+) {
+    let (mut ws_sender, mut ws_receiver) = socket.split();
+    let mut notifications = hub.subscribe();
+
+    loop {
+        tokio::select! {
+            msg = ws_receiver.next() => {
+                match msg {
+                    Some(Ok(Message::Ping(data))) => {
+                        let _ = ws_sender.send(Message::Pong(data)).await;
+                    }
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => {}
+                }
+            }
+            received = notifications.recv() => {
+                let notification = match received {
+                    Ok(notification) if notification.user_id == user_id => notification,
+                    Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                };
+                let id = notification.id;
+                let message = NotificationMessage::Notification {
+                    notification: notification.into(),
+                };
+                let Ok(json) = serde_json::to_string(&message) else {
+                    continue;
+                };
+                if ws_sender.send(Message::Text(json)).await.is_err() {
+                    break;
+                }
+                if let Err(e) = NotificationService::new(&state.db)
+                    .mark_delivered(user_id, id)
+                    .await
+                {
+                    tracing::warn!("Failed to mark notification {} delivered: {}", id, e);
+                }
+            }
+        }
+    }
+
+    tracing::debug!("Notification WebSocket closed for user {}", user_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_format() {
+        let message = NotificationMessage::Notification {
+            notification: NotificationResponse {
+                id: 1,
+                post_id: 2,
+                board_id: 3,
+                board_name: "general".to_string(),
+                thread_id: Some(4),
+                title: "Hello".to_string(),
+                author: "alice".to_string(),
+                created_at: "2024-01-01T00:00:00Z".to_string(),
+            },
+        };
+        let json = serde_json::to_value(&message).unwrap();
+        assert_eq!(json["type"], "notification");
+        assert_eq!(json["notification"]["post_id"], 2);
+        assert_eq!(json["notification"]["thread_id"], 4);
+    }
+}
@@ -561,6 +561,12 @@ pub fn test_config() -> Config {
         templates: Default::default(),
         terminal: Default::default(),
         rss: Default::default(),
+        qwk: Default::default(),
+        ftn: Default::default(),
+        nntp: Default::default(),
+        notifications: Default::default(),
+        retention: Default::default(),
+        polls: Default::default(),
         web: Default::default(),
         ssh: Default::default(),
         rate_limits: Default::default(),
@@ -183,6 +183,15 @@ async fn test_change_language_en_to_ja() {
     // Keep auto paging as default
     client.send_line("").await.unwrap();
 
+    // Wait for color display prompt
+    let _ = client
+        .recv_timeout(Duration::from_secs(1))
+        .await
+        .unwrap_or_default();
+
+    // Keep color display as default
+    client.send_line("").await.unwrap();
+
     // Wait for settings saved message and return to main menu
     // After SettingsChanged, we go back to main menu (not profile)
     let mut response = client.recv_timeout(Duration::from_secs(2)).await.unwrap();
@@ -292,6 +301,15 @@ async fn test_change_encoding_utf8_to_shiftjis() {
     // Keep auto paging as default
     client.send_line("").await.unwrap();
 
+    // Wait for color display prompt
+    let _ = client
+        .recv_timeout(Duration::from_secs(1))
+        .await
+        .unwrap_or_default();
+
+    // Keep color display as default
+    client.send_line("").await.unwrap();
+
     // Wait for settings saved message
     let mut response = client.recv_timeout(Duration::from_secs(2)).await.unwrap();
 
@@ -395,6 +413,15 @@ async fn test_settings_persist_on_main_menu() {
     // Keep auto paging as default
     client.send_line("").await.unwrap();
 
+    // Wait for color display prompt
+    let _ = client
+        .recv_timeout(Duration::from_secs(1))
+        .await
+        .unwrap_or_default();
+
+    // Keep color display as default
+    client.send_line("").await.unwrap();
+
     // After SettingsChanged, we go back to main menu (not profile)
     // Get the settings saved message and/or main menu
     let mut response = client.recv_timeout(Duration::from_secs(2)).await.unwrap();
@@ -521,6 +521,243 @@ async fn test_admin_delete_board() {
     response.assert_status(axum::http::StatusCode::NOT_FOUND);
 }
 
+// ============================================================================
+// Moderation Queue Tests
+// ============================================================================
+
+/// Refresh the tokens from registration to pick up a changed role.
+async fn refresh_access_token(server: &TestServer, register_response: &Value) -> String {
+    let response = server
+        .post("/api/auth/refresh")
+        .json(&json!({
+            "refresh_token": register_response["data"]["refresh_token"]
+        }))
+        .await;
+    get_access_token(&response.json::<Value>())
+}
+
+#[tokio::test]
+async fn test_admin_moderation_queue() {
+    let (server, db) = create_test_server().await;
+
+    let board_id = BoardRepository::new(db.pool())
+        .create(&NewBoard::new("Moderated").with_moderated(true))
+        .await
+        .expect("Failed to create test board")
+        .id;
+
+    let sysop_response = register_test_user(&server, "sysop", "password123", "SysOp").await;
+    set_user_role(&db, get_user_id(&sysop_response), Role::SysOp).await;
+    let sysop_token = refresh_access_token(&server, &sysop_response).await;
+    let member_response = register_test_user(&server, "poster", "password123", "Poster").await;
+    let member_token = get_access_token(&member_response);
+
+    // Posts by members are queued instead of published
+    let response = server
+        .post(&format!("/api/boards/{}/threads", board_id))
+        .add_header(AUTHORIZATION, format!("Bearer {}", member_token))
+        .json(&json!({
+            "title": "Question",
+            "body": "Please approve me"
+        }))
+        .await;
+    response.assert_status(axum::http::StatusCode::ACCEPTED);
+    let body: Value = response.json();
+    let pending_id = body["data"]["id"].as_i64().unwrap();
+    assert_eq!(body["data"]["board_name"], "Moderated");
+    assert_eq!(body["data"]["author"]["username"], "poster");
+
+    let response = server
+        .get(&format!("/api/boards/{}/threads", board_id))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<Value>()["data"].as_array().unwrap().len(), 0);
+
+    // Only admins can see the queue
+    let response = server
+        .get("/api/admin/moderation")
+        .add_header(AUTHORIZATION, format!("Bearer {}", member_token))
+        .await;
+    response.assert_status(axum::http::StatusCode::FORBIDDEN);
+
+    let response = server
+        .get("/api/admin/moderation")
+        .add_header(AUTHORIZATION, format!("Bearer {}", sysop_token))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<Value>()["data"].as_array().unwrap().len(), 1);
+
+    // Approving publishes the post under its author
+    let response = server
+        .post(&format!("/api/admin/moderation/{}/approve", pending_id))
+        .add_header(AUTHORIZATION, format!("Bearer {}", sysop_token))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["data"]["author"]["username"], "poster");
+    assert_eq!(body["data"]["body"], "Please approve me");
+
+    let response = server
+        .get(&format!("/api/boards/{}/threads", board_id))
+        .await;
+    assert_eq!(response.json::<Value>()["data"].as_array().unwrap().len(), 1);
+
+    let response = server
+        .post(&format!("/api/admin/moderation/{}/approve", pending_id))
+        .add_header(AUTHORIZATION, format!("Bearer {}", sysop_token))
+        .await;
+    response.assert_status_not_found();
+
+    // Rejecting requires a reason
+    let response = server
+        .post(&format!("/api/boards/{}/threads", board_id))
+        .add_header(AUTHORIZATION, format!("Bearer {}", member_token))
+        .json(&json!({
+            "title": "Spam",
+            "body": "Buy now"
+        }))
+        .await;
+    let pending_id = response.json::<Value>()["data"]["id"].as_i64().unwrap();
+
+    let response = server
+        .post(&format!("/api/admin/moderation/{}/reject", pending_id))
+        .add_header(AUTHORIZATION, format!("Bearer {}", sysop_token))
+        .json(&json!({ "reason": "" }))
+        .await;
+    response.assert_status(axum::http::StatusCode::UNPROCESSABLE_ENTITY);
+
+    let response = server
+        .post(&format!("/api/admin/moderation/{}/reject", pending_id))
+        .add_header(AUTHORIZATION, format!("Bearer {}", sysop_token))
+        .json(&json!({ "reason": "No advertising" }))
+        .await;
+    response.assert_status_ok();
+
+    let response = server
+        .get("/api/admin/moderation")
+        .add_header(AUTHORIZATION, format!("Bearer {}", sysop_token))
+        .await;
+    assert_eq!(response.json::<Value>()["data"].as_array().unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn test_admin_posts_skip_moderation() {
+    let (server, db) = create_test_server().await;
+
+    let board_id = BoardRepository::new(db.pool())
+        .create(&NewBoard::new("Moderated").with_moderated(true))
+        .await
+        .expect("Failed to create test board")
+        .id;
+
+    let sysop_response = register_test_user(&server, "sysop", "password123", "SysOp").await;
+    set_user_role(&db, get_user_id(&sysop_response), Role::SysOp).await;
+    let sysop_token = refresh_access_token(&server, &sysop_response).await;
+
+    let response = server
+        .post(&format!("/api/boards/{}/threads", board_id))
+        .add_header(AUTHORIZATION, format!("Bearer {}", sysop_token))
+        .json(&json!({
+            "title": "Announcement",
+            "body": "Published right away"
+        }))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<Value>()["data"]["title"], "Announcement");
+}
+
+// ============================================================================
+// Board Archive Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_admin_export_and_import_boards() {
+    let (server, db) = create_test_server().await;
+    let board_id = create_test_board(&db, "Archived").await;
+
+    let sysop_response = register_test_user(&server, "sysop", "password123", "SysOp").await;
+    set_user_role(&db, get_user_id(&sysop_response), Role::SysOp).await;
+    let sysop_token = refresh_access_token(&server, &sysop_response).await;
+
+    server
+        .post(&format!("/api/boards/{}/threads", board_id))
+        .add_header(AUTHORIZATION, format!("Bearer {}", sysop_token))
+        .json(&json!({
+            "title": "Old news",
+            "body": "Keep this"
+        }))
+        .await
+        .assert_status_ok();
+
+    // JSON export
+    let response = server
+        .get(&format!("/api/admin/boards/export?board_id={}", board_id))
+        .add_header(AUTHORIZATION, format!("Bearer {}", sysop_token))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.header("content-type"), "application/json");
+    let archive = response.text();
+    let body: Value = serde_json::from_str(&archive).unwrap();
+    assert_eq!(body["format"], "hobbs-board-archive");
+    assert_eq!(body["boards"][0]["threads"][0]["author"], "sysop");
+
+    // mbox export
+    let response = server
+        .get("/api/admin/boards/export?format=mbox")
+        .add_header(AUTHORIZATION, format!("Bearer {}", sysop_token))
+        .await;
+    response.assert_status_ok();
+    assert!(response.text().starts_with("From sysop@"));
+
+    // Import into a fresh board after deleting the original
+    server
+        .delete(&format!("/api/admin/boards/{}", board_id))
+        .add_header(AUTHORIZATION, format!("Bearer {}", sysop_token))
+        .await
+        .assert_status_ok();
+    let response = server
+        .post("/api/admin/boards/import")
+        .add_header(AUTHORIZATION, format!("Bearer {}", sysop_token))
+        .content_type("application/json")
+        .text(archive)
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["data"]["boards"], 1);
+    assert_eq!(body["data"]["posts"], 1);
+
+    // Invalid archives are rejected
+    server
+        .post("/api/admin/boards/import")
+        .add_header(AUTHORIZATION, format!("Bearer {}", sysop_token))
+        .content_type("application/json")
+        .text(r#"{"format":"other"}"#)
+        .await
+        .assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn test_admin_export_boards_requires_sysop() {
+    let (server, db) = create_test_server().await;
+
+    let subop_response = register_test_user(&server, "subop", "password123", "SubOp").await;
+    set_user_role(&db, get_user_id(&subop_response), Role::SubOp).await;
+    let subop_token = refresh_access_token(&server, &subop_response).await;
+
+    server
+        .get("/api/admin/boards/export")
+        .add_header(AUTHORIZATION, format!("Bearer {}", subop_token))
+        .await
+        .assert_status(axum::http::StatusCode::FORBIDDEN);
+    server
+        .post("/api/admin/boards/import")
+        .add_header(AUTHORIZATION, format!("Bearer {}", subop_token))
+        .content_type("application/json")
+        .text("{}")
+        .await
+        .assert_status(axum::http::StatusCode::FORBIDDEN);
+}
+
 // ============================================================================
 // Permission Edge Cases
 // ============================================================================
@@ -4,7 +4,7 @@
 
 use axum::http::header::AUTHORIZATION;
 use axum_test::TestServer;
-use hobbs::board::{BoardRepository, BoardType, NewBoard};
+use hobbs::board::{BoardGroupRepository, BoardRepository, BoardType, NewBoard, NewBoardGroup};
 use hobbs::config::WebConfig;
 use hobbs::db::{Role, UserRepository, UserUpdate};
 use hobbs::web::handlers::AppState;
@@ -198,6 +198,45 @@ async fn test_list_boards_member_can_see_all() {
     assert_eq!(boards.len(), 2);
 }
 
+#[tokio::test]
+async fn test_board_tree() {
+    let (server, db) = create_test_server().await;
+
+    let groups = BoardGroupRepository::new(db.pool());
+    let hobby = groups.create(&NewBoardGroup::new("Hobby")).await.unwrap();
+    let staff = groups
+        .create(&NewBoardGroup::new("Staff").with_min_read_role(Role::SubOp))
+        .await
+        .unwrap();
+    groups.create(&NewBoardGroup::new("Empty")).await.unwrap();
+
+    let boards = BoardRepository::new(db.pool());
+    create_test_board(&db, "General", BoardType::Thread).await;
+    boards
+        .create(&NewBoard::new("Photos").with_group(hobby.id))
+        .await
+        .unwrap();
+    boards
+        .create(&NewBoard::new("Ops").with_group(staff.id))
+        .await
+        .unwrap();
+
+    let response = server.get("/api/boards/tree").await;
+
+    response.assert_status_ok();
+
+    // Guests see the readable group only; empty groups are left out
+    let body: Value = response.json();
+    let tree = &body["data"];
+    let tree_groups = tree["groups"].as_array().unwrap();
+    assert_eq!(tree_groups.len(), 1);
+    assert_eq!(tree_groups[0]["name"], "Hobby");
+    assert_eq!(tree_groups[0]["boards"][0]["name"], "Photos");
+    assert!(tree_groups[0].get("unread_count").is_none());
+    assert_eq!(tree["boards"].as_array().unwrap().len(), 1);
+    assert_eq!(tree["boards"][0]["name"], "General");
+}
+
 // ============================================================================
 // Get Board Tests
 // ============================================================================
@@ -418,6 +457,145 @@ async fn test_create_thread_post_success() {
     assert_eq!(body["data"]["body"], "This is a reply!");
 }
 
+#[tokio::test]
+async fn test_reply_tree() {
+    let (server, db) = create_test_server().await;
+
+    let board_id = create_test_board(&db, "Test Board", BoardType::Thread).await;
+
+    let login_response = register_test_user(&server, "treeuser", "password123", "Tree User").await;
+    let access_token = login_response["data"]["access_token"].as_str().unwrap();
+
+    let create_response = server
+        .post(&format!("/api/boards/{}/threads", board_id))
+        .add_header(AUTHORIZATION, format!("Bearer {}", access_token))
+        .json(&json!({
+            "title": "Tree Thread",
+            "body": "Root"
+        }))
+        .await;
+    let thread_id = create_response.json::<Value>()["data"]["id"]
+        .as_i64()
+        .unwrap();
+
+    let mut ids = Vec::new();
+    for body in ["First", "Second"] {
+        let response = server
+            .post(&format!("/api/threads/{}/posts", thread_id))
+            .add_header(AUTHORIZATION, format!("Bearer {}", access_token))
+            .json(&json!({ "body": body }))
+            .await;
+        ids.push(response.json::<Value>()["data"]["id"].as_i64().unwrap());
+    }
+
+    // Reply to the first post
+    let response = server
+        .post(&format!("/api/threads/{}/posts", thread_id))
+        .add_header(AUTHORIZATION, format!("Bearer {}", access_token))
+        .json(&json!({ "body": "> First\nReply", "parent_id": ids[0] }))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["data"]["parent_id"], ids[0]);
+
+    // A parent outside the thread is rejected
+    let response = server
+        .post(&format!("/api/threads/{}/posts", thread_id))
+        .add_header(AUTHORIZATION, format!("Bearer {}", access_token))
+        .json(&json!({ "body": "Lost", "parent_id": 9999 }))
+        .await;
+    response.assert_status_bad_request();
+
+    let response = server
+        .get(&format!("/api/threads/{}/posts?tree=true", thread_id))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    let tree: Vec<(&str, i64)> = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| (p["body"].as_str().unwrap(), p["depth"].as_i64().unwrap()))
+        .collect();
+    assert_eq!(
+        tree,
+        vec![("Root", 0), ("First", 0), ("> First\nReply", 1), ("Second", 0)]
+    );
+}
+
+#[tokio::test]
+async fn test_pin_and_lock_thread() {
+    let (server, db) = create_test_server().await;
+
+    let board_id = create_test_board(&db, "Test Board", BoardType::Thread).await;
+
+    // The first user to register becomes SysOp, so register the moderator first
+    let subop = register_test_user(&server, "pinmod", "password123", "Pin Moderator").await;
+    let subop_id = subop["data"]["user"]["id"].as_i64().unwrap();
+    let member = register_test_user(&server, "pinmember", "password123", "Pin Member").await;
+    let member_token = member["data"]["access_token"].as_str().unwrap().to_string();
+
+    // Issue a token carrying the SubOp role
+    let config = create_test_config();
+    let token_issuer = AppState::new(
+        db.clone(),
+        &config.jwt_secret,
+        config.jwt_access_token_expiry_secs,
+        config.jwt_refresh_token_expiry_days,
+    );
+    let subop_token = token_issuer
+        .generate_access_token(subop_id, "pinmod", &Role::SubOp)
+        .unwrap();
+
+    let mut thread_ids = Vec::new();
+    for title in ["Announcement", "Chatter"] {
+        let response = server
+            .post(&format!("/api/boards/{}/threads", board_id))
+            .add_header(AUTHORIZATION, format!("Bearer {}", member_token))
+            .json(&json!({ "title": title, "body": "Body" }))
+            .await;
+        thread_ids.push(response.json::<Value>()["data"]["id"].as_i64().unwrap());
+    }
+    let announcement = thread_ids[0];
+
+    // Members cannot pin or lock
+    let response = server
+        .put(&format!("/api/threads/{}/status", announcement))
+        .add_header(AUTHORIZATION, format!("Bearer {}", member_token))
+        .json(&json!({ "is_pinned": true }))
+        .await;
+    response.assert_status(axum::http::StatusCode::FORBIDDEN);
+
+    let response = server
+        .put(&format!("/api/threads/{}/status", announcement))
+        .add_header(AUTHORIZATION, format!("Bearer {}", subop_token))
+        .json(&json!({ "is_pinned": true, "is_locked": true }))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["data"]["is_pinned"], true);
+    assert_eq!(body["data"]["is_locked"], true);
+
+    // The pinned thread is listed first
+    let response = server
+        .get(&format!("/api/boards/{}/threads", board_id))
+        .await;
+    let body: Value = response.json();
+    assert_eq!(body["data"][0]["title"], "Announcement");
+    assert_eq!(body["data"][0]["is_pinned"], true);
+    assert_eq!(body["data"][1]["is_locked"], false);
+
+    // The locked thread rejects replies
+    let response = server
+        .post(&format!("/api/threads/{}/posts", announcement))
+        .add_header(AUTHORIZATION, format!("Bearer {}", member_token))
+        .json(&json!({ "body": "Reply" }))
+        .await;
+    response.assert_status(axum::http::StatusCode::FORBIDDEN);
+    let body: Value = response.json();
+    assert_eq!(body["error"]["message"], "Thread is locked");
+}
+
 #[tokio::test]
 async fn test_list_thread_posts_success() {
     let (server, db) = create_test_server().await;
@@ -529,6 +707,48 @@ async fn test_list_flat_posts_success() {
     assert_eq!(posts.len(), 3);
 }
 
+// ============================================================================
+// Search Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_search_posts() {
+    let (server, db) = create_test_server().await;
+
+    let board_id = create_test_board(&db, "Flat Board", BoardType::Flat).await;
+
+    let login_response =
+        register_test_user(&server, "searchuser", "password123", "Search User").await;
+    let access_token = login_response["data"]["access_token"].as_str().unwrap();
+
+    for body in ["東京都の天気", "Weather in Osaka"] {
+        server
+            .post(&format!("/api/boards/{}/posts", board_id))
+            .add_header(AUTHORIZATION, format!("Bearer {}", access_token))
+            .json(&json!({
+                "title": "Weather",
+                "body": body
+            }))
+            .await
+            .assert_status_ok();
+    }
+
+    let response = server.get("/api/boards/search?q=東京").await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    let results = body["data"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["post"]["body"], "東京都の天気");
+    assert_eq!(results[0]["board_name"], "Flat Board");
+
+    let response = server.get("/api/boards/search?q=weather").await;
+    let body: Value = response.json();
+    assert_eq!(body["data"].as_array().unwrap().len(), 2);
+
+    let response = server.get("/api/boards/search?q=%20").await;
+    response.assert_status_bad_request();
+}
+
 // ============================================================================
 // Delete Post Tests
 // ============================================================================
@@ -627,6 +847,75 @@ async fn test_delete_other_user_post_forbidden() {
     response.assert_status(axum::http::StatusCode::FORBIDDEN);
 }
 
+// ============================================================================
+// Revision Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_post_revisions() {
+    let (server, db) = create_test_server().await;
+
+    let board_id = create_test_board(&db, "Flat Board", BoardType::Flat).await;
+
+    let login_response = register_test_user(&server, "reviser", "password123", "Reviser").await;
+    let access_token = login_response["data"]["access_token"].as_str().unwrap();
+
+    let post_response = server
+        .post(&format!("/api/boards/{}/posts", board_id))
+        .add_header(AUTHORIZATION, format!("Bearer {}", access_token))
+        .json(&json!({
+            "title": "Notes",
+            "body": "line one\nline two"
+        }))
+        .await;
+    let post_id = post_response.json::<Value>()["data"]["id"]
+        .as_i64()
+        .unwrap();
+
+    let response = server
+        .patch(&format!("/api/posts/{}", post_id))
+        .add_header(AUTHORIZATION, format!("Bearer {}", access_token))
+        .json(&json!({ "body": "line one\nline 2" }))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert!(body["data"]["edited_at"].is_string());
+
+    let response = server
+        .get(&format!("/api/posts/{}/revisions", post_id))
+        .add_header(AUTHORIZATION, format!("Bearer {}", access_token))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    let versions = body["data"].as_array().unwrap();
+    assert_eq!(versions.len(), 2);
+    assert_eq!(versions[0]["body"], "line one\nline two");
+    assert_eq!(versions[1]["current"], true);
+
+    let response = server
+        .get(&format!("/api/posts/{}/revisions/diff", post_id))
+        .add_header(AUTHORIZATION, format!("Bearer {}", access_token))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    let lines: Vec<String> = body["data"]["lines"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|l| format!("{}{}", l["op"].as_str().unwrap(), l["text"].as_str().unwrap()))
+        .collect();
+    assert_eq!(lines, vec![" line one", "-line two", "+line 2"]);
+
+    // Other members cannot see the history
+    let other = register_test_user(&server, "bystander", "password123", "Bystander").await;
+    let other_token = other["data"]["access_token"].as_str().unwrap();
+    let response = server
+        .get(&format!("/api/posts/{}/revisions", post_id))
+        .add_header(AUTHORIZATION, format!("Bearer {}", other_token))
+        .await;
+    response.assert_status(axum::http::StatusCode::FORBIDDEN);
+}
+
 // ============================================================================
 // Pagination Tests
 // ============================================================================
@@ -676,3 +965,83 @@ async fn test_threads_pagination() {
     assert_eq!(body["data"].as_array().unwrap().len(), 5);
     assert_eq!(body["meta"]["page"], 2);
 }
+
+// ============================================================================
+// Subscription Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_board_subscription_lifecycle() {
+    let (server, db) = create_test_server().await;
+    let board_id = create_test_board(&db, "Subscribed", BoardType::Thread).await;
+
+    let login_response = register_test_user(&server, "subuser", "password123", "Sub User").await;
+    let access_token = login_response["data"]["access_token"].as_str().unwrap();
+    let auth = format!("Bearer {}", access_token);
+
+    // Subscribe
+    let response = server
+        .post("/api/subscriptions")
+        .add_header(AUTHORIZATION, auth.clone())
+        .json(&json!({ "board_id": board_id }))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    let id = body["data"]["id"].as_i64().unwrap();
+    assert_eq!(body["data"]["name"], "Subscribed");
+    assert_eq!(body["data"]["digest"], false);
+
+    // Switch to digest delivery
+    let response = server
+        .put(&format!("/api/subscriptions/{}", id))
+        .add_header(AUTHORIZATION, auth.clone())
+        .json(&json!({ "digest": true }))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["data"]["digest"], true);
+
+    // List
+    let response = server
+        .get("/api/subscriptions")
+        .add_header(AUTHORIZATION, auth.clone())
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+
+    // Unsubscribe
+    server
+        .delete(&format!("/api/subscriptions/{}", id))
+        .add_header(AUTHORIZATION, auth.clone())
+        .await
+        .assert_status_ok();
+    server
+        .delete(&format!("/api/subscriptions/{}", id))
+        .add_header(AUTHORIZATION, auth)
+        .await
+        .assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_subscription_requires_single_target() {
+    let (server, db) = create_test_server().await;
+    let board_id = create_test_board(&db, "Target", BoardType::Thread).await;
+
+    let login_response =
+        register_test_user(&server, "targetuser", "password123", "Target User").await;
+    let access_token = login_response["data"]["access_token"].as_str().unwrap();
+
+    server
+        .post("/api/subscriptions")
+        .add_header(AUTHORIZATION, format!("Bearer {}", access_token))
+        .json(&json!({ "board_id": board_id, "thread_id": 1 }))
+        .await
+        .assert_status_bad_request();
+    server
+        .post("/api/subscriptions")
+        .add_header(AUTHORIZATION, format!("Bearer {}", access_token))
+        .json(&json!({}))
+        .await
+        .assert_status_bad_request();
+}
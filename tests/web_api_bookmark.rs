@@ -0,0 +1,211 @@
+//! Web API Bookmark Tests
+//!
+//! Integration tests for bookmark endpoints.
+
+use axum::http::header::AUTHORIZATION;
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use hobbs::board::{
+    BoardRepository, NewBoard, NewThread, NewThreadPost, PostRepository, ThreadRepository,
+};
+use hobbs::config::WebConfig;
+use hobbs::web::handlers::AppState;
+use hobbs::web::middleware::JwtState;
+use hobbs::web::router::create_router;
+use hobbs::Database;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// Create a test configuration.
+fn create_test_config() -> WebConfig {
+    WebConfig {
+        enabled: true,
+        host: "127.0.0.1".to_string(),
+        port: 0,
+        cors_origins: vec![],
+        jwt_secret: "test-secret-key-for-testing-only".to_string(),
+        jwt_access_token_expiry_secs: 900,
+        jwt_refresh_token_expiry_days: 7,
+        serve_static: false,
+        static_path: "web/dist".to_string(),
+        login_rate_limit: 100,
+        api_rate_limit: 1000,
+    }
+}
+
+/// Create a test server with an in-memory database.
+async fn create_test_server() -> (TestServer, Arc<Database>) {
+    let config = create_test_config();
+
+    let db = Database::open_in_memory()
+        .await
+        .expect("Failed to create test database");
+    let shared_db = Arc::new(db);
+
+    let app_state = Arc::new(AppState::new(
+        shared_db.clone(),
+        &config.jwt_secret,
+        config.jwt_access_token_expiry_secs,
+        config.jwt_refresh_token_expiry_days,
+    ));
+    let jwt_state = Arc::new(JwtState::new(&config.jwt_secret));
+    let router = create_router(app_state, jwt_state, None, &config);
+    let server = TestServer::new(router).expect("Failed to create test server");
+
+    (server, shared_db)
+}
+
+/// Register a user and return (access token, user ID).
+async fn register(server: &TestServer, username: &str) -> (String, i64) {
+    let response = server
+        .post("/api/auth/register")
+        .json(&json!({
+            "username": username,
+            "password": "password123",
+            "nickname": username
+        }))
+        .await;
+    let body = response.json::<Value>();
+    (
+        body["data"]["access_token"].as_str().unwrap().to_string(),
+        body["data"]["user"]["id"].as_i64().unwrap(),
+    )
+}
+
+fn bearer(token: &str) -> String {
+    format!("Bearer {}", token)
+}
+
+/// Create a thread with one post and return (thread ID, post ID).
+async fn create_thread(db: &Database, author_id: i64) -> (i64, i64) {
+    let board = BoardRepository::new(db.pool())
+        .create(&NewBoard::new("general"))
+        .await
+        .unwrap();
+    let thread = ThreadRepository::new(db.pool())
+        .create(&NewThread::new(board.id, "FAQ", author_id))
+        .await
+        .unwrap();
+    let post = PostRepository::new(db.pool())
+        .create_thread_post(&NewThreadPost::new(board.id, thread.id, author_id, "body"))
+        .await
+        .unwrap();
+    (thread.id, post.id)
+}
+
+#[tokio::test]
+async fn test_bookmark_lifecycle() {
+    let (server, db) = create_test_server().await;
+    let (token, user_id) = register(&server, "alice").await;
+    let (thread_id, post_id) = create_thread(&db, user_id).await;
+
+    let response = server
+        .post("/api/bookmarks")
+        .add_header(AUTHORIZATION, bearer(&token))
+        .json(&json!({ "thread_id": thread_id, "note": "read first" }))
+        .await;
+    response.assert_status_ok();
+    let bookmark = response.json::<Value>()["data"].clone();
+    assert_eq!(bookmark["title"], "FAQ");
+    assert_eq!(bookmark["board_name"], "general");
+    assert_eq!(bookmark["note"], "read first");
+    assert_eq!(bookmark["deleted"], false);
+    let bookmark_id = bookmark["id"].as_i64().unwrap();
+
+    let response = server
+        .post("/api/bookmarks")
+        .add_header(AUTHORIZATION, bearer(&token))
+        .json(&json!({ "post_id": post_id }))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(
+        response.json::<Value>()["data"]["post_thread_id"],
+        thread_id
+    );
+
+    let response = server
+        .put(&format!("/api/bookmarks/{}", bookmark_id))
+        .add_header(AUTHORIZATION, bearer(&token))
+        .json(&json!({ "note": "chapter 3" }))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<Value>()["data"]["note"], "chapter 3");
+
+    // Deleting the thread leaves tombstones instead of errors
+    ThreadRepository::new(db.pool())
+        .delete(thread_id)
+        .await
+        .unwrap();
+    let response = server
+        .get("/api/bookmarks")
+        .add_header(AUTHORIZATION, bearer(&token))
+        .await;
+    response.assert_status_ok();
+    let bookmarks = response.json::<Value>()["data"].clone();
+    assert_eq!(bookmarks.as_array().unwrap().len(), 2);
+    for bookmark in bookmarks.as_array().unwrap() {
+        assert_eq!(bookmark["deleted"], true);
+        assert_eq!(bookmark["title"], "FAQ");
+        assert!(bookmark.get("board_id").is_none());
+    }
+
+    let response = server
+        .delete(&format!("/api/bookmarks/{}", bookmark_id))
+        .add_header(AUTHORIZATION, bearer(&token))
+        .await;
+    response.assert_status_ok();
+    let response = server
+        .get("/api/bookmarks")
+        .add_header(AUTHORIZATION, bearer(&token))
+        .await;
+    assert_eq!(
+        response.json::<Value>()["data"].as_array().unwrap().len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_bookmark_errors() {
+    let (server, db) = create_test_server().await;
+    let (alice, alice_id) = register(&server, "alice").await;
+    let (bob, _) = register(&server, "bob").await;
+    let (thread_id, post_id) = create_thread(&db, alice_id).await;
+
+    // Exactly one target
+    let response = server
+        .post("/api/bookmarks")
+        .add_header(AUTHORIZATION, bearer(&alice))
+        .json(&json!({ "thread_id": thread_id, "post_id": post_id }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+
+    let response = server
+        .post("/api/bookmarks")
+        .add_header(AUTHORIZATION, bearer(&alice))
+        .json(&json!({ "thread_id": 9999 }))
+        .await;
+    response.assert_status(StatusCode::NOT_FOUND);
+
+    let response = server
+        .post("/api/bookmarks")
+        .add_header(AUTHORIZATION, bearer(&alice))
+        .json(&json!({ "thread_id": thread_id, "note": "x".repeat(201) }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+
+    // Other users' bookmarks are not found
+    let response = server
+        .post("/api/bookmarks")
+        .add_header(AUTHORIZATION, bearer(&alice))
+        .json(&json!({ "thread_id": thread_id }))
+        .await;
+    let bookmark_id = response.json::<Value>()["data"]["id"].as_i64().unwrap();
+    let response = server
+        .delete(&format!("/api/bookmarks/{}", bookmark_id))
+        .add_header(AUTHORIZATION, bearer(&bob))
+        .await;
+    response.assert_status(StatusCode::NOT_FOUND);
+
+    let response = server.get("/api/bookmarks").await;
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
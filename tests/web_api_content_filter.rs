@@ -0,0 +1,214 @@
+//! Web API Content Filter Tests
+//!
+//! Integration tests for content filter administration and enforcement.
+
+use axum::http::header::AUTHORIZATION;
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use hobbs::board::{BoardRepository, NewBoard};
+use hobbs::config::WebConfig;
+use hobbs::web::handlers::AppState;
+use hobbs::web::middleware::JwtState;
+use hobbs::web::router::create_router;
+use hobbs::Database;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// Create a test configuration.
+fn create_test_config() -> WebConfig {
+    WebConfig {
+        enabled: true,
+        host: "127.0.0.1".to_string(),
+        port: 0,
+        cors_origins: vec![],
+        jwt_secret: "test-secret-key-for-testing-only".to_string(),
+        jwt_access_token_expiry_secs: 900,
+        jwt_refresh_token_expiry_days: 7,
+        serve_static: false,
+        static_path: "web/dist".to_string(),
+        login_rate_limit: 100,
+        api_rate_limit: 1000,
+    }
+}
+
+/// Create a test server with an in-memory database.
+async fn create_test_server() -> (TestServer, Arc<Database>) {
+    let config = create_test_config();
+
+    let db = Database::open_in_memory()
+        .await
+        .expect("Failed to create test database");
+    let shared_db = Arc::new(db);
+
+    let app_state = Arc::new(AppState::new(
+        shared_db.clone(),
+        &config.jwt_secret,
+        config.jwt_access_token_expiry_secs,
+        config.jwt_refresh_token_expiry_days,
+    ));
+    let jwt_state = Arc::new(JwtState::new(&config.jwt_secret));
+    let router = create_router(app_state, jwt_state, None, &config);
+    let server = TestServer::new(router).expect("Failed to create test server");
+
+    (server, shared_db)
+}
+
+/// Register a user and return (access token, user ID).
+async fn register(server: &TestServer, username: &str) -> (String, i64) {
+    let response = server
+        .post("/api/auth/register")
+        .json(&json!({
+            "username": username,
+            "password": "password123",
+            "nickname": username
+        }))
+        .await;
+    let body = response.json::<Value>();
+    (
+        body["data"]["access_token"].as_str().unwrap().to_string(),
+        body["data"]["user"]["id"].as_i64().unwrap(),
+    )
+}
+
+fn bearer(token: &str) -> String {
+    format!("Bearer {}", token)
+}
+
+/// Add a filter rule as the SysOp and return its ID.
+async fn add_rule(
+    server: &TestServer,
+    token: &str,
+    rule_type: &str,
+    pattern: &str,
+    action: &str,
+) -> i64 {
+    let response = server
+        .post("/api/admin/filter-rules")
+        .add_header(AUTHORIZATION, bearer(token))
+        .json(&json!({ "rule_type": rule_type, "pattern": pattern, "action": action }))
+        .await;
+    response.assert_status_ok();
+    response.json::<Value>()["data"]["id"].as_i64().unwrap()
+}
+
+#[tokio::test]
+async fn test_filter_rule_management() {
+    let (server, _db) = create_test_server().await;
+    let (sysop_token, _) = register(&server, "sysop").await;
+    let (member_token, _) = register(&server, "alice").await;
+
+    let rule_id = add_rule(&server, &sysop_token, "word", "spam, scam", "mask").await;
+
+    let response = server
+        .put(&format!("/api/admin/filter-rules/{}", rule_id))
+        .add_header(AUTHORIZATION, bearer(&sysop_token))
+        .json(&json!({ "is_active": false }))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<Value>()["data"]["is_active"], false);
+
+    let response = server
+        .get("/api/admin/filter-rules")
+        .add_header(AUTHORIZATION, bearer(&sysop_token))
+        .await;
+    response.assert_status_ok();
+    let rules = response.json::<Value>()["data"].clone();
+    assert_eq!(rules.as_array().unwrap().len(), 1);
+    assert_eq!(rules[0]["rule_type"], "word");
+    assert_eq!(rules[0]["pattern"], "spam, scam");
+    assert_eq!(rules[0]["action"], "mask");
+
+    // Invalid patterns are refused
+    let response = server
+        .post("/api/admin/filter-rules")
+        .add_header(AUTHORIZATION, bearer(&sysop_token))
+        .json(&json!({ "rule_type": "regex", "pattern": "(", "action": "reject" }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+
+    // Only the SysOp may manage rules
+    let response = server
+        .get("/api/admin/filter-rules")
+        .add_header(AUTHORIZATION, bearer(&member_token))
+        .await;
+    response.assert_status(StatusCode::FORBIDDEN);
+
+    let response = server
+        .delete(&format!("/api/admin/filter-rules/{}", rule_id))
+        .add_header(AUTHORIZATION, bearer(&sysop_token))
+        .await;
+    response.assert_status_ok();
+
+    let response = server
+        .delete(&format!("/api/admin/filter-rules/{}", rule_id))
+        .add_header(AUTHORIZATION, bearer(&sysop_token))
+        .await;
+    response.assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_filter_applies_to_posts_and_mail() {
+    let (server, db) = create_test_server().await;
+    let (sysop_token, _) = register(&server, "sysop").await;
+    let (member_token, _) = register(&server, "alice").await;
+    let board = BoardRepository::new(db.pool())
+        .create(&NewBoard::new("general"))
+        .await
+        .unwrap();
+
+    add_rule(&server, &sysop_token, "word", "spam", "mask").await;
+    add_rule(&server, &sysop_token, "regex", "buy\\s+now", "reject").await;
+    add_rule(&server, &sysop_token, "links", "1", "hold").await;
+
+    let response = server
+        .post(&format!("/api/boards/{}/threads", board.id))
+        .add_header(AUTHORIZATION, bearer(&member_token))
+        .json(&json!({ "title": "No spam here", "body": "hello" }))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<Value>()["data"]["title"], "No **** here");
+
+    let response = server
+        .post(&format!("/api/boards/{}/threads", board.id))
+        .add_header(AUTHORIZATION, bearer(&member_token))
+        .json(&json!({ "title": "Deal", "body": "buy  now!" }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+
+    let response = server
+        .post(&format!("/api/boards/{}/threads", board.id))
+        .add_header(AUTHORIZATION, bearer(&member_token))
+        .json(&json!({ "title": "Links", "body": "https://a.example https://b.example" }))
+        .await;
+    response.assert_status(StatusCode::ACCEPTED);
+
+    let response = server
+        .post("/api/mail")
+        .add_header(AUTHORIZATION, bearer(&member_token))
+        .json(&json!({ "recipient": "sysop", "subject": "Hi", "body": "buy now" }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+
+    // The SysOp is exempt
+    let response = server
+        .post(&format!("/api/boards/{}/threads", board.id))
+        .add_header(AUTHORIZATION, bearer(&sysop_token))
+        .json(&json!({ "title": "spam", "body": "buy now" }))
+        .await;
+    response.assert_status_ok();
+
+    let response = server
+        .get("/api/admin/filter-hits")
+        .add_header(AUTHORIZATION, bearer(&sysop_token))
+        .await;
+    response.assert_status_ok();
+    let hits = response.json::<Value>()["data"].clone();
+    let hits = hits.as_array().unwrap();
+    assert_eq!(hits.len(), 4);
+    assert_eq!(hits[0]["target"], "mail");
+    assert_eq!(hits[0]["action"], "reject");
+    assert_eq!(hits[0]["username"], "alice");
+    assert_eq!(hits[3]["target"], "post");
+    assert_eq!(hits[3]["action"], "mask");
+    assert_eq!(hits[3]["detail"], "spam");
+}
@@ -3,13 +3,17 @@
 //! Integration tests for mail endpoints.
 
 use axum::http::header::AUTHORIZATION;
+use axum_test::multipart::{MultipartForm, Part};
 use axum_test::TestServer;
 use hobbs::config::WebConfig;
+use hobbs::qwk::{parse_messages, write_reply_packet, QwkMessage, BLOCK_SIZE, MAIL_CONFERENCE};
+use hobbs::server::CharacterEncoding;
 use hobbs::web::handlers::AppState;
 use hobbs::web::middleware::JwtState;
 use hobbs::web::router::create_router;
 use hobbs::Database;
 use serde_json::{json, Value};
+use std::io::{Cursor, Read};
 use std::sync::Arc;
 
 /// Create a test configuration.
@@ -579,3 +583,123 @@ async fn test_reading_mail_marks_as_read() {
     let body: Value = response.json();
     assert_eq!(body["data"]["count"], 0);
 }
+
+// ============================================================================
+// QWK Tests
+// ============================================================================
+
+/// Read MESSAGES.DAT from a QWK packet.
+fn qwk_messages(packet: &[u8]) -> Vec<QwkMessage> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(packet)).unwrap();
+    let mut data = Vec::new();
+    archive
+        .by_name("MESSAGES.DAT")
+        .unwrap()
+        .read_to_end(&mut data)
+        .unwrap();
+    parse_messages(&data[BLOCK_SIZE..], CharacterEncoding::ShiftJIS).unwrap()
+}
+
+#[tokio::test]
+async fn test_qwk_download_and_reply() {
+    let (server, _db) = create_test_server().await;
+
+    let sender_response = register_test_user(&server, "sender", "password123", "Sender").await;
+    let sender_token = get_access_token(&sender_response);
+
+    let recipient_response =
+        register_test_user(&server, "recipient", "password123", "Recipient").await;
+    let recipient_token = get_access_token(&recipient_response);
+
+    server
+        .post("/api/mail")
+        .add_header(AUTHORIZATION, format!("Bearer {}", sender_token))
+        .json(&json!({
+            "recipient": "recipient",
+            "subject": "Offline",
+            "body": "Read me offline"
+        }))
+        .await
+        .assert_status_ok();
+
+    // Download the packet
+    let response = server
+        .get("/api/qwk")
+        .add_header(AUTHORIZATION, format!("Bearer {}", recipient_token))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.header("content-type"), "application/zip");
+    assert!(response
+        .header("content-disposition")
+        .to_str()
+        .unwrap()
+        .contains("HOBBS.QWK"));
+
+    let messages = qwk_messages(response.as_bytes());
+    let mail = messages.iter().find(|m| m.subject == "Offline").unwrap();
+    assert_eq!(mail.conference, MAIL_CONFERENCE);
+    assert_eq!(mail.from, "sender");
+    assert_eq!(mail.body, "Read me offline");
+
+    // Downloaded mail is marked as read
+    let response = server
+        .get("/api/mail/unread-count")
+        .add_header(AUTHORIZATION, format!("Bearer {}", recipient_token))
+        .await;
+    assert_eq!(response.json::<Value>()["data"]["count"], 0);
+
+    let response = server
+        .get("/api/qwk")
+        .add_header(AUTHORIZATION, format!("Bearer {}", recipient_token))
+        .await;
+    assert!(qwk_messages(response.as_bytes()).is_empty());
+
+    // Upload a reply
+    let reply = QwkMessage {
+        conference: MAIL_CONFERENCE,
+        number: 0,
+        private: true,
+        read: false,
+        date: Default::default(),
+        to: "sender".to_string(),
+        from: "recipient".to_string(),
+        subject: "Re: Offline".to_string(),
+        reference: mail.number,
+        body: "Replied offline".to_string(),
+    };
+    let rep = write_reply_packet("HOBBS", &[reply], CharacterEncoding::ShiftJIS).unwrap();
+    let response = server
+        .post("/api/qwk/rep")
+        .add_header(AUTHORIZATION, format!("Bearer {}", recipient_token))
+        .multipart(MultipartForm::new().add_part("file", Part::bytes(rep).file_name("HOBBS.REP")))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["data"]["mails"], 1);
+    assert_eq!(body["data"]["posts"], 0);
+
+    let response = server
+        .get("/api/mail/inbox")
+        .add_header(AUTHORIZATION, format!("Bearer {}", sender_token))
+        .await;
+    let body: Value = response.json();
+    assert_eq!(body["data"][0]["subject"], "Re: Offline");
+}
+
+#[tokio::test]
+async fn test_qwk_upload_invalid_packet() {
+    let (server, _db) = create_test_server().await;
+
+    let response = register_test_user(&server, "user", "password123", "User").await;
+    let token = get_access_token(&response);
+
+    let response = server
+        .post("/api/qwk/rep")
+        .add_header(AUTHORIZATION, format!("Bearer {}", token))
+        .multipart(MultipartForm::new().add_part("file", Part::bytes(b"garbage".to_vec())))
+        .await;
+    response.assert_status_bad_request();
+
+    let response = server.get("/api/qwk").await;
+    response.assert_status_unauthorized();
+}
@@ -0,0 +1,258 @@
+//! Web API Poll Tests
+//!
+//! Integration tests for voting booth endpoints.
+
+use axum::http::header::AUTHORIZATION;
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use hobbs::board::{BoardRepository, BoardType, NewBoard, NewThread, ThreadRepository};
+use hobbs::config::WebConfig;
+use hobbs::db::Role;
+use hobbs::web::handlers::AppState;
+use hobbs::web::middleware::JwtState;
+use hobbs::web::router::create_router;
+use hobbs::Database;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// Create a test configuration.
+fn create_test_config() -> WebConfig {
+    WebConfig {
+        enabled: true,
+        host: "127.0.0.1".to_string(),
+        port: 0,
+        cors_origins: vec![],
+        jwt_secret: "test-secret-key-for-testing-only".to_string(),
+        jwt_access_token_expiry_secs: 900,
+        jwt_refresh_token_expiry_days: 7,
+        serve_static: false,
+        static_path: "web/dist".to_string(),
+        login_rate_limit: 100,
+        api_rate_limit: 1000,
+    }
+}
+
+/// Create a test server with an in-memory database.
+async fn create_test_server(poll_create_role: Role) -> (TestServer, Arc<Database>) {
+    let config = create_test_config();
+
+    let db = Database::open_in_memory()
+        .await
+        .expect("Failed to create test database");
+    let shared_db = Arc::new(db);
+
+    let app_state = Arc::new(
+        AppState::new(
+            shared_db.clone(),
+            &config.jwt_secret,
+            config.jwt_access_token_expiry_secs,
+            config.jwt_refresh_token_expiry_days,
+        )
+        .with_poll_create_role(poll_create_role),
+    );
+    let jwt_state = Arc::new(JwtState::new(&config.jwt_secret));
+    let router = create_router(app_state, jwt_state, None, &config);
+    let server = TestServer::new(router).expect("Failed to create test server");
+
+    (server, shared_db)
+}
+
+/// Register a user and return (access token, user ID).
+///
+/// The first registered user becomes SysOp.
+async fn register(server: &TestServer, username: &str) -> (String, i64) {
+    let response = server
+        .post("/api/auth/register")
+        .json(&json!({
+            "username": username,
+            "password": "password123",
+            "nickname": username
+        }))
+        .await;
+    let body = response.json::<Value>();
+    (
+        body["data"]["access_token"].as_str().unwrap().to_string(),
+        body["data"]["user"]["id"].as_i64().unwrap(),
+    )
+}
+
+fn bearer(token: &str) -> String {
+    format!("Bearer {}", token)
+}
+
+#[tokio::test]
+async fn test_poll_lifecycle() {
+    let (server, _db) = create_test_server(Role::Member).await;
+    let (sysop, _) = register(&server, "sysop").await;
+    let (alice, _) = register(&server, "alice").await;
+    let (bob, _) = register(&server, "bob").await;
+
+    let response = server
+        .post("/api/polls")
+        .add_header(AUTHORIZATION, bearer(&alice))
+        .json(&json!({
+            "question": "Next meetup?",
+            "options": ["Saturday", "Sunday"],
+            "allow_write_in": true
+        }))
+        .await;
+    response.assert_status_ok();
+    let poll = response.json::<Value>()["data"].clone();
+    let poll_id = poll["id"].as_i64().unwrap();
+    assert_eq!(poll["status"], "open");
+    assert_eq!(poll["can_vote"], true);
+    assert_eq!(poll["can_manage"], true);
+    let saturday = poll["options"][0]["id"].as_i64().unwrap();
+
+    // Single choice polls take one option
+    let response = server
+        .post(&format!("/api/polls/{}/vote", poll_id))
+        .add_header(AUTHORIZATION, bearer(&bob))
+        .json(&json!({ "option_ids": [saturday, poll["options"][1]["id"]] }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+
+    let response = server
+        .post(&format!("/api/polls/{}/vote", poll_id))
+        .add_header(AUTHORIZATION, bearer(&bob))
+        .json(&json!({ "option_ids": [saturday] }))
+        .await;
+    response.assert_status_ok();
+    let results = response.json::<Value>()["data"].clone();
+    assert_eq!(results["voters"], 1);
+    assert_eq!(results["my_votes"], json!([saturday]));
+    assert_eq!(results["can_vote"], false);
+
+    // One vote per user
+    let response = server
+        .post(&format!("/api/polls/{}/vote", poll_id))
+        .add_header(AUTHORIZATION, bearer(&bob))
+        .json(&json!({ "option_ids": [saturday] }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+
+    // Write-ins become new options
+    let response = server
+        .post(&format!("/api/polls/{}/vote", poll_id))
+        .add_header(AUTHORIZATION, bearer(&sysop))
+        .json(&json!({ "write_in": "Friday night" }))
+        .await;
+    response.assert_status_ok();
+    let options = response.json::<Value>()["data"]["options"].clone();
+    assert_eq!(options.as_array().unwrap().len(), 3);
+    assert_eq!(options[2]["text"], "Friday night");
+    assert_eq!(options[2]["is_write_in"], true);
+
+    // Guests see results but cannot vote
+    let response = server.get(&format!("/api/polls/{}", poll_id)).await;
+    response.assert_status_ok();
+    let results = response.json::<Value>()["data"].clone();
+    assert_eq!(results["voters"], 2);
+    assert_eq!(results["can_vote"], false);
+
+    // Only the creator or a SubOp may close
+    let response = server
+        .post(&format!("/api/polls/{}/close", poll_id))
+        .add_header(AUTHORIZATION, bearer(&bob))
+        .await;
+    response.assert_status(StatusCode::FORBIDDEN);
+    let response = server
+        .post(&format!("/api/polls/{}/close", poll_id))
+        .add_header(AUTHORIZATION, bearer(&alice))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<Value>()["data"]["status"], "closed");
+
+    let response = server
+        .delete(&format!("/api/polls/{}", poll_id))
+        .add_header(AUTHORIZATION, bearer(&sysop))
+        .await;
+    response.assert_status_ok();
+    let response = server.get("/api/polls").await;
+    assert!(response.json::<Value>()["data"]
+        .as_array()
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_poll_create_role() {
+    let (server, _db) = create_test_server(Role::SubOp).await;
+    let (sysop, _) = register(&server, "sysop").await;
+    let (alice, _) = register(&server, "alice").await;
+
+    let request = json!({ "question": "Q", "options": ["a", "b"] });
+    let response = server
+        .post("/api/polls")
+        .add_header(AUTHORIZATION, bearer(&alice))
+        .json(&request)
+        .await;
+    response.assert_status(StatusCode::FORBIDDEN);
+
+    let response = server
+        .post("/api/polls")
+        .add_header(AUTHORIZATION, bearer(&sysop))
+        .json(&request)
+        .await;
+    response.assert_status_ok();
+}
+
+#[tokio::test]
+async fn test_thread_poll() {
+    let (server, db) = create_test_server(Role::Member).await;
+    let (_sysop, _) = register(&server, "sysop").await;
+    let (alice, alice_id) = register(&server, "alice").await;
+    let (bob, _) = register(&server, "bob").await;
+
+    let board = BoardRepository::new(db.pool())
+        .create(
+            &NewBoard::new("Members")
+                .with_board_type(BoardType::Thread)
+                .with_min_read_role(Role::Member),
+        )
+        .await
+        .unwrap();
+    let thread = ThreadRepository::new(db.pool())
+        .create(&NewThread::new(board.id, "Lunch", alice_id))
+        .await
+        .unwrap();
+
+    let request = json!({
+        "question": "Where?",
+        "options": ["here", "there"],
+        "thread_id": thread.id,
+        "closes_at": "2099-01-01T00:00:00Z"
+    });
+    // Only the thread's author (or a SubOp) may attach a poll
+    let response = server
+        .post("/api/polls")
+        .add_header(AUTHORIZATION, bearer(&bob))
+        .json(&request)
+        .await;
+    response.assert_status(StatusCode::FORBIDDEN);
+    let response = server
+        .post("/api/polls")
+        .add_header(AUTHORIZATION, bearer(&alice))
+        .json(&request)
+        .await;
+    response.assert_status_ok();
+    let poll = response.json::<Value>()["data"].clone();
+    assert_eq!(poll["thread_id"], thread.id);
+    assert_eq!(poll["closes_at"], "2099-01-01T00:00:00Z");
+
+    let response = server
+        .get(&format!("/api/polls?thread_id={}", thread.id))
+        .add_header(AUTHORIZATION, bearer(&bob))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<Value>()["data"][0]["id"], poll["id"]);
+
+    // Guests cannot read the board, so the poll is hidden
+    let response = server.get("/api/polls").await;
+    assert!(response.json::<Value>()["data"]
+        .as_array()
+        .unwrap()
+        .is_empty());
+    let response = server.get(&format!("/api/polls/{}", poll["id"])).await;
+    response.assert_status(StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,209 @@
+//! Web API QWK Tests
+//!
+//! Integration tests for downloading QWK packets and confirming them.
+
+use axum::http::header::AUTHORIZATION;
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use hobbs::board::{
+    BoardRepository, BoardType, NewBoard, NewFlatPost, PostRepository, UnreadRepository,
+};
+use hobbs::config::WebConfig;
+use hobbs::mail::{MailRepository, NewMail};
+use hobbs::web::handlers::AppState;
+use hobbs::web::middleware::JwtState;
+use hobbs::web::router::create_router;
+use hobbs::Database;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// Create a test configuration.
+fn create_test_config() -> WebConfig {
+    WebConfig {
+        enabled: true,
+        host: "127.0.0.1".to_string(),
+        port: 0,
+        cors_origins: vec![],
+        jwt_secret: "test-secret-key-for-testing-only".to_string(),
+        jwt_access_token_expiry_secs: 900,
+        jwt_refresh_token_expiry_days: 7,
+        serve_static: false,
+        static_path: "web/dist".to_string(),
+        login_rate_limit: 100,
+        api_rate_limit: 1000,
+    }
+}
+
+/// Create a test server with an in-memory database.
+async fn create_test_server() -> (TestServer, Arc<Database>) {
+    let config = create_test_config();
+
+    let db = Database::open_in_memory()
+        .await
+        .expect("Failed to create test database");
+    let shared_db = Arc::new(db);
+
+    let app_state = Arc::new(AppState::new(
+        shared_db.clone(),
+        &config.jwt_secret,
+        config.jwt_access_token_expiry_secs,
+        config.jwt_refresh_token_expiry_days,
+    ));
+    let jwt_state = Arc::new(JwtState::new(&config.jwt_secret));
+    let router = create_router(app_state, jwt_state, None, &config);
+    let server = TestServer::new(router).expect("Failed to create test server");
+
+    (server, shared_db)
+}
+
+/// Register a user and return (access token, user ID).
+async fn register(server: &TestServer, username: &str) -> (String, i64) {
+    let response = server
+        .post("/api/auth/register")
+        .json(&json!({
+            "username": username,
+            "password": "password123",
+            "nickname": username
+        }))
+        .await;
+    let body = response.json::<Value>();
+    (
+        body["data"]["access_token"].as_str().unwrap().to_string(),
+        body["data"]["user"]["id"].as_i64().unwrap(),
+    )
+}
+
+fn bearer(token: &str) -> String {
+    format!("Bearer {}", token)
+}
+
+/// Create a flat board with the given number of posts and return
+/// (board ID, post IDs).
+async fn create_board_with_posts(db: &Database, author_id: i64, posts: usize) -> (i64, Vec<i64>) {
+    let board = BoardRepository::new(db.pool())
+        .create(&NewBoard::new("general").with_board_type(BoardType::Flat))
+        .await
+        .unwrap();
+    let mut ids = Vec::new();
+    for i in 0..posts {
+        let post = PostRepository::new(db.pool())
+            .create_flat_post(&NewFlatPost::new(
+                board.id,
+                author_id,
+                format!("post {i}"),
+                "body",
+            ))
+            .await
+            .unwrap();
+        ids.push(post.id);
+    }
+    (board.id, ids)
+}
+
+/// Get the unread post count of a board and the unread mail count.
+async fn unread_counts(
+    server: &TestServer,
+    db: &Database,
+    token: &str,
+    user_id: i64,
+    board_id: i64,
+) -> (i64, i64) {
+    let posts = UnreadRepository::new(db.pool())
+        .get_unread_count(user_id, board_id)
+        .await
+        .unwrap();
+    let mail = server
+        .get("/api/mail/unread-count")
+        .add_header(AUTHORIZATION, bearer(token))
+        .await
+        .json::<Value>()["data"]["count"]
+        .as_i64()
+        .unwrap();
+    (posts, mail)
+}
+
+#[tokio::test]
+async fn test_download_does_not_advance_pointers_until_commit() {
+    let (server, db) = create_test_server().await;
+    let (token, user_id) = register(&server, "reader").await;
+    let (_, author_id) = register(&server, "author").await;
+    let (board_id, posts) = create_board_with_posts(&db, author_id, 2).await;
+    MailRepository::new(db.pool())
+        .create(&NewMail::new(author_id, user_id, "Hello", "Hi there"))
+        .await
+        .unwrap();
+    assert_eq!(
+        unread_counts(&server, &db, &token, user_id, board_id).await,
+        (2, 1)
+    );
+
+    // Downloading, even twice, changes nothing.
+    for _ in 0..2 {
+        let response = server
+            .get("/api/qwk")
+            .add_header(AUTHORIZATION, bearer(&token))
+            .await;
+        response.assert_status_ok();
+        assert_eq!(response.header("content-type"), "application/zip");
+    }
+    assert_eq!(
+        unread_counts(&server, &db, &token, user_id, board_id).await,
+        (2, 1)
+    );
+
+    let response = server
+        .get("/api/qwk")
+        .add_header(AUTHORIZATION, bearer(&token))
+        .await;
+    let snapshot: Value =
+        serde_json::from_str(response.header("x-qwk-pointers").to_str().unwrap()).unwrap();
+    assert_eq!(
+        snapshot["pointers"],
+        json!([{"board_id": board_id, "post_id": posts[1]}])
+    );
+
+    // A post made after the download stays unread after the commit.
+    PostRepository::new(db.pool())
+        .create_flat_post(&NewFlatPost::new(board_id, author_id, "late", "body"))
+        .await
+        .unwrap();
+    let response = server
+        .post("/api/qwk/commit")
+        .add_header(AUTHORIZATION, bearer(&token))
+        .json(&snapshot)
+        .await;
+    response.assert_status_ok();
+    assert_eq!(
+        unread_counts(&server, &db, &token, user_id, board_id).await,
+        (1, 0)
+    );
+}
+
+#[tokio::test]
+async fn test_commit_ignores_other_users_mail() {
+    let (server, db) = create_test_server().await;
+    let (token, _) = register(&server, "reader").await;
+    let (other_token, other_id) = register(&server, "other").await;
+    let (board_id, _) = create_board_with_posts(&db, other_id, 0).await;
+    let mail = MailRepository::new(db.pool())
+        .create(&NewMail::new(other_id, other_id, "Note", "to self"))
+        .await
+        .unwrap();
+
+    let response = server
+        .post("/api/qwk/commit")
+        .add_header(AUTHORIZATION, bearer(&token))
+        .json(&json!({"pointers": [], "mail_ids": [mail.id]}))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(
+        unread_counts(&server, &db, &other_token, other_id, board_id).await,
+        (0, 1)
+    );
+
+    let response = server
+        .post("/api/qwk/commit")
+        .json(&json!({"pointers": [], "mail_ids": []}))
+        .await;
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
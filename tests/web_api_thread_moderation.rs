@@ -0,0 +1,238 @@
+//! Web API Thread Moderation Tests
+//!
+//! Integration tests for moving, merging, splitting and converting threads.
+
+use axum::http::header::AUTHORIZATION;
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use hobbs::board::{
+    BoardRepository, BoardType, NewBoard, NewThread, NewThreadPost, PostRepository,
+    ThreadRepository,
+};
+use hobbs::config::WebConfig;
+use hobbs::web::handlers::AppState;
+use hobbs::web::middleware::JwtState;
+use hobbs::web::router::create_router;
+use hobbs::Database;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// Create a test configuration.
+fn create_test_config() -> WebConfig {
+    WebConfig {
+        enabled: true,
+        host: "127.0.0.1".to_string(),
+        port: 0,
+        cors_origins: vec![],
+        jwt_secret: "test-secret-key-for-testing-only".to_string(),
+        jwt_access_token_expiry_secs: 900,
+        jwt_refresh_token_expiry_days: 7,
+        serve_static: false,
+        static_path: "web/dist".to_string(),
+        login_rate_limit: 100,
+        api_rate_limit: 1000,
+    }
+}
+
+/// Create a test server with an in-memory database.
+async fn create_test_server() -> (TestServer, Arc<Database>) {
+    let config = create_test_config();
+
+    let db = Database::open_in_memory()
+        .await
+        .expect("Failed to create test database");
+    let shared_db = Arc::new(db);
+
+    let app_state = Arc::new(AppState::new(
+        shared_db.clone(),
+        &config.jwt_secret,
+        config.jwt_access_token_expiry_secs,
+        config.jwt_refresh_token_expiry_days,
+    ));
+    let jwt_state = Arc::new(JwtState::new(&config.jwt_secret));
+    let router = create_router(app_state, jwt_state, None, &config);
+    let server = TestServer::new(router).expect("Failed to create test server");
+
+    (server, shared_db)
+}
+
+/// Register a user and return (access token, user ID).
+async fn register(server: &TestServer, username: &str) -> (String, i64) {
+    let response = server
+        .post("/api/auth/register")
+        .json(&json!({
+            "username": username,
+            "password": "password123",
+            "nickname": username
+        }))
+        .await;
+    let body = response.json::<Value>();
+    (
+        body["data"]["access_token"].as_str().unwrap().to_string(),
+        body["data"]["user"]["id"].as_i64().unwrap(),
+    )
+}
+
+fn bearer(token: &str) -> String {
+    format!("Bearer {}", token)
+}
+
+/// Create a board and return its ID.
+async fn create_board(db: &Database, name: &str, board_type: BoardType) -> i64 {
+    BoardRepository::new(db.pool())
+        .create(&NewBoard::new(name).with_board_type(board_type))
+        .await
+        .unwrap()
+        .id
+}
+
+/// Create a thread with the given number of posts and return
+/// (thread ID, post IDs).
+async fn create_thread(
+    db: &Database,
+    board_id: i64,
+    author_id: i64,
+    title: &str,
+    posts: usize,
+) -> (i64, Vec<i64>) {
+    let thread = ThreadRepository::new(db.pool())
+        .create(&NewThread::new(board_id, title, author_id))
+        .await
+        .unwrap();
+    let mut ids = Vec::new();
+    for i in 0..posts {
+        let post = PostRepository::new(db.pool())
+            .create_thread_post(&NewThreadPost::new(
+                board_id,
+                thread.id,
+                author_id,
+                format!("post {i}"),
+            ))
+            .await
+            .unwrap();
+        ThreadRepository::new(db.pool())
+            .touch_and_increment(thread.id)
+            .await
+            .unwrap();
+        ids.push(post.id);
+    }
+    (thread.id, ids)
+}
+
+#[tokio::test]
+async fn test_move_merge_and_split_threads() {
+    let (server, db) = create_test_server().await;
+    let (token, user_id) = register(&server, "sysop").await;
+    let general = create_board(&db, "general", BoardType::Thread).await;
+    let other = create_board(&db, "other", BoardType::Thread).await;
+    let (first, _) = create_thread(&db, general, user_id, "First", 2).await;
+    let (second, second_posts) = create_thread(&db, general, user_id, "Second", 2).await;
+
+    let response = server
+        .post(&format!("/api/admin/threads/{}/move", first))
+        .add_header(AUTHORIZATION, bearer(&token))
+        .json(&json!({ "board_id": other }))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<Value>()["data"]["board_id"], other);
+
+    let response = server
+        .post(&format!("/api/admin/threads/{}/merge", second))
+        .add_header(AUTHORIZATION, bearer(&token))
+        .json(&json!({ "target_thread_id": first }))
+        .await;
+    response.assert_status_ok();
+    let thread = response.json::<Value>()["data"].clone();
+    assert_eq!(thread["id"], first);
+    assert_eq!(thread["post_count"], 4);
+
+    let response = server
+        .get(&format!("/api/threads/{}", second))
+        .add_header(AUTHORIZATION, bearer(&token))
+        .await;
+    response.assert_status(StatusCode::NOT_FOUND);
+
+    let response = server
+        .post(&format!("/api/admin/threads/{}/split", first))
+        .add_header(AUTHORIZATION, bearer(&token))
+        .json(&json!({ "post_id": second_posts[0], "title": "Split off" }))
+        .await;
+    response.assert_status_ok();
+    let thread = response.json::<Value>()["data"].clone();
+    assert_eq!(thread["title"], "Split off");
+    assert_eq!(thread["board_id"], other);
+    assert_eq!(thread["post_count"], 2);
+
+    // The post is no longer in the original thread
+    let response = server
+        .post(&format!("/api/admin/threads/{}/split", first))
+        .add_header(AUTHORIZATION, bearer(&token))
+        .json(&json!({ "post_id": second_posts[0], "title": "Again" }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_convert_thread_and_post() {
+    let (server, db) = create_test_server().await;
+    let (token, user_id) = register(&server, "sysop").await;
+    let threads = create_board(&db, "threads", BoardType::Thread).await;
+    let flat = create_board(&db, "flat", BoardType::Flat).await;
+    let (thread_id, post_ids) = create_thread(&db, threads, user_id, "Topic", 2).await;
+
+    let response = server
+        .post(&format!("/api/admin/threads/{}/convert", thread_id))
+        .add_header(AUTHORIZATION, bearer(&token))
+        .json(&json!({ "board_id": threads }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+
+    let response = server
+        .post(&format!("/api/admin/threads/{}/convert", thread_id))
+        .add_header(AUTHORIZATION, bearer(&token))
+        .json(&json!({ "board_id": flat }))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<Value>()["data"]["posts"], 2);
+
+    let response = server
+        .get(&format!("/api/boards/{}/posts", flat))
+        .add_header(AUTHORIZATION, bearer(&token))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<Value>()["meta"]["total"], 2);
+
+    let response = server
+        .post(&format!("/api/admin/posts/{}/convert", post_ids[1]))
+        .add_header(AUTHORIZATION, bearer(&token))
+        .json(&json!({ "board_id": threads, "title": "Follow-up" }))
+        .await;
+    response.assert_status_ok();
+    let thread = response.json::<Value>()["data"].clone();
+    assert_eq!(thread["title"], "Follow-up");
+    assert_eq!(thread["board_id"], threads);
+    assert_eq!(thread["post_count"], 1);
+}
+
+#[tokio::test]
+async fn test_thread_moderation_requires_admin() {
+    let (server, db) = create_test_server().await;
+    let (_, sysop_id) = register(&server, "sysop").await;
+    let (token, _) = register(&server, "member").await;
+    let general = create_board(&db, "general", BoardType::Thread).await;
+    let other = create_board(&db, "other", BoardType::Thread).await;
+    let (thread_id, _) = create_thread(&db, general, sysop_id, "Topic", 1).await;
+
+    let response = server
+        .post(&format!("/api/admin/threads/{}/move", thread_id))
+        .add_header(AUTHORIZATION, bearer(&token))
+        .json(&json!({ "board_id": other }))
+        .await;
+    response.assert_status(StatusCode::FORBIDDEN);
+
+    let response = server
+        .post("/api/admin/threads/999/move")
+        .json(&json!({ "board_id": other }))
+        .await;
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
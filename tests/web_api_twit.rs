@@ -0,0 +1,260 @@
+//! Web API Twit Filter Tests
+//!
+//! Integration tests for twit filter endpoints and the show_hidden toggle.
+
+use axum::http::header::AUTHORIZATION;
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use hobbs::board::{
+    BoardRepository, NewBoard, NewThread, NewThreadPost, PostRepository, ThreadRepository,
+};
+use hobbs::config::WebConfig;
+use hobbs::web::handlers::AppState;
+use hobbs::web::middleware::JwtState;
+use hobbs::web::router::create_router;
+use hobbs::Database;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// Create a test configuration.
+fn create_test_config() -> WebConfig {
+    WebConfig {
+        enabled: true,
+        host: "127.0.0.1".to_string(),
+        port: 0,
+        cors_origins: vec![],
+        jwt_secret: "test-secret-key-for-testing-only".to_string(),
+        jwt_access_token_expiry_secs: 900,
+        jwt_refresh_token_expiry_days: 7,
+        serve_static: false,
+        static_path: "web/dist".to_string(),
+        login_rate_limit: 100,
+        api_rate_limit: 1000,
+    }
+}
+
+/// Create a test server with an in-memory database.
+async fn create_test_server() -> (TestServer, Arc<Database>) {
+    let config = create_test_config();
+
+    let db = Database::open_in_memory()
+        .await
+        .expect("Failed to create test database");
+    let shared_db = Arc::new(db);
+
+    let app_state = Arc::new(AppState::new(
+        shared_db.clone(),
+        &config.jwt_secret,
+        config.jwt_access_token_expiry_secs,
+        config.jwt_refresh_token_expiry_days,
+    ));
+    let jwt_state = Arc::new(JwtState::new(&config.jwt_secret));
+    let router = create_router(app_state, jwt_state, None, &config);
+    let server = TestServer::new(router).expect("Failed to create test server");
+
+    (server, shared_db)
+}
+
+/// Register a user and return (access token, user ID).
+async fn register(server: &TestServer, username: &str) -> (String, i64) {
+    let response = server
+        .post("/api/auth/register")
+        .json(&json!({
+            "username": username,
+            "password": "password123",
+            "nickname": username
+        }))
+        .await;
+    let body = response.json::<Value>();
+    (
+        body["data"]["access_token"].as_str().unwrap().to_string(),
+        body["data"]["user"]["id"].as_i64().unwrap(),
+    )
+}
+
+fn bearer(token: &str) -> String {
+    format!("Bearer {}", token)
+}
+
+/// Create a board and return its ID.
+async fn create_board(db: &Database) -> i64 {
+    BoardRepository::new(db.pool())
+        .create(&NewBoard::new("general"))
+        .await
+        .unwrap()
+        .id
+}
+
+/// Create a thread with one post and return the thread ID.
+async fn create_thread(db: &Database, board_id: i64, author_id: i64, title: &str) -> i64 {
+    let thread = ThreadRepository::new(db.pool())
+        .create(&NewThread::new(board_id, title, author_id))
+        .await
+        .unwrap();
+    PostRepository::new(db.pool())
+        .create_thread_post(&NewThreadPost::new(board_id, thread.id, author_id, "body"))
+        .await
+        .unwrap();
+    thread.id
+}
+
+/// Return the titles of a board's threads and the reported total.
+async fn thread_titles(server: &TestServer, token: &str, query: &str) -> (Vec<String>, i64) {
+    let response = server
+        .get(query)
+        .add_header(AUTHORIZATION, bearer(token))
+        .await;
+    response.assert_status_ok();
+    let body = response.json::<Value>();
+    let titles = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["title"].as_str().unwrap().to_string())
+        .collect();
+    (titles, body["meta"]["total"].as_i64().unwrap())
+}
+
+#[tokio::test]
+async fn test_twit_filter_lifecycle() {
+    let (server, _db) = create_test_server().await;
+    let (token, _) = register(&server, "alice").await;
+    let (_, bob_id) = register(&server, "bob").await;
+
+    let response = server
+        .post("/api/twit-filters")
+        .add_header(AUTHORIZATION, bearer(&token))
+        .json(&json!({ "keyword": "Spam" }))
+        .await;
+    response.assert_status_ok();
+    let keyword_id = response.json::<Value>()["data"]["id"].as_i64().unwrap();
+
+    let response = server
+        .post("/api/twit-filters")
+        .add_header(AUTHORIZATION, bearer(&token))
+        .json(&json!({ "username": "bob" }))
+        .await;
+    response.assert_status_ok();
+    let filter = response.json::<Value>()["data"].clone();
+    assert_eq!(filter["kind"], "user");
+    assert_eq!(filter["user_id"], bob_id);
+    assert_eq!(filter["value"], "bob");
+
+    // Users are listed before keywords
+    let response = server
+        .get("/api/twit-filters")
+        .add_header(AUTHORIZATION, bearer(&token))
+        .await;
+    response.assert_status_ok();
+    let filters = response.json::<Value>()["data"].as_array().unwrap().clone();
+    assert_eq!(filters.len(), 2);
+    assert_eq!(filters[0]["kind"], "user");
+    assert_eq!(filters[1]["kind"], "keyword");
+    assert_eq!(filters[1]["value"], "Spam");
+
+    let response = server
+        .delete(&format!("/api/twit-filters/{}", keyword_id))
+        .add_header(AUTHORIZATION, bearer(&token))
+        .await;
+    response.assert_status_ok();
+
+    let response = server
+        .delete(&format!("/api/twit-filters/{}", keyword_id))
+        .add_header(AUTHORIZATION, bearer(&token))
+        .await;
+    response.assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_create_twit_filter_validation() {
+    let (server, _db) = create_test_server().await;
+    let (token, _) = register(&server, "alice").await;
+
+    for body in [
+        json!({}),
+        json!({ "username": "alice" }),
+        json!({ "username": "alice", "keyword": "spam" }),
+        json!({ "keyword": "   " }),
+    ] {
+        let response = server
+            .post("/api/twit-filters")
+            .add_header(AUTHORIZATION, bearer(&token))
+            .json(&body)
+            .await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    let response = server
+        .post("/api/twit-filters")
+        .add_header(AUTHORIZATION, bearer(&token))
+        .json(&json!({ "username": "nobody" }))
+        .await;
+    response.assert_status(StatusCode::NOT_FOUND);
+
+    let response = server.get("/api/twit-filters").await;
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_twit_filter_hides_threads_and_mail() {
+    let (server, db) = create_test_server().await;
+    let (token, alice_id) = register(&server, "alice").await;
+    let (bob_token, bob_id) = register(&server, "bob").await;
+    let board_id = create_board(&db).await;
+    create_thread(&db, board_id, alice_id, "Welcome").await;
+    create_thread(&db, board_id, bob_id, "Trolling").await;
+    create_thread(&db, board_id, alice_id, "Cheap SPAM here").await;
+
+    let response = server
+        .post("/api/mail")
+        .add_header(AUTHORIZATION, bearer(&bob_token))
+        .json(&json!({ "recipient": "alice", "subject": "hi", "body": "hello" }))
+        .await;
+    response.assert_status_ok();
+    let response = server
+        .get("/api/mail/inbox")
+        .add_header(AUTHORIZATION, bearer(&token))
+        .await;
+    let inbox_before = response.json::<Value>()["meta"]["total"].as_i64().unwrap();
+
+    for body in [json!({ "username": "bob" }), json!({ "keyword": "spam" })] {
+        server
+            .post("/api/twit-filters")
+            .add_header(AUTHORIZATION, bearer(&token))
+            .json(&body)
+            .await
+            .assert_status_ok();
+    }
+
+    let threads = format!("/api/boards/{}/threads", board_id);
+    let (titles, total) = thread_titles(&server, &token, &threads).await;
+    assert_eq!(titles, vec!["Welcome"]);
+    assert_eq!(total, 1);
+
+    let (titles, total) =
+        thread_titles(&server, &token, &format!("{}?show_hidden=true", threads)).await;
+    assert_eq!(titles.len(), 3);
+    assert_eq!(total, 3);
+
+    // Other users are not affected
+    let (titles, _) = thread_titles(&server, &bob_token, &threads).await;
+    assert_eq!(titles.len(), 3);
+
+    let response = server
+        .get("/api/mail/inbox")
+        .add_header(AUTHORIZATION, bearer(&token))
+        .await;
+    assert_eq!(
+        response.json::<Value>()["meta"]["total"].as_i64().unwrap(),
+        inbox_before - 1
+    );
+
+    let response = server
+        .get("/api/mail/inbox?show_hidden=true")
+        .add_header(AUTHORIZATION, bearer(&token))
+        .await;
+    assert_eq!(
+        response.json::<Value>()["meta"]["total"].as_i64().unwrap(),
+        inbox_before
+    );
+}
@@ -1,5 +1,18 @@
 import { api, buildQueryString, type PaginationParams } from './client';
-import type { AdminUser, AdminBoard, AdminFolder, PaginatedResponse } from '../types';
+import type {
+  AdminUser,
+  AdminBoard,
+  AdminBoardGroup,
+  AdminFilterHit,
+  AdminFilterRule,
+  AdminFolder,
+  FilterAction,
+  FilterRuleType,
+  PaginatedResponse,
+  PendingPost,
+  Post,
+  Thread,
+} from '../types';
 
 // User management
 export async function getUsers(
@@ -48,6 +61,12 @@ export interface CreateBoardRequest {
   min_write_role: string;
   sort_order?: number;
   disable_paging?: boolean;
+  allow_color?: boolean;
+  is_moderated?: boolean;
+  group_id?: number;
+  max_posts?: number;
+  max_age_days?: number;
+  archive_pruned?: boolean;
 }
 
 export async function createBoard(data: CreateBoardRequest): Promise<AdminBoard> {
@@ -63,6 +82,12 @@ export interface UpdateBoardRequest {
   sort_order?: number;
   is_active?: boolean;
   disable_paging?: boolean;
+  allow_color?: boolean;
+  is_moderated?: boolean;
+  group_id?: number | null;
+  max_posts?: number | null;
+  max_age_days?: number | null;
+  archive_pruned?: boolean;
 }
 
 export async function updateBoard(id: number, data: UpdateBoardRequest): Promise<AdminBoard> {
@@ -73,6 +98,110 @@ export async function deleteBoard(id: number): Promise<void> {
   await api.delete(`/admin/boards/${id}`);
 }
 
+// Board group management
+export async function getAdminBoardGroups(): Promise<AdminBoardGroup[]> {
+  return api.get<AdminBoardGroup[]>('/admin/board-groups');
+}
+
+export interface CreateBoardGroupRequest {
+  name: string;
+  description?: string;
+  min_read_role?: string;
+  sort_order?: number;
+}
+
+export async function createBoardGroup(data: CreateBoardGroupRequest): Promise<AdminBoardGroup> {
+  return api.post<AdminBoardGroup>('/admin/board-groups', data);
+}
+
+export interface UpdateBoardGroupRequest {
+  name?: string;
+  description?: string | null;
+  min_read_role?: string;
+  sort_order?: number;
+}
+
+export async function updateBoardGroup(
+  id: number,
+  data: UpdateBoardGroupRequest
+): Promise<AdminBoardGroup> {
+  return api.put<AdminBoardGroup>(`/admin/board-groups/${id}`, data);
+}
+
+export async function deleteBoardGroup(id: number): Promise<void> {
+  await api.delete(`/admin/board-groups/${id}`);
+}
+
+// Moderation queue
+export async function getPendingPosts(): Promise<PendingPost[]> {
+  return api.get<PendingPost[]>('/admin/moderation');
+}
+
+export async function approvePost(id: number): Promise<Post> {
+  return api.post<Post>(`/admin/moderation/${id}/approve`);
+}
+
+export async function rejectPost(id: number, reason: string): Promise<void> {
+  await api.post(`/admin/moderation/${id}/reject`, { reason });
+}
+
+// Thread moderation
+export async function moveThread(id: number, boardId: number): Promise<Thread> {
+  return api.post<Thread>(`/admin/threads/${id}/move`, { board_id: boardId });
+}
+
+export async function mergeThread(id: number, targetThreadId: number): Promise<Thread> {
+  return api.post<Thread>(`/admin/threads/${id}/merge`, { target_thread_id: targetThreadId });
+}
+
+export async function splitThread(id: number, postId: number, title: string): Promise<Thread> {
+  return api.post<Thread>(`/admin/threads/${id}/split`, { post_id: postId, title });
+}
+
+export interface ConvertThreadResult {
+  board_id: number;
+  posts: number;
+}
+
+export async function convertThreadToFlat(id: number, boardId: number): Promise<ConvertThreadResult> {
+  return api.post<ConvertThreadResult>(`/admin/threads/${id}/convert`, { board_id: boardId });
+}
+
+export async function convertPostToThread(
+  id: number,
+  boardId: number,
+  title?: string
+): Promise<Thread> {
+  return api.post<Thread>(`/admin/posts/${id}/convert`, { board_id: boardId, title });
+}
+
+// Content filter
+export async function getFilterRules(): Promise<AdminFilterRule[]> {
+  return api.get<AdminFilterRule[]>('/admin/filter-rules');
+}
+
+export interface CreateFilterRuleRequest {
+  rule_type: FilterRuleType;
+  pattern: string;
+  action: FilterAction;
+}
+
+export async function createFilterRule(data: CreateFilterRuleRequest): Promise<AdminFilterRule> {
+  return api.post<AdminFilterRule>('/admin/filter-rules', data);
+}
+
+export async function setFilterRuleActive(id: number, isActive: boolean): Promise<AdminFilterRule> {
+  return api.put<AdminFilterRule>(`/admin/filter-rules/${id}`, { is_active: isActive });
+}
+
+export async function deleteFilterRule(id: number): Promise<void> {
+  await api.delete(`/admin/filter-rules/${id}`);
+}
+
+export async function getFilterHits(): Promise<AdminFilterHit[]> {
+  return api.get<AdminFilterHit[]>('/admin/filter-hits');
+}
+
 // Folder management
 export async function getAdminFolders(): Promise<AdminFolder[]> {
   return api.get<AdminFolder[]>('/admin/folders');
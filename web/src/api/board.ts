@@ -1,6 +1,7 @@
 import { api, buildQueryString, type PaginationParams } from './client';
 import type {
   Board,
+  BoardTree,
   Thread,
   Post,
   PaginatedResponse,
@@ -13,15 +14,21 @@ export async function getBoards(): Promise<Board[]> {
   return api.get<Board[]>('/boards');
 }
 
+export async function getBoardTree(showHidden?: boolean): Promise<BoardTree> {
+  const query = buildQueryString({ show_hidden: showHidden });
+  return api.get<BoardTree>(`/boards/tree${query}`);
+}
+
 export async function getBoard(id: number): Promise<Board> {
   return api.get<Board>(`/boards/${id}`);
 }
 
 export async function getThreads(
   boardId: number,
-  params?: PaginationParams
+  params?: PaginationParams,
+  showHidden?: boolean
 ): Promise<PaginatedResponse<Thread>> {
-  const query = buildQueryString(params || {});
+  const query = buildQueryString({ ...params, show_hidden: showHidden });
   return api.get<PaginatedResponse<Thread>>(`/boards/${boardId}/threads${query}`);
 }
 
@@ -38,9 +45,10 @@ export async function getThread(threadId: number): Promise<Thread> {
 
 export async function getPosts(
   threadId: number,
-  params?: PaginationParams
+  params?: PaginationParams,
+  showHidden?: boolean
 ): Promise<PaginatedResponse<Post>> {
-  const query = buildQueryString(params || {});
+  const query = buildQueryString({ ...params, show_hidden: showHidden });
   return api.get<PaginatedResponse<Post>>(`/threads/${threadId}/posts${query}`);
 }
 
@@ -54,9 +62,10 @@ export async function createPost(
 // Flat board posts (for flat-type boards)
 export async function getFlatPosts(
   boardId: number,
-  params?: PaginationParams
+  params?: PaginationParams,
+  showHidden?: boolean
 ): Promise<PaginatedResponse<Post>> {
-  const query = buildQueryString(params || {});
+  const query = buildQueryString({ ...params, show_hidden: showHidden });
   return api.get<PaginatedResponse<Post>>(`/boards/${boardId}/posts${query}`);
 }
 
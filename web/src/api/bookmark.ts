@@ -0,0 +1,24 @@
+import { api } from './client';
+import type { Bookmark } from '../types';
+
+export async function getBookmarks(): Promise<Bookmark[]> {
+  return api.get<Bookmark[]>('/bookmarks');
+}
+
+export interface CreateBookmarkRequest {
+  thread_id?: number;
+  post_id?: number;
+  note?: string;
+}
+
+export async function addBookmark(data: CreateBookmarkRequest): Promise<Bookmark> {
+  return api.post<Bookmark>('/bookmarks', data);
+}
+
+export async function updateBookmarkNote(id: number, note: string | null): Promise<Bookmark> {
+  return api.put<Bookmark>(`/bookmarks/${id}`, { note });
+}
+
+export async function removeBookmark(id: number): Promise<void> {
+  await api.delete(`/bookmarks/${id}`);
+}
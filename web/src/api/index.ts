@@ -1,9 +1,14 @@
 export * from './client';
 export * as auth from './auth';
 export * as board from './board';
+export * as bookmark from './bookmark';
 export * as mail from './mail';
+export * as newscan from './newscan';
+export * as notification from './notification';
+export * as poll from './poll';
 export * as chat from './chat';
 export * as file from './file';
 export * as admin from './admin';
 export * as rss from './rss';
+export * as twit from './twit';
 export * as user from './user';
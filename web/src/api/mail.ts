@@ -7,9 +7,10 @@ import type {
 } from '../types';
 
 export async function getInbox(
-  params?: PaginationParams
+  params?: PaginationParams,
+  showHidden?: boolean
 ): Promise<PaginatedResponse<MailListItem>> {
-  const query = buildQueryString(params || {});
+  const query = buildQueryString({ ...params, show_hidden: showHidden });
   return api.get<PaginatedResponse<MailListItem>>(`/mail/inbox${query}`);
 }
 
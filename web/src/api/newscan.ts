@@ -0,0 +1,20 @@
+import { api } from './client';
+import type { NewscanSettings } from '../types';
+
+export async function getNewscan(): Promise<NewscanSettings> {
+  return api.get<NewscanSettings>('/newscan');
+}
+
+export interface UpdateNewscanRequest {
+  boards?: { board_id: number; included: boolean }[];
+  scan_on_login?: boolean;
+}
+
+export async function updateNewscan(data: UpdateNewscanRequest): Promise<NewscanSettings> {
+  return api.put<NewscanSettings>('/newscan', data);
+}
+
+// Posts before `since` (RFC3339 or YYYY-MM-DD) become read, later ones unread.
+export async function resetNewscan(since: string): Promise<{ boards: number }> {
+  return api.post<{ boards: number }>('/newscan/reset', { since });
+}
@@ -0,0 +1,57 @@
+import { api } from './client';
+import { getOneTimeToken } from './auth';
+import type { NotificationMessage, Subscription, SubscriptionNotification } from '../types';
+
+export async function getSubscriptions(): Promise<Subscription[]> {
+  return api.get<Subscription[]>('/subscriptions');
+}
+
+export interface CreateSubscriptionRequest {
+  board_id?: number;
+  thread_id?: number;
+  digest?: boolean;
+}
+
+export async function subscribe(data: CreateSubscriptionRequest): Promise<Subscription> {
+  return api.post<Subscription>('/subscriptions', data);
+}
+
+export async function updateSubscription(id: number, digest: boolean): Promise<Subscription> {
+  return api.put<Subscription>(`/subscriptions/${id}`, { digest });
+}
+
+export async function unsubscribe(id: number): Promise<void> {
+  await api.delete(`/subscriptions/${id}`);
+}
+
+export async function getNotifications(): Promise<SubscriptionNotification[]> {
+  return api.get<SubscriptionNotification[]>('/notifications');
+}
+
+export async function markNotificationsRead(): Promise<void> {
+  await api.post('/notifications/read');
+}
+
+// Open a WebSocket that receives notifications as they are queued.
+// Pushed notifications are marked as read on the server.
+export async function connectNotifications(
+  onNotification: (notification: SubscriptionNotification) => void
+): Promise<WebSocket> {
+  const tokenResponse = await getOneTimeToken('websocket');
+  const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:';
+  const host = window.location.host;
+  const url = `${protocol}//${host}/api/notifications/ws?token=${encodeURIComponent(tokenResponse.token)}`;
+
+  const ws = new WebSocket(url);
+  ws.onmessage = (event) => {
+    try {
+      const message = JSON.parse(event.data) as NotificationMessage;
+      if (message.type === 'notification') {
+        onNotification(message.notification);
+      }
+    } catch (e) {
+      console.error('Failed to parse notification message:', e);
+    }
+  };
+  return ws;
+}
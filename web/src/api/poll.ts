@@ -0,0 +1,44 @@
+import { api } from './client';
+import type { Poll } from '../types';
+
+export async function getPolls(threadId?: number): Promise<Poll[]> {
+  const query = threadId !== undefined ? `?thread_id=${threadId}` : '';
+  return api.get<Poll[]>(`/polls${query}`);
+}
+
+export async function getPoll(id: number): Promise<Poll> {
+  return api.get<Poll>(`/polls/${id}`);
+}
+
+export interface CreatePollRequest {
+  question: string;
+  description?: string;
+  options: string[];
+  allow_multiple?: boolean;
+  allow_write_in?: boolean;
+  min_vote_role?: string;
+  opens_at?: string;
+  closes_at?: string;
+  thread_id?: number;
+}
+
+export async function createPoll(data: CreatePollRequest): Promise<Poll> {
+  return api.post<Poll>('/polls', data);
+}
+
+export interface VoteRequest {
+  option_ids?: number[];
+  write_in?: string;
+}
+
+export async function vote(id: number, data: VoteRequest): Promise<Poll> {
+  return api.post<Poll>(`/polls/${id}/vote`, data);
+}
+
+export async function closePoll(id: number): Promise<Poll> {
+  return api.post<Poll>(`/polls/${id}/close`);
+}
+
+export async function deletePoll(id: number): Promise<void> {
+  await api.delete(`/polls/${id}`);
+}
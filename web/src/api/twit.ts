@@ -0,0 +1,19 @@
+import { api } from './client';
+import type { TwitFilter } from '../types';
+
+export async function getTwitFilters(): Promise<TwitFilter[]> {
+  return api.get<TwitFilter[]>('/twit-filters');
+}
+
+export interface CreateTwitFilterRequest {
+  username?: string;
+  keyword?: string;
+}
+
+export async function addTwitFilter(data: CreateTwitFilterRequest): Promise<TwitFilter> {
+  return api.post<TwitFilter>('/twit-filters', data);
+}
+
+export async function removeTwitFilter(id: number): Promise<void> {
+  await api.delete(`/twit-filters/${id}`);
+}
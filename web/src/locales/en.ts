@@ -262,6 +262,9 @@ export const dict = {
     paging: 'Paging',
     pagingOn: 'ON',
     pagingOff: 'OFF',
+    color: 'Color Codes',
+    colorOn: 'Shown',
+    colorOff: 'Stripped',
     confirmDeleteBoard: 'Delete this board?',
     confirmDeleteFolder: 'Delete this folder?',
     updateFailed: 'Update failed',
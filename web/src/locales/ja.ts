@@ -262,6 +262,9 @@ export const dict = {
     paging: 'ページング',
     pagingOn: 'ON',
     pagingOff: 'OFF',
+    color: 'カラーコード',
+    colorOn: '表示',
+    colorOff: '除去',
     confirmDeleteBoard: 'この掲示板を削除しますか？',
     confirmDeleteFolder: 'このフォルダを削除しますか？',
     updateFailed: '更新に失敗しました',
@@ -197,7 +197,7 @@ const BoardsTab: Component = () => {
                     <div>
                       <h3 class="font-medium text-gray-200">{board.name}</h3>
                       <p class="text-sm text-gray-500 mt-1">
-                        {board.board_type === 'thread' ? t('admin.threadType') : t('admin.flatType')} | {t('admin.readPermission')}: {t(`roles.${board.min_read_role}` as any)} | {t('admin.writePermission')}: {t(`roles.${board.min_write_role}` as any)} | {t('admin.paging')}: {board.disable_paging ? t('admin.pagingOff') : t('admin.pagingOn')}
+                        {board.board_type === 'thread' ? t('admin.threadType') : t('admin.flatType')} | {t('admin.readPermission')}: {t(`roles.${board.min_read_role}` as any)} | {t('admin.writePermission')}: {t(`roles.${board.min_write_role}` as any)} | {t('admin.paging')}: {board.disable_paging ? t('admin.pagingOff') : t('admin.pagingOn')} | {t('admin.color')}: {board.allow_color ? t('admin.colorOn') : t('admin.colorOff')}
                       </p>
                     </div>
                     <div class="flex space-x-2">
@@ -442,6 +442,7 @@ const BoardForm: Component<BoardFormProps> = (props) => {
   const [minReadRole, setMinReadRole] = createSignal(props.board?.min_read_role || 'guest');
   const [minWriteRole, setMinWriteRole] = createSignal(props.board?.min_write_role || 'member');
   const [disablePaging, setDisablePaging] = createSignal(props.board?.disable_paging || false);
+  const [allowColor, setAllowColor] = createSignal(props.board?.allow_color ?? true);
   const [error, setError] = createSignal('');
   const [loading, setLoading] = createSignal(false);
 
@@ -458,6 +459,7 @@ const BoardForm: Component<BoardFormProps> = (props) => {
           min_read_role: minReadRole(),
           min_write_role: minWriteRole(),
           disable_paging: disablePaging(),
+          allow_color: allowColor(),
         });
       } else {
         await adminApi.createBoard({
@@ -467,6 +469,7 @@ const BoardForm: Component<BoardFormProps> = (props) => {
           min_read_role: minReadRole(),
           min_write_role: minWriteRole(),
           disable_paging: disablePaging(),
+          allow_color: allowColor(),
         });
       }
       props.onSuccess();
@@ -542,6 +545,18 @@ const BoardForm: Component<BoardFormProps> = (props) => {
         </span>
       </label>
 
+      <label class="flex items-center space-x-2">
+        <input
+          type="checkbox"
+          checked={allowColor()}
+          onChange={(e) => setAllowColor(e.currentTarget.checked)}
+          class="form-checkbox"
+        />
+        <span class="text-sm text-gray-400">
+          {t('admin.color')}: {allowColor() ? t('admin.colorOn') : t('admin.colorOff')}
+        </span>
+      </label>
+
       <div class="flex justify-end space-x-3">
         <Button type="button" variant="secondary" onClick={props.onCancel}>
           {t('common.cancel')}
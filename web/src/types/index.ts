@@ -82,9 +82,24 @@ export interface Board {
   post_count?: number;
   can_read: boolean;
   can_write: boolean;
+  group_id?: number;
+  unread_count?: number;
   created_at: string;
 }
 
+export interface BoardGroup {
+  id: number;
+  name: string;
+  description?: string;
+  unread_count?: number;
+  boards: Board[];
+}
+
+export interface BoardTree {
+  groups: BoardGroup[];
+  boards: Board[];
+}
+
 export interface Thread {
   id: number;
   board_id: number;
@@ -152,6 +167,97 @@ export interface SendMailRequest {
   body: string;
 }
 
+// Subscription types
+export interface Subscription {
+  id: number;
+  board_id?: number;
+  thread_id?: number;
+  name: string;
+  digest: boolean;
+  created_at: string;
+}
+
+export interface SubscriptionNotification {
+  id: number;
+  post_id: number;
+  board_id: number;
+  board_name: string;
+  thread_id?: number;
+  title: string;
+  author: string;
+  created_at: string;
+}
+
+export interface NotificationMessage {
+  type: 'notification';
+  notification: SubscriptionNotification;
+}
+
+// Bookmark types
+export interface Bookmark {
+  id: number;
+  thread_id?: number;
+  post_id?: number;
+  post_thread_id?: number;
+  board_id?: number;
+  board_name?: string;
+  title: string;
+  note?: string;
+  deleted: boolean;
+  created_at: string;
+}
+
+// Twit filter types
+export interface TwitFilter {
+  id: number;
+  kind: 'user' | 'keyword';
+  user_id?: number;
+  value: string;
+  created_at: string;
+}
+
+// Newscan types
+export interface NewscanBoard {
+  board_id: number;
+  name: string;
+  included: boolean;
+}
+
+export interface NewscanSettings {
+  boards: NewscanBoard[];
+  scan_on_login: boolean;
+}
+
+// Poll types
+export type PollStatus = 'upcoming' | 'open' | 'closed';
+
+export interface PollOption {
+  id: number;
+  text: string;
+  is_write_in: boolean;
+  votes: number;
+}
+
+export interface Poll {
+  id: number;
+  question: string;
+  description?: string;
+  allow_multiple: boolean;
+  allow_write_in: boolean;
+  min_vote_role: string;
+  opens_at?: string;
+  closes_at?: string;
+  status: PollStatus;
+  thread_id?: number;
+  creator?: string;
+  options: PollOption[];
+  voters: number;
+  my_votes: number[];
+  can_vote: boolean;
+  can_manage: boolean;
+  created_at: string;
+}
+
 // Chat types
 export interface ChatRoom {
   id: string;
@@ -251,6 +357,34 @@ export interface AdminBoard {
   sort_order: number;
   is_active: boolean;
   disable_paging: boolean;
+  allow_color: boolean;
+  is_moderated: boolean;
+  group_id?: number;
+  max_posts?: number;
+  max_age_days?: number;
+  archive_pruned: boolean;
+  created_at: string;
+}
+
+export interface AdminBoardGroup {
+  id: number;
+  name: string;
+  description?: string;
+  min_read_role: string;
+  sort_order: number;
+  created_at: string;
+}
+
+export interface PendingPost {
+  id: number;
+  board_id: number;
+  board_name: string;
+  thread_id?: number;
+  thread_title?: string;
+  parent_id?: number;
+  author: AuthorInfo;
+  title?: string;
+  body: string;
   created_at: string;
 }
 
@@ -265,3 +399,26 @@ export interface AdminFolder {
   file_count: number;
   created_at: string;
 }
+
+export type FilterRuleType = 'word' | 'regex' | 'links' | 'new_account';
+
+export type FilterAction = 'reject' | 'hold' | 'mask';
+
+export interface AdminFilterRule {
+  id: number;
+  rule_type: FilterRuleType;
+  pattern: string;
+  action: FilterAction;
+  is_active: boolean;
+  created_at: string;
+}
+
+export interface AdminFilterHit {
+  id: number;
+  rule_id?: number;
+  username?: string;
+  target: 'post' | 'mail' | 'chat';
+  action: FilterAction;
+  detail: string;
+  created_at: string;
+}